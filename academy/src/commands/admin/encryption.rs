@@ -0,0 +1,46 @@
+use academy_config::Config;
+use academy_core_mfa_contracts::totp_secret::MfaTotpSecretService;
+use academy_di::Provide;
+use academy_persistence_contracts::{Database, Transaction};
+use anyhow::Context;
+use clap::Subcommand;
+use tracing::info;
+
+use crate::{
+    database,
+    environment::{types, ConfigProvider},
+};
+
+#[derive(Debug, Subcommand)]
+pub enum AdminEncryptionCommand {
+    /// Re-encrypt all stored secrets using the current encryption key
+    #[command(aliases(["r"]))]
+    Rotate,
+}
+
+impl AdminEncryptionCommand {
+    pub async fn invoke(self, config: Config) -> anyhow::Result<()> {
+        match self {
+            AdminEncryptionCommand::Rotate => rotate(config).await,
+        }
+    }
+}
+
+async fn rotate(config: Config) -> anyhow::Result<()> {
+    let db = database::connect(&config.database).await?;
+    let mut provider = ConfigProvider::new(&config)?;
+    let mfa_totp_secret: types::MfaTotpSecret = provider.provide();
+
+    let mut txn = db.begin_transaction().await?;
+
+    let count = mfa_totp_secret
+        .reencrypt(&mut txn)
+        .await
+        .context("Failed to re-encrypt totp secrets")?;
+
+    txn.commit().await?;
+
+    info!("Re-encrypted {count} totp secrets");
+
+    Ok(())
+}
@@ -1,7 +1,13 @@
 use academy_config::Config;
 use clap::Subcommand;
+use encryption::AdminEncryptionCommand;
+use password::AdminPasswordCommand;
+use role::AdminRoleCommand;
 use user::AdminUserCommand;
 
+mod encryption;
+mod password;
+mod role;
 mod user;
 
 #[derive(Debug, Subcommand)]
@@ -12,12 +18,33 @@ pub enum AdminCommand {
         #[command(subcommand)]
         command: AdminUserCommand,
     },
+    /// Manage roles and permissions
+    #[command(aliases(["r"]))]
+    Role {
+        #[command(subcommand)]
+        command: AdminRoleCommand,
+    },
+    /// Manage encryption keys
+    #[command(aliases(["e"]))]
+    Encryption {
+        #[command(subcommand)]
+        command: AdminEncryptionCommand,
+    },
+    /// Inspect stored password hashes
+    #[command(aliases(["p"]))]
+    Password {
+        #[command(subcommand)]
+        command: AdminPasswordCommand,
+    },
 }
 
 impl AdminCommand {
     pub async fn invoke(self, config: Config) -> anyhow::Result<()> {
         match self {
             AdminCommand::User { command } => command.invoke(config).await,
+            AdminCommand::Role { command } => command.invoke(config).await,
+            AdminCommand::Encryption { command } => command.invoke(config).await,
+            AdminCommand::Password { command } => command.invoke(config).await,
         }
     }
 }
@@ -0,0 +1,62 @@
+use academy_config::Config;
+use academy_di::Provide;
+use academy_persistence_contracts::{user::UserRepository, Database as _};
+use academy_shared_contracts::password::PasswordService;
+use anyhow::Context;
+use clap::Subcommand;
+use tracing::info;
+
+use crate::{
+    cache, database, email,
+    environment::{
+        types::{self, Database},
+        ConfigProvider, Provider,
+    },
+};
+
+#[derive(Debug, Subcommand)]
+pub enum AdminPasswordCommand {
+    /// Report how many password hashes use outdated algorithms or parameters
+    #[command(aliases(["r"]))]
+    Report,
+}
+
+impl AdminPasswordCommand {
+    pub async fn invoke(self, config: Config) -> anyhow::Result<()> {
+        match self {
+            AdminPasswordCommand::Report => report(config).await,
+        }
+    }
+}
+
+async fn report(config: Config) -> anyhow::Result<()> {
+    let database = database::connect(&config.database).await?;
+    let cache = cache::connect(&config.cache).await?;
+    let email_service = email::connect(&config.email).await?;
+    let config_provider = ConfigProvider::new(&config)?;
+    let mut provider = Provider::new(config_provider, database, cache, email_service);
+
+    let db: Database = provider.provide();
+    let mut txn = db.begin_transaction().await?;
+
+    let user_repo: types::UserRepo = provider.provide();
+    let password: types::Password = provider.provide();
+
+    let hashes = user_repo
+        .list_password_hashes(&mut txn)
+        .await
+        .context("Failed to get password hashes from database")?;
+
+    let total = hashes.len();
+    let outdated = hashes
+        .iter()
+        .filter(|hash| password.needs_rehash(hash))
+        .count();
+
+    info!(
+        "{outdated} of {total} password hashes use an outdated algorithm or outdated parameters \
+         and will be replaced on the next successful login"
+    );
+
+    Ok(())
+}
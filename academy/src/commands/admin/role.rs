@@ -0,0 +1,222 @@
+use academy_auth_contracts::AuthService;
+use academy_config::Config;
+use academy_di::Provide;
+use academy_models::role::{Permission, Role};
+use academy_persistence_contracts::{
+    role::RoleRepository, user::UserRepository, Database as _, Transaction,
+};
+use academy_shared_contracts::{id::IdService, time::TimeService};
+use anyhow::{anyhow, Context};
+use clap::Subcommand;
+use tracing::info;
+
+use crate::{
+    cache, database, email,
+    environment::{
+        types::{self, Database},
+        ConfigProvider, Provider,
+    },
+};
+
+#[derive(Debug, Subcommand)]
+pub enum AdminRoleCommand {
+    /// List all roles
+    #[command(aliases(["l", "ls"]))]
+    List,
+    /// Create a new role
+    #[command(aliases(["c", "new", "n", "+"]))]
+    Create {
+        /// Permission to grant to users with this role (can be specified
+        /// multiple times)
+        #[arg(long = "permission", short)]
+        permissions: Vec<Permission>,
+        /// The name of the new role
+        name: String,
+    },
+    /// Delete a role and revoke it from all users
+    #[command(aliases(["d", "rm", "-"]))]
+    Delete {
+        /// The name of the role
+        name: String,
+    },
+    /// Grant a role to a user
+    #[command(aliases(["g", "add"]))]
+    Grant {
+        /// The name of the user
+        user: String,
+        /// The name of the role
+        role: String,
+    },
+    /// Revoke a role from a user
+    #[command(aliases(["r"]))]
+    Revoke {
+        /// The name of the user
+        user: String,
+        /// The name of the role
+        role: String,
+    },
+}
+
+impl AdminRoleCommand {
+    pub async fn invoke(self, config: Config) -> anyhow::Result<()> {
+        let database = database::connect(&config.database).await?;
+        let cache = cache::connect(&config.cache).await?;
+        let email_service = email::connect(&config.email).await?;
+        let config_provider = ConfigProvider::new(&config)?;
+        let mut provider = Provider::new(config_provider, database, cache, email_service);
+
+        match self {
+            AdminRoleCommand::List => list(&mut provider).await,
+            AdminRoleCommand::Create { name, permissions } => {
+                create(&mut provider, name, permissions).await
+            }
+            AdminRoleCommand::Delete { name } => delete(&mut provider, name).await,
+            AdminRoleCommand::Grant { user, role } => grant(&mut provider, user, role, true).await,
+            AdminRoleCommand::Revoke { user, role } => {
+                grant(&mut provider, user, role, false).await
+            }
+        }
+    }
+}
+
+async fn list(provider: &mut Provider) -> anyhow::Result<()> {
+    let db: Database = provider.provide();
+    let mut txn = db.begin_transaction().await?;
+
+    let role_repo: types::RoleRepo = provider.provide();
+    let roles = role_repo
+        .list(&mut txn)
+        .await
+        .context("Failed to get roles from database")?;
+
+    for role in roles {
+        let permissions = role
+            .permissions
+            .iter()
+            .map(|x| x.as_str())
+            .collect::<Vec<_>>();
+        println!("{} {} [{}]", *role.id, *role.name, permissions.join(", "));
+    }
+
+    Ok(())
+}
+
+async fn create(
+    provider: &mut Provider,
+    name: String,
+    permissions: Vec<Permission>,
+) -> anyhow::Result<()> {
+    let db: Database = provider.provide();
+    let mut txn = db.begin_transaction().await?;
+
+    let id: types::Id = provider.provide();
+    let time: types::Time = provider.provide();
+    let role_repo: types::RoleRepo = provider.provide();
+
+    let role = Role {
+        id: id.generate(),
+        name: name.try_into()?,
+        permissions: permissions.into_iter().collect(),
+        created_at: time.now(),
+    };
+
+    role_repo
+        .create(&mut txn, &role)
+        .await
+        .context("Failed to create role")?;
+
+    txn.commit().await?;
+
+    info!("Role has been created:\n{role:#?}");
+
+    Ok(())
+}
+
+async fn delete(provider: &mut Provider, name: String) -> anyhow::Result<()> {
+    let db: Database = provider.provide();
+    let mut txn = db.begin_transaction().await?;
+
+    let auth: types::Auth = provider.provide();
+    let role_repo: types::RoleRepo = provider.provide();
+
+    let role = role_repo
+        .get_by_name(&mut txn, &name.try_into()?)
+        .await
+        .context("Failed to get role from database")?
+        .ok_or_else(|| anyhow!("Role does not exist"))?;
+
+    for user_id in role_repo
+        .list_users(&mut txn, role.id)
+        .await
+        .context("Failed to get role users from database")?
+    {
+        auth.invalidate_access_tokens(&mut txn, user_id)
+            .await
+            .context("Failed to invalidate access tokens")?;
+    }
+
+    role_repo
+        .delete(&mut txn, role.id)
+        .await
+        .context("Failed to delete role")?;
+
+    txn.commit().await?;
+
+    info!("Role {:?} has been deleted", *role.name);
+
+    Ok(())
+}
+
+async fn grant(
+    provider: &mut Provider,
+    user: String,
+    role: String,
+    grant: bool,
+) -> anyhow::Result<()> {
+    let db: Database = provider.provide();
+    let mut txn = db.begin_transaction().await?;
+
+    let auth: types::Auth = provider.provide();
+    let user_repo: types::UserRepo = provider.provide();
+    let role_repo: types::RoleRepo = provider.provide();
+
+    let user = user_repo
+        .get_composite_by_name(&mut txn, &user.try_into()?)
+        .await
+        .context("Failed to get user from database")?
+        .ok_or_else(|| anyhow!("User does not exist"))?
+        .user;
+
+    let role = role_repo
+        .get_by_name(&mut txn, &role.try_into()?)
+        .await
+        .context("Failed to get role from database")?
+        .ok_or_else(|| anyhow!("Role does not exist"))?;
+
+    let changed = if grant {
+        role_repo.add_to_user(&mut txn, user.id, role.id).await
+    } else {
+        role_repo.remove_from_user(&mut txn, user.id, role.id).await
+    }
+    .context("Failed to update user roles in database")?;
+
+    if changed {
+        auth.invalidate_access_tokens(&mut txn, user.id)
+            .await
+            .context("Failed to invalidate access tokens")?;
+    }
+
+    txn.commit().await?;
+
+    match (grant, changed) {
+        (true, true) => info!("Role {:?} has been granted to {:?}", *role.name, *user.name),
+        (true, false) => info!("User {:?} already has role {:?}", *user.name, *role.name),
+        (false, true) => info!(
+            "Role {:?} has been revoked from {:?}",
+            *role.name, *user.name
+        ),
+        (false, false) => info!("User {:?} does not have role {:?}", *user.name, *role.name),
+    }
+
+    Ok(())
+}
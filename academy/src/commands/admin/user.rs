@@ -1,8 +1,11 @@
 use academy_config::Config;
-use academy_core_user_contracts::user::{UserCreateCommand, UserService};
+use academy_core_user_contracts::{
+    export::UserExportService,
+    user::{UserCreateCommand, UserService},
+};
 use academy_di::Provide;
-use academy_persistence_contracts::{Database as _, Transaction};
-use anyhow::Context;
+use academy_persistence_contracts::{user::UserRepository, Database as _, Transaction};
+use anyhow::{anyhow, Context};
 use clap::Subcommand;
 use tracing::info;
 
@@ -35,6 +38,12 @@ pub enum AdminUserCommand {
         /// The password of the new user
         password: String,
     },
+    /// Export all data stored about a user as JSON
+    #[command(aliases(["e"]))]
+    Export {
+        /// The name of the user
+        user: String,
+    },
 }
 
 impl AdminUserCommand {
@@ -48,6 +57,7 @@ impl AdminUserCommand {
                 disabled,
                 verified,
             } => create(config, name, email, password, admin, !disabled, verified).await,
+            AdminUserCommand::Export { user } => export(config, user).await,
         }
     }
 }
@@ -94,3 +104,35 @@ async fn create(
 
     Ok(())
 }
+
+async fn export(config: Config, user: String) -> anyhow::Result<()> {
+    let database = database::connect(&config.database).await?;
+    let cache = cache::connect(&config.cache).await?;
+    let email_service = email::connect(&config.email).await?;
+    let config_provider = ConfigProvider::new(&config)?;
+    let mut provider = Provider::new(config_provider, database, cache, email_service);
+
+    let db: Database = provider.provide();
+    let mut txn = db.begin_transaction().await?;
+
+    let user_repo: types::UserRepo = provider.provide();
+    let user_export: types::UserExport = provider.provide();
+
+    let user_id = user_repo
+        .get_composite_by_name(&mut txn, &user.try_into()?)
+        .await
+        .context("Failed to get user from database")?
+        .ok_or_else(|| anyhow!("User does not exist"))?
+        .user
+        .id;
+
+    let export = user_export
+        .generate(user_id)
+        .await
+        .context("Failed to generate user export")?
+        .ok_or_else(|| anyhow!("User does not exist"))?;
+
+    println!("{}", serde_json::to_string_pretty(&export)?);
+
+    Ok(())
+}
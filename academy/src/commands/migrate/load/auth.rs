@@ -1,7 +1,8 @@
 use academy_models::{
+    encryption::EncryptedData,
     mfa::{MfaRecoveryCodeHash, TotpDevice, TotpSecret},
     oauth2::{OAuth2Link, OAuth2UserInfo},
-    session::{Session, SessionRefreshTokenHash},
+    session::{Session, SessionLoginMethod, SessionRefreshTokenHash},
     user::{User, UserInvoiceInfo, UserProfile},
     Sha256Hash,
 };
@@ -76,6 +77,7 @@ pub async fn load(db: PostgresDatabase, auth: DbConnection) -> anyhow::Result<()
             enabled,
             admin,
             newsletter: newsletter.unwrap_or(false),
+            deleted_at: None,
         };
 
         let profile = UserProfile {
@@ -85,6 +87,7 @@ pub async fn load(db: PostgresDatabase, auth: DbConnection) -> anyhow::Result<()
                 .transpose()?
                 .unwrap_or_default(),
             tags: serde_json::from_str(tags.as_deref().unwrap_or("[]"))?,
+            avatar_url: None,
         };
 
         let invoice_info = UserInvoiceInfo {
@@ -112,6 +115,7 @@ pub async fn load(db: PostgresDatabase, auth: DbConnection) -> anyhow::Result<()
             let totp_device = TotpDevice {
                 id: Uuid::new_v4().into(),
                 user_id: user.id,
+                name: Default::default(),
                 enabled: mfa_enabled.unwrap_or(false),
                 created_at: user.created_at,
             };
@@ -119,6 +123,7 @@ pub async fn load(db: PostgresDatabase, auth: DbConnection) -> anyhow::Result<()
                 base32::decode(base32::Alphabet::Rfc4648 { padding: false }, &mfa_secret)
                     .ok_or_else(|| anyhow::anyhow!("Failed to decode totp secret"))?,
             )?;
+            let secret = EncryptedData::unencrypted(secret.into_inner());
 
             mfa_repo
                 .create_totp_device(&mut txn, &totp_device, &secret)
@@ -134,7 +139,7 @@ pub async fn load(db: PostgresDatabase, auth: DbConnection) -> anyhow::Result<()
 
             let hash = MfaRecoveryCodeHash::new(hash);
             mfa_repo
-                .save_mfa_recovery_code_hash(&mut txn, user.id, hash)
+                .save_mfa_recovery_code_hashes(&mut txn, user.id, &[hash])
                 .await?;
         }
     }
@@ -156,6 +161,12 @@ pub async fn load(db: PostgresDatabase, auth: DbConnection) -> anyhow::Result<()
             id: id.parse::<Uuid>()?.into(),
             user_id: user_id.parse::<Uuid>()?.into(),
             device_name: Some(device_name.try_into()?),
+            ip: None,
+            device_fingerprint: None,
+            last_ip: None,
+            browser: None,
+            os: None,
+            login_method: SessionLoginMethod::Password,
             created_at: last_update.and_utc(),
             updated_at: last_update.and_utc(),
         };
@@ -198,6 +209,8 @@ pub async fn load(db: PostgresDatabase, auth: DbConnection) -> anyhow::Result<()
             remote_user: OAuth2UserInfo {
                 id: remote_user_id.try_into()?,
                 name: display_name.try_into()?,
+                email: None,
+                email_verified: false,
             },
         };
 
@@ -208,6 +221,8 @@ pub async fn load(db: PostgresDatabase, auth: DbConnection) -> anyhow::Result<()
     txn.commit().await?;
 
     info!("done");
+    info!("run `academy admin encryption rotate` to encrypt the imported totp secrets");
+    info!("run `academy admin password report` to check the imported password hashes");
 
     Ok(())
 }
@@ -1,7 +1,9 @@
 use academy_config::Config;
 use academy_persistence_contracts::{Database, Transaction};
 use academy_persistence_postgres::{
-    mfa::PostgresMfaRepository, oauth2::PostgresOAuth2Repository,
+    audit::PostgresAuditEventRepository, mfa::PostgresMfaRepository,
+    oauth2::PostgresOAuth2Repository, oauth2_server::PostgresOAuth2ServerRepository,
+    personal_access_token::PostgresPersonalAccessTokenRepository, role::PostgresRoleRepository,
     session::PostgresSessionRepository, user::PostgresUserRepository, MigrationStatus,
     PostgresDatabase,
 };
@@ -112,6 +114,10 @@ async fn demo(db: PostgresDatabase) -> anyhow::Result<()> {
         PostgresSessionRepository,
         PostgresMfaRepository,
         PostgresOAuth2Repository,
+        PostgresOAuth2ServerRepository,
+        PostgresPersonalAccessTokenRepository,
+        PostgresRoleRepository,
+        PostgresAuditEventRepository,
     )
     .await
     .context("Failed to restore demo dataset")?;
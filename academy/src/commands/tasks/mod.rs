@@ -1,23 +1,40 @@
 use academy_config::Config;
-use academy_persistence_contracts::{session::SessionRepository, Database, Transaction};
-use academy_persistence_postgres::session::PostgresSessionRepository;
+use academy_core_user_contracts::{avatar::UserAvatarService, export::UserExportService};
+use academy_di::Provide;
+use academy_persistence_contracts::{
+    personal_access_token::PersonalAccessTokenRepository, session::SessionRepository,
+    user::UserRepository, Database, Transaction,
+};
+use academy_persistence_postgres::{
+    personal_access_token::PostgresPersonalAccessTokenRepository,
+    session::PostgresSessionRepository, user::PostgresUserRepository,
+};
 use anyhow::Context;
 use chrono::Utc;
 use clap::Subcommand;
-use tracing::info;
+use tracing::{info, warn};
 
-use crate::database;
+use crate::{
+    cache, database, email,
+    environment::{types, ConfigProvider, Provider},
+};
 
 #[derive(Debug, Subcommand)]
 pub enum TaskCommand {
     /// Remove expired records from the database.
     PruneDatabase,
+    /// Permanently delete users whose deletion grace period has expired.
+    PurgeDeletedUsers,
+    /// Generate requested user data exports and send download links.
+    ProcessUserExports,
 }
 
 impl TaskCommand {
     pub async fn invoke(self, config: Config) -> anyhow::Result<()> {
         match self {
             TaskCommand::PruneDatabase => prune_database(config).await,
+            TaskCommand::PurgeDeletedUsers => purge_deleted_users(config).await,
+            TaskCommand::ProcessUserExports => process_user_exports(config).await,
         }
     }
 }
@@ -34,7 +51,60 @@ async fn prune_database(config: Config) -> anyhow::Result<()> {
         .context("Failed to prune sessions")?;
     info!("Pruned {pruned} expired sessions.");
 
+    let pruned = session_repo
+        .delete_superseded_refresh_token_hashes(&mut txn, now - config.session.refresh_token_ttl.0)
+        .await
+        .context("Failed to prune superseded refresh tokens")?;
+    info!("Pruned {pruned} superseded refresh tokens.");
+
+    let personal_access_token_repo = PostgresPersonalAccessTokenRepository;
+    let pruned = personal_access_token_repo
+        .delete_expired(&mut txn, now)
+        .await
+        .context("Failed to prune personal access tokens")?;
+    info!("Pruned {pruned} expired personal access tokens.");
+
     txn.commit().await?;
 
     Ok(())
 }
+
+async fn purge_deleted_users(config: Config) -> anyhow::Result<()> {
+    let db = database::connect(&config.database).await?;
+    let mut provider = ConfigProvider::new(&config)?;
+    let user_avatar: types::UserAvatar = provider.provide();
+
+    let mut txn = db.begin_transaction().await?;
+
+    let now = Utc::now();
+    let purged = PostgresUserRepository
+        .delete_by_deleted_at(&mut txn, now - config.user.deletion_grace_period.0, now)
+        .await
+        .context("Failed to purge deleted users")?;
+    info!("Purged {} deleted users.", purged.len());
+
+    txn.commit().await?;
+
+    for user_id in purged {
+        if let Err(err) = user_avatar.remove(user_id).await {
+            warn!("Failed to remove avatar of purged user {user_id:?}: {err:#}");
+        }
+    }
+
+    Ok(())
+}
+
+async fn process_user_exports(config: Config) -> anyhow::Result<()> {
+    let database = database::connect(&config.database).await?;
+    let cache = cache::connect(&config.cache).await?;
+    let email_service = email::connect(&config.email).await?;
+    let config_provider = ConfigProvider::new(&config)?;
+    let mut provider = Provider::new(config_provider, database, cache, email_service);
+
+    let user_export: types::UserExport = provider.provide();
+
+    user_export
+        .process_requests()
+        .await
+        .context("Failed to process user export requests")
+}
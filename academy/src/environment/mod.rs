@@ -1,24 +1,39 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use academy_api_rest::{RestServerConfig, RestServerRealIpConfig};
 use academy_auth_impl::AuthServiceConfig;
-use academy_config::Config;
+use academy_config::{Config, StorageConfig};
 use academy_core_contact_impl::ContactFeatureConfig;
 use academy_core_health_impl::HealthFeatureConfig;
+use academy_core_mfa_impl::MfaFeatureConfig;
 use academy_core_oauth2_impl::OAuth2FeatureConfig;
+use academy_core_oauth2_server_impl::OAuth2ServerFeatureConfig;
+use academy_core_rate_limit_impl::{RateLimitFeatureConfig, RateLimitPolicy};
 use academy_core_session_impl::SessionFeatureConfig;
-use academy_core_user_impl::UserFeatureConfig;
+use academy_core_user_impl::{UserFeatureConfig, UserPasswordPolicyConfig};
 use academy_di::provider;
 use academy_extern_impl::{
-    internal::InternalApiServiceConfig, recaptcha::RecaptchaApiServiceConfig,
+    internal::InternalApiServiceConfig,
+    pwned_passwords::{PwnedPasswordsApiServiceConfig, PwnedPasswordsSource},
+    recaptcha::RecaptchaApiServiceConfig,
     vat::VatApiServiceConfig,
 };
-use academy_models::oauth2::OAuth2Provider;
+use academy_models::oauth2::{OAuth2Provider, OAuth2ProviderKind};
 use academy_shared_impl::{
     captcha::{CaptchaServiceConfig, RecaptchaCaptchaServiceConfig},
+    encryption::EncryptionServiceConfig,
     jwt::JwtServiceConfig,
+    password::PasswordServiceConfig,
     totp::TotpServiceConfig,
+    webauthn::WebauthnServiceConfig,
+};
+use academy_storage_impl::{
+    LocalStorageServiceConfig, S3StorageServiceConfig, StorageServiceConfig,
 };
+use anyhow::anyhow;
 use types::{Cache, Database, Email};
 
 pub mod types;
@@ -37,12 +52,19 @@ provider! {
             InternalApiServiceConfig,
             RecaptchaApiServiceConfig,
             VatApiServiceConfig,
+            PwnedPasswordsApiServiceConfig,
+
+            // Storage
+            StorageServiceConfig,
 
             // Shared
             CaptchaServiceConfig,
+            EncryptionServiceConfig,
             JwtServiceConfig,
             OAuth2FeatureConfig,
+            PasswordServiceConfig,
             TotpServiceConfig,
+            WebauthnServiceConfig,
 
             // Auth
             AuthServiceConfig,
@@ -50,6 +72,9 @@ provider! {
             // Core
             ContactFeatureConfig,
             HealthFeatureConfig,
+            MfaFeatureConfig,
+            OAuth2ServerFeatureConfig,
+            RateLimitFeatureConfig,
             SessionFeatureConfig,
             UserFeatureConfig,
         }
@@ -78,12 +103,19 @@ provider! {
         internal_api_service_config: InternalApiServiceConfig,
         recaptcha_api_service_config: RecaptchaApiServiceConfig,
         vat_api_service_config: VatApiServiceConfig,
+        pwned_passwords_api_service_config: PwnedPasswordsApiServiceConfig,
+
+        // Storage
+        storage_service_config: StorageServiceConfig,
 
         // Shared
         captcha_service_config: CaptchaServiceConfig,
+        encryption_service_config: EncryptionServiceConfig,
         jwt_service_config: JwtServiceConfig,
         oauth2_service_config: OAuth2FeatureConfig,
+        password_service_config: PasswordServiceConfig,
         totp_service_config: TotpServiceConfig,
+        webauthn_service_config: WebauthnServiceConfig,
 
         // Auth
         auth_service_config: AuthServiceConfig,
@@ -91,6 +123,9 @@ provider! {
         // Core
         contact_feature_config: ContactFeatureConfig,
         health_feature_config: HealthFeatureConfig,
+        mfa_feature_config: MfaFeatureConfig,
+        oauth2_server_feature_config: OAuth2ServerFeatureConfig,
+        rate_limit_feature_config: RateLimitFeatureConfig,
         session_feature_config: SessionFeatureConfig,
         user_feature_config: UserFeatureConfig,
     }
@@ -125,6 +160,36 @@ impl ConfigProvider {
         let vat_api_service_config =
             VatApiServiceConfig::new(config.vat.validate_endpoint_override.clone());
 
+        let pwned_passwords_api_service_config = PwnedPasswordsApiServiceConfig::new(
+            config
+                .pwned_passwords
+                .dataset_path
+                .clone()
+                .map(|path| PwnedPasswordsSource::Dataset(path.into()))
+                .or_else(|| {
+                    config
+                        .pwned_passwords
+                        .range_endpoint
+                        .clone()
+                        .map(|url| PwnedPasswordsSource::RangeEndpoint(url.into()))
+                }),
+        );
+
+        // Storage
+        let storage_service_config = match &config.storage {
+            StorageConfig::Local(local) => StorageServiceConfig::Local(
+                LocalStorageServiceConfig::new(local.path.clone(), local.public_url.clone()),
+            ),
+            StorageConfig::S3(s3) => StorageServiceConfig::S3(S3StorageServiceConfig::new(
+                s3.endpoint.clone(),
+                s3.bucket.clone(),
+                s3.region.clone(),
+                s3.access_key_id.clone(),
+                s3.secret_access_key.clone(),
+                s3.public_url.clone(),
+            )),
+        };
+
         // Shared
         let captcha_service_config = match config.recaptcha.as_ref() {
             Some(recaptcha) => CaptchaServiceConfig::Recaptcha(RecaptchaCaptchaServiceConfig {
@@ -135,7 +200,29 @@ impl ConfigProvider {
             None => CaptchaServiceConfig::Disabled,
         };
 
-        let jwt_service_config = JwtServiceConfig::new(&config.jwt.secret)?;
+        let encryption_service_config = EncryptionServiceConfig::new(
+            config.encryption.key_version,
+            config
+                .encryption
+                .keys
+                .iter()
+                .map(|(&version, key)| (version, key.as_str())),
+        )?;
+
+        let password_service_config = PasswordServiceConfig::new(
+            config.argon2.memory_cost,
+            config.argon2.iterations,
+            config.argon2.parallelism,
+        )?;
+
+        let jwt_service_config = JwtServiceConfig::new(
+            &config.jwt.signing_key,
+            config
+                .jwt
+                .keys
+                .iter()
+                .map(|(kid, key)| (kid.as_str(), key.algorithm, key.key.as_str())),
+        )?;
 
         let oauth2_service_config = OAuth2FeatureConfig {
             registration_token_ttl: config
@@ -143,27 +230,59 @@ impl ConfigProvider {
                 .as_ref()
                 .map(|oauth2| oauth2.registration_token_ttl.0)
                 .unwrap_or_default(),
+            state_ttl: config
+                .oauth2
+                .as_ref()
+                .map(|oauth2| oauth2.state_ttl.0)
+                .unwrap_or_default(),
             providers: config
                 .oauth2
                 .iter()
                 .flat_map(|oauth2| oauth2.providers.iter())
                 .map(|(id, provider)| {
-                    (
+                    let kind = match &provider.issuer_url {
+                        Some(issuer_url) => OAuth2ProviderKind::Oidc {
+                            issuer_url: issuer_url.clone(),
+                        },
+                        None => {
+                            let missing =
+                                |field| anyhow!("Missing {field} for OAuth2 provider {id:?}");
+                            OAuth2ProviderKind::OAuth2 {
+                                auth_url: provider
+                                    .auth_url
+                                    .clone()
+                                    .ok_or_else(|| missing("auth_url"))?,
+                                token_url: provider
+                                    .token_url
+                                    .clone()
+                                    .ok_or_else(|| missing("token_url"))?,
+                                userinfo_url: provider
+                                    .userinfo_url
+                                    .clone()
+                                    .ok_or_else(|| missing("userinfo_url"))?,
+                                userinfo_id_key: provider
+                                    .userinfo_id_key
+                                    .clone()
+                                    .ok_or_else(|| missing("userinfo_id_key"))?,
+                                userinfo_name_key: provider
+                                    .userinfo_name_key
+                                    .clone()
+                                    .ok_or_else(|| missing("userinfo_name_key"))?,
+                            }
+                        }
+                    };
+                    Ok((
                         id.clone().into(),
                         OAuth2Provider {
                             name: provider.name.clone().into(),
                             client_id: provider.client_id.clone(),
                             client_secret: Some(provider.client_secret.clone().into()),
-                            auth_url: provider.auth_url.clone(),
-                            token_url: provider.token_url.clone(),
-                            userinfo_url: provider.userinfo_url.clone(),
-                            userinfo_id_key: provider.userinfo_id_key.clone(),
-                            userinfo_name_key: provider.userinfo_name_key.clone(),
+                            kind,
                             scopes: provider.scopes.clone(),
                         },
-                    )
+                    ))
                 })
-                .collect::<HashMap<_, _>>()
+                .collect::<anyhow::Result<HashMap<_, _>>>()?
                 .into(),
         };
 
@@ -171,6 +290,12 @@ impl ConfigProvider {
             secret_length: config.totp.secret_length,
         };
 
+        let webauthn_service_config = WebauthnServiceConfig {
+            rp_id: config.webauthn.rp_id.clone().into(),
+            rp_name: config.webauthn.rp_name.clone().into(),
+            origins: config.webauthn.origins.clone().into(),
+        };
+
         // Auth
         let auth_service_config = AuthServiceConfig {
             access_token_ttl: config.session.access_token_ttl.into(),
@@ -190,12 +315,64 @@ impl ConfigProvider {
             email_cache_ttl: config.health.email_cache_ttl.into(),
         };
 
+        let mfa_feature_config = MfaFeatureConfig {
+            recovery_code_count: config.mfa.recovery_code_count,
+            webauthn_challenge_ttl: config.webauthn.challenge_ttl.into(),
+        };
+
+        let oauth2_server_feature_config = OAuth2ServerFeatureConfig {
+            issuer: config.oauth2_server.issuer.clone(),
+            access_token_ttl: config.oauth2_server.access_token_ttl.into(),
+            refresh_token_ttl: config.oauth2_server.refresh_token_ttl.into(),
+            code_ttl: config.oauth2_server.code_ttl.into(),
+        };
+
+        let rate_limit_feature_config = RateLimitFeatureConfig {
+            policies: config
+                .rate_limit
+                .iter()
+                .map(|(&group, policy)| {
+                    (
+                        group,
+                        RateLimitPolicy {
+                            requests: policy.requests,
+                            window: policy.window.into(),
+                            key: policy.key,
+                        },
+                    )
+                })
+                .collect::<HashMap<_, _>>()
+                .into(),
+        };
+
         let session_feature_config = SessionFeatureConfig {
             login_fails_before_captcha: config.session.login_fails_before_captcha,
+            failed_login_attempts_ttl: config.session.failed_login_attempts_ttl.into(),
+            login_fails_before_lockout: config.session.login_fails_before_lockout,
+            ip_login_fails_before_lockout: config.session.ip_login_fails_before_lockout,
+            lockout_duration: config.session.lockout_duration.into(),
+            max_lockout_duration: config.session.max_lockout_duration.into(),
+            new_device_revoke_redirect_url: config
+                .session
+                .new_device_revoke_redirect_url
+                .clone()
+                .into(),
+            new_device_revoke_code_ttl: config.session.new_device_revoke_code_ttl.into(),
+            login_code_redirect_url: config.session.login_code_redirect_url.clone().into(),
+            login_code_ttl: config.session.login_code_ttl.into(),
         };
 
         let user_feature_config = UserFeatureConfig {
             name_change_rate_limit: config.user.name_change_rate_limit.into(),
+            name_quarantine: config.user.name_quarantine.into(),
+            reserved_names: config
+                .user
+                .reserved_names
+                .iter()
+                .map(|name| name.to_lowercase())
+                .collect::<HashSet<_>>()
+                .into(),
+            blocked_names: config.user.blocked_names.clone(),
             verification_redirect_url: config.user.verification_redirect_url.clone().into(),
             verification_verification_code_ttl: config.user.verification_code_ttl.into(),
             password_reset_redirect_url: config.user.password_reset_redirect_url.clone().into(),
@@ -206,6 +383,28 @@ impl ConfigProvider {
                 .clone()
                 .into(),
             newsletter_subscription_verification_code_ttl: config.user.newsletter_code_ttl.into(),
+            email_change_redirect_url: config.user.email_change_redirect_url.clone().into(),
+            email_change_cancel_redirect_url: config
+                .user
+                .email_change_cancel_redirect_url
+                .clone()
+                .into(),
+            email_change_verification_code_ttl: config.user.email_change_code_ttl.into(),
+            additional_email_redirect_url: config.user.additional_email_redirect_url.clone().into(),
+            additional_email_verification_code_ttl: config.user.additional_email_code_ttl.into(),
+            password_policy: config.password_policy.as_ref().map(|password_policy| {
+                UserPasswordPolicyConfig {
+                    min_length: password_policy.min_length,
+                    min_character_classes: password_policy.min_character_classes,
+                    check_breached: password_policy.check_breached,
+                }
+            }),
+            export_redirect_url: config.user.export_redirect_url.clone().into(),
+            export_code_ttl: config.user.export_code_ttl.into(),
+            deletion_grace_period: config.user.deletion_grace_period.into(),
+            restore_redirect_url: config.user.restore_redirect_url.clone().into(),
+            avatar_max_size: config.user.avatar_max_size,
+            avatar_max_dimension: config.user.avatar_max_dimension,
         };
 
         Ok(Self {
@@ -218,10 +417,17 @@ impl ConfigProvider {
             internal_api_service_config,
             recaptcha_api_service_config,
             vat_api_service_config,
+            pwned_passwords_api_service_config,
+
+            // Storage
+            storage_service_config,
 
             // Shared
+            encryption_service_config,
             jwt_service_config,
+            password_service_config,
             totp_service_config,
+            webauthn_service_config,
             captcha_service_config,
             oauth2_service_config,
 
@@ -231,6 +437,9 @@ impl ConfigProvider {
             // Core
             contact_feature_config,
             health_feature_config,
+            mfa_feature_config,
+            oauth2_server_feature_config,
+            rate_limit_feature_config,
             session_feature_config,
             user_feature_config,
         })
@@ -2,43 +2,62 @@
 
 use academy_auth_impl::{
     access_token::AuthAccessTokenServiceImpl, internal::AuthInternalServiceImpl,
+    personal_access_token::AuthPersonalAccessTokenServiceImpl,
     refresh_token::AuthRefreshTokenServiceImpl, AuthServiceImpl,
 };
 use academy_cache_valkey::ValkeyCache;
+use academy_core_audit_impl::{audit::AuditServiceImpl, AuditFeatureServiceImpl};
 use academy_core_config_impl::ConfigFeatureServiceImpl;
 use academy_core_contact_impl::ContactFeatureServiceImpl;
 use academy_core_health_impl::HealthFeatureServiceImpl;
 use academy_core_internal_impl::InternalServiceImpl;
 use academy_core_mfa_impl::{
     authenticate::MfaAuthenticateServiceImpl, disable::MfaDisableServiceImpl,
-    recovery::MfaRecoveryServiceImpl, totp_device::MfaTotpDeviceServiceImpl, MfaFeatureServiceImpl,
+    recovery::MfaRecoveryServiceImpl, totp_device::MfaTotpDeviceServiceImpl,
+    totp_secret::MfaTotpSecretServiceImpl, webauthn_device::MfaWebauthnDeviceServiceImpl,
+    MfaFeatureServiceImpl,
 };
 use academy_core_oauth2_impl::{
     link::OAuth2LinkServiceImpl, login::OAuth2LoginServiceImpl,
     registration::OAuth2RegistrationServiceImpl, OAuth2FeatureServiceImpl,
 };
+use academy_core_oauth2_server_impl::{
+    client::OAuth2ClientServiceImpl, code::OAuth2AuthorizationCodeServiceImpl,
+    token::OAuth2ServerTokenServiceImpl, OAuth2ServerFeatureServiceImpl,
+};
+use academy_core_personal_access_token_impl::PersonalAccessTokenFeatureServiceImpl;
+use academy_core_rate_limit_impl::RateLimitFeatureServiceImpl;
+use academy_core_role_impl::RoleFeatureServiceImpl;
 use academy_core_session_impl::{
-    failed_auth_count::SessionFailedAuthCountServiceImpl, session::SessionServiceImpl,
+    failed_auth_count::SessionFailedAuthCountServiceImpl, login_code::SessionLoginCodeServiceImpl,
+    new_device::SessionNewDeviceServiceImpl, session::SessionServiceImpl,
     SessionFeatureServiceImpl,
 };
 use academy_core_user_impl::{
-    email_confirmation::UserEmailConfirmationServiceImpl, update::UserUpdateServiceImpl,
-    user::UserServiceImpl, UserFeatureServiceImpl,
+    avatar::UserAvatarServiceImpl, deletion::UserDeletionServiceImpl,
+    email_confirmation::UserEmailConfirmationServiceImpl, export::UserExportServiceImpl,
+    name_policy::UserNamePolicyServiceImpl, password_policy::UserPasswordPolicyServiceImpl,
+    update::UserUpdateServiceImpl, user::UserServiceImpl, UserFeatureServiceImpl,
 };
 use academy_email_impl::{template::TemplateEmailServiceImpl, EmailServiceImpl};
 use academy_extern_impl::{
     internal::InternalApiServiceImpl, oauth2::OAuth2ApiServiceImpl,
-    recaptcha::RecaptchaApiServiceImpl, vat::VatApiServiceImpl,
+    pwned_passwords::PwnedPasswordsApiServiceImpl, recaptcha::RecaptchaApiServiceImpl,
+    vat::VatApiServiceImpl,
 };
 use academy_persistence_postgres::{
-    mfa::PostgresMfaRepository, oauth2::PostgresOAuth2Repository,
+    audit::PostgresAuditEventRepository, mfa::PostgresMfaRepository,
+    oauth2::PostgresOAuth2Repository, oauth2_server::PostgresOAuth2ServerRepository,
+    personal_access_token::PostgresPersonalAccessTokenRepository, role::PostgresRoleRepository,
     session::PostgresSessionRepository, user::PostgresUserRepository, PostgresDatabase,
 };
 use academy_shared_impl::{
-    captcha::CaptchaServiceImpl, hash::HashServiceImpl, id::IdServiceImpl, jwt::JwtServiceImpl,
-    password::PasswordServiceImpl, secret::SecretServiceImpl, time::TimeServiceImpl,
-    totp::TotpServiceImpl,
+    captcha::CaptchaServiceImpl, encryption::EncryptionServiceImpl, hash::HashServiceImpl,
+    id::IdServiceImpl, jwt::JwtServiceImpl, password::PasswordServiceImpl,
+    secret::SecretServiceImpl, time::TimeServiceImpl, totp::TotpServiceImpl,
+    user_agent::UserAgentServiceImpl, webauthn::WebauthnServiceImpl,
 };
+use academy_storage_impl::StorageServiceImpl;
 use academy_templates_impl::TemplateServiceImpl;
 
 // API
@@ -50,6 +69,11 @@ pub type RestServer = academy_api_rest::RestServer<
     ContactFeature,
     MfaFeature,
     OAuth2Feature,
+    OAuth2ServerFeature,
+    PersonalAccessTokenFeature,
+    RoleFeature,
+    AuditFeature,
+    RateLimitFeature,
     Internal,
 >;
 
@@ -63,17 +87,22 @@ pub type Cache = ValkeyCache;
 pub type Email = EmailServiceImpl;
 pub type TemplateEmail = TemplateEmailServiceImpl<Email, Template>;
 
+// Storage
+pub type Storage = StorageServiceImpl<Time>;
+
 // Extern
 pub type RecaptchaApi = RecaptchaApiServiceImpl;
 pub type OAuth2Api = OAuth2ApiServiceImpl;
 pub type InternalApi = InternalApiServiceImpl<AuthInternal>;
 pub type VatApi = VatApiServiceImpl;
+pub type PwnedPasswordsApi = PwnedPasswordsApiServiceImpl;
 
 // Template
 pub type Template = TemplateServiceImpl;
 
 // Shared
 pub type Captcha = CaptchaServiceImpl<RecaptchaApi>;
+pub type Encryption = EncryptionServiceImpl<Secret>;
 pub type Hash = HashServiceImpl;
 pub type Id = IdServiceImpl;
 pub type Jwt = JwtServiceImpl<Time>;
@@ -81,24 +110,41 @@ pub type Password = PasswordServiceImpl;
 pub type Secret = SecretServiceImpl;
 pub type Time = TimeServiceImpl;
 pub type Totp = TotpServiceImpl<Secret, Time, Hash, Cache>;
+pub type UserAgent = UserAgentServiceImpl;
+pub type Webauthn = WebauthnServiceImpl<Secret>;
 
 // Repositories
 pub type SessionRepo = PostgresSessionRepository;
 pub type UserRepo = PostgresUserRepository;
 pub type MfaRepo = PostgresMfaRepository;
 pub type OAuth2Repo = PostgresOAuth2Repository;
+pub type OAuth2ServerRepo = PostgresOAuth2ServerRepository;
+pub type PersonalAccessTokenRepo = PostgresPersonalAccessTokenRepository;
+pub type RoleRepo = PostgresRoleRepository;
+pub type AuditEventRepo = PostgresAuditEventRepository;
 
 // Auth
-pub type Auth =
-    AuthServiceImpl<Time, Password, UserRepo, SessionRepo, AuthAccessToken, AuthRefreshToken>;
+pub type Auth = AuthServiceImpl<
+    Database,
+    Time,
+    Password,
+    UserRepo,
+    SessionRepo,
+    PersonalAccessTokenRepo,
+    RoleRepo,
+    AuthAccessToken,
+    AuthRefreshToken,
+    AuthPersonalAccessToken,
+>;
 pub type AuthAccessToken = AuthAccessTokenServiceImpl<Jwt, Cache>;
 pub type AuthRefreshToken = AuthRefreshTokenServiceImpl<Secret, Hash>;
+pub type AuthPersonalAccessToken = AuthPersonalAccessTokenServiceImpl<Secret, Hash>;
 pub type AuthInternal = AuthInternalServiceImpl<Jwt>;
 
 // Core
 pub type HealthFeature = HealthFeatureServiceImpl<Time, Database, Cache, Email>;
 
-pub type ConfigFeature = ConfigFeatureServiceImpl<Captcha>;
+pub type ConfigFeature = ConfigFeatureServiceImpl<Captcha, Jwt>;
 
 pub type UserFeature = UserFeatureServiceImpl<
     Database,
@@ -109,27 +155,60 @@ pub type UserFeature = UserFeatureServiceImpl<
     User,
     UserEmailConfirmation,
     UserUpdate,
+    UserPasswordPolicy,
+    UserNamePolicy,
+    UserAvatar,
+    UserExport,
+    UserDeletion,
     Session,
+    SessionNewDevice,
     OAuth2Registration,
+    Audit,
     UserRepo,
 >;
 pub type User = UserServiceImpl<Id, Time, Password, UserRepo, OAuth2Link>;
 pub type UserEmailConfirmation =
-    UserEmailConfirmationServiceImpl<Auth, Secret, TemplateEmail, Cache, Password, UserRepo>;
+    UserEmailConfirmationServiceImpl<Auth, Time, Secret, TemplateEmail, Cache, Password, UserRepo>;
 pub type UserUpdate = UserUpdateServiceImpl<Auth, Time, Password, Session, UserRepo>;
+pub type UserPasswordPolicy = UserPasswordPolicyServiceImpl<PwnedPasswordsApi>;
+pub type UserNamePolicy = UserNamePolicyServiceImpl<Time, UserRepo>;
+pub type UserAvatar = UserAvatarServiceImpl<Time, Storage>;
+pub type UserExport = UserExportServiceImpl<
+    Database,
+    Time,
+    Secret,
+    Cache,
+    TemplateEmail,
+    UserRepo,
+    SessionRepo,
+    OAuth2Repo,
+    MfaRepo,
+    AuditEventRepo,
+>;
+pub type UserDeletion = UserDeletionServiceImpl<Time, Secret, Hash, TemplateEmail, UserRepo>;
 
 pub type SessionFeature = SessionFeatureServiceImpl<
     Database,
     Auth,
     Captcha,
+    TemplateEmail,
     Session,
     SessionFailedAuthCount,
+    SessionNewDevice,
+    SessionLoginCode,
     MfaAuthenticate,
+    UserEmailConfirmation,
+    Audit,
     UserRepo,
     SessionRepo,
+    PersonalAccessTokenRepo,
+    OAuth2ServerRepo,
 >;
-pub type Session = SessionServiceImpl<Id, Time, Auth, AuthAccessToken, SessionRepo, UserRepo>;
-pub type SessionFailedAuthCount = SessionFailedAuthCountServiceImpl<Hash, Cache>;
+pub type Session =
+    SessionServiceImpl<Id, Time, Hash, UserAgent, Auth, AuthAccessToken, SessionRepo, UserRepo>;
+pub type SessionFailedAuthCount = SessionFailedAuthCountServiceImpl<Hash, Time, Cache>;
+pub type SessionNewDevice = SessionNewDeviceServiceImpl<Secret, Cache, TemplateEmail, SessionRepo>;
+pub type SessionLoginCode = SessionLoginCodeServiceImpl<Secret, Cache, TemplateEmail>;
 
 pub type ContactFeature = ContactFeatureServiceImpl<Captcha, Email>;
 
@@ -141,25 +220,73 @@ pub type MfaFeature = MfaFeatureServiceImpl<
     MfaRecovery,
     MfaDisable,
     MfaTotpDevice,
+    MfaWebauthnDevice,
+    Audit,
 >;
 pub type MfaRecovery = MfaRecoveryServiceImpl<Secret, Hash, MfaRepo>;
-pub type MfaAuthenticate = MfaAuthenticateServiceImpl<Hash, Totp, MfaDisable, MfaRepo>;
+pub type MfaAuthenticate =
+    MfaAuthenticateServiceImpl<Hash, Totp, Encryption, MfaWebauthnDevice, MfaRepo>;
 pub type MfaDisable = MfaDisableServiceImpl<MfaRepo>;
-pub type MfaTotpDevice = MfaTotpDeviceServiceImpl<Id, Time, Totp, MfaRepo>;
+pub type MfaTotpDevice = MfaTotpDeviceServiceImpl<Id, Time, Totp, Encryption, MfaRepo>;
+pub type MfaTotpSecret = MfaTotpSecretServiceImpl<Encryption, MfaRepo>;
+pub type MfaWebauthnDevice = MfaWebauthnDeviceServiceImpl<Id, Time, Webauthn, Cache, MfaRepo>;
 
 pub type OAuth2Feature = OAuth2FeatureServiceImpl<
     Database,
     Auth,
-    OAuth2Api,
     UserRepo,
     OAuth2Repo,
     OAuth2Link,
     OAuth2Login,
     OAuth2Registration,
     Session,
+    SessionNewDevice,
+    Audit,
 >;
 pub type OAuth2Link = OAuth2LinkServiceImpl<Id, Time, OAuth2Repo>;
-pub type OAuth2Login = OAuth2LoginServiceImpl<OAuth2Api>;
+pub type OAuth2Login = OAuth2LoginServiceImpl<Secret, Hash, Cache, OAuth2Api>;
 pub type OAuth2Registration = OAuth2RegistrationServiceImpl<Secret, Cache>;
 
+pub type OAuth2ServerFeature = OAuth2ServerFeatureServiceImpl<
+    Database,
+    Auth,
+    Id,
+    Time,
+    UserRepo,
+    SessionRepo,
+    OAuth2ServerRepo,
+    OAuth2Client,
+    OAuth2AuthorizationCode,
+    OAuth2ServerToken,
+    Audit,
+>;
+pub type OAuth2Client = OAuth2ClientServiceImpl<Id, Time, Secret, Hash, OAuth2ServerRepo>;
+pub type OAuth2AuthorizationCode = OAuth2AuthorizationCodeServiceImpl<Secret, Hash, Cache>;
+pub type OAuth2ServerToken = OAuth2ServerTokenServiceImpl<Jwt, Secret, Hash, Time>;
+
+pub type PersonalAccessTokenFeature = PersonalAccessTokenFeatureServiceImpl<
+    Database,
+    Auth,
+    Id,
+    Time,
+    AuthPersonalAccessToken,
+    UserRepo,
+    PersonalAccessTokenRepo,
+    Audit,
+>;
+
+pub type RoleFeature = RoleFeatureServiceImpl<Database, Auth, Id, Time, UserRepo, RoleRepo, Audit>;
+
+pub type AuditFeature = AuditFeatureServiceImpl<Database, Auth, UserRepo, AuditEventRepo>;
+pub type Audit = AuditServiceImpl<Id, Time, AuditEventRepo>;
+
+pub type RateLimitFeature = RateLimitFeatureServiceImpl<
+    Database,
+    Time,
+    AuthAccessToken,
+    AuthPersonalAccessToken,
+    Cache,
+    PersonalAccessTokenRepo,
+>;
+
 pub type Internal = InternalServiceImpl<Database, AuthInternal, UserRepo>;
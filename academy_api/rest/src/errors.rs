@@ -25,11 +25,17 @@ pub fn auth_error(err: AuthError) -> Response {
         AuthError::Authenticate(AuthenticateError::InvalidToken) => {
             InvalidTokenError.into_response()
         }
+        AuthError::Authenticate(AuthenticateError::ReadOnlyToken) => {
+            ReadOnlyTokenError.into_response()
+        }
         AuthError::Authenticate(AuthenticateError::Other(err)) => internal_server_error(err),
-        AuthError::Authorize(AuthorizeError::Admin) => PermissionDeniedError.into_response(),
+        AuthError::Authorize(AuthorizeError::Admin | AuthorizeError::Permission(_)) => {
+            PermissionDeniedError.into_response()
+        }
         AuthError::Authorize(AuthorizeError::EmailVerified) => {
             EmailNotVerifiedError.into_response()
         }
+        AuthError::Authorize(AuthorizeError::Session) => SessionRequiredError.into_response(),
     }
 }
 
@@ -38,6 +44,12 @@ pub fn auth_error_docs(op: TransformOperation) -> TransformOperation {
         .with(internal_server_error_docs)
         .add_error::<PermissionDeniedError>()
         .add_error::<EmailNotVerifiedError>()
+        .add_error::<SessionRequiredError>()
+        .add_error::<ReadOnlyTokenError>()
+}
+
+pub fn rate_limit_docs(op: TransformOperation) -> TransformOperation {
+    op.add_error::<RateLimitExceededError>()
 }
 
 /// A simple error response containing only the error code
@@ -62,7 +74,17 @@ error_code! {
     pub PermissionDeniedError(FORBIDDEN, "Permission denied");
     /// The authenticated user has not verified their email address.
     EmailNotVerifiedError(FORBIDDEN, "Email not verified");
+    /// This action is not available when authenticated using a personal access
+    /// token.
+    SessionRequiredError(FORBIDDEN, "Session required");
+    /// This action is not available when authenticated using a personal access
+    /// token with the `read_only` scope.
+    ReadOnlyTokenError(FORBIDDEN, "Read-only token");
 
     /// reCAPTCHA is enabled but no valid reCAPTCHA response has been provided.
     pub RecaptchaFailedError(PRECONDITION_FAILED, "Recaptcha failed");
+
+    /// Too many requests have been sent to this endpoint. The `Retry-After`
+    /// header contains the number of seconds to wait before trying again.
+    pub RateLimitExceededError(TOO_MANY_REQUESTS, "Rate limit exceeded");
 }
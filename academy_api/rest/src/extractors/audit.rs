@@ -0,0 +1,28 @@
+use std::convert::Infallible;
+
+use academy_models::audit::AuditContext;
+use aide::OperationInput;
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+
+use super::user_agent::UserAgent;
+use crate::middlewares::{client_ip::ClientIp, request_id::RequestId};
+
+/// Extract the client IP, user agent and request ID for the audit log
+pub struct ApiAuditContext(pub AuditContext);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ApiAuditContext {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let UserAgent(user_agent) = UserAgent::from_request_parts(parts, state).await?;
+
+        Ok(Self(AuditContext {
+            ip: parts.extensions.get::<ClientIp>().map(|x| x.0),
+            user_agent,
+            request_id: parts.extensions.get::<RequestId>().map(|x| x.0),
+        }))
+    }
+}
+
+impl OperationInput for ApiAuditContext {}
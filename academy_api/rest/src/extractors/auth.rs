@@ -1,6 +1,9 @@
 use std::convert::Infallible;
 
-use academy_models::auth::{AccessToken, InternalToken};
+use academy_models::{
+    auth::{AccessToken, InternalToken},
+    oauth2_server::OAuth2ServerAccessToken,
+};
 use aide::{gen::GenContext, openapi::Operation, OperationInput};
 use axum::{
     async_trait,
@@ -21,12 +24,16 @@ impl ApiTokenType for AccessToken {
 impl ApiTokenType for InternalToken {
     const NAME: &str = "InternalToken";
 }
+impl ApiTokenType for OAuth2ServerAccessToken {
+    const NAME: &str = "OAuth2Token";
+}
 
 mod private {
     use super::*;
     pub trait Sealed {}
     impl Sealed for AccessToken {}
     impl Sealed for InternalToken {}
+    impl Sealed for OAuth2ServerAccessToken {}
 }
 
 #[async_trait]
@@ -0,0 +1,45 @@
+use std::convert::Infallible;
+
+use aide::{gen::GenContext, openapi::Operation, OperationInput};
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header::AUTHORIZATION, request::Parts},
+};
+use base64::{prelude::BASE64_STANDARD, Engine};
+
+/// Extract username and password from a Basic Authorization header
+pub struct BasicAuth(pub Option<(String, String)>);
+
+impl BasicAuth {
+    pub const NAME: &str = "BasicAuth";
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for BasicAuth {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self(
+            parts
+                .headers
+                .get(AUTHORIZATION)
+                .and_then(|x| x.to_str().ok())
+                .and_then(|x| x.strip_prefix("Basic "))
+                .and_then(|x| BASE64_STANDARD.decode(x).ok())
+                .and_then(|x| String::from_utf8(x).ok())
+                .and_then(|x| {
+                    x.split_once(':')
+                        .map(|(username, password)| (username.into(), password.into()))
+                }),
+        ))
+    }
+}
+
+impl OperationInput for BasicAuth {
+    fn operation_input(_ctx: &mut GenContext, operation: &mut Operation) {
+        operation
+            .security
+            .push([(Self::NAME.into(), Vec::new())].into());
+    }
+}
@@ -1,2 +1,4 @@
+pub mod audit;
 pub mod auth;
+pub mod basic_auth;
 pub mod user_agent;
@@ -3,16 +3,24 @@ use std::{
     sync::Arc,
 };
 
+use academy_core_audit_contracts::AuditFeatureService;
 use academy_core_config_contracts::ConfigFeatureService;
 use academy_core_contact_contracts::ContactFeatureService;
 use academy_core_health_contracts::HealthFeatureService;
 use academy_core_internal_contracts::InternalService;
 use academy_core_mfa_contracts::MfaFeatureService;
 use academy_core_oauth2_contracts::OAuth2FeatureService;
+use academy_core_oauth2_server_contracts::OAuth2ServerFeatureService;
+use academy_core_personal_access_token_contracts::PersonalAccessTokenFeatureService;
+use academy_core_rate_limit_contracts::RateLimitFeatureService;
+use academy_core_role_contracts::RoleFeatureService;
 use academy_core_session_contracts::SessionFeatureService;
 use academy_core_user_contracts::UserFeatureService;
 use academy_di::Build;
-use academy_models::auth::{AccessToken, InternalToken};
+use academy_models::{
+    auth::{AccessToken, InternalToken},
+    oauth2_server::OAuth2ServerAccessToken,
+};
 use academy_utils::{academy_version, Apply};
 use aide::{
     axum::ApiRouter,
@@ -24,7 +32,7 @@ use axum::{
     response::{IntoResponse, Response},
     Extension, Json,
 };
-use extractors::auth::ApiTokenType;
+use extractors::{auth::ApiTokenType, basic_auth::BasicAuth};
 use regex::bytes::RegexSet;
 use tokio::net::TcpListener;
 use tower_http::cors::{AllowOrigin, Any, CorsLayer};
@@ -39,7 +47,21 @@ mod models;
 mod routes;
 
 #[derive(Debug, Clone, Build)]
-pub struct RestServer<Health, Config, User, Session, Contact, Mfa, OAuth2, Internal> {
+pub struct RestServer<
+    Health,
+    Config,
+    User,
+    Session,
+    Contact,
+    Mfa,
+    OAuth2,
+    OAuth2Server,
+    PersonalAccessToken,
+    Role,
+    Audit,
+    RateLimit,
+    Internal,
+> {
     _config: RestServerConfig,
     health: Health,
     config: Config,
@@ -48,6 +70,11 @@ pub struct RestServer<Health, Config, User, Session, Contact, Mfa, OAuth2, Inter
     contact: Contact,
     mfa: Mfa,
     oauth2: OAuth2,
+    oauth2_server: OAuth2Server,
+    personal_access_token: PersonalAccessToken,
+    role: Role,
+    audit: Audit,
+    rate_limit: RateLimit,
     internal: Internal,
 }
 
@@ -64,8 +91,36 @@ pub struct RestServerRealIpConfig {
     pub set_from: IpAddr,
 }
 
-impl<Health, Config, User, Session, Contact, Mfa, OAuth2, Internal>
-    RestServer<Health, Config, User, Session, Contact, Mfa, OAuth2, Internal>
+impl<
+        Health,
+        Config,
+        User,
+        Session,
+        Contact,
+        Mfa,
+        OAuth2,
+        OAuth2Server,
+        PersonalAccessToken,
+        Role,
+        Audit,
+        RateLimit,
+        Internal,
+    >
+    RestServer<
+        Health,
+        Config,
+        User,
+        Session,
+        Contact,
+        Mfa,
+        OAuth2,
+        OAuth2Server,
+        PersonalAccessToken,
+        Role,
+        Audit,
+        RateLimit,
+        Internal,
+    >
 where
     Health: HealthFeatureService,
     Config: ConfigFeatureService,
@@ -74,6 +129,11 @@ where
     Contact: ContactFeatureService,
     Mfa: MfaFeatureService,
     OAuth2: OAuth2FeatureService,
+    OAuth2Server: OAuth2ServerFeatureService,
+    PersonalAccessToken: PersonalAccessTokenFeatureService,
+    Role: RoleFeatureService,
+    Audit: AuditFeatureService,
+    RateLimit: RateLimitFeatureService,
     Internal: InternalService,
 {
     pub async fn serve(self) -> anyhow::Result<()> {
@@ -100,6 +160,10 @@ where
                 routes::session::TAG,
                 routes::mfa::TAG,
                 routes::oauth2::TAG,
+                routes::oauth2_server::TAG,
+                routes::personal_access_token::TAG,
+                routes::role::TAG,
+                routes::audit::TAG,
                 routes::internal::TAG,
             ]
             .into_iter()
@@ -116,9 +180,17 @@ where
                         description: None,
                         extensions: Default::default(),
                     });
+                    let basic = ReferenceOr::Item(SecurityScheme::Http {
+                        scheme: "basic".into(),
+                        bearer_format: None,
+                        description: None,
+                        extensions: Default::default(),
+                    });
                     [
                         (AccessToken::NAME.into(), bearer.clone()),
-                        (InternalToken::NAME.into(), bearer),
+                        (InternalToken::NAME.into(), bearer.clone()),
+                        (OAuth2ServerAccessToken::NAME.into(), bearer),
+                        (BasicAuth::NAME.into(), basic),
                     ]
                     .into()
                 },
@@ -164,14 +236,28 @@ where
     }
 
     fn router(self) -> ApiRouter<()> {
+        let rate_limit = Arc::new(self.rate_limit);
+
         ApiRouter::new()
             .merge(routes::health::router(self.health.into()))
             .merge(routes::config::router(self.config.into()))
-            .merge(routes::user::router(self.user.into()))
-            .merge(routes::session::router(self.session.into()))
-            .merge(routes::contact::router(self.contact.into()))
+            .merge(routes::user::router(
+                self.user.into(),
+                Arc::clone(&rate_limit),
+            ))
+            .merge(routes::session::router(
+                self.session.into(),
+                Arc::clone(&rate_limit),
+            ))
+            .merge(routes::contact::router(self.contact.into(), rate_limit))
             .merge(routes::mfa::router(self.mfa.into()))
             .merge(routes::oauth2::router(self.oauth2.into()))
+            .merge(routes::oauth2_server::router(self.oauth2_server.into()))
+            .merge(routes::personal_access_token::router(
+                self.personal_access_token.into(),
+            ))
+            .merge(routes::role::router(self.role.into()))
+            .merge(routes::audit::router(self.audit.into()))
             .merge(routes::internal::router(self.internal.into()))
     }
 }
@@ -0,0 +1,58 @@
+//! Limit the rate of requests to an endpoint.
+//!
+//! Requests are counted per [`RateLimitGroup`] by the rate limit feature. If the
+//! configured limit has been exceeded, the request is rejected with `429 Too
+//! Many Requests` and a `Retry-After` header.
+
+use std::sync::Arc;
+
+use academy_core_rate_limit_contracts::{RateLimitCheckError, RateLimitFeatureService};
+use academy_models::rate_limit::RateLimitGroup;
+use aide::axum::routing::ApiMethodRouter;
+use axum::{
+    extract::Request,
+    http::header::RETRY_AFTER,
+    middleware::{from_fn, Next},
+    response::{IntoResponse, Response},
+    Extension,
+};
+
+use super::client_ip::ClientIp;
+use crate::{
+    errors::{internal_server_error, RateLimitExceededError},
+    extractors::auth::ApiToken,
+};
+
+pub fn add<S: Clone + Send + Sync + 'static>(
+    service: Arc<impl RateLimitFeatureService>,
+    group: RateLimitGroup,
+) -> impl FnOnce(ApiMethodRouter<S>) -> ApiMethodRouter<S> {
+    move |router| {
+        router.route_layer(from_fn(
+            move |Extension(ClientIp(ip)): Extension<ClientIp>,
+                  ApiToken(token): ApiToken,
+                  request: Request,
+                  next: Next| {
+                let service = Arc::clone(&service);
+                async move {
+                    let token = (!token.is_empty()).then_some(&token);
+                    match service.check(group, ip, token).await {
+                        Ok(()) => next.run(request).await,
+                        Err(RateLimitCheckError::Exceeded { retry_after }) => {
+                            rate_limit_exceeded(retry_after.as_secs().max(1))
+                        }
+                        Err(RateLimitCheckError::Other(err)) => internal_server_error(err),
+                    }
+                }
+            },
+        ))
+    }
+}
+
+fn rate_limit_exceeded(retry_after_secs: u64) -> Response {
+    (
+        [(RETRY_AFTER, retry_after_secs.to_string())],
+        RateLimitExceededError,
+    )
+        .into_response()
+}
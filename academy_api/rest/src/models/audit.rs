@@ -0,0 +1,73 @@
+use std::net::IpAddr;
+
+use academy_models::{
+    audit::{AuditAction, AuditEvent, AuditEventFilter, AuditEventId},
+    user::UserId,
+};
+use chrono::DateTime;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::middlewares::request_id::RequestId;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiAuditEvent {
+    /// Audit event ID
+    pub id: AuditEventId,
+    /// ID of the user who performed the action
+    pub actor_id: Option<UserId>,
+    /// ID of the affected user
+    pub user_id: UserId,
+    /// The action that has been performed
+    pub action: AuditAction,
+    /// IP address of the client
+    pub ip: Option<IpAddr>,
+    /// User agent of the client
+    pub user_agent: Option<String>,
+    /// ID of the request in which the action has been performed (see the
+    /// `X-Request-Id` response header)
+    pub request_id: Option<String>,
+    /// Timestamp of the action
+    pub created_at: i64,
+}
+
+impl From<AuditEvent> for ApiAuditEvent {
+    fn from(value: AuditEvent) -> Self {
+        Self {
+            id: value.id,
+            actor_id: value.actor_id,
+            user_id: value.user_id,
+            action: value.action,
+            ip: value.ip,
+            user_agent: value.user_agent,
+            request_id: value.request_id.map(|x| RequestId(x).to_string()),
+            created_at: value.created_at.timestamp(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ApiAuditEventFilter {
+    /// Filter by `user_id`
+    pub user_id: Option<UserId>,
+    /// Filter by `actor_id`
+    pub actor_id: Option<UserId>,
+    /// Filter by `action`
+    pub action: Option<AuditAction>,
+    /// Only include events at or after this timestamp
+    pub after: Option<i64>,
+    /// Only include events before this timestamp
+    pub before: Option<i64>,
+}
+
+impl From<ApiAuditEventFilter> for AuditEventFilter {
+    fn from(value: ApiAuditEventFilter) -> Self {
+        Self {
+            user_id: value.user_id,
+            actor_id: value.actor_id,
+            action: value.action,
+            after: value.after.and_then(|x| DateTime::from_timestamp(x, 0)),
+            before: value.before.and_then(|x| DateTime::from_timestamp(x, 0)),
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use academy_models::{
+    mfa::{
+        TotpDevice, TotpDeviceId, TotpDeviceName, WebauthnAssertion, WebauthnAssertionOptions,
+        WebauthnCredentialId, WebauthnDevice, WebauthnDeviceId, WebauthnDeviceName,
+        WebauthnRegistration, WebauthnRegistrationOptions,
+    },
+    user::{UserDisplayName, UserName},
+    user_export::UserExportWebauthnDevice,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct ApiTotpDevice {
+    /// TOTP device ID
+    pub id: TotpDeviceId,
+    /// Display name of the device
+    pub name: TotpDeviceName,
+    /// Whether the device has been confirmed and can be used to log in
+    pub enabled: bool,
+    /// Timestamp of creation
+    pub created_at: i64,
+}
+
+impl From<TotpDevice> for ApiTotpDevice {
+    fn from(value: TotpDevice) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            enabled: value.enabled,
+            created_at: value.created_at.timestamp(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct ApiWebauthnDevice {
+    /// WebAuthn device ID
+    pub id: WebauthnDeviceId,
+    /// Display name of the device
+    pub name: WebauthnDeviceName,
+    /// Timestamp of registration
+    pub created_at: i64,
+    /// Timestamp of last successful authentication
+    pub last_used_at: Option<i64>,
+}
+
+impl From<WebauthnDevice> for ApiWebauthnDevice {
+    fn from(value: WebauthnDevice) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            created_at: value.created_at.timestamp(),
+            last_used_at: value.last_used_at.map(|x| x.timestamp()),
+        }
+    }
+}
+
+impl From<UserExportWebauthnDevice> for ApiWebauthnDevice {
+    fn from(value: UserExportWebauthnDevice) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            created_at: value.created_at.timestamp(),
+            last_used_at: value.last_used_at.map(|x| x.timestamp()),
+        }
+    }
+}
+
+/// Options for `navigator.credentials.create()` (all binary values are base64url
+/// encoded)
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiWebauthnRegistrationOptions {
+    /// Challenge that must be signed by the authenticator
+    #[serde(with = "academy_utils::serde::base64url")]
+    #[schemars(with = "String")]
+    pub challenge: Vec<u8>,
+    /// Relying party
+    pub rp: ApiWebauthnRelyingParty,
+    /// User entity
+    pub user: ApiWebauthnUser,
+    /// IDs of credentials already registered for this user
+    pub exclude_credentials: Vec<ApiWebauthnCredentialDescriptor>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiWebauthnRelyingParty {
+    /// Relying party ID (domain)
+    pub id: String,
+    /// Display name
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiWebauthnUser {
+    /// Opaque user handle
+    #[serde(with = "academy_utils::serde::base64url")]
+    #[schemars(with = "String")]
+    pub id: Vec<u8>,
+    /// Unique user name
+    pub name: UserName,
+    /// Display name
+    pub display_name: UserDisplayName,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiWebauthnCredentialDescriptor {
+    /// Credential ID
+    #[serde(with = "academy_utils::serde::base64url")]
+    #[schemars(with = "String")]
+    pub id: Vec<u8>,
+}
+
+impl From<WebauthnRegistrationOptions> for ApiWebauthnRegistrationOptions {
+    fn from(value: WebauthnRegistrationOptions) -> Self {
+        Self {
+            challenge: value.challenge.into_inner(),
+            rp: ApiWebauthnRelyingParty {
+                id: value.rp.id,
+                name: value.rp.name,
+            },
+            user: ApiWebauthnUser {
+                id: value.user_id.as_bytes().to_vec(),
+                name: value.user_name,
+                display_name: value.user_display_name,
+            },
+            exclude_credentials: value
+                .exclude_credentials
+                .into_iter()
+                .map(|id| ApiWebauthnCredentialDescriptor {
+                    id: id.into_inner(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Options for `navigator.credentials.get()` (all binary values are base64url
+/// encoded)
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiWebauthnAssertionOptions {
+    /// Challenge that must be signed by the authenticator
+    #[serde(with = "academy_utils::serde::base64url")]
+    #[schemars(with = "String")]
+    pub challenge: Vec<u8>,
+    /// Relying party ID (domain)
+    pub rp_id: String,
+}
+
+impl From<WebauthnAssertionOptions> for ApiWebauthnAssertionOptions {
+    fn from(value: WebauthnAssertionOptions) -> Self {
+        Self {
+            challenge: value.challenge.into_inner(),
+            rp_id: value.rp_id,
+        }
+    }
+}
+
+/// Response of the authenticator to `navigator.credentials.create()` (all
+/// binary values are base64url encoded)
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ApiWebauthnRegistration {
+    /// `response.clientDataJSON`
+    #[serde(with = "academy_utils::serde::base64url")]
+    #[schemars(with = "String")]
+    pub client_data_json: Vec<u8>,
+    /// `response.attestationObject`
+    #[serde(with = "academy_utils::serde::base64url")]
+    #[schemars(with = "String")]
+    pub attestation_object: Vec<u8>,
+}
+
+impl From<ApiWebauthnRegistration> for WebauthnRegistration {
+    fn from(value: ApiWebauthnRegistration) -> Self {
+        Self {
+            client_data_json: value.client_data_json,
+            attestation_object: value.attestation_object,
+        }
+    }
+}
+
+/// Response of the authenticator to `navigator.credentials.get()` (all binary
+/// values are base64url encoded)
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ApiWebauthnAssertion {
+    /// `rawId`
+    #[serde(with = "academy_utils::serde::base64url")]
+    #[schemars(with = "String")]
+    pub credential_id: WebauthnCredentialId,
+    /// `response.clientDataJSON`
+    #[serde(with = "academy_utils::serde::base64url")]
+    #[schemars(with = "String")]
+    pub client_data_json: Vec<u8>,
+    /// `response.authenticatorData`
+    #[serde(with = "academy_utils::serde::base64url")]
+    #[schemars(with = "String")]
+    pub authenticator_data: Vec<u8>,
+    /// `response.signature`
+    #[serde(with = "academy_utils::serde::base64url")]
+    #[schemars(with = "String")]
+    pub signature: Vec<u8>,
+}
+
+impl From<ApiWebauthnAssertion> for WebauthnAssertion {
+    fn from(value: ApiWebauthnAssertion) -> Self {
+        Self {
+            credential_id: value.credential_id,
+            client_data_json: value.client_data_json,
+            authenticator_data: value.authenticator_data,
+            signature: value.signature,
+        }
+    }
+}
@@ -6,8 +6,13 @@ use serde::Deserialize;
 
 use crate::const_schema;
 
+pub mod audit;
 pub mod contact;
+pub mod mfa;
 pub mod oauth2;
+pub mod oauth2_server;
+pub mod personal_access_token;
+pub mod role;
 pub mod session;
 pub mod user;
 
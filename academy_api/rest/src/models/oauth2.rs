@@ -1,7 +1,8 @@
 use academy_models::{
     oauth2::{
-        OAuth2AuthorizationCode, OAuth2Link, OAuth2LinkId, OAuth2Login, OAuth2ProviderId,
-        OAuth2ProviderName, OAuth2ProviderSummary, OAuth2RemoteUserName,
+        OAuth2AuthorizationCode, OAuth2AuthorizationRequest, OAuth2Link, OAuth2LinkId, OAuth2Login,
+        OAuth2LoginBinding, OAuth2ProviderId, OAuth2ProviderName, OAuth2ProviderSummary,
+        OAuth2RemoteUserName, OAuth2State,
     },
     url::Url,
 };
@@ -14,9 +15,6 @@ pub struct ApiOAuth2ProviderSummary {
     pub id: OAuth2ProviderId,
     /// Display name
     pub name: OAuth2ProviderName,
-    /// Remote authorize endpoint URL *without* `state` and `redirect_uri`
-    /// parameters
-    pub authorize_url: Url,
 }
 
 impl From<OAuth2ProviderSummary> for ApiOAuth2ProviderSummary {
@@ -24,7 +22,31 @@ impl From<OAuth2ProviderSummary> for ApiOAuth2ProviderSummary {
         Self {
             id: value.id,
             name: value.name,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiOAuth2AuthorizationRequest {
+    /// Remote authorize endpoint URL including the `state`, `redirect_uri`
+    /// and PKCE code challenge parameters
+    pub authorize_url: Url,
+    /// State that is returned by the OAuth2 provider after successful
+    /// authentication. Clients should store this value and compare it with
+    /// the state returned by the provider.
+    pub state: OAuth2State,
+    /// Secret that binds the login to this client. Clients must keep this
+    /// value private (e.g. in session storage) and send it together with the
+    /// authorization code to complete the login.
+    pub binding: OAuth2LoginBinding,
+}
+
+impl From<OAuth2AuthorizationRequest> for ApiOAuth2AuthorizationRequest {
+    fn from(value: OAuth2AuthorizationRequest) -> Self {
+        Self {
             authorize_url: value.auth_url,
+            state: value.state,
+            binding: value.binding,
         }
     }
 }
@@ -57,6 +79,10 @@ pub struct ApiOAuth2Login {
     pub code: OAuth2AuthorizationCode,
     /// Redirect URI that was used for this authentication.
     pub redirect_uri: Url,
+    /// State returned by the OAuth2 provider
+    pub state: OAuth2State,
+    /// Binding returned when the login was started
+    pub binding: OAuth2LoginBinding,
 }
 
 impl From<ApiOAuth2Login> for OAuth2Login {
@@ -65,6 +91,8 @@ impl From<ApiOAuth2Login> for OAuth2Login {
             provider_id: value.provider_id,
             code: value.code,
             redirect_uri: value.redirect_uri,
+            state: value.state,
+            binding: value.binding,
         }
     }
 }
@@ -0,0 +1,137 @@
+use academy_models::{
+    oauth2_server::{
+        OAuth2Client, OAuth2ClientId, OAuth2ClientName, OAuth2ClientSecret, OAuth2Consent,
+        OAuth2Scope, OAuth2ServerAccessToken, OAuth2ServerIdToken, OAuth2ServerRefreshToken,
+        OAuth2TokenInfo, OAuth2TokenResponse,
+    },
+    session::SessionId,
+    url::Url,
+    user::UserId,
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiOAuth2Client {
+    /// OAuth2 client ID
+    pub id: OAuth2ClientId,
+    /// Display name of the client application
+    pub name: OAuth2ClientName,
+    /// Redirect URIs the client is allowed to use
+    pub redirect_uris: Vec<Url>,
+    /// Whether the client must authenticate using its secret
+    pub confidential: bool,
+    /// Creation timestamp
+    pub created_at: i64,
+}
+
+impl From<OAuth2Client> for ApiOAuth2Client {
+    fn from(value: OAuth2Client) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            redirect_uris: value.redirect_uris,
+            confidential: value.confidential,
+            created_at: value.created_at.timestamp(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiOAuth2ClientWithSecret {
+    #[serde(flatten)]
+    pub client: ApiOAuth2Client,
+    /// Client secret of a confidential client. This is the only time the
+    /// secret is returned.
+    pub secret: Option<OAuth2ClientSecret>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiOAuth2ClientSummary {
+    /// OAuth2 client ID
+    pub id: OAuth2ClientId,
+    /// Display name of the client application
+    pub name: OAuth2ClientName,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiOAuth2Consent {
+    /// The client that requests access
+    pub client: ApiOAuth2ClientSummary,
+    /// The requested scopes
+    pub scopes: Vec<OAuth2Scope>,
+}
+
+impl From<OAuth2Consent> for ApiOAuth2Consent {
+    fn from(value: OAuth2Consent) -> Self {
+        Self {
+            client: ApiOAuth2ClientSummary {
+                id: value.client.id,
+                name: value.client.name,
+            },
+            scopes: value.scopes.0.into_iter().collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiOAuth2TokenResponse {
+    pub access_token: OAuth2ServerAccessToken,
+    /// Always `Bearer`
+    pub token_type: &'static str,
+    /// Lifetime of the access token in seconds
+    pub expires_in: u64,
+    pub refresh_token: OAuth2ServerRefreshToken,
+    /// Only included if the `openid` scope has been granted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<OAuth2ServerIdToken>,
+    /// Space-delimited list of granted scopes
+    pub scope: String,
+}
+
+impl From<OAuth2TokenResponse> for ApiOAuth2TokenResponse {
+    fn from(value: OAuth2TokenResponse) -> Self {
+        Self {
+            access_token: value.access_token,
+            token_type: "Bearer",
+            expires_in: value.expires_in.as_secs(),
+            refresh_token: value.refresh_token,
+            id_token: value.id_token,
+            scope: value.scopes.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiOAuth2Introspection {
+    /// Whether the token is active
+    pub active: bool,
+    #[serde(flatten)]
+    pub info: Option<ApiOAuth2TokenInfo>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiOAuth2TokenInfo {
+    /// ID of the user who granted access
+    pub sub: UserId,
+    /// ID of the session backing the grant
+    pub sid: SessionId,
+    /// ID of the client the token has been issued to
+    pub client_id: OAuth2ClientId,
+    /// Space-delimited list of granted scopes
+    pub scope: String,
+}
+
+impl From<Option<OAuth2TokenInfo>> for ApiOAuth2Introspection {
+    fn from(value: Option<OAuth2TokenInfo>) -> Self {
+        Self {
+            active: value.is_some(),
+            info: value.map(|info| ApiOAuth2TokenInfo {
+                sub: info.user_id,
+                sid: info.session_id,
+                client_id: info.client_id,
+                scope: info.scopes.to_string(),
+            }),
+        }
+    }
+}
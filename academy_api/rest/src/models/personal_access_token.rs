@@ -0,0 +1,53 @@
+use std::collections::BTreeSet;
+
+use academy_models::{
+    auth::AccessToken,
+    personal_access_token::{
+        PersonalAccessToken, PersonalAccessTokenId, PersonalAccessTokenName,
+        PersonalAccessTokenScope,
+    },
+    user::UserId,
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiPersonalAccessToken {
+    /// Personal access token ID
+    pub id: PersonalAccessTokenId,
+    /// User ID
+    pub user_id: UserId,
+    /// Display name of the token
+    pub name: PersonalAccessTokenName,
+    /// Scopes granted to the token
+    pub scopes: BTreeSet<PersonalAccessTokenScope>,
+    /// Creation timestamp
+    pub created_at: i64,
+    /// Expiration timestamp (`null` if the token does not expire)
+    pub expires_at: Option<i64>,
+    /// Timestamp of the last successful authentication using this token
+    pub last_used_at: Option<i64>,
+}
+
+impl From<PersonalAccessToken> for ApiPersonalAccessToken {
+    fn from(value: PersonalAccessToken) -> Self {
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            name: value.name,
+            scopes: value.scopes,
+            created_at: value.created_at.timestamp(),
+            expires_at: value.expires_at.map(|x| x.timestamp()),
+            last_used_at: value.last_used_at.map(|x| x.timestamp()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiPersonalAccessTokenWithToken {
+    #[serde(flatten)]
+    pub personal_access_token: ApiPersonalAccessToken,
+    /// The token which can be used in the `Authorization` header instead of
+    /// an access token. This is the only time the token is returned.
+    pub token: AccessToken,
+}
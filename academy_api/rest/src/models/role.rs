@@ -0,0 +1,28 @@
+use std::collections::BTreeSet;
+
+use academy_models::role::{Permission, Role, RoleId, RoleName};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiRole {
+    /// Role ID
+    pub id: RoleId,
+    /// Unique name of the role
+    pub name: RoleName,
+    /// Permissions granted to users with this role
+    pub permissions: BTreeSet<Permission>,
+    /// Creation timestamp
+    pub created_at: i64,
+}
+
+impl From<Role> for ApiRole {
+    fn from(value: Role) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            permissions: value.permissions,
+            created_at: value.created_at.timestamp(),
+        }
+    }
+}
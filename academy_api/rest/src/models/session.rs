@@ -1,6 +1,9 @@
+use std::net::IpAddr;
+
 use academy_models::{
     auth::{AccessToken, Login, RefreshToken},
-    session::{DeviceName, Session, SessionId},
+    oauth2::OAuth2ProviderId,
+    session::{DeviceName, Session, SessionId, SessionLoginMethod},
     user::UserId,
 };
 use schemars::JsonSchema;
@@ -16,21 +19,74 @@ pub struct ApiSession {
     pub user_id: UserId,
     /// Device Name
     pub device_name: Option<DeviceName>,
+    /// IP address of the client which created the session
+    pub ip: Option<IpAddr>,
+    /// IP address of the client which most recently refreshed the session
+    pub last_ip: Option<IpAddr>,
+    /// Browser of the client which created the session
+    pub browser: Option<String>,
+    /// Operating system of the client which created the session
+    pub os: Option<String>,
+    /// Method which has been used to create the session
+    pub login_method: ApiSessionLoginMethod,
+    /// ID of the OAuth2 provider (only if `login_method` is `oauth2`)
+    pub oauth2_provider_id: Option<OAuth2ProviderId>,
+    /// Whether this is the currently authenticated session
+    pub is_current: bool,
+    /// Timestamp of session creation
+    pub created_at: i64,
     /// Timestamp of last refresh
     pub last_update: i64,
 }
 
-impl From<Session> for ApiSession {
-    fn from(value: Session) -> Self {
+impl ApiSession {
+    pub fn new(session: Session, is_current: bool) -> Self {
+        let (login_method, oauth2_provider_id) = match session.login_method {
+            SessionLoginMethod::Password => (ApiSessionLoginMethod::Password, None),
+            SessionLoginMethod::OAuth2(provider_id) => {
+                (ApiSessionLoginMethod::OAuth2, Some(provider_id))
+            }
+            SessionLoginMethod::Impersonation => (ApiSessionLoginMethod::Impersonation, None),
+            SessionLoginMethod::OAuth2Client => (ApiSessionLoginMethod::OAuth2Client, None),
+            SessionLoginMethod::LoginCode => (ApiSessionLoginMethod::LoginCode, None),
+        };
+
         Self {
-            id: value.id,
-            user_id: value.user_id,
-            device_name: value.device_name,
-            last_update: value.updated_at.timestamp(),
+            id: session.id,
+            user_id: session.user_id,
+            device_name: session.device_name,
+            ip: session.ip,
+            last_ip: session.last_ip,
+            browser: session.browser,
+            os: session.os,
+            login_method,
+            oauth2_provider_id,
+            is_current,
+            created_at: session.created_at.timestamp(),
+            last_update: session.updated_at.timestamp(),
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+pub enum ApiSessionLoginMethod {
+    /// Username/email and password
+    #[serde(rename = "password")]
+    Password,
+    /// Remote user of an external OAuth2 provider
+    #[serde(rename = "oauth2")]
+    OAuth2,
+    /// Impersonation by an administrator
+    #[serde(rename = "impersonation")]
+    Impersonation,
+    /// Authorization of an OAuth2 client application
+    #[serde(rename = "oauth2_client")]
+    OAuth2Client,
+    /// One-time login code sent via email
+    #[serde(rename = "login_code")]
+    LoginCode,
+}
+
 #[derive(Serialize, JsonSchema)]
 pub struct ApiLogin {
     user: ApiUser,
@@ -43,7 +99,7 @@ impl From<Login> for ApiLogin {
     fn from(value: Login) -> Self {
         Self {
             user: value.user_composite.into(),
-            session: value.session.into(),
+            session: ApiSession::new(value.session, true),
             access_token: value.access_token,
             refresh_token: value.refresh_token,
         }
@@ -2,10 +2,11 @@ use academy_models::{
     email_address::EmailAddress,
     url::Url,
     user::{
-        UserBio, UserCity, UserComposite, UserCountry, UserDisplayName, UserFilter, UserFirstName,
-        UserId, UserIdOrSelf, UserLastName, UserName, UserPassword, UserStreet, UserTags,
-        UserVatId, UserZipCode,
+        UserBio, UserCity, UserComposite, UserCountry, UserDisplayName, UserEmail, UserFilter,
+        UserFirstName, UserId, UserIdOrSelf, UserLastName, UserName, UserNameHistoryEntry,
+        UserPassword, UserStreet, UserTags, UserVatId, UserZipCode,
     },
+    user_export::UserExport,
     SearchTerm,
 };
 use schemars::{
@@ -16,6 +17,12 @@ use schemars::{
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
+use super::{
+    audit::ApiAuditEvent,
+    mfa::{ApiTotpDevice, ApiWebauthnDevice},
+    oauth2::ApiOAuth2Link,
+    session::ApiSession,
+};
 use crate::const_schema;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -41,6 +48,9 @@ pub struct ApiUser {
     pub enabled: bool,
     /// Whether the user is an administrator
     pub admin: bool,
+    /// Timestamp at which the user has been marked as deleted (the account is
+    /// purged after the deletion grace period)
+    pub deleted_at: Option<i64>,
     /// Whether the user has set a password (if not, login is only possible via
     /// OAuth2)
     pub password: bool,
@@ -72,7 +82,8 @@ pub struct ApiUser {
     pub can_buy_coins: bool,
     /// Whether the user can receive coins
     pub can_receive_coins: bool,
-    /// URL of the user's avatar
+    /// URL of the user's avatar (the uploaded image if available, otherwise
+    /// the Gravatar of the user's email address)
     pub avatar_url: Option<Url>,
 }
 
@@ -88,7 +99,10 @@ impl From<UserComposite> for ApiUser {
             invoice_info,
         } = user_composite;
 
-        let avatar_url = user.email.as_ref().map(get_avatar_url);
+        let avatar_url = profile
+            .avatar_url
+            .clone()
+            .or_else(|| user.email.as_ref().map(get_avatar_url));
 
         Self {
             id: user.id,
@@ -100,6 +114,7 @@ impl From<UserComposite> for ApiUser {
             last_name_change: user.last_name_change.map(|x| x.timestamp()),
             enabled: user.enabled,
             admin: user.admin,
+            deleted_at: user.deleted_at.map(|x| x.timestamp()),
             newsletter: user.newsletter,
 
             display_name: profile.display_name,
@@ -125,6 +140,82 @@ impl From<UserComposite> for ApiUser {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct ApiUserEmail {
+    /// Additional email address (can be used for login and password reset)
+    pub email: EmailAddress,
+    /// Timestamp at which the email address has been added
+    pub created_at: i64,
+}
+
+impl From<UserEmail> for ApiUserEmail {
+    fn from(value: UserEmail) -> Self {
+        Self {
+            email: value.email,
+            created_at: value.created_at.timestamp(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct ApiUserNameHistoryEntry {
+    /// Previous name of the user
+    pub name: UserName,
+    /// Timestamp at which the user stopped using this name
+    pub changed_at: i64,
+}
+
+impl From<UserNameHistoryEntry> for ApiUserNameHistoryEntry {
+    fn from(value: UserNameHistoryEntry) -> Self {
+        Self {
+            name: value.name,
+            changed_at: value.changed_at.timestamp(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiUserExport {
+    /// Timestamp of the creation of the export
+    pub created_at: i64,
+    /// The user
+    pub user: ApiUser,
+    /// All additional email addresses of the user
+    pub emails: Vec<ApiUserEmail>,
+    /// All previous names of the user
+    pub name_history: Vec<ApiUserNameHistoryEntry>,
+    /// All sessions of the user
+    pub sessions: Vec<ApiSession>,
+    /// All OAuth2 links of the user
+    pub oauth2_links: Vec<ApiOAuth2Link>,
+    /// All TOTP devices of the user
+    pub totp_devices: Vec<ApiTotpDevice>,
+    /// All WebAuthn devices of the user
+    pub webauthn_devices: Vec<ApiWebauthnDevice>,
+    /// All audit events affecting the user
+    pub audit_events: Vec<ApiAuditEvent>,
+}
+
+impl From<UserExport> for ApiUserExport {
+    fn from(value: UserExport) -> Self {
+        Self {
+            created_at: value.created_at.timestamp(),
+            user: value.user_composite.into(),
+            emails: value.emails.into_iter().map(Into::into).collect(),
+            name_history: value.name_history.into_iter().map(Into::into).collect(),
+            sessions: value
+                .sessions
+                .into_iter()
+                .map(|session| ApiSession::new(session, false))
+                .collect(),
+            oauth2_links: value.oauth2_links.into_iter().map(Into::into).collect(),
+            totp_devices: value.totp_devices.into_iter().map(Into::into).collect(),
+            webauthn_devices: value.webauthn_devices.into_iter().map(Into::into).collect(),
+            audit_events: value.audit_events.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
 #[derive(Deserialize, JsonSchema)]
 pub struct ApiUserFilter {
     /// Filter by `name` and `display_name`
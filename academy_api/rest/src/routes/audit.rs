@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use academy_core_audit_contracts::{
+    AuditEventListQuery, AuditEventListResult, AuditFeatureService, AuditListEventsError,
+    AuditListUserEventsError,
+};
+use aide::{
+    axum::{routing, ApiRouter},
+    transform::TransformOperation,
+};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::user::UserNotFoundError;
+use crate::{
+    docs::TransformOperationExt,
+    errors::{auth_error, auth_error_docs, internal_server_error, internal_server_error_docs},
+    extractors::auth::ApiToken,
+    models::{
+        audit::{ApiAuditEvent, ApiAuditEventFilter},
+        user::PathUserIdOrSelf,
+        ApiPaginationSlice,
+    },
+};
+
+pub const TAG: &str = "Audit";
+
+pub fn router(service: Arc<impl AuditFeatureService>) -> ApiRouter<()> {
+    ApiRouter::new()
+        .api_route(
+            "/auth/audit_events",
+            routing::get_with(list_events, list_events_docs),
+        )
+        .api_route(
+            "/auth/users/:user_id/audit_events",
+            routing::get_with(list_user_events, list_user_events_docs),
+        )
+        .with_state(service)
+        .with_path_items(|op| op.tag(TAG))
+}
+
+#[derive(Serialize, JsonSchema)]
+struct ListResult {
+    /// The total number of audit events matching the given query
+    total: u64,
+    /// The paginated list of audit events, most recent events first
+    events: Vec<ApiAuditEvent>,
+}
+
+impl From<AuditEventListResult> for ListResult {
+    fn from(value: AuditEventListResult) -> Self {
+        Self {
+            total: value.total,
+            events: value.events.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+async fn list_events(
+    service: State<Arc<impl AuditFeatureService>>,
+    token: ApiToken,
+    Query(pagination): Query<ApiPaginationSlice>,
+    Query(filter): Query<ApiAuditEventFilter>,
+) -> Response {
+    match service
+        .list_events(
+            &token.0,
+            AuditEventListQuery {
+                pagination: pagination.into(),
+                filter: filter.into(),
+            },
+        )
+        .await
+    {
+        Ok(result) => Json(ListResult::from(result)).into_response(),
+        Err(AuditListEventsError::Auth(err)) => auth_error(err),
+        Err(AuditListEventsError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn list_events_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Return all audit events matching the given query.")
+        .description("Requires admin privileges.")
+        .add_response::<ListResult>(StatusCode::OK, None)
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+async fn list_user_events(
+    service: State<Arc<impl AuditFeatureService>>,
+    token: ApiToken,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+    Query(pagination): Query<ApiPaginationSlice>,
+) -> Response {
+    match service
+        .list_user_events(&token.0, user_id.into(), pagination.into())
+        .await
+    {
+        Ok(result) => Json(ListResult::from(result)).into_response(),
+        Err(AuditListUserEventsError::NotFound) => UserNotFoundError.into_response(),
+        Err(AuditListUserEventsError::Auth(err)) => auth_error(err),
+        Err(AuditListUserEventsError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn list_user_events_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Return the security activity of the given user.")
+        .description(
+            "Lists all security-relevant actions which have been performed on the user account, \
+             e.g. password resets, MFA changes or updates by administrators.",
+        )
+        .add_response::<ListResult>(StatusCode::OK, None)
+        .add_error::<UserNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use academy_core_config_contracts::ConfigFeatureService;
+use academy_models::jwt::Jwks;
 use aide::{
     axum::{routing, ApiRouter},
     transform::TransformOperation,
@@ -22,6 +23,10 @@ pub fn router(service: Arc<impl ConfigFeatureService>) -> ApiRouter<()> {
             "/auth/recaptcha",
             routing::get_with(get_recaptcha_sitekey, get_recaptcha_sitekey_docs),
         )
+        .api_route(
+            "/.well-known/jwks.json",
+            routing::get_with(get_jwks, get_jwks_docs),
+        )
         .with_state(service)
         .with_path_items(|op| op.tag(TAG))
 }
@@ -37,3 +42,16 @@ fn get_recaptcha_sitekey_docs(op: TransformOperation) -> TransformOperation {
             op.example("recaptcha-sitekey")
         })
 }
+
+async fn get_jwks(service: State<Arc<impl ConfigFeatureService>>) -> Response {
+    Json(service.get_jwks()).into_response()
+}
+
+fn get_jwks_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Return the public keys that can be used to verify JWTs issued by this service.")
+        .description(
+            "Access tokens and internal tokens include the id of the signing key in the `kid` \
+             header. Keys of symmetric algorithms are not included.",
+        )
+        .add_response::<Jwks>(StatusCode::OK, None)
+}
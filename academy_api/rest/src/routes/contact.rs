@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use academy_core_contact_contracts::{ContactFeatureService, ContactSendMessageError};
-use academy_models::RecaptchaResponse;
+use academy_core_rate_limit_contracts::RateLimitFeatureService;
+use academy_models::{rate_limit::RateLimitGroup, RecaptchaResponse};
+use academy_utils::Apply;
 use aide::{
     axum::{routing, ApiRouter},
     transform::TransformOperation,
@@ -18,17 +20,24 @@ use serde::Deserialize;
 use crate::{
     docs::TransformOperationExt,
     error_code,
-    errors::{internal_server_error, internal_server_error_docs, RecaptchaFailedError},
+    errors::{
+        internal_server_error, internal_server_error_docs, rate_limit_docs, RecaptchaFailedError,
+    },
+    middlewares::rate_limit,
     models::{contact::ApiContactMessage, OkResponse, StringOption},
 };
 
 pub const TAG: &str = "Contact";
 
-pub fn router(service: Arc<impl ContactFeatureService>) -> ApiRouter<()> {
+pub fn router(
+    service: Arc<impl ContactFeatureService>,
+    rate_limit_service: Arc<impl RateLimitFeatureService>,
+) -> ApiRouter<()> {
     ApiRouter::new()
         .api_route(
             "/auth/contact",
-            routing::post_with(send_message, send_message_docs),
+            routing::post_with(send_message, send_message_docs)
+                .apply(rate_limit::add(rate_limit_service, RateLimitGroup::Contact)),
         )
         .with_state(service)
         .with_path_items(|op| op.tag(TAG))
@@ -67,6 +76,7 @@ fn send_message_docs(op: TransformOperation) -> TransformOperation {
         .add_response::<OkResponse>(StatusCode::OK, "The message has been sent.")
         .add_error::<RecaptchaFailedError>()
         .add_error::<CouldNotSendMessageError>()
+        .with(rate_limit_docs)
         .with(internal_server_error_docs)
 }
 
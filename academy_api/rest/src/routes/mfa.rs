@@ -1,9 +1,19 @@
 use std::sync::Arc;
 
 use academy_core_mfa_contracts::{
-    MfaDisableError, MfaEnableError, MfaFeatureService, MfaInitializeError,
+    MfaConfirmTotpDeviceError, MfaCountRecoveryCodesError, MfaCreateTotpDeviceError,
+    MfaDeleteTotpDeviceError, MfaDeleteWebauthnDeviceError, MfaDisableError, MfaEnableError,
+    MfaFeatureService, MfaFinishWebauthnRegistrationError, MfaInitializeError,
+    MfaListTotpDevicesError, MfaListWebauthnDevicesError, MfaRegenerateRecoveryCodesError,
+    MfaRenameTotpDeviceError, MfaStartWebauthnRegistrationError,
+};
+use academy_models::{
+    mfa::{
+        MfaRecoveryCode, TotpCode, TotpDeviceId, TotpDeviceName, TotpSecretBase32,
+        WebauthnDeviceId, WebauthnDeviceName,
+    },
+    user::UserNameOrEmailAddress,
 };
-use academy_models::mfa::TotpCode;
 use aide::{
     axum::{routing, ApiRouter},
     transform::TransformOperation,
@@ -15,15 +25,22 @@ use axum::{
     Json,
 };
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::user::UserNotFoundError;
 use crate::{
     docs::TransformOperationExt,
     error_code,
     errors::{auth_error, auth_error_docs, internal_server_error, internal_server_error_docs},
-    extractors::auth::ApiToken,
-    models::{user::PathUserIdOrSelf, OkResponse},
+    extractors::{audit::ApiAuditContext, auth::ApiToken},
+    models::{
+        mfa::{
+            ApiTotpDevice, ApiWebauthnAssertionOptions, ApiWebauthnDevice, ApiWebauthnRegistration,
+            ApiWebauthnRegistrationOptions,
+        },
+        user::{ApiUserIdOrSelf, PathUserIdOrSelf},
+        OkResponse,
+    },
 };
 
 pub const TAG: &str = "MFA";
@@ -36,6 +53,45 @@ pub fn router(service: Arc<impl MfaFeatureService>) -> ApiRouter<()> {
                 .put_with(enable, enable_docs)
                 .delete_with(disable, disable_docs),
         )
+        .api_route(
+            "/auth/users/:user_id/mfa/recovery_codes",
+            routing::get_with(count_recovery_codes, count_recovery_codes_docs)
+                .post_with(regenerate_recovery_codes, regenerate_recovery_codes_docs),
+        )
+        .api_route(
+            "/auth/users/:user_id/mfa/totp",
+            routing::get_with(list_totp_devices, list_totp_devices_docs)
+                .post_with(create_totp_device, create_totp_device_docs),
+        )
+        .api_route(
+            "/auth/users/:user_id/mfa/totp/:device_id",
+            routing::put_with(confirm_totp_device, confirm_totp_device_docs)
+                .patch_with(rename_totp_device, rename_totp_device_docs)
+                .delete_with(delete_totp_device, delete_totp_device_docs),
+        )
+        .api_route(
+            "/auth/users/:user_id/mfa/webauthn",
+            routing::get_with(list_webauthn_devices, list_webauthn_devices_docs)
+                .post_with(
+                    start_webauthn_registration,
+                    start_webauthn_registration_docs,
+                )
+                .put_with(
+                    finish_webauthn_registration,
+                    finish_webauthn_registration_docs,
+                ),
+        )
+        .api_route(
+            "/auth/users/:user_id/mfa/webauthn/:device_id",
+            routing::delete_with(delete_webauthn_device, delete_webauthn_device_docs),
+        )
+        .api_route(
+            "/auth/mfa/webauthn",
+            routing::post_with(
+                create_webauthn_assertion_options,
+                create_webauthn_assertion_options_docs,
+            ),
+        )
         .with_state(service)
         .with_path_items(|op| op.tag(TAG))
 }
@@ -78,11 +134,15 @@ struct EnableRequest {
 async fn enable(
     service: State<Arc<impl MfaFeatureService>>,
     token: ApiToken,
+    audit: ApiAuditContext,
     Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
     Json(EnableRequest { code }): Json<EnableRequest>,
 ) -> Response {
-    match service.enable(&token.0, user_id.into(), code).await {
-        Ok(recovery_code) => Json(recovery_code).into_response(),
+    match service
+        .enable(&token.0, user_id.into(), code, &audit.0)
+        .await
+    {
+        Ok(recovery_codes) => Json(recovery_codes).into_response(),
         Err(MfaEnableError::AlreadyEnabled) => MfaAlreadyEnabledError.into_response(),
         Err(MfaEnableError::NotInitialized) => MfaNotInitializedError.into_response(),
         Err(MfaEnableError::InvalidCode) => InvalidMfaCodeError.into_response(),
@@ -95,12 +155,12 @@ async fn enable(
 fn enable_docs(op: TransformOperation) -> TransformOperation {
     op.summary("Enable MFA for the given user.")
         .description(
-            "Generates and returns a recovery code for disabling MFA in case the user loses \
-             access to their MFA authenticator.\n\nAfter enabling MFA, the user is required to \
-             additionally provide a valid TOTP code when logging in.",
+            "Generates and returns a set of single-use recovery codes which can be used to log \
+             in if the user loses access to their MFA authenticator.\n\nAfter enabling MFA, the \
+             user is required to additionally provide a valid TOTP code when logging in.",
         )
-        .add_response_with::<String>(StatusCode::OK, "MFA has been enabled.", |op| {
-            op.example("mfa-recovery-code")
+        .add_response_with::<Vec<MfaRecoveryCode>>(StatusCode::OK, "MFA has been enabled.", |op| {
+            op.example(vec!["mfa-recovery-code".try_into().unwrap()])
         })
         .add_error::<MfaAlreadyEnabledError>()
         .add_error::<MfaNotInitializedError>()
@@ -113,9 +173,10 @@ fn enable_docs(op: TransformOperation) -> TransformOperation {
 async fn disable(
     service: State<Arc<impl MfaFeatureService>>,
     token: ApiToken,
+    audit: ApiAuditContext,
     Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
 ) -> Response {
-    match service.disable(&token.0, user_id.into()).await {
+    match service.disable(&token.0, user_id.into(), &audit.0).await {
         Ok(()) => Json(OkResponse).into_response(),
         Err(MfaDisableError::NotEnabled) => MfaNotEnabledError.into_response(),
         Err(MfaDisableError::NotFound) => UserNotFoundError.into_response(),
@@ -133,6 +194,448 @@ fn disable_docs(op: TransformOperation) -> TransformOperation {
         .with(internal_server_error_docs)
 }
 
+#[derive(Serialize, JsonSchema)]
+struct CountRecoveryCodesResponse {
+    /// Number of recovery codes which have not been used yet
+    remaining: u64,
+}
+
+async fn count_recovery_codes(
+    service: State<Arc<impl MfaFeatureService>>,
+    token: ApiToken,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+) -> Response {
+    match service.count_recovery_codes(&token.0, user_id.into()).await {
+        Ok(remaining) => Json(CountRecoveryCodesResponse { remaining }).into_response(),
+        Err(MfaCountRecoveryCodesError::Auth(err)) => auth_error(err),
+        Err(MfaCountRecoveryCodesError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn count_recovery_codes_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Return the number of unused MFA recovery codes of the given user.")
+        .add_response::<CountRecoveryCodesResponse>(StatusCode::OK, None)
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+async fn regenerate_recovery_codes(
+    service: State<Arc<impl MfaFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+) -> Response {
+    match service
+        .regenerate_recovery_codes(&token.0, user_id.into(), &audit.0)
+        .await
+    {
+        Ok(recovery_codes) => Json(recovery_codes).into_response(),
+        Err(MfaRegenerateRecoveryCodesError::NotEnabled) => MfaNotEnabledError.into_response(),
+        Err(MfaRegenerateRecoveryCodesError::NotFound) => UserNotFoundError.into_response(),
+        Err(MfaRegenerateRecoveryCodesError::Auth(err)) => auth_error(err),
+        Err(MfaRegenerateRecoveryCodesError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn regenerate_recovery_codes_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Generate a new set of MFA recovery codes for the given user.")
+        .description("All previously generated recovery codes are invalidated.")
+        .add_response_with::<Vec<MfaRecoveryCode>>(
+            StatusCode::OK,
+            "New recovery codes have been generated.",
+            |op| op.example(vec!["mfa-recovery-code".try_into().unwrap()]),
+        )
+        .add_error::<MfaNotEnabledError>()
+        .add_error::<UserNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+async fn list_totp_devices(
+    service: State<Arc<impl MfaFeatureService>>,
+    token: ApiToken,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+) -> Response {
+    match service.list_totp_devices(&token.0, user_id.into()).await {
+        Ok(devices) => Json(
+            devices
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<ApiTotpDevice>>(),
+        )
+        .into_response(),
+        Err(MfaListTotpDevicesError::Auth(err)) => auth_error(err),
+        Err(MfaListTotpDevicesError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn list_totp_devices_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Return all TOTP devices of the given user.")
+        .add_response::<Vec<ApiTotpDevice>>(StatusCode::OK, None)
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct CreateTotpDeviceRequest {
+    /// Display name of the new device
+    name: TotpDeviceName,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct CreateTotpDeviceResponse {
+    /// The new (disabled) TOTP device
+    device: ApiTotpDevice,
+    /// The TOTP secret which should be used to configure the authenticator
+    secret: TotpSecretBase32,
+}
+
+async fn create_totp_device(
+    service: State<Arc<impl MfaFeatureService>>,
+    token: ApiToken,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+    Json(CreateTotpDeviceRequest { name }): Json<CreateTotpDeviceRequest>,
+) -> Response {
+    match service
+        .create_totp_device(&token.0, user_id.into(), name)
+        .await
+    {
+        Ok(result) => Json(CreateTotpDeviceResponse {
+            device: result.totp_device.into(),
+            secret: result.setup.secret,
+        })
+        .into_response(),
+        Err(MfaCreateTotpDeviceError::NotFound) => UserNotFoundError.into_response(),
+        Err(MfaCreateTotpDeviceError::Auth(err)) => auth_error(err),
+        Err(MfaCreateTotpDeviceError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn create_totp_device_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Create a new TOTP device for the given user.")
+        .description(
+            "Generates and returns a new TOTP secret, which should be used to configure the \
+             user's authenticator. The device must be confirmed before it can be used to log in.",
+        )
+        .add_response::<CreateTotpDeviceResponse>(
+            StatusCode::OK,
+            "The TOTP device has been created.",
+        )
+        .add_error::<UserNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct TotpDevicePath {
+    user_id: ApiUserIdOrSelf,
+    device_id: TotpDeviceId,
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct ConfirmTotpDeviceRequest {
+    /// TOTP code generated by the authenticator
+    code: TotpCode,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct ConfirmTotpDeviceResponse {
+    /// The new MFA recovery codes (only set if MFA has not been enabled before)
+    recovery_codes: Option<Vec<MfaRecoveryCode>>,
+}
+
+async fn confirm_totp_device(
+    service: State<Arc<impl MfaFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(TotpDevicePath { user_id, device_id }): Path<TotpDevicePath>,
+    Json(ConfirmTotpDeviceRequest { code }): Json<ConfirmTotpDeviceRequest>,
+) -> Response {
+    match service
+        .confirm_totp_device(&token.0, user_id.into(), device_id, code, &audit.0)
+        .await
+    {
+        Ok(recovery_codes) => Json(ConfirmTotpDeviceResponse { recovery_codes }).into_response(),
+        Err(MfaConfirmTotpDeviceError::AlreadyEnabled) => {
+            TotpDeviceAlreadyEnabledError.into_response()
+        }
+        Err(MfaConfirmTotpDeviceError::InvalidCode) => InvalidMfaCodeError.into_response(),
+        Err(MfaConfirmTotpDeviceError::NotFound) => TotpDeviceNotFoundError.into_response(),
+        Err(MfaConfirmTotpDeviceError::Auth(err)) => auth_error(err),
+        Err(MfaConfirmTotpDeviceError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn confirm_totp_device_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Confirm and enable the given TOTP device.")
+        .description(
+            "If the user has not enabled MFA before, MFA is enabled and a set of recovery \
+             codes is returned.",
+        )
+        .add_response::<ConfirmTotpDeviceResponse>(
+            StatusCode::OK,
+            "The TOTP device has been enabled.",
+        )
+        .add_error::<TotpDeviceAlreadyEnabledError>()
+        .add_error::<InvalidMfaCodeError>()
+        .add_error::<TotpDeviceNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct RenameTotpDeviceRequest {
+    /// New display name of the device
+    name: TotpDeviceName,
+}
+
+async fn rename_totp_device(
+    service: State<Arc<impl MfaFeatureService>>,
+    token: ApiToken,
+    Path(TotpDevicePath { user_id, device_id }): Path<TotpDevicePath>,
+    Json(RenameTotpDeviceRequest { name }): Json<RenameTotpDeviceRequest>,
+) -> Response {
+    match service
+        .rename_totp_device(&token.0, user_id.into(), device_id, name)
+        .await
+    {
+        Ok(device) => Json(ApiTotpDevice::from(device)).into_response(),
+        Err(MfaRenameTotpDeviceError::NotFound) => TotpDeviceNotFoundError.into_response(),
+        Err(MfaRenameTotpDeviceError::Auth(err)) => auth_error(err),
+        Err(MfaRenameTotpDeviceError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn rename_totp_device_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Rename the given TOTP device.")
+        .add_response::<ApiTotpDevice>(StatusCode::OK, "The TOTP device has been renamed.")
+        .add_error::<TotpDeviceNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+async fn delete_totp_device(
+    service: State<Arc<impl MfaFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(TotpDevicePath { user_id, device_id }): Path<TotpDevicePath>,
+) -> Response {
+    match service
+        .delete_totp_device(&token.0, user_id.into(), device_id, &audit.0)
+        .await
+    {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(MfaDeleteTotpDeviceError::NotFound) => TotpDeviceNotFoundError.into_response(),
+        Err(MfaDeleteTotpDeviceError::Auth(err)) => auth_error(err),
+        Err(MfaDeleteTotpDeviceError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn delete_totp_device_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Delete the given TOTP device.")
+        .description("MFA is disabled if this was the last remaining MFA device of the user.")
+        .add_response::<OkResponse>(StatusCode::OK, "The TOTP device has been deleted.")
+        .add_error::<TotpDeviceNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+async fn list_webauthn_devices(
+    service: State<Arc<impl MfaFeatureService>>,
+    token: ApiToken,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+) -> Response {
+    match service
+        .list_webauthn_devices(&token.0, user_id.into())
+        .await
+    {
+        Ok(devices) => Json(
+            devices
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<ApiWebauthnDevice>>(),
+        )
+        .into_response(),
+        Err(MfaListWebauthnDevicesError::Auth(err)) => auth_error(err),
+        Err(MfaListWebauthnDevicesError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn list_webauthn_devices_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Return all WebAuthn devices of the given user.")
+        .add_response::<Vec<ApiWebauthnDevice>>(StatusCode::OK, None)
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+async fn start_webauthn_registration(
+    service: State<Arc<impl MfaFeatureService>>,
+    token: ApiToken,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+) -> Response {
+    match service
+        .start_webauthn_registration(&token.0, user_id.into())
+        .await
+    {
+        Ok(options) => Json(ApiWebauthnRegistrationOptions::from(options)).into_response(),
+        Err(MfaStartWebauthnRegistrationError::NotFound) => UserNotFoundError.into_response(),
+        Err(MfaStartWebauthnRegistrationError::Auth(err)) => auth_error(err),
+        Err(MfaStartWebauthnRegistrationError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn start_webauthn_registration_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Start the registration of a new WebAuthn device for the given user.")
+        .description(
+            "Returns the options that should be passed to `navigator.credentials.create()`.",
+        )
+        .add_response::<ApiWebauthnRegistrationOptions>(
+            StatusCode::OK,
+            "WebAuthn registration has been started.",
+        )
+        .add_error::<UserNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct FinishWebauthnRegistrationRequest {
+    /// Display name of the new device
+    name: WebauthnDeviceName,
+    #[serde(flatten)]
+    registration: ApiWebauthnRegistration,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct FinishWebauthnRegistrationResponse {
+    /// The new WebAuthn device
+    device: ApiWebauthnDevice,
+    /// The new MFA recovery codes (only set if MFA has not been enabled before)
+    recovery_codes: Option<Vec<MfaRecoveryCode>>,
+}
+
+async fn finish_webauthn_registration(
+    service: State<Arc<impl MfaFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+    Json(FinishWebauthnRegistrationRequest { name, registration }): Json<
+        FinishWebauthnRegistrationRequest,
+    >,
+) -> Response {
+    match service
+        .finish_webauthn_registration(
+            &token.0,
+            user_id.into(),
+            name,
+            registration.into(),
+            &audit.0,
+        )
+        .await
+    {
+        Ok(result) => Json(FinishWebauthnRegistrationResponse {
+            device: result.webauthn_device.into(),
+            recovery_codes: result.recovery_codes,
+        })
+        .into_response(),
+        Err(MfaFinishWebauthnRegistrationError::NotStarted) => {
+            WebauthnRegistrationNotStartedError.into_response()
+        }
+        Err(MfaFinishWebauthnRegistrationError::InvalidRegistration) => {
+            InvalidWebauthnRegistrationError.into_response()
+        }
+        Err(MfaFinishWebauthnRegistrationError::AlreadyRegistered) => {
+            WebauthnCredentialAlreadyRegisteredError.into_response()
+        }
+        Err(MfaFinishWebauthnRegistrationError::NotFound) => UserNotFoundError.into_response(),
+        Err(MfaFinishWebauthnRegistrationError::Auth(err)) => auth_error(err),
+        Err(MfaFinishWebauthnRegistrationError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn finish_webauthn_registration_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Finish the registration of a new WebAuthn device for the given user.")
+        .description(
+            "Expects the response of the authenticator to `navigator.credentials.create()`. If \
+             the user has not enabled MFA before, MFA is enabled and a set of recovery \
+             codes is returned.",
+        )
+        .add_response::<FinishWebauthnRegistrationResponse>(
+            StatusCode::OK,
+            "The WebAuthn device has been registered.",
+        )
+        .add_error::<WebauthnRegistrationNotStartedError>()
+        .add_error::<InvalidWebauthnRegistrationError>()
+        .add_error::<WebauthnCredentialAlreadyRegisteredError>()
+        .add_error::<UserNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct DeleteWebauthnDevicePath {
+    user_id: ApiUserIdOrSelf,
+    device_id: WebauthnDeviceId,
+}
+
+async fn delete_webauthn_device(
+    service: State<Arc<impl MfaFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(DeleteWebauthnDevicePath { user_id, device_id }): Path<DeleteWebauthnDevicePath>,
+) -> Response {
+    match service
+        .delete_webauthn_device(&token.0, user_id.into(), device_id, &audit.0)
+        .await
+    {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(MfaDeleteWebauthnDeviceError::NotFound) => WebauthnDeviceNotFoundError.into_response(),
+        Err(MfaDeleteWebauthnDeviceError::Auth(err)) => auth_error(err),
+        Err(MfaDeleteWebauthnDeviceError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn delete_webauthn_device_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Delete the given WebAuthn device.")
+        .description("MFA is disabled if this was the last remaining MFA device of the user.")
+        .add_response::<OkResponse>(StatusCode::OK, "The WebAuthn device has been deleted.")
+        .add_error::<WebauthnDeviceNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct CreateWebauthnAssertionOptionsRequest {
+    /// The name or email address of the user who wants to log in
+    name_or_email: UserNameOrEmailAddress,
+}
+
+async fn create_webauthn_assertion_options(
+    service: State<Arc<impl MfaFeatureService>>,
+    Json(CreateWebauthnAssertionOptionsRequest { name_or_email }): Json<
+        CreateWebauthnAssertionOptionsRequest,
+    >,
+) -> Response {
+    match service
+        .create_webauthn_assertion_options(&name_or_email)
+        .await
+    {
+        Ok(options) => Json(ApiWebauthnAssertionOptions::from(options)).into_response(),
+        Err(err) => internal_server_error(err),
+    }
+}
+
+fn create_webauthn_assertion_options_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Create a new WebAuthn challenge for logging in.")
+        .description(
+            "Returns the options that should be passed to `navigator.credentials.get()`. The \
+             response of the authenticator can then be used to create a new session for the \
+             given user. Every challenge can only be used once.",
+        )
+        .add_response::<ApiWebauthnAssertionOptions>(StatusCode::OK, None)
+        .with(internal_server_error_docs)
+}
+
 error_code! {
     /// The user has already enabled MFA.
     MfaAlreadyEnabledError(CONFLICT, "MFA already enabled");
@@ -142,4 +645,16 @@ error_code! {
     pub InvalidMfaCodeError(PRECONDITION_FAILED, "Invalid code");
     /// The user has not enabled MFA.
     MfaNotEnabledError(PRECONDITION_FAILED, "MFA not enabled");
+    /// The TOTP device does not exist.
+    TotpDeviceNotFoundError(NOT_FOUND, "TOTP device not found");
+    /// The TOTP device has already been enabled.
+    TotpDeviceAlreadyEnabledError(CONFLICT, "TOTP device already enabled");
+    /// The WebAuthn registration has not been started or has expired.
+    WebauthnRegistrationNotStartedError(PRECONDITION_FAILED, "WebAuthn registration not started");
+    /// The WebAuthn registration response is invalid.
+    InvalidWebauthnRegistrationError(PRECONDITION_FAILED, "Invalid WebAuthn registration");
+    /// The WebAuthn credential has already been registered.
+    WebauthnCredentialAlreadyRegisteredError(CONFLICT, "WebAuthn credential already registered");
+    /// The WebAuthn device does not exist.
+    WebauthnDeviceNotFoundError(NOT_FOUND, "WebAuthn device not found");
 }
@@ -1,8 +1,12 @@
+pub mod audit;
 pub mod config;
 pub mod contact;
 pub mod health;
 pub mod internal;
 pub mod mfa;
 pub mod oauth2;
+pub mod oauth2_server;
+pub mod personal_access_token;
+pub mod role;
 pub mod session;
 pub mod user;
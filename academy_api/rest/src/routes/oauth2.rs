@@ -2,11 +2,12 @@ use std::sync::Arc;
 
 use academy_core_oauth2_contracts::{
     OAuth2CreateLinkError, OAuth2CreateSessionError, OAuth2CreateSessionResponse,
-    OAuth2DeleteLinkError, OAuth2FeatureService, OAuth2ListLinksError,
+    OAuth2DeleteLinkError, OAuth2FeatureService, OAuth2ListLinksError, OAuth2StartLoginError,
 };
 use academy_models::{
-    oauth2::{OAuth2LinkId, OAuth2RegistrationToken},
+    oauth2::{OAuth2LinkId, OAuth2ProviderId, OAuth2RegistrationToken},
     session::DeviceName,
+    url::Url,
 };
 use aide::{
     axum::{routing, ApiRouter},
@@ -16,7 +17,7 @@ use axum::{
     extract::{Path, State},
     http::StatusCode,
     response::{IntoResponse, Response},
-    Json,
+    Extension, Json,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -26,9 +27,12 @@ use crate::{
     docs::TransformOperationExt,
     error_code,
     errors::{auth_error, auth_error_docs, internal_server_error, internal_server_error_docs},
-    extractors::{auth::ApiToken, user_agent::UserAgent},
+    extractors::{audit::ApiAuditContext, auth::ApiToken, user_agent::UserAgent},
+    middlewares::client_ip::ClientIp,
     models::{
-        oauth2::{ApiOAuth2Link, ApiOAuth2Login, ApiOAuth2ProviderSummary},
+        oauth2::{
+            ApiOAuth2AuthorizationRequest, ApiOAuth2Link, ApiOAuth2Login, ApiOAuth2ProviderSummary,
+        },
         session::ApiLogin,
         user::{ApiUserIdOrSelf, PathUserIdOrSelf},
         OkResponse,
@@ -43,6 +47,10 @@ pub fn router(service: Arc<impl OAuth2FeatureService>) -> ApiRouter<()> {
             "/auth/oauth/providers",
             routing::get_with(list_providers, list_providers_docs),
         )
+        .api_route(
+            "/auth/oauth/authorize",
+            routing::post_with(start_login, start_login_docs),
+        )
         .api_route(
             "/auth/oauth/links/:user_id",
             routing::get_with(list_links, list_links_docs).post_with(create_link, create_link_docs),
@@ -75,6 +83,45 @@ fn list_providers_docs(op: TransformOperation) -> TransformOperation {
         .add_response::<Vec<ApiOAuth2ProviderSummary>>(StatusCode::OK, None)
 }
 
+#[derive(Deserialize, JsonSchema)]
+struct StartLoginRequest {
+    /// OAuth2 provider ID
+    provider_id: OAuth2ProviderId,
+    /// Redirect URI the OAuth2 provider should redirect to after
+    /// authentication
+    redirect_uri: Url,
+}
+
+async fn start_login(
+    service: State<Arc<impl OAuth2FeatureService>>,
+    Json(StartLoginRequest {
+        provider_id,
+        redirect_uri,
+    }): Json<StartLoginRequest>,
+) -> Response {
+    match service.start_login(provider_id, redirect_uri).await {
+        Ok(request) => Json(ApiOAuth2AuthorizationRequest::from(request)).into_response(),
+        Err(OAuth2StartLoginError::InvalidProvider) => ProviderNotFoundError.into_response(),
+        Err(OAuth2StartLoginError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn start_login_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Start a new OAuth2 login.")
+        .description(
+            "Returns the authorize URL of the OAuth2 provider, which includes a new `state` and \
+             PKCE code challenge. The `state` returned by the provider must be passed to the \
+             endpoints which create an OAuth2 link or session, together with the `binding` \
+             returned by this endpoint. Each `state` can only be used once.",
+        )
+        .add_response::<ApiOAuth2AuthorizationRequest>(
+            StatusCode::OK,
+            "OAuth2 login has been started.",
+        )
+        .add_error::<ProviderNotFoundError>()
+        .with(internal_server_error_docs)
+}
+
 async fn list_links(
     service: State<Arc<impl OAuth2FeatureService>>,
     token: ApiToken,
@@ -105,15 +152,17 @@ fn list_links_docs(op: TransformOperation) -> TransformOperation {
 async fn create_link(
     service: State<Arc<impl OAuth2FeatureService>>,
     token: ApiToken,
+    audit: ApiAuditContext,
     Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
     Json(login): Json<ApiOAuth2Login>,
 ) -> Response {
     match service
-        .create_link(&token.0, user_id.into(), login.into())
+        .create_link(&token.0, user_id.into(), login.into(), &audit.0)
         .await
     {
         Ok(link) => Json(ApiOAuth2Link::from(link)).into_response(),
         Err(OAuth2CreateLinkError::InvalidProvider) => ProviderNotFoundError.into_response(),
+        Err(OAuth2CreateLinkError::InvalidState) => InvalidStateError.into_response(),
         Err(OAuth2CreateLinkError::InvalidCode) => InvalidCodeError.into_response(),
         Err(OAuth2CreateLinkError::RemoteAlreadyLinked) => RemoteAlreadyLinkedError.into_response(),
         Err(OAuth2CreateLinkError::NotFound) => UserNotFoundError.into_response(),
@@ -126,6 +175,7 @@ fn create_link_docs(op: TransformOperation) -> TransformOperation {
     op.summary("Create a new OAuth2 link for the given user.")
         .add_response::<ApiOAuth2Link>(StatusCode::OK, "OAuth2 link has been created.")
         .add_error::<ProviderNotFoundError>()
+        .add_error::<InvalidStateError>()
         .add_error::<InvalidCodeError>()
         .add_error::<RemoteAlreadyLinkedError>()
         .add_error::<UserNotFoundError>()
@@ -142,9 +192,13 @@ struct DeleteLinkPath {
 async fn delete_link(
     service: State<Arc<impl OAuth2FeatureService>>,
     token: ApiToken,
+    audit: ApiAuditContext,
     Path(DeleteLinkPath { user_id, link_id }): Path<DeleteLinkPath>,
 ) -> Response {
-    match service.delete_link(&token.0, user_id.into(), link_id).await {
+    match service
+        .delete_link(&token.0, user_id.into(), link_id, &audit.0)
+        .await
+    {
         Ok(()) => Json(OkResponse).into_response(),
         Err(OAuth2DeleteLinkError::NotFound) => LinkNotFoundError.into_response(),
         Err(OAuth2DeleteLinkError::CannotRemoveLink) => {
@@ -180,12 +234,14 @@ struct CreateSessionRegistrationTokenResponse {
 async fn create_session(
     service: State<Arc<impl OAuth2FeatureService>>,
     user_agent: UserAgent,
+    Extension(ClientIp(ip)): Extension<ClientIp>,
     Json(login): Json<ApiOAuth2Login>,
 ) -> Response {
     match service
         .create_session(
             login.into(),
             user_agent.0.map(DeviceName::from_string_truncated),
+            Some(ip),
         )
         .await
     {
@@ -197,6 +253,7 @@ async fn create_session(
             Json(CreateSessionRegistrationTokenResponse { register_token }).into_response()
         }
         Err(OAuth2CreateSessionError::InvalidProvider) => ProviderNotFoundError.into_response(),
+        Err(OAuth2CreateSessionError::InvalidState) => InvalidStateError.into_response(),
         Err(OAuth2CreateSessionError::InvalidCode) => InvalidCodeError.into_response(),
         Err(OAuth2CreateSessionError::UserDisabled) => UserDisabledError.into_response(),
         Err(OAuth2CreateSessionError::Other(err)) => internal_server_error(err),
@@ -218,6 +275,7 @@ fn create_session_docs(op: TransformOperation) -> TransformOperation {
             "A registration token has been generated.",
         )
         .add_error::<ProviderNotFoundError>()
+        .add_error::<InvalidStateError>()
         .add_error::<InvalidCodeError>()
         .add_error::<UserDisabledError>()
         .with(internal_server_error_docs)
@@ -226,6 +284,9 @@ fn create_session_docs(op: TransformOperation) -> TransformOperation {
 error_code! {
     /// The OAuth2 provider does not exist.
     ProviderNotFoundError(NOT_FOUND, "Provider not found");
+    /// The state is invalid, has expired, has already been used or does not
+    /// match the binding.
+    InvalidStateError(UNAUTHORIZED, "Invalid state");
     /// The authorization code is invalid.
     InvalidCodeError(UNAUTHORIZED, "Invalid code");
     /// The remote user has already been linked to another account.
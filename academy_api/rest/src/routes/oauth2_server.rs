@@ -0,0 +1,551 @@
+use std::sync::Arc;
+
+use academy_core_oauth2_server_contracts::{
+    OAuth2ClientCreateRequest, OAuth2CreateClientResponse, OAuth2ServerAuthorizeError,
+    OAuth2ServerCreateClientError, OAuth2ServerDeleteClientError, OAuth2ServerFeatureService,
+    OAuth2ServerIntrospectError, OAuth2ServerListClientsError, OAuth2ServerRevokeError,
+    OAuth2ServerTokenError, OAuth2ServerUserInfoError,
+};
+use academy_models::{
+    oauth2_server::{
+        OAuth2AuthorizeRequest, OAuth2ClientCredentials, OAuth2ClientId, OAuth2ClientName,
+        OAuth2ClientNonce, OAuth2ClientPkceCodeVerifier, OAuth2ClientSecret, OAuth2ClientState,
+        OAuth2PkceCodeChallenge, OAuth2ServerAccessToken, OAuth2ServerAuthorizationCode,
+        OAuth2ServerRefreshToken, OAuth2ServerUserInfo, OAuth2TokenRequest,
+    },
+    url::Url,
+};
+use aide::{
+    axum::{routing, ApiRouter},
+    transform::TransformOperation,
+};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Form, Json,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    docs::TransformOperationExt,
+    error_code,
+    errors::{auth_error, auth_error_docs, internal_server_error, internal_server_error_docs},
+    extractors::{audit::ApiAuditContext, auth::ApiToken, basic_auth::BasicAuth},
+    models::{
+        oauth2_server::{
+            ApiOAuth2Client, ApiOAuth2ClientWithSecret, ApiOAuth2Consent, ApiOAuth2Introspection,
+            ApiOAuth2TokenResponse,
+        },
+        OkResponse,
+    },
+};
+
+pub const TAG: &str = "OAuth2 Server";
+
+pub fn router(service: Arc<impl OAuth2ServerFeatureService>) -> ApiRouter<()> {
+    ApiRouter::new()
+        .api_route(
+            "/auth/oauth2/clients",
+            routing::get_with(list_clients, list_clients_docs)
+                .post_with(create_client, create_client_docs),
+        )
+        .api_route(
+            "/auth/oauth2/clients/:client_id",
+            routing::delete_with(delete_client, delete_client_docs),
+        )
+        .api_route(
+            "/auth/oauth2/authorize",
+            routing::get_with(get_consent, get_consent_docs).post_with(authorize, authorize_docs),
+        )
+        .api_route("/auth/oauth2/token", routing::post_with(token, token_docs))
+        .api_route(
+            "/auth/oauth2/revoke",
+            routing::post_with(revoke, revoke_docs),
+        )
+        .api_route(
+            "/auth/oauth2/introspect",
+            routing::post_with(introspect, introspect_docs),
+        )
+        .api_route(
+            "/auth/oauth2/userinfo",
+            routing::get_with(userinfo, userinfo_docs),
+        )
+        .with_state(service)
+        .with_path_items(|op| op.tag(TAG))
+}
+
+async fn list_clients(
+    service: State<Arc<impl OAuth2ServerFeatureService>>,
+    token: ApiToken,
+) -> Response {
+    match service.list_clients(&token.0).await {
+        Ok(clients) => Json(
+            clients
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<ApiOAuth2Client>>(),
+        )
+        .into_response(),
+        Err(OAuth2ServerListClientsError::Auth(err)) => auth_error(err),
+        Err(OAuth2ServerListClientsError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn list_clients_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Return all registered OAuth2 clients.")
+        .add_response::<Vec<ApiOAuth2Client>>(StatusCode::OK, None)
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct CreateClientRequest {
+    /// Display name of the client application
+    name: OAuth2ClientName,
+    /// Redirect URIs the client is allowed to use
+    redirect_uris: Vec<Url>,
+    /// Whether the client can keep a secret (e.g. a server-side web
+    /// application). Public clients (e.g. native or single-page apps) must
+    /// use PKCE only.
+    confidential: bool,
+}
+
+async fn create_client(
+    service: State<Arc<impl OAuth2ServerFeatureService>>,
+    token: ApiToken,
+    Json(CreateClientRequest {
+        name,
+        redirect_uris,
+        confidential,
+    }): Json<CreateClientRequest>,
+) -> Response {
+    match service
+        .create_client(
+            &token.0,
+            OAuth2ClientCreateRequest {
+                name,
+                redirect_uris,
+                confidential,
+            },
+        )
+        .await
+    {
+        Ok(OAuth2CreateClientResponse { client, secret }) => Json(ApiOAuth2ClientWithSecret {
+            client: client.into(),
+            secret,
+        })
+        .into_response(),
+        Err(OAuth2ServerCreateClientError::NoRedirectUri) => NoRedirectUriError.into_response(),
+        Err(OAuth2ServerCreateClientError::Auth(err)) => auth_error(err),
+        Err(OAuth2ServerCreateClientError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn create_client_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Register a new OAuth2 client.")
+        .add_response::<ApiOAuth2ClientWithSecret>(
+            StatusCode::OK,
+            "The client has been registered.",
+        )
+        .add_error::<NoRedirectUriError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct ClientIdPath {
+    client_id: OAuth2ClientId,
+}
+
+async fn delete_client(
+    service: State<Arc<impl OAuth2ServerFeatureService>>,
+    token: ApiToken,
+    Path(ClientIdPath { client_id }): Path<ClientIdPath>,
+) -> Response {
+    match service.delete_client(&token.0, client_id).await {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(OAuth2ServerDeleteClientError::NotFound) => ClientNotFoundError.into_response(),
+        Err(OAuth2ServerDeleteClientError::Auth(err)) => auth_error(err),
+        Err(OAuth2ServerDeleteClientError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn delete_client_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Delete an OAuth2 client.")
+        .description("All grants of the client are revoked.")
+        .add_response::<OkResponse>(StatusCode::OK, "The client has been deleted.")
+        .add_error::<ClientNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum ResponseType {
+    Code,
+}
+
+#[derive(Deserialize, JsonSchema)]
+enum CodeChallengeMethod {
+    S256,
+}
+
+/// The parameters of an authorization request, as sent by the client
+#[derive(Deserialize, JsonSchema)]
+struct AuthorizeRequest {
+    /// Must be `code`
+    #[expect(dead_code, reason = "only the authorization code flow is supported")]
+    response_type: ResponseType,
+    /// ID of the client requesting access
+    client_id: OAuth2ClientId,
+    /// One of the redirect URIs registered for the client
+    redirect_uri: Url,
+    /// Space-delimited list of requested scopes
+    #[serde(default)]
+    scope: String,
+    /// Opaque value that is passed back to the client
+    state: Option<OAuth2ClientState>,
+    /// PKCE code challenge
+    code_challenge: OAuth2PkceCodeChallenge,
+    /// Must be `S256`
+    #[expect(dead_code, reason = "only the S256 method is supported")]
+    code_challenge_method: CodeChallengeMethod,
+    /// Value to include in the ID token
+    nonce: Option<OAuth2ClientNonce>,
+}
+
+impl AuthorizeRequest {
+    fn into_request(self) -> Result<OAuth2AuthorizeRequest, InvalidScopeError> {
+        Ok(OAuth2AuthorizeRequest {
+            client_id: self.client_id,
+            redirect_uri: self.redirect_uri,
+            scopes: self.scope.parse().map_err(|_| InvalidScopeError)?,
+            state: self.state,
+            code_challenge: self.code_challenge,
+            nonce: self.nonce,
+        })
+    }
+}
+
+async fn get_consent(
+    service: State<Arc<impl OAuth2ServerFeatureService>>,
+    token: ApiToken,
+    Query(request): Query<AuthorizeRequest>,
+) -> Response {
+    let request = match request.into_request() {
+        Ok(request) => request,
+        Err(err) => return err.into_response(),
+    };
+
+    match service.get_consent(&token.0, request).await {
+        Ok(consent) => Json(ApiOAuth2Consent::from(consent)).into_response(),
+        Err(err) => authorize_error(err),
+    }
+}
+
+fn get_consent_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Validate an authorization request and return the data for the consent screen.")
+        .description(
+            "The frontend should call this endpoint with the query parameters the client has \
+             sent, display the consent screen and then submit the same parameters to `POST \
+             /auth/oauth2/authorize`.",
+        )
+        .add_response::<ApiOAuth2Consent>(StatusCode::OK, None)
+        .with(authorize_error_docs)
+}
+
+#[derive(Serialize, JsonSchema)]
+struct AuthorizeResponse {
+    /// The URI to redirect the user's browser to. Includes the authorization
+    /// code and the state.
+    redirect_uri: Url,
+}
+
+async fn authorize(
+    service: State<Arc<impl OAuth2ServerFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Json(request): Json<AuthorizeRequest>,
+) -> Response {
+    let request = match request.into_request() {
+        Ok(request) => request,
+        Err(err) => return err.into_response(),
+    };
+
+    match service.authorize(&token.0, request, &audit.0).await {
+        Ok(redirect_uri) => Json(AuthorizeResponse { redirect_uri }).into_response(),
+        Err(err) => authorize_error(err),
+    }
+}
+
+fn authorize_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Grant the client access to the authenticated user's account.")
+        .add_response::<AuthorizeResponse>(StatusCode::OK, "The client has been authorized.")
+        .with(authorize_error_docs)
+}
+
+fn authorize_error(err: OAuth2ServerAuthorizeError) -> Response {
+    match err {
+        OAuth2ServerAuthorizeError::InvalidClient => ClientNotFoundError.into_response(),
+        OAuth2ServerAuthorizeError::InvalidRedirectUri => InvalidRedirectUriError.into_response(),
+        OAuth2ServerAuthorizeError::Auth(err) => auth_error(err),
+        OAuth2ServerAuthorizeError::Other(err) => internal_server_error(err),
+    }
+}
+
+fn authorize_error_docs(op: TransformOperation) -> TransformOperation {
+    op.add_error::<InvalidScopeError>()
+        .add_error::<ClientNotFoundError>()
+        .add_error::<InvalidRedirectUriError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+/// Client credentials, which may also be sent using HTTP Basic
+/// authentication
+#[derive(Deserialize, JsonSchema)]
+struct ClientCredentials {
+    client_id: Option<String>,
+    client_secret: Option<String>,
+}
+
+impl ClientCredentials {
+    fn resolve(self, basic_auth: BasicAuth) -> Result<OAuth2ClientCredentials, OAuth2ErrorCode> {
+        let (client_id, client_secret) = match basic_auth.0 {
+            Some((client_id, client_secret)) => (client_id, Some(client_secret)),
+            None => (
+                self.client_id.ok_or(OAuth2ErrorCode::InvalidClient)?,
+                self.client_secret,
+            ),
+        };
+
+        Ok(OAuth2ClientCredentials {
+            client_id: client_id
+                .parse::<uuid::Uuid>()
+                .map_err(|_| OAuth2ErrorCode::InvalidClient)?
+                .into(),
+            client_secret: client_secret
+                .filter(|secret| !secret.is_empty())
+                .map(OAuth2ClientSecret::try_new)
+                .transpose()
+                .map_err(|_| OAuth2ErrorCode::InvalidClient)?,
+        })
+    }
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum GrantType {
+    AuthorizationCode,
+    RefreshToken,
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct TokenRequest {
+    grant_type: GrantType,
+    /// Authorization code (`authorization_code` grant only)
+    code: Option<OAuth2ServerAuthorizationCode>,
+    /// Redirect URI used in the authorization request (`authorization_code`
+    /// grant only)
+    redirect_uri: Option<Url>,
+    /// PKCE code verifier (`authorization_code` grant only)
+    code_verifier: Option<OAuth2ClientPkceCodeVerifier>,
+    /// Refresh token (`refresh_token` grant only)
+    refresh_token: Option<OAuth2ServerRefreshToken>,
+    #[serde(flatten)]
+    credentials: ClientCredentials,
+}
+
+async fn token(
+    service: State<Arc<impl OAuth2ServerFeatureService>>,
+    basic_auth: BasicAuth,
+    Form(request): Form<TokenRequest>,
+) -> Response {
+    let credentials = match request.credentials.resolve(basic_auth) {
+        Ok(credentials) => credentials,
+        Err(err) => return oauth2_error(err),
+    };
+
+    let token_request = match request.grant_type {
+        GrantType::AuthorizationCode => {
+            match (request.code, request.redirect_uri, request.code_verifier) {
+                (Some(code), Some(redirect_uri), Some(code_verifier)) => {
+                    OAuth2TokenRequest::AuthorizationCode {
+                        code,
+                        redirect_uri,
+                        code_verifier,
+                    }
+                }
+                _ => return oauth2_error(OAuth2ErrorCode::InvalidRequest),
+            }
+        }
+        GrantType::RefreshToken => match request.refresh_token {
+            Some(refresh_token) => OAuth2TokenRequest::RefreshToken { refresh_token },
+            None => return oauth2_error(OAuth2ErrorCode::InvalidRequest),
+        },
+    };
+
+    match service.token(credentials, token_request).await {
+        Ok(response) => Json(ApiOAuth2TokenResponse::from(response)).into_response(),
+        Err(OAuth2ServerTokenError::InvalidClient) => oauth2_error(OAuth2ErrorCode::InvalidClient),
+        Err(OAuth2ServerTokenError::InvalidGrant) => oauth2_error(OAuth2ErrorCode::InvalidGrant),
+        Err(OAuth2ServerTokenError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn token_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Exchange an authorization code or a refresh token for new tokens.")
+        .description(
+            "The request body must be `application/x-www-form-urlencoded`. Refresh tokens are \
+             rotated, i.e. each refresh token can only be used once.",
+        )
+        .add_response::<ApiOAuth2TokenResponse>(StatusCode::OK, "New tokens have been issued.")
+        .with(oauth2_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct TokenForm {
+    /// An access token or a refresh token
+    token: String,
+    #[serde(flatten)]
+    credentials: ClientCredentials,
+}
+
+async fn revoke(
+    service: State<Arc<impl OAuth2ServerFeatureService>>,
+    basic_auth: BasicAuth,
+    audit: ApiAuditContext,
+    Form(TokenForm { token, credentials }): Form<TokenForm>,
+) -> Response {
+    let credentials = match credentials.resolve(basic_auth) {
+        Ok(credentials) => credentials,
+        Err(err) => return oauth2_error(err),
+    };
+
+    match service.revoke(credentials, token, &audit.0).await {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(OAuth2ServerRevokeError::InvalidClient) => oauth2_error(OAuth2ErrorCode::InvalidClient),
+        Err(OAuth2ServerRevokeError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn revoke_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Revoke the grant of an access token or a refresh token.")
+        .description(
+            "The request body must be `application/x-www-form-urlencoded`. Invalid tokens and \
+             tokens issued to other clients are ignored.",
+        )
+        .add_response::<OkResponse>(StatusCode::OK, "The token has been revoked.")
+        .with(oauth2_error_docs)
+        .with(internal_server_error_docs)
+}
+
+async fn introspect(
+    service: State<Arc<impl OAuth2ServerFeatureService>>,
+    basic_auth: BasicAuth,
+    Form(TokenForm { token, credentials }): Form<TokenForm>,
+) -> Response {
+    let credentials = match credentials.resolve(basic_auth) {
+        Ok(credentials) => credentials,
+        Err(err) => return oauth2_error(err),
+    };
+
+    match service.introspect(credentials, token).await {
+        Ok(info) => Json(ApiOAuth2Introspection::from(info)).into_response(),
+        Err(OAuth2ServerIntrospectError::InvalidClient) => {
+            oauth2_error(OAuth2ErrorCode::InvalidClient)
+        }
+        Err(OAuth2ServerIntrospectError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn introspect_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Return information about an access token or a refresh token.")
+        .description(
+            "The request body must be `application/x-www-form-urlencoded`. Tokens issued to \
+             other clients are reported as inactive.",
+        )
+        .add_response::<ApiOAuth2Introspection>(StatusCode::OK, None)
+        .with(oauth2_error_docs)
+        .with(internal_server_error_docs)
+}
+
+async fn userinfo(
+    service: State<Arc<impl OAuth2ServerFeatureService>>,
+    token: ApiToken<OAuth2ServerAccessToken>,
+) -> Response {
+    match service.userinfo(&token.0).await {
+        Ok(userinfo) => Json(userinfo).into_response(),
+        Err(OAuth2ServerUserInfoError::InvalidToken) => InvalidOAuth2TokenError.into_response(),
+        Err(OAuth2ServerUserInfoError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn userinfo_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Return the OpenID Connect claims about the user who granted the access token.")
+        .description(
+            "`preferred_username` and `name` require the `profile` scope, `email` and \
+             `email_verified` require the `email` scope.",
+        )
+        .add_response::<OAuth2ServerUserInfo>(StatusCode::OK, None)
+        .add_error::<InvalidOAuth2TokenError>()
+        .with(internal_server_error_docs)
+}
+
+/// Error response of the token, revocation and introspection endpoints as
+/// defined in RFC 6749
+#[derive(Serialize, JsonSchema)]
+struct OAuth2ErrorResponse {
+    error: OAuth2ErrorCode,
+}
+
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[expect(
+    clippy::enum_variant_names,
+    reason = "the variants are named after the error codes of RFC 6749"
+)]
+enum OAuth2ErrorCode {
+    /// The request is missing a required parameter.
+    InvalidRequest,
+    /// The client authentication failed.
+    InvalidClient,
+    /// The authorization code or refresh token is invalid, has expired or has
+    /// been issued to another client.
+    InvalidGrant,
+}
+
+fn oauth2_error(error: OAuth2ErrorCode) -> Response {
+    let status = match error {
+        OAuth2ErrorCode::InvalidClient => StatusCode::UNAUTHORIZED,
+        OAuth2ErrorCode::InvalidRequest | OAuth2ErrorCode::InvalidGrant => StatusCode::BAD_REQUEST,
+    };
+    (status, Json(OAuth2ErrorResponse { error })).into_response()
+}
+
+fn oauth2_error_docs(op: TransformOperation) -> TransformOperation {
+    op.add_response::<OAuth2ErrorResponse>(
+        StatusCode::BAD_REQUEST,
+        "The request is invalid or the grant is invalid.",
+    )
+    .add_response::<OAuth2ErrorResponse>(
+        StatusCode::UNAUTHORIZED,
+        "The client authentication failed.",
+    )
+}
+
+error_code! {
+    /// The client must have at least one redirect uri.
+    NoRedirectUriError(UNPROCESSABLE_ENTITY, "No redirect uri");
+    /// The OAuth2 client does not exist.
+    ClientNotFoundError(NOT_FOUND, "Client not found");
+    /// The redirect uri has not been registered for this client.
+    InvalidRedirectUriError(BAD_REQUEST, "Invalid redirect uri");
+    /// One of the requested scopes is not supported.
+    InvalidScopeError(BAD_REQUEST, "Invalid scope");
+    /// The OAuth2 access token is invalid, has expired or has been revoked.
+    InvalidOAuth2TokenError(UNAUTHORIZED, "Invalid token");
+}
@@ -0,0 +1,194 @@
+use std::{collections::BTreeSet, sync::Arc};
+
+use academy_core_personal_access_token_contracts::{
+    PersonalAccessTokenCreateError, PersonalAccessTokenCreateRequest,
+    PersonalAccessTokenCreateResponse, PersonalAccessTokenDeleteError,
+    PersonalAccessTokenFeatureService, PersonalAccessTokenListError,
+};
+use academy_models::personal_access_token::{
+    PersonalAccessTokenId, PersonalAccessTokenName, PersonalAccessTokenScope,
+};
+use aide::{
+    axum::{routing, ApiRouter},
+    transform::TransformOperation,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::DateTime;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::user::UserNotFoundError;
+use crate::{
+    docs::TransformOperationExt,
+    error_code,
+    errors::{auth_error, auth_error_docs, internal_server_error, internal_server_error_docs},
+    extractors::{audit::ApiAuditContext, auth::ApiToken},
+    models::{
+        personal_access_token::{ApiPersonalAccessToken, ApiPersonalAccessTokenWithToken},
+        user::{ApiUserIdOrSelf, PathUserIdOrSelf},
+        OkResponse,
+    },
+};
+
+pub const TAG: &str = "Personal Access Tokens";
+
+pub fn router(service: Arc<impl PersonalAccessTokenFeatureService>) -> ApiRouter<()> {
+    ApiRouter::new()
+        .api_route(
+            "/auth/users/:user_id/personal_access_tokens",
+            routing::get_with(list_tokens, list_tokens_docs)
+                .post_with(create_token, create_token_docs),
+        )
+        .api_route(
+            "/auth/users/:user_id/personal_access_tokens/:token_id",
+            routing::delete_with(delete_token, delete_token_docs),
+        )
+        .with_state(service)
+        .with_path_items(|op| op.tag(TAG))
+}
+
+async fn list_tokens(
+    service: State<Arc<impl PersonalAccessTokenFeatureService>>,
+    token: ApiToken,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+) -> Response {
+    match service.list_tokens(&token.0, user_id.into()).await {
+        Ok(tokens) => Json(
+            tokens
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<ApiPersonalAccessToken>>(),
+        )
+        .into_response(),
+        Err(PersonalAccessTokenListError::NotFound) => UserNotFoundError.into_response(),
+        Err(PersonalAccessTokenListError::Auth(err)) => auth_error(err),
+        Err(PersonalAccessTokenListError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn list_tokens_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Return all personal access tokens of the given user.")
+        .add_response::<Vec<ApiPersonalAccessToken>>(StatusCode::OK, None)
+        .add_error::<UserNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct CreateTokenRequest {
+    /// Display name of the token
+    name: PersonalAccessTokenName,
+    /// Scopes to grant to the token. The `admin` scope can only be requested
+    /// by administrators and users who have been granted any role permission.
+    /// Tokens with the `read_only` scope cannot be used to modify any data.
+    #[serde(default)]
+    scopes: BTreeSet<PersonalAccessTokenScope>,
+    /// Expiration timestamp (the token does not expire if omitted)
+    expires_at: Option<i64>,
+}
+
+async fn create_token(
+    service: State<Arc<impl PersonalAccessTokenFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+    Json(CreateTokenRequest {
+        name,
+        scopes,
+        expires_at,
+    }): Json<CreateTokenRequest>,
+) -> Response {
+    let expires_at = match expires_at.map(|x| DateTime::from_timestamp(x, 0)) {
+        Some(None) => return InvalidExpiryError.into_response(),
+        x => x.flatten(),
+    };
+
+    match service
+        .create_token(
+            &token.0,
+            user_id.into(),
+            PersonalAccessTokenCreateRequest {
+                name,
+                scopes,
+                expires_at,
+            },
+            &audit.0,
+        )
+        .await
+    {
+        Ok(PersonalAccessTokenCreateResponse {
+            personal_access_token,
+            token,
+        }) => Json(ApiPersonalAccessTokenWithToken {
+            personal_access_token: personal_access_token.into(),
+            token,
+        })
+        .into_response(),
+        Err(PersonalAccessTokenCreateError::InvalidExpiry) => InvalidExpiryError.into_response(),
+        Err(PersonalAccessTokenCreateError::Auth(err)) => auth_error(err),
+        Err(PersonalAccessTokenCreateError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn create_token_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Create a new personal access token for the authenticated user.")
+        .description(
+            "Personal access tokens can be used instead of access tokens, e.g. for scripts or \
+             CLI tools. This endpoint cannot be used with a personal access token.",
+        )
+        .add_response::<ApiPersonalAccessTokenWithToken>(
+            StatusCode::OK,
+            "The personal access token has been created.",
+        )
+        .add_error::<InvalidExpiryError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct TokenPath {
+    user_id: ApiUserIdOrSelf,
+    token_id: PersonalAccessTokenId,
+}
+
+async fn delete_token(
+    service: State<Arc<impl PersonalAccessTokenFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(TokenPath { user_id, token_id }): Path<TokenPath>,
+) -> Response {
+    match service
+        .delete_token(&token.0, user_id.into(), token_id, &audit.0)
+        .await
+    {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(PersonalAccessTokenDeleteError::NotFound) => {
+            PersonalAccessTokenNotFoundError.into_response()
+        }
+        Err(PersonalAccessTokenDeleteError::Auth(err)) => auth_error(err),
+        Err(PersonalAccessTokenDeleteError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn delete_token_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Revoke the given personal access token.")
+        .add_response::<OkResponse>(
+            StatusCode::OK,
+            "The personal access token has been revoked.",
+        )
+        .add_error::<PersonalAccessTokenNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+error_code! {
+    /// The expiration timestamp is invalid or in the past.
+    InvalidExpiryError(UNPROCESSABLE_ENTITY, "Invalid expiry");
+    /// The personal access token does not exist.
+    PersonalAccessTokenNotFoundError(NOT_FOUND, "Personal access token not found");
+}
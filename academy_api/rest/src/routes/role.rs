@@ -0,0 +1,273 @@
+use std::{collections::BTreeSet, sync::Arc};
+
+use academy_core_role_contracts::{
+    RoleAddUserRoleError, RoleCreateError, RoleCreateRequest, RoleDeleteError, RoleFeatureService,
+    RoleListError, RoleListUserRolesError, RoleRemoveUserRoleError, RoleUpdateError,
+};
+use academy_models::role::{Permission, RoleId, RoleName, RolePatch};
+use aide::{
+    axum::{routing, ApiRouter},
+    transform::TransformOperation,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::user::UserNotFoundError;
+use crate::{
+    docs::TransformOperationExt,
+    error_code,
+    errors::{auth_error, auth_error_docs, internal_server_error, internal_server_error_docs},
+    extractors::{audit::ApiAuditContext, auth::ApiToken},
+    models::{
+        role::ApiRole,
+        user::{ApiUserIdOrSelf, PathUserIdOrSelf},
+        OkResponse,
+    },
+};
+
+pub const TAG: &str = "Roles";
+
+pub fn router(service: Arc<impl RoleFeatureService>) -> ApiRouter<()> {
+    ApiRouter::new()
+        .api_route(
+            "/auth/roles",
+            routing::get_with(list_roles, list_roles_docs).post_with(create_role, create_role_docs),
+        )
+        .api_route(
+            "/auth/roles/:role_id",
+            routing::patch_with(update_role, update_role_docs)
+                .delete_with(delete_role, delete_role_docs),
+        )
+        .api_route(
+            "/auth/users/:user_id/roles",
+            routing::get_with(list_user_roles, list_user_roles_docs),
+        )
+        .api_route(
+            "/auth/users/:user_id/roles/:role_id",
+            routing::put_with(add_user_role, add_user_role_docs)
+                .delete_with(remove_user_role, remove_user_role_docs),
+        )
+        .with_state(service)
+        .with_path_items(|op| op.tag(TAG))
+}
+
+async fn list_roles(service: State<Arc<impl RoleFeatureService>>, token: ApiToken) -> Response {
+    match service.list_roles(&token.0).await {
+        Ok(roles) => {
+            Json(roles.into_iter().map(Into::into).collect::<Vec<ApiRole>>()).into_response()
+        }
+        Err(RoleListError::Auth(err)) => auth_error(err),
+        Err(RoleListError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn list_roles_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Return all roles.")
+        .description("Requires admin privileges.")
+        .add_response::<Vec<ApiRole>>(StatusCode::OK, None)
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct CreateRoleRequest {
+    /// Unique name of the role
+    name: RoleName,
+    /// Permissions to grant to users with this role
+    #[serde(default)]
+    permissions: BTreeSet<Permission>,
+}
+
+async fn create_role(
+    service: State<Arc<impl RoleFeatureService>>,
+    token: ApiToken,
+    Json(CreateRoleRequest { name, permissions }): Json<CreateRoleRequest>,
+) -> Response {
+    match service
+        .create_role(&token.0, RoleCreateRequest { name, permissions })
+        .await
+    {
+        Ok(role) => Json(ApiRole::from(role)).into_response(),
+        Err(RoleCreateError::NameConflict) => RoleAlreadyExistsError.into_response(),
+        Err(RoleCreateError::Auth(err)) => auth_error(err),
+        Err(RoleCreateError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn create_role_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Create a new role.")
+        .description("Requires admin privileges.")
+        .add_response::<ApiRole>(StatusCode::OK, "The role has been created.")
+        .add_error::<RoleAlreadyExistsError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct RolePath {
+    role_id: RoleId,
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct UpdateRoleRequest {
+    /// Unique name of the role
+    name: Option<RoleName>,
+    /// Permissions to grant to users with this role
+    permissions: Option<BTreeSet<Permission>>,
+}
+
+async fn update_role(
+    service: State<Arc<impl RoleFeatureService>>,
+    token: ApiToken,
+    Path(RolePath { role_id }): Path<RolePath>,
+    Json(UpdateRoleRequest { name, permissions }): Json<UpdateRoleRequest>,
+) -> Response {
+    match service
+        .update_role(
+            &token.0,
+            role_id,
+            RolePatch {
+                name: name.into(),
+                permissions: permissions.into(),
+            },
+        )
+        .await
+    {
+        Ok(role) => Json(ApiRole::from(role)).into_response(),
+        Err(RoleUpdateError::NotFound) => RoleNotFoundError.into_response(),
+        Err(RoleUpdateError::NameConflict) => RoleAlreadyExistsError.into_response(),
+        Err(RoleUpdateError::Auth(err)) => auth_error(err),
+        Err(RoleUpdateError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn update_role_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Update the given role.")
+        .description(
+            "Requires admin privileges. Changing the permissions of a role invalidates all access \
+             tokens of users with this role.",
+        )
+        .add_response::<ApiRole>(StatusCode::OK, "The role has been updated.")
+        .add_error::<RoleNotFoundError>()
+        .add_error::<RoleAlreadyExistsError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+async fn delete_role(
+    service: State<Arc<impl RoleFeatureService>>,
+    token: ApiToken,
+    Path(RolePath { role_id }): Path<RolePath>,
+) -> Response {
+    match service.delete_role(&token.0, role_id).await {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(RoleDeleteError::NotFound) => RoleNotFoundError.into_response(),
+        Err(RoleDeleteError::Auth(err)) => auth_error(err),
+        Err(RoleDeleteError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn delete_role_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Delete the given role and revoke it from all users.")
+        .description("Requires admin privileges.")
+        .add_response::<OkResponse>(StatusCode::OK, "The role has been deleted.")
+        .add_error::<RoleNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+async fn list_user_roles(
+    service: State<Arc<impl RoleFeatureService>>,
+    token: ApiToken,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+) -> Response {
+    match service.list_user_roles(&token.0, user_id.into()).await {
+        Ok(roles) => {
+            Json(roles.into_iter().map(Into::into).collect::<Vec<ApiRole>>()).into_response()
+        }
+        Err(RoleListUserRolesError::NotFound) => UserNotFoundError.into_response(),
+        Err(RoleListUserRolesError::Auth(err)) => auth_error(err),
+        Err(RoleListUserRolesError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn list_user_roles_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Return all roles granted to the given user.")
+        .add_response::<Vec<ApiRole>>(StatusCode::OK, None)
+        .add_error::<UserNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct UserRolePath {
+    user_id: ApiUserIdOrSelf,
+    role_id: RoleId,
+}
+
+async fn add_user_role(
+    service: State<Arc<impl RoleFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(UserRolePath { user_id, role_id }): Path<UserRolePath>,
+) -> Response {
+    match service
+        .add_user_role(&token.0, user_id.into(), role_id, &audit.0)
+        .await
+    {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(RoleAddUserRoleError::UserNotFound) => UserNotFoundError.into_response(),
+        Err(RoleAddUserRoleError::RoleNotFound) => RoleNotFoundError.into_response(),
+        Err(RoleAddUserRoleError::Auth(err)) => auth_error(err),
+        Err(RoleAddUserRoleError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn add_user_role_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Grant the given role to the given user.")
+        .description("Requires admin privileges.")
+        .add_response::<OkResponse>(StatusCode::OK, "The role has been granted.")
+        .add_error::<UserNotFoundError>()
+        .add_error::<RoleNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+async fn remove_user_role(
+    service: State<Arc<impl RoleFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(UserRolePath { user_id, role_id }): Path<UserRolePath>,
+) -> Response {
+    match service
+        .remove_user_role(&token.0, user_id.into(), role_id, &audit.0)
+        .await
+    {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(RoleRemoveUserRoleError::NotFound) => RoleNotFoundError.into_response(),
+        Err(RoleRemoveUserRoleError::Auth(err)) => auth_error(err),
+        Err(RoleRemoveUserRoleError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn remove_user_role_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Revoke the given role from the given user.")
+        .description("Requires admin privileges.")
+        .add_response::<OkResponse>(StatusCode::OK, "The role has been revoked.")
+        .add_error::<RoleNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+error_code! {
+    /// The role does not exist.
+    RoleNotFoundError(NOT_FOUND, "Role not found");
+    /// A role with the same name already exists.
+    RoleAlreadyExistsError(CONFLICT, "Role already exists");
+}
@@ -1,26 +1,32 @@
 use std::sync::Arc;
 
+use academy_core_rate_limit_contracts::RateLimitFeatureService;
 use academy_core_session_contracts::{
-    SessionCreateCommand, SessionCreateError, SessionDeleteByUserError, SessionDeleteCurrentError,
-    SessionDeleteError, SessionFeatureService, SessionGetCurrentError, SessionImpersonateError,
-    SessionListByUserError, SessionRefreshError,
+    SessionCreateByLoginCodeCommand, SessionCreateByLoginCodeError, SessionCreateCommand,
+    SessionCreateError, SessionDeleteByUserError, SessionDeleteCurrentError, SessionDeleteError,
+    SessionFeatureService, SessionGetCurrentError, SessionImpersonateError, SessionListByUserError,
+    SessionListByUserResult, SessionRefreshError, SessionRequestLoginCodeError,
+    SessionRevokeNewDeviceSessionError, SessionUnlockUserError,
 };
 use academy_models::{
     auth::RefreshToken,
+    email_address::EmailAddress,
     mfa::{MfaAuthentication, MfaRecoveryCode, TotpCode},
-    session::{DeviceName, SessionId},
+    rate_limit::RateLimitGroup,
+    session::{DeviceName, SessionId, SessionLoginCode},
     user::{UserNameOrEmailAddress, UserPassword},
-    RecaptchaResponse,
+    RecaptchaResponse, VerificationCode,
 };
+use academy_utils::Apply;
 use aide::{
     axum::{routing, ApiRouter},
     transform::TransformOperation,
 };
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
+    http::{header::RETRY_AFTER, StatusCode},
     response::{IntoResponse, Response},
-    Json,
+    Extension, Json,
 };
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -34,10 +40,12 @@ use crate::{
     error_code,
     errors::{
         auth_error, auth_error_docs, internal_server_error, internal_server_error_docs,
-        RecaptchaFailedError,
+        rate_limit_docs, RecaptchaFailedError,
     },
-    extractors::{auth::ApiToken, user_agent::UserAgent},
+    extractors::{audit::ApiAuditContext, auth::ApiToken, user_agent::UserAgent},
+    middlewares::{client_ip::ClientIp, rate_limit},
     models::{
+        mfa::ApiWebauthnAssertion,
         session::{ApiLogin, ApiSession},
         user::{ApiUserIdOrSelf, PathUserId, PathUserIdOrSelf},
         OkResponse, StringOption,
@@ -46,7 +54,10 @@ use crate::{
 
 pub const TAG: &str = "Session";
 
-pub fn router(service: Arc<impl SessionFeatureService>) -> ApiRouter<()> {
+pub fn router(
+    service: Arc<impl SessionFeatureService>,
+    rate_limit_service: Arc<impl RateLimitFeatureService>,
+) -> ApiRouter<()> {
     ApiRouter::new()
         .api_route(
             "/auth/session",
@@ -54,7 +65,28 @@ pub fn router(service: Arc<impl SessionFeatureService>) -> ApiRouter<()> {
                 .put_with(refresh, refresh_docs)
                 .delete_with(delete_current, delete_current_docs),
         )
-        .api_route("/auth/sessions", routing::post_with(create, create_docs))
+        .api_route(
+            "/auth/sessions",
+            routing::post_with(create, create_docs).apply(rate_limit::add(
+                Arc::clone(&rate_limit_service),
+                RateLimitGroup::Login,
+            )),
+        )
+        .api_route(
+            "/auth/sessions/login_code",
+            routing::post_with(create_by_login_code, create_by_login_code_docs).apply(
+                rate_limit::add(Arc::clone(&rate_limit_service), RateLimitGroup::Login),
+            ),
+        )
+        .api_route(
+            "/auth/login_code",
+            routing::post_with(request_login_code, request_login_code_docs)
+                .apply(rate_limit::add(rate_limit_service, RateLimitGroup::Login)),
+        )
+        .api_route(
+            "/auth/sessions/revoke",
+            routing::post_with(revoke_new_device, revoke_new_device_docs),
+        )
         .api_route(
             "/auth/sessions/:user_id",
             routing::get_with(list_by_user, list_by_user_docs)
@@ -65,6 +97,10 @@ pub fn router(service: Arc<impl SessionFeatureService>) -> ApiRouter<()> {
             "/auth/sessions/:user_id/:session_id",
             routing::delete_with(delete, delete_docs),
         )
+        .api_route(
+            "/auth/users/:user_id/lockout",
+            routing::delete_with(unlock_user, unlock_user_docs),
+        )
         .with_state(service)
         .with_path_items(|op| op.tag(TAG))
 }
@@ -74,7 +110,7 @@ async fn get_current(
     token: ApiToken,
 ) -> Response {
     match session_service.get_current_session(&token.0).await {
-        Ok(session) => Json(ApiSession::from(session)).into_response(),
+        Ok(session) => Json(ApiSession::new(session, true)).into_response(),
         Err(SessionGetCurrentError::Auth(err)) => auth_error(err),
         Err(SessionGetCurrentError::Other(err)) => internal_server_error(err),
     }
@@ -93,11 +129,17 @@ async fn list_by_user(
     Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
 ) -> Response {
     match session_service.list_by_user(&token.0, user_id.into()).await {
-        Ok(sessions) => Json(
+        Ok(SessionListByUserResult {
+            sessions,
+            current_session_id,
+        }) => Json(
             sessions
                 .into_iter()
-                .map(Into::into)
-                .collect::<Vec<ApiSession>>(),
+                .map(|session| {
+                    let is_current = current_session_id == Some(session.id);
+                    ApiSession::new(session, is_current)
+                })
+                .collect::<Vec<_>>(),
         )
         .into_response(),
         Err(SessionListByUserError::Auth(err)) => auth_error(err),
@@ -107,6 +149,10 @@ async fn list_by_user(
 
 fn list_by_user_docs(op: TransformOperation) -> TransformOperation {
     op.summary("Return all sessions of the given user.")
+        .description(
+            "The session which has been used to authenticate the request is marked with \
+             `is_current`.",
+        )
         .add_response::<Vec<ApiSession>>(StatusCode::OK, None)
         .with(auth_error_docs)
         .with(internal_server_error_docs)
@@ -118,17 +164,21 @@ struct CreateRequest {
     password: UserPassword,
     mfa_code: StringOption<TotpCode>,
     recovery_code: StringOption<MfaRecoveryCode>,
+    #[serde(default)]
+    webauthn_assertion: Option<ApiWebauthnAssertion>,
     recaptcha_response: StringOption<RecaptchaResponse>,
 }
 
 async fn create(
     session_service: State<Arc<impl SessionFeatureService>>,
     user_agent: UserAgent,
+    Extension(ClientIp(ip)): Extension<ClientIp>,
     Json(CreateRequest {
         name_or_email,
         password,
         mfa_code,
         recovery_code,
+        webauthn_assertion,
         recaptcha_response,
     }): Json<CreateRequest>,
 ) -> Response {
@@ -138,9 +188,11 @@ async fn create(
                 name_or_email,
                 password,
                 device_name: user_agent.0.map(DeviceName::from_string_truncated),
+                ip: Some(ip),
                 mfa: MfaAuthentication {
                     totp_code: mfa_code.into(),
                     recovery_code: recovery_code.into(),
+                    webauthn_assertion: webauthn_assertion.map(Into::into),
                 },
             },
             recaptcha_response.into(),
@@ -152,6 +204,11 @@ async fn create(
         Err(SessionCreateError::MfaFailed) => InvalidMfaCodeError.into_response(),
         Err(SessionCreateError::UserDisabled) => UserDisabledError.into_response(),
         Err(SessionCreateError::Recaptcha) => RecaptchaFailedError.into_response(),
+        Err(SessionCreateError::Locked { retry_after }) => (
+            [(RETRY_AFTER, retry_after.as_secs().max(1).to_string())],
+            AccountLockedError,
+        )
+            .into_response(),
         Err(SessionCreateError::Other(err)) => internal_server_error(err),
     }
 }
@@ -159,24 +216,150 @@ async fn create(
 fn create_docs(op: TransformOperation) -> TransformOperation {
     op.summary("Create a new session via username/password authentication.")
         .description(
-            "If the user has MFA enabled, the current TOTP needs to provided. Alternatively, the \
-             recovery code can be used to disable MFA.\n\nAfter too many failed login attempts, a \
-             valid reCAPTCHA response is required, if reCAPTCHA is enabled.",
+            "If the user has MFA enabled, the current TOTP or a WebAuthn assertion needs to \
+             provided. Alternatively, one of the recovery codes can be used, which is consumed \
+             in the process.\n\nAfter too many failed login attempts, a valid reCAPTCHA \
+             response is required, if reCAPTCHA is enabled. If the number of failed login \
+             attempts for the account or client ip address keeps increasing, logins are \
+             temporarily blocked with an exponentially increasing lockout duration. The \
+             `Retry-After` header contains the number of seconds until the lockout expires.",
         )
         .add_response::<ApiLogin>(StatusCode::OK, "A new session has been created.")
         .add_error::<InvalidCredentialsError>()
         .add_error::<InvalidMfaCodeError>()
         .add_error::<UserDisabledError>()
         .add_error::<RecaptchaFailedError>()
+        .add_error::<AccountLockedError>()
+        .with(rate_limit_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct RequestLoginCodeRequest {
+    email: EmailAddress,
+    recaptcha_response: StringOption<RecaptchaResponse>,
+}
+
+async fn request_login_code(
+    session_service: State<Arc<impl SessionFeatureService>>,
+    Json(RequestLoginCodeRequest {
+        email,
+        recaptcha_response,
+    }): Json<RequestLoginCodeRequest>,
+) -> Response {
+    match session_service
+        .request_login_code(email, recaptcha_response.into())
+        .await
+    {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(SessionRequestLoginCodeError::Recaptcha) => RecaptchaFailedError.into_response(),
+        Err(SessionRequestLoginCodeError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn request_login_code_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Request a one-time login code via email.")
+        .description(
+            "Sends an email containing a login link and a 6-digit code to the given email \
+             address, which can be used to create a session without a password. For privacy \
+             reasons, this endpoint also succeeds if no user with this email address exists. \
+             Requires a valid reCAPTCHA response, if reCAPTCHA is enabled.",
+        )
+        .add_response::<OkResponse>(
+            StatusCode::OK,
+            "The login code has been sent, if the user exists.",
+        )
+        .add_error::<RecaptchaFailedError>()
+        .with(rate_limit_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct CreateByLoginCodeRequest {
+    email: EmailAddress,
+    code: SessionLoginCode,
+    mfa_code: StringOption<TotpCode>,
+    recovery_code: StringOption<MfaRecoveryCode>,
+    #[serde(default)]
+    webauthn_assertion: Option<ApiWebauthnAssertion>,
+    recaptcha_response: StringOption<RecaptchaResponse>,
+}
+
+async fn create_by_login_code(
+    session_service: State<Arc<impl SessionFeatureService>>,
+    user_agent: UserAgent,
+    Extension(ClientIp(ip)): Extension<ClientIp>,
+    Json(CreateByLoginCodeRequest {
+        email,
+        code,
+        mfa_code,
+        recovery_code,
+        webauthn_assertion,
+        recaptcha_response,
+    }): Json<CreateByLoginCodeRequest>,
+) -> Response {
+    match session_service
+        .create_session_by_login_code(
+            SessionCreateByLoginCodeCommand {
+                email,
+                code,
+                device_name: user_agent.0.map(DeviceName::from_string_truncated),
+                ip: Some(ip),
+                mfa: MfaAuthentication {
+                    totp_code: mfa_code.into(),
+                    recovery_code: recovery_code.into(),
+                    webauthn_assertion: webauthn_assertion.map(Into::into),
+                },
+            },
+            recaptcha_response.into(),
+        )
+        .await
+    {
+        Ok(result) => Json(ApiLogin::from(result)).into_response(),
+        Err(SessionCreateByLoginCodeError::InvalidCode) => InvalidLoginCodeError.into_response(),
+        Err(SessionCreateByLoginCodeError::MfaFailed) => InvalidMfaCodeError.into_response(),
+        Err(SessionCreateByLoginCodeError::UserDisabled) => UserDisabledError.into_response(),
+        Err(SessionCreateByLoginCodeError::Recaptcha) => RecaptchaFailedError.into_response(),
+        Err(SessionCreateByLoginCodeError::Locked { retry_after }) => (
+            [(RETRY_AFTER, retry_after.as_secs().max(1).to_string())],
+            AccountLockedError,
+        )
+            .into_response(),
+        Err(SessionCreateByLoginCodeError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn create_by_login_code_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Create a new session via a one-time login code.")
+        .description(
+            "The login code can be requested using the `POST /auth/login_code` endpoint. If the \
+             user has MFA enabled, the current TOTP, a WebAuthn assertion or a recovery code \
+             needs to be provided as well. The login code becomes invalid after the first \
+             attempt to use it, so a new one has to be requested if the code or MFA is \
+             wrong.\n\nFailed attempts \
+             count towards the same limits as password logins, i.e. a reCAPTCHA response may be \
+             required and logins may be temporarily blocked.",
+        )
+        .add_response::<ApiLogin>(StatusCode::OK, "A new session has been created.")
+        .add_error::<InvalidLoginCodeError>()
+        .add_error::<InvalidMfaCodeError>()
+        .add_error::<UserDisabledError>()
+        .add_error::<RecaptchaFailedError>()
+        .add_error::<AccountLockedError>()
+        .with(rate_limit_docs)
         .with(internal_server_error_docs)
 }
 
 async fn impersonate(
     session_service: State<Arc<impl SessionFeatureService>>,
     token: ApiToken,
+    audit: ApiAuditContext,
     Path(PathUserId { user_id }): Path<PathUserId>,
 ) -> Response {
-    match session_service.impersonate(&token.0, user_id).await {
+    match session_service
+        .impersonate(&token.0, user_id, &audit.0)
+        .await
+    {
         Ok(login) => Json(ApiLogin::from(login)).into_response(),
         Err(SessionImpersonateError::NotFound) => UserNotFoundError.into_response(),
         Err(SessionImpersonateError::Auth(err)) => auth_error(err),
@@ -199,9 +382,13 @@ struct RefreshRequest {
 
 async fn refresh(
     session_service: State<Arc<impl SessionFeatureService>>,
+    audit: ApiAuditContext,
     Json(RefreshRequest { refresh_token }): Json<RefreshRequest>,
 ) -> Response {
-    match session_service.refresh_session(&refresh_token).await {
+    match session_service
+        .refresh_session(&refresh_token, &audit.0)
+        .await
+    {
         Ok(login) => Json(ApiLogin::from(login)).into_response(),
         Err(SessionRefreshError::InvalidRefreshToken) => InvalidRefreshTokenError.into_response(),
         Err(SessionRefreshError::Other(err)) => internal_server_error(err),
@@ -211,7 +398,9 @@ async fn refresh(
 fn refresh_docs(op: TransformOperation) -> TransformOperation {
     op.summary("Refresh session via refresh token")
         .description(
-            "Generates and returns a new access/refresh token pair and invalidates the old tokens.",
+            "Generates and returns a new access/refresh token pair and invalidates the old tokens. \
+             If a refresh token is used again after it has already been replaced by a new one, \
+             the whole session is revoked.",
         )
         .add_response::<ApiLogin>(StatusCode::OK, "The session has been refreshed.")
         .add_error::<InvalidRefreshTokenError>()
@@ -291,11 +480,75 @@ fn delete_by_user_docs(op: TransformOperation) -> TransformOperation {
         .with(internal_server_error_docs)
 }
 
+async fn unlock_user(
+    session_service: State<Arc<impl SessionFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(PathUserId { user_id }): Path<PathUserId>,
+) -> Response {
+    match session_service
+        .unlock_user(&token.0, user_id, &audit.0)
+        .await
+    {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(SessionUnlockUserError::NotFound) => UserNotFoundError.into_response(),
+        Err(SessionUnlockUserError::Auth(err)) => auth_error(err),
+        Err(SessionUnlockUserError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn unlock_user_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Lift the lockout of the given user caused by too many failed login attempts.")
+        .add_response::<OkResponse>(StatusCode::OK, "The lockout has been lifted.")
+        .add_error::<UserNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct RevokeNewDeviceRequest {
+    code: VerificationCode,
+}
+
+async fn revoke_new_device(
+    session_service: State<Arc<impl SessionFeatureService>>,
+    audit: ApiAuditContext,
+    Json(RevokeNewDeviceRequest { code }): Json<RevokeNewDeviceRequest>,
+) -> Response {
+    match session_service
+        .revoke_new_device_session(code, &audit.0)
+        .await
+    {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(SessionRevokeNewDeviceSessionError::InvalidCode) => {
+            InvalidRevokeCodeError.into_response()
+        }
+        Err(SessionRevokeNewDeviceSessionError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn revoke_new_device_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Revoke a session using the code from a new sign-in notification email.")
+        .description(
+            "Deletes all sessions, personal access tokens and OAuth2 grants of the user, removes \
+             the user's password and sends a password reset email to the user.",
+        )
+        .add_response::<OkResponse>(StatusCode::OK, "The session has been revoked.")
+        .add_error::<InvalidRevokeCodeError>()
+        .with(internal_server_error_docs)
+}
+
 error_code! {
+    /// Too many failed login attempts for the account or client ip address.
+    AccountLockedError(TOO_MANY_REQUESTS, "Account locked");
     /// The user does not exist or the password is incorrect.
     InvalidCredentialsError(UNAUTHORIZED, "Invalid credentials");
     /// The session does not exist.
     SessionNotFoundError(NOT_FOUND, "Session not found");
     /// The refresh token is invalid or has expired.
     InvalidRefreshTokenError(UNAUTHORIZED, "Invalid refresh token");
+    /// The revoke code is invalid or has expired.
+    InvalidRevokeCodeError(UNAUTHORIZED, "Invalid revoke code");
+    /// The user does not exist or the login code is invalid or has expired.
+    InvalidLoginCodeError(UNAUTHORIZED, "Invalid login code");
 }
@@ -1,32 +1,41 @@
 use std::sync::Arc;
 
+use academy_core_rate_limit_contracts::RateLimitFeatureService;
 use academy_core_user_contracts::{
+    name_policy::UserNamePolicyViolation,
+    password_policy::UserPasswordPolicyViolation,
     user::{UserListQuery, UserListResult},
-    PasswordUpdate, UserCreateError, UserCreateRequest, UserDeleteError, UserFeatureService,
-    UserGetError, UserListError, UserRequestPasswordResetError, UserRequestVerificationEmailError,
-    UserResetPasswordError, UserUpdateError, UserUpdateRequest, UserUpdateUserRequest,
-    UserVerifyEmailError, UserVerifyNewsletterSubscriptionError,
+    PasswordUpdate, UserAddEmailError, UserCancelEmailChangeError, UserConfirmEmailChangeError,
+    UserConfirmEmailError, UserCreateError, UserCreateRequest, UserDeleteAvatarError,
+    UserDeleteError, UserFeatureService, UserForceRestoreError, UserGetError, UserGetExportError,
+    UserListEmailsError, UserListError, UserListNameHistoryError, UserRemoveEmailError,
+    UserRequestExportError, UserRequestPasswordResetError, UserRequestVerificationEmailError,
+    UserResetPasswordError, UserRestoreError, UserSetPrimaryEmailError, UserUpdateError,
+    UserUpdateRequest, UserUpdateUserRequest, UserUploadAvatarError, UserVerifyEmailError,
+    UserVerifyNewsletterSubscriptionError,
 };
 use academy_models::{
     email_address::EmailAddress,
     oauth2::OAuth2RegistrationToken,
+    rate_limit::RateLimitGroup,
     session::DeviceName,
     user::{
-        UserBio, UserCity, UserCountry, UserDisplayName, UserFirstName, UserInvoiceInfo,
+        UserBio, UserCity, UserCountry, UserDisplayName, UserFirstName, UserId, UserInvoiceInfo,
         UserLastName, UserName, UserPassword, UserProfilePatch, UserStreet, UserTags, UserVatId,
         UserZipCode,
     },
     RecaptchaResponse, VerificationCode,
 };
+use academy_utils::Apply;
 use aide::{
     axum::{routing, ApiRouter},
     transform::TransformOperation,
 };
 use axum::{
-    extract::{Path, Query, State},
+    extract::{DefaultBodyLimit, Multipart, Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Response},
-    Json,
+    Extension, Json,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -37,23 +46,39 @@ use crate::{
     error_code,
     errors::{
         auth_error, auth_error_docs, internal_server_error, internal_server_error_docs,
-        PermissionDeniedError, RecaptchaFailedError,
+        rate_limit_docs, PermissionDeniedError, RecaptchaFailedError,
     },
-    extractors::{auth::ApiToken, user_agent::UserAgent},
+    extractors::{audit::ApiAuditContext, auth::ApiToken, user_agent::UserAgent},
+    middlewares::{client_ip::ClientIp, rate_limit},
     models::{
         session::ApiLogin,
-        user::{ApiUser, ApiUserFilter, ApiUserIdOrSelf, ApiUserPasswordOrEmpty, PathUserIdOrSelf},
+        user::{
+            ApiUser, ApiUserEmail, ApiUserExport, ApiUserFilter, ApiUserIdOrSelf,
+            ApiUserNameHistoryEntry, ApiUserPasswordOrEmpty, PathUserId, PathUserIdOrSelf,
+        },
         ApiPaginationSlice, OkResponse, StringOption,
     },
 };
 
 pub const TAG: &str = "User";
 
-pub fn router(service: Arc<impl UserFeatureService>) -> ApiRouter<()> {
+/// Maximum size of avatar upload requests. The actual limit for the image is
+/// enforced by the user feature.
+const AVATAR_BODY_LIMIT: usize = 16 << 20;
+
+pub fn router(
+    service: Arc<impl UserFeatureService>,
+    rate_limit_service: Arc<impl RateLimitFeatureService>,
+) -> ApiRouter<()> {
     ApiRouter::new()
         .api_route(
             "/auth/users",
-            routing::get_with(list, list_docs).post_with(create, create_docs),
+            routing::get_with(list, list_docs).merge(
+                routing::post_with(create, create_docs).apply(rate_limit::add(
+                    Arc::clone(&rate_limit_service),
+                    RateLimitGroup::Signup,
+                )),
+            ),
         )
         .api_route(
             "/auth/users/:user_id",
@@ -61,11 +86,56 @@ pub fn router(service: Arc<impl UserFeatureService>) -> ApiRouter<()> {
                 .patch_with(update, update_docs)
                 .delete_with(delete, delete_docs),
         )
+        .api_route(
+            "/auth/users/:user_id/restore",
+            routing::put_with(restore, restore_docs),
+        )
+        .api_route(
+            "/auth/users/:user_id/restore/force",
+            routing::put_with(force_restore, force_restore_docs),
+        )
+        .api_route(
+            "/auth/users/:user_id/avatar",
+            routing::put_with(upload_avatar, upload_avatar_docs)
+                .layer(DefaultBodyLimit::max(AVATAR_BODY_LIMIT))
+                .delete_with(delete_avatar, delete_avatar_docs),
+        )
         .api_route(
             "/auth/users/:user_id/email",
             routing::post_with(request_verification_email, request_verification_email_docs)
+                .apply(rate_limit::add(
+                    Arc::clone(&rate_limit_service),
+                    RateLimitGroup::VerificationEmail,
+                ))
                 .put_with(verify_email, verify_email_docs),
         )
+        .api_route(
+            "/auth/users/:user_id/email_change",
+            routing::put_with(confirm_email_change, confirm_email_change_docs)
+                .delete_with(cancel_email_change, cancel_email_change_docs),
+        )
+        .api_route(
+            "/auth/users/:user_id/emails",
+            routing::post_with(add_email, add_email_docs)
+                .apply(rate_limit::add(
+                    Arc::clone(&rate_limit_service),
+                    RateLimitGroup::VerificationEmail,
+                ))
+                .get_with(list_emails, list_emails_docs)
+                .put_with(confirm_email, confirm_email_docs),
+        )
+        .api_route(
+            "/auth/users/:user_id/emails/:email",
+            routing::delete_with(remove_email, remove_email_docs),
+        )
+        .api_route(
+            "/auth/users/:user_id/emails/:email/primary",
+            routing::put_with(set_primary_email, set_primary_email_docs),
+        )
+        .api_route(
+            "/auth/users/:user_id/name_history",
+            routing::get_with(list_name_history, list_name_history_docs),
+        )
         .api_route(
             "/auth/users/:user_id/newsletter",
             routing::put_with(
@@ -73,10 +143,25 @@ pub fn router(service: Arc<impl UserFeatureService>) -> ApiRouter<()> {
                 verify_newsletter_subscription_docs,
             ),
         )
+        .api_route(
+            "/auth/users/:user_id/export",
+            routing::get_with(request_export, request_export_docs).apply(rate_limit::add(
+                Arc::clone(&rate_limit_service),
+                RateLimitGroup::UserExport,
+            )),
+        )
+        .api_route(
+            "/auth/users/:user_id/export/:code",
+            routing::get_with(get_export, get_export_docs),
+        )
         .api_route(
             "/auth/password_reset",
             routing::post_with(request_password_reset, request_password_reset_docs)
-                .put_with(reset_password, reset_password_docs),
+                .put_with(reset_password, reset_password_docs)
+                .apply(rate_limit::add(
+                    rate_limit_service,
+                    RateLimitGroup::PasswordReset,
+                )),
         )
         .with_state(service)
         .with_path_items(|op| op.tag(TAG))
@@ -160,6 +245,7 @@ struct CreateRequest {
 async fn create(
     user_service: State<Arc<impl UserFeatureService>>,
     user_agent: UserAgent,
+    Extension(ClientIp(ip)): Extension<ClientIp>,
     Json(CreateRequest {
         name,
         display_name,
@@ -179,6 +265,7 @@ async fn create(
                 oauth2_registration_token: oauth_register_token.into(),
             },
             user_agent.0.map(DeviceName::from_string_truncated),
+            Some(ip),
             recaptcha_response.into(),
         )
         .await
@@ -192,6 +279,8 @@ async fn create(
             InvalidOAuthTokenError.into_response()
         }
         Err(UserCreateError::RemoteAlreadyLinked) => RemoteAlreadyLinkedError.into_response(),
+        Err(UserCreateError::NamePolicy(violation)) => name_policy_error(violation),
+        Err(UserCreateError::PasswordPolicy(violation)) => password_policy_error(violation),
         Err(UserCreateError::Other(err)) => internal_server_error(err),
     }
 }
@@ -206,6 +295,9 @@ fn create_docs(op: TransformOperation) -> TransformOperation {
         .add_error::<NoLoginMethodError>()
         .add_error::<InvalidOAuthTokenError>()
         .add_error::<RemoteAlreadyLinkedError>()
+        .with(name_policy_error_docs)
+        .with(password_policy_error_docs)
+        .with(rate_limit_docs)
         .with(internal_server_error_docs)
 }
 
@@ -234,6 +326,7 @@ struct UpdateRequest {
 async fn update(
     user_service: State<Arc<impl UserFeatureService>>,
     token: ApiToken,
+    audit: ApiAuditContext,
     Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
     Json(UpdateRequest {
         name,
@@ -279,6 +372,7 @@ async fn update(
                     display_name: Option::from(display_name).into(),
                     bio: Option::from(description).into(),
                     tags: tags.into(),
+                    avatar_url: Default::default(),
                 },
                 invoice_info: UserInvoiceInfo {
                     business,
@@ -291,6 +385,7 @@ async fn update(
                     vat_id: vat_id.into(),
                 },
             },
+            &audit.0,
         )
         .await
     {
@@ -308,6 +403,8 @@ async fn update(
         ) => PermissionDeniedError.into_response(),
         Err(UserUpdateError::NoEmail) => NoEmailError.into_response(),
         Err(UserUpdateError::InvalidVatId) => InvalidVatIdError.into_response(),
+        Err(UserUpdateError::NamePolicy(violation)) => name_policy_error(violation),
+        Err(UserUpdateError::PasswordPolicy(violation)) => password_policy_error(violation),
         Err(UserUpdateError::Auth(err)) => auth_error(err),
         Err(UserUpdateError::Other(err)) => internal_server_error(err),
     }
@@ -315,6 +412,11 @@ async fn update(
 
 fn update_docs(op: TransformOperation) -> TransformOperation {
     op.summary("Update the given user.")
+        .description(
+            "When a non-admin user changes their own email address, the new address is not \
+             applied immediately. Instead a confirmation code is sent to the new address and the \
+             old address is notified about the requested change.",
+        )
         .add_response::<ApiUser>(StatusCode::OK, "The user has been updated.")
         .add_error::<UserNotFoundError>()
         .add_error::<UserAlreadyExistsError>()
@@ -323,6 +425,8 @@ fn update_docs(op: TransformOperation) -> TransformOperation {
         .add_error::<PermissionDeniedError>()
         .add_error::<NoEmailError>()
         .add_error::<InvalidVatIdError>()
+        .with(name_policy_error_docs)
+        .with(password_policy_error_docs)
         .with(auth_error_docs)
         .with(internal_server_error_docs)
 }
@@ -330,9 +434,13 @@ fn update_docs(op: TransformOperation) -> TransformOperation {
 async fn delete(
     user_service: State<Arc<impl UserFeatureService>>,
     token: ApiToken,
+    audit: ApiAuditContext,
     Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
 ) -> Response {
-    match user_service.delete_user(&token.0, user_id.into()).await {
+    match user_service
+        .delete_user(&token.0, user_id.into(), &audit.0)
+        .await
+    {
         Ok(()) => Json(OkResponse).into_response(),
         Err(UserDeleteError::NotFound) => UserNotFoundError.into_response(),
         Err(UserDeleteError::Auth(err)) => auth_error(err),
@@ -342,7 +450,365 @@ async fn delete(
 
 fn delete_docs(op: TransformOperation) -> TransformOperation {
     op.summary("Delete the given user.")
-        .add_response::<OkResponse>(StatusCode::OK, "The user has been deleted.")
+        .description(
+            "The user is logged out and the account is purged after the deletion grace period. \
+             Until then, the account can be restored using the code sent via email or by an \
+             administrator.",
+        )
+        .add_response::<OkResponse>(StatusCode::OK, "The user has been marked as deleted.")
+        .add_error::<UserNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct RestoreRequest {
+    code: VerificationCode,
+}
+
+async fn restore(
+    service: State<Arc<impl UserFeatureService>>,
+    audit: ApiAuditContext,
+    Path(PathUserId { user_id }): Path<PathUserId>,
+    Json(RestoreRequest { code }): Json<RestoreRequest>,
+) -> Response {
+    match service.restore_user(user_id, code, &audit.0).await {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(UserRestoreError::InvalidCode) => InvalidRestoreCodeError.into_response(),
+        Err(UserRestoreError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn restore_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Restore a deleted user using the code sent via email.")
+        .add_response::<OkResponse>(StatusCode::OK, "The user has been restored.")
+        .add_error::<InvalidRestoreCodeError>()
+        .with(internal_server_error_docs)
+}
+
+async fn force_restore(
+    service: State<Arc<impl UserFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(PathUserId { user_id }): Path<PathUserId>,
+) -> Response {
+    match service
+        .force_restore_user(&token.0, user_id, &audit.0)
+        .await
+    {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(UserForceRestoreError::NotFound) => UserNotFoundError.into_response(),
+        Err(UserForceRestoreError::Auth(err)) => auth_error(err),
+        Err(UserForceRestoreError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn force_restore_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Restore a deleted user without the code sent via email.")
+        .description("Requires admin privileges.")
+        .add_response::<OkResponse>(StatusCode::OK, "The user has been restored.")
+        .add_error::<UserNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct EmailChangeRequest {
+    code: VerificationCode,
+}
+
+async fn confirm_email_change(
+    service: State<Arc<impl UserFeatureService>>,
+    audit: ApiAuditContext,
+    Path(PathUserId { user_id }): Path<PathUserId>,
+    Json(EmailChangeRequest { code }): Json<EmailChangeRequest>,
+) -> Response {
+    match service.confirm_email_change(user_id, code, &audit.0).await {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(UserConfirmEmailChangeError::InvalidCode) => {
+            InvalidVerificationCodeError.into_response()
+        }
+        Err(UserConfirmEmailChangeError::Conflict) => EmailAlreadyExistsError.into_response(),
+        Err(UserConfirmEmailChangeError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn confirm_email_change_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Confirm a pending email address change using the code sent to the new address.")
+        .add_response::<OkResponse>(StatusCode::OK, "The email address has been changed.")
+        .add_error::<InvalidVerificationCodeError>()
+        .add_error::<EmailAlreadyExistsError>()
+        .with(internal_server_error_docs)
+}
+
+async fn cancel_email_change(
+    service: State<Arc<impl UserFeatureService>>,
+    audit: ApiAuditContext,
+    Path(PathUserId { user_id }): Path<PathUserId>,
+    Json(EmailChangeRequest { code }): Json<EmailChangeRequest>,
+) -> Response {
+    match service.cancel_email_change(user_id, code, &audit.0).await {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(UserCancelEmailChangeError::InvalidCode) => {
+            InvalidVerificationCodeError.into_response()
+        }
+        Err(UserCancelEmailChangeError::Conflict) => EmailAlreadyExistsError.into_response(),
+        Err(UserCancelEmailChangeError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn cancel_email_change_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Cancel an email address change using the code sent to the old address.")
+        .description("If the change has already been confirmed, the old email address is restored.")
+        .add_response::<OkResponse>(
+            StatusCode::OK,
+            "The email address change has been cancelled.",
+        )
+        .add_error::<InvalidVerificationCodeError>()
+        .add_error::<EmailAlreadyExistsError>()
+        .with(internal_server_error_docs)
+}
+
+async fn list_emails(
+    service: State<Arc<impl UserFeatureService>>,
+    token: ApiToken,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+) -> Response {
+    match service.list_user_emails(&token.0, user_id.into()).await {
+        Ok(emails) => Json(
+            emails
+                .into_iter()
+                .map(ApiUserEmail::from)
+                .collect::<Vec<_>>(),
+        )
+        .into_response(),
+        Err(UserListEmailsError::NotFound) => UserNotFoundError.into_response(),
+        Err(UserListEmailsError::Auth(err)) => auth_error(err),
+        Err(UserListEmailsError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn list_emails_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Return the additional email addresses of the given user.")
+        .add_response::<Vec<ApiUserEmail>>(StatusCode::OK, None)
+        .add_error::<UserNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct AddEmailRequest {
+    email: EmailAddress,
+}
+
+async fn add_email(
+    service: State<Arc<impl UserFeatureService>>,
+    token: ApiToken,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+    Json(AddEmailRequest { email }): Json<AddEmailRequest>,
+) -> Response {
+    match service
+        .add_user_email(&token.0, user_id.into(), email)
+        .await
+    {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(UserAddEmailError::NotFound) => UserNotFoundError.into_response(),
+        Err(UserAddEmailError::Conflict) => EmailAlreadyExistsError.into_response(),
+        Err(UserAddEmailError::Auth(err)) => auth_error(err),
+        Err(UserAddEmailError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn add_email_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Add an additional email address to the given user.")
+        .description(
+            "The email address is not added immediately. Instead a confirmation code is sent to \
+             the new address. Verified additional email addresses can be used for login and \
+             password reset.",
+        )
+        .add_response::<OkResponse>(
+            StatusCode::OK,
+            "A confirmation code has been sent to the email address.",
+        )
+        .add_error::<UserNotFoundError>()
+        .add_error::<EmailAlreadyExistsError>()
+        .with(auth_error_docs)
+        .with(rate_limit_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct ConfirmEmailRequest {
+    code: VerificationCode,
+}
+
+async fn confirm_email(
+    service: State<Arc<impl UserFeatureService>>,
+    audit: ApiAuditContext,
+    Path(PathUserId { user_id }): Path<PathUserId>,
+    Json(ConfirmEmailRequest { code }): Json<ConfirmEmailRequest>,
+) -> Response {
+    match service.confirm_user_email(user_id, code, &audit.0).await {
+        Ok(email) => Json(ApiUserEmail::from(email)).into_response(),
+        Err(UserConfirmEmailError::InvalidCode) => InvalidVerificationCodeError.into_response(),
+        Err(UserConfirmEmailError::Conflict) => EmailAlreadyExistsError.into_response(),
+        Err(UserConfirmEmailError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn confirm_email_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Confirm a pending additional email address using the code sent to this address.")
+        .add_response::<ApiUserEmail>(StatusCode::OK, "The email address has been added.")
+        .add_error::<InvalidVerificationCodeError>()
+        .add_error::<EmailAlreadyExistsError>()
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct EmailPath {
+    user_id: ApiUserIdOrSelf,
+    email: EmailAddress,
+}
+
+async fn remove_email(
+    service: State<Arc<impl UserFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(EmailPath { user_id, email }): Path<EmailPath>,
+) -> Response {
+    match service
+        .remove_user_email(&token.0, user_id.into(), email, &audit.0)
+        .await
+    {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(UserRemoveEmailError::NotFound) => EmailNotFoundError.into_response(),
+        Err(UserRemoveEmailError::Auth(err)) => auth_error(err),
+        Err(UserRemoveEmailError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn remove_email_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Remove an additional email address from the given user.")
+        .add_response::<OkResponse>(StatusCode::OK, "The email address has been removed.")
+        .add_error::<EmailNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+async fn set_primary_email(
+    service: State<Arc<impl UserFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(EmailPath { user_id, email }): Path<EmailPath>,
+) -> Response {
+    match service
+        .set_primary_user_email(&token.0, user_id.into(), email, &audit.0)
+        .await
+    {
+        Ok(user) => Json(ApiUser::from(user)).into_response(),
+        Err(UserSetPrimaryEmailError::NotFound) => UserNotFoundError.into_response(),
+        Err(UserSetPrimaryEmailError::EmailNotFound) => EmailNotFoundError.into_response(),
+        Err(UserSetPrimaryEmailError::Auth(err)) => auth_error(err),
+        Err(UserSetPrimaryEmailError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn set_primary_email_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Make an additional email address the primary email address of the given user.")
+        .description(
+            "The previous primary email address is kept as an additional email address if it \
+             has been verified.",
+        )
+        .add_response::<ApiUser>(
+            StatusCode::OK,
+            "The primary email address has been changed.",
+        )
+        .add_error::<UserNotFoundError>()
+        .add_error::<EmailNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+async fn upload_avatar(
+    service: State<Arc<impl UserFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+    mut multipart: Multipart,
+) -> Response {
+    let data = match read_avatar_field(&mut multipart).await {
+        Ok(Some(data)) => data,
+        Ok(None) => return MissingAvatarError.into_response(),
+        Err(err) => return err.into_response(),
+    };
+
+    match service
+        .upload_user_avatar(&token.0, user_id.into(), data, &audit.0)
+        .await
+    {
+        Ok(user) => Json(ApiUser::from(user)).into_response(),
+        Err(UserUploadAvatarError::NotFound) => UserNotFoundError.into_response(),
+        Err(UserUploadAvatarError::TooLarge) => AvatarTooLargeError.into_response(),
+        Err(UserUploadAvatarError::UnsupportedFormat) => {
+            UnsupportedAvatarFormatError.into_response()
+        }
+        Err(UserUploadAvatarError::InvalidImage) => InvalidAvatarError.into_response(),
+        Err(UserUploadAvatarError::Auth(err)) => auth_error(err),
+        Err(UserUploadAvatarError::Other(err)) => internal_server_error(err),
+    }
+}
+
+/// Read the contents of the `avatar` field of a multipart request.
+async fn read_avatar_field(
+    multipart: &mut Multipart,
+) -> Result<Option<Vec<u8>>, axum::extract::multipart::MultipartError> {
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() == Some("avatar") {
+            return Ok(Some(field.bytes().await?.into()));
+        }
+    }
+    Ok(None)
+}
+
+fn upload_avatar_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Upload a new avatar image for the given user.")
+        .description(
+            "The image must be sent in the `avatar` field of a `multipart/form-data` request. \
+             Supported formats are PNG, JPEG, WebP and GIF. The image is cropped to a square \
+             and stored in several sizes; the `avatar_url` of the user points to the default \
+             size.",
+        )
+        .add_response::<ApiUser>(StatusCode::OK, "The avatar has been uploaded.")
+        .add_error::<UserNotFoundError>()
+        .add_error::<MissingAvatarError>()
+        .add_error::<AvatarTooLargeError>()
+        .add_error::<UnsupportedAvatarFormatError>()
+        .add_error::<InvalidAvatarError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
+async fn delete_avatar(
+    service: State<Arc<impl UserFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+) -> Response {
+    match service
+        .delete_user_avatar(&token.0, user_id.into(), &audit.0)
+        .await
+    {
+        Ok(user) => Json(ApiUser::from(user)).into_response(),
+        Err(UserDeleteAvatarError::NotFound) => UserNotFoundError.into_response(),
+        Err(UserDeleteAvatarError::Auth(err)) => auth_error(err),
+        Err(UserDeleteAvatarError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn delete_avatar_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Delete the uploaded avatar image of the given user.")
+        .description("Afterwards the user's Gravatar is used again.")
+        .add_response::<ApiUser>(StatusCode::OK, "The avatar has been deleted.")
         .add_error::<UserNotFoundError>()
         .with(auth_error_docs)
         .with(internal_server_error_docs)
@@ -378,6 +844,7 @@ fn request_verification_email_docs(op: TransformOperation) -> TransformOperation
         .add_error::<EmailAlreadyVerifiedError>()
         .add_error::<NoEmailError>()
         .with(auth_error_docs)
+        .with(rate_limit_docs)
         .with(internal_server_error_docs)
 }
 
@@ -417,6 +884,34 @@ struct VerifyNewsletterSubscriptionRequest {
     code: VerificationCode,
 }
 
+async fn list_name_history(
+    service: State<Arc<impl UserFeatureService>>,
+    token: ApiToken,
+    Path(PathUserId { user_id }): Path<PathUserId>,
+) -> Response {
+    match service.list_user_name_history(&token.0, user_id).await {
+        Ok(history) => Json(
+            history
+                .into_iter()
+                .map(ApiUserNameHistoryEntry::from)
+                .collect::<Vec<_>>(),
+        )
+        .into_response(),
+        Err(UserListNameHistoryError::NotFound) => UserNotFoundError.into_response(),
+        Err(UserListNameHistoryError::Auth(err)) => auth_error(err),
+        Err(UserListNameHistoryError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn list_name_history_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Return the previous names of the given user, most recent first.")
+        .description("Requires admin privileges.")
+        .add_response::<Vec<ApiUserNameHistoryEntry>>(StatusCode::OK, None)
+        .add_error::<UserNotFoundError>()
+        .with(auth_error_docs)
+        .with(internal_server_error_docs)
+}
+
 async fn verify_newsletter_subscription(
     service: State<Arc<impl UserFeatureService>>,
     token: ApiToken,
@@ -483,6 +978,7 @@ fn request_password_reset_docs(op: TransformOperation) -> TransformOperation {
             "The user has been sent a password reset email.",
         )
         .add_error::<RecaptchaFailedError>()
+        .with(rate_limit_docs)
         .with(internal_server_error_docs)
 }
 
@@ -495,15 +991,20 @@ struct ResetPasswordRequest {
 
 async fn reset_password(
     service: State<Arc<impl UserFeatureService>>,
+    audit: ApiAuditContext,
     Json(ResetPasswordRequest {
         email,
         code,
         password,
     }): Json<ResetPasswordRequest>,
 ) -> Response {
-    match service.reset_password(email, code, password).await {
+    match service
+        .reset_password(email, code, password, &audit.0)
+        .await
+    {
         Ok(user) => Json(ApiUser::from(user)).into_response(),
         Err(UserResetPasswordError::Failed) => PasswordResetFailedError.into_response(),
+        Err(UserResetPasswordError::PasswordPolicy(violation)) => password_policy_error(violation),
         Err(UserResetPasswordError::Other(err)) => internal_server_error(err),
     }
 }
@@ -512,9 +1013,99 @@ fn reset_password_docs(op: TransformOperation) -> TransformOperation {
     op.summary("Reset a user's password using a password reset verification code.")
         .add_response::<ApiUser>(StatusCode::OK, "The user's password has been changed.")
         .add_error::<PasswordResetFailedError>()
+        .with(password_policy_error_docs)
+        .with(rate_limit_docs)
+        .with(internal_server_error_docs)
+}
+
+async fn request_export(
+    service: State<Arc<impl UserFeatureService>>,
+    token: ApiToken,
+    audit: ApiAuditContext,
+    Path(PathUserIdOrSelf { user_id }): Path<PathUserIdOrSelf>,
+) -> Response {
+    match service
+        .request_user_export(&token.0, user_id.into(), &audit.0)
+        .await
+    {
+        Ok(()) => Json(OkResponse).into_response(),
+        Err(UserRequestExportError::NotFound) => UserNotFoundError.into_response(),
+        Err(UserRequestExportError::NoEmail) => NoEmailError.into_response(),
+        Err(UserRequestExportError::Auth(err)) => auth_error(err),
+        Err(UserRequestExportError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn request_export_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Request an export of all data stored about the given user.")
+        .description(
+            "The export is generated in the background. Once it is ready, the user is sent an \
+             email with a link to download it.",
+        )
+        .add_response::<OkResponse>(StatusCode::OK, "The export has been requested.")
+        .add_error::<UserNotFoundError>()
+        .add_error::<NoEmailError>()
+        .with(auth_error_docs)
+        .with(rate_limit_docs)
+        .with(internal_server_error_docs)
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct GetExportPath {
+    user_id: UserId,
+    code: VerificationCode,
+}
+
+async fn get_export(
+    service: State<Arc<impl UserFeatureService>>,
+    Path(GetExportPath { user_id, code }): Path<GetExportPath>,
+) -> Response {
+    match service.get_user_export(user_id, code).await {
+        Ok(export) => Json(ApiUserExport::from(export)).into_response(),
+        Err(UserGetExportError::InvalidCode) => InvalidExportCodeError.into_response(),
+        Err(UserGetExportError::Other(err)) => internal_server_error(err),
+    }
+}
+
+fn get_export_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Download a user's data export using the code sent via email.")
+        .add_response::<ApiUserExport>(StatusCode::OK, None)
+        .add_error::<InvalidExportCodeError>()
         .with(internal_server_error_docs)
 }
 
+fn name_policy_error(violation: UserNamePolicyViolation) -> Response {
+    match violation {
+        UserNamePolicyViolation::Reserved => NameReservedError.into_response(),
+        UserNamePolicyViolation::Blocked => NameBlockedError.into_response(),
+        UserNamePolicyViolation::Quarantined { .. } => NameQuarantinedError.into_response(),
+    }
+}
+
+fn name_policy_error_docs(op: TransformOperation) -> TransformOperation {
+    op.add_error::<NameReservedError>()
+        .add_error::<NameBlockedError>()
+        .add_error::<NameQuarantinedError>()
+}
+
+fn password_policy_error(violation: UserPasswordPolicyViolation) -> Response {
+    match violation {
+        UserPasswordPolicyViolation::TooShort { .. } => PasswordTooShortError.into_response(),
+        UserPasswordPolicyViolation::TooWeak { .. } => PasswordTooWeakError.into_response(),
+        UserPasswordPolicyViolation::MatchesUserData => {
+            PasswordMatchesUserDataError.into_response()
+        }
+        UserPasswordPolicyViolation::Breached => PasswordBreachedError.into_response(),
+    }
+}
+
+fn password_policy_error_docs(op: TransformOperation) -> TransformOperation {
+    op.add_error::<PasswordTooShortError>()
+        .add_error::<PasswordTooWeakError>()
+        .add_error::<PasswordMatchesUserDataError>()
+        .add_error::<PasswordBreachedError>()
+}
+
 error_code! {
     /// The user does not exist.
     pub UserNotFoundError(NOT_FOUND, "User not found");
@@ -546,4 +1137,33 @@ error_code! {
     InvalidEmailError(BAD_REQUEST, "Invalid email");
     /// Only the email address of the currently authenticated user can be verified.
     CanOnlyVerifyEmailForSelfError(BAD_REQUEST, "Can only verify email for self");
+    /// The password is shorter than the configured minimum length.
+    PasswordTooShortError(BAD_REQUEST, "Password too short");
+    /// The password does not contain enough different character classes (lowercase letters,
+    /// uppercase letters, digits and other characters).
+    PasswordTooWeakError(BAD_REQUEST, "Password too weak");
+    /// The password matches the user's name or email address.
+    PasswordMatchesUserDataError(BAD_REQUEST, "Password matches user data");
+    /// The password has appeared in a known data breach.
+    PasswordBreachedError(BAD_REQUEST, "Password breached");
+    /// The name is reserved.
+    NameReservedError(BAD_REQUEST, "Name reserved");
+    /// The name is not allowed.
+    NameBlockedError(BAD_REQUEST, "Name blocked");
+    /// The name has recently been used by another user and cannot be used yet.
+    NameQuarantinedError(CONFLICT, "Name quarantined");
+    /// The export code is invalid or the export has expired.
+    InvalidExportCodeError(UNAUTHORIZED, "Invalid export code");
+    /// The restore code is invalid or the deletion grace period has expired.
+    InvalidRestoreCodeError(UNAUTHORIZED, "Invalid restore code");
+    /// The user does not have this additional email address.
+    EmailNotFoundError(NOT_FOUND, "Email not found");
+    /// The request does not contain an `avatar` field.
+    MissingAvatarError(BAD_REQUEST, "Missing avatar");
+    /// The avatar image exceeds the maximum file size or dimensions.
+    AvatarTooLargeError(PAYLOAD_TOO_LARGE, "Avatar too large");
+    /// The format of the avatar image is not supported.
+    UnsupportedAvatarFormatError(UNSUPPORTED_MEDIA_TYPE, "Unsupported avatar format");
+    /// The avatar image could not be decoded.
+    InvalidAvatarError(BAD_REQUEST, "Invalid avatar");
 }
@@ -1,7 +1,8 @@
-use std::future::Future;
+use std::{collections::BTreeSet, future::Future};
 
 use academy_models::{
     auth::AccessToken,
+    role::Permission,
     session::{SessionId, SessionRefreshTokenHash},
     user::User,
 };
@@ -14,6 +15,7 @@ pub trait AuthAccessTokenService: Send + Sync + 'static {
     fn issue(
         &self,
         user: &User,
+        permissions: &BTreeSet<Permission>,
         session_id: SessionId,
         refresh_token_hash: SessionRefreshTokenHash,
     ) -> anyhow::Result<AccessToken>;
@@ -39,6 +41,7 @@ impl MockAuthAccessTokenService {
     pub fn with_issue(
         mut self,
         user: User,
+        permissions: BTreeSet<Permission>,
         session_id: SessionId,
         refresh_token_hash: SessionRefreshTokenHash,
         result: AccessToken,
@@ -47,10 +50,11 @@ impl MockAuthAccessTokenService {
             .once()
             .with(
                 mockall::predicate::eq(user),
+                mockall::predicate::eq(permissions),
                 mockall::predicate::eq(session_id),
                 mockall::predicate::eq(refresh_token_hash),
             )
-            .return_once(|_, _, _| Ok(result));
+            .return_once(|_, _, _, _| Ok(result));
         self
     }
 
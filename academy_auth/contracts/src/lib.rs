@@ -1,7 +1,9 @@
-use std::future::Future;
+use std::{collections::BTreeSet, future::Future};
 
 use academy_models::{
     auth::{AccessToken, AuthError, AuthenticateError, AuthorizeError, RefreshToken},
+    personal_access_token::PersonalAccessTokenId,
+    role::Permission,
     session::{SessionId, SessionRefreshTokenHash},
     user::{User, UserId, UserPassword},
 };
@@ -9,17 +11,32 @@ use thiserror::Error;
 
 pub mod access_token;
 pub mod internal;
+pub mod personal_access_token;
 pub mod refresh_token;
 
 #[cfg_attr(feature = "mock", mockall::automock)]
 pub trait AuthService<Txn: Send + Sync + 'static>: Send + Sync + 'static {
-    /// Authenticates a user using an access token.
+    /// Authenticates a user using an access token or a personal access token.
+    ///
+    /// Personal access tokens with the `read_only` scope are rejected.
     fn authenticate(
         &self,
         token: &AccessToken,
     ) -> impl Future<Output = Result<Authentication, AuthenticateError>> + Send;
 
+    /// Authenticates a user for a request which does not modify any data.
+    ///
+    /// Unlike [`AuthService::authenticate`], this also accepts personal access
+    /// tokens with the `read_only` scope.
+    fn authenticate_read(
+        &self,
+        token: &AccessToken,
+    ) -> impl Future<Output = Result<Authentication, AuthenticateError>> + Send;
+
     /// Authenticates a user using their account password.
+    ///
+    /// If the stored password hash uses an outdated algorithm or outdated
+    /// parameters, it is replaced by a new hash of the given password.
     fn authenticate_by_password(
         &self,
         txn: &mut Txn,
@@ -35,7 +52,15 @@ pub trait AuthService<Txn: Send + Sync + 'static>: Send + Sync + 'static {
     ) -> impl Future<Output = Result<SessionId, AuthenticateByRefreshTokenError>> + Send;
 
     /// Issues an access and refresh token for a given user and session.
-    fn issue_tokens(&self, user: &User, session_id: SessionId) -> anyhow::Result<Tokens>;
+    ///
+    /// The access token includes the permissions granted to the user via their
+    /// roles.
+    fn issue_tokens(
+        &self,
+        txn: &mut Txn,
+        user: &User,
+        session_id: SessionId,
+    ) -> impl Future<Output = anyhow::Result<Tokens>> + Send;
 
     /// Invalidates all previously issued access tokens of a user.
     fn invalidate_access_tokens(
@@ -52,13 +77,25 @@ pub struct Tokens {
     pub refresh_token_hash: SessionRefreshTokenHash,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Authentication {
     pub user_id: UserId,
-    pub session_id: SessionId,
-    pub refresh_token_hash: SessionRefreshTokenHash,
+    pub source: AuthenticationSource,
     pub admin: bool,
     pub email_verified: bool,
+    pub permissions: BTreeSet<Permission>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthenticationSource {
+    /// The user has been authenticated using an access token issued for a
+    /// session.
+    Session {
+        session_id: SessionId,
+        refresh_token_hash: SessionRefreshTokenHash,
+    },
+    /// The user has been authenticated using a personal access token.
+    PersonalAccessToken(PersonalAccessTokenId),
 }
 
 #[derive(Debug, Error)]
@@ -75,6 +112,8 @@ pub enum AuthenticateByRefreshTokenError {
     Invalid,
     #[error("The refresh token has expired")]
     Expired(SessionId),
+    #[error("The refresh token has already been used and was replaced by a new one")]
+    Reused(SessionId),
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -93,6 +132,16 @@ impl Authentication {
             .ok_or(AuthorizeError::EmailVerified)
     }
 
+    /// Return the id of the authenticated session or an error if the user has
+    /// not been authenticated using a session (e.g. using a personal access
+    /// token instead).
+    pub fn ensure_session(&self) -> Result<SessionId, AuthorizeError> {
+        match self.source {
+            AuthenticationSource::Session { session_id, .. } => Ok(session_id),
+            AuthenticationSource::PersonalAccessToken(_) => Err(AuthorizeError::Session),
+        }
+    }
+
     /// Return an error if the authenticated user is neither the same as the one
     /// identified by the given `user_id` nor an administrator.
     pub fn ensure_self_or_admin(&self, user_id: UserId) -> Result<(), AuthorizeError> {
@@ -100,6 +149,35 @@ impl Authentication {
             .then_some(())
             .ok_or(AuthorizeError::Admin)
     }
+
+    /// Return whether the authenticated user has been granted the given
+    /// permission. Administrators implicitly have all permissions.
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.admin || self.permissions.contains(&permission)
+    }
+
+    /// Return an error if the authenticated user has not been granted the
+    /// given permission.
+    pub fn ensure_permission(&self, permission: Permission) -> Result<(), AuthorizeError> {
+        self.has_permission(permission)
+            .then_some(())
+            .ok_or(AuthorizeError::Permission(permission))
+    }
+
+    /// Return an error if the authenticated user is neither the same as the one
+    /// identified by the given `user_id` nor has been granted the given
+    /// permission.
+    pub fn ensure_self_or_permission(
+        &self,
+        user_id: UserId,
+        permission: Permission,
+    ) -> Result<(), AuthorizeError> {
+        if self.user_id == user_id {
+            Ok(())
+        } else {
+            self.ensure_permission(permission)
+        }
+    }
 }
 
 pub trait AuthResultExt<T> {
@@ -122,23 +200,95 @@ impl<Txn: Send + Sync + 'static> MockAuthService<Txn> {
         auth: Option<(User, academy_models::session::Session)>,
     ) -> Self {
         self.expect_authenticate()
+            .once()
+            .with(mockall::predicate::eq(AccessToken::new("token")))
+            .return_once(|_| Box::pin(std::future::ready(mock_session_authentication(auth))));
+        self
+    }
+
+    pub fn with_authenticate_read(
+        mut self,
+        auth: Option<(User, academy_models::session::Session)>,
+    ) -> Self {
+        self.expect_authenticate_read()
+            .once()
+            .with(mockall::predicate::eq(AccessToken::new("token")))
+            .return_once(|_| Box::pin(std::future::ready(mock_session_authentication(auth))));
+        self
+    }
+
+    pub fn with_authenticate_personal_access_token(
+        mut self,
+        auth: Option<(
+            User,
+            academy_models::personal_access_token::PersonalAccessToken,
+        )>,
+    ) -> Self {
+        self.expect_authenticate()
+            .once()
+            .with(mockall::predicate::eq(AccessToken::new("token")))
+            .return_once(|_| {
+                Box::pin(std::future::ready(
+                    mock_personal_access_token_authentication(auth, false),
+                ))
+            });
+        self
+    }
+
+    pub fn with_authenticate_read_personal_access_token(
+        mut self,
+        auth: Option<(
+            User,
+            academy_models::personal_access_token::PersonalAccessToken,
+        )>,
+    ) -> Self {
+        self.expect_authenticate_read()
             .once()
             .with(mockall::predicate::eq(AccessToken::new("token")))
             .return_once(|_| {
                 Box::pin(std::future::ready(
-                    auth.map(|(user, session)| Authentication {
-                        user_id: user.id,
-                        session_id: session.id,
-                        refresh_token_hash: SessionRefreshTokenHash::new(Default::default()),
-                        admin: user.admin,
-                        email_verified: user.email_verified,
-                    })
-                    .ok_or(AuthenticateError::InvalidToken),
+                    mock_personal_access_token_authentication(auth, true),
                 ))
             });
         self
     }
 
+    pub fn with_authenticate_permissions(
+        mut self,
+        user: User,
+        session: academy_models::session::Session,
+        permissions: BTreeSet<Permission>,
+    ) -> Self {
+        self.expect_authenticate()
+            .once()
+            .with(mockall::predicate::eq(AccessToken::new("token")))
+            .return_once(move |_| {
+                Box::pin(std::future::ready(Ok(Authentication {
+                    permissions,
+                    ..mock_session_authentication(Some((user, session))).unwrap()
+                })))
+            });
+        self
+    }
+
+    pub fn with_authenticate_read_permissions(
+        mut self,
+        user: User,
+        session: academy_models::session::Session,
+        permissions: BTreeSet<Permission>,
+    ) -> Self {
+        self.expect_authenticate_read()
+            .once()
+            .with(mockall::predicate::eq(AccessToken::new("token")))
+            .return_once(move |_| {
+                Box::pin(std::future::ready(Ok(Authentication {
+                    permissions,
+                    ..mock_session_authentication(Some((user, session))).unwrap()
+                })))
+            });
+        self
+    }
+
     pub fn with_authenticate_by_password(
         mut self,
         user_id: UserId,
@@ -178,10 +328,11 @@ impl<Txn: Send + Sync + 'static> MockAuthService<Txn> {
         self.expect_issue_tokens()
             .once()
             .with(
+                mockall::predicate::always(),
                 mockall::predicate::eq(user),
                 mockall::predicate::eq(session_id),
             )
-            .return_once(|_, _| Ok(tokens));
+            .return_once(|_, _, _| Box::pin(std::future::ready(Ok(tokens))));
         self
     }
 
@@ -196,3 +347,44 @@ impl<Txn: Send + Sync + 'static> MockAuthService<Txn> {
         self
     }
 }
+
+#[cfg(feature = "mock")]
+fn mock_session_authentication(
+    auth: Option<(User, academy_models::session::Session)>,
+) -> Result<Authentication, AuthenticateError> {
+    auth.map(|(user, session)| Authentication {
+        user_id: user.id,
+        source: AuthenticationSource::Session {
+            session_id: session.id,
+            refresh_token_hash: SessionRefreshTokenHash::new(Default::default()),
+        },
+        admin: user.admin,
+        email_verified: user.email_verified,
+        permissions: Default::default(),
+    })
+    .ok_or(AuthenticateError::InvalidToken)
+}
+
+#[cfg(feature = "mock")]
+fn mock_personal_access_token_authentication(
+    auth: Option<(
+        User,
+        academy_models::personal_access_token::PersonalAccessToken,
+    )>,
+    read: bool,
+) -> Result<Authentication, AuthenticateError> {
+    use academy_models::personal_access_token::PersonalAccessTokenScope;
+
+    let (user, token) = auth.ok_or(AuthenticateError::InvalidToken)?;
+    if !read && token.scopes.contains(&PersonalAccessTokenScope::ReadOnly) {
+        return Err(AuthenticateError::ReadOnlyToken);
+    }
+
+    Ok(Authentication {
+        user_id: user.id,
+        source: AuthenticationSource::PersonalAccessToken(token.id),
+        admin: user.admin && token.scopes.contains(&PersonalAccessTokenScope::Admin),
+        email_verified: user.email_verified,
+        permissions: Default::default(),
+    })
+}
@@ -0,0 +1,35 @@
+use academy_models::{auth::AccessToken, personal_access_token::PersonalAccessTokenHash};
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait AuthPersonalAccessTokenService: Send + Sync + 'static {
+    /// Generate a new random personal access token and return it together
+    /// with its hash.
+    fn issue(&self) -> (AccessToken, PersonalAccessTokenHash);
+
+    /// Return the hash of the given token or `None` if it is not a personal
+    /// access token.
+    fn hash(&self, token: &AccessToken) -> Option<PersonalAccessTokenHash>;
+}
+
+#[cfg(feature = "mock")]
+impl MockAuthPersonalAccessTokenService {
+    pub fn with_issue(mut self, token: AccessToken, hash: PersonalAccessTokenHash) -> Self {
+        self.expect_issue()
+            .once()
+            .with()
+            .return_once(move || (token, hash));
+        self
+    }
+
+    pub fn with_hash(
+        mut self,
+        token: AccessToken,
+        result: Option<PersonalAccessTokenHash>,
+    ) -> Self {
+        self.expect_hash()
+            .once()
+            .with(mockall::predicate::eq(token))
+            .return_once(move |_| result);
+        self
+    }
+}
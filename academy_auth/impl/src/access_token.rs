@@ -1,8 +1,13 @@
-use academy_auth_contracts::{access_token::AuthAccessTokenService, Authentication};
+use std::collections::BTreeSet;
+
+use academy_auth_contracts::{
+    access_token::AuthAccessTokenService, Authentication, AuthenticationSource,
+};
 use academy_cache_contracts::CacheService;
 use academy_di::Build;
 use academy_models::{
     auth::AccessToken,
+    role::Permission,
     session::{SessionId, SessionRefreshTokenHash},
     user::{User, UserId},
 };
@@ -30,19 +35,23 @@ where
     fn issue(
         &self,
         user: &User,
+        permissions: &BTreeSet<Permission>,
         session_id: SessionId,
         refresh_token_hash: SessionRefreshTokenHash,
     ) -> anyhow::Result<AccessToken> {
-        let auth = Authentication {
-            user_id: user.id,
-            session_id,
-            refresh_token_hash,
-            admin: user.admin,
-            email_verified: user.email_verified,
+        let token = Token {
+            uid: user.id,
+            sid: session_id,
+            rt: refresh_token_hash,
+            data: TokenData {
+                admin: user.admin,
+                email_verified: user.email_verified,
+                permissions: permissions.clone(),
+            },
         };
 
         self.jwt
-            .sign(Token::from(auth), self.config.access_token_ttl)
+            .sign(token, self.config.access_token_ttl)
             .context("Failed to sign JWT")
     }
 
@@ -83,7 +92,7 @@ where
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 struct Token {
     uid: UserId,
     sid: SessionId,
@@ -91,34 +100,25 @@ struct Token {
     data: TokenData,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 struct TokenData {
     admin: bool,
     email_verified: bool,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    permissions: BTreeSet<Permission>,
 }
 
 impl From<Token> for Authentication {
     fn from(value: Token) -> Self {
         Self {
             user_id: value.uid,
-            session_id: value.sid,
-            refresh_token_hash: value.rt,
+            source: AuthenticationSource::Session {
+                session_id: value.sid,
+                refresh_token_hash: value.rt,
+            },
             admin: value.data.admin,
             email_verified: value.data.email_verified,
-        }
-    }
-}
-
-impl From<Authentication> for Token {
-    fn from(value: Authentication) -> Self {
-        Self {
-            uid: value.user_id,
-            sid: value.session_id,
-            rt: value.refresh_token_hash,
-            data: TokenData {
-                admin: value.admin,
-                email_verified: value.email_verified,
-            },
+            permissions: value.data.permissions,
         }
     }
 }
@@ -147,16 +147,8 @@ mod tests {
 
         let expected = "the access token";
 
-        let auth = Authentication {
-            user_id: FOO.user.id,
-            session_id: UUID1.into(),
-            refresh_token_hash: (*SHA256HASH1).into(),
-            admin: FOO.user.admin,
-            email_verified: FOO.user.email_verified,
-        };
-
         let jwt = MockJwtService::new().with_sign(
-            Token::from(auth),
+            token(),
             config.access_token_ttl,
             Ok(AccessToken::new(expected)),
         );
@@ -167,7 +159,12 @@ mod tests {
         };
 
         // Act
-        let result = sut.issue(&FOO.user, UUID1.into(), (*SHA256HASH1).into());
+        let result = sut.issue(
+            &FOO.user,
+            &[Permission::ReadUsers].into(),
+            UUID1.into(),
+            (*SHA256HASH1).into(),
+        );
 
         // Assert
         assert_eq!(result.unwrap().into_inner(), expected);
@@ -180,14 +177,16 @@ mod tests {
 
         let expected = Authentication {
             user_id: FOO.user.id,
-            session_id: UUID1.into(),
-            refresh_token_hash: (*SHA256HASH1).into(),
+            source: AuthenticationSource::Session {
+                session_id: UUID1.into(),
+                refresh_token_hash: (*SHA256HASH1).into(),
+            },
             admin: FOO.user.admin,
             email_verified: FOO.user.email_verified,
+            permissions: [Permission::ReadUsers].into(),
         };
 
-        let jwt =
-            MockJwtService::new().with_verify(AccessToken::new(token), Ok(Token::from(expected)));
+        let jwt = MockJwtService::new().with_verify(AccessToken::new(token), Ok(self::token()));
 
         let sut = AuthAccessTokenServiceImpl {
             jwt,
@@ -227,17 +226,9 @@ mod tests {
         // Arrange
         let token = "the access token";
 
-        let auth = Authentication {
-            user_id: FOO.user.id,
-            session_id: UUID1.into(),
-            refresh_token_hash: (*SHA256HASH1).into(),
-            admin: FOO.user.admin,
-            email_verified: FOO.user.email_verified,
-        };
-
         let jwt = MockJwtService::new().with_verify(
             AccessToken::new(token),
-            Err(VerifyJwtError::Expired(Token::from(auth))),
+            Err(VerifyJwtError::Expired(self::token())),
         );
 
         let sut = AuthAccessTokenServiceImpl {
@@ -314,4 +305,17 @@ mod tests {
         // Assert
         assert!(!result.unwrap());
     }
+
+    fn token() -> Token {
+        Token {
+            uid: FOO.user.id,
+            sid: UUID1.into(),
+            rt: (*SHA256HASH1).into(),
+            data: TokenData {
+                admin: FOO.user.admin,
+                email_verified: FOO.user.email_verified,
+                permissions: [Permission::ReadUsers].into(),
+            },
+        }
+    }
 }
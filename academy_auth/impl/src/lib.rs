@@ -1,16 +1,22 @@
-use std::time::Duration;
+use std::{collections::BTreeSet, time::Duration};
 
 use academy_auth_contracts::{
-    access_token::AuthAccessTokenService, refresh_token::AuthRefreshTokenService, AuthService,
-    AuthenticateByPasswordError, AuthenticateByRefreshTokenError, Authentication, Tokens,
+    access_token::AuthAccessTokenService, personal_access_token::AuthPersonalAccessTokenService,
+    refresh_token::AuthRefreshTokenService, AuthService, AuthenticateByPasswordError,
+    AuthenticateByRefreshTokenError, Authentication, AuthenticationSource, Tokens,
 };
 use academy_di::Build;
 use academy_models::{
     auth::{AccessToken, AuthenticateError, RefreshToken},
+    personal_access_token::{PersonalAccessTokenHash, PersonalAccessTokenScope},
+    role::Permission,
     session::SessionId,
     user::{User, UserId, UserPassword},
 };
-use academy_persistence_contracts::{session::SessionRepository, user::UserRepository};
+use academy_persistence_contracts::{
+    personal_access_token::PersonalAccessTokenRepository, role::RoleRepository,
+    session::SessionRepository, user::UserRepository, Database, Transaction,
+};
 use academy_shared_contracts::{
     password::{PasswordService, PasswordVerifyError},
     time::TimeService,
@@ -21,21 +27,40 @@ use tracing::trace;
 
 pub mod access_token;
 pub mod internal;
+pub mod personal_access_token;
 pub mod refresh_token;
 
 #[cfg(test)]
 mod tests;
 
+/// Minimum time between two updates of the `last_used_at` timestamp of a
+/// personal access token.
+const PERSONAL_ACCESS_TOKEN_LAST_USED_AT_RESOLUTION: Duration = Duration::from_secs(60);
+
 #[derive(Debug, Clone, Build)]
 #[cfg_attr(test, derive(Default))]
-pub struct AuthServiceImpl<Time, Password, UserRepo, SessionRepo, AuthAccessToken, AuthRefreshToken>
-{
+pub struct AuthServiceImpl<
+    Db,
+    Time,
+    Password,
+    UserRepo,
+    SessionRepo,
+    PersonalAccessTokenRepo,
+    RoleRepo,
+    AuthAccessToken,
+    AuthRefreshToken,
+    AuthPersonalAccessToken,
+> {
+    db: Db,
     time: Time,
     password: Password,
     user_repo: UserRepo,
     session_repo: SessionRepo,
+    personal_access_token_repo: PersonalAccessTokenRepo,
+    role_repo: RoleRepo,
     auth_access_token: AuthAccessToken,
     auth_refresh_token: AuthRefreshToken,
+    auth_personal_access_token: AuthPersonalAccessToken,
     config: AuthServiceConfig,
 }
 
@@ -47,41 +72,59 @@ pub struct AuthServiceConfig {
     pub internal_token_ttl: Duration,
 }
 
-impl<Txn, Time, Password, UserRepo, SessionRepo, AuthAccessToken, AuthRefreshToken> AuthService<Txn>
-    for AuthServiceImpl<Time, Password, UserRepo, SessionRepo, AuthAccessToken, AuthRefreshToken>
+impl<
+        Db,
+        Time,
+        Password,
+        UserRepo,
+        SessionRepo,
+        PersonalAccessTokenRepo,
+        RoleRepo,
+        AuthAccessToken,
+        AuthRefreshToken,
+        AuthPersonalAccessToken,
+    > AuthService<Db::Transaction>
+    for AuthServiceImpl<
+        Db,
+        Time,
+        Password,
+        UserRepo,
+        SessionRepo,
+        PersonalAccessTokenRepo,
+        RoleRepo,
+        AuthAccessToken,
+        AuthRefreshToken,
+        AuthPersonalAccessToken,
+    >
 where
-    Txn: Send + Sync + 'static,
+    Db: Database,
     Time: TimeService,
     Password: PasswordService,
-    UserRepo: UserRepository<Txn>,
-    SessionRepo: SessionRepository<Txn>,
+    UserRepo: UserRepository<Db::Transaction>,
+    SessionRepo: SessionRepository<Db::Transaction>,
+    PersonalAccessTokenRepo: PersonalAccessTokenRepository<Db::Transaction>,
+    RoleRepo: RoleRepository<Db::Transaction>,
     AuthAccessToken: AuthAccessTokenService,
     AuthRefreshToken: AuthRefreshTokenService,
+    AuthPersonalAccessToken: AuthPersonalAccessTokenService,
 {
     #[trace_instrument(skip(self))]
     async fn authenticate(&self, token: &AccessToken) -> Result<Authentication, AuthenticateError> {
-        let auth = self
-            .auth_access_token
-            .verify(token)
-            .ok_or(AuthenticateError::InvalidToken)?;
-
-        if self
-            .auth_access_token
-            .is_invalidated(auth.refresh_token_hash)
-            .await
-            .context("Failed to check whether access token has been invalidated")?
-        {
-            trace!(?auth, "token invalidated");
-            return Err(AuthenticateError::InvalidToken);
-        }
+        self.authenticate_token(token, false).await
+    }
 
-        Ok(auth)
+    #[trace_instrument(skip(self))]
+    async fn authenticate_read(
+        &self,
+        token: &AccessToken,
+    ) -> Result<Authentication, AuthenticateError> {
+        self.authenticate_token(token, true).await
     }
 
     #[trace_instrument(skip(self, txn))]
     async fn authenticate_by_password(
         &self,
-        txn: &mut Txn,
+        txn: &mut Db::Transaction,
         user_id: UserId,
         password: UserPassword,
     ) -> Result<(), AuthenticateByPasswordError> {
@@ -94,7 +137,7 @@ where
             .inspect_err(|_| trace!("no password set"))?;
 
         self.password
-            .verify(password.into_inner().into(), password_hash)
+            .verify(password.clone().into_inner().into(), password_hash.clone())
             .await
             .map_err(|err| match err {
                 PasswordVerifyError::InvalidPassword => {
@@ -104,24 +147,48 @@ where
                 PasswordVerifyError::Other(err) => {
                     err.context("Failed to verify password against hash").into()
                 }
-            })
+            })?;
+
+        if self.password.needs_rehash(&password_hash) {
+            trace!("rehash password");
+            let password_hash = self
+                .password
+                .hash(password.into_inner().into())
+                .await
+                .context("Failed to rehash password")?;
+            self.user_repo
+                .save_password_hash(txn, user_id, password_hash)
+                .await
+                .context("Failed to save password hash in database")?;
+        }
+
+        Ok(())
     }
 
     #[trace_instrument(skip(self, txn))]
     async fn authenticate_by_refresh_token(
         &self,
-        txn: &mut Txn,
+        txn: &mut Db::Transaction,
         refresh_token: &RefreshToken,
     ) -> Result<SessionId, AuthenticateByRefreshTokenError> {
         let refresh_token_hash = self.auth_refresh_token.hash(refresh_token);
 
-        let session = self
+        let Some(session) = self
             .session_repo
             .get_by_refresh_token_hash(txn, refresh_token_hash)
             .await
             .context("Failed to get session from database")?
-            .ok_or(AuthenticateByRefreshTokenError::Invalid)
-            .inspect_err(|_| trace!("no session"))?;
+        else {
+            let session = self
+                .session_repo
+                .get_by_superseded_refresh_token_hash(txn, refresh_token_hash)
+                .await
+                .context("Failed to get session from database")?
+                .ok_or(AuthenticateByRefreshTokenError::Invalid)
+                .inspect_err(|_| trace!("no session"))?;
+            trace!("refresh token reused");
+            return Err(AuthenticateByRefreshTokenError::Reused(session.id));
+        };
 
         let now = self.time.now();
         if now >= session.updated_at + self.config.refresh_token_ttl {
@@ -132,13 +199,23 @@ where
         Ok(session.id)
     }
 
-    #[trace_instrument(skip(self))]
-    fn issue_tokens(&self, user: &User, session_id: SessionId) -> anyhow::Result<Tokens> {
+    #[trace_instrument(skip(self, txn))]
+    async fn issue_tokens(
+        &self,
+        txn: &mut Db::Transaction,
+        user: &User,
+        session_id: SessionId,
+    ) -> anyhow::Result<Tokens> {
+        let permissions = self
+            .user_permissions(txn, user.id)
+            .await
+            .context("Failed to get user permissions")?;
+
         let refresh_token = self.auth_refresh_token.issue();
         let refresh_token_hash = self.auth_refresh_token.hash(&refresh_token);
         let access_token = self
             .auth_access_token
-            .issue(user, session_id, refresh_token_hash)
+            .issue(user, &permissions, session_id, refresh_token_hash)
             .context("Failed to issue access token")?;
 
         Ok(Tokens {
@@ -149,7 +226,11 @@ where
     }
 
     #[trace_instrument(skip(self, txn))]
-    async fn invalidate_access_tokens(&self, txn: &mut Txn, user_id: UserId) -> anyhow::Result<()> {
+    async fn invalidate_access_tokens(
+        &self,
+        txn: &mut Db::Transaction,
+        user_id: UserId,
+    ) -> anyhow::Result<()> {
         for refresh_token_hash in self
             .session_repo
             .list_refresh_token_hashes_by_user(txn, user_id)
@@ -165,3 +246,152 @@ where
         Ok(())
     }
 }
+
+impl<
+        Db,
+        Time,
+        Password,
+        UserRepo,
+        SessionRepo,
+        PersonalAccessTokenRepo,
+        RoleRepo,
+        AuthAccessToken,
+        AuthRefreshToken,
+        AuthPersonalAccessToken,
+    >
+    AuthServiceImpl<
+        Db,
+        Time,
+        Password,
+        UserRepo,
+        SessionRepo,
+        PersonalAccessTokenRepo,
+        RoleRepo,
+        AuthAccessToken,
+        AuthRefreshToken,
+        AuthPersonalAccessToken,
+    >
+where
+    Db: Database,
+    Time: TimeService,
+    UserRepo: UserRepository<Db::Transaction>,
+    PersonalAccessTokenRepo: PersonalAccessTokenRepository<Db::Transaction>,
+    RoleRepo: RoleRepository<Db::Transaction>,
+    AuthAccessToken: AuthAccessTokenService,
+    AuthPersonalAccessToken: AuthPersonalAccessTokenService,
+{
+    async fn authenticate_token(
+        &self,
+        token: &AccessToken,
+        read: bool,
+    ) -> Result<Authentication, AuthenticateError> {
+        if let Some(hash) = self.auth_personal_access_token.hash(token) {
+            return self.authenticate_by_personal_access_token(hash, read).await;
+        }
+
+        let auth = self
+            .auth_access_token
+            .verify(token)
+            .ok_or(AuthenticateError::InvalidToken)?;
+
+        let AuthenticationSource::Session {
+            refresh_token_hash, ..
+        } = auth.source
+        else {
+            return Err(AuthenticateError::InvalidToken);
+        };
+
+        if self
+            .auth_access_token
+            .is_invalidated(refresh_token_hash)
+            .await
+            .context("Failed to check whether access token has been invalidated")?
+        {
+            trace!(?auth, "token invalidated");
+            return Err(AuthenticateError::InvalidToken);
+        }
+
+        Ok(auth)
+    }
+
+    async fn authenticate_by_personal_access_token(
+        &self,
+        hash: PersonalAccessTokenHash,
+        read: bool,
+    ) -> Result<Authentication, AuthenticateError> {
+        let mut txn = self.db.begin_transaction().await?;
+
+        let token = self
+            .personal_access_token_repo
+            .get_by_hash(&mut txn, hash)
+            .await
+            .context("Failed to get personal access token from database")?
+            .ok_or(AuthenticateError::InvalidToken)
+            .inspect_err(|_| trace!("no personal access token"))?;
+
+        let now = self.time.now();
+        if token.is_expired(now) {
+            trace!(?token, "personal access token expired");
+            return Err(AuthenticateError::InvalidToken);
+        }
+
+        if !read && token.scopes.contains(&PersonalAccessTokenScope::ReadOnly) {
+            trace!(?token, "read-only personal access token");
+            return Err(AuthenticateError::ReadOnlyToken);
+        }
+
+        let user_composite = self
+            .user_repo
+            .get_composite(&mut txn, token.user_id)
+            .await
+            .context("Failed to get user from database")?
+            .filter(|user_composite| user_composite.user.is_active())
+            .ok_or(AuthenticateError::InvalidToken)
+            .inspect_err(|_| trace!("user does not exist or is disabled"))?;
+
+        let user = user_composite.user;
+        let admin_scope = token.scopes.contains(&PersonalAccessTokenScope::Admin);
+        let permissions = if admin_scope {
+            self.user_permissions(&mut txn, user.id)
+                .await
+                .context("Failed to get user permissions")?
+        } else {
+            Default::default()
+        };
+
+        // avoid a database write on every request
+        if token.last_used_at.is_none_or(|last_used_at| {
+            now >= last_used_at + PERSONAL_ACCESS_TOKEN_LAST_USED_AT_RESOLUTION
+        }) {
+            self.personal_access_token_repo
+                .update_last_used_at(&mut txn, token.id, now)
+                .await
+                .context("Failed to update personal access token in database")?;
+
+            txn.commit().await?;
+        }
+
+        Ok(Authentication {
+            user_id: user.id,
+            source: AuthenticationSource::PersonalAccessToken(token.id),
+            admin: user.admin && admin_scope,
+            email_verified: user.email_verified,
+            permissions,
+        })
+    }
+
+    async fn user_permissions(
+        &self,
+        txn: &mut Db::Transaction,
+        user_id: UserId,
+    ) -> anyhow::Result<BTreeSet<Permission>> {
+        Ok(self
+            .role_repo
+            .list_by_user(txn, user_id)
+            .await
+            .context("Failed to get roles from database")?
+            .into_iter()
+            .flat_map(|role| role.permissions)
+            .collect())
+    }
+}
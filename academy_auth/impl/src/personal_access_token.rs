@@ -0,0 +1,96 @@
+use academy_auth_contracts::personal_access_token::AuthPersonalAccessTokenService;
+use academy_di::Build;
+use academy_models::{
+    auth::AccessToken,
+    personal_access_token::{PersonalAccessToken, PersonalAccessTokenHash},
+};
+use academy_shared_contracts::{hash::HashService, secret::SecretService};
+use academy_utils::trace_instrument;
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct AuthPersonalAccessTokenServiceImpl<Secret, Hash> {
+    secret: Secret,
+    hash: Hash,
+}
+
+impl<Secret, Hash> AuthPersonalAccessTokenService
+    for AuthPersonalAccessTokenServiceImpl<Secret, Hash>
+where
+    Secret: SecretService,
+    Hash: HashService,
+{
+    #[trace_instrument(skip(self))]
+    fn issue(&self) -> (AccessToken, PersonalAccessTokenHash) {
+        let secret = self.secret.generate(PersonalAccessToken::SECRET_LEN);
+        let token = AccessToken::new(format!("{}{}", PersonalAccessToken::PREFIX, *secret));
+        let hash = self.hash.sha256(&token).into();
+        (token, hash)
+    }
+
+    #[trace_instrument(skip(self))]
+    fn hash(&self, token: &AccessToken) -> Option<PersonalAccessTokenHash> {
+        token
+            .starts_with(PersonalAccessToken::PREFIX)
+            .then(|| self.hash.sha256(token).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_demo::SHA256HASH1;
+    use academy_shared_contracts::{hash::MockHashService, secret::MockSecretService};
+
+    use super::*;
+
+    type Sut = AuthPersonalAccessTokenServiceImpl<MockSecretService, MockHashService>;
+
+    #[test]
+    fn issue() {
+        // Arrange
+        let secret = MockSecretService::new()
+            .with_generate(PersonalAccessToken::SECRET_LEN, "the secret".into());
+
+        let hash =
+            MockHashService::new().with_sha256(AccessToken::new("bapat_the secret"), *SHA256HASH1);
+
+        let sut = AuthPersonalAccessTokenServiceImpl { secret, hash };
+
+        // Act
+        let result = sut.issue();
+
+        // Assert
+        assert_eq!(result, ("bapat_the secret".into(), (*SHA256HASH1).into()));
+    }
+
+    #[test]
+    fn hash() {
+        // Arrange
+        let token = "bapat_the secret";
+
+        let hash = MockHashService::new().with_sha256(AccessToken::new(token), *SHA256HASH1);
+
+        let sut = AuthPersonalAccessTokenServiceImpl {
+            hash,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.hash(&token.into());
+
+        // Assert
+        assert_eq!(result, Some((*SHA256HASH1).into()));
+    }
+
+    #[test]
+    fn hash_jwt() {
+        // Arrange
+        let sut = Sut::default();
+
+        // Act
+        let result = sut.hash(&"eyJhbGciOiJFZERTQSJ9.e30.c2lnbmF0dXJl".into());
+
+        // Assert
+        assert_eq!(result, None);
+    }
+}
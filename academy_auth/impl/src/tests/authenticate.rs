@@ -1,8 +1,25 @@
+use std::time::Duration;
+
 use academy_auth_contracts::{
-    access_token::MockAuthAccessTokenService, AuthService, Authentication,
+    access_token::MockAuthAccessTokenService,
+    personal_access_token::MockAuthPersonalAccessTokenService, AuthService, Authentication,
+    AuthenticationSource,
+};
+use academy_demo::{
+    personal_access_token::{ADMIN_PAT, FOO_PAT},
+    role::SUPPORT,
+    user::{ADMIN, BAR, FOO},
+    SHA256HASH1, UUID1,
+};
+use academy_models::{
+    auth::AuthenticateError,
+    personal_access_token::{PersonalAccessToken, PersonalAccessTokenScope},
 };
-use academy_demo::{user::FOO, SHA256HASH1, UUID1};
-use academy_models::auth::AuthenticateError;
+use academy_persistence_contracts::{
+    personal_access_token::MockPersonalAccessTokenRepository, role::MockRoleRepository,
+    user::MockUserRepository, MockDatabase,
+};
+use academy_shared_contracts::time::MockTimeService;
 use academy_utils::assert_matches;
 
 use crate::{tests::Sut, AuthServiceImpl};
@@ -12,18 +29,25 @@ async fn ok() {
     // Arrange
     let expected = Authentication {
         user_id: FOO.user.id,
-        session_id: UUID1.into(),
-        refresh_token_hash: (*SHA256HASH1).into(),
+        source: AuthenticationSource::Session {
+            session_id: UUID1.into(),
+            refresh_token_hash: (*SHA256HASH1).into(),
+        },
         admin: FOO.user.admin,
         email_verified: FOO.user.email_verified,
+        permissions: Default::default(),
     };
 
     let auth_access_token = MockAuthAccessTokenService::new()
-        .with_verify("my auth token".into(), Some(expected))
-        .with_is_invalidated(expected.refresh_token_hash, false);
+        .with_verify("my auth token".into(), Some(expected.clone()))
+        .with_is_invalidated((*SHA256HASH1).into(), false);
+
+    let auth_personal_access_token =
+        MockAuthPersonalAccessTokenService::new().with_hash("my auth token".into(), None);
 
     let sut = AuthServiceImpl {
         auth_access_token,
+        auth_personal_access_token,
         ..Sut::default()
     };
 
@@ -40,8 +64,12 @@ async fn invalid_token() {
     let auth_access_token =
         MockAuthAccessTokenService::new().with_verify("my auth token".into(), None);
 
+    let auth_personal_access_token =
+        MockAuthPersonalAccessTokenService::new().with_hash("my auth token".into(), None);
+
     let sut = AuthServiceImpl {
         auth_access_token,
+        auth_personal_access_token,
         ..Sut::default()
     };
 
@@ -57,18 +85,25 @@ async fn access_token_invalidated() {
     // Arrange
     let expected = Authentication {
         user_id: FOO.user.id,
-        session_id: UUID1.into(),
-        refresh_token_hash: (*SHA256HASH1).into(),
+        source: AuthenticationSource::Session {
+            session_id: UUID1.into(),
+            refresh_token_hash: (*SHA256HASH1).into(),
+        },
         admin: FOO.user.admin,
         email_verified: FOO.user.email_verified,
+        permissions: Default::default(),
     };
 
     let auth_access_token = MockAuthAccessTokenService::new()
-        .with_verify("my auth token".into(), Some(expected))
-        .with_is_invalidated(expected.refresh_token_hash, true);
+        .with_verify("my auth token".into(), Some(expected.clone()))
+        .with_is_invalidated((*SHA256HASH1).into(), true);
+
+    let auth_personal_access_token =
+        MockAuthPersonalAccessTokenService::new().with_hash("my auth token".into(), None);
 
     let sut = AuthServiceImpl {
         auth_access_token,
+        auth_personal_access_token,
         ..Sut::default()
     };
 
@@ -78,3 +113,345 @@ async fn access_token_invalidated() {
     // Assert
     assert_matches!(result, Err(AuthenticateError::InvalidToken));
 }
+
+#[tokio::test]
+async fn personal_access_token_ok() {
+    // Arrange
+    let now = FOO_PAT.created_at + Duration::from_secs(3600);
+
+    let db = MockDatabase::build(true);
+
+    let time = MockTimeService::new().with_now(now);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+        .with_get_by_hash((*SHA256HASH1).into(), Some(FOO_PAT.clone()))
+        .with_update_last_used_at(FOO_PAT.id, now);
+
+    let auth_personal_access_token = MockAuthPersonalAccessTokenService::new()
+        .with_hash("bapat_secret".into(), Some((*SHA256HASH1).into()));
+
+    let sut = AuthServiceImpl {
+        db,
+        time,
+        user_repo,
+        personal_access_token_repo,
+        auth_personal_access_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.authenticate(&"bapat_secret".into()).await;
+
+    // Assert
+    assert_eq!(
+        result.unwrap(),
+        Authentication {
+            user_id: FOO.user.id,
+            source: AuthenticationSource::PersonalAccessToken(FOO_PAT.id),
+            admin: false,
+            email_verified: FOO.user.email_verified,
+            permissions: Default::default(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn personal_access_token_read_only_scope() {
+    // Arrange
+    let token = PersonalAccessToken {
+        scopes: [PersonalAccessTokenScope::ReadOnly].into(),
+        ..FOO_PAT.clone()
+    };
+    let now = token.created_at + Duration::from_secs(3600);
+
+    let db = MockDatabase::build(false);
+
+    let time = MockTimeService::new().with_now(now);
+
+    let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+        .with_get_by_hash((*SHA256HASH1).into(), Some(token));
+
+    let auth_personal_access_token = MockAuthPersonalAccessTokenService::new()
+        .with_hash("bapat_secret".into(), Some((*SHA256HASH1).into()));
+
+    let sut = AuthServiceImpl {
+        db,
+        time,
+        personal_access_token_repo,
+        auth_personal_access_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.authenticate(&"bapat_secret".into()).await;
+
+    // Assert
+    assert_matches!(result, Err(AuthenticateError::ReadOnlyToken));
+}
+
+#[tokio::test]
+async fn personal_access_token_read_only_scope_read() {
+    // Arrange
+    let token = PersonalAccessToken {
+        scopes: [PersonalAccessTokenScope::ReadOnly].into(),
+        ..FOO_PAT.clone()
+    };
+    let now = token.created_at + Duration::from_secs(3600);
+
+    let db = MockDatabase::build(true);
+
+    let time = MockTimeService::new().with_now(now);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+        .with_get_by_hash((*SHA256HASH1).into(), Some(token))
+        .with_update_last_used_at(FOO_PAT.id, now);
+
+    let auth_personal_access_token = MockAuthPersonalAccessTokenService::new()
+        .with_hash("bapat_secret".into(), Some((*SHA256HASH1).into()));
+
+    let sut = AuthServiceImpl {
+        db,
+        time,
+        user_repo,
+        personal_access_token_repo,
+        auth_personal_access_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.authenticate_read(&"bapat_secret".into()).await;
+
+    // Assert
+    assert_eq!(
+        result.unwrap(),
+        Authentication {
+            user_id: FOO.user.id,
+            source: AuthenticationSource::PersonalAccessToken(FOO_PAT.id),
+            admin: false,
+            email_verified: FOO.user.email_verified,
+            permissions: Default::default(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn personal_access_token_admin_scope() {
+    // Arrange
+    let now = ADMIN_PAT.last_used_at.unwrap() + Duration::from_secs(3600);
+
+    let db = MockDatabase::build(true);
+
+    let time = MockTimeService::new().with_now(now);
+
+    let user_repo =
+        MockUserRepository::new().with_get_composite(ADMIN.user.id, Some(ADMIN.clone()));
+
+    let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+        .with_get_by_hash((*SHA256HASH1).into(), Some(ADMIN_PAT.clone()))
+        .with_update_last_used_at(ADMIN_PAT.id, now);
+
+    let role_repo =
+        MockRoleRepository::new().with_list_by_user(ADMIN.user.id, vec![SUPPORT.clone()]);
+
+    let auth_personal_access_token = MockAuthPersonalAccessTokenService::new()
+        .with_hash("bapat_secret".into(), Some((*SHA256HASH1).into()));
+
+    let sut = AuthServiceImpl {
+        db,
+        time,
+        user_repo,
+        personal_access_token_repo,
+        role_repo,
+        auth_personal_access_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.authenticate(&"bapat_secret".into()).await;
+
+    // Assert
+    assert_eq!(
+        result.unwrap(),
+        Authentication {
+            user_id: ADMIN.user.id,
+            source: AuthenticationSource::PersonalAccessToken(ADMIN_PAT.id),
+            admin: true,
+            email_verified: ADMIN.user.email_verified,
+            permissions: SUPPORT.permissions.clone(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn personal_access_token_without_admin_scope() {
+    // Arrange
+    let now = ADMIN_PAT.last_used_at.unwrap() + Duration::from_secs(3600);
+    let token = PersonalAccessToken {
+        scopes: Default::default(),
+        ..ADMIN_PAT.clone()
+    };
+
+    let db = MockDatabase::build(true);
+
+    let time = MockTimeService::new().with_now(now);
+
+    let user_repo =
+        MockUserRepository::new().with_get_composite(ADMIN.user.id, Some(ADMIN.clone()));
+
+    let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+        .with_get_by_hash((*SHA256HASH1).into(), Some(token))
+        .with_update_last_used_at(ADMIN_PAT.id, now);
+
+    let auth_personal_access_token = MockAuthPersonalAccessTokenService::new()
+        .with_hash("bapat_secret".into(), Some((*SHA256HASH1).into()));
+
+    let sut = AuthServiceImpl {
+        db,
+        time,
+        user_repo,
+        personal_access_token_repo,
+        auth_personal_access_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.authenticate(&"bapat_secret".into()).await;
+
+    // Assert
+    let result = result.unwrap();
+    assert!(!result.admin);
+    assert!(result.permissions.is_empty());
+}
+
+#[tokio::test]
+async fn personal_access_token_recently_used() {
+    // Arrange
+    let now = ADMIN_PAT.last_used_at.unwrap() + Duration::from_secs(30);
+
+    let db = MockDatabase::build(false);
+
+    let time = MockTimeService::new().with_now(now);
+
+    let user_repo =
+        MockUserRepository::new().with_get_composite(ADMIN.user.id, Some(ADMIN.clone()));
+
+    let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+        .with_get_by_hash((*SHA256HASH1).into(), Some(ADMIN_PAT.clone()));
+
+    let role_repo = MockRoleRepository::new().with_list_by_user(ADMIN.user.id, vec![]);
+
+    let auth_personal_access_token = MockAuthPersonalAccessTokenService::new()
+        .with_hash("bapat_secret".into(), Some((*SHA256HASH1).into()));
+
+    let sut = AuthServiceImpl {
+        db,
+        time,
+        user_repo,
+        personal_access_token_repo,
+        role_repo,
+        auth_personal_access_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.authenticate(&"bapat_secret".into()).await;
+
+    // Assert
+    assert_eq!(
+        result.unwrap().source,
+        AuthenticationSource::PersonalAccessToken(ADMIN_PAT.id)
+    );
+}
+
+#[tokio::test]
+async fn personal_access_token_not_found() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let personal_access_token_repo =
+        MockPersonalAccessTokenRepository::new().with_get_by_hash((*SHA256HASH1).into(), None);
+
+    let auth_personal_access_token = MockAuthPersonalAccessTokenService::new()
+        .with_hash("bapat_secret".into(), Some((*SHA256HASH1).into()));
+
+    let sut = AuthServiceImpl {
+        db,
+        personal_access_token_repo,
+        auth_personal_access_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.authenticate(&"bapat_secret".into()).await;
+
+    // Assert
+    assert_matches!(result, Err(AuthenticateError::InvalidToken));
+}
+
+#[tokio::test]
+async fn personal_access_token_expired() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let time = MockTimeService::new().with_now(FOO_PAT.expires_at.unwrap());
+
+    let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+        .with_get_by_hash((*SHA256HASH1).into(), Some(FOO_PAT.clone()));
+
+    let auth_personal_access_token = MockAuthPersonalAccessTokenService::new()
+        .with_hash("bapat_secret".into(), Some((*SHA256HASH1).into()));
+
+    let sut = AuthServiceImpl {
+        db,
+        time,
+        personal_access_token_repo,
+        auth_personal_access_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.authenticate(&"bapat_secret".into()).await;
+
+    // Assert
+    assert_matches!(result, Err(AuthenticateError::InvalidToken));
+}
+
+#[tokio::test]
+async fn personal_access_token_user_disabled() {
+    // Arrange
+    let token = PersonalAccessToken {
+        user_id: BAR.user.id,
+        ..FOO_PAT.clone()
+    };
+
+    let db = MockDatabase::build(false);
+
+    let time = MockTimeService::new().with_now(FOO_PAT.created_at);
+
+    let user_repo = MockUserRepository::new().with_get_composite(BAR.user.id, Some(BAR.clone()));
+
+    let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+        .with_get_by_hash((*SHA256HASH1).into(), Some(token));
+
+    let auth_personal_access_token = MockAuthPersonalAccessTokenService::new()
+        .with_hash("bapat_secret".into(), Some((*SHA256HASH1).into()));
+
+    let sut = AuthServiceImpl {
+        db,
+        time,
+        user_repo,
+        personal_access_token_repo,
+        auth_personal_access_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.authenticate(&"bapat_secret".into()).await;
+
+    // Assert
+    assert_matches!(result, Err(AuthenticateError::InvalidToken));
+}
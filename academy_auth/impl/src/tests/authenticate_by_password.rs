@@ -1,6 +1,6 @@
 use academy_auth_contracts::{AuthService, AuthenticateByPasswordError};
 use academy_demo::user::{FOO, FOO_PASSWORD};
-use academy_persistence_contracts::user::MockUserRepository;
+use academy_persistence_contracts::{user::MockUserRepository, MockTransaction};
 use academy_shared_contracts::password::MockPasswordService;
 use academy_utils::assert_matches;
 
@@ -14,11 +14,51 @@ async fn ok() {
     let user_repo =
         MockUserRepository::new().with_get_password_hash(FOO.user.id, Some(password_hash.into()));
 
-    let password = MockPasswordService::new().with_verify(
-        FOO_PASSWORD.clone().into_inner(),
-        password_hash.into(),
-        true,
-    );
+    let password = MockPasswordService::new()
+        .with_verify(
+            FOO_PASSWORD.clone().into_inner(),
+            password_hash.into(),
+            true,
+        )
+        .with_needs_rehash(password_hash.into(), false);
+
+    let sut = AuthServiceImpl {
+        user_repo,
+        password,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .authenticate_by_password(
+            &mut MockTransaction::new(),
+            FOO.user.id,
+            FOO_PASSWORD.clone(),
+        )
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn ok_rehash() {
+    // Arrange
+    let password_hash = "outdated hash of foo's password";
+    let new_password_hash = "new hash of foo's password";
+
+    let user_repo = MockUserRepository::new()
+        .with_get_password_hash(FOO.user.id, Some(password_hash.into()))
+        .with_save_password_hash(FOO.user.id, new_password_hash.into());
+
+    let password = MockPasswordService::new()
+        .with_verify(
+            FOO_PASSWORD.clone().into_inner(),
+            password_hash.into(),
+            true,
+        )
+        .with_needs_rehash(password_hash.into(), true)
+        .with_hash(FOO_PASSWORD.clone().into_inner(), new_password_hash.into());
 
     let sut = AuthServiceImpl {
         user_repo,
@@ -28,7 +68,11 @@ async fn ok() {
 
     // Act
     let result = sut
-        .authenticate_by_password(&mut (), FOO.user.id, FOO_PASSWORD.clone())
+        .authenticate_by_password(
+            &mut MockTransaction::new(),
+            FOO.user.id,
+            FOO_PASSWORD.clone(),
+        )
         .await;
 
     // Assert
@@ -47,7 +91,11 @@ async fn user_not_found() {
 
     // Act
     let result = sut
-        .authenticate_by_password(&mut (), FOO.user.id, FOO_PASSWORD.clone())
+        .authenticate_by_password(
+            &mut MockTransaction::new(),
+            FOO.user.id,
+            FOO_PASSWORD.clone(),
+        )
         .await;
 
     // Assert
@@ -76,7 +124,11 @@ async fn wrong_password() {
 
     // Act
     let result = sut
-        .authenticate_by_password(&mut (), FOO.user.id, FOO_PASSWORD.clone())
+        .authenticate_by_password(
+            &mut MockTransaction::new(),
+            FOO.user.id,
+            FOO_PASSWORD.clone(),
+        )
         .await;
 
     // Assert
@@ -4,7 +4,7 @@ use academy_auth_contracts::{
     refresh_token::MockAuthRefreshTokenService, AuthService, AuthenticateByRefreshTokenError,
 };
 use academy_demo::{session::FOO_1, SHA256HASH1};
-use academy_persistence_contracts::session::MockSessionRepository;
+use academy_persistence_contracts::{session::MockSessionRepository, MockTransaction};
 use academy_shared_contracts::time::MockTimeService;
 use academy_utils::assert_matches;
 
@@ -33,7 +33,7 @@ async fn authenticate_by_refresh_token_ok() {
 
     // Act
     let result = sut
-        .authenticate_by_refresh_token(&mut (), &"the refresh token".into())
+        .authenticate_by_refresh_token(&mut MockTransaction::new(), &"the refresh token".into())
         .await;
 
     // Assert
@@ -46,8 +46,9 @@ async fn authenticate_by_refresh_token_invalid() {
     let auth_refresh_token = MockAuthRefreshTokenService::new()
         .with_hash("the refresh token".into(), (*SHA256HASH1).into());
 
-    let session_repo =
-        MockSessionRepository::new().with_get_by_refresh_token_hash((*SHA256HASH1).into(), None);
+    let session_repo = MockSessionRepository::new()
+        .with_get_by_refresh_token_hash((*SHA256HASH1).into(), None)
+        .with_get_by_superseded_refresh_token_hash((*SHA256HASH1).into(), None);
 
     let sut = AuthServiceImpl {
         auth_refresh_token,
@@ -57,13 +58,38 @@ async fn authenticate_by_refresh_token_invalid() {
 
     // Act
     let result = sut
-        .authenticate_by_refresh_token(&mut (), &"the refresh token".into())
+        .authenticate_by_refresh_token(&mut MockTransaction::new(), &"the refresh token".into())
         .await;
 
     // Assert
     assert_matches!(result, Err(AuthenticateByRefreshTokenError::Invalid));
 }
 
+#[tokio::test]
+async fn authenticate_by_refresh_token_reused() {
+    // Arrange
+    let auth_refresh_token = MockAuthRefreshTokenService::new()
+        .with_hash("the refresh token".into(), (*SHA256HASH1).into());
+
+    let session_repo = MockSessionRepository::new()
+        .with_get_by_refresh_token_hash((*SHA256HASH1).into(), None)
+        .with_get_by_superseded_refresh_token_hash((*SHA256HASH1).into(), Some(FOO_1.clone()));
+
+    let sut = AuthServiceImpl {
+        auth_refresh_token,
+        session_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .authenticate_by_refresh_token(&mut MockTransaction::new(), &"the refresh token".into())
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(AuthenticateByRefreshTokenError::Reused(x)) if *x == FOO_1.id);
+}
+
 #[tokio::test]
 async fn authenticate_by_refresh_token_expired() {
     // Arrange
@@ -87,7 +113,7 @@ async fn authenticate_by_refresh_token_expired() {
 
     // Act
     let result = sut
-        .authenticate_by_refresh_token(&mut (), &"the refresh token".into())
+        .authenticate_by_refresh_token(&mut MockTransaction::new(), &"the refresh token".into())
         .await;
 
     // Assert
@@ -1,7 +1,7 @@
 use academy_auth_contracts::{access_token::MockAuthAccessTokenService, AuthService};
 use academy_demo::{user::FOO, SHA256HASH1, SHA256HASH2};
 use academy_models::session::SessionRefreshTokenHash;
-use academy_persistence_contracts::session::MockSessionRepository;
+use academy_persistence_contracts::{session::MockSessionRepository, MockTransaction};
 
 use crate::{tests::Sut, AuthServiceImpl};
 
@@ -27,7 +27,9 @@ async fn ok() {
     };
 
     // Act
-    let result = sut.invalidate_access_tokens(&mut (), FOO.user.id).await;
+    let result = sut
+        .invalidate_access_tokens(&mut MockTransaction::new(), FOO.user.id)
+        .await;
 
     // Assert
     result.unwrap();
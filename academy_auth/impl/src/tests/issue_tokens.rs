@@ -2,7 +2,8 @@ use academy_auth_contracts::{
     access_token::MockAuthAccessTokenService, refresh_token::MockAuthRefreshTokenService,
     AuthService, Tokens,
 };
-use academy_demo::{user::FOO, SHA256HASH1, UUID1};
+use academy_demo::{role::SUPPORT, user::FOO, SHA256HASH1, UUID1};
+use academy_persistence_contracts::{role::MockRoleRepository, MockTransaction};
 
 use crate::{tests::Sut, AuthServiceConfig, AuthServiceImpl};
 
@@ -17,8 +18,11 @@ async fn ok() {
         refresh_token_hash: (*SHA256HASH1).into(),
     };
 
+    let role_repo = MockRoleRepository::new().with_list_by_user(FOO.user.id, vec![SUPPORT.clone()]);
+
     let auth_access_token = MockAuthAccessTokenService::new().with_issue(
         FOO.user.clone(),
+        SUPPORT.permissions.clone(),
         UUID1.into(),
         (*SHA256HASH1).into(),
         expected.access_token.clone(),
@@ -30,13 +34,16 @@ async fn ok() {
 
     let sut = AuthServiceImpl {
         config,
+        role_repo,
         auth_access_token,
         auth_refresh_token,
         ..Sut::default()
     };
 
     // Act
-    let result = sut.issue_tokens(&FOO.user, UUID1.into());
+    let result = sut
+        .issue_tokens(&mut MockTransaction::new(), &FOO.user, UUID1.into())
+        .await;
 
     // Assert
     assert_eq!(result.unwrap(), expected);
@@ -1,9 +1,14 @@
 use std::time::Duration;
 
 use academy_auth_contracts::{
-    access_token::MockAuthAccessTokenService, refresh_token::MockAuthRefreshTokenService,
+    access_token::MockAuthAccessTokenService,
+    personal_access_token::MockAuthPersonalAccessTokenService,
+    refresh_token::MockAuthRefreshTokenService,
+};
+use academy_persistence_contracts::{
+    personal_access_token::MockPersonalAccessTokenRepository, role::MockRoleRepository,
+    session::MockSessionRepository, user::MockUserRepository, MockDatabase, MockTransaction,
 };
-use academy_persistence_contracts::{session::MockSessionRepository, user::MockUserRepository};
 use academy_shared_contracts::{password::MockPasswordService, time::MockTimeService};
 
 use crate::{AuthServiceConfig, AuthServiceImpl};
@@ -15,12 +20,16 @@ mod invalidate_access_tokens;
 mod issue_tokens;
 
 type Sut = AuthServiceImpl<
+    MockDatabase,
     MockTimeService,
     MockPasswordService,
-    MockUserRepository<()>,
-    MockSessionRepository<()>,
+    MockUserRepository<MockTransaction>,
+    MockSessionRepository<MockTransaction>,
+    MockPersonalAccessTokenRepository<MockTransaction>,
+    MockRoleRepository<MockTransaction>,
     MockAuthAccessTokenService,
     MockAuthRefreshTokenService,
+    MockAuthPersonalAccessTokenService,
 >;
 
 impl Default for AuthServiceConfig {
@@ -20,6 +20,33 @@ pub trait CacheService: Sized + Send + Sync + 'static {
         ttl: Option<Duration>,
     ) -> impl Future<Output = anyhow::Result<()>> + Send;
 
+    /// Atomically read and remove a cache item.
+    ///
+    /// If multiple callers take the same item concurrently, at most one of
+    /// them receives its value.
+    fn take<T: DeserializeOwned + Debug + 'static>(
+        &self,
+        key: &str,
+    ) -> impl Future<Output = anyhow::Result<Option<T>>> + Send;
+
+    /// Read the current value of a counter.
+    ///
+    /// Returns `0` if the counter does not exist.
+    fn get_counter(&self, key: &str) -> impl Future<Output = anyhow::Result<u64>> + Send;
+
+    /// Atomically increment a counter and return its new value.
+    ///
+    /// Counters that do not exist are initialized with `0` before being
+    /// incremented. If `ttl` is set, the counter is automatically removed
+    /// after this timeout, which is reset on every increment. Counters are not
+    /// compatible with [`get`](CacheService::get) and
+    /// [`set`](CacheService::set).
+    fn increment(
+        &self,
+        key: &str,
+        ttl: Option<Duration>,
+    ) -> impl Future<Output = anyhow::Result<u64>> + Send;
+
     /// Remove an existing cache item.
     ///
     /// Does nothing if the cache item does not exist.
@@ -60,6 +87,34 @@ impl MockCacheService {
         self
     }
 
+    pub fn with_take<T: DeserializeOwned + Debug + Send + 'static>(
+        mut self,
+        key: String,
+        result: Option<T>,
+    ) -> Self {
+        self.expect_take()
+            .once()
+            .with(mockall::predicate::eq(key))
+            .return_once(|_| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_get_counter(mut self, key: String, result: u64) -> Self {
+        self.expect_get_counter()
+            .once()
+            .with(mockall::predicate::eq(key))
+            .return_once(move |_| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_increment(mut self, key: String, ttl: Option<Duration>, result: u64) -> Self {
+        self.expect_increment()
+            .once()
+            .with(mockall::predicate::eq(key), mockall::predicate::eq(ttl))
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
     pub fn with_remove(mut self, key: String) -> Self {
         self.expect_remove()
             .once()
@@ -107,6 +107,62 @@ impl CacheService for ValkeyCache {
         .context("Failed to write value to cache")
     }
 
+    #[trace_instrument(skip(self))]
+    async fn take<T: DeserializeOwned + Debug + 'static>(
+        &self,
+        key: &str,
+    ) -> anyhow::Result<Option<T>> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .context("Failed to acquire cache connection")?;
+
+        let result = conn
+            .get_del::<_, Option<Vec<u8>>>(key)
+            .await
+            .context("Failed to take value from cache")?;
+
+        result
+            .map(|data| rmp_serde::from_slice(&data))
+            .transpose()
+            .context("Failed to deserialize cached value")
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn get_counter(&self, key: &str) -> anyhow::Result<u64> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .context("Failed to acquire cache connection")?;
+
+        conn.get::<_, Option<u64>>(key)
+            .await
+            .map(|x| x.unwrap_or(0))
+            .context("Failed to read counter from cache")
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn increment(&self, key: &str, ttl: Option<Duration>) -> anyhow::Result<u64> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .context("Failed to acquire cache connection")?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic().incr(key, 1u64);
+        if let Some(ttl) = ttl {
+            pipe.pexpire(key, ttl.as_millis().try_into()?).ignore();
+        }
+
+        pipe.query_async::<(u64,)>(&mut *conn)
+            .await
+            .map(|(x,)| x)
+            .context("Failed to increment counter in cache")
+    }
+
     #[trace_instrument(skip(self))]
     async fn remove(&self, key: &str) -> anyhow::Result<()> {
         let mut conn = self
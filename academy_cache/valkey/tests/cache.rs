@@ -85,6 +85,52 @@ async fn remove() {
     assert!(cache.get::<()>("x").await.unwrap().is_none());
 }
 
+#[tokio::test]
+async fn take() {
+    let cache = setup().await;
+
+    assert_eq!(cache.take::<i32>("x").await.unwrap(), None);
+
+    cache.set("x", &42i32, None).await.unwrap();
+    assert_eq!(cache.take::<i32>("x").await.unwrap(), Some(42));
+
+    assert_eq!(cache.take::<i32>("x").await.unwrap(), None);
+    assert_eq!(cache.get::<i32>("x").await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn increment_no_ttl() {
+    let cache = setup().await;
+
+    assert_eq!(cache.get_counter("x").await.unwrap(), 0);
+
+    assert_eq!(cache.increment("x", None).await.unwrap(), 1);
+    assert_eq!(cache.increment("x", None).await.unwrap(), 2);
+    assert_eq!(cache.increment("x", None).await.unwrap(), 3);
+    assert_eq!(cache.get_counter("x").await.unwrap(), 3);
+
+    cache.remove("x").await.unwrap();
+    assert_eq!(cache.get_counter("x").await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn increment_ttl() {
+    let cache = setup().await;
+
+    let ttl = Some(Duration::from_millis(200));
+
+    assert_eq!(cache.increment("x", ttl).await.unwrap(), 1);
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(cache.increment("x", ttl).await.unwrap(), 2);
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(cache.get_counter("x").await.unwrap(), 2);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(cache.get_counter("x").await.unwrap(), 0);
+}
+
 #[tokio::test]
 async fn types() {
     let cache = setup().await;
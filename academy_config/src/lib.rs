@@ -1,12 +1,18 @@
 use std::{
     collections::HashMap,
     net::{IpAddr, SocketAddr},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 use academy_assets::CONFIG_TOML;
-use academy_models::{email_address::EmailAddressWithName, mfa::TotpSecretLength, url::Url};
-use anyhow::Context;
+use academy_models::{
+    email_address::EmailAddressWithName,
+    jwt::JwtAlgorithm,
+    mfa::TotpSecretLength,
+    rate_limit::{RateLimitGroup, RateLimitKey},
+    url::Url,
+};
+use anyhow::{bail, Context};
 use config::{File, FileFormat};
 use duration::Duration;
 use regex::bytes::RegexSet;
@@ -18,6 +24,9 @@ const DEV_CONFIG_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../config.de
 
 pub const ENVIRONMENT_VARIABLE: &str = "ACADEMY_CONFIG";
 
+/// Key id of the HS256 key derived from the deprecated `jwt.secret` option.
+pub const LEGACY_JWT_KEY_ID: &str = "legacy";
+
 pub fn load() -> anyhow::Result<Config> {
     load_paths(&parse_env_var()?, &[])
 }
@@ -62,6 +71,10 @@ fn load_paths(paths: &[impl AsRef<Path>], overrides: &[&str]) -> anyhow::Result<
         .recaptcha
         .take_if(|recaptcha| recaptcha.enable == Some(false));
 
+    config
+        .password_policy
+        .take_if(|password_policy| password_policy.enable == Some(false));
+
     config.sentry.take_if(|sentry| sentry.enable == Some(false));
 
     if let Some(oauth2) = &mut config.oauth2 {
@@ -71,26 +84,87 @@ fn load_paths(paths: &[impl AsRef<Path>], overrides: &[&str]) -> anyhow::Result<
         .oauth2
         .take_if(|oauth2| oauth2.enable == Some(false) || oauth2.providers.is_empty());
 
+    config.rate_limit.retain(|_, p| p.enable != Some(false));
+
+    apply_legacy_jwt_secret(&mut config.jwt);
+
+    check_pwned_passwords(&config)?;
+
     Ok(config)
 }
 
+/// Convert the deprecated `jwt.secret` option into an HS256 key with the id
+/// [`LEGACY_JWT_KEY_ID`], which is also used to sign new tokens unless
+/// `jwt.signing_key` is set.
+fn apply_legacy_jwt_secret(jwt: &mut JwtConfig) {
+    let Some(secret) = jwt.secret.take() else {
+        return;
+    };
+
+    jwt.keys
+        .entry(LEGACY_JWT_KEY_ID.into())
+        .or_insert(JwtKeyConfig {
+            algorithm: JwtAlgorithm::HS256,
+            key: secret,
+        });
+
+    if jwt.signing_key.is_empty() {
+        jwt.signing_key = LEGACY_JWT_KEY_ID.into();
+    }
+}
+
+/// Ensure that a local source for the breached password check is configured
+/// if the check is enabled, so that password hashes are never sent to a third
+/// party.
+fn check_pwned_passwords(config: &Config) -> anyhow::Result<()> {
+    let pwned_passwords = &config.pwned_passwords;
+    if pwned_passwords.dataset_path.is_some() && pwned_passwords.range_endpoint.is_some() {
+        bail!("Only one of pwned_passwords.dataset_path and pwned_passwords.range_endpoint may be set");
+    }
+
+    let check_breached = config
+        .password_policy
+        .as_ref()
+        .is_some_and(|password_policy| password_policy.check_breached);
+    if check_breached
+        && pwned_passwords.dataset_path.is_none()
+        && pwned_passwords.range_endpoint.is_none()
+    {
+        bail!(
+            "password_policy.check_breached requires pwned_passwords.dataset_path or \
+             pwned_passwords.range_endpoint to be set"
+        );
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Config {
     pub http: HttpConfig,
     pub database: DatabaseConfig,
     pub cache: CacheConfig,
     pub email: EmailConfig,
+    pub storage: StorageConfig,
     pub jwt: JwtConfig,
+    pub encryption: EncryptionConfig,
     pub internal: InternalConfig,
     pub health: HealthConfig,
     pub user: UserConfig,
+    pub password_policy: Option<PasswordPolicyConfig>,
+    pub argon2: Argon2Config,
     pub session: SessionConfig,
+    pub mfa: MfaConfig,
     pub totp: TotpConfig,
+    pub webauthn: WebauthnConfig,
     pub contact: ContactConfig,
     pub recaptcha: Option<RecaptchaConfig>,
     pub vat: VatConfig,
+    pub pwned_passwords: PwnedPasswordsConfig,
     pub sentry: Option<SentryConfig>,
     pub oauth2: Option<OAuth2Config>,
+    pub oauth2_server: OAuth2ServerConfig,
+    pub rate_limit: HashMap<RateLimitGroup, RateLimitPolicyConfig>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -141,9 +215,53 @@ pub struct EmailConfig {
     pub from: EmailAddressWithName,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum StorageConfig {
+    /// Store files in a local directory.
+    Local(LocalStorageConfig),
+    /// Store files in an S3 compatible object storage.
+    S3(S3StorageConfig),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LocalStorageConfig {
+    pub path: PathBuf,
+    /// URL under which the contents of `path` are publicly available.
+    pub public_url: Url,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct S3StorageConfig {
+    pub endpoint: Url,
+    pub bucket: String,
+    pub region: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// URL under which the objects in the bucket are publicly available.
+    pub public_url: Url,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct JwtConfig {
-    pub secret: String,
+    /// Deprecated shared secret, see [`LEGACY_JWT_KEY_ID`].
+    pub secret: Option<String>,
+    #[serde(default)]
+    pub signing_key: String,
+    #[serde(default)]
+    pub keys: HashMap<String, JwtKeyConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JwtKeyConfig {
+    pub algorithm: JwtAlgorithm,
+    pub key: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EncryptionConfig {
+    pub key_version: u32,
+    pub keys: HashMap<u32, String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -162,12 +280,55 @@ pub struct HealthConfig {
 #[derive(Debug, Deserialize)]
 pub struct UserConfig {
     pub name_change_rate_limit: Duration,
+    /// Time after which a name that has been given up by a user may be used
+    /// by other users.
+    pub name_quarantine: Duration,
+    /// Names that cannot be used by non-admin users (case insensitive).
+    pub reserved_names: Vec<String>,
+    /// Names matching any of these regexes cannot be used by non-admin users.
+    /// The regexes are matched against the lowercase name.
+    #[serde(deserialize_with = "deserialize_regex_set")]
+    pub blocked_names: RegexSet,
     pub verification_code_ttl: Duration,
     pub verification_redirect_url: String,
     pub password_reset_code_ttl: Duration,
     pub password_reset_redirect_url: String,
     pub newsletter_code_ttl: Duration,
     pub newsletter_redirect_url: String,
+    pub email_change_code_ttl: Duration,
+    pub email_change_redirect_url: String,
+    pub email_change_cancel_redirect_url: String,
+    pub additional_email_code_ttl: Duration,
+    pub additional_email_redirect_url: String,
+    pub export_code_ttl: Duration,
+    pub export_redirect_url: String,
+    /// Time after which users that have been marked as deleted are purged
+    /// from the database.
+    pub deletion_grace_period: Duration,
+    pub restore_redirect_url: String,
+    /// Maximum size of uploaded avatar images in bytes.
+    pub avatar_max_size: usize,
+    /// Maximum width and height of uploaded avatar images in pixels.
+    pub avatar_max_dimension: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PasswordPolicyConfig {
+    pub enable: Option<bool>,
+    pub min_length: usize,
+    /// Minimum number of character classes (lowercase letters, uppercase
+    /// letters, digits, other characters) a password has to contain.
+    pub min_character_classes: usize,
+    /// Reject passwords that have appeared in known data breaches.
+    pub check_breached: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Argon2Config {
+    /// Memory size in KiB
+    pub memory_cost: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -176,6 +337,15 @@ pub struct SessionConfig {
     pub refresh_token_ttl: Duration,
     pub refresh_token_length: usize,
     pub login_fails_before_captcha: u64,
+    pub failed_login_attempts_ttl: Duration,
+    pub login_fails_before_lockout: u64,
+    pub ip_login_fails_before_lockout: u64,
+    pub lockout_duration: Duration,
+    pub max_lockout_duration: Duration,
+    pub new_device_revoke_redirect_url: String,
+    pub new_device_revoke_code_ttl: Duration,
+    pub login_code_redirect_url: String,
+    pub login_code_ttl: Duration,
 }
 
 #[derive(Debug, Deserialize)]
@@ -183,6 +353,19 @@ pub struct TotpConfig {
     pub secret_length: TotpSecretLength,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct MfaConfig {
+    pub recovery_code_count: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WebauthnConfig {
+    pub rp_id: String,
+    pub rp_name: String,
+    pub origins: Vec<String>,
+    pub challenge_ttl: Duration,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ContactConfig {
     pub email: EmailAddressWithName,
@@ -202,6 +385,17 @@ pub struct VatConfig {
     pub validate_endpoint_override: Option<Url>,
 }
 
+/// Source of the SHA-1 hash-prefix ranges used by the breached password check.
+/// At most one of the options may be set.
+#[derive(Debug, Deserialize)]
+pub struct PwnedPasswordsConfig {
+    /// Directory containing one `<PREFIX>.txt` file per hash prefix, e.g. as
+    /// downloaded by the `PwnedPasswordsDownloader`.
+    pub dataset_path: Option<PathBuf>,
+    /// URL of a self-hosted pwned passwords range API.
+    pub range_endpoint: Option<Url>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SentryConfig {
     pub enable: Option<bool>,
@@ -212,6 +406,7 @@ pub struct SentryConfig {
 pub struct OAuth2Config {
     pub enable: Option<bool>,
     pub registration_token_ttl: Duration,
+    pub state_ttl: Duration,
     pub providers: HashMap<String, OAuth2ProviderConfig>,
 }
 
@@ -221,18 +416,123 @@ pub struct OAuth2ProviderConfig {
     pub name: String,
     pub client_id: String,
     pub client_secret: String,
-    pub auth_url: Url,
-    pub token_url: Url,
-    pub userinfo_url: Url,
-    pub userinfo_id_key: String,
-    pub userinfo_name_key: String,
+    /// Issuer of an OpenID Connect provider. If set, the endpoints are
+    /// retrieved via OIDC discovery and the remaining urls and keys are
+    /// ignored.
+    pub issuer_url: Option<Url>,
+    pub auth_url: Option<Url>,
+    pub token_url: Option<Url>,
+    pub userinfo_url: Option<Url>,
+    pub userinfo_id_key: Option<String>,
+    pub userinfo_name_key: Option<String>,
     pub scopes: Vec<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct OAuth2ServerConfig {
+    /// Issuer identifier included in ID tokens, i.e. the public url of the
+    /// backend.
+    pub issuer: Url,
+    pub access_token_ttl: Duration,
+    pub refresh_token_ttl: Duration,
+    pub code_ttl: Duration,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RateLimitPolicyConfig {
+    pub enable: Option<bool>,
+    /// Maximum number of requests allowed within one window.
+    pub requests: u64,
+    pub window: Duration,
+    pub key: RateLimitKey,
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn load_dev_config() {
         super::load_dev_config().unwrap();
     }
+
+    #[test]
+    fn check_pwned_passwords_without_source() {
+        // Arrange
+        let mut config = super::load_dev_config().unwrap();
+        config.pwned_passwords.range_endpoint = None;
+
+        // Act
+        let result = check_pwned_passwords(&config);
+
+        // Assert
+        result.unwrap_err();
+
+        // Arrange
+        config.password_policy.as_mut().unwrap().check_breached = false;
+
+        // Act
+        let result = check_pwned_passwords(&config);
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[test]
+    fn check_pwned_passwords_multiple_sources() {
+        // Arrange
+        let mut config = super::load_dev_config().unwrap();
+        config.pwned_passwords.dataset_path = Some("/var/lib/pwned-passwords".into());
+
+        // Act
+        let result = check_pwned_passwords(&config);
+
+        // Assert
+        result.unwrap_err();
+    }
+
+    #[test]
+    fn legacy_jwt_secret() {
+        // Arrange
+        let mut jwt = JwtConfig {
+            secret: Some("changeme".into()),
+            signing_key: String::new(),
+            keys: HashMap::new(),
+        };
+
+        // Act
+        apply_legacy_jwt_secret(&mut jwt);
+
+        // Assert
+        assert_eq!(jwt.secret, None);
+        assert_eq!(jwt.signing_key, LEGACY_JWT_KEY_ID);
+        let key = &jwt.keys[LEGACY_JWT_KEY_ID];
+        assert_eq!(key.algorithm, JwtAlgorithm::HS256);
+        assert_eq!(key.key, "changeme");
+    }
+
+    #[test]
+    fn legacy_jwt_secret_with_signing_key() {
+        // Arrange
+        let mut jwt = JwtConfig {
+            secret: Some("changeme".into()),
+            signing_key: "new".into(),
+            keys: [(
+                "new".into(),
+                JwtKeyConfig {
+                    algorithm: JwtAlgorithm::EdDSA,
+                    key: "key".into(),
+                },
+            )]
+            .into(),
+        };
+
+        // Act
+        apply_legacy_jwt_secret(&mut jwt);
+
+        // Assert
+        assert_eq!(jwt.signing_key, "new");
+        assert_eq!(jwt.keys.len(), 2);
+        assert_eq!(jwt.keys[LEGACY_JWT_KEY_ID].algorithm, JwtAlgorithm::HS256);
+    }
 }
@@ -0,0 +1,46 @@
+use std::future::Future;
+
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    user::UserId,
+};
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait AuditService<Txn: Send + Sync + 'static>: Send + Sync + 'static {
+    /// Record that the given action has been performed on a user account.
+    ///
+    /// `actor_id` is the user who performed the action, which is not
+    /// necessarily the affected user (e.g. if an administrator updates
+    /// another user).
+    fn record(
+        &self,
+        txn: &mut Txn,
+        ctx: &AuditContext,
+        actor_id: Option<UserId>,
+        user_id: UserId,
+        action: AuditAction,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
+#[cfg(feature = "mock")]
+impl<Txn: Send + Sync + 'static> MockAuditService<Txn> {
+    pub fn with_record(
+        mut self,
+        ctx: AuditContext,
+        actor_id: Option<UserId>,
+        user_id: UserId,
+        action: AuditAction,
+    ) -> Self {
+        self.expect_record()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(ctx),
+                mockall::predicate::eq(actor_id),
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(action),
+            )
+            .return_once(|_, _, _, _, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+}
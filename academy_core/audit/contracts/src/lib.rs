@@ -0,0 +1,64 @@
+use std::future::Future;
+
+use academy_models::{
+    audit::{AuditEvent, AuditEventFilter},
+    auth::{AccessToken, AuthError},
+    pagination::PaginationSlice,
+    user::UserIdOrSelf,
+};
+use thiserror::Error;
+
+pub mod audit;
+
+pub trait AuditFeatureService: Send + Sync + 'static {
+    /// Return all audit events matching the given query, most recent events
+    /// first.
+    ///
+    /// Requires admin privileges.
+    fn list_events(
+        &self,
+        token: &AccessToken,
+        query: AuditEventListQuery,
+    ) -> impl Future<Output = Result<AuditEventListResult, AuditListEventsError>> + Send;
+
+    /// Return the audit events affecting the given user, most recent events
+    /// first.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    fn list_user_events(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        pagination: PaginationSlice,
+    ) -> impl Future<Output = Result<AuditEventListResult, AuditListUserEventsError>> + Send;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AuditEventListQuery {
+    pub pagination: PaginationSlice,
+    pub filter: AuditEventFilter,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEventListResult {
+    pub total: u64,
+    pub events: Vec<AuditEvent>,
+}
+
+#[derive(Debug, Error)]
+pub enum AuditListEventsError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum AuditListUserEventsError {
+    #[error("The user does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
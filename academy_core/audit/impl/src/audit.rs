@@ -0,0 +1,96 @@
+use academy_core_audit_contracts::audit::AuditService;
+use academy_di::Build;
+use academy_models::{
+    audit::{AuditAction, AuditContext, AuditEvent},
+    user::UserId,
+};
+use academy_persistence_contracts::audit::AuditEventRepository;
+use academy_shared_contracts::{id::IdService, time::TimeService};
+use academy_utils::trace_instrument;
+use anyhow::Context;
+
+#[derive(Debug, Clone, Build)]
+pub struct AuditServiceImpl<Id, Time, AuditEventRepo> {
+    id: Id,
+    time: Time,
+    audit_event_repo: AuditEventRepo,
+}
+
+impl<Txn, Id, Time, AuditEventRepo> AuditService<Txn> for AuditServiceImpl<Id, Time, AuditEventRepo>
+where
+    Txn: Send + Sync + 'static,
+    Id: IdService,
+    Time: TimeService,
+    AuditEventRepo: AuditEventRepository<Txn>,
+{
+    #[trace_instrument(skip(self, txn))]
+    async fn record(
+        &self,
+        txn: &mut Txn,
+        ctx: &AuditContext,
+        actor_id: Option<UserId>,
+        user_id: UserId,
+        action: AuditAction,
+    ) -> anyhow::Result<()> {
+        let event = AuditEvent {
+            id: self.id.generate(),
+            actor_id,
+            user_id,
+            action,
+            ip: ctx.ip,
+            user_agent: ctx.user_agent.clone(),
+            request_id: ctx.request_id,
+            created_at: self.time.now(),
+        };
+
+        self.audit_event_repo
+            .create(txn, &event)
+            .await
+            .context("Failed to save audit event in database")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_demo::{audit::ADMIN_UPDATE_FOO, user::ADMIN};
+    use academy_persistence_contracts::audit::MockAuditEventRepository;
+    use academy_shared_contracts::{id::MockIdService, time::MockTimeService};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn record() {
+        // Arrange
+        let event = ADMIN_UPDATE_FOO.clone();
+        let ctx = AuditContext {
+            ip: event.ip,
+            user_agent: event.user_agent.clone(),
+            request_id: event.request_id,
+        };
+
+        let id = MockIdService::new().with_generate(event.id);
+        let time = MockTimeService::new().with_now(event.created_at);
+
+        let audit_event_repo = MockAuditEventRepository::new().with_create(event.clone());
+
+        let sut = AuditServiceImpl {
+            id,
+            time,
+            audit_event_repo,
+        };
+
+        // Act
+        let result = sut
+            .record(
+                &mut (),
+                &ctx,
+                Some(ADMIN.user.id),
+                event.user_id,
+                AuditAction::UserUpdate,
+            )
+            .await;
+
+        // Assert
+        result.unwrap();
+    }
+}
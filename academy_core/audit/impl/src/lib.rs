@@ -0,0 +1,110 @@
+use academy_auth_contracts::{AuthResultExt, AuthService};
+use academy_core_audit_contracts::{
+    AuditEventListQuery, AuditEventListResult, AuditFeatureService, AuditListEventsError,
+    AuditListUserEventsError,
+};
+use academy_di::Build;
+use academy_models::{
+    audit::AuditEventFilter, auth::AccessToken, pagination::PaginationSlice, user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{audit::AuditEventRepository, user::UserRepository, Database};
+use academy_utils::trace_instrument;
+use anyhow::Context;
+
+pub mod audit;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct AuditFeatureServiceImpl<Db, Auth, UserRepo, AuditEventRepo> {
+    db: Db,
+    auth: Auth,
+    user_repo: UserRepo,
+    audit_event_repo: AuditEventRepo,
+}
+
+impl<Db, Auth, UserRepo, AuditEventRepo> AuditFeatureService
+    for AuditFeatureServiceImpl<Db, Auth, UserRepo, AuditEventRepo>
+where
+    Db: Database,
+    Auth: AuthService<Db::Transaction>,
+    UserRepo: UserRepository<Db::Transaction>,
+    AuditEventRepo: AuditEventRepository<Db::Transaction>,
+{
+    #[trace_instrument(skip(self))]
+    async fn list_events(
+        &self,
+        token: &AccessToken,
+        AuditEventListQuery { pagination, filter }: AuditEventListQuery,
+    ) -> Result<AuditEventListResult, AuditListEventsError> {
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
+        auth.ensure_admin().map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        self.list(&mut txn, &filter, pagination)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn list_user_events(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        pagination: PaginationSlice,
+    ) -> Result<AuditEventListResult, AuditListUserEventsError> {
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        if !self
+            .user_repo
+            .exists(&mut txn, user_id)
+            .await
+            .context("Failed to check user existence")?
+        {
+            return Err(AuditListUserEventsError::NotFound);
+        }
+
+        let filter = AuditEventFilter {
+            user_id: Some(user_id),
+            ..Default::default()
+        };
+
+        self.list(&mut txn, &filter, pagination)
+            .await
+            .map_err(Into::into)
+    }
+}
+
+impl<Db, Auth, UserRepo, AuditEventRepo> AuditFeatureServiceImpl<Db, Auth, UserRepo, AuditEventRepo>
+where
+    Db: Database,
+    AuditEventRepo: AuditEventRepository<Db::Transaction>,
+{
+    async fn list(
+        &self,
+        txn: &mut Db::Transaction,
+        filter: &AuditEventFilter,
+        pagination: PaginationSlice,
+    ) -> anyhow::Result<AuditEventListResult> {
+        let total = self
+            .audit_event_repo
+            .count(txn, filter)
+            .await
+            .context("Failed to get total number of audit events from database")?;
+
+        let events = self
+            .audit_event_repo
+            .list(txn, filter, pagination)
+            .await
+            .context("Failed to get audit events from database")?;
+
+        Ok(AuditEventListResult { total, events })
+    }
+}
@@ -0,0 +1,102 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::{
+    AuditEventListQuery, AuditEventListResult, AuditFeatureService, AuditListEventsError,
+};
+use academy_demo::{
+    audit::{ADMIN_IMPERSONATE_BAR, ADMIN_UPDATE_FOO},
+    session::{ADMIN_1, FOO_1},
+    user::{ADMIN, FOO},
+};
+use academy_models::{
+    audit::AuditEventFilter,
+    auth::{AuthError, AuthorizeError},
+};
+use academy_persistence_contracts::{audit::MockAuditEventRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, AuditFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let query = AuditEventListQuery {
+        pagination: Default::default(),
+        filter: AuditEventFilter {
+            actor_id: Some(ADMIN.user.id),
+            ..Default::default()
+        },
+    };
+    let expected = AuditEventListResult {
+        total: 2,
+        events: vec![ADMIN_IMPERSONATE_BAR.clone(), ADMIN_UPDATE_FOO.clone()],
+    };
+
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let audit_event_repo = MockAuditEventRepository::new()
+        .with_count(query.filter.clone(), expected.total)
+        .with_list(
+            query.filter.clone(),
+            query.pagination,
+            expected.events.clone(),
+        );
+
+    let sut = AuditFeatureServiceImpl {
+        db,
+        auth,
+        audit_event_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.list_events(&"token".into(), query).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn unauthenticated() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate_read(None);
+
+    let sut = AuditFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.list_events(&"token".into(), Default::default()).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(AuditListEventsError::Auth(AuthError::Authenticate(_)))
+    );
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = AuditFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.list_events(&"token".into(), Default::default()).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(AuditListEventsError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
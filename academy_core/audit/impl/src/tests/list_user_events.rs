@@ -0,0 +1,176 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::{
+    AuditEventListResult, AuditFeatureService, AuditListUserEventsError,
+};
+use academy_demo::{
+    audit::{ADMIN_UPDATE_FOO, FOO_PASSWORD_RESET},
+    session::{ADMIN_1, BAR_1, FOO_1},
+    user::{ADMIN, BAR, FOO},
+};
+use academy_models::{
+    audit::AuditEventFilter,
+    auth::{AuthError, AuthorizeError},
+    pagination::PaginationSlice,
+    user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{
+    audit::MockAuditEventRepository, user::MockUserRepository, MockDatabase,
+};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, AuditFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let filter = AuditEventFilter {
+        user_id: Some(FOO.user.id),
+        ..Default::default()
+    };
+    let pagination = PaginationSlice {
+        limit: 2.try_into().unwrap(),
+        offset: 0,
+    };
+    let expected = AuditEventListResult {
+        total: 2,
+        events: vec![ADMIN_UPDATE_FOO.clone(), FOO_PASSWORD_RESET.clone()],
+    };
+
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_exists(FOO.user.id, true);
+
+    let audit_event_repo = MockAuditEventRepository::new()
+        .with_count(filter.clone(), expected.total)
+        .with_list(filter, pagination, expected.events.clone());
+
+    let sut = AuditFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        audit_event_repo,
+    };
+
+    // Act
+    let result = sut
+        .list_user_events(&"token".into(), UserIdOrSelf::Slf, pagination)
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn ok_admin() {
+    // Arrange
+    let filter = AuditEventFilter {
+        user_id: Some(FOO.user.id),
+        ..Default::default()
+    };
+    let expected = AuditEventListResult {
+        total: 2,
+        events: vec![ADMIN_UPDATE_FOO.clone(), FOO_PASSWORD_RESET.clone()],
+    };
+
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_exists(FOO.user.id, true);
+
+    let audit_event_repo = MockAuditEventRepository::new()
+        .with_count(filter.clone(), expected.total)
+        .with_list(filter, Default::default(), expected.events.clone());
+
+    let sut = AuditFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        audit_event_repo,
+    };
+
+    // Act
+    let result = sut
+        .list_user_events(&"token".into(), FOO.user.id.into(), Default::default())
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn unauthenticated() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate_read(None);
+
+    let sut = AuditFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_user_events(&"token".into(), UserIdOrSelf::Slf, Default::default())
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(AuditListUserEventsError::Auth(AuthError::Authenticate(_)))
+    );
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let sut = AuditFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_user_events(&"token".into(), FOO.user.id.into(), Default::default())
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(AuditListUserEventsError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_exists(FOO.user.id, false);
+
+    let sut = AuditFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_user_events(&"token".into(), FOO.user.id.into(), Default::default())
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(AuditListUserEventsError::NotFound));
+}
@@ -0,0 +1,16 @@
+use academy_auth_contracts::MockAuthService;
+use academy_persistence_contracts::{
+    audit::MockAuditEventRepository, user::MockUserRepository, MockDatabase, MockTransaction,
+};
+
+use crate::AuditFeatureServiceImpl;
+
+mod list_events;
+mod list_user_events;
+
+type Sut = AuditFeatureServiceImpl<
+    MockDatabase,
+    MockAuthService<MockTransaction>,
+    MockUserRepository<MockTransaction>,
+    MockAuditEventRepository<MockTransaction>,
+>;
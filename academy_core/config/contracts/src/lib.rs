@@ -1,4 +1,10 @@
+use academy_models::jwt::Jwks;
+
 pub trait ConfigFeatureService: Send + Sync + 'static {
     /// Return the public reCAPTCHA sitekey if reCAPTCHA is enabled.
     fn get_recaptcha_sitekey(&self) -> Option<&str>;
+
+    /// Return the public keys that can be used to verify JWTs issued by this
+    /// service.
+    fn get_jwks(&self) -> &Jwks;
 }
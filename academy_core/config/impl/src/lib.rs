@@ -1,26 +1,35 @@
 use academy_core_config_contracts::ConfigFeatureService;
 use academy_di::Build;
-use academy_shared_contracts::captcha::CaptchaService;
+use academy_models::jwt::Jwks;
+use academy_shared_contracts::{captcha::CaptchaService, jwt::JwtService};
 use academy_utils::trace_instrument;
 
 #[derive(Debug, Clone, Build)]
-pub struct ConfigFeatureServiceImpl<Captcha> {
+pub struct ConfigFeatureServiceImpl<Captcha, Jwt> {
     captcha: Captcha,
+    jwt: Jwt,
 }
 
-impl<Captcha> ConfigFeatureService for ConfigFeatureServiceImpl<Captcha>
+impl<Captcha, Jwt> ConfigFeatureService for ConfigFeatureServiceImpl<Captcha, Jwt>
 where
     Captcha: CaptchaService,
+    Jwt: JwtService,
 {
     #[trace_instrument(skip(self))]
     fn get_recaptcha_sitekey(&self) -> Option<&str> {
         self.captcha.get_recaptcha_sitekey()
     }
+
+    #[trace_instrument(skip(self))]
+    fn get_jwks(&self) -> &Jwks {
+        self.jwt.jwks()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use academy_shared_contracts::captcha::MockCaptchaService;
+    use academy_models::jwt::{Jwk, JwkCurve, JwkParams, JwkUse, JwtAlgorithm};
+    use academy_shared_contracts::{captcha::MockCaptchaService, jwt::MockJwtService};
 
     use super::*;
 
@@ -29,7 +38,10 @@ mod tests {
         // Arrange
         let captcha = MockCaptchaService::new().with_get_recaptcha_sitekey(Some("sitekey"));
 
-        let sut = ConfigFeatureServiceImpl { captcha };
+        let sut = ConfigFeatureServiceImpl {
+            captcha,
+            jwt: MockJwtService::new(),
+        };
 
         // Act
         let result = sut.get_recaptcha_sitekey();
@@ -43,7 +55,10 @@ mod tests {
         // Arrange
         let captcha = MockCaptchaService::new().with_get_recaptcha_sitekey(None);
 
-        let sut = ConfigFeatureServiceImpl { captcha };
+        let sut = ConfigFeatureServiceImpl {
+            captcha,
+            jwt: MockJwtService::new(),
+        };
 
         // Act
         let result = sut.get_recaptcha_sitekey();
@@ -51,4 +66,33 @@ mod tests {
         // Assert
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn get_jwks() {
+        // Arrange
+        let jwks = Jwks {
+            keys: vec![Jwk {
+                kid: "key".into(),
+                alg: JwtAlgorithm::EdDSA,
+                use_: JwkUse::Sig,
+                params: JwkParams::Okp {
+                    crv: JwkCurve::Ed25519,
+                    x: "TjhJ4BFvRpD7SA1rgXkhEVCYldpBb0jSvehGcTrWwbo".into(),
+                },
+            }],
+        };
+
+        let jwt = MockJwtService::new().with_jwks(jwks.clone());
+
+        let sut = ConfigFeatureServiceImpl {
+            captcha: MockCaptchaService::new(),
+            jwt,
+        };
+
+        // Act
+        let result = sut.get_jwks();
+
+        // Assert
+        assert_eq!(*result, jwks);
+    }
 }
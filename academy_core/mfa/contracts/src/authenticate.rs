@@ -7,7 +7,7 @@ use thiserror::Error;
 pub trait MfaAuthenticateService<Txn: Send + Sync + 'static>: Send + Sync + 'static {
     /// Authenticate the given user using a second factor.
     ///
-    /// Disables MFA for the user if a correct recovery code is provided.
+    /// A correct recovery code is consumed and cannot be used again.
     fn authenticate(
         &self,
         txn: &mut Txn,
@@ -22,8 +22,6 @@ pub enum MfaAuthenticateResult {
     Disabled,
     /// MFA is enabled and authentication was successful
     Ok,
-    /// Recovery code has been used and MFA has been disabled
-    Reset,
 }
 
 #[derive(Debug, Error)]
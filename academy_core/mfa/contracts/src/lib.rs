@@ -1,9 +1,14 @@
 use std::future::Future;
 
 use academy_models::{
+    audit::AuditContext,
     auth::{AccessToken, AuthError},
-    mfa::{MfaRecoveryCode, TotpCode, TotpSetup},
-    user::UserIdOrSelf,
+    mfa::{
+        MfaRecoveryCode, TotpCode, TotpDevice, TotpDeviceId, TotpDeviceName, TotpSetup,
+        WebauthnAssertionOptions, WebauthnDevice, WebauthnDeviceId, WebauthnDeviceName,
+        WebauthnRegistration, WebauthnRegistrationOptions,
+    },
+    user::{UserIdOrSelf, UserNameOrEmailAddress},
 };
 use thiserror::Error;
 
@@ -11,37 +16,217 @@ pub mod authenticate;
 pub mod disable;
 pub mod recovery;
 pub mod totp_device;
+pub mod totp_secret;
+pub mod webauthn_device;
 
 pub trait MfaFeatureService: Send + Sync + 'static {
     /// Create a new disabled TOTP device or reset an existing disabled TOTP
     /// device.
     ///
     /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
     fn initialize(
         &self,
         token: &AccessToken,
         user_id: UserIdOrSelf,
     ) -> impl Future<Output = Result<TotpSetup, MfaInitializeError>> + Send;
 
-    /// Enable a previously created disabled TOTP device and generate an MFA
-    /// recovery code.
+    /// Enable a previously created disabled TOTP device and generate a new
+    /// set of MFA recovery codes.
     ///
     /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
     fn enable(
         &self,
         token: &AccessToken,
         user_id: UserIdOrSelf,
         code: TotpCode,
-    ) -> impl Future<Output = Result<MfaRecoveryCode, MfaEnableError>> + Send;
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<Vec<MfaRecoveryCode>, MfaEnableError>> + Send;
 
-    /// Delete all TOTP devices and invalidate the MFA recovery code.
+    /// Delete all MFA devices and invalidate all MFA recovery codes.
     ///
     /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
     fn disable(
         &self,
         token: &AccessToken,
         user_id: UserIdOrSelf,
+        ctx: &AuditContext,
     ) -> impl Future<Output = Result<(), MfaDisableError>> + Send;
+
+    /// Return the number of unused MFA recovery codes of the given user.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    fn count_recovery_codes(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+    ) -> impl Future<Output = Result<u64, MfaCountRecoveryCodesError>> + Send;
+
+    /// Invalidate all existing MFA recovery codes and generate a new set.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
+    fn regenerate_recovery_codes(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<Vec<MfaRecoveryCode>, MfaRegenerateRecoveryCodesError>> + Send;
+
+    /// Return all TOTP devices of the given user.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    fn list_totp_devices(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+    ) -> impl Future<Output = Result<Vec<TotpDevice>, MfaListTotpDevicesError>> + Send;
+
+    /// Create a new disabled TOTP device with the given name.
+    ///
+    /// The device needs to be confirmed using [`confirm_totp_device`] before
+    /// it can be used to log in.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// [`confirm_totp_device`]: MfaFeatureService::confirm_totp_device
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
+    fn create_totp_device(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        name: TotpDeviceName,
+    ) -> impl Future<Output = Result<MfaCreateTotpDeviceResult, MfaCreateTotpDeviceError>> + Send;
+
+    /// Enable a disabled TOTP device by verifying a TOTP code.
+    ///
+    /// If MFA has not been enabled before, a new set of MFA recovery codes is
+    /// generated and returned.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
+    fn confirm_totp_device(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        totp_device_id: TotpDeviceId,
+        code: TotpCode,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<Option<Vec<MfaRecoveryCode>>, MfaConfirmTotpDeviceError>> + Send;
+
+    /// Rename a TOTP device.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
+    fn rename_totp_device(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        totp_device_id: TotpDeviceId,
+        name: TotpDeviceName,
+    ) -> impl Future<Output = Result<TotpDevice, MfaRenameTotpDeviceError>> + Send;
+
+    /// Delete a TOTP device.
+    ///
+    /// Disables MFA if this was the last remaining MFA device.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
+    fn delete_totp_device(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        totp_device_id: TotpDeviceId,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<(), MfaDeleteTotpDeviceError>> + Send;
+
+    /// Return all WebAuthn devices of the given user.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    fn list_webauthn_devices(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+    ) -> impl Future<Output = Result<Vec<WebauthnDevice>, MfaListWebauthnDevicesError>> + Send;
+
+    /// Start the registration of a new WebAuthn device.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
+    fn start_webauthn_registration(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+    ) -> impl Future<Output = Result<WebauthnRegistrationOptions, MfaStartWebauthnRegistrationError>>
+           + Send;
+
+    /// Finish the registration of a new WebAuthn device.
+    ///
+    /// If MFA has not been enabled before, a new set of MFA recovery codes is
+    /// generated and returned.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
+    fn finish_webauthn_registration(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        name: WebauthnDeviceName,
+        registration: WebauthnRegistration,
+        ctx: &AuditContext,
+    ) -> impl Future<
+        Output = Result<MfaWebauthnRegistrationResult, MfaFinishWebauthnRegistrationError>,
+    > + Send;
+
+    /// Delete a WebAuthn device.
+    ///
+    /// Disables MFA if this was the last remaining MFA device.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
+    fn delete_webauthn_device(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        webauthn_device_id: WebauthnDeviceId,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<(), MfaDeleteWebauthnDeviceError>> + Send;
+
+    /// Generate a new challenge that can be used by the given user to
+    /// authenticate with a WebAuthn device when logging in.
+    ///
+    /// If the user does not exist, options are returned anyway to not reveal
+    /// which users exist, but they cannot be used to log in.
+    fn create_webauthn_assertion_options(
+        &self,
+        name_or_email: &UserNameOrEmailAddress,
+    ) -> impl Future<Output = anyhow::Result<WebauthnAssertionOptions>> + Send;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MfaCreateTotpDeviceResult {
+    pub totp_device: TotpDevice,
+    pub setup: TotpSetup,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MfaWebauthnRegistrationResult {
+    pub webauthn_device: WebauthnDevice,
+    /// The new MFA recovery codes, if MFA has not been enabled before.
+    pub recovery_codes: Option<Vec<MfaRecoveryCode>>,
 }
 
 #[derive(Debug, Error)]
@@ -83,3 +268,119 @@ pub enum MfaDisableError {
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
+
+#[derive(Debug, Error)]
+pub enum MfaCountRecoveryCodesError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum MfaRegenerateRecoveryCodesError {
+    #[error("The user has not enabled mfa.")]
+    NotEnabled,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error("The user does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum MfaListTotpDevicesError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum MfaCreateTotpDeviceError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error("The user does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum MfaConfirmTotpDeviceError {
+    #[error("The totp device has already been enabled.")]
+    AlreadyEnabled,
+    #[error("The totp code in incorrect.")]
+    InvalidCode,
+    #[error("The totp device does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum MfaRenameTotpDeviceError {
+    #[error("The totp device does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum MfaDeleteTotpDeviceError {
+    #[error("The totp device does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum MfaListWebauthnDevicesError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum MfaStartWebauthnRegistrationError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error("The user does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum MfaFinishWebauthnRegistrationError {
+    #[error("The registration has not been started or has expired.")]
+    NotStarted,
+    #[error("The registration response is invalid.")]
+    InvalidRegistration,
+    #[error("The credential has already been registered.")]
+    AlreadyRegistered,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error("The user does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum MfaDeleteWebauthnDeviceError {
+    #[error("The webauthn device does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
@@ -0,0 +1,10 @@
+use std::future::Future;
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait MfaTotpSecretService<Txn: Send + Sync + 'static>: Send + Sync + 'static {
+    /// Encrypt all TOTP secrets again which have not been encrypted using the
+    /// current encryption key.
+    ///
+    /// Returns the number of secrets that have been encrypted again.
+    fn reencrypt(&self, txn: &mut Txn) -> impl Future<Output = anyhow::Result<usize>> + Send;
+}
@@ -0,0 +1,133 @@
+use std::future::Future;
+
+use academy_models::{
+    mfa::{
+        WebauthnAssertion, WebauthnAssertionOptions, WebauthnDevice, WebauthnDeviceName,
+        WebauthnRegistration, WebauthnRegistrationOptions,
+    },
+    user::{UserComposite, UserId},
+};
+use thiserror::Error;
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait MfaWebauthnDeviceService<Txn: Send + Sync + 'static>: Send + Sync + 'static {
+    /// Start the registration of a new WebAuthn device by generating a new
+    /// registration challenge for the given user.
+    fn start_registration(
+        &self,
+        txn: &mut Txn,
+        user_composite: &UserComposite,
+    ) -> impl Future<Output = anyhow::Result<WebauthnRegistrationOptions>> + Send;
+
+    /// Verify the response of the authenticator to the pending registration
+    /// challenge of the given user and create a new WebAuthn device.
+    fn finish_registration(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        name: WebauthnDeviceName,
+        registration: WebauthnRegistration,
+    ) -> impl Future<Output = Result<WebauthnDevice, MfaWebauthnDeviceFinishRegistrationError>> + Send;
+
+    /// Generate a new challenge that can be used by the given user to
+    /// authenticate with a WebAuthn device.
+    ///
+    /// If `user_id` is `None`, the challenge is not stored and can therefore
+    /// not be used to authenticate.
+    fn start_assertion(
+        &self,
+        user_id: Option<UserId>,
+    ) -> impl Future<Output = anyhow::Result<WebauthnAssertionOptions>> + Send;
+
+    /// Verify a WebAuthn assertion of one of the given user's WebAuthn
+    /// devices.
+    ///
+    /// Returns `false` if the assertion is invalid or its challenge has not
+    /// been issued to this user by
+    /// [`MfaWebauthnDeviceService::start_assertion`]. Every challenge can
+    /// only be used once.
+    fn verify_assertion(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        assertion: WebauthnAssertion,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+}
+
+#[derive(Debug, Error)]
+pub enum MfaWebauthnDeviceFinishRegistrationError {
+    #[error("The registration has not been started or has expired.")]
+    NotStarted,
+    #[error("The registration response is invalid.")]
+    InvalidRegistration,
+    #[error("The credential has already been registered.")]
+    AlreadyRegistered,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[cfg(feature = "mock")]
+impl<Txn: Send + Sync + 'static> MockMfaWebauthnDeviceService<Txn> {
+    pub fn with_start_registration(
+        mut self,
+        user_composite: UserComposite,
+        result: WebauthnRegistrationOptions,
+    ) -> Self {
+        self.expect_start_registration()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_composite),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_finish_registration(
+        mut self,
+        user_id: UserId,
+        name: WebauthnDeviceName,
+        registration: WebauthnRegistration,
+        result: Result<WebauthnDevice, MfaWebauthnDeviceFinishRegistrationError>,
+    ) -> Self {
+        self.expect_finish_registration()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(name),
+                mockall::predicate::eq(registration),
+            )
+            .return_once(|_, _, _, _| Box::pin(std::future::ready(result)));
+        self
+    }
+
+    pub fn with_start_assertion(
+        mut self,
+        user_id: Option<UserId>,
+        result: WebauthnAssertionOptions,
+    ) -> Self {
+        self.expect_start_assertion()
+            .once()
+            .with(mockall::predicate::eq(user_id))
+            .return_once(|_| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_verify_assertion(
+        mut self,
+        user_id: UserId,
+        assertion: WebauthnAssertion,
+        result: bool,
+    ) -> Self {
+        self.expect_verify_assertion()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(assertion),
+            )
+            .return_once(move |_, _, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+}
@@ -1,11 +1,12 @@
 use academy_core_mfa_contracts::{
     authenticate::{MfaAuthenticateError, MfaAuthenticateResult, MfaAuthenticateService},
-    disable::MfaDisableService,
+    webauthn_device::MfaWebauthnDeviceService,
 };
 use academy_di::Build;
 use academy_models::{mfa::MfaAuthentication, user::UserId};
 use academy_persistence_contracts::mfa::MfaRepository;
 use academy_shared_contracts::{
+    encryption::EncryptionService,
     hash::HashService,
     totp::{TotpCheckError, TotpService},
 };
@@ -13,21 +14,25 @@ use academy_utils::trace_instrument;
 use anyhow::Context;
 use tracing::trace;
 
+use crate::totp_device::decrypt_secret;
+
 #[derive(Debug, Clone, Build, Default)]
-pub struct MfaAuthenticateServiceImpl<Hash, Totp, MfaDisable, MfaRepo> {
+pub struct MfaAuthenticateServiceImpl<Hash, Totp, Encryption, MfaWebauthnDevice, MfaRepo> {
     hash: Hash,
     totp: Totp,
-    mfa_disable: MfaDisable,
+    encryption: Encryption,
+    mfa_webauthn_device: MfaWebauthnDevice,
     mfa_repo: MfaRepo,
 }
 
-impl<Txn, Hash, Totp, MfaDisable, MfaRepo> MfaAuthenticateService<Txn>
-    for MfaAuthenticateServiceImpl<Hash, Totp, MfaDisable, MfaRepo>
+impl<Txn, Hash, Totp, Encryption, MfaWebauthnDevice, MfaRepo> MfaAuthenticateService<Txn>
+    for MfaAuthenticateServiceImpl<Hash, Totp, Encryption, MfaWebauthnDevice, MfaRepo>
 where
     Txn: Send + Sync + 'static,
     Hash: HashService,
     Totp: TotpService,
-    MfaDisable: MfaDisableService<Txn>,
+    Encryption: EncryptionService,
+    MfaWebauthnDevice: MfaWebauthnDeviceService<Txn>,
     MfaRepo: MfaRepository<Txn>,
 {
     #[trace_instrument(skip(self, txn))]
@@ -45,27 +50,31 @@ where
             .context("Failed to get totp secrets from database")?;
 
         if totp_secrets.is_empty() {
-            trace!("no totp secrets");
-            return Ok(MfaAuthenticateResult::Disabled);
+            trace!("no totp secrets, list webauthn devices");
+            let webauthn_devices = self
+                .mfa_repo
+                .list_webauthn_devices_by_user(txn, user_id)
+                .await
+                .context("Failed to get webauthn devices from database")?;
+
+            if webauthn_devices.is_empty() {
+                trace!("no webauthn devices");
+                return Ok(MfaAuthenticateResult::Disabled);
+            }
         }
 
         if let Some(recovery_code) = cmd.recovery_code {
             trace!("try recovery code");
 
-            if let Some(hash) = self
+            let hash = self.hash.sha256(&recovery_code).into();
+            if self
                 .mfa_repo
-                .get_mfa_recovery_code_hash(txn, user_id)
+                .delete_mfa_recovery_code_hash(txn, user_id, hash)
                 .await
-                .context("Failed to get recovery code hash from database")?
+                .context("Failed to delete recovery code hash from database")?
             {
-                if self.hash.sha256(&recovery_code) == *hash {
-                    trace!("recovery code matches");
-                    self.mfa_disable
-                        .disable(txn, user_id)
-                        .await
-                        .context("Failed to disable MFA")?;
-                    return Ok(MfaAuthenticateResult::Reset);
-                }
+                trace!("recovery code matches and has been consumed");
+                return Ok(MfaAuthenticateResult::Ok);
             }
         }
 
@@ -73,6 +82,7 @@ where
             trace!("try totp code");
 
             for secret in totp_secrets {
+                let secret = decrypt_secret(&self.encryption, &secret)?;
                 match self.totp.check(&code, secret).await {
                     Ok(()) => {
                         trace!("totp code matches");
@@ -86,6 +96,20 @@ where
             }
         }
 
+        if let Some(assertion) = cmd.webauthn_assertion {
+            trace!("try webauthn assertion");
+
+            if self
+                .mfa_webauthn_device
+                .verify_assertion(txn, user_id, assertion)
+                .await
+                .context("Failed to verify webauthn assertion")?
+            {
+                trace!("webauthn assertion is valid");
+                return Ok(MfaAuthenticateResult::Ok);
+            }
+        }
+
         trace!("all mfa options failed");
 
         Err(MfaAuthenticateError::Failed)
@@ -94,11 +118,19 @@ where
 
 #[cfg(test)]
 mod tests {
-    use academy_core_mfa_contracts::disable::MockMfaDisableService;
-    use academy_demo::{user::FOO, SHA256HASH1, SHA256HASH2};
-    use academy_models::mfa::TotpSecret;
+    use academy_core_mfa_contracts::webauthn_device::MockMfaWebauthnDeviceService;
+    use academy_demo::{
+        mfa::ADMIN2_WEBAUTHN_1,
+        user::{ADMIN2, FOO},
+        SHA256HASH1,
+    };
+    use academy_models::{
+        encryption::EncryptedData,
+        mfa::{TotpSecret, WebauthnAssertion},
+    };
     use academy_persistence_contracts::mfa::MockMfaRepository;
     use academy_shared_contracts::{
+        encryption::MockEncryptionService,
         hash::MockHashService,
         totp::{MockTotpService, TotpCheckError},
     };
@@ -109,7 +141,8 @@ mod tests {
     type Sut = MfaAuthenticateServiceImpl<
         MockHashService,
         MockTotpService,
-        MockMfaDisableService<()>,
+        MockEncryptionService,
+        MockMfaWebauthnDeviceService<()>,
         MockMfaRepository<()>,
     >;
 
@@ -119,10 +152,12 @@ mod tests {
         let cmd = MfaAuthentication {
             totp_code: None,
             recovery_code: None,
+            webauthn_assertion: None,
         };
 
         let mfa_repo = MockMfaRepository::new()
-            .with_list_enabled_totp_device_secrets_by_user(FOO.user.id, vec![]);
+            .with_list_enabled_totp_device_secrets_by_user(FOO.user.id, vec![])
+            .with_list_webauthn_devices_by_user(FOO.user.id, vec![]);
 
         let sut = MfaAuthenticateServiceImpl {
             mfa_repo,
@@ -142,23 +177,18 @@ mod tests {
         let cmd = MfaAuthentication {
             totp_code: None,
             recovery_code: Some("PJVURV-QRK3YJ-O3U7T6-D50KAC".try_into().unwrap()),
+            webauthn_assertion: None,
         };
 
-        let secret =
-            TotpSecret::try_new("IZ6GJPVVwQWfRhQTuxwrdBfn".to_owned().into_bytes()).unwrap();
-
         let hash =
             MockHashService::new().with_sha256(cmd.recovery_code.clone().unwrap(), *SHA256HASH1);
 
-        let mfa_disable = MockMfaDisableService::new().with_disable(FOO.user.id);
-
         let mfa_repo = MockMfaRepository::new()
-            .with_list_enabled_totp_device_secrets_by_user(FOO.user.id, vec![secret])
-            .with_get_mfa_recovery_code_hash(FOO.user.id, Some((*SHA256HASH1).into()));
+            .with_list_enabled_totp_device_secrets_by_user(FOO.user.id, vec![encrypted_secret()])
+            .with_delete_mfa_recovery_code_hash(FOO.user.id, (*SHA256HASH1).into(), true);
 
         let sut = MfaAuthenticateServiceImpl {
             hash,
-            mfa_disable,
             mfa_repo,
             ..Sut::default()
         };
@@ -167,7 +197,7 @@ mod tests {
         let result = sut.authenticate(&mut (), FOO.user.id, cmd).await;
 
         // Assert
-        assert_eq!(result.unwrap(), MfaAuthenticateResult::Reset);
+        assert_eq!(result.unwrap(), MfaAuthenticateResult::Ok);
     }
 
     #[tokio::test]
@@ -176,6 +206,7 @@ mod tests {
         let cmd = MfaAuthentication {
             totp_code: Some("123456".try_into().unwrap()),
             recovery_code: None,
+            webauthn_assertion: None,
         };
 
         let secret =
@@ -187,11 +218,15 @@ mod tests {
             Ok(()),
         );
 
+        let encryption =
+            MockEncryptionService::new().with_decrypt(encrypted_secret(), secret.into_inner());
+
         let mfa_repo = MockMfaRepository::new()
-            .with_list_enabled_totp_device_secrets_by_user(FOO.user.id, vec![secret]);
+            .with_list_enabled_totp_device_secrets_by_user(FOO.user.id, vec![encrypted_secret()]);
 
         let sut = MfaAuthenticateServiceImpl {
             totp,
+            encryption,
             mfa_repo,
             ..Sut::default()
         };
@@ -209,13 +244,11 @@ mod tests {
         let cmd = MfaAuthentication {
             totp_code: None,
             recovery_code: None,
+            webauthn_assertion: None,
         };
 
-        let secret =
-            TotpSecret::try_new("IZ6GJPVVwQWfRhQTuxwrdBfn".to_owned().into_bytes()).unwrap();
-
         let mfa_repo = MockMfaRepository::new()
-            .with_list_enabled_totp_device_secrets_by_user(FOO.user.id, vec![secret]);
+            .with_list_enabled_totp_device_secrets_by_user(FOO.user.id, vec![encrypted_secret()]);
 
         let sut = MfaAuthenticateServiceImpl {
             mfa_repo,
@@ -230,21 +263,23 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn failed_no_recovery_code() {
+    async fn failed_invalid_recovery_code() {
         // Arrange
         let cmd = MfaAuthentication {
             totp_code: None,
             recovery_code: Some("PJVURV-QRK3YJ-O3U7T6-D50KAC".try_into().unwrap()),
+            webauthn_assertion: None,
         };
 
-        let secret =
-            TotpSecret::try_new("IZ6GJPVVwQWfRhQTuxwrdBfn".to_owned().into_bytes()).unwrap();
+        let hash =
+            MockHashService::new().with_sha256(cmd.recovery_code.clone().unwrap(), *SHA256HASH1);
 
         let mfa_repo = MockMfaRepository::new()
-            .with_list_enabled_totp_device_secrets_by_user(FOO.user.id, vec![secret])
-            .with_get_mfa_recovery_code_hash(FOO.user.id, None);
+            .with_list_enabled_totp_device_secrets_by_user(FOO.user.id, vec![encrypted_secret()])
+            .with_delete_mfa_recovery_code_hash(FOO.user.id, (*SHA256HASH1).into(), false);
 
         let sut = MfaAuthenticateServiceImpl {
+            hash,
             mfa_repo,
             ..Sut::default()
         };
@@ -257,25 +292,32 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn failed_invalid_recovery_code() {
+    async fn failed_invalid_totp_code() {
         // Arrange
         let cmd = MfaAuthentication {
-            totp_code: None,
-            recovery_code: Some("PJVURV-QRK3YJ-O3U7T6-D50KAC".try_into().unwrap()),
+            totp_code: Some("123456".try_into().unwrap()),
+            recovery_code: None,
+            webauthn_assertion: None,
         };
 
         let secret =
             TotpSecret::try_new("IZ6GJPVVwQWfRhQTuxwrdBfn".to_owned().into_bytes()).unwrap();
 
-        let hash =
-            MockHashService::new().with_sha256(cmd.recovery_code.clone().unwrap(), *SHA256HASH1);
+        let totp = MockTotpService::new().with_check(
+            cmd.totp_code.clone().unwrap(),
+            secret.clone(),
+            Err(TotpCheckError::InvalidCode),
+        );
+
+        let encryption =
+            MockEncryptionService::new().with_decrypt(encrypted_secret(), secret.into_inner());
 
         let mfa_repo = MockMfaRepository::new()
-            .with_list_enabled_totp_device_secrets_by_user(FOO.user.id, vec![secret])
-            .with_get_mfa_recovery_code_hash(FOO.user.id, Some((*SHA256HASH2).into()));
+            .with_list_enabled_totp_device_secrets_by_user(FOO.user.id, vec![encrypted_secret()]);
 
         let sut = MfaAuthenticateServiceImpl {
-            hash,
+            totp,
+            encryption,
             mfa_repo,
             ..Sut::default()
         };
@@ -288,11 +330,12 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn failed_invalid_totp_code() {
+    async fn failed_totp_code_recently_used() {
         // Arrange
         let cmd = MfaAuthentication {
             totp_code: Some("123456".try_into().unwrap()),
             recovery_code: None,
+            webauthn_assertion: None,
         };
 
         let secret =
@@ -301,14 +344,18 @@ mod tests {
         let totp = MockTotpService::new().with_check(
             cmd.totp_code.clone().unwrap(),
             secret.clone(),
-            Err(TotpCheckError::InvalidCode),
+            Err(TotpCheckError::RecentlyUsed),
         );
 
+        let encryption =
+            MockEncryptionService::new().with_decrypt(encrypted_secret(), secret.into_inner());
+
         let mfa_repo = MockMfaRepository::new()
-            .with_list_enabled_totp_device_secrets_by_user(FOO.user.id, vec![secret]);
+            .with_list_enabled_totp_device_secrets_by_user(FOO.user.id, vec![encrypted_secret()]);
 
         let sut = MfaAuthenticateServiceImpl {
             totp,
+            encryption,
             mfa_repo,
             ..Sut::default()
         };
@@ -321,35 +368,82 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn failed_totp_code_recently_used() {
+    async fn ok_webauthn() {
         // Arrange
         let cmd = MfaAuthentication {
-            totp_code: Some("123456".try_into().unwrap()),
+            totp_code: None,
             recovery_code: None,
+            webauthn_assertion: Some(webauthn_assertion()),
         };
 
-        let secret =
-            TotpSecret::try_new("IZ6GJPVVwQWfRhQTuxwrdBfn".to_owned().into_bytes()).unwrap();
+        let mfa_repo = MockMfaRepository::new()
+            .with_list_enabled_totp_device_secrets_by_user(ADMIN2.user.id, vec![])
+            .with_list_webauthn_devices_by_user(ADMIN2.user.id, vec![ADMIN2_WEBAUTHN_1.clone()]);
 
-        let totp = MockTotpService::new().with_check(
-            cmd.totp_code.clone().unwrap(),
-            secret.clone(),
-            Err(TotpCheckError::RecentlyUsed),
+        let mfa_webauthn_device = MockMfaWebauthnDeviceService::new().with_verify_assertion(
+            ADMIN2.user.id,
+            webauthn_assertion(),
+            true,
         );
 
+        let sut = MfaAuthenticateServiceImpl {
+            mfa_webauthn_device,
+            mfa_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.authenticate(&mut (), ADMIN2.user.id, cmd).await;
+
+        // Assert
+        assert_eq!(result.unwrap(), MfaAuthenticateResult::Ok);
+    }
+
+    #[tokio::test]
+    async fn failed_invalid_webauthn_assertion() {
+        // Arrange
+        let cmd = MfaAuthentication {
+            totp_code: None,
+            recovery_code: None,
+            webauthn_assertion: Some(webauthn_assertion()),
+        };
+
         let mfa_repo = MockMfaRepository::new()
-            .with_list_enabled_totp_device_secrets_by_user(FOO.user.id, vec![secret]);
+            .with_list_enabled_totp_device_secrets_by_user(ADMIN2.user.id, vec![])
+            .with_list_webauthn_devices_by_user(ADMIN2.user.id, vec![ADMIN2_WEBAUTHN_1.clone()]);
+
+        let mfa_webauthn_device = MockMfaWebauthnDeviceService::new().with_verify_assertion(
+            ADMIN2.user.id,
+            webauthn_assertion(),
+            false,
+        );
 
         let sut = MfaAuthenticateServiceImpl {
-            totp,
+            mfa_webauthn_device,
             mfa_repo,
             ..Sut::default()
         };
 
         // Act
-        let result = sut.authenticate(&mut (), FOO.user.id, cmd).await;
+        let result = sut.authenticate(&mut (), ADMIN2.user.id, cmd).await;
 
         // Assert
         assert_matches!(result, Err(MfaAuthenticateError::Failed));
     }
+
+    fn encrypted_secret() -> EncryptedData {
+        EncryptedData {
+            key_version: Some(1),
+            data: b"the encrypted totp secret".to_vec(),
+        }
+    }
+
+    fn webauthn_assertion() -> WebauthnAssertion {
+        WebauthnAssertion {
+            credential_id: ADMIN2_WEBAUTHN_1.credential_id.clone(),
+            client_data_json: b"client data".to_vec(),
+            authenticator_data: b"authenticator data".to_vec(),
+            signature: b"signature".to_vec(),
+        }
+    }
 }
@@ -24,11 +24,17 @@ where
             .await
             .context("Failed to delete totp devices from database")?;
 
-        trace!("delete recovery code");
+        trace!("delete webauthn devices");
         self.mfa_repo
-            .delete_mfa_recovery_code_hash(txn, user_id)
+            .delete_webauthn_devices_by_user(txn, user_id)
             .await
-            .context("Failed to delete MFA recovery code hash from database")?;
+            .context("Failed to delete webauthn devices from database")?;
+
+        trace!("delete recovery codes");
+        self.mfa_repo
+            .delete_mfa_recovery_code_hashes_by_user(txn, user_id)
+            .await
+            .context("Failed to delete MFA recovery code hashes from database")?;
 
         Ok(())
     }
@@ -46,7 +52,8 @@ mod tests {
         // Arrange
         let mfa_repo = MockMfaRepository::new()
             .with_delete_totp_devices_by_user(FOO.user.id)
-            .with_delete_mfa_recovery_code_hash(FOO.user.id);
+            .with_delete_webauthn_devices_by_user(FOO.user.id)
+            .with_delete_mfa_recovery_code_hashes_by_user(FOO.user.id);
 
         let sut = MfaDisableServiceImpl { mfa_repo };
 
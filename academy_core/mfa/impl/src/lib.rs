@@ -1,20 +1,34 @@
+use std::time::Duration;
+
 use academy_auth_contracts::{AuthResultExt, AuthService};
+use academy_core_audit_contracts::audit::AuditService;
 use academy_core_mfa_contracts::{
     disable::MfaDisableService,
     recovery::MfaRecoveryService,
     totp_device::{MfaTotpDeviceConfirmError, MfaTotpDeviceService},
-    MfaDisableError, MfaEnableError, MfaFeatureService, MfaInitializeError,
+    webauthn_device::{MfaWebauthnDeviceFinishRegistrationError, MfaWebauthnDeviceService},
+    MfaConfirmTotpDeviceError, MfaCountRecoveryCodesError, MfaCreateTotpDeviceError,
+    MfaCreateTotpDeviceResult, MfaDeleteTotpDeviceError, MfaDeleteWebauthnDeviceError,
+    MfaDisableError, MfaEnableError, MfaFeatureService, MfaFinishWebauthnRegistrationError,
+    MfaInitializeError, MfaListTotpDevicesError, MfaListWebauthnDevicesError,
+    MfaRegenerateRecoveryCodesError, MfaRenameTotpDeviceError, MfaStartWebauthnRegistrationError,
+    MfaWebauthnRegistrationResult,
 };
 use academy_di::Build;
 use academy_models::{
+    audit::{AuditAction, AuditContext},
     auth::AccessToken,
-    mfa::{MfaRecoveryCode, TotpCode, TotpSetup},
-    user::UserIdOrSelf,
+    mfa::{
+        MfaRecoveryCode, TotpCode, TotpDevice, TotpDeviceId, TotpDeviceName, TotpDevicePatch,
+        TotpSetup, WebauthnAssertionOptions, WebauthnDevice, WebauthnDeviceId, WebauthnDeviceName,
+        WebauthnRegistration, WebauthnRegistrationOptions,
+    },
+    user::{UserIdOrSelf, UserNameOrEmailAddress},
 };
 use academy_persistence_contracts::{
     mfa::MfaRepository, user::UserRepository, Database, Transaction,
 };
-use academy_utils::trace_instrument;
+use academy_utils::{patch::Patch, trace_instrument};
 use anyhow::Context;
 use tracing::trace;
 
@@ -22,6 +36,8 @@ pub mod authenticate;
 pub mod disable;
 pub mod recovery;
 pub mod totp_device;
+pub mod totp_secret;
+pub mod webauthn_device;
 
 #[cfg(test)]
 mod tests;
@@ -35,6 +51,8 @@ pub struct MfaFeatureServiceImpl<
     MfaRecovery,
     MfaDisable,
     MfaTotpDevice,
+    MfaWebauthnDevice,
+    Audit,
 > {
     db: Db,
     auth: Auth,
@@ -43,10 +61,38 @@ pub struct MfaFeatureServiceImpl<
     mfa_recovery: MfaRecovery,
     mfa_disable: MfaDisable,
     mfa_totp_device: MfaTotpDevice,
+    mfa_webauthn_device: MfaWebauthnDevice,
+    audit: Audit,
+}
+
+#[derive(Debug, Clone)]
+pub struct MfaFeatureConfig {
+    pub recovery_code_count: usize,
+    pub webauthn_challenge_ttl: Duration,
 }
 
-impl<Db, Auth, UserRepo, MfaRepo, MfaRecovery, MfaDisable, MfaTotpDevice> MfaFeatureService
-    for MfaFeatureServiceImpl<Db, Auth, UserRepo, MfaRepo, MfaRecovery, MfaDisable, MfaTotpDevice>
+impl<
+        Db,
+        Auth,
+        UserRepo,
+        MfaRepo,
+        MfaRecovery,
+        MfaDisable,
+        MfaTotpDevice,
+        MfaWebauthnDevice,
+        Audit,
+    > MfaFeatureService
+    for MfaFeatureServiceImpl<
+        Db,
+        Auth,
+        UserRepo,
+        MfaRepo,
+        MfaRecovery,
+        MfaDisable,
+        MfaTotpDevice,
+        MfaWebauthnDevice,
+        Audit,
+    >
 where
     Db: Database,
     Auth: AuthService<Db::Transaction>,
@@ -55,6 +101,8 @@ where
     MfaRecovery: MfaRecoveryService<Db::Transaction>,
     MfaDisable: MfaDisableService<Db::Transaction>,
     MfaTotpDevice: MfaTotpDeviceService<Db::Transaction>,
+    MfaWebauthnDevice: MfaWebauthnDeviceService<Db::Transaction>,
+    Audit: AuditService<Db::Transaction>,
 {
     #[trace_instrument(skip(self))]
     async fn initialize(
@@ -63,6 +111,7 @@ where
         user_id: UserIdOrSelf,
     ) -> Result<TotpSetup, MfaInitializeError> {
         let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
         let user_id = user_id.unwrap_or(auth.user_id);
         auth.ensure_self_or_admin(user_id).map_auth_err()?;
 
@@ -101,9 +150,10 @@ where
         } else {
             trace!("create new device");
             self.mfa_totp_device
-                .create(&mut txn, user_id)
+                .create(&mut txn, user_id, TotpDeviceName::default())
                 .await
                 .context("Failed to create new totp device")?
+                .1
         };
 
         txn.commit().await?;
@@ -117,8 +167,10 @@ where
         token: &AccessToken,
         user_id: UserIdOrSelf,
         code: TotpCode,
-    ) -> Result<MfaRecoveryCode, MfaEnableError> {
+        ctx: &AuditContext,
+    ) -> Result<Vec<MfaRecoveryCode>, MfaEnableError> {
         let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
         let user_id = user_id.unwrap_or(auth.user_id);
         auth.ensure_self_or_admin(user_id).map_auth_err()?;
 
@@ -163,16 +215,28 @@ where
                     .into(),
             })?;
 
-        trace!("setup recovery code");
-        let recovery_code = self
+        trace!("setup recovery codes");
+        let recovery_codes = self
             .mfa_recovery
             .setup(&mut txn, user_id)
             .await
-            .context("Failed to setup recovery code")?;
+            .context("Failed to setup recovery codes")?;
+
+        trace!("record audit event");
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::MfaEnable,
+            )
+            .await
+            .context("Failed to record audit event")?;
 
         txn.commit().await?;
 
-        Ok(recovery_code)
+        Ok(recovery_codes)
     }
 
     #[trace_instrument(skip(self))]
@@ -180,8 +244,10 @@ where
         &self,
         token: &AccessToken,
         user_id: UserIdOrSelf,
+        ctx: &AuditContext,
     ) -> Result<(), MfaDisableError> {
         let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
         let user_id = user_id.unwrap_or(auth.user_id);
         auth.ensure_self_or_admin(user_id).map_auth_err()?;
 
@@ -205,7 +271,16 @@ where
             .context("Failed to get totp devices from database")?;
 
         if totp_devices.iter().all(|x| !x.enabled) {
-            return Err(MfaDisableError::NotEnabled);
+            trace!("list webauthn devices");
+            let webauthn_devices = self
+                .mfa_repo
+                .list_webauthn_devices_by_user(&mut txn, user_id)
+                .await
+                .context("Failed to get webauthn devices from database")?;
+
+            if webauthn_devices.is_empty() {
+                return Err(MfaDisableError::NotEnabled);
+            }
         }
 
         trace!("disable mfa");
@@ -214,8 +289,525 @@ where
             .await
             .context("Failed to disable mfa")?;
 
+        trace!("record audit event");
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::MfaDisable,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn count_recovery_codes(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+    ) -> Result<u64, MfaCountRecoveryCodesError> {
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        self.mfa_repo
+            .count_mfa_recovery_code_hashes(&mut txn, user_id)
+            .await
+            .context("Failed to count recovery code hashes in database")
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn regenerate_recovery_codes(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        ctx: &AuditContext,
+    ) -> Result<Vec<MfaRecoveryCode>, MfaRegenerateRecoveryCodesError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let user_composite = self
+            .user_repo
+            .get_composite(&mut txn, user_id)
+            .await
+            .context("Failed to get user from database")?
+            .ok_or(MfaRegenerateRecoveryCodesError::NotFound)?;
+
+        if !user_composite.details.mfa_enabled {
+            return Err(MfaRegenerateRecoveryCodesError::NotEnabled);
+        }
+
+        trace!("setup recovery codes");
+        let recovery_codes = self
+            .mfa_recovery
+            .setup(&mut txn, user_id)
+            .await
+            .context("Failed to setup recovery codes")?;
+
+        trace!("record audit event");
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::RecoveryCodesRegenerate,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        Ok(recovery_codes)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn list_totp_devices(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+    ) -> Result<Vec<TotpDevice>, MfaListTotpDevicesError> {
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        self.mfa_repo
+            .list_totp_devices_by_user(&mut txn, user_id)
+            .await
+            .context("Failed to get totp devices from database")
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn create_totp_device(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        name: TotpDeviceName,
+    ) -> Result<MfaCreateTotpDeviceResult, MfaCreateTotpDeviceError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        trace!("check user existence");
+        if !self
+            .user_repo
+            .exists(&mut txn, user_id)
+            .await
+            .context("Failed to check user existence")?
+        {
+            return Err(MfaCreateTotpDeviceError::NotFound);
+        }
+
+        let (totp_device, setup) = self
+            .mfa_totp_device
+            .create(&mut txn, user_id, name)
+            .await
+            .context("Failed to create new totp device")?;
+
+        txn.commit().await?;
+
+        Ok(MfaCreateTotpDeviceResult { totp_device, setup })
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn confirm_totp_device(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        totp_device_id: TotpDeviceId,
+        code: TotpCode,
+        ctx: &AuditContext,
+    ) -> Result<Option<Vec<MfaRecoveryCode>>, MfaConfirmTotpDeviceError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let totp_device = self
+            .mfa_repo
+            .get_totp_device(&mut txn, totp_device_id)
+            .await
+            .context("Failed to get totp device from database")?
+            .filter(|device| device.user_id == user_id)
+            .ok_or(MfaConfirmTotpDeviceError::NotFound)?;
+
+        if totp_device.enabled {
+            return Err(MfaConfirmTotpDeviceError::AlreadyEnabled);
+        }
+
+        let user_composite = self
+            .user_repo
+            .get_composite(&mut txn, user_id)
+            .await
+            .context("Failed to get user from database")?
+            .ok_or(MfaConfirmTotpDeviceError::NotFound)?;
+
+        self.mfa_totp_device
+            .confirm(&mut txn, totp_device, code)
+            .await
+            .map_err(|err| match err {
+                MfaTotpDeviceConfirmError::InvalidCode => MfaConfirmTotpDeviceError::InvalidCode,
+                MfaTotpDeviceConfirmError::Other(err) => err
+                    .context(format!("Failed to confirm totp device {}", *totp_device_id))
+                    .into(),
+            })?;
+
+        let recovery_codes = if user_composite.details.mfa_enabled {
+            None
+        } else {
+            trace!("setup recovery codes");
+            let recovery_codes = self
+                .mfa_recovery
+                .setup(&mut txn, user_id)
+                .await
+                .context("Failed to setup recovery codes")?;
+
+            trace!("record audit event");
+            self.audit
+                .record(
+                    &mut txn,
+                    ctx,
+                    Some(auth.user_id),
+                    user_id,
+                    AuditAction::MfaEnable,
+                )
+                .await
+                .context("Failed to record audit event")?;
+
+            Some(recovery_codes)
+        };
+
+        txn.commit().await?;
+
+        Ok(recovery_codes)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn rename_totp_device(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        totp_device_id: TotpDeviceId,
+        name: TotpDeviceName,
+    ) -> Result<TotpDevice, MfaRenameTotpDeviceError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let totp_device = self
+            .mfa_repo
+            .get_totp_device(&mut txn, totp_device_id)
+            .await
+            .context("Failed to get totp device from database")?
+            .filter(|device| device.user_id == user_id)
+            .ok_or(MfaRenameTotpDeviceError::NotFound)?;
+
+        let patch = TotpDevicePatch::new().update_name(name);
+        self.mfa_repo
+            .update_totp_device(&mut txn, totp_device_id, patch.as_ref())
+            .await
+            .context("Failed to update totp device in database")?;
+
+        txn.commit().await?;
+
+        Ok(totp_device.update(patch))
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn delete_totp_device(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        totp_device_id: TotpDeviceId,
+        ctx: &AuditContext,
+    ) -> Result<(), MfaDeleteTotpDeviceError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let totp_device = self
+            .mfa_repo
+            .get_totp_device(&mut txn, totp_device_id)
+            .await
+            .context("Failed to get totp device from database")?
+            .filter(|device| device.user_id == user_id)
+            .ok_or(MfaDeleteTotpDeviceError::NotFound)?;
+
+        trace!("delete totp device");
+        self.mfa_repo
+            .delete_totp_device(&mut txn, totp_device_id)
+            .await
+            .context("Failed to delete totp device from database")?;
+
+        if totp_device.enabled {
+            trace!("check remaining mfa devices");
+            let totp_devices = self
+                .mfa_repo
+                .list_totp_devices_by_user(&mut txn, user_id)
+                .await
+                .context("Failed to get totp devices from database")?;
+            let webauthn_devices = self
+                .mfa_repo
+                .list_webauthn_devices_by_user(&mut txn, user_id)
+                .await
+                .context("Failed to get webauthn devices from database")?;
+
+            if totp_devices.iter().all(|x| !x.enabled) && webauthn_devices.is_empty() {
+                trace!("last mfa device deleted, disable mfa");
+                self.mfa_disable
+                    .disable(&mut txn, user_id)
+                    .await
+                    .context("Failed to disable mfa")?;
+
+                trace!("record audit event");
+                self.audit
+                    .record(
+                        &mut txn,
+                        ctx,
+                        Some(auth.user_id),
+                        user_id,
+                        AuditAction::MfaDisable,
+                    )
+                    .await
+                    .context("Failed to record audit event")?;
+            }
+        }
+
         txn.commit().await?;
 
         Ok(())
     }
+
+    #[trace_instrument(skip(self))]
+    async fn list_webauthn_devices(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+    ) -> Result<Vec<WebauthnDevice>, MfaListWebauthnDevicesError> {
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        self.mfa_repo
+            .list_webauthn_devices_by_user(&mut txn, user_id)
+            .await
+            .context("Failed to get webauthn devices from database")
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn start_webauthn_registration(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+    ) -> Result<WebauthnRegistrationOptions, MfaStartWebauthnRegistrationError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let user_composite = self
+            .user_repo
+            .get_composite(&mut txn, user_id)
+            .await
+            .context("Failed to get user from database")?
+            .ok_or(MfaStartWebauthnRegistrationError::NotFound)?;
+
+        self.mfa_webauthn_device
+            .start_registration(&mut txn, &user_composite)
+            .await
+            .context("Failed to start webauthn registration")
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn finish_webauthn_registration(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        name: WebauthnDeviceName,
+        registration: WebauthnRegistration,
+        ctx: &AuditContext,
+    ) -> Result<MfaWebauthnRegistrationResult, MfaFinishWebauthnRegistrationError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let user_composite = self
+            .user_repo
+            .get_composite(&mut txn, user_id)
+            .await
+            .context("Failed to get user from database")?
+            .ok_or(MfaFinishWebauthnRegistrationError::NotFound)?;
+
+        let webauthn_device = self
+            .mfa_webauthn_device
+            .finish_registration(&mut txn, user_id, name, registration)
+            .await
+            .map_err(|err| match err {
+                MfaWebauthnDeviceFinishRegistrationError::NotStarted => {
+                    MfaFinishWebauthnRegistrationError::NotStarted
+                }
+                MfaWebauthnDeviceFinishRegistrationError::InvalidRegistration => {
+                    MfaFinishWebauthnRegistrationError::InvalidRegistration
+                }
+                MfaWebauthnDeviceFinishRegistrationError::AlreadyRegistered => {
+                    MfaFinishWebauthnRegistrationError::AlreadyRegistered
+                }
+                MfaWebauthnDeviceFinishRegistrationError::Other(err) => {
+                    err.context("Failed to finish webauthn registration").into()
+                }
+            })?;
+
+        let recovery_codes = if user_composite.details.mfa_enabled {
+            None
+        } else {
+            trace!("setup recovery codes");
+            let recovery_codes = self
+                .mfa_recovery
+                .setup(&mut txn, user_id)
+                .await
+                .context("Failed to setup recovery codes")?;
+
+            trace!("record audit event");
+            self.audit
+                .record(
+                    &mut txn,
+                    ctx,
+                    Some(auth.user_id),
+                    user_id,
+                    AuditAction::MfaEnable,
+                )
+                .await
+                .context("Failed to record audit event")?;
+
+            Some(recovery_codes)
+        };
+
+        txn.commit().await?;
+
+        Ok(MfaWebauthnRegistrationResult {
+            webauthn_device,
+            recovery_codes,
+        })
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn delete_webauthn_device(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        webauthn_device_id: WebauthnDeviceId,
+        ctx: &AuditContext,
+    ) -> Result<(), MfaDeleteWebauthnDeviceError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        self.mfa_repo
+            .get_webauthn_device(&mut txn, webauthn_device_id)
+            .await
+            .context("Failed to get webauthn device from database")?
+            .filter(|device| device.user_id == user_id)
+            .ok_or(MfaDeleteWebauthnDeviceError::NotFound)?;
+
+        trace!("delete webauthn device");
+        self.mfa_repo
+            .delete_webauthn_device(&mut txn, webauthn_device_id)
+            .await
+            .context("Failed to delete webauthn device from database")?;
+
+        trace!("check remaining mfa devices");
+        let webauthn_devices = self
+            .mfa_repo
+            .list_webauthn_devices_by_user(&mut txn, user_id)
+            .await
+            .context("Failed to get webauthn devices from database")?;
+        let totp_devices = self
+            .mfa_repo
+            .list_totp_devices_by_user(&mut txn, user_id)
+            .await
+            .context("Failed to get totp devices from database")?;
+
+        if webauthn_devices.is_empty() && totp_devices.iter().all(|x| !x.enabled) {
+            trace!("last mfa device deleted, disable mfa");
+            self.mfa_disable
+                .disable(&mut txn, user_id)
+                .await
+                .context("Failed to disable mfa")?;
+
+            trace!("record audit event");
+            self.audit
+                .record(
+                    &mut txn,
+                    ctx,
+                    Some(auth.user_id),
+                    user_id,
+                    AuditAction::MfaDisable,
+                )
+                .await
+                .context("Failed to record audit event")?;
+        }
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn create_webauthn_assertion_options(
+        &self,
+        name_or_email: &UserNameOrEmailAddress,
+    ) -> anyhow::Result<WebauthnAssertionOptions> {
+        let mut txn = self.db.begin_transaction().await?;
+
+        let user_id = self
+            .user_repo
+            .get_composite_by_name_or_email(&mut txn, name_or_email)
+            .await
+            .context("Failed to get user from database")?
+            .map(|user_composite| user_composite.user.id);
+
+        self.mfa_webauthn_device
+            .start_assertion(user_id)
+            .await
+            .context("Failed to start webauthn assertion")
+    }
 }
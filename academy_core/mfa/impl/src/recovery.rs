@@ -6,11 +6,14 @@ use academy_shared_contracts::{hash::HashService, secret::SecretService};
 use academy_utils::trace_instrument;
 use anyhow::Context;
 
+use crate::MfaFeatureConfig;
+
 #[derive(Debug, Clone, Build)]
 pub struct MfaRecoveryServiceImpl<Secret, Hash, MfaRepo> {
     secret: Secret,
     hash: Hash,
     mfa_repo: MfaRepo,
+    config: MfaFeatureConfig,
 }
 
 impl<Txn, Secret, Hash, MfaRepo> MfaRecoveryService<Txn>
@@ -22,22 +25,27 @@ where
     MfaRepo: MfaRepository<Txn>,
 {
     #[trace_instrument(skip(self, txn))]
-    async fn setup(&self, txn: &mut Txn, user_id: UserId) -> anyhow::Result<MfaRecoveryCode> {
-        let recovery_code = self.secret.generate_mfa_recovery_code();
+    async fn setup(&self, txn: &mut Txn, user_id: UserId) -> anyhow::Result<Vec<MfaRecoveryCode>> {
+        let recovery_codes = (0..self.config.recovery_code_count)
+            .map(|_| self.secret.generate_mfa_recovery_code())
+            .collect::<Vec<_>>();
 
-        let hash = self.hash.sha256(&recovery_code).into();
+        let hashes = recovery_codes
+            .iter()
+            .map(|code| self.hash.sha256(code).into())
+            .collect::<Vec<_>>();
         self.mfa_repo
-            .save_mfa_recovery_code_hash(txn, user_id, hash)
+            .save_mfa_recovery_code_hashes(txn, user_id, &hashes)
             .await
-            .context("Failed to save MFA recovery code hash in database")?;
+            .context("Failed to save MFA recovery code hashes in database")?;
 
-        Ok(recovery_code)
+        Ok(recovery_codes)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use academy_demo::{user::FOO, SHA256HASH1};
+    use academy_demo::{user::FOO, SHA256HASH1, SHA256HASH2};
     use academy_persistence_contracts::mfa::MockMfaRepository;
     use academy_shared_contracts::{hash::MockHashService, secret::MockSecretService};
 
@@ -46,19 +54,34 @@ mod tests {
     #[tokio::test]
     async fn setup() {
         // Arrange
-        let expected = MfaRecoveryCode::try_new("PJVURV-QRK3YJ-O3U7T6-D50KAC").unwrap();
+        let expected = vec![
+            MfaRecoveryCode::try_new("PJVURV-QRK3YJ-O3U7T6-D50KAC").unwrap(),
+            MfaRecoveryCode::try_new("L5P3CS-8TKQ0G-KE2VRO-QPIUAJ").unwrap(),
+        ];
+
+        let config = MfaFeatureConfig {
+            recovery_code_count: 2,
+            ..Default::default()
+        };
 
-        let secret = MockSecretService::new().with_generate_mfa_recovery_code(expected.clone());
+        let secret = MockSecretService::new()
+            .with_generate_mfa_recovery_code(expected[0].clone())
+            .with_generate_mfa_recovery_code(expected[1].clone());
 
-        let hash = MockHashService::new().with_sha256(expected.clone(), *SHA256HASH1);
+        let hash = MockHashService::new()
+            .with_sha256(expected[0].clone(), *SHA256HASH1)
+            .with_sha256(expected[1].clone(), *SHA256HASH2);
 
-        let mfa_repo = MockMfaRepository::new()
-            .with_save_mfa_recovery_code_hash(FOO.user.id, (*SHA256HASH1).into());
+        let mfa_repo = MockMfaRepository::new().with_save_mfa_recovery_code_hashes(
+            FOO.user.id,
+            vec![(*SHA256HASH1).into(), (*SHA256HASH2).into()],
+        );
 
         let sut = MfaRecoveryServiceImpl {
             secret,
             hash,
             mfa_repo,
+            config,
         };
 
         // Act
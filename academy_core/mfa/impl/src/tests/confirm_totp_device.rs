@@ -0,0 +1,276 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_mfa_contracts::{
+    recovery::MockMfaRecoveryService,
+    totp_device::{MfaTotpDeviceConfirmError, MockMfaTotpDeviceService},
+    MfaConfirmTotpDeviceError, MfaFeatureService,
+};
+use academy_demo::{
+    mfa::{ADMIN2_TOTP_1, FOO_TOTP_1},
+    session::{ADMIN_1, BAR_1, FOO_1},
+    user::{ADMIN, ADMIN2, BAR, FOO},
+    UUID1,
+};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthorizeError},
+    mfa::{MfaRecoveryCode, TotpCode, TotpDevice},
+    user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{
+    mfa::MockMfaRepository, user::MockUserRepository, MockDatabase,
+};
+use academy_utils::{assert_matches, Apply};
+
+use crate::{tests::Sut, MfaFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok_mfa_disabled() {
+    // Arrange
+    let expected = vec![MfaRecoveryCode::try_new("PJVURV-QRK3YJ-O3U7T6-D50KAC").unwrap()];
+
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let mfa_repo =
+        MockMfaRepository::new().with_get_totp_device(FOO_TOTP_1.id, Some(FOO_TOTP_1.clone()));
+
+    let mfa_totp_device = MockMfaTotpDeviceService::new().with_confirm(
+        FOO_TOTP_1.clone(),
+        code(),
+        Ok(FOO_TOTP_1.clone().with(|x| x.enabled = true)),
+    );
+
+    let mfa_recovery = MockMfaRecoveryService::new().with_setup(FOO.user.id, expected.clone());
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::MfaEnable,
+    );
+
+    let sut = MfaFeatureServiceImpl {
+        audit,
+        auth,
+        db,
+        user_repo,
+        mfa_repo,
+        mfa_recovery,
+        mfa_totp_device,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .confirm_totp_device(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            FOO_TOTP_1.id,
+            code(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), Some(expected));
+}
+
+#[tokio::test]
+async fn ok_mfa_enabled() {
+    // Arrange
+    let totp_device = second_admin2_device();
+
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let user_repo =
+        MockUserRepository::new().with_get_composite(ADMIN2.user.id, Some(ADMIN2.clone()));
+
+    let mfa_repo =
+        MockMfaRepository::new().with_get_totp_device(totp_device.id, Some(totp_device.clone()));
+
+    let mfa_totp_device = MockMfaTotpDeviceService::new().with_confirm(
+        totp_device.clone(),
+        code(),
+        Ok(totp_device.clone().with(|x| x.enabled = true)),
+    );
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        mfa_repo,
+        mfa_totp_device,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .confirm_totp_device(
+            &"token".into(),
+            ADMIN2.user.id.into(),
+            totp_device.id,
+            code(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), None);
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .confirm_totp_device(
+            &"token".into(),
+            FOO.user.id.into(),
+            FOO_TOTP_1.id,
+            code(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(MfaConfirmTotpDeviceError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let mfa_repo = MockMfaRepository::new()
+        .with_get_totp_device(ADMIN2_TOTP_1.id, Some(ADMIN2_TOTP_1.clone()));
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        mfa_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .confirm_totp_device(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            ADMIN2_TOTP_1.id,
+            code(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(MfaConfirmTotpDeviceError::NotFound));
+}
+
+#[tokio::test]
+async fn already_enabled() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let mfa_repo = MockMfaRepository::new()
+        .with_get_totp_device(ADMIN2_TOTP_1.id, Some(ADMIN2_TOTP_1.clone()));
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        mfa_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .confirm_totp_device(
+            &"token".into(),
+            ADMIN2.user.id.into(),
+            ADMIN2_TOTP_1.id,
+            code(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(MfaConfirmTotpDeviceError::AlreadyEnabled));
+}
+
+#[tokio::test]
+async fn invalid_code() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let mfa_repo =
+        MockMfaRepository::new().with_get_totp_device(FOO_TOTP_1.id, Some(FOO_TOTP_1.clone()));
+
+    let mfa_totp_device = MockMfaTotpDeviceService::new().with_confirm(
+        FOO_TOTP_1.clone(),
+        code(),
+        Err(MfaTotpDeviceConfirmError::InvalidCode),
+    );
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        mfa_repo,
+        mfa_totp_device,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .confirm_totp_device(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            FOO_TOTP_1.id,
+            code(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(MfaConfirmTotpDeviceError::InvalidCode));
+}
+
+fn code() -> TotpCode {
+    TotpCode::try_new("123456").unwrap()
+}
+
+fn second_admin2_device() -> TotpDevice {
+    TotpDevice {
+        id: UUID1.into(),
+        name: "Backup Phone".try_into().unwrap(),
+        enabled: false,
+        ..ADMIN2_TOTP_1.clone()
+    }
+}
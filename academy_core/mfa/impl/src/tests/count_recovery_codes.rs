@@ -0,0 +1,62 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_mfa_contracts::{MfaCountRecoveryCodesError, MfaFeatureService};
+use academy_demo::{
+    session::{ADMIN_1, BAR_1},
+    user::{ADMIN, ADMIN2, BAR, FOO},
+};
+use academy_models::auth::{AuthError, AuthorizeError};
+use academy_persistence_contracts::{mfa::MockMfaRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, MfaFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let mfa_repo = MockMfaRepository::new().with_count_mfa_recovery_code_hashes(ADMIN2.user.id, 7);
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        mfa_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .count_recovery_codes(&"token".into(), ADMIN2.user.id.into())
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), 7);
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .count_recovery_codes(&"token".into(), FOO.user.id.into())
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(MfaCountRecoveryCodesError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
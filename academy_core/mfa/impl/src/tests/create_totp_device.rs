@@ -0,0 +1,142 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_mfa_contracts::{
+    totp_device::MockMfaTotpDeviceService, MfaCreateTotpDeviceError, MfaCreateTotpDeviceResult,
+    MfaFeatureService,
+};
+use academy_demo::{
+    mfa::ADMIN2_TOTP_1,
+    session::{ADMIN_1, FOO_1},
+    user::{ADMIN, ADMIN2, FOO},
+};
+use academy_models::{
+    auth::{AuthError, AuthenticateError, AuthorizeError},
+    mfa::{TotpDevice, TotpDeviceName, TotpSetup},
+    user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, MfaFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let totp_device = TotpDevice {
+        name: name(),
+        enabled: false,
+        ..ADMIN2_TOTP_1.clone()
+    };
+    let setup = TotpSetup {
+        secret: "the totp secret".into(),
+    };
+
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let user_repo = MockUserRepository::new().with_exists(ADMIN2.user.id, true);
+
+    let mfa_totp_device = MockMfaTotpDeviceService::new().with_create(
+        ADMIN2.user.id,
+        name(),
+        (totp_device.clone(), setup.clone()),
+    );
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        mfa_totp_device,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_totp_device(&"token".into(), ADMIN2.user.id.into(), name())
+        .await;
+
+    // Assert
+    assert_eq!(
+        result.unwrap(),
+        MfaCreateTotpDeviceResult { totp_device, setup }
+    );
+}
+
+#[tokio::test]
+async fn unauthenticated() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(None);
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_totp_device(&"token".into(), UserIdOrSelf::Slf, name())
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(MfaCreateTotpDeviceError::Auth(AuthError::Authenticate(
+            AuthenticateError::InvalidToken
+        )))
+    );
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_totp_device(&"token".into(), ADMIN2.user.id.into(), name())
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(MfaCreateTotpDeviceError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn user_not_found() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_exists(FOO.user.id, false);
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_totp_device(&"token".into(), FOO.user.id.into(), name())
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(MfaCreateTotpDeviceError::NotFound));
+}
+
+fn name() -> TotpDeviceName {
+    "Backup Phone".try_into().unwrap()
+}
@@ -0,0 +1,69 @@
+use academy_core_mfa_contracts::{
+    webauthn_device::MockMfaWebauthnDeviceService, MfaFeatureService,
+};
+use academy_demo::user::ADMIN2;
+use academy_models::{mfa::WebauthnAssertionOptions, user::UserNameOrEmailAddress};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+
+use crate::{tests::Sut, MfaFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let name_or_email = UserNameOrEmailAddress::Name(ADMIN2.user.name.clone());
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite_by_name_or_email(name_or_email.clone(), Some(ADMIN2.clone()));
+
+    let mfa_webauthn_device =
+        MockMfaWebauthnDeviceService::new().with_start_assertion(Some(ADMIN2.user.id), options());
+
+    let sut = MfaFeatureServiceImpl {
+        db,
+        user_repo,
+        mfa_webauthn_device,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_webauthn_assertion_options(&name_or_email).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), options());
+}
+
+#[tokio::test]
+async fn unknown_user() {
+    // Arrange
+    let name_or_email = UserNameOrEmailAddress::Email("nobody@example.com".parse().unwrap());
+
+    let db = MockDatabase::build(false);
+
+    let user_repo =
+        MockUserRepository::new().with_get_composite_by_name_or_email(name_or_email.clone(), None);
+
+    let mfa_webauthn_device =
+        MockMfaWebauthnDeviceService::new().with_start_assertion(None, options());
+
+    let sut = MfaFeatureServiceImpl {
+        db,
+        user_repo,
+        mfa_webauthn_device,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_webauthn_assertion_options(&name_or_email).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), options());
+}
+
+fn options() -> WebauthnAssertionOptions {
+    WebauthnAssertionOptions {
+        challenge: vec![42; 32].into(),
+        rp_id: "bootstrap.academy".into(),
+    }
+}
@@ -0,0 +1,160 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_mfa_contracts::{
+    disable::MockMfaDisableService, MfaDeleteWebauthnDeviceError, MfaFeatureService,
+};
+use academy_demo::{
+    mfa::{ADMIN2_TOTP_1, ADMIN2_WEBAUTHN_1},
+    session::{ADMIN_1, FOO_1},
+    user::{ADMIN, ADMIN2, FOO},
+};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthorizeError},
+    user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{mfa::MockMfaRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, MfaFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let mfa_repo = MockMfaRepository::new()
+        .with_get_webauthn_device(ADMIN2_WEBAUTHN_1.id, Some(ADMIN2_WEBAUTHN_1.clone()))
+        .with_delete_webauthn_device(ADMIN2_WEBAUTHN_1.id, true)
+        .with_list_webauthn_devices_by_user(ADMIN2.user.id, vec![])
+        .with_list_totp_devices_by_user(ADMIN2.user.id, vec![ADMIN2_TOTP_1.clone()]);
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        mfa_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_webauthn_device(
+            &"token".into(),
+            ADMIN2.user.id.into(),
+            ADMIN2_WEBAUTHN_1.id,
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn ok_last_device() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let mfa_repo = MockMfaRepository::new()
+        .with_get_webauthn_device(ADMIN2_WEBAUTHN_1.id, Some(ADMIN2_WEBAUTHN_1.clone()))
+        .with_delete_webauthn_device(ADMIN2_WEBAUTHN_1.id, true)
+        .with_list_webauthn_devices_by_user(ADMIN2.user.id, vec![])
+        .with_list_totp_devices_by_user(ADMIN2.user.id, vec![]);
+
+    let mfa_disable = MockMfaDisableService::new().with_disable(ADMIN2.user.id);
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        ADMIN2.user.id,
+        AuditAction::MfaDisable,
+    );
+
+    let sut = MfaFeatureServiceImpl {
+        audit,
+        auth,
+        db,
+        mfa_repo,
+        mfa_disable,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_webauthn_device(
+            &"token".into(),
+            ADMIN2.user.id.into(),
+            ADMIN2_WEBAUTHN_1.id,
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_webauthn_device(
+            &"token".into(),
+            ADMIN2.user.id.into(),
+            ADMIN2_WEBAUTHN_1.id,
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(MfaDeleteWebauthnDeviceError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let mfa_repo = MockMfaRepository::new()
+        .with_get_webauthn_device(ADMIN2_WEBAUTHN_1.id, Some(ADMIN2_WEBAUTHN_1.clone()));
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        mfa_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_webauthn_device(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            ADMIN2_WEBAUTHN_1.id,
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(MfaDeleteWebauthnDeviceError::NotFound));
+}
@@ -1,13 +1,16 @@
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_mfa_contracts::{
     disable::MockMfaDisableService, MfaDisableError, MfaFeatureService,
 };
 use academy_demo::{
-    mfa::FOO_TOTP_1,
+    mfa::{ADMIN2_WEBAUTHN_1, FOO_TOTP_1},
+    personal_access_token::FOO_PAT,
     session::{ADMIN_1, BAR_1, FOO_1},
-    user::{ADMIN, BAR, FOO},
+    user::{ADMIN, ADMIN2, BAR, FOO},
 };
 use academy_models::{
+    audit::{AuditAction, AuditContext},
     auth::{AuthError, AuthenticateError, AuthorizeError},
     user::UserIdOrSelf,
 };
@@ -34,7 +37,57 @@ async fn ok() {
 
     let mfa_disable = MockMfaDisableService::new().with_disable(FOO.user.id);
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::MfaDisable,
+    );
+
+    let sut = MfaFeatureServiceImpl {
+        audit,
+        auth,
+        db,
+        user_repo,
+        mfa_repo,
+        mfa_disable,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .disable(&"token".into(), UserIdOrSelf::Slf, &AuditContext::default())
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn ok_webauthn() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let user_repo = MockUserRepository::new().with_exists(ADMIN2.user.id, true);
+
+    let mfa_repo = MockMfaRepository::new()
+        .with_list_totp_devices_by_user(ADMIN2.user.id, vec![])
+        .with_list_webauthn_devices_by_user(ADMIN2.user.id, vec![ADMIN2_WEBAUTHN_1.clone()]);
+
+    let mfa_disable = MockMfaDisableService::new().with_disable(ADMIN2.user.id);
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        ADMIN2.user.id,
+        AuditAction::MfaDisable,
+    );
+
     let sut = MfaFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_repo,
@@ -44,7 +97,13 @@ async fn ok() {
     };
 
     // Act
-    let result = sut.disable(&"token".into(), UserIdOrSelf::Slf).await;
+    let result = sut
+        .disable(
+            &"token".into(),
+            ADMIN2.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
 
     // Assert
     result.unwrap();
@@ -61,7 +120,13 @@ async fn unauthenticated() {
     };
 
     // Act
-    let result = sut.disable(&"token".into(), FOO.user.id.into()).await;
+    let result = sut
+        .disable(
+            &"token".into(),
+            FOO.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
 
     // Assert
     assert_matches!(
@@ -83,7 +148,13 @@ async fn unauthorized() {
     };
 
     // Act
-    let result = sut.disable(&"token".into(), FOO.user.id.into()).await;
+    let result = sut
+        .disable(
+            &"token".into(),
+            FOO.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
 
     // Assert
     assert_matches!(
@@ -94,6 +165,35 @@ async fn unauthorized() {
     );
 }
 
+#[tokio::test]
+async fn personal_access_token_not_allowed() {
+    // Arrange
+    let auth = MockAuthService::new()
+        .with_authenticate_personal_access_token(Some((FOO.user.clone(), FOO_PAT.clone())));
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .disable(
+            &"token".into(),
+            FOO.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(MfaDisableError::Auth(AuthError::Authorize(
+            AuthorizeError::Session
+        )))
+    );
+}
+
 #[tokio::test]
 async fn user_not_found() {
     // Arrange
@@ -112,7 +212,13 @@ async fn user_not_found() {
     };
 
     // Act
-    let result = sut.disable(&"token".into(), FOO.user.id.into()).await;
+    let result = sut
+        .disable(
+            &"token".into(),
+            FOO.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
 
     // Assert
     assert_matches!(result, Err(MfaDisableError::NotFound));
@@ -127,7 +233,9 @@ async fn not_initialized() {
 
     let user_repo = MockUserRepository::new().with_exists(FOO.user.id, true);
 
-    let mfa_repo = MockMfaRepository::new().with_list_totp_devices_by_user(FOO.user.id, vec![]);
+    let mfa_repo = MockMfaRepository::new()
+        .with_list_totp_devices_by_user(FOO.user.id, vec![])
+        .with_list_webauthn_devices_by_user(FOO.user.id, vec![]);
 
     let sut = MfaFeatureServiceImpl {
         auth,
@@ -138,7 +246,9 @@ async fn not_initialized() {
     };
 
     // Act
-    let result = sut.disable(&"token".into(), UserIdOrSelf::Slf).await;
+    let result = sut
+        .disable(&"token".into(), UserIdOrSelf::Slf, &AuditContext::default())
+        .await;
 
     // Assert
     assert_matches!(result, Err(MfaDisableError::NotEnabled));
@@ -154,7 +264,8 @@ async fn not_enabled() {
     let user_repo = MockUserRepository::new().with_exists(FOO.user.id, true);
 
     let mfa_repo = MockMfaRepository::new()
-        .with_list_totp_devices_by_user(FOO.user.id, vec![FOO_TOTP_1.clone()]);
+        .with_list_totp_devices_by_user(FOO.user.id, vec![FOO_TOTP_1.clone()])
+        .with_list_webauthn_devices_by_user(FOO.user.id, vec![]);
 
     let sut = MfaFeatureServiceImpl {
         auth,
@@ -165,7 +276,9 @@ async fn not_enabled() {
     };
 
     // Act
-    let result = sut.disable(&"token".into(), UserIdOrSelf::Slf).await;
+    let result = sut
+        .disable(&"token".into(), UserIdOrSelf::Slf, &AuditContext::default())
+        .await;
 
     // Assert
     assert_matches!(result, Err(MfaDisableError::NotEnabled));
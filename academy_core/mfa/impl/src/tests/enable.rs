@@ -1,4 +1,5 @@
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_mfa_contracts::{
     recovery::MockMfaRecoveryService,
     totp_device::{MfaTotpDeviceConfirmError, MockMfaTotpDeviceService},
@@ -10,6 +11,7 @@ use academy_demo::{
     user::{ADMIN, BAR, FOO},
 };
 use academy_models::{
+    audit::{AuditAction, AuditContext},
     auth::{AuthError, AuthenticateError, AuthorizeError},
     mfa::{MfaRecoveryCode, TotpCode},
     user::UserIdOrSelf,
@@ -24,7 +26,7 @@ use crate::{tests::Sut, MfaFeatureServiceImpl};
 #[tokio::test]
 async fn ok() {
     // Arrange
-    let expected = MfaRecoveryCode::try_new("PJVURV-QRK3YJ-O3U7T6-D50KAC").unwrap();
+    let expected = vec![MfaRecoveryCode::try_new("PJVURV-QRK3YJ-O3U7T6-D50KAC").unwrap()];
     let code = TotpCode::try_new("123456").unwrap();
 
     let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
@@ -44,7 +46,15 @@ async fn ok() {
 
     let mfa_recovery = MockMfaRecoveryService::new().with_setup(FOO.user.id, expected.clone());
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::MfaEnable,
+    );
+
     let sut = MfaFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_repo,
@@ -55,7 +65,14 @@ async fn ok() {
     };
 
     // Act
-    let result = sut.enable(&"token".into(), UserIdOrSelf::Slf, code).await;
+    let result = sut
+        .enable(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            code,
+            &AuditContext::default(),
+        )
+        .await;
 
     // Assert
     assert_eq!(result.unwrap(), expected);
@@ -74,7 +91,14 @@ async fn unauthenticated() {
     };
 
     // Act
-    let result = sut.enable(&"token".into(), FOO.user.id.into(), code).await;
+    let result = sut
+        .enable(
+            &"token".into(),
+            FOO.user.id.into(),
+            code,
+            &AuditContext::default(),
+        )
+        .await;
 
     // Assert
     assert_matches!(
@@ -98,7 +122,14 @@ async fn unauthorized() {
     };
 
     // Act
-    let result = sut.enable(&"token".into(), FOO.user.id.into(), code).await;
+    let result = sut
+        .enable(
+            &"token".into(),
+            FOO.user.id.into(),
+            code,
+            &AuditContext::default(),
+        )
+        .await;
 
     // Assert
     assert_matches!(
@@ -129,7 +160,14 @@ async fn user_not_found() {
     };
 
     // Act
-    let result = sut.enable(&"token".into(), FOO.user.id.into(), code).await;
+    let result = sut
+        .enable(
+            &"token".into(),
+            FOO.user.id.into(),
+            code,
+            &AuditContext::default(),
+        )
+        .await;
 
     // Assert
     assert_matches!(result, Err(MfaEnableError::NotFound));
@@ -160,7 +198,14 @@ async fn already_enabled() {
     };
 
     // Act
-    let result = sut.enable(&"token".into(), UserIdOrSelf::Slf, code).await;
+    let result = sut
+        .enable(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            code,
+            &AuditContext::default(),
+        )
+        .await;
 
     // Assert
     assert_matches!(result, Err(MfaEnableError::AlreadyEnabled));
@@ -188,7 +233,14 @@ async fn not_initialized() {
     };
 
     // Act
-    let result = sut.enable(&"token".into(), UserIdOrSelf::Slf, code).await;
+    let result = sut
+        .enable(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            code,
+            &AuditContext::default(),
+        )
+        .await;
 
     // Assert
     assert_matches!(result, Err(MfaEnableError::NotInitialized));
@@ -224,7 +276,14 @@ async fn invalid_code() {
     };
 
     // Act
-    let result = sut.enable(&"token".into(), UserIdOrSelf::Slf, code).await;
+    let result = sut
+        .enable(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            code,
+            &AuditContext::default(),
+        )
+        .await;
 
     // Assert
     assert_matches!(result, Err(MfaEnableError::InvalidCode));
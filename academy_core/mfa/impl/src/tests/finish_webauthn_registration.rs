@@ -0,0 +1,257 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_mfa_contracts::{
+    recovery::MockMfaRecoveryService,
+    webauthn_device::{MfaWebauthnDeviceFinishRegistrationError, MockMfaWebauthnDeviceService},
+    MfaFeatureService, MfaFinishWebauthnRegistrationError, MfaWebauthnRegistrationResult,
+};
+use academy_demo::{
+    mfa::ADMIN2_WEBAUTHN_1,
+    session::{ADMIN_1, FOO_1},
+    user::{ADMIN, ADMIN2, FOO},
+};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    mfa::{MfaRecoveryCode, WebauthnDevice, WebauthnDeviceName, WebauthnRegistration},
+    user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_utils::{assert_matches, Apply};
+
+use crate::{tests::Sut, MfaFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok_mfa_disabled() {
+    // Arrange
+    let webauthn_device = ADMIN2_WEBAUTHN_1.clone().with(|x| x.user_id = FOO.user.id);
+    let recovery_codes = vec![MfaRecoveryCode::try_new("PJVURV-QRK3YJ-O3U7T6-D50KAC").unwrap()];
+
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let mfa_webauthn_device = MockMfaWebauthnDeviceService::new().with_finish_registration(
+        FOO.user.id,
+        name(),
+        registration(),
+        Ok(webauthn_device.clone()),
+    );
+
+    let mfa_recovery =
+        MockMfaRecoveryService::new().with_setup(FOO.user.id, recovery_codes.clone());
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::MfaEnable,
+    );
+
+    let sut = MfaFeatureServiceImpl {
+        audit,
+        auth,
+        db,
+        user_repo,
+        mfa_recovery,
+        mfa_webauthn_device,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .finish_webauthn_registration(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            name(),
+            registration(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(
+        result.unwrap(),
+        MfaWebauthnRegistrationResult {
+            webauthn_device,
+            recovery_codes: Some(recovery_codes),
+        }
+    );
+}
+
+#[tokio::test]
+async fn ok_mfa_enabled() {
+    // Arrange
+    let webauthn_device = WebauthnDevice {
+        name: name(),
+        ..ADMIN2_WEBAUTHN_1.clone()
+    };
+
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let user_repo =
+        MockUserRepository::new().with_get_composite(ADMIN2.user.id, Some(ADMIN2.clone()));
+
+    let mfa_webauthn_device = MockMfaWebauthnDeviceService::new().with_finish_registration(
+        ADMIN2.user.id,
+        name(),
+        registration(),
+        Ok(webauthn_device.clone()),
+    );
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        mfa_webauthn_device,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .finish_webauthn_registration(
+            &"token".into(),
+            ADMIN2.user.id.into(),
+            name(),
+            registration(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(
+        result.unwrap(),
+        MfaWebauthnRegistrationResult {
+            webauthn_device,
+            recovery_codes: None,
+        }
+    );
+}
+
+#[tokio::test]
+async fn user_not_found() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, None);
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .finish_webauthn_registration(
+            &"token".into(),
+            FOO.user.id.into(),
+            name(),
+            registration(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(MfaFinishWebauthnRegistrationError::NotFound));
+}
+
+#[tokio::test]
+async fn not_started() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let mfa_webauthn_device = MockMfaWebauthnDeviceService::new().with_finish_registration(
+        FOO.user.id,
+        name(),
+        registration(),
+        Err(MfaWebauthnDeviceFinishRegistrationError::NotStarted),
+    );
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        mfa_webauthn_device,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .finish_webauthn_registration(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            name(),
+            registration(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(MfaFinishWebauthnRegistrationError::NotStarted));
+}
+
+#[tokio::test]
+async fn invalid_registration() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let mfa_webauthn_device = MockMfaWebauthnDeviceService::new().with_finish_registration(
+        FOO.user.id,
+        name(),
+        registration(),
+        Err(MfaWebauthnDeviceFinishRegistrationError::InvalidRegistration),
+    );
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        mfa_webauthn_device,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .finish_webauthn_registration(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            name(),
+            registration(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(MfaFinishWebauthnRegistrationError::InvalidRegistration)
+    );
+}
+
+fn name() -> WebauthnDeviceName {
+    "Backup Key".try_into().unwrap()
+}
+
+fn registration() -> WebauthnRegistration {
+    WebauthnRegistration {
+        client_data_json: b"client data".to_vec(),
+        attestation_object: b"attestation object".to_vec(),
+    }
+}
@@ -9,7 +9,7 @@ use academy_demo::{
 };
 use academy_models::{
     auth::{AuthError, AuthenticateError, AuthorizeError},
-    mfa::TotpSetup,
+    mfa::{TotpDeviceName, TotpSetup},
     user::UserIdOrSelf,
 };
 use academy_persistence_contracts::{
@@ -34,8 +34,11 @@ async fn new() {
 
     let mfa_repo = MockMfaRepository::new().with_list_totp_devices_by_user(FOO.user.id, vec![]);
 
-    let mfa_totp_device =
-        MockMfaTotpDeviceService::new().with_create(FOO.user.id, expected.clone());
+    let mfa_totp_device = MockMfaTotpDeviceService::new().with_create(
+        FOO.user.id,
+        TotpDeviceName::default(),
+        (FOO_TOTP_1.clone(), expected.clone()),
+    );
 
     let sut = MfaFeatureServiceImpl {
         auth,
@@ -0,0 +1,64 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_mfa_contracts::{MfaFeatureService, MfaListTotpDevicesError};
+use academy_demo::{
+    mfa::FOO_TOTP_1,
+    session::{ADMIN_1, BAR_1},
+    user::{ADMIN, BAR, FOO},
+};
+use academy_models::auth::{AuthError, AuthorizeError};
+use academy_persistence_contracts::{mfa::MockMfaRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, MfaFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let mfa_repo = MockMfaRepository::new()
+        .with_list_totp_devices_by_user(FOO.user.id, vec![FOO_TOTP_1.clone()]);
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        mfa_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_totp_devices(&"token".into(), FOO.user.id.into())
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), vec![FOO_TOTP_1.clone()]);
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_totp_devices(&"token".into(), FOO.user.id.into())
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(MfaListTotpDevicesError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
@@ -0,0 +1,64 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_mfa_contracts::{MfaFeatureService, MfaListWebauthnDevicesError};
+use academy_demo::{
+    mfa::ADMIN2_WEBAUTHN_1,
+    session::{ADMIN_1, FOO_1},
+    user::{ADMIN, ADMIN2, FOO},
+};
+use academy_models::auth::{AuthError, AuthorizeError};
+use academy_persistence_contracts::{mfa::MockMfaRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, MfaFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let mfa_repo = MockMfaRepository::new()
+        .with_list_webauthn_devices_by_user(ADMIN2.user.id, vec![ADMIN2_WEBAUTHN_1.clone()]);
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        mfa_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_webauthn_devices(&"token".into(), ADMIN2.user.id.into())
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), vec![ADMIN2_WEBAUTHN_1.clone()]);
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_webauthn_devices(&"token".into(), ADMIN2.user.id.into())
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(MfaListWebauthnDevicesError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
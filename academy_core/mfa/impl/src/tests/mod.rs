@@ -1,17 +1,32 @@
+use std::time::Duration;
+
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_mfa_contracts::{
     disable::MockMfaDisableService, recovery::MockMfaRecoveryService,
-    totp_device::MockMfaTotpDeviceService,
+    totp_device::MockMfaTotpDeviceService, webauthn_device::MockMfaWebauthnDeviceService,
 };
 use academy_persistence_contracts::{
     mfa::MockMfaRepository, user::MockUserRepository, MockDatabase, MockTransaction,
 };
 
-use crate::MfaFeatureServiceImpl;
+use crate::{MfaFeatureConfig, MfaFeatureServiceImpl};
 
+mod confirm_totp_device;
+mod count_recovery_codes;
+mod create_totp_device;
+mod create_webauthn_assertion_options;
+mod delete_totp_device;
+mod delete_webauthn_device;
 mod disable;
 mod enable;
+mod finish_webauthn_registration;
 mod initialize;
+mod list_totp_devices;
+mod list_webauthn_devices;
+mod regenerate_recovery_codes;
+mod rename_totp_device;
+mod start_webauthn_registration;
 
 type Sut = MfaFeatureServiceImpl<
     MockDatabase,
@@ -21,4 +36,15 @@ type Sut = MfaFeatureServiceImpl<
     MockMfaRecoveryService<MockTransaction>,
     MockMfaDisableService<MockTransaction>,
     MockMfaTotpDeviceService<MockTransaction>,
+    MockMfaWebauthnDeviceService<MockTransaction>,
+    MockAuditService<MockTransaction>,
 >;
+
+impl Default for MfaFeatureConfig {
+    fn default() -> Self {
+        Self {
+            recovery_code_count: 10,
+            webauthn_challenge_ttl: Duration::from_secs(300),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_mfa_contracts::{
+    recovery::MockMfaRecoveryService, MfaFeatureService, MfaRegenerateRecoveryCodesError,
+};
+use academy_demo::{
+    session::{ADMIN_1, BAR_1, FOO_1},
+    user::{ADMIN, ADMIN2, BAR, FOO},
+};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthorizeError},
+    mfa::MfaRecoveryCode,
+    user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, MfaFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let expected = vec![
+        MfaRecoveryCode::try_new("PJVURV-QRK3YJ-O3U7T6-D50KAC").unwrap(),
+        MfaRecoveryCode::try_new("L5P3CS-8TKQ0G-KE2VRO-QPIUAJ").unwrap(),
+    ];
+
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let user_repo =
+        MockUserRepository::new().with_get_composite(ADMIN2.user.id, Some(ADMIN2.clone()));
+
+    let mfa_recovery = MockMfaRecoveryService::new().with_setup(ADMIN2.user.id, expected.clone());
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        ADMIN2.user.id,
+        AuditAction::RecoveryCodesRegenerate,
+    );
+
+    let sut = MfaFeatureServiceImpl {
+        audit,
+        auth,
+        db,
+        user_repo,
+        mfa_recovery,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .regenerate_recovery_codes(
+            &"token".into(),
+            ADMIN2.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .regenerate_recovery_codes(
+            &"token".into(),
+            FOO.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(MfaRegenerateRecoveryCodesError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, None);
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .regenerate_recovery_codes(
+            &"token".into(),
+            FOO.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(MfaRegenerateRecoveryCodesError::NotFound));
+}
+
+#[tokio::test]
+async fn not_enabled() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let sut = MfaFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .regenerate_recovery_codes(&"token".into(), UserIdOrSelf::Slf, &AuditContext::default())
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(MfaRegenerateRecoveryCodesError::NotEnabled));
+}
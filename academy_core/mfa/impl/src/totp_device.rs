@@ -1,11 +1,16 @@
 use academy_core_mfa_contracts::totp_device::{MfaTotpDeviceConfirmError, MfaTotpDeviceService};
 use academy_di::Build;
 use academy_models::{
-    mfa::{TotpCode, TotpDevice, TotpDeviceId, TotpDevicePatch, TotpDevicePatchRef, TotpSetup},
+    encryption::EncryptedData,
+    mfa::{
+        TotpCode, TotpDevice, TotpDeviceId, TotpDeviceName, TotpDevicePatch, TotpDevicePatchRef,
+        TotpSecret, TotpSetup,
+    },
     user::UserId,
 };
 use academy_persistence_contracts::mfa::MfaRepository;
 use academy_shared_contracts::{
+    encryption::EncryptionService,
     id::IdService,
     time::TimeService,
     totp::{TotpCheckError, TotpService},
@@ -15,29 +20,41 @@ use anyhow::Context;
 use tracing::trace;
 
 #[derive(Debug, Clone, Build, Default)]
-pub struct MfaTotpDeviceServiceImpl<Id, Time, Totp, MfaRepo> {
+pub struct MfaTotpDeviceServiceImpl<Id, Time, Totp, Encryption, MfaRepo> {
     id: Id,
     time: Time,
     totp: Totp,
+    encryption: Encryption,
     mfa_repo: MfaRepo,
 }
 
-impl<Txn, Id, Time, Totp, MfaRepo> MfaTotpDeviceService<Txn>
-    for MfaTotpDeviceServiceImpl<Id, Time, Totp, MfaRepo>
+impl<Txn, Id, Time, Totp, Encryption, MfaRepo> MfaTotpDeviceService<Txn>
+    for MfaTotpDeviceServiceImpl<Id, Time, Totp, Encryption, MfaRepo>
 where
     Txn: Send + Sync + 'static,
     Id: IdService,
     Time: TimeService,
     Totp: TotpService,
+    Encryption: EncryptionService,
     MfaRepo: MfaRepository<Txn>,
 {
     #[trace_instrument(skip(self, txn))]
-    async fn create(&self, txn: &mut Txn, user_id: UserId) -> anyhow::Result<TotpSetup> {
+    async fn create(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        name: TotpDeviceName,
+    ) -> anyhow::Result<(TotpDevice, TotpSetup)> {
         let (secret, setup) = self.totp.generate_secret();
+        let secret = self
+            .encryption
+            .encrypt(&secret)
+            .context("Failed to encrypt totp secret")?;
 
         let totp_device = TotpDevice {
             id: self.id.generate(),
             user_id,
+            name,
             enabled: false,
             created_at: self.time.now(),
         };
@@ -47,7 +64,7 @@ where
             .await
             .context("Failed to save totp device in database")?;
 
-        Ok(setup)
+        Ok((totp_device, setup))
     }
 
     #[trace_instrument(skip(self, txn))]
@@ -63,6 +80,7 @@ where
             .get_totp_device_secret(txn, totp_device.id)
             .await
             .context("Failed to get totp device secret from database")?;
+        let secret = decrypt_secret(&self.encryption, &secret)?;
 
         trace!("check code");
         self.totp
@@ -92,6 +110,10 @@ where
         totp_device_id: TotpDeviceId,
     ) -> anyhow::Result<TotpSetup> {
         let (secret, setup) = self.totp.generate_secret();
+        let secret = self
+            .encryption
+            .encrypt(&secret)
+            .context("Failed to encrypt totp secret")?;
 
         trace!("update device");
         self.mfa_repo
@@ -113,13 +135,23 @@ where
     }
 }
 
+pub(crate) fn decrypt_secret(
+    encryption: &impl EncryptionService,
+    secret: &EncryptedData,
+) -> anyhow::Result<TotpSecret> {
+    let secret = encryption
+        .decrypt(secret)
+        .context("Failed to decrypt totp secret")?;
+    TotpSecret::try_new(secret.0).context("Failed to load decrypted totp secret")
+}
+
 #[cfg(test)]
 mod tests {
     use academy_demo::{mfa::FOO_TOTP_1, user::FOO};
-    use academy_models::mfa::TotpSecret;
     use academy_persistence_contracts::mfa::MockMfaRepository;
     use academy_shared_contracts::{
-        id::MockIdService, time::MockTimeService, totp::MockTotpService,
+        encryption::MockEncryptionService, id::MockIdService, time::MockTimeService,
+        totp::MockTotpService,
     };
     use academy_utils::{assert_matches, Apply};
 
@@ -129,6 +161,7 @@ mod tests {
         MockIdService,
         MockTimeService,
         MockTotpService,
+        MockEncryptionService,
         MockMfaRepository<()>,
     >;
 
@@ -145,20 +178,27 @@ mod tests {
 
         let totp = MockTotpService::new().with_generate_secret(secret.clone(), setup.clone());
 
-        let mfa_repo = MockMfaRepository::new().with_create_totp_device(FOO_TOTP_1.clone(), secret);
+        let encryption =
+            MockEncryptionService::new().with_encrypt(secret.into_inner(), encrypted_secret());
+
+        let mfa_repo = MockMfaRepository::new()
+            .with_create_totp_device(FOO_TOTP_1.clone(), encrypted_secret());
 
         let sut = MfaTotpDeviceServiceImpl {
             id,
             time,
             totp,
+            encryption,
             mfa_repo,
         };
 
         // Act
-        let result = sut.create(&mut (), FOO.user.id).await;
+        let result = sut
+            .create(&mut (), FOO.user.id, FOO_TOTP_1.name.clone())
+            .await;
 
         // Assert
-        assert_eq!(result.unwrap(), setup);
+        assert_eq!(result.unwrap(), (FOO_TOTP_1.clone(), setup));
     }
 
     #[tokio::test]
@@ -170,8 +210,11 @@ mod tests {
 
         let totp = MockTotpService::new().with_check(code.clone(), secret.clone(), Ok(()));
 
+        let encryption =
+            MockEncryptionService::new().with_decrypt(encrypted_secret(), secret.into_inner());
+
         let mfa_repo = MockMfaRepository::new()
-            .with_get_totp_device_secret(FOO_TOTP_1.id, secret)
+            .with_get_totp_device_secret(FOO_TOTP_1.id, encrypted_secret())
             .with_update_totp_device(
                 FOO_TOTP_1.id,
                 TotpDevicePatch::new().update_enabled(true),
@@ -180,6 +223,7 @@ mod tests {
 
         let sut = MfaTotpDeviceServiceImpl {
             totp,
+            encryption,
             mfa_repo,
             ..Sut::default()
         };
@@ -203,10 +247,15 @@ mod tests {
             Err(TotpCheckError::InvalidCode),
         );
 
-        let mfa_repo = MockMfaRepository::new().with_get_totp_device_secret(FOO_TOTP_1.id, secret);
+        let encryption =
+            MockEncryptionService::new().with_decrypt(encrypted_secret(), secret.into_inner());
+
+        let mfa_repo =
+            MockMfaRepository::new().with_get_totp_device_secret(FOO_TOTP_1.id, encrypted_secret());
 
         let sut = MfaTotpDeviceServiceImpl {
             totp,
+            encryption,
             mfa_repo,
             ..Sut::default()
         };
@@ -228,16 +277,20 @@ mod tests {
 
         let totp = MockTotpService::new().with_generate_secret(secret.clone(), setup.clone());
 
+        let encryption =
+            MockEncryptionService::new().with_encrypt(secret.into_inner(), encrypted_secret());
+
         let mfa_repo = MockMfaRepository::new()
             .with_update_totp_device(
                 FOO_TOTP_1.id,
                 TotpDevicePatch::new().update_enabled(false),
                 true,
             )
-            .with_save_totp_device_secret(FOO_TOTP_1.id, secret);
+            .with_save_totp_device_secret(FOO_TOTP_1.id, encrypted_secret());
 
         let sut = MfaTotpDeviceServiceImpl {
             totp,
+            encryption,
             mfa_repo,
             ..Sut::default()
         };
@@ -248,4 +301,11 @@ mod tests {
         // Assert
         assert_eq!(result.unwrap(), setup);
     }
+
+    fn encrypted_secret() -> EncryptedData {
+        EncryptedData {
+            key_version: Some(1),
+            data: b"the encrypted totp secret".to_vec(),
+        }
+    }
 }
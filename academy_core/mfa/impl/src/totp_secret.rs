@@ -0,0 +1,104 @@
+use academy_core_mfa_contracts::totp_secret::MfaTotpSecretService;
+use academy_di::Build;
+use academy_persistence_contracts::mfa::MfaRepository;
+use academy_shared_contracts::encryption::EncryptionService;
+use academy_utils::trace_instrument;
+use anyhow::Context;
+use tracing::trace;
+
+use crate::totp_device::decrypt_secret;
+
+#[derive(Debug, Clone, Build)]
+pub struct MfaTotpSecretServiceImpl<Encryption, MfaRepo> {
+    encryption: Encryption,
+    mfa_repo: MfaRepo,
+}
+
+impl<Txn, Encryption, MfaRepo> MfaTotpSecretService<Txn>
+    for MfaTotpSecretServiceImpl<Encryption, MfaRepo>
+where
+    Txn: Send + Sync + 'static,
+    Encryption: EncryptionService,
+    MfaRepo: MfaRepository<Txn>,
+{
+    #[trace_instrument(skip(self, txn))]
+    async fn reencrypt(&self, txn: &mut Txn) -> anyhow::Result<usize> {
+        trace!("list secrets");
+        let secrets = self
+            .mfa_repo
+            .list_totp_device_secrets(txn)
+            .await
+            .context("Failed to get totp device secrets from database")?;
+
+        let mut cnt = 0;
+        for (totp_device_id, secret) in secrets {
+            if !self.encryption.is_outdated(&secret) {
+                continue;
+            }
+
+            trace!(?totp_device_id, "reencrypt secret");
+            let secret = decrypt_secret(&self.encryption, &secret)?;
+            let secret = self
+                .encryption
+                .encrypt(&secret)
+                .context("Failed to encrypt totp secret")?;
+
+            self.mfa_repo
+                .save_totp_device_secret(txn, totp_device_id, &secret)
+                .await
+                .context("Failed to update totp device secret in database")?;
+
+            cnt += 1;
+        }
+
+        Ok(cnt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_demo::mfa::{ADMIN2_TOTP_1, FOO_TOTP_1};
+    use academy_models::encryption::EncryptedData;
+    use academy_persistence_contracts::mfa::MockMfaRepository;
+    use academy_shared_contracts::encryption::MockEncryptionService;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn ok() {
+        // Arrange
+        let secret = b"the random totp secret".to_vec();
+        let old_secret = EncryptedData::unencrypted(secret.clone());
+
+        let encryption = MockEncryptionService::new()
+            .with_is_outdated(encrypted_secret(), false)
+            .with_is_outdated(old_secret.clone(), true)
+            .with_decrypt(old_secret.clone(), secret.clone())
+            .with_encrypt(secret, encrypted_secret());
+
+        let mfa_repo = MockMfaRepository::new()
+            .with_list_totp_device_secrets(vec![
+                (ADMIN2_TOTP_1.id, encrypted_secret()),
+                (FOO_TOTP_1.id, old_secret),
+            ])
+            .with_save_totp_device_secret(FOO_TOTP_1.id, encrypted_secret());
+
+        let sut = MfaTotpSecretServiceImpl {
+            encryption,
+            mfa_repo,
+        };
+
+        // Act
+        let result = sut.reencrypt(&mut ()).await;
+
+        // Assert
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    fn encrypted_secret() -> EncryptedData {
+        EncryptedData {
+            key_version: Some(1),
+            data: b"the encrypted totp secret".to_vec(),
+        }
+    }
+}
@@ -0,0 +1,774 @@
+use academy_cache_contracts::CacheService;
+use academy_core_mfa_contracts::webauthn_device::{
+    MfaWebauthnDeviceFinishRegistrationError, MfaWebauthnDeviceService,
+};
+use academy_di::Build;
+use academy_models::{
+    mfa::{
+        WebauthnAssertion, WebauthnAssertionOptions, WebauthnChallenge, WebauthnDevice,
+        WebauthnDeviceName, WebauthnDevicePatchRef, WebauthnRegistration,
+        WebauthnRegistrationOptions,
+    },
+    user::{UserComposite, UserId},
+};
+use academy_persistence_contracts::mfa::MfaRepository;
+use academy_shared_contracts::{id::IdService, time::TimeService, webauthn::WebauthnService};
+use academy_utils::trace_instrument;
+use anyhow::Context;
+use tracing::trace;
+
+use crate::MfaFeatureConfig;
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct MfaWebauthnDeviceServiceImpl<Id, Time, Webauthn, Cache, MfaRepo> {
+    id: Id,
+    time: Time,
+    webauthn: Webauthn,
+    cache: Cache,
+    mfa_repo: MfaRepo,
+    config: MfaFeatureConfig,
+}
+
+impl<Txn, Id, Time, Webauthn, Cache, MfaRepo> MfaWebauthnDeviceService<Txn>
+    for MfaWebauthnDeviceServiceImpl<Id, Time, Webauthn, Cache, MfaRepo>
+where
+    Txn: Send + Sync + 'static,
+    Id: IdService,
+    Time: TimeService,
+    Webauthn: WebauthnService,
+    Cache: CacheService,
+    MfaRepo: MfaRepository<Txn>,
+{
+    #[trace_instrument(skip(self, txn))]
+    async fn start_registration(
+        &self,
+        txn: &mut Txn,
+        user_composite: &UserComposite,
+    ) -> anyhow::Result<WebauthnRegistrationOptions> {
+        let user_id = user_composite.user.id;
+
+        trace!("list existing devices");
+        let webauthn_devices = self
+            .mfa_repo
+            .list_webauthn_devices_by_user(txn, user_id)
+            .await
+            .context("Failed to get webauthn devices from database")?;
+
+        let options = self.webauthn.generate_registration_options(
+            user_id,
+            user_composite.user.name.clone(),
+            user_composite.profile.display_name.clone(),
+            webauthn_devices
+                .into_iter()
+                .map(|device| device.credential_id)
+                .collect(),
+        );
+
+        self.cache
+            .set(
+                &registration_challenge_cache_key(user_id),
+                &options.challenge,
+                Some(self.config.webauthn_challenge_ttl),
+            )
+            .await
+            .context("Failed to save registration challenge in cache")?;
+
+        Ok(options)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn finish_registration(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        name: WebauthnDeviceName,
+        registration: WebauthnRegistration,
+    ) -> Result<WebauthnDevice, MfaWebauthnDeviceFinishRegistrationError> {
+        let cache_key = registration_challenge_cache_key(user_id);
+        let expected_challenge = self
+            .cache
+            .get::<WebauthnChallenge>(&cache_key)
+            .await
+            .context("Failed to get registration challenge from cache")?
+            .ok_or(MfaWebauthnDeviceFinishRegistrationError::NotStarted)?;
+
+        trace!("verify registration");
+        let verified = self
+            .webauthn
+            .verify_registration(&registration)
+            .map_err(|err| {
+                trace!(%err, "invalid registration");
+                MfaWebauthnDeviceFinishRegistrationError::InvalidRegistration
+            })?;
+
+        if verified.challenge != expected_challenge {
+            trace!("challenge mismatch");
+            return Err(MfaWebauthnDeviceFinishRegistrationError::InvalidRegistration);
+        }
+
+        if self
+            .mfa_repo
+            .get_webauthn_device_by_credential_id(txn, &verified.credential_id)
+            .await
+            .context("Failed to get webauthn device from database")?
+            .is_some()
+        {
+            return Err(MfaWebauthnDeviceFinishRegistrationError::AlreadyRegistered);
+        }
+
+        self.cache
+            .remove(&cache_key)
+            .await
+            .context("Failed to remove registration challenge from cache")?;
+
+        let webauthn_device = WebauthnDevice {
+            id: self.id.generate(),
+            user_id,
+            name,
+            credential_id: verified.credential_id,
+            public_key: verified.public_key,
+            sign_count: verified.sign_count,
+            created_at: self.time.now(),
+            last_used_at: None,
+        };
+
+        self.mfa_repo
+            .create_webauthn_device(txn, &webauthn_device)
+            .await
+            .context("Failed to save webauthn device in database")?;
+
+        Ok(webauthn_device)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn start_assertion(
+        &self,
+        user_id: Option<UserId>,
+    ) -> anyhow::Result<WebauthnAssertionOptions> {
+        let options = self.webauthn.generate_assertion_options();
+
+        if let Some(user_id) = user_id {
+            self.cache
+                .set(
+                    &assertion_challenge_cache_key(user_id, &options.challenge),
+                    &(),
+                    Some(self.config.webauthn_challenge_ttl),
+                )
+                .await
+                .context("Failed to save assertion challenge in cache")?;
+        }
+
+        Ok(options)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn verify_assertion(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        assertion: WebauthnAssertion,
+    ) -> anyhow::Result<bool> {
+        let Some(webauthn_device) = self
+            .mfa_repo
+            .get_webauthn_device_by_credential_id(txn, &assertion.credential_id)
+            .await
+            .context("Failed to get webauthn device from database")?
+            .filter(|device| device.user_id == user_id)
+        else {
+            trace!("unknown credential");
+            return Ok(false);
+        };
+
+        let verified = match self.webauthn.verify_assertion(
+            &assertion,
+            &webauthn_device.public_key,
+            webauthn_device.sign_count,
+        ) {
+            Ok(verified) => verified,
+            Err(err) => {
+                trace!(%err, "invalid assertion");
+                return Ok(false);
+            }
+        };
+
+        // challenges can only be used once, so they must be read and removed
+        // atomically
+        if self
+            .cache
+            .take::<()>(&assertion_challenge_cache_key(user_id, &verified.challenge))
+            .await
+            .context("Failed to take assertion challenge from cache")?
+            .is_none()
+        {
+            trace!("unknown challenge");
+            return Ok(false);
+        }
+
+        self.mfa_repo
+            .update_webauthn_device(
+                txn,
+                webauthn_device.id,
+                WebauthnDevicePatchRef::new()
+                    .update_sign_count(&verified.sign_count)
+                    .update_last_used_at(&Some(self.time.now())),
+            )
+            .await
+            .context("Failed to update webauthn device in database")?;
+
+        Ok(true)
+    }
+}
+
+fn registration_challenge_cache_key(user_id: UserId) -> String {
+    format!("webauthn_registration_challenge:{}", user_id.hyphenated())
+}
+
+fn assertion_challenge_cache_key(user_id: UserId, challenge: &WebauthnChallenge) -> String {
+    format!(
+        "webauthn_assertion_challenge:{}:{}",
+        user_id.hyphenated(),
+        hex::encode(&**challenge)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_cache_contracts::MockCacheService;
+    use academy_demo::{
+        mfa::ADMIN2_WEBAUTHN_1,
+        user::{ADMIN2, FOO},
+        UUID1,
+    };
+    use academy_models::mfa::{WebauthnDevicePatch, WebauthnRelyingParty};
+    use academy_persistence_contracts::mfa::MockMfaRepository;
+    use academy_shared_contracts::{
+        id::MockIdService,
+        time::MockTimeService,
+        webauthn::{
+            MockWebauthnService, WebauthnVerifiedAssertion, WebauthnVerifiedRegistration,
+            WebauthnVerifyError,
+        },
+    };
+    use academy_utils::assert_matches;
+
+    use super::*;
+
+    type Sut = MfaWebauthnDeviceServiceImpl<
+        MockIdService,
+        MockTimeService,
+        MockWebauthnService,
+        MockCacheService,
+        MockMfaRepository<()>,
+    >;
+
+    #[tokio::test]
+    async fn start_registration() {
+        // Arrange
+        let options = registration_options();
+        let config = MfaFeatureConfig::default();
+
+        let mfa_repo = MockMfaRepository::new()
+            .with_list_webauthn_devices_by_user(ADMIN2.user.id, vec![ADMIN2_WEBAUTHN_1.clone()]);
+
+        let webauthn = MockWebauthnService::new().with_generate_registration_options(
+            ADMIN2.user.id,
+            vec![ADMIN2_WEBAUTHN_1.credential_id.clone()],
+            options.clone(),
+        );
+
+        let cache = MockCacheService::new().with_set(
+            registration_challenge_cache_key(ADMIN2.user.id),
+            options.challenge.clone(),
+            Some(config.webauthn_challenge_ttl),
+        );
+
+        let sut = MfaWebauthnDeviceServiceImpl {
+            webauthn,
+            cache,
+            mfa_repo,
+            config,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.start_registration(&mut (), &ADMIN2).await;
+
+        // Assert
+        assert_eq!(result.unwrap(), options);
+    }
+
+    #[tokio::test]
+    async fn finish_registration() {
+        // Arrange
+        let expected = WebauthnDevice {
+            id: UUID1.into(),
+            user_id: FOO.user.id,
+            name: "Phone".try_into().unwrap(),
+            credential_id: vec![1, 2, 3].try_into().unwrap(),
+            public_key: vec![4, 5, 6].into(),
+            sign_count: 0,
+            created_at: FOO.user.created_at,
+            last_used_at: None,
+        };
+        let registration = registration();
+
+        let cache = MockCacheService::new()
+            .with_get(
+                registration_challenge_cache_key(FOO.user.id),
+                Some(challenge()),
+            )
+            .with_remove(registration_challenge_cache_key(FOO.user.id));
+
+        let webauthn = MockWebauthnService::new().with_verify_registration(
+            registration.clone(),
+            Ok(WebauthnVerifiedRegistration {
+                challenge: challenge(),
+                credential_id: expected.credential_id.clone(),
+                public_key: expected.public_key.clone(),
+                sign_count: 0,
+            }),
+        );
+
+        let mfa_repo = MockMfaRepository::new()
+            .with_get_webauthn_device_by_credential_id(expected.credential_id.clone(), None)
+            .with_create_webauthn_device(expected.clone());
+
+        let id = MockIdService::new().with_generate(expected.id);
+        let time = MockTimeService::new().with_now(expected.created_at);
+
+        let sut = MfaWebauthnDeviceServiceImpl {
+            id,
+            time,
+            webauthn,
+            cache,
+            mfa_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .finish_registration(&mut (), FOO.user.id, expected.name.clone(), registration)
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn finish_registration_not_started() {
+        // Arrange
+        let cache = MockCacheService::new().with_get(
+            registration_challenge_cache_key(FOO.user.id),
+            None::<WebauthnChallenge>,
+        );
+
+        let sut = MfaWebauthnDeviceServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .finish_registration(
+                &mut (),
+                FOO.user.id,
+                "Phone".try_into().unwrap(),
+                registration(),
+            )
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(MfaWebauthnDeviceFinishRegistrationError::NotStarted)
+        );
+    }
+
+    #[tokio::test]
+    async fn finish_registration_invalid() {
+        // Arrange
+        let registration = registration();
+
+        let cache = MockCacheService::new().with_get(
+            registration_challenge_cache_key(FOO.user.id),
+            Some(challenge()),
+        );
+
+        let webauthn = MockWebauthnService::new().with_verify_registration(
+            registration.clone(),
+            Err(WebauthnVerifyError::InvalidClientData),
+        );
+
+        let sut = MfaWebauthnDeviceServiceImpl {
+            webauthn,
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .finish_registration(
+                &mut (),
+                FOO.user.id,
+                "Phone".try_into().unwrap(),
+                registration,
+            )
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(MfaWebauthnDeviceFinishRegistrationError::InvalidRegistration)
+        );
+    }
+
+    #[tokio::test]
+    async fn finish_registration_challenge_mismatch() {
+        // Arrange
+        let registration = registration();
+
+        let cache = MockCacheService::new().with_get(
+            registration_challenge_cache_key(FOO.user.id),
+            Some(challenge()),
+        );
+
+        let webauthn = MockWebauthnService::new().with_verify_registration(
+            registration.clone(),
+            Ok(WebauthnVerifiedRegistration {
+                challenge: vec![0; 32].into(),
+                credential_id: vec![1, 2, 3].try_into().unwrap(),
+                public_key: vec![4, 5, 6].into(),
+                sign_count: 0,
+            }),
+        );
+
+        let sut = MfaWebauthnDeviceServiceImpl {
+            webauthn,
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .finish_registration(
+                &mut (),
+                FOO.user.id,
+                "Phone".try_into().unwrap(),
+                registration,
+            )
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(MfaWebauthnDeviceFinishRegistrationError::InvalidRegistration)
+        );
+    }
+
+    #[tokio::test]
+    async fn finish_registration_already_registered() {
+        // Arrange
+        let registration = registration();
+
+        let cache = MockCacheService::new().with_get(
+            registration_challenge_cache_key(FOO.user.id),
+            Some(challenge()),
+        );
+
+        let webauthn = MockWebauthnService::new().with_verify_registration(
+            registration.clone(),
+            Ok(WebauthnVerifiedRegistration {
+                challenge: challenge(),
+                credential_id: ADMIN2_WEBAUTHN_1.credential_id.clone(),
+                public_key: vec![4, 5, 6].into(),
+                sign_count: 0,
+            }),
+        );
+
+        let mfa_repo = MockMfaRepository::new().with_get_webauthn_device_by_credential_id(
+            ADMIN2_WEBAUTHN_1.credential_id.clone(),
+            Some(ADMIN2_WEBAUTHN_1.clone()),
+        );
+
+        let sut = MfaWebauthnDeviceServiceImpl {
+            webauthn,
+            cache,
+            mfa_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .finish_registration(
+                &mut (),
+                FOO.user.id,
+                "Phone".try_into().unwrap(),
+                registration,
+            )
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(MfaWebauthnDeviceFinishRegistrationError::AlreadyRegistered)
+        );
+    }
+
+    #[tokio::test]
+    async fn start_assertion() {
+        // Arrange
+        let options = WebauthnAssertionOptions {
+            challenge: challenge(),
+            rp_id: "bootstrap.academy".into(),
+        };
+        let config = MfaFeatureConfig::default();
+
+        let webauthn = MockWebauthnService::new().with_generate_assertion_options(options.clone());
+
+        let cache = MockCacheService::new().with_set(
+            assertion_challenge_cache_key(ADMIN2.user.id, &options.challenge),
+            (),
+            Some(config.webauthn_challenge_ttl),
+        );
+
+        let sut = MfaWebauthnDeviceServiceImpl {
+            webauthn,
+            cache,
+            config,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.start_assertion(Some(ADMIN2.user.id)).await;
+
+        // Assert
+        assert_eq!(result.unwrap(), options);
+    }
+
+    #[tokio::test]
+    async fn start_assertion_unknown_user() {
+        // Arrange
+        let options = WebauthnAssertionOptions {
+            challenge: challenge(),
+            rp_id: "bootstrap.academy".into(),
+        };
+
+        let webauthn = MockWebauthnService::new().with_generate_assertion_options(options.clone());
+
+        let sut = MfaWebauthnDeviceServiceImpl {
+            webauthn,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.start_assertion(None).await;
+
+        // Assert
+        assert_eq!(result.unwrap(), options);
+    }
+
+    #[tokio::test]
+    async fn verify_assertion() {
+        // Arrange
+        let assertion = assertion();
+        let now = ADMIN2.user.created_at;
+
+        let mfa_repo = MockMfaRepository::new()
+            .with_get_webauthn_device_by_credential_id(
+                assertion.credential_id.clone(),
+                Some(ADMIN2_WEBAUTHN_1.clone()),
+            )
+            .with_update_webauthn_device(
+                ADMIN2_WEBAUTHN_1.id,
+                WebauthnDevicePatch::new()
+                    .update_sign_count(4)
+                    .update_last_used_at(Some(now)),
+                true,
+            );
+
+        let webauthn = MockWebauthnService::new().with_verify_assertion(
+            assertion.clone(),
+            ADMIN2_WEBAUTHN_1.public_key.clone(),
+            ADMIN2_WEBAUTHN_1.sign_count,
+            Ok(WebauthnVerifiedAssertion {
+                challenge: challenge(),
+                sign_count: 4,
+            }),
+        );
+
+        let cache = MockCacheService::new().with_take(
+            assertion_challenge_cache_key(ADMIN2.user.id, &challenge()),
+            Some(()),
+        );
+
+        let time = MockTimeService::new().with_now(now);
+
+        let sut = MfaWebauthnDeviceServiceImpl {
+            time,
+            webauthn,
+            cache,
+            mfa_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .verify_assertion(&mut (), ADMIN2.user.id, assertion)
+            .await;
+
+        // Assert
+        assert!(result.unwrap());
+    }
+
+    #[tokio::test]
+    async fn verify_assertion_unknown_credential() {
+        // Arrange
+        let assertion = assertion();
+
+        let mfa_repo = MockMfaRepository::new()
+            .with_get_webauthn_device_by_credential_id(assertion.credential_id.clone(), None);
+
+        let sut = MfaWebauthnDeviceServiceImpl {
+            mfa_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .verify_assertion(&mut (), ADMIN2.user.id, assertion)
+            .await;
+
+        // Assert
+        assert!(!result.unwrap());
+    }
+
+    #[tokio::test]
+    async fn verify_assertion_other_user() {
+        // Arrange
+        let assertion = assertion();
+
+        let mfa_repo = MockMfaRepository::new().with_get_webauthn_device_by_credential_id(
+            assertion.credential_id.clone(),
+            Some(ADMIN2_WEBAUTHN_1.clone()),
+        );
+
+        let sut = MfaWebauthnDeviceServiceImpl {
+            mfa_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.verify_assertion(&mut (), FOO.user.id, assertion).await;
+
+        // Assert
+        assert!(!result.unwrap());
+    }
+
+    #[tokio::test]
+    async fn verify_assertion_invalid() {
+        // Arrange
+        let assertion = assertion();
+
+        let mfa_repo = MockMfaRepository::new().with_get_webauthn_device_by_credential_id(
+            assertion.credential_id.clone(),
+            Some(ADMIN2_WEBAUTHN_1.clone()),
+        );
+
+        let webauthn = MockWebauthnService::new().with_verify_assertion(
+            assertion.clone(),
+            ADMIN2_WEBAUTHN_1.public_key.clone(),
+            ADMIN2_WEBAUTHN_1.sign_count,
+            Err(WebauthnVerifyError::SignCountNotIncreased),
+        );
+
+        let sut = MfaWebauthnDeviceServiceImpl {
+            webauthn,
+            mfa_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .verify_assertion(&mut (), ADMIN2.user.id, assertion)
+            .await;
+
+        // Assert
+        assert!(!result.unwrap());
+    }
+
+    #[tokio::test]
+    async fn verify_assertion_unknown_challenge() {
+        // Arrange
+        let assertion = assertion();
+
+        let mfa_repo = MockMfaRepository::new().with_get_webauthn_device_by_credential_id(
+            assertion.credential_id.clone(),
+            Some(ADMIN2_WEBAUTHN_1.clone()),
+        );
+
+        let webauthn = MockWebauthnService::new().with_verify_assertion(
+            assertion.clone(),
+            ADMIN2_WEBAUTHN_1.public_key.clone(),
+            ADMIN2_WEBAUTHN_1.sign_count,
+            Ok(WebauthnVerifiedAssertion {
+                challenge: challenge(),
+                sign_count: 4,
+            }),
+        );
+
+        let cache = MockCacheService::new().with_take(
+            assertion_challenge_cache_key(ADMIN2.user.id, &challenge()),
+            None::<()>,
+        );
+
+        let sut = MfaWebauthnDeviceServiceImpl {
+            webauthn,
+            cache,
+            mfa_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .verify_assertion(&mut (), ADMIN2.user.id, assertion)
+            .await;
+
+        // Assert
+        assert!(!result.unwrap());
+    }
+
+    fn challenge() -> WebauthnChallenge {
+        vec![42; 32].into()
+    }
+
+    fn registration_options() -> WebauthnRegistrationOptions {
+        WebauthnRegistrationOptions {
+            challenge: challenge(),
+            rp: WebauthnRelyingParty {
+                id: "bootstrap.academy".into(),
+                name: "Bootstrap Academy".into(),
+            },
+            user_id: ADMIN2.user.id,
+            user_name: ADMIN2.user.name.clone(),
+            user_display_name: ADMIN2.profile.display_name.clone(),
+            exclude_credentials: vec![ADMIN2_WEBAUTHN_1.credential_id.clone()],
+        }
+    }
+
+    fn registration() -> WebauthnRegistration {
+        WebauthnRegistration {
+            client_data_json: b"client data".to_vec(),
+            attestation_object: b"attestation object".to_vec(),
+        }
+    }
+
+    fn assertion() -> WebauthnAssertion {
+        WebauthnAssertion {
+            credential_id: ADMIN2_WEBAUTHN_1.credential_id.clone(),
+            client_data_json: b"client data".to_vec(),
+            authenticator_data: b"authenticator data".to_vec(),
+            signature: b"signature".to_vec(),
+        }
+    }
+}
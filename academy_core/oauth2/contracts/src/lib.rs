@@ -1,11 +1,14 @@
-use std::future::Future;
+use std::{future::Future, net::IpAddr};
 
 use academy_models::{
+    audit::AuditContext,
     auth::{AccessToken, AuthError, Login},
     oauth2::{
-        OAuth2Link, OAuth2LinkId, OAuth2Login, OAuth2ProviderSummary, OAuth2RegistrationToken,
+        OAuth2AuthorizationRequest, OAuth2Link, OAuth2LinkId, OAuth2Login, OAuth2ProviderId,
+        OAuth2ProviderSummary, OAuth2RegistrationToken,
     },
     session::DeviceName,
+    url::Url,
     user::UserIdOrSelf,
 };
 use thiserror::Error;
@@ -18,6 +21,17 @@ pub trait OAuth2FeatureService: Send + Sync + 'static {
     /// Return all available OAuth2 providers.
     fn list_providers(&self) -> Vec<OAuth2ProviderSummary>;
 
+    /// Start a new OAuth2 login and return the authorize URL of the given
+    /// provider.
+    ///
+    /// The returned state must be passed to [`OAuth2FeatureService::create_link`]
+    /// or [`OAuth2FeatureService::create_session`] to complete the login.
+    fn start_login(
+        &self,
+        provider_id: OAuth2ProviderId,
+        redirect_uri: Url,
+    ) -> impl Future<Output = Result<OAuth2AuthorizationRequest, OAuth2StartLoginError>> + Send;
+
     /// Return all OAuth2 links of the given user.
     ///
     /// Requires admin privileges if not used on the authenticated user.
@@ -30,21 +44,27 @@ pub trait OAuth2FeatureService: Send + Sync + 'static {
     /// Create a new OAuth2 for the given user.
     ///
     /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
     fn create_link(
         &self,
         token: &AccessToken,
         user_id: UserIdOrSelf,
         login: OAuth2Login,
+        ctx: &AuditContext,
     ) -> impl Future<Output = Result<OAuth2Link, OAuth2CreateLinkError>> + Send;
 
     /// Delete the given OAuth2 link.
     ///
     /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
     fn delete_link(
         &self,
         token: &AccessToken,
         user_id: UserIdOrSelf,
         link_id: OAuth2LinkId,
+        ctx: &AuditContext,
     ) -> impl Future<Output = Result<(), OAuth2DeleteLinkError>> + Send;
 
     /// Create a session via OAuth2.
@@ -52,9 +72,18 @@ pub trait OAuth2FeatureService: Send + Sync + 'static {
         &self,
         login: OAuth2Login,
         device_name: Option<DeviceName>,
+        ip: Option<IpAddr>,
     ) -> impl Future<Output = Result<OAuth2CreateSessionResponse, OAuth2CreateSessionError>> + Send;
 }
 
+#[derive(Debug, Error)]
+pub enum OAuth2StartLoginError {
+    #[error("The provider does not exist.")]
+    InvalidProvider,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
 #[derive(Debug, Error)]
 pub enum OAuth2ListLinksError {
     #[error("The user does not exist.")]
@@ -69,6 +98,8 @@ pub enum OAuth2ListLinksError {
 pub enum OAuth2CreateLinkError {
     #[error("The provider does not exist.")]
     InvalidProvider,
+    #[error("The state is invalid.")]
+    InvalidState,
     #[error("The authorization code is invalid.")]
     InvalidCode,
     #[error("The remote user has already been linked.")]
@@ -105,6 +136,8 @@ pub enum OAuth2CreateSessionResponse {
 pub enum OAuth2CreateSessionError {
     #[error("The provider does not exist.")]
     InvalidProvider,
+    #[error("The state is invalid.")]
+    InvalidState,
     #[error("The authorization code is invalid.")]
     InvalidCode,
     #[error("The user account has been disabled.")]
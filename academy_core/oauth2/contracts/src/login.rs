@@ -1,22 +1,50 @@
 use std::future::Future;
 
-use academy_models::oauth2::{OAuth2Login, OAuth2UserInfo};
+use academy_models::{
+    oauth2::{OAuth2AuthorizationRequest, OAuth2Login, OAuth2ProviderId, OAuth2UserInfo},
+    url::Url,
+};
 use thiserror::Error;
 
 #[cfg_attr(feature = "mock", mockall::automock)]
 pub trait OAuth2LoginService: Send + Sync + 'static {
+    /// Start a new OAuth2 login by generating a state, a PKCE code verifier
+    /// and a binding and return the authorize URL of the given provider.
+    ///
+    /// The binding is only returned to the client which has started the
+    /// login and must be presented again to complete it.
+    fn start(
+        &self,
+        provider_id: OAuth2ProviderId,
+        redirect_uri: Url,
+    ) -> impl Future<Output = Result<OAuth2AuthorizationRequest, OAuth2LoginServiceStartError>> + Send;
+
     /// Resolve the given [`OAuth2Login`] and return the external user's
     /// [`OAuth2UserInfo`].
+    ///
+    /// The login must have been started using [`OAuth2LoginService::start`]
+    /// and the binding must match the one returned by it. The state is
+    /// invalidated afterwards.
     fn login(
         &self,
         login: OAuth2Login,
     ) -> impl Future<Output = Result<OAuth2UserInfo, OAuth2LoginServiceError>> + Send;
 }
 
+#[derive(Debug, Error)]
+pub enum OAuth2LoginServiceStartError {
+    #[error("The provider does not exist.")]
+    InvalidProvider,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
 #[derive(Debug, Error)]
 pub enum OAuth2LoginServiceError {
     #[error("The provider does not exist.")]
     InvalidProvider,
+    #[error("The state is invalid.")]
+    InvalidState,
     #[error("The authorization code is invalid.")]
     InvalidCode,
     #[error(transparent)]
@@ -25,6 +53,22 @@ pub enum OAuth2LoginServiceError {
 
 #[cfg(feature = "mock")]
 impl MockOAuth2LoginService {
+    pub fn with_start(
+        mut self,
+        provider_id: OAuth2ProviderId,
+        redirect_uri: Url,
+        result: Result<OAuth2AuthorizationRequest, OAuth2LoginServiceStartError>,
+    ) -> Self {
+        self.expect_start()
+            .once()
+            .with(
+                mockall::predicate::eq(provider_id),
+                mockall::predicate::eq(redirect_uri),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(result)));
+        self
+    }
+
     pub fn with_login(
         mut self,
         login: OAuth2Login,
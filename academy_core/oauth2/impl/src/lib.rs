@@ -1,23 +1,27 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{collections::HashMap, net::IpAddr, sync::Arc, time::Duration};
 
 use academy_auth_contracts::{AuthResultExt, AuthService};
+use academy_core_audit_contracts::audit::AuditService;
 use academy_core_oauth2_contracts::{
     link::{OAuth2LinkService, OAuth2LinkServiceError},
-    login::{OAuth2LoginService, OAuth2LoginServiceError},
+    login::{OAuth2LoginService, OAuth2LoginServiceError, OAuth2LoginServiceStartError},
     registration::OAuth2RegistrationService,
     OAuth2CreateLinkError, OAuth2CreateSessionError, OAuth2CreateSessionResponse,
-    OAuth2DeleteLinkError, OAuth2FeatureService, OAuth2ListLinksError,
+    OAuth2DeleteLinkError, OAuth2FeatureService, OAuth2ListLinksError, OAuth2StartLoginError,
+};
+use academy_core_session_contracts::{
+    new_device::SessionNewDeviceService, session::SessionService,
 };
-use academy_core_session_contracts::session::SessionService;
 use academy_di::Build;
-use academy_extern_contracts::oauth2::OAuth2ApiService;
 use academy_models::{
+    audit::{AuditAction, AuditContext},
     auth::AccessToken,
     oauth2::{
-        OAuth2Link, OAuth2LinkId, OAuth2Login, OAuth2Provider, OAuth2ProviderId,
-        OAuth2ProviderSummary, OAuth2Registration,
+        OAuth2AuthorizationRequest, OAuth2Link, OAuth2LinkId, OAuth2Login, OAuth2Provider,
+        OAuth2ProviderId, OAuth2ProviderSummary, OAuth2Registration,
     },
-    session::DeviceName,
+    session::{DeviceName, SessionLoginMethod},
+    url::Url,
     user::UserIdOrSelf,
 };
 use academy_persistence_contracts::{
@@ -25,6 +29,7 @@ use academy_persistence_contracts::{
 };
 use academy_utils::trace_instrument;
 use anyhow::Context;
+use tracing::warn;
 
 pub mod link;
 pub mod login;
@@ -38,23 +43,25 @@ mod tests;
 pub struct OAuth2FeatureServiceImpl<
     Db,
     Auth,
-    OAuth2Api,
     UserRepo,
     OAuth2Repo,
     OAuth2Link,
     OAuth2Login,
     OAuth2Registration,
     Session,
+    SessionNewDevice,
+    Audit,
 > {
     db: Db,
     auth: Auth,
-    oauth2_api: OAuth2Api,
     user_repo: UserRepo,
     oauth2_repo: OAuth2Repo,
     oauth2_create_link: OAuth2Link,
     oauth2_login: OAuth2Login,
     oauth2_registration: OAuth2Registration,
     session: Session,
+    session_new_device: SessionNewDevice,
+    audit: Audit,
     config: OAuth2FeatureConfig,
 }
 
@@ -62,40 +69,44 @@ pub struct OAuth2FeatureServiceImpl<
 pub struct OAuth2FeatureConfig {
     pub providers: Arc<HashMap<OAuth2ProviderId, OAuth2Provider>>,
     pub registration_token_ttl: Duration,
+    pub state_ttl: Duration,
 }
 
 impl<
         Db,
         Auth,
-        OAuth2Api,
         UserRepo,
         OAuth2Repo,
         OAuth2LinkS,
         OAuth2LoginS,
         OAuth2RegistrationS,
         Session,
+        SessionNewDevice,
+        Audit,
     > OAuth2FeatureService
     for OAuth2FeatureServiceImpl<
         Db,
         Auth,
-        OAuth2Api,
         UserRepo,
         OAuth2Repo,
         OAuth2LinkS,
         OAuth2LoginS,
         OAuth2RegistrationS,
         Session,
+        SessionNewDevice,
+        Audit,
     >
 where
     Db: Database,
     Auth: AuthService<Db::Transaction>,
-    OAuth2Api: OAuth2ApiService,
     UserRepo: UserRepository<Db::Transaction>,
     OAuth2Repo: OAuth2Repository<Db::Transaction>,
     OAuth2LinkS: OAuth2LinkService<Db::Transaction>,
     OAuth2LoginS: OAuth2LoginService,
     OAuth2RegistrationS: OAuth2RegistrationService,
     Session: SessionService<Db::Transaction>,
+    SessionNewDevice: SessionNewDeviceService<Db::Transaction>,
+    Audit: AuditService<Db::Transaction>,
 {
     #[trace_instrument(skip(self))]
     fn list_providers(&self) -> Vec<OAuth2ProviderSummary> {
@@ -105,18 +116,36 @@ where
             .map(|(id, provider)| OAuth2ProviderSummary {
                 id: id.clone(),
                 name: provider.name.clone(),
-                auth_url: self.oauth2_api.generate_auth_url(provider),
             })
             .collect()
     }
 
+    #[trace_instrument(skip(self))]
+    async fn start_login(
+        &self,
+        provider_id: OAuth2ProviderId,
+        redirect_uri: Url,
+    ) -> Result<OAuth2AuthorizationRequest, OAuth2StartLoginError> {
+        self.oauth2_login
+            .start(provider_id, redirect_uri)
+            .await
+            .map_err(|err| match err {
+                OAuth2LoginServiceStartError::InvalidProvider => {
+                    OAuth2StartLoginError::InvalidProvider
+                }
+                OAuth2LoginServiceStartError::Other(err) => {
+                    err.context("Failed to start OAuth2 login").into()
+                }
+            })
+    }
+
     #[trace_instrument(skip(self))]
     async fn list_links(
         &self,
         token: &AccessToken,
         user_id: UserIdOrSelf,
     ) -> Result<Vec<OAuth2Link>, OAuth2ListLinksError> {
-        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
         let user_id = user_id.unwrap_or(auth.user_id);
         auth.ensure_self_or_admin(user_id).map_auth_err()?;
 
@@ -149,8 +178,10 @@ where
         token: &AccessToken,
         user_id: UserIdOrSelf,
         login: OAuth2Login,
+        ctx: &AuditContext,
     ) -> Result<OAuth2Link, OAuth2CreateLinkError> {
         let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
         let user_id = user_id.unwrap_or(auth.user_id);
         auth.ensure_self_or_admin(user_id).map_auth_err()?;
 
@@ -173,6 +204,7 @@ where
             .await
             .map_err(|err| match err {
                 OAuth2LoginServiceError::InvalidProvider => OAuth2CreateLinkError::InvalidProvider,
+                OAuth2LoginServiceError::InvalidState => OAuth2CreateLinkError::InvalidState,
                 OAuth2LoginServiceError::InvalidCode => OAuth2CreateLinkError::InvalidCode,
                 OAuth2LoginServiceError::Other(err) => {
                     err.context("Failed to perform OAuth2 login").into()
@@ -192,6 +224,17 @@ where
                 }
             })?;
 
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::OAuth2Link,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
         txn.commit().await?;
 
         Ok(link)
@@ -203,8 +246,10 @@ where
         token: &AccessToken,
         user_id: UserIdOrSelf,
         link_id: OAuth2LinkId,
+        ctx: &AuditContext,
     ) -> Result<(), OAuth2DeleteLinkError> {
         let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
         let user_id = user_id.unwrap_or(auth.user_id);
         auth.ensure_self_or_admin(user_id).map_auth_err()?;
 
@@ -235,6 +280,17 @@ where
             return Err(OAuth2DeleteLinkError::CannotRemoveLink);
         }
 
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::OAuth2Unlink,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
         txn.commit().await?;
 
         Ok(())
@@ -245,6 +301,7 @@ where
         &self,
         login: OAuth2Login,
         device_name: Option<DeviceName>,
+        ip: Option<IpAddr>,
     ) -> Result<OAuth2CreateSessionResponse, OAuth2CreateSessionError> {
         let provider_id = login.provider_id.clone();
         let user_info = self
@@ -255,6 +312,7 @@ where
                 OAuth2LoginServiceError::InvalidProvider => {
                     OAuth2CreateSessionError::InvalidProvider
                 }
+                OAuth2LoginServiceError::InvalidState => OAuth2CreateSessionError::InvalidState,
                 OAuth2LoginServiceError::InvalidCode => OAuth2CreateSessionError::InvalidCode,
                 OAuth2LoginServiceError::Other(err) => {
                     err.context("Failed to perform OAuth2 login").into()
@@ -290,18 +348,40 @@ where
             ));
         };
 
-        if !user_composite.user.enabled {
+        if !user_composite.user.is_active() {
             return Err(OAuth2CreateSessionError::UserDisabled);
         }
 
         let login = self
             .session
-            .create(&mut txn, user_composite, device_name, true)
+            .create(
+                &mut txn,
+                user_composite,
+                device_name,
+                ip,
+                SessionLoginMethod::OAuth2(provider_id),
+            )
             .await
             .context("Failed to create session")?;
 
+        let notify_new_device = self
+            .session_new_device
+            .handle_login(&mut txn, &login.user_composite, &login.session)
+            .await
+            .context("Failed to handle login from new device")?;
+
         txn.commit().await?;
 
+        if notify_new_device {
+            if let Err(err) = self
+                .session_new_device
+                .notify(&login.user_composite, &login.session)
+                .await
+            {
+                warn!("Failed to notify user about login from new device: {err:?}");
+            }
+        }
+
         Ok(OAuth2CreateSessionResponse::Login(login.into()))
     }
 }
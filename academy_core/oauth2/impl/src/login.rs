@@ -1,22 +1,89 @@
-use academy_core_oauth2_contracts::login::{OAuth2LoginService, OAuth2LoginServiceError};
+use academy_cache_contracts::CacheService;
+use academy_core_oauth2_contracts::login::{
+    OAuth2LoginService, OAuth2LoginServiceError, OAuth2LoginServiceStartError,
+};
 use academy_di::Build;
 use academy_extern_contracts::oauth2::{OAuth2ApiService, OAuth2ResolveCodeError};
-use academy_models::oauth2::{OAuth2Login, OAuth2UserInfo};
+use academy_models::{
+    oauth2::{
+        OAuth2AuthorizationRequest, OAuth2Login, OAuth2LoginAttempt, OAuth2LoginBinding,
+        OAuth2Nonce, OAuth2PkceCodeVerifier, OAuth2ProviderId, OAuth2State, OAuth2UserInfo,
+    },
+    url::Url,
+};
+use academy_shared_contracts::{hash::HashService, secret::SecretService};
 use academy_utils::trace_instrument;
+use anyhow::Context;
+use tracing::trace;
 
 use crate::OAuth2FeatureConfig;
 
 #[derive(Debug, Clone, Build)]
 #[cfg_attr(test, derive(Default))]
-pub struct OAuth2LoginServiceImpl<OAuth2Api> {
+pub struct OAuth2LoginServiceImpl<Secret, Hash, Cache, OAuth2Api> {
+    secret: Secret,
+    hash: Hash,
+    cache: Cache,
     oauth2_api: OAuth2Api,
     config: OAuth2FeatureConfig,
 }
 
-impl<OAuth2Api> OAuth2LoginService for OAuth2LoginServiceImpl<OAuth2Api>
+impl<Secret, Hash, Cache, OAuth2Api> OAuth2LoginService
+    for OAuth2LoginServiceImpl<Secret, Hash, Cache, OAuth2Api>
 where
+    Secret: SecretService,
+    Hash: HashService,
+    Cache: CacheService,
     OAuth2Api: OAuth2ApiService,
 {
+    #[trace_instrument(skip(self))]
+    async fn start(
+        &self,
+        provider_id: OAuth2ProviderId,
+        redirect_uri: Url,
+    ) -> Result<OAuth2AuthorizationRequest, OAuth2LoginServiceStartError> {
+        let provider = self
+            .config
+            .providers
+            .get(&provider_id)
+            .ok_or(OAuth2LoginServiceStartError::InvalidProvider)?;
+
+        let state = OAuth2State::try_new(self.secret.generate(OAuth2State::LEN).0).unwrap();
+        let pkce_verifier =
+            OAuth2PkceCodeVerifier::try_new(self.secret.generate(OAuth2PkceCodeVerifier::LEN).0)
+                .unwrap();
+        let nonce = OAuth2Nonce::try_new(self.secret.generate(OAuth2Nonce::LEN).0).unwrap();
+        let binding =
+            OAuth2LoginBinding::try_new(self.secret.generate(OAuth2LoginBinding::LEN).0).unwrap();
+
+        let auth_url = self
+            .oauth2_api
+            .generate_auth_url(provider, &redirect_uri, &state, &pkce_verifier, &nonce)
+            .await
+            .context("Failed to generate OAuth2 authorize URL")?;
+
+        self.cache
+            .set(
+                &oauth2_login_cache_key(&state),
+                &OAuth2LoginAttempt {
+                    provider_id,
+                    redirect_uri,
+                    pkce_verifier,
+                    nonce,
+                    binding_hash: self.hash.sha256(&binding),
+                },
+                Some(self.config.state_ttl),
+            )
+            .await
+            .context("Failed to save OAuth2 login attempt in cache")?;
+
+        Ok(OAuth2AuthorizationRequest {
+            auth_url,
+            state,
+            binding,
+        })
+    }
+
     #[trace_instrument(skip(self))]
     async fn login(&self, login: OAuth2Login) -> Result<OAuth2UserInfo, OAuth2LoginServiceError> {
         let provider = self
@@ -25,9 +92,34 @@ where
             .get(&login.provider_id)
             .ok_or(OAuth2LoginServiceError::InvalidProvider)?;
 
+        // the state can only be used once
+        let attempt = self
+            .cache
+            .take::<OAuth2LoginAttempt>(&oauth2_login_cache_key(&login.state))
+            .await
+            .context("Failed to take OAuth2 login attempt from cache")?
+            .ok_or(OAuth2LoginServiceError::InvalidState)?;
+
+        // the login must be completed by the same client that has started it
+        let binding_hash = self.hash.sha256(&login.binding);
+
+        if attempt.provider_id != login.provider_id
+            || attempt.redirect_uri != login.redirect_uri
+            || attempt.binding_hash != binding_hash
+        {
+            trace!("login attempt does not match");
+            return Err(OAuth2LoginServiceError::InvalidState);
+        }
+
         let user_info = self
             .oauth2_api
-            .resolve_code(provider.clone(), login.code, login.redirect_uri)
+            .resolve_code(
+                provider.clone(),
+                login.code,
+                login.redirect_uri,
+                attempt.pkce_verifier,
+                attempt.nonce,
+            )
             .await
             .map_err(|err| match err {
                 OAuth2ResolveCodeError::InvalidCode => OAuth2LoginServiceError::InvalidCode,
@@ -40,33 +132,126 @@ where
     }
 }
 
+fn oauth2_login_cache_key(state: &OAuth2State) -> String {
+    format!("oauth2_login:{}", **state)
+}
+
 #[cfg(test)]
 mod tests {
+    use academy_cache_contracts::MockCacheService;
     use academy_demo::oauth2::{FOO_OAUTH2_LINK_1, TEST_OAUTH2_PROVIDER, TEST_OAUTH2_PROVIDER_ID};
+    use academy_demo::{SHA256HASH1, SHA256HASH2};
     use academy_extern_contracts::oauth2::MockOAuth2ApiService;
+    use academy_shared_contracts::{hash::MockHashService, secret::MockSecretService};
     use academy_utils::assert_matches;
 
     use super::*;
 
-    type Sut = OAuth2LoginServiceImpl<MockOAuth2ApiService>;
+    type Sut = OAuth2LoginServiceImpl<
+        MockSecretService,
+        MockHashService,
+        MockCacheService,
+        MockOAuth2ApiService,
+    >;
 
     #[tokio::test]
-    async fn ok() {
+    async fn start_ok() {
         // Arrange
-        let login = OAuth2Login {
-            provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
-            code: "code".try_into().unwrap(),
-            redirect_uri: "http://test/redirect".parse().unwrap(),
+        let config = OAuth2FeatureConfig::default();
+        let redirect_uri: Url = "http://test/redirect".parse().unwrap();
+        let auth_url: Url = "http://test/auth?state=xyz".parse().unwrap();
+        let attempt = attempt();
+
+        let secret = MockSecretService::new()
+            .with_generate(OAuth2State::LEN, state().into_inner())
+            .with_generate(
+                OAuth2PkceCodeVerifier::LEN,
+                attempt.pkce_verifier.clone().into_inner(),
+            )
+            .with_generate(OAuth2Nonce::LEN, attempt.nonce.clone().into_inner())
+            .with_generate(OAuth2LoginBinding::LEN, binding().into_inner());
+
+        let hash = MockHashService::new().with_sha256(binding(), attempt.binding_hash);
+
+        let oauth2_api = MockOAuth2ApiService::new().with_generate_auth_url(
+            TEST_OAUTH2_PROVIDER.clone(),
+            redirect_uri.clone(),
+            state(),
+            attempt.pkce_verifier.clone(),
+            attempt.nonce.clone(),
+            auth_url.clone(),
+        );
+
+        let cache = MockCacheService::new().with_set(
+            format!("oauth2_login:{}", *state()),
+            attempt,
+            Some(config.state_ttl),
+        );
+
+        let sut = OAuth2LoginServiceImpl {
+            secret,
+            hash,
+            cache,
+            oauth2_api,
+            config,
         };
 
+        // Act
+        let result = sut
+            .start(TEST_OAUTH2_PROVIDER_ID.clone(), redirect_uri)
+            .await;
+
+        // Assert
+        assert_eq!(
+            result.unwrap(),
+            OAuth2AuthorizationRequest {
+                auth_url,
+                state: state(),
+                binding: binding(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn start_invalid_provider() {
+        // Arrange
+        let sut = Sut::default();
+
+        // Act
+        let result = sut
+            .start(
+                "invalid-provider".into(),
+                "http://test/redirect".parse().unwrap(),
+            )
+            .await;
+
+        // Assert
+        assert_matches!(result, Err(OAuth2LoginServiceStartError::InvalidProvider));
+    }
+
+    #[tokio::test]
+    async fn ok() {
+        // Arrange
+        let login = login();
+        let attempt = attempt();
+
+        let cache = MockCacheService::new()
+            .with_take(format!("oauth2_login:{}", *state()), Some(attempt.clone()));
+
+        let hash = MockHashService::new().with_sha256(binding(), attempt.binding_hash);
+
         let oauth2_api = MockOAuth2ApiService::new().with_resolve_code(
             TEST_OAUTH2_PROVIDER.clone(),
             login.code.clone(),
             login.redirect_uri.clone(),
+            attempt.pkce_verifier,
+            attempt.nonce,
             Ok(FOO_OAUTH2_LINK_1.remote_user.clone()),
         );
 
         let sut = OAuth2LoginServiceImpl {
+            hash,
+            cache,
             oauth2_api,
             ..Sut::default()
         };
@@ -83,8 +268,7 @@ mod tests {
         // Arrange
         let login = OAuth2Login {
             provider_id: "invalid-provider".into(),
-            code: "code".try_into().unwrap(),
-            redirect_uri: "http://test/redirect".parse().unwrap(),
+            ..login()
         };
 
         let sut = Sut::default();
@@ -97,22 +281,102 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn invalid_code() {
+    async fn invalid_state() {
+        // Arrange
+        let login = login();
+
+        let cache = MockCacheService::new().with_take(
+            format!("oauth2_login:{}", *state()),
+            None::<OAuth2LoginAttempt>,
+        );
+
+        let sut = OAuth2LoginServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.login(login).await;
+
+        // Assert
+        assert_matches!(result, Err(OAuth2LoginServiceError::InvalidState));
+    }
+
+    #[tokio::test]
+    async fn redirect_uri_mismatch() {
         // Arrange
         let login = OAuth2Login {
-            provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
-            code: "code".try_into().unwrap(),
-            redirect_uri: "http://test/redirect".parse().unwrap(),
+            redirect_uri: "http://evil/redirect".parse().unwrap(),
+            ..login()
+        };
+
+        let cache = MockCacheService::new()
+            .with_take(format!("oauth2_login:{}", *state()), Some(attempt()));
+
+        let hash = MockHashService::new().with_sha256(binding(), *SHA256HASH1);
+
+        let sut = OAuth2LoginServiceImpl {
+            hash,
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.login(login).await;
+
+        // Assert
+        assert_matches!(result, Err(OAuth2LoginServiceError::InvalidState));
+    }
+
+    #[tokio::test]
+    async fn binding_mismatch() {
+        // Arrange
+        let login = OAuth2Login {
+            binding: OAuth2LoginBinding::try_new("EVY0FHDD8WtnmNaQCmAPyfGVgaCi2EDo").unwrap(),
+            ..login()
         };
 
+        let cache = MockCacheService::new()
+            .with_take(format!("oauth2_login:{}", *state()), Some(attempt()));
+
+        let hash = MockHashService::new().with_sha256(login.binding.clone(), *SHA256HASH2);
+
+        let sut = OAuth2LoginServiceImpl {
+            hash,
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.login(login).await;
+
+        // Assert
+        assert_matches!(result, Err(OAuth2LoginServiceError::InvalidState));
+    }
+
+    #[tokio::test]
+    async fn invalid_code() {
+        // Arrange
+        let login = login();
+        let attempt = attempt();
+
+        let cache = MockCacheService::new()
+            .with_take(format!("oauth2_login:{}", *state()), Some(attempt.clone()));
+
+        let hash = MockHashService::new().with_sha256(binding(), attempt.binding_hash);
+
         let oauth2_api = MockOAuth2ApiService::new().with_resolve_code(
             TEST_OAUTH2_PROVIDER.clone(),
             login.code.clone(),
             login.redirect_uri.clone(),
+            attempt.pkce_verifier,
+            attempt.nonce,
             Err(OAuth2ResolveCodeError::InvalidCode),
         );
 
         let sut = OAuth2LoginServiceImpl {
+            hash,
+            cache,
             oauth2_api,
             ..Sut::default()
         };
@@ -123,4 +387,35 @@ mod tests {
         // Assert
         assert_matches!(result, Err(OAuth2LoginServiceError::InvalidCode));
     }
+
+    fn state() -> OAuth2State {
+        OAuth2State::try_new("hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu").unwrap()
+    }
+
+    fn login() -> OAuth2Login {
+        OAuth2Login {
+            provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
+            code: "code".try_into().unwrap(),
+            redirect_uri: "http://test/redirect".parse().unwrap(),
+            state: state(),
+            binding: binding(),
+        }
+    }
+
+    fn binding() -> OAuth2LoginBinding {
+        OAuth2LoginBinding::try_new("Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU").unwrap()
+    }
+
+    fn attempt() -> OAuth2LoginAttempt {
+        OAuth2LoginAttempt {
+            provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
+            redirect_uri: "http://test/redirect".parse().unwrap(),
+            pkce_verifier: OAuth2PkceCodeVerifier::try_new(
+                "W1NhzrHUhfLDxK8WPoV0ufCTbU9hH2Y3Tgp2sR1QCbsOM8V6RMoP12ieQQNqmbu9",
+            )
+            .unwrap(),
+            nonce: OAuth2Nonce::try_new("tsF4ivBH8EmhQV8dOKEb2hlsvU6aKBSH").unwrap(),
+            binding_hash: *SHA256HASH1,
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_oauth2_contracts::{
     link::{MockOAuth2LinkService, OAuth2LinkServiceError},
     login::{MockOAuth2LoginService, OAuth2LoginServiceError},
@@ -10,6 +11,7 @@ use academy_demo::{
     user::{ADMIN, BAR, FOO},
 };
 use academy_models::{
+    audit::{AuditAction, AuditContext},
     auth::{AuthError, AuthenticateError, AuthorizeError},
     oauth2::OAuth2Login,
     user::UserIdOrSelf,
@@ -26,6 +28,8 @@ async fn ok() {
         provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
         code: "code".try_into().unwrap(),
         redirect_uri: "http://test/redirect".parse().unwrap(),
+        state: "hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu".try_into().unwrap(),
+        binding: "Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU".try_into().unwrap(),
     };
 
     let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
@@ -44,7 +48,15 @@ async fn ok() {
         Ok(FOO_OAUTH2_LINK_1.clone()),
     );
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::OAuth2Link,
+    );
+
     let sut = OAuth2FeatureServiceImpl {
+        audit,
         db,
         auth,
         user_repo,
@@ -55,7 +67,12 @@ async fn ok() {
 
     // Act
     let result = sut
-        .create_link(&"token".into(), UserIdOrSelf::Slf, login)
+        .create_link(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            login,
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
@@ -69,6 +86,8 @@ async fn unauthenticated() {
         provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
         code: "code".try_into().unwrap(),
         redirect_uri: "http://test/redirect".parse().unwrap(),
+        state: "hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu".try_into().unwrap(),
+        binding: "Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU".try_into().unwrap(),
     };
 
     let auth = MockAuthService::new().with_authenticate(None);
@@ -80,7 +99,12 @@ async fn unauthenticated() {
 
     // Act
     let result = sut
-        .create_link(&"token".into(), FOO.user.id.into(), login)
+        .create_link(
+            &"token".into(),
+            FOO.user.id.into(),
+            login,
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
@@ -99,6 +123,8 @@ async fn unauthorized() {
         provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
         code: "code".try_into().unwrap(),
         redirect_uri: "http://test/redirect".parse().unwrap(),
+        state: "hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu".try_into().unwrap(),
+        binding: "Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU".try_into().unwrap(),
     };
 
     let auth = MockAuthService::new().with_authenticate(Some((BAR.user.clone(), BAR_1.clone())));
@@ -110,7 +136,12 @@ async fn unauthorized() {
 
     // Act
     let result = sut
-        .create_link(&"token".into(), FOO.user.id.into(), login)
+        .create_link(
+            &"token".into(),
+            FOO.user.id.into(),
+            login,
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
@@ -129,6 +160,8 @@ async fn not_found() {
         provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
         code: "code".try_into().unwrap(),
         redirect_uri: "http://test/redirect".parse().unwrap(),
+        state: "hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu".try_into().unwrap(),
+        binding: "Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU".try_into().unwrap(),
     };
 
     let auth =
@@ -147,7 +180,12 @@ async fn not_found() {
 
     // Act
     let result = sut
-        .create_link(&"token".into(), FOO.user.id.into(), login)
+        .create_link(
+            &"token".into(),
+            FOO.user.id.into(),
+            login,
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
@@ -161,6 +199,8 @@ async fn invalid_provider() {
         provider_id: "invalid-provider".into(),
         code: "code".try_into().unwrap(),
         redirect_uri: "http://test/redirect".parse().unwrap(),
+        state: "hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu".try_into().unwrap(),
+        binding: "Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU".try_into().unwrap(),
     };
 
     let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
@@ -182,13 +222,60 @@ async fn invalid_provider() {
 
     // Act
     let result = sut
-        .create_link(&"token".into(), UserIdOrSelf::Slf, login)
+        .create_link(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            login,
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
     assert_matches!(result, Err(OAuth2CreateLinkError::InvalidProvider));
 }
 
+#[tokio::test]
+async fn invalid_state() {
+    // Arrange
+    let login = OAuth2Login {
+        provider_id: "invalid-provider".into(),
+        code: "code".try_into().unwrap(),
+        redirect_uri: "http://test/redirect".parse().unwrap(),
+        state: "hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu".try_into().unwrap(),
+        binding: "Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU".try_into().unwrap(),
+    };
+
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_exists(FOO.user.id, true);
+
+    let oauth2_login = MockOAuth2LoginService::new()
+        .with_login(login.clone(), Err(OAuth2LoginServiceError::InvalidState));
+
+    let sut = OAuth2FeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        oauth2_login,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_link(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            login,
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2CreateLinkError::InvalidState));
+}
+
 #[tokio::test]
 async fn invalid_code() {
     // Arrange
@@ -196,6 +283,8 @@ async fn invalid_code() {
         provider_id: "invalid-provider".into(),
         code: "code".try_into().unwrap(),
         redirect_uri: "http://test/redirect".parse().unwrap(),
+        state: "hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu".try_into().unwrap(),
+        binding: "Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU".try_into().unwrap(),
     };
 
     let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
@@ -217,7 +306,12 @@ async fn invalid_code() {
 
     // Act
     let result = sut
-        .create_link(&"token".into(), UserIdOrSelf::Slf, login)
+        .create_link(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            login,
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
@@ -231,6 +325,8 @@ async fn remote_already_linked() {
         provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
         code: "code".try_into().unwrap(),
         redirect_uri: "http://test/redirect".parse().unwrap(),
+        state: "hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu".try_into().unwrap(),
+        binding: "Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU".try_into().unwrap(),
     };
 
     let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
@@ -260,7 +356,12 @@ async fn remote_already_linked() {
 
     // Act
     let result = sut
-        .create_link(&"token".into(), UserIdOrSelf::Slf, login)
+        .create_link(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            login,
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
@@ -3,7 +3,9 @@ use academy_core_oauth2_contracts::{
     registration::MockOAuth2RegistrationService,
     OAuth2CreateSessionError, OAuth2CreateSessionResponse, OAuth2FeatureService,
 };
-use academy_core_session_contracts::session::MockSessionService;
+use academy_core_session_contracts::{
+    new_device::MockSessionNewDeviceService, session::MockSessionService,
+};
 use academy_demo::{
     oauth2::{FOO_OAUTH2_LINK_1, TEST_OAUTH2_PROVIDER_ID},
     session::FOO_1,
@@ -12,6 +14,7 @@ use academy_demo::{
 use academy_models::{
     auth::Login,
     oauth2::{OAuth2Login, OAuth2RegistrationToken},
+    session::SessionLoginMethod,
 };
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
 use academy_utils::{assert_matches, Apply};
@@ -25,6 +28,8 @@ async fn ok() {
         provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
         code: "code".try_into().unwrap(),
         redirect_uri: "http://test/redirect".parse().unwrap(),
+        state: "hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu".try_into().unwrap(),
+        binding: "Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU".try_into().unwrap(),
     };
     let expected = Login {
         user_composite: FOO.clone(),
@@ -45,18 +50,31 @@ async fn ok() {
             Some(FOO.clone()),
         );
 
-    let session = MockSessionService::new().with_create(FOO.clone(), None, true, expected.clone());
+    let session = MockSessionService::new().with_create(
+        FOO.clone(),
+        None,
+        None,
+        SessionLoginMethod::OAuth2(TEST_OAUTH2_PROVIDER_ID.clone()),
+        expected.clone(),
+    );
+
+    let session_new_device = MockSessionNewDeviceService::new().with_handle_login(
+        expected.user_composite.clone(),
+        expected.session.clone(),
+        false,
+    );
 
     let sut = OAuth2FeatureServiceImpl {
         db,
         oauth2_login,
         user_repo,
         session,
+        session_new_device,
         ..Sut::default()
     };
 
     // Act
-    let result = sut.create_session(login, None).await;
+    let result = sut.create_session(login, None, None).await;
 
     // Assert
     assert_eq!(
@@ -72,6 +90,8 @@ async fn not_linked() {
         provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
         code: "code".try_into().unwrap(),
         redirect_uri: "http://test/redirect".parse().unwrap(),
+        state: "hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu".try_into().unwrap(),
+        binding: "Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU".try_into().unwrap(),
     };
     let expected = OAuth2RegistrationToken::try_new(
         "kvyhRRjn83JC223MwAbqhFTW09J8a75VIBMyLaxhiLtSl0Mddhyr7qctXcqKBINC",
@@ -107,7 +127,7 @@ async fn not_linked() {
     };
 
     // Act
-    let result = sut.create_session(login, None).await;
+    let result = sut.create_session(login, None, None).await;
 
     // Assert
     assert_eq!(
@@ -123,6 +143,8 @@ async fn invalid_provider() {
         provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
         code: "code".try_into().unwrap(),
         redirect_uri: "http://test/redirect".parse().unwrap(),
+        state: "hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu".try_into().unwrap(),
+        binding: "Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU".try_into().unwrap(),
     };
 
     let oauth2_login = MockOAuth2LoginService::new()
@@ -134,12 +156,38 @@ async fn invalid_provider() {
     };
 
     // Act
-    let result = sut.create_session(login, None).await;
+    let result = sut.create_session(login, None, None).await;
 
     // Assert
     assert_matches!(result, Err(OAuth2CreateSessionError::InvalidProvider));
 }
 
+#[tokio::test]
+async fn invalid_state() {
+    // Arrange
+    let login = OAuth2Login {
+        provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
+        code: "code".try_into().unwrap(),
+        redirect_uri: "http://test/redirect".parse().unwrap(),
+        state: "hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu".try_into().unwrap(),
+        binding: "Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU".try_into().unwrap(),
+    };
+
+    let oauth2_login = MockOAuth2LoginService::new()
+        .with_login(login.clone(), Err(OAuth2LoginServiceError::InvalidState));
+
+    let sut = OAuth2FeatureServiceImpl {
+        oauth2_login,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session(login, None, None).await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2CreateSessionError::InvalidState));
+}
+
 #[tokio::test]
 async fn invalid_code() {
     // Arrange
@@ -147,6 +195,8 @@ async fn invalid_code() {
         provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
         code: "code".try_into().unwrap(),
         redirect_uri: "http://test/redirect".parse().unwrap(),
+        state: "hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu".try_into().unwrap(),
+        binding: "Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU".try_into().unwrap(),
     };
 
     let oauth2_login = MockOAuth2LoginService::new()
@@ -158,7 +208,7 @@ async fn invalid_code() {
     };
 
     // Act
-    let result = sut.create_session(login, None).await;
+    let result = sut.create_session(login, None, None).await;
 
     // Assert
     assert_matches!(result, Err(OAuth2CreateSessionError::InvalidCode));
@@ -171,6 +221,8 @@ async fn user_disabled() {
         provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
         code: "code".try_into().unwrap(),
         redirect_uri: "http://test/redirect".parse().unwrap(),
+        state: "hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu".try_into().unwrap(),
+        binding: "Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU".try_into().unwrap(),
     };
 
     let db = MockDatabase::build(false);
@@ -193,7 +245,7 @@ async fn user_disabled() {
     };
 
     // Act
-    let result = sut.create_session(login, None).await;
+    let result = sut.create_session(login, None, None).await;
 
     // Assert
     assert_matches!(result, Err(OAuth2CreateSessionError::UserDisabled));
@@ -1,11 +1,16 @@
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_oauth2_contracts::{OAuth2DeleteLinkError, OAuth2FeatureService};
 use academy_demo::{
     oauth2::FOO_OAUTH2_LINK_1,
+    personal_access_token::FOO_PAT,
     session::{ADMIN_1, BAR_1, FOO_1},
     user::{ADMIN, BAR, FOO},
 };
-use academy_models::auth::{AuthError, AuthenticateError, AuthorizeError};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthenticateError, AuthorizeError},
+};
 use academy_persistence_contracts::{
     oauth2::MockOAuth2Repository, user::MockUserRepository, MockDatabase,
 };
@@ -29,7 +34,15 @@ async fn ok() {
         Some(FOO.clone().with(|u| u.details.oauth2_login = false)),
     );
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::OAuth2Unlink,
+    );
+
     let sut = OAuth2FeatureServiceImpl {
+        audit,
         db,
         auth,
         oauth2_repo,
@@ -39,7 +52,12 @@ async fn ok() {
 
     // Act
     let result = sut
-        .delete_link(&"token".into(), FOO.user.id.into(), FOO_OAUTH2_LINK_1.id)
+        .delete_link(
+            &"token".into(),
+            FOO.user.id.into(),
+            FOO_OAUTH2_LINK_1.id,
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
@@ -58,7 +76,12 @@ async fn unauthenticated() {
 
     // Act
     let result = sut
-        .delete_link(&"token".into(), FOO.user.id.into(), FOO_OAUTH2_LINK_1.id)
+        .delete_link(
+            &"token".into(),
+            FOO.user.id.into(),
+            FOO_OAUTH2_LINK_1.id,
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
@@ -82,7 +105,12 @@ async fn unauthorized() {
 
     // Act
     let result = sut
-        .delete_link(&"token".into(), FOO.user.id.into(), FOO_OAUTH2_LINK_1.id)
+        .delete_link(
+            &"token".into(),
+            FOO.user.id.into(),
+            FOO_OAUTH2_LINK_1.id,
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
@@ -94,6 +122,36 @@ async fn unauthorized() {
     );
 }
 
+#[tokio::test]
+async fn personal_access_token_not_allowed() {
+    // Arrange
+    let auth = MockAuthService::new()
+        .with_authenticate_personal_access_token(Some((FOO.user.clone(), FOO_PAT.clone())));
+
+    let sut = OAuth2FeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_link(
+            &"token".into(),
+            FOO.user.id.into(),
+            FOO_OAUTH2_LINK_1.id,
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(OAuth2DeleteLinkError::Auth(AuthError::Authorize(
+            AuthorizeError::Session
+        )))
+    );
+}
+
 #[tokio::test]
 async fn not_found() {
     // Arrange
@@ -113,7 +171,12 @@ async fn not_found() {
 
     // Act
     let result = sut
-        .delete_link(&"token".into(), FOO.user.id.into(), FOO_OAUTH2_LINK_1.id)
+        .delete_link(
+            &"token".into(),
+            FOO.user.id.into(),
+            FOO_OAUTH2_LINK_1.id,
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
@@ -140,7 +203,12 @@ async fn user_id_mismatch() {
 
     // Act
     let result = sut
-        .delete_link(&"token".into(), BAR.user.id.into(), FOO_OAUTH2_LINK_1.id)
+        .delete_link(
+            &"token".into(),
+            BAR.user.id.into(),
+            FOO_OAUTH2_LINK_1.id,
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
@@ -176,7 +244,12 @@ async fn last_login_method() {
 
     // Act
     let result = sut
-        .delete_link(&"token".into(), FOO.user.id.into(), FOO_OAUTH2_LINK_1.id)
+        .delete_link(
+            &"token".into(),
+            FOO.user.id.into(),
+            FOO_OAUTH2_LINK_1.id,
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
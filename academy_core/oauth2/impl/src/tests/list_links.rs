@@ -20,7 +20,8 @@ use crate::{tests::Sut, OAuth2FeatureServiceImpl};
 #[tokio::test]
 async fn ok() {
     // Arrange
-    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
 
     let db = MockDatabase::build(false);
 
@@ -56,7 +57,7 @@ async fn ok() {
 #[tokio::test]
 async fn unauthenticated() {
     // Arrange
-    let auth = MockAuthService::new().with_authenticate(None);
+    let auth = MockAuthService::new().with_authenticate_read(None);
 
     let sut = OAuth2FeatureServiceImpl {
         auth,
@@ -78,7 +79,8 @@ async fn unauthenticated() {
 #[tokio::test]
 async fn unauthorized() {
     // Arrange
-    let auth = MockAuthService::new().with_authenticate(Some((BAR.user.clone(), BAR_1.clone())));
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((BAR.user.clone(), BAR_1.clone())));
 
     let sut = OAuth2FeatureServiceImpl {
         auth,
@@ -101,7 +103,7 @@ async fn unauthorized() {
 async fn not_found() {
     // Arrange
     let auth =
-        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
 
     let db = MockDatabase::build(false);
 
@@ -0,0 +1,66 @@
+use academy_core_oauth2_contracts::{
+    login::{MockOAuth2LoginService, OAuth2LoginServiceStartError},
+    OAuth2FeatureService, OAuth2StartLoginError,
+};
+use academy_demo::oauth2::TEST_OAUTH2_PROVIDER_ID;
+use academy_models::{oauth2::OAuth2AuthorizationRequest, url::Url};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, OAuth2FeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let redirect_uri: Url = "http://test/redirect".parse().unwrap();
+    let expected = OAuth2AuthorizationRequest {
+        auth_url: "http://test/auth?state=hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu"
+            .parse()
+            .unwrap(),
+        state: "hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu".try_into().unwrap(),
+        binding: "Xr3kPzLq0vC9mW2tYb8nHs5dJf1gA7eU".try_into().unwrap(),
+    };
+
+    let oauth2_login = MockOAuth2LoginService::new().with_start(
+        TEST_OAUTH2_PROVIDER_ID.clone(),
+        redirect_uri.clone(),
+        Ok(expected.clone()),
+    );
+
+    let sut = OAuth2FeatureServiceImpl {
+        oauth2_login,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .start_login(TEST_OAUTH2_PROVIDER_ID.clone(), redirect_uri)
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn invalid_provider() {
+    // Arrange
+    let redirect_uri: Url = "http://test/redirect".parse().unwrap();
+
+    let oauth2_login = MockOAuth2LoginService::new().with_start(
+        "invalid-provider".into(),
+        redirect_uri.clone(),
+        Err(OAuth2LoginServiceStartError::InvalidProvider),
+    );
+
+    let sut = OAuth2FeatureServiceImpl {
+        oauth2_login,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .start_login("invalid-provider".into(), redirect_uri)
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2StartLoginError::InvalidProvider));
+}
@@ -0,0 +1,69 @@
+use std::future::Future;
+
+use academy_models::oauth2_server::{OAuth2Client, OAuth2ClientCredentials, OAuth2ClientSecret};
+use thiserror::Error;
+
+use crate::OAuth2ClientCreateRequest;
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait OAuth2ClientService<Txn: Send + Sync + 'static>: Send + Sync + 'static {
+    /// Create a new OAuth2 client.
+    ///
+    /// A new secret is generated and returned for confidential clients.
+    fn create(
+        &self,
+        txn: &mut Txn,
+        request: OAuth2ClientCreateRequest,
+    ) -> impl Future<Output = anyhow::Result<(OAuth2Client, Option<OAuth2ClientSecret>)>> + Send;
+
+    /// Authenticate an OAuth2 client using the given credentials.
+    ///
+    /// Confidential clients must provide their secret, while public clients
+    /// must not provide any secret.
+    fn authenticate(
+        &self,
+        txn: &mut Txn,
+        credentials: &OAuth2ClientCredentials,
+    ) -> impl Future<Output = Result<OAuth2Client, OAuth2ClientAuthenticateError>> + Send;
+}
+
+#[derive(Debug, Error)]
+pub enum OAuth2ClientAuthenticateError {
+    #[error("The client does not exist or the secret is incorrect.")]
+    InvalidCredentials,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[cfg(feature = "mock")]
+impl<Txn: Send + Sync + 'static> MockOAuth2ClientService<Txn> {
+    pub fn with_create(
+        mut self,
+        request: OAuth2ClientCreateRequest,
+        result: (OAuth2Client, Option<OAuth2ClientSecret>),
+    ) -> Self {
+        self.expect_create()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(request),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_authenticate(
+        mut self,
+        credentials: OAuth2ClientCredentials,
+        result: Result<OAuth2Client, OAuth2ClientAuthenticateError>,
+    ) -> Self {
+        self.expect_authenticate()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(credentials),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(result)));
+        self
+    }
+}
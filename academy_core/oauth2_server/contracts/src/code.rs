@@ -0,0 +1,75 @@
+use std::future::Future;
+
+use academy_models::oauth2_server::{
+    OAuth2Authorization, OAuth2ClientPkceCodeVerifier, OAuth2PkceCodeChallenge,
+    OAuth2ServerAuthorizationCode,
+};
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait OAuth2AuthorizationCodeService: Send + Sync + 'static {
+    /// Save the given authorization and return a new authorization code that
+    /// can be redeemed once.
+    fn issue(
+        &self,
+        authorization: &OAuth2Authorization,
+    ) -> impl Future<Output = anyhow::Result<OAuth2ServerAuthorizationCode>> + Send;
+
+    /// Return the authorization of the given code and invalidate the code.
+    ///
+    /// Each code can be redeemed at most once, even by concurrent callers.
+    fn redeem(
+        &self,
+        code: &OAuth2ServerAuthorizationCode,
+    ) -> impl Future<Output = anyhow::Result<Option<OAuth2Authorization>>> + Send;
+
+    /// Check whether the given PKCE code verifier matches the S256 code
+    /// challenge.
+    fn verify_code_verifier(
+        &self,
+        code_challenge: &OAuth2PkceCodeChallenge,
+        code_verifier: &OAuth2ClientPkceCodeVerifier,
+    ) -> bool;
+}
+
+#[cfg(feature = "mock")]
+impl MockOAuth2AuthorizationCodeService {
+    pub fn with_issue(
+        mut self,
+        authorization: OAuth2Authorization,
+        result: OAuth2ServerAuthorizationCode,
+    ) -> Self {
+        self.expect_issue()
+            .once()
+            .with(mockall::predicate::eq(authorization))
+            .return_once(|_| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_redeem(
+        mut self,
+        code: OAuth2ServerAuthorizationCode,
+        result: Option<OAuth2Authorization>,
+    ) -> Self {
+        self.expect_redeem()
+            .once()
+            .with(mockall::predicate::eq(code))
+            .return_once(|_| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_verify_code_verifier(
+        mut self,
+        code_challenge: OAuth2PkceCodeChallenge,
+        code_verifier: OAuth2ClientPkceCodeVerifier,
+        result: bool,
+    ) -> Self {
+        self.expect_verify_code_verifier()
+            .once()
+            .with(
+                mockall::predicate::eq(code_challenge),
+                mockall::predicate::eq(code_verifier),
+            )
+            .return_const(result);
+        self
+    }
+}
@@ -0,0 +1,187 @@
+use std::future::Future;
+
+use academy_models::{
+    audit::AuditContext,
+    auth::{AccessToken, AuthError},
+    oauth2_server::{
+        OAuth2AuthorizeRequest, OAuth2Client, OAuth2ClientCredentials, OAuth2ClientId,
+        OAuth2ClientName, OAuth2ClientSecret, OAuth2Consent, OAuth2ServerAccessToken,
+        OAuth2ServerUserInfo, OAuth2TokenInfo, OAuth2TokenRequest, OAuth2TokenResponse,
+    },
+    url::Url,
+};
+use thiserror::Error;
+
+pub mod client;
+pub mod code;
+pub mod token;
+
+pub trait OAuth2ServerFeatureService: Send + Sync + 'static {
+    /// Return all registered OAuth2 clients.
+    ///
+    /// Requires admin privileges.
+    fn list_clients(
+        &self,
+        token: &AccessToken,
+    ) -> impl Future<Output = Result<Vec<OAuth2Client>, OAuth2ServerListClientsError>> + Send;
+
+    /// Register a new OAuth2 client.
+    ///
+    /// The secret of a confidential client is only returned once.
+    ///
+    /// Requires admin privileges.
+    fn create_client(
+        &self,
+        token: &AccessToken,
+        request: OAuth2ClientCreateRequest,
+    ) -> impl Future<Output = Result<OAuth2CreateClientResponse, OAuth2ServerCreateClientError>> + Send;
+
+    /// Delete the given OAuth2 client and revoke all of its grants.
+    ///
+    /// Requires admin privileges.
+    fn delete_client(
+        &self,
+        token: &AccessToken,
+        client_id: OAuth2ClientId,
+    ) -> impl Future<Output = Result<(), OAuth2ServerDeleteClientError>> + Send;
+
+    /// Validate the given authorization request and return the data to
+    /// display on the consent screen.
+    fn get_consent(
+        &self,
+        token: &AccessToken,
+        request: OAuth2AuthorizeRequest,
+    ) -> impl Future<Output = Result<OAuth2Consent, OAuth2ServerAuthorizeError>> + Send;
+
+    /// Authorize the client of the given request to access the authenticated
+    /// user's account.
+    ///
+    /// Returns the redirect URI including the authorization code, which the
+    /// client can exchange for tokens using [`OAuth2ServerFeatureService::token`].
+    /// Requires a session, i.e. cannot be used with a personal access token.
+    fn authorize(
+        &self,
+        token: &AccessToken,
+        request: OAuth2AuthorizeRequest,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<Url, OAuth2ServerAuthorizeError>> + Send;
+
+    /// Exchange an authorization code or a refresh token for new tokens.
+    fn token(
+        &self,
+        credentials: OAuth2ClientCredentials,
+        request: OAuth2TokenRequest,
+    ) -> impl Future<Output = Result<OAuth2TokenResponse, OAuth2ServerTokenError>> + Send;
+
+    /// Revoke the grant of the given access or refresh token.
+    ///
+    /// Tokens that are invalid or have been issued to another client are
+    /// ignored.
+    fn revoke(
+        &self,
+        credentials: OAuth2ClientCredentials,
+        token: String,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<(), OAuth2ServerRevokeError>> + Send;
+
+    /// Return information about the given access or refresh token, or `None`
+    /// if it is not active.
+    fn introspect(
+        &self,
+        credentials: OAuth2ClientCredentials,
+        token: String,
+    ) -> impl Future<Output = Result<Option<OAuth2TokenInfo>, OAuth2ServerIntrospectError>> + Send;
+
+    /// Return the claims about the user who granted the given access token.
+    fn userinfo(
+        &self,
+        token: &OAuth2ServerAccessToken,
+    ) -> impl Future<Output = Result<OAuth2ServerUserInfo, OAuth2ServerUserInfoError>> + Send;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuth2ClientCreateRequest {
+    pub name: OAuth2ClientName,
+    pub redirect_uris: Vec<Url>,
+    pub confidential: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuth2CreateClientResponse {
+    pub client: OAuth2Client,
+    pub secret: Option<OAuth2ClientSecret>,
+}
+
+#[derive(Debug, Error)]
+pub enum OAuth2ServerListClientsError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum OAuth2ServerCreateClientError {
+    #[error("The client must have at least one redirect uri.")]
+    NoRedirectUri,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum OAuth2ServerDeleteClientError {
+    #[error("The client does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum OAuth2ServerAuthorizeError {
+    #[error("The client does not exist.")]
+    InvalidClient,
+    #[error("The redirect uri has not been registered for this client.")]
+    InvalidRedirectUri,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum OAuth2ServerTokenError {
+    #[error("The client authentication failed.")]
+    InvalidClient,
+    #[error("The authorization code or refresh token is invalid.")]
+    InvalidGrant,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum OAuth2ServerRevokeError {
+    #[error("The client authentication failed.")]
+    InvalidClient,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum OAuth2ServerIntrospectError {
+    #[error("The client authentication failed.")]
+    InvalidClient,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum OAuth2ServerUserInfoError {
+    #[error("The access token is invalid or has expired.")]
+    InvalidToken,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
@@ -0,0 +1,84 @@
+use academy_models::{
+    oauth2_server::{
+        OAuth2ClientNonce, OAuth2Grant, OAuth2ServerAccessToken, OAuth2ServerRefreshToken,
+        OAuth2ServerRefreshTokenHash, OAuth2TokenInfo, OAuth2TokenResponse,
+    },
+    user::UserComposite,
+};
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait OAuth2ServerTokenService: Send + Sync + 'static {
+    /// Issue a new access token and refresh token for the given grant.
+    ///
+    /// An ID token is only included if the grant contains the `openid`
+    /// scope.
+    fn issue(
+        &self,
+        user_composite: &UserComposite,
+        grant: &OAuth2Grant,
+        nonce: Option<OAuth2ClientNonce>,
+    ) -> anyhow::Result<OAuth2ServerTokens>;
+
+    /// Verify the given access token and return its contents.
+    fn verify_access_token(
+        &self,
+        access_token: &OAuth2ServerAccessToken,
+    ) -> Option<OAuth2TokenInfo>;
+
+    /// Compute the hash of the given refresh token.
+    fn hash_refresh_token(
+        &self,
+        refresh_token: &OAuth2ServerRefreshToken,
+    ) -> OAuth2ServerRefreshTokenHash;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuth2ServerTokens {
+    pub response: OAuth2TokenResponse,
+    pub refresh_token_hash: OAuth2ServerRefreshTokenHash,
+}
+
+#[cfg(feature = "mock")]
+impl MockOAuth2ServerTokenService {
+    pub fn with_issue(
+        mut self,
+        user_composite: UserComposite,
+        grant: OAuth2Grant,
+        nonce: Option<OAuth2ClientNonce>,
+        result: OAuth2ServerTokens,
+    ) -> Self {
+        self.expect_issue()
+            .once()
+            .with(
+                mockall::predicate::eq(user_composite),
+                mockall::predicate::eq(grant),
+                mockall::predicate::eq(nonce),
+            )
+            .return_once(|_, _, _| Ok(result));
+        self
+    }
+
+    pub fn with_verify_access_token(
+        mut self,
+        access_token: OAuth2ServerAccessToken,
+        result: Option<OAuth2TokenInfo>,
+    ) -> Self {
+        self.expect_verify_access_token()
+            .once()
+            .with(mockall::predicate::eq(access_token))
+            .return_once(|_| result);
+        self
+    }
+
+    pub fn with_hash_refresh_token(
+        mut self,
+        refresh_token: OAuth2ServerRefreshToken,
+        result: OAuth2ServerRefreshTokenHash,
+    ) -> Self {
+        self.expect_hash_refresh_token()
+            .once()
+            .with(mockall::predicate::eq(refresh_token))
+            .return_const(result);
+        self
+    }
+}
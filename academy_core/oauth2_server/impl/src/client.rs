@@ -0,0 +1,390 @@
+use academy_core_oauth2_server_contracts::{
+    client::{OAuth2ClientAuthenticateError, OAuth2ClientService},
+    OAuth2ClientCreateRequest,
+};
+use academy_di::Build;
+use academy_models::oauth2_server::{OAuth2Client, OAuth2ClientCredentials, OAuth2ClientSecret};
+use academy_persistence_contracts::oauth2_server::OAuth2ServerRepository;
+use academy_shared_contracts::{
+    hash::HashService, id::IdService, secret::SecretService, time::TimeService,
+};
+use academy_utils::trace_instrument;
+use anyhow::Context;
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct OAuth2ClientServiceImpl<Id, Time, Secret, Hash, OAuth2ServerRepo> {
+    id: Id,
+    time: Time,
+    secret: Secret,
+    hash: Hash,
+    oauth2_server_repo: OAuth2ServerRepo,
+}
+
+impl<Txn, Id, Time, Secret, Hash, OAuth2ServerRepo> OAuth2ClientService<Txn>
+    for OAuth2ClientServiceImpl<Id, Time, Secret, Hash, OAuth2ServerRepo>
+where
+    Txn: Send + Sync + 'static,
+    Id: IdService,
+    Time: TimeService,
+    Secret: SecretService,
+    Hash: HashService,
+    OAuth2ServerRepo: OAuth2ServerRepository<Txn>,
+{
+    #[trace_instrument(skip(self, txn))]
+    async fn create(
+        &self,
+        txn: &mut Txn,
+        OAuth2ClientCreateRequest {
+            name,
+            redirect_uris,
+            confidential,
+        }: OAuth2ClientCreateRequest,
+    ) -> anyhow::Result<(OAuth2Client, Option<OAuth2ClientSecret>)> {
+        let client = OAuth2Client {
+            id: self.id.generate(),
+            name,
+            redirect_uris,
+            confidential,
+            created_at: self.time.now(),
+        };
+
+        let secret = confidential.then(|| {
+            OAuth2ClientSecret::try_new(self.secret.generate(OAuth2ClientSecret::LEN).0).unwrap()
+        });
+        let secret_hash = secret
+            .as_ref()
+            .map(|secret| self.hash.sha256(secret).into());
+
+        self.oauth2_server_repo
+            .create_client(txn, &client, secret_hash)
+            .await
+            .context("Failed to create OAuth2 client in database")?;
+
+        Ok((client, secret))
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn authenticate(
+        &self,
+        txn: &mut Txn,
+        credentials: &OAuth2ClientCredentials,
+    ) -> Result<OAuth2Client, OAuth2ClientAuthenticateError> {
+        let client = self
+            .oauth2_server_repo
+            .get_client(txn, credentials.client_id)
+            .await
+            .context("Failed to get OAuth2 client from database")?
+            .ok_or(OAuth2ClientAuthenticateError::InvalidCredentials)?;
+
+        match (&credentials.client_secret, client.confidential) {
+            (None, false) => {}
+            (Some(secret), true) => {
+                let secret_hash = self
+                    .oauth2_server_repo
+                    .get_client_secret_hash(txn, client.id)
+                    .await
+                    .context("Failed to get OAuth2 client secret hash from database")?;
+                let hash = self.hash.sha256(secret).into();
+                if secret_hash != Some(hash) {
+                    return Err(OAuth2ClientAuthenticateError::InvalidCredentials);
+                }
+            }
+            _ => return Err(OAuth2ClientAuthenticateError::InvalidCredentials),
+        }
+
+        Ok(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_demo::{
+        oauth2_server::{PUBLIC_OAUTH2_CLIENT, TEST_OAUTH2_CLIENT, TEST_OAUTH2_CLIENT_SECRET_HASH},
+        SHA256HASH2,
+    };
+    use academy_persistence_contracts::{
+        oauth2_server::MockOAuth2ServerRepository, MockTransaction,
+    };
+    use academy_shared_contracts::{
+        hash::MockHashService, id::MockIdService, secret::MockSecretService, time::MockTimeService,
+    };
+    use academy_utils::assert_matches;
+
+    use super::*;
+
+    type Sut = OAuth2ClientServiceImpl<
+        MockIdService,
+        MockTimeService,
+        MockSecretService,
+        MockHashService,
+        MockOAuth2ServerRepository<MockTransaction>,
+    >;
+
+    #[tokio::test]
+    async fn create_confidential() {
+        // Arrange
+        let expected = TEST_OAUTH2_CLIENT.clone();
+        let secret = secret();
+
+        let id = MockIdService::new().with_generate(expected.id);
+        let time = MockTimeService::new().with_now(expected.created_at);
+        let secret_service = MockSecretService::new()
+            .with_generate(OAuth2ClientSecret::LEN, secret.clone().into_inner());
+        let hash =
+            MockHashService::new().with_sha256(secret.clone(), **TEST_OAUTH2_CLIENT_SECRET_HASH);
+
+        let oauth2_server_repo = MockOAuth2ServerRepository::new()
+            .with_create_client(expected.clone(), Some(*TEST_OAUTH2_CLIENT_SECRET_HASH));
+
+        let sut = OAuth2ClientServiceImpl {
+            id,
+            time,
+            secret: secret_service,
+            hash,
+            oauth2_server_repo,
+        };
+
+        // Act
+        let result = sut
+            .create(
+                &mut MockTransaction::new(),
+                OAuth2ClientCreateRequest {
+                    name: expected.name.clone(),
+                    redirect_uris: expected.redirect_uris.clone(),
+                    confidential: true,
+                },
+            )
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), (expected, Some(secret)));
+    }
+
+    #[tokio::test]
+    async fn create_public() {
+        // Arrange
+        let expected = PUBLIC_OAUTH2_CLIENT.clone();
+
+        let id = MockIdService::new().with_generate(expected.id);
+        let time = MockTimeService::new().with_now(expected.created_at);
+
+        let oauth2_server_repo =
+            MockOAuth2ServerRepository::new().with_create_client(expected.clone(), None);
+
+        let sut = OAuth2ClientServiceImpl {
+            id,
+            time,
+            oauth2_server_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .create(
+                &mut MockTransaction::new(),
+                OAuth2ClientCreateRequest {
+                    name: expected.name.clone(),
+                    redirect_uris: expected.redirect_uris.clone(),
+                    confidential: false,
+                },
+            )
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), (expected, None));
+    }
+
+    #[tokio::test]
+    async fn authenticate_confidential() {
+        // Arrange
+        let secret = secret();
+
+        let hash =
+            MockHashService::new().with_sha256(secret.clone(), **TEST_OAUTH2_CLIENT_SECRET_HASH);
+
+        let oauth2_server_repo = MockOAuth2ServerRepository::new()
+            .with_get_client(TEST_OAUTH2_CLIENT.id, Some(TEST_OAUTH2_CLIENT.clone()))
+            .with_get_client_secret_hash(
+                TEST_OAUTH2_CLIENT.id,
+                Some(*TEST_OAUTH2_CLIENT_SECRET_HASH),
+            );
+
+        let sut = OAuth2ClientServiceImpl {
+            hash,
+            oauth2_server_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .authenticate(
+                &mut MockTransaction::new(),
+                &OAuth2ClientCredentials {
+                    client_id: TEST_OAUTH2_CLIENT.id,
+                    client_secret: Some(secret),
+                },
+            )
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), *TEST_OAUTH2_CLIENT);
+    }
+
+    #[tokio::test]
+    async fn authenticate_confidential_wrong_secret() {
+        // Arrange
+        let secret = secret();
+
+        let hash = MockHashService::new().with_sha256(secret.clone(), *SHA256HASH2);
+
+        let oauth2_server_repo = MockOAuth2ServerRepository::new()
+            .with_get_client(TEST_OAUTH2_CLIENT.id, Some(TEST_OAUTH2_CLIENT.clone()))
+            .with_get_client_secret_hash(
+                TEST_OAUTH2_CLIENT.id,
+                Some(*TEST_OAUTH2_CLIENT_SECRET_HASH),
+            );
+
+        let sut = OAuth2ClientServiceImpl {
+            hash,
+            oauth2_server_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .authenticate(
+                &mut MockTransaction::new(),
+                &OAuth2ClientCredentials {
+                    client_id: TEST_OAUTH2_CLIENT.id,
+                    client_secret: Some(secret),
+                },
+            )
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(OAuth2ClientAuthenticateError::InvalidCredentials)
+        );
+    }
+
+    #[tokio::test]
+    async fn authenticate_confidential_missing_secret() {
+        // Arrange
+        let oauth2_server_repo = MockOAuth2ServerRepository::new()
+            .with_get_client(TEST_OAUTH2_CLIENT.id, Some(TEST_OAUTH2_CLIENT.clone()));
+
+        let sut = OAuth2ClientServiceImpl {
+            oauth2_server_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .authenticate(
+                &mut MockTransaction::new(),
+                &OAuth2ClientCredentials {
+                    client_id: TEST_OAUTH2_CLIENT.id,
+                    client_secret: None,
+                },
+            )
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(OAuth2ClientAuthenticateError::InvalidCredentials)
+        );
+    }
+
+    #[tokio::test]
+    async fn authenticate_public() {
+        // Arrange
+        let oauth2_server_repo = MockOAuth2ServerRepository::new()
+            .with_get_client(PUBLIC_OAUTH2_CLIENT.id, Some(PUBLIC_OAUTH2_CLIENT.clone()));
+
+        let sut = OAuth2ClientServiceImpl {
+            oauth2_server_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .authenticate(
+                &mut MockTransaction::new(),
+                &OAuth2ClientCredentials {
+                    client_id: PUBLIC_OAUTH2_CLIENT.id,
+                    client_secret: None,
+                },
+            )
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), *PUBLIC_OAUTH2_CLIENT);
+    }
+
+    #[tokio::test]
+    async fn authenticate_public_with_secret() {
+        // Arrange
+        let oauth2_server_repo = MockOAuth2ServerRepository::new()
+            .with_get_client(PUBLIC_OAUTH2_CLIENT.id, Some(PUBLIC_OAUTH2_CLIENT.clone()));
+
+        let sut = OAuth2ClientServiceImpl {
+            oauth2_server_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .authenticate(
+                &mut MockTransaction::new(),
+                &OAuth2ClientCredentials {
+                    client_id: PUBLIC_OAUTH2_CLIENT.id,
+                    client_secret: Some(secret()),
+                },
+            )
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(OAuth2ClientAuthenticateError::InvalidCredentials)
+        );
+    }
+
+    #[tokio::test]
+    async fn authenticate_not_found() {
+        // Arrange
+        let oauth2_server_repo =
+            MockOAuth2ServerRepository::new().with_get_client(TEST_OAUTH2_CLIENT.id, None);
+
+        let sut = OAuth2ClientServiceImpl {
+            oauth2_server_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .authenticate(
+                &mut MockTransaction::new(),
+                &OAuth2ClientCredentials {
+                    client_id: TEST_OAUTH2_CLIENT.id,
+                    client_secret: Some(secret()),
+                },
+            )
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(OAuth2ClientAuthenticateError::InvalidCredentials)
+        );
+    }
+
+    fn secret() -> OAuth2ClientSecret {
+        "6NvOuR4m3Mxn1E8GhBpTKE7XaN4OUZ4sVD1KSb5Kx5HdXlrGkOmlYZkUrm8GfSXq"
+            .try_into()
+            .unwrap()
+    }
+}
@@ -0,0 +1,269 @@
+use academy_cache_contracts::CacheService;
+use academy_core_oauth2_server_contracts::code::OAuth2AuthorizationCodeService;
+use academy_di::Build;
+use academy_models::oauth2_server::{
+    OAuth2Authorization, OAuth2ClientPkceCodeVerifier, OAuth2PkceCodeChallenge,
+    OAuth2ServerAuthorizationCode,
+};
+use academy_shared_contracts::{hash::HashService, secret::SecretService};
+use academy_utils::trace_instrument;
+use anyhow::Context;
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+
+use crate::OAuth2ServerFeatureConfig;
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct OAuth2AuthorizationCodeServiceImpl<Secret, Hash, Cache> {
+    secret: Secret,
+    hash: Hash,
+    cache: Cache,
+    config: OAuth2ServerFeatureConfig,
+}
+
+impl<Secret, Hash, Cache> OAuth2AuthorizationCodeService
+    for OAuth2AuthorizationCodeServiceImpl<Secret, Hash, Cache>
+where
+    Secret: SecretService,
+    Hash: HashService,
+    Cache: CacheService,
+{
+    #[trace_instrument(skip(self))]
+    async fn issue(
+        &self,
+        authorization: &OAuth2Authorization,
+    ) -> anyhow::Result<OAuth2ServerAuthorizationCode> {
+        let code = OAuth2ServerAuthorizationCode::try_new(
+            self.secret.generate(OAuth2ServerAuthorizationCode::LEN).0,
+        )
+        .unwrap();
+
+        self.cache
+            .set(
+                &oauth2_server_code_cache_key(&code),
+                authorization,
+                Some(self.config.code_ttl),
+            )
+            .await
+            .context("Failed to save OAuth2 authorization in cache")?;
+
+        Ok(code)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn redeem(
+        &self,
+        code: &OAuth2ServerAuthorizationCode,
+    ) -> anyhow::Result<Option<OAuth2Authorization>> {
+        self.cache
+            .take(&oauth2_server_code_cache_key(code))
+            .await
+            .context("Failed to take OAuth2 authorization from cache")
+    }
+
+    #[trace_instrument(skip(self))]
+    fn verify_code_verifier(
+        &self,
+        code_challenge: &OAuth2PkceCodeChallenge,
+        code_verifier: &OAuth2ClientPkceCodeVerifier,
+    ) -> bool {
+        let hash = self.hash.sha256(code_verifier);
+        BASE64_URL_SAFE_NO_PAD.encode(hash.0) == **code_challenge
+    }
+}
+
+fn oauth2_server_code_cache_key(code: &OAuth2ServerAuthorizationCode) -> String {
+    format!("oauth2_server_code:{}", **code)
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_cache_contracts::MockCacheService;
+    use academy_demo::{oauth2_server::TEST_OAUTH2_CLIENT, user::FOO, SHA256HASH1};
+    use academy_models::oauth2_server::OAuth2Scope;
+    use academy_shared_contracts::{hash::MockHashService, secret::MockSecretService};
+
+    use super::*;
+
+    type Sut =
+        OAuth2AuthorizationCodeServiceImpl<MockSecretService, MockHashService, MockCacheService>;
+
+    #[tokio::test]
+    async fn issue() {
+        // Arrange
+        let config = OAuth2ServerFeatureConfig::default();
+        let expected = code();
+        let authorization = authorization();
+
+        let secret = MockSecretService::new().with_generate(
+            OAuth2ServerAuthorizationCode::LEN,
+            expected.clone().into_inner(),
+        );
+
+        let cache = MockCacheService::new().with_set(
+            format!("oauth2_server_code:{}", *expected),
+            authorization.clone(),
+            Some(config.code_ttl),
+        );
+
+        let sut = OAuth2AuthorizationCodeServiceImpl {
+            secret,
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.issue(&authorization).await;
+
+        // Assert
+        assert_eq!(result.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn redeem_ok() {
+        // Arrange
+        let expected = authorization();
+        let code = code();
+
+        let cache = MockCacheService::new().with_take(
+            format!("oauth2_server_code:{}", *code),
+            Some(expected.clone()),
+        );
+
+        let sut = OAuth2AuthorizationCodeServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.redeem(&code).await;
+
+        // Assert
+        assert_eq!(result.unwrap(), Some(expected));
+    }
+
+    #[tokio::test]
+    async fn redeem_twice() {
+        // Arrange
+        let expected = authorization();
+        let code = code();
+
+        let cache = MockCacheService::new()
+            .with_take(
+                format!("oauth2_server_code:{}", *code),
+                Some(expected.clone()),
+            )
+            .with_take(
+                format!("oauth2_server_code:{}", *code),
+                None::<OAuth2Authorization>,
+            );
+
+        let sut = OAuth2AuthorizationCodeServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let first = sut.redeem(&code).await;
+        let second = sut.redeem(&code).await;
+
+        // Assert
+        assert_eq!(first.unwrap(), Some(expected));
+        assert_eq!(second.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn redeem_invalid_code() {
+        // Arrange
+        let code = code();
+
+        let cache = MockCacheService::new().with_take(
+            format!("oauth2_server_code:{}", *code),
+            None::<OAuth2Authorization>,
+        );
+
+        let sut = OAuth2AuthorizationCodeServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.redeem(&code).await;
+
+        // Assert
+        assert_eq!(result.unwrap(), None);
+    }
+
+    #[test]
+    fn verify_code_verifier_ok() {
+        // Arrange
+        let code_verifier = code_verifier();
+
+        let hash = MockHashService::new().with_sha256(code_verifier.clone(), *SHA256HASH1);
+
+        let sut = OAuth2AuthorizationCodeServiceImpl {
+            hash,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.verify_code_verifier(
+            &BASE64_URL_SAFE_NO_PAD
+                .encode(SHA256HASH1.0)
+                .try_into()
+                .unwrap(),
+            &code_verifier,
+        );
+
+        // Assert
+        assert!(result);
+    }
+
+    #[test]
+    fn verify_code_verifier_mismatch() {
+        // Arrange
+        let code_verifier = code_verifier();
+
+        let hash = MockHashService::new().with_sha256(code_verifier.clone(), *SHA256HASH1);
+
+        let sut = OAuth2AuthorizationCodeServiceImpl {
+            hash,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.verify_code_verifier(&code_challenge(), &code_verifier);
+
+        // Assert
+        assert!(!result);
+    }
+
+    fn code() -> OAuth2ServerAuthorizationCode {
+        "o6xbPG2Hk3SIRYtSn5kmFdeZ4Rj9rjv0cA6WITiGpB4a4ZgVWDFY6cXUEE2yN5kb"
+            .try_into()
+            .unwrap()
+    }
+
+    fn code_verifier() -> OAuth2ClientPkceCodeVerifier {
+        "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"
+            .try_into()
+            .unwrap()
+    }
+
+    fn code_challenge() -> OAuth2PkceCodeChallenge {
+        "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+            .try_into()
+            .unwrap()
+    }
+
+    fn authorization() -> OAuth2Authorization {
+        OAuth2Authorization {
+            client_id: TEST_OAUTH2_CLIENT.id,
+            user_id: FOO.user.id,
+            redirect_uri: TEST_OAUTH2_CLIENT.redirect_uris[0].clone(),
+            scopes: [OAuth2Scope::Openid].into_iter().collect(),
+            code_challenge: code_challenge(),
+            nonce: None,
+        }
+    }
+}
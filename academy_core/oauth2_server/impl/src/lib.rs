@@ -0,0 +1,638 @@
+use std::time::Duration;
+
+use academy_auth_contracts::{AuthResultExt, AuthService};
+use academy_core_audit_contracts::audit::AuditService;
+use academy_core_oauth2_server_contracts::{
+    client::{OAuth2ClientAuthenticateError, OAuth2ClientService},
+    code::OAuth2AuthorizationCodeService,
+    token::OAuth2ServerTokenService,
+    OAuth2ClientCreateRequest, OAuth2CreateClientResponse, OAuth2ServerAuthorizeError,
+    OAuth2ServerCreateClientError, OAuth2ServerDeleteClientError, OAuth2ServerFeatureService,
+    OAuth2ServerIntrospectError, OAuth2ServerListClientsError, OAuth2ServerRevokeError,
+    OAuth2ServerTokenError, OAuth2ServerUserInfoError,
+};
+use academy_di::Build;
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::AccessToken,
+    oauth2_server::{
+        OAuth2Authorization, OAuth2AuthorizeRequest, OAuth2Client, OAuth2ClientCredentials,
+        OAuth2ClientId, OAuth2Consent, OAuth2Grant, OAuth2ServerAccessToken,
+        OAuth2ServerRefreshToken, OAuth2ServerUserInfo, OAuth2TokenInfo, OAuth2TokenRequest,
+        OAuth2TokenResponse,
+    },
+    session::{DeviceName, Session, SessionLoginMethod, SessionPatch},
+    url::Url,
+    user::{UserComposite, UserId},
+};
+use academy_persistence_contracts::{
+    oauth2_server::OAuth2ServerRepository, session::SessionRepository, user::UserRepository,
+    Database, Transaction,
+};
+use academy_shared_contracts::{id::IdService, time::TimeService};
+use academy_utils::trace_instrument;
+use anyhow::Context;
+
+pub mod client;
+pub mod code;
+pub mod token;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct OAuth2ServerFeatureServiceImpl<
+    Db,
+    Auth,
+    Id,
+    Time,
+    UserRepo,
+    SessionRepo,
+    OAuth2ServerRepo,
+    OAuth2Client,
+    OAuth2AuthorizationCode,
+    OAuth2ServerToken,
+    Audit,
+> {
+    db: Db,
+    auth: Auth,
+    id: Id,
+    time: Time,
+    user_repo: UserRepo,
+    session_repo: SessionRepo,
+    oauth2_server_repo: OAuth2ServerRepo,
+    oauth2_client: OAuth2Client,
+    oauth2_authorization_code: OAuth2AuthorizationCode,
+    oauth2_server_token: OAuth2ServerToken,
+    audit: Audit,
+    config: OAuth2ServerFeatureConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct OAuth2ServerFeatureConfig {
+    pub issuer: Url,
+    pub access_token_ttl: Duration,
+    pub refresh_token_ttl: Duration,
+    pub code_ttl: Duration,
+}
+
+impl<
+        Db,
+        Auth,
+        Id,
+        Time,
+        UserRepo,
+        SessionRepo,
+        OAuth2ServerRepo,
+        OAuth2ClientS,
+        OAuth2AuthorizationCode,
+        OAuth2ServerToken,
+        Audit,
+    > OAuth2ServerFeatureService
+    for OAuth2ServerFeatureServiceImpl<
+        Db,
+        Auth,
+        Id,
+        Time,
+        UserRepo,
+        SessionRepo,
+        OAuth2ServerRepo,
+        OAuth2ClientS,
+        OAuth2AuthorizationCode,
+        OAuth2ServerToken,
+        Audit,
+    >
+where
+    Db: Database,
+    Auth: AuthService<Db::Transaction>,
+    Id: IdService,
+    Time: TimeService,
+    UserRepo: UserRepository<Db::Transaction>,
+    SessionRepo: SessionRepository<Db::Transaction>,
+    OAuth2ServerRepo: OAuth2ServerRepository<Db::Transaction>,
+    OAuth2ClientS: OAuth2ClientService<Db::Transaction>,
+    OAuth2AuthorizationCode: OAuth2AuthorizationCodeService,
+    OAuth2ServerToken: OAuth2ServerTokenService,
+    Audit: AuditService<Db::Transaction>,
+{
+    #[trace_instrument(skip(self))]
+    async fn list_clients(
+        &self,
+        token: &AccessToken,
+    ) -> Result<Vec<OAuth2Client>, OAuth2ServerListClientsError> {
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
+        auth.ensure_admin().map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        self.oauth2_server_repo
+            .list_clients(&mut txn)
+            .await
+            .context("Failed to get OAuth2 clients from database")
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn create_client(
+        &self,
+        token: &AccessToken,
+        request: OAuth2ClientCreateRequest,
+    ) -> Result<OAuth2CreateClientResponse, OAuth2ServerCreateClientError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_admin().map_auth_err()?;
+
+        if request.redirect_uris.is_empty() {
+            return Err(OAuth2ServerCreateClientError::NoRedirectUri);
+        }
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let (client, secret) = self
+            .oauth2_client
+            .create(&mut txn, request)
+            .await
+            .context("Failed to create OAuth2 client")?;
+
+        txn.commit().await?;
+
+        Ok(OAuth2CreateClientResponse { client, secret })
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn delete_client(
+        &self,
+        token: &AccessToken,
+        client_id: OAuth2ClientId,
+    ) -> Result<(), OAuth2ServerDeleteClientError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_admin().map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        // Deleting the sessions backing the grants invalidates both the refresh
+        // tokens and the access tokens issued to the client, as they are only
+        // accepted as long as their grant exists.
+        let grants = self
+            .oauth2_server_repo
+            .list_grants_by_client(&mut txn, client_id)
+            .await
+            .context("Failed to get OAuth2 grants from database")?;
+        for grant in grants {
+            self.session_repo
+                .delete(&mut txn, grant.session_id)
+                .await
+                .context("Failed to delete session from database")?;
+        }
+
+        if !self
+            .oauth2_server_repo
+            .delete_client(&mut txn, client_id)
+            .await
+            .context("Failed to delete OAuth2 client from database")?
+        {
+            return Err(OAuth2ServerDeleteClientError::NotFound);
+        }
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn get_consent(
+        &self,
+        token: &AccessToken,
+        request: OAuth2AuthorizeRequest,
+    ) -> Result<OAuth2Consent, OAuth2ServerAuthorizeError> {
+        self.auth.authenticate_read(token).await.map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let client = self.validate_authorize_request(&mut txn, &request).await?;
+
+        Ok(OAuth2Consent {
+            client,
+            scopes: request.scopes,
+        })
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn authorize(
+        &self,
+        token: &AccessToken,
+        request: OAuth2AuthorizeRequest,
+        ctx: &AuditContext,
+    ) -> Result<Url, OAuth2ServerAuthorizeError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        self.validate_authorize_request(&mut txn, &request).await?;
+
+        let code = self
+            .oauth2_authorization_code
+            .issue(&OAuth2Authorization {
+                client_id: request.client_id,
+                user_id: auth.user_id,
+                redirect_uri: request.redirect_uri.clone(),
+                scopes: request.scopes,
+                code_challenge: request.code_challenge,
+                nonce: request.nonce,
+            })
+            .await
+            .context("Failed to issue OAuth2 authorization code")?;
+
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                auth.user_id,
+                AuditAction::OAuth2ClientAuthorize,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        let mut redirect_uri = request.redirect_uri;
+        {
+            let mut query = redirect_uri.query_pairs_mut();
+            query.append_pair("code", &code);
+            if let Some(state) = &request.state {
+                query.append_pair("state", state);
+            }
+        }
+
+        Ok(redirect_uri)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn token(
+        &self,
+        credentials: OAuth2ClientCredentials,
+        request: OAuth2TokenRequest,
+    ) -> Result<OAuth2TokenResponse, OAuth2ServerTokenError> {
+        let mut txn = self.db.begin_transaction().await?;
+
+        let client = self
+            .oauth2_client
+            .authenticate(&mut txn, &credentials)
+            .await
+            .map_err(|err| match err {
+                OAuth2ClientAuthenticateError::InvalidCredentials => {
+                    OAuth2ServerTokenError::InvalidClient
+                }
+                OAuth2ClientAuthenticateError::Other(err) => {
+                    err.context("Failed to authenticate OAuth2 client").into()
+                }
+            })?;
+
+        let response = match request {
+            OAuth2TokenRequest::AuthorizationCode {
+                code,
+                redirect_uri,
+                code_verifier,
+            } => {
+                let authorization = self
+                    .oauth2_authorization_code
+                    .redeem(&code)
+                    .await
+                    .context("Failed to redeem OAuth2 authorization code")?
+                    .filter(|authorization| {
+                        authorization.client_id == client.id
+                            && authorization.redirect_uri == redirect_uri
+                    })
+                    .ok_or(OAuth2ServerTokenError::InvalidGrant)?;
+
+                if !self
+                    .oauth2_authorization_code
+                    .verify_code_verifier(&authorization.code_challenge, &code_verifier)
+                {
+                    return Err(OAuth2ServerTokenError::InvalidGrant);
+                }
+
+                let user_composite = self
+                    .get_enabled_user(&mut txn, authorization.user_id)
+                    .await?
+                    .ok_or(OAuth2ServerTokenError::InvalidGrant)?;
+
+                let now = self.time.now();
+                let session = Session {
+                    id: self.id.generate(),
+                    user_id: user_composite.user.id,
+                    device_name: Some(DeviceName::from_string_truncated(client.name.into_inner())),
+                    ip: None,
+                    device_fingerprint: None,
+                    last_ip: None,
+                    browser: None,
+                    os: None,
+                    login_method: SessionLoginMethod::OAuth2Client,
+                    created_at: now,
+                    updated_at: now,
+                };
+                self.session_repo
+                    .create(&mut txn, &session)
+                    .await
+                    .context("Failed to create session in database")?;
+
+                let grant = OAuth2Grant {
+                    session_id: session.id,
+                    client_id: client.id,
+                    scopes: authorization.scopes,
+                };
+                let tokens = self
+                    .oauth2_server_token
+                    .issue(&user_composite, &grant, authorization.nonce)
+                    .context("Failed to issue OAuth2 tokens")?;
+
+                self.oauth2_server_repo
+                    .create_grant(&mut txn, &grant, tokens.refresh_token_hash)
+                    .await
+                    .context("Failed to create OAuth2 grant in database")?;
+
+                tokens.response
+            }
+            OAuth2TokenRequest::RefreshToken { refresh_token } => {
+                let (grant, session) = self
+                    .get_active_grant_by_refresh_token(&mut txn, client.id, &refresh_token)
+                    .await?
+                    .ok_or(OAuth2ServerTokenError::InvalidGrant)?;
+
+                let user_composite = self
+                    .get_enabled_user(&mut txn, session.user_id)
+                    .await?
+                    .ok_or(OAuth2ServerTokenError::InvalidGrant)?;
+
+                // the refresh token is rotated on every use
+                let tokens = self
+                    .oauth2_server_token
+                    .issue(&user_composite, &grant, None)
+                    .context("Failed to issue OAuth2 tokens")?;
+
+                let patch = SessionPatch::new().update_updated_at(self.time.now());
+                self.session_repo
+                    .update(&mut txn, session.id, patch.as_ref())
+                    .await
+                    .context("Failed to update session in database")?;
+
+                self.oauth2_server_repo
+                    .update_grant_refresh_token_hash(
+                        &mut txn,
+                        grant.session_id,
+                        tokens.refresh_token_hash,
+                    )
+                    .await
+                    .context("Failed to update OAuth2 grant refresh token hash in database")?;
+
+                tokens.response
+            }
+        };
+
+        txn.commit().await?;
+
+        Ok(response)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn revoke(
+        &self,
+        credentials: OAuth2ClientCredentials,
+        token: String,
+        ctx: &AuditContext,
+    ) -> Result<(), OAuth2ServerRevokeError> {
+        let mut txn = self.db.begin_transaction().await?;
+
+        let client = self
+            .oauth2_client
+            .authenticate(&mut txn, &credentials)
+            .await
+            .map_err(|err| match err {
+                OAuth2ClientAuthenticateError::InvalidCredentials => {
+                    OAuth2ServerRevokeError::InvalidClient
+                }
+                OAuth2ClientAuthenticateError::Other(err) => {
+                    err.context("Failed to authenticate OAuth2 client").into()
+                }
+            })?;
+
+        let Some(info) = self.get_token_info(&mut txn, client.id, token).await? else {
+            return Ok(());
+        };
+
+        self.session_repo
+            .delete(&mut txn, info.session_id)
+            .await
+            .context("Failed to delete session from database")?;
+
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                None,
+                info.user_id,
+                AuditAction::OAuth2ClientRevoke,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn introspect(
+        &self,
+        credentials: OAuth2ClientCredentials,
+        token: String,
+    ) -> Result<Option<OAuth2TokenInfo>, OAuth2ServerIntrospectError> {
+        let mut txn = self.db.begin_transaction().await?;
+
+        let client = self
+            .oauth2_client
+            .authenticate(&mut txn, &credentials)
+            .await
+            .map_err(|err| match err {
+                OAuth2ClientAuthenticateError::InvalidCredentials => {
+                    OAuth2ServerIntrospectError::InvalidClient
+                }
+                OAuth2ClientAuthenticateError::Other(err) => {
+                    err.context("Failed to authenticate OAuth2 client").into()
+                }
+            })?;
+
+        self.get_token_info(&mut txn, client.id, token)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn userinfo(
+        &self,
+        token: &OAuth2ServerAccessToken,
+    ) -> Result<OAuth2ServerUserInfo, OAuth2ServerUserInfoError> {
+        let info = self
+            .oauth2_server_token
+            .verify_access_token(token)
+            .ok_or(OAuth2ServerUserInfoError::InvalidToken)?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        // the grant may have been revoked since the access token was issued
+        if self
+            .oauth2_server_repo
+            .get_grant(&mut txn, info.session_id)
+            .await
+            .context("Failed to get OAuth2 grant from database")?
+            .is_none()
+        {
+            return Err(OAuth2ServerUserInfoError::InvalidToken);
+        }
+
+        let user_composite = self
+            .get_enabled_user(&mut txn, info.user_id)
+            .await?
+            .ok_or(OAuth2ServerUserInfoError::InvalidToken)?;
+
+        Ok(OAuth2ServerUserInfo::new(&user_composite, &info.scopes))
+    }
+}
+
+impl<
+        Db,
+        Auth,
+        Id,
+        Time,
+        UserRepo,
+        SessionRepo,
+        OAuth2ServerRepo,
+        OAuth2ClientS,
+        OAuth2AuthorizationCode,
+        OAuth2ServerToken,
+        Audit,
+    >
+    OAuth2ServerFeatureServiceImpl<
+        Db,
+        Auth,
+        Id,
+        Time,
+        UserRepo,
+        SessionRepo,
+        OAuth2ServerRepo,
+        OAuth2ClientS,
+        OAuth2AuthorizationCode,
+        OAuth2ServerToken,
+        Audit,
+    >
+where
+    Db: Database,
+    Time: TimeService,
+    UserRepo: UserRepository<Db::Transaction>,
+    SessionRepo: SessionRepository<Db::Transaction>,
+    OAuth2ServerRepo: OAuth2ServerRepository<Db::Transaction>,
+    OAuth2ServerToken: OAuth2ServerTokenService,
+{
+    async fn validate_authorize_request(
+        &self,
+        txn: &mut Db::Transaction,
+        request: &OAuth2AuthorizeRequest,
+    ) -> Result<OAuth2Client, OAuth2ServerAuthorizeError> {
+        let client = self
+            .oauth2_server_repo
+            .get_client(txn, request.client_id)
+            .await
+            .context("Failed to get OAuth2 client from database")?
+            .ok_or(OAuth2ServerAuthorizeError::InvalidClient)?;
+
+        if !client.redirect_uris.contains(&request.redirect_uri) {
+            return Err(OAuth2ServerAuthorizeError::InvalidRedirectUri);
+        }
+
+        Ok(client)
+    }
+
+    async fn get_enabled_user(
+        &self,
+        txn: &mut Db::Transaction,
+        user_id: UserId,
+    ) -> anyhow::Result<Option<UserComposite>> {
+        self.user_repo
+            .get_composite(txn, user_id)
+            .await
+            .context("Failed to get user from database")
+            .map(|user_composite| user_composite.filter(|u| u.user.is_active()))
+    }
+
+    /// Return the grant and the session of the given refresh token if it has
+    /// been issued to the given client and has not expired yet.
+    async fn get_active_grant_by_refresh_token(
+        &self,
+        txn: &mut Db::Transaction,
+        client_id: OAuth2ClientId,
+        refresh_token: &OAuth2ServerRefreshToken,
+    ) -> anyhow::Result<Option<(OAuth2Grant, Session)>> {
+        let refresh_token_hash = self.oauth2_server_token.hash_refresh_token(refresh_token);
+
+        let Some(grant) = self
+            .oauth2_server_repo
+            .get_grant_by_refresh_token_hash(txn, refresh_token_hash)
+            .await
+            .context("Failed to get OAuth2 grant from database")?
+            .filter(|grant| grant.client_id == client_id)
+        else {
+            return Ok(None);
+        };
+
+        let session = self
+            .session_repo
+            .get(txn, grant.session_id)
+            .await
+            .context("Failed to get session from database")?
+            .filter(|session| self.time.now() < session.updated_at + self.config.refresh_token_ttl);
+
+        Ok(session.map(|session| (grant, session)))
+    }
+
+    /// Return information about the given access or refresh token if it is
+    /// active and has been issued to the given client.
+    async fn get_token_info(
+        &self,
+        txn: &mut Db::Transaction,
+        client_id: OAuth2ClientId,
+        token: String,
+    ) -> anyhow::Result<Option<OAuth2TokenInfo>> {
+        if let Ok(refresh_token) = OAuth2ServerRefreshToken::try_new(token.clone()) {
+            if let Some((grant, session)) = self
+                .get_active_grant_by_refresh_token(txn, client_id, &refresh_token)
+                .await?
+            {
+                return Ok(Some(OAuth2TokenInfo {
+                    user_id: session.user_id,
+                    session_id: grant.session_id,
+                    client_id: grant.client_id,
+                    scopes: grant.scopes,
+                }));
+            }
+        }
+
+        let Some(info) = self
+            .oauth2_server_token
+            .verify_access_token(&token.into())
+            .filter(|info| info.client_id == client_id)
+        else {
+            return Ok(None);
+        };
+
+        let active = self
+            .oauth2_server_repo
+            .get_grant(txn, info.session_id)
+            .await
+            .context("Failed to get OAuth2 grant from database")?
+            .is_some();
+
+        Ok(active.then_some(info))
+    }
+}
@@ -0,0 +1,259 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_oauth2_server_contracts::{
+    code::MockOAuth2AuthorizationCodeService, OAuth2ServerAuthorizeError,
+    OAuth2ServerFeatureService,
+};
+use academy_demo::{
+    oauth2_server::TEST_OAUTH2_CLIENT, personal_access_token::FOO_PAT, session::FOO_1, user::FOO,
+};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthenticateError, AuthorizeError},
+    oauth2_server::{OAuth2Authorization, OAuth2AuthorizeRequest, OAuth2ServerAuthorizationCode},
+};
+use academy_persistence_contracts::{oauth2_server::MockOAuth2ServerRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{
+    tests::{authorize_request, Sut},
+    OAuth2ServerFeatureServiceImpl,
+};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let request = authorize_request();
+    let code = code();
+
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new()
+        .with_get_client(TEST_OAUTH2_CLIENT.id, Some(TEST_OAUTH2_CLIENT.clone()));
+
+    let oauth2_authorization_code = MockOAuth2AuthorizationCodeService::new().with_issue(
+        OAuth2Authorization {
+            client_id: request.client_id,
+            user_id: FOO.user.id,
+            redirect_uri: request.redirect_uri.clone(),
+            scopes: request.scopes.clone(),
+            code_challenge: request.code_challenge.clone(),
+            nonce: request.nonce.clone(),
+        },
+        code.clone(),
+    );
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::OAuth2ClientAuthorize,
+    );
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        auth,
+        oauth2_server_repo,
+        oauth2_authorization_code,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .authorize(&"token".into(), request, &AuditContext::default())
+        .await;
+
+    // Assert
+    assert_eq!(
+        result.unwrap().as_str(),
+        format!(
+            "https://client.example.com/callback?code={}&state=some-state",
+            *code
+        )
+    );
+}
+
+#[tokio::test]
+async fn ok_without_state() {
+    // Arrange
+    let request = OAuth2AuthorizeRequest {
+        state: None,
+        ..authorize_request()
+    };
+    let code = code();
+
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new()
+        .with_get_client(TEST_OAUTH2_CLIENT.id, Some(TEST_OAUTH2_CLIENT.clone()));
+
+    let oauth2_authorization_code = MockOAuth2AuthorizationCodeService::new().with_issue(
+        OAuth2Authorization {
+            client_id: request.client_id,
+            user_id: FOO.user.id,
+            redirect_uri: request.redirect_uri.clone(),
+            scopes: request.scopes.clone(),
+            code_challenge: request.code_challenge.clone(),
+            nonce: request.nonce.clone(),
+        },
+        code.clone(),
+    );
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::OAuth2ClientAuthorize,
+    );
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        auth,
+        oauth2_server_repo,
+        oauth2_authorization_code,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .authorize(&"token".into(), request, &AuditContext::default())
+        .await;
+
+    // Assert
+    assert_eq!(
+        result.unwrap().as_str(),
+        format!("https://client.example.com/callback?code={}", *code)
+    );
+}
+
+#[tokio::test]
+async fn unauthenticated() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(None);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .authorize(
+            &"token".into(),
+            authorize_request(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(OAuth2ServerAuthorizeError::Auth(AuthError::Authenticate(
+            AuthenticateError::InvalidToken
+        )))
+    );
+}
+
+#[tokio::test]
+async fn personal_access_token_not_allowed() {
+    // Arrange
+    let auth = MockAuthService::new()
+        .with_authenticate_personal_access_token(Some((FOO.user.clone(), FOO_PAT.clone())));
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .authorize(
+            &"token".into(),
+            authorize_request(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(OAuth2ServerAuthorizeError::Auth(AuthError::Authorize(
+            AuthorizeError::Session
+        )))
+    );
+}
+
+#[tokio::test]
+async fn invalid_client() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let oauth2_server_repo =
+        MockOAuth2ServerRepository::new().with_get_client(TEST_OAUTH2_CLIENT.id, None);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        auth,
+        oauth2_server_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .authorize(
+            &"token".into(),
+            authorize_request(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerAuthorizeError::InvalidClient));
+}
+
+#[tokio::test]
+async fn invalid_redirect_uri() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new()
+        .with_get_client(TEST_OAUTH2_CLIENT.id, Some(TEST_OAUTH2_CLIENT.clone()));
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        auth,
+        oauth2_server_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .authorize(
+            &"token".into(),
+            OAuth2AuthorizeRequest {
+                redirect_uri: "https://client.example.com/other".parse().unwrap(),
+                ..authorize_request()
+            },
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerAuthorizeError::InvalidRedirectUri));
+}
+
+fn code() -> OAuth2ServerAuthorizationCode {
+    "o6xbPG2Hk3SIRYtSn5kmFdeZ4Rj9rjv0cA6WITiGpB4a4ZgVWDFY6cXUEE2yN5kb"
+        .try_into()
+        .unwrap()
+}
@@ -0,0 +1,134 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_oauth2_server_contracts::{
+    client::MockOAuth2ClientService, OAuth2ClientCreateRequest, OAuth2CreateClientResponse,
+    OAuth2ServerCreateClientError, OAuth2ServerFeatureService,
+};
+use academy_demo::{
+    oauth2_server::TEST_OAUTH2_CLIENT,
+    session::{ADMIN_1, FOO_1},
+    user::{ADMIN, FOO},
+};
+use academy_models::{
+    auth::{AuthError, AuthenticateError, AuthorizeError},
+    oauth2_server::OAuth2ClientSecret,
+};
+use academy_persistence_contracts::MockDatabase;
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, OAuth2ServerFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let secret = OAuth2ClientSecret::try_new(
+        "6NvOuR4m3Mxn1E8GhBpTKE7XaN4OUZ4sVD1KSb5Kx5HdXlrGkOmlYZkUrm8GfSXq",
+    )
+    .unwrap();
+
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let oauth2_client = MockOAuth2ClientService::new().with_create(
+        request(),
+        (TEST_OAUTH2_CLIENT.clone(), Some(secret.clone())),
+    );
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        auth,
+        oauth2_client,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_client(&"token".into(), request()).await;
+
+    // Assert
+    assert_eq!(
+        result.unwrap(),
+        OAuth2CreateClientResponse {
+            client: TEST_OAUTH2_CLIENT.clone(),
+            secret: Some(secret),
+        }
+    );
+}
+
+#[tokio::test]
+async fn unauthenticated() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(None);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_client(&"token".into(), request()).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(OAuth2ServerCreateClientError::Auth(
+            AuthError::Authenticate(AuthenticateError::InvalidToken)
+        ))
+    );
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_client(&"token".into(), request()).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(OAuth2ServerCreateClientError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn no_redirect_uri() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_client(
+            &"token".into(),
+            OAuth2ClientCreateRequest {
+                redirect_uris: vec![],
+                ..request()
+            },
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerCreateClientError::NoRedirectUri));
+}
+
+fn request() -> OAuth2ClientCreateRequest {
+    OAuth2ClientCreateRequest {
+        name: TEST_OAUTH2_CLIENT.name.clone(),
+        redirect_uris: TEST_OAUTH2_CLIENT.redirect_uris.clone(),
+        confidential: true,
+    }
+}
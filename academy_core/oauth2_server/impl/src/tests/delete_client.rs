@@ -0,0 +1,140 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_oauth2_server_contracts::{
+    OAuth2ServerDeleteClientError, OAuth2ServerFeatureService,
+};
+use academy_demo::{
+    oauth2_server::TEST_OAUTH2_CLIENT,
+    session::{ADMIN_1, BAR_1, FOO_1},
+    user::{ADMIN, FOO},
+};
+use academy_models::{
+    auth::{AuthError, AuthenticateError, AuthorizeError},
+    oauth2_server::{OAuth2Grant, OAuth2Scope},
+    session::SessionId,
+};
+use academy_persistence_contracts::{
+    oauth2_server::MockOAuth2ServerRepository, session::MockSessionRepository, MockDatabase,
+};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, OAuth2ServerFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new()
+        .with_list_grants_by_client(
+            TEST_OAUTH2_CLIENT.id,
+            vec![grant(FOO_1.id), grant(BAR_1.id)],
+        )
+        .with_delete_client(TEST_OAUTH2_CLIENT.id, true);
+
+    let session_repo = MockSessionRepository::new()
+        .with_delete(FOO_1.id, true)
+        .with_delete(BAR_1.id, true);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        auth,
+        session_repo,
+        oauth2_server_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_client(&"token".into(), TEST_OAUTH2_CLIENT.id)
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn unauthenticated() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(None);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_client(&"token".into(), TEST_OAUTH2_CLIENT.id)
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(OAuth2ServerDeleteClientError::Auth(
+            AuthError::Authenticate(AuthenticateError::InvalidToken)
+        ))
+    );
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_client(&"token".into(), TEST_OAUTH2_CLIENT.id)
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(OAuth2ServerDeleteClientError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new()
+        .with_list_grants_by_client(TEST_OAUTH2_CLIENT.id, vec![])
+        .with_delete_client(TEST_OAUTH2_CLIENT.id, false);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        auth,
+        oauth2_server_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_client(&"token".into(), TEST_OAUTH2_CLIENT.id)
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerDeleteClientError::NotFound));
+}
+
+fn grant(session_id: SessionId) -> OAuth2Grant {
+    OAuth2Grant {
+        session_id,
+        client_id: TEST_OAUTH2_CLIENT.id,
+        scopes: [OAuth2Scope::Openid].into_iter().collect(),
+    }
+}
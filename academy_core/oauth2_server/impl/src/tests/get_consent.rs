@@ -0,0 +1,129 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_oauth2_server_contracts::{
+    OAuth2ServerAuthorizeError, OAuth2ServerFeatureService,
+};
+use academy_demo::{oauth2_server::TEST_OAUTH2_CLIENT, session::FOO_1, user::FOO};
+use academy_models::{
+    auth::{AuthError, AuthenticateError},
+    oauth2_server::{OAuth2AuthorizeRequest, OAuth2Consent},
+};
+use academy_persistence_contracts::{oauth2_server::MockOAuth2ServerRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{
+    tests::{authorize_request, Sut},
+    OAuth2ServerFeatureServiceImpl,
+};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let request = authorize_request();
+
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new()
+        .with_get_client(TEST_OAUTH2_CLIENT.id, Some(TEST_OAUTH2_CLIENT.clone()));
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        auth,
+        oauth2_server_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.get_consent(&"token".into(), request.clone()).await;
+
+    // Assert
+    assert_eq!(
+        result.unwrap(),
+        OAuth2Consent {
+            client: TEST_OAUTH2_CLIENT.clone(),
+            scopes: request.scopes,
+        }
+    );
+}
+
+#[tokio::test]
+async fn unauthenticated() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate_read(None);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.get_consent(&"token".into(), authorize_request()).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(OAuth2ServerAuthorizeError::Auth(AuthError::Authenticate(
+            AuthenticateError::InvalidToken
+        )))
+    );
+}
+
+#[tokio::test]
+async fn invalid_client() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let oauth2_server_repo =
+        MockOAuth2ServerRepository::new().with_get_client(TEST_OAUTH2_CLIENT.id, None);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        auth,
+        oauth2_server_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.get_consent(&"token".into(), authorize_request()).await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerAuthorizeError::InvalidClient));
+}
+
+#[tokio::test]
+async fn invalid_redirect_uri() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new()
+        .with_get_client(TEST_OAUTH2_CLIENT.id, Some(TEST_OAUTH2_CLIENT.clone()));
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        auth,
+        oauth2_server_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .get_consent(
+            &"token".into(),
+            OAuth2AuthorizeRequest {
+                redirect_uri: "https://evil.example.com/callback".parse().unwrap(),
+                ..authorize_request()
+            },
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerAuthorizeError::InvalidRedirectUri));
+}
@@ -0,0 +1,191 @@
+use academy_core_oauth2_server_contracts::{
+    client::{MockOAuth2ClientService, OAuth2ClientAuthenticateError},
+    token::MockOAuth2ServerTokenService,
+    OAuth2ServerFeatureService, OAuth2ServerIntrospectError,
+};
+use academy_demo::{oauth2_server::PUBLIC_OAUTH2_CLIENT, session::FOO_1, user::FOO, SHA256HASH1};
+use academy_models::oauth2_server::{
+    OAuth2ClientCredentials, OAuth2Grant, OAuth2Scope, OAuth2ServerAccessToken, OAuth2TokenInfo,
+};
+use academy_persistence_contracts::{
+    oauth2_server::MockOAuth2ServerRepository, session::MockSessionRepository, MockDatabase,
+};
+use academy_shared_contracts::time::MockTimeService;
+use academy_utils::assert_matches;
+
+use crate::{
+    tests::{refresh_token, Sut},
+    OAuth2ServerFeatureServiceImpl,
+};
+
+#[tokio::test]
+async fn ok_refresh_token() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let oauth2_client = MockOAuth2ClientService::new()
+        .with_authenticate(credentials(), Ok(PUBLIC_OAUTH2_CLIENT.clone()));
+
+    let oauth2_server_token = MockOAuth2ServerTokenService::new()
+        .with_hash_refresh_token(refresh_token(), (*SHA256HASH1).into());
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new()
+        .with_get_grant_by_refresh_token_hash((*SHA256HASH1).into(), Some(grant()));
+
+    let session_repo = MockSessionRepository::new().with_get(FOO_1.id, Some(FOO_1.clone()));
+
+    let time = MockTimeService::new().with_now(FOO_1.updated_at);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        time,
+        session_repo,
+        oauth2_server_repo,
+        oauth2_client,
+        oauth2_server_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .introspect(credentials(), refresh_token().into_inner())
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), Some(token_info()));
+}
+
+#[tokio::test]
+async fn ok_access_token() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let oauth2_client = MockOAuth2ClientService::new()
+        .with_authenticate(credentials(), Ok(PUBLIC_OAUTH2_CLIENT.clone()));
+
+    let oauth2_server_token = MockOAuth2ServerTokenService::new().with_verify_access_token(
+        OAuth2ServerAccessToken::from("access token".to_owned()),
+        Some(token_info()),
+    );
+
+    let oauth2_server_repo =
+        MockOAuth2ServerRepository::new().with_get_grant(FOO_1.id, Some(grant()));
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        oauth2_server_repo,
+        oauth2_client,
+        oauth2_server_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.introspect(credentials(), "access token".into()).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), Some(token_info()));
+}
+
+#[tokio::test]
+async fn revoked_access_token() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let oauth2_client = MockOAuth2ClientService::new()
+        .with_authenticate(credentials(), Ok(PUBLIC_OAUTH2_CLIENT.clone()));
+
+    let oauth2_server_token = MockOAuth2ServerTokenService::new().with_verify_access_token(
+        OAuth2ServerAccessToken::from("access token".to_owned()),
+        Some(token_info()),
+    );
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new().with_get_grant(FOO_1.id, None);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        oauth2_server_repo,
+        oauth2_client,
+        oauth2_server_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.introspect(credentials(), "access token".into()).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), None);
+}
+
+#[tokio::test]
+async fn invalid_token() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let oauth2_client = MockOAuth2ClientService::new()
+        .with_authenticate(credentials(), Ok(PUBLIC_OAUTH2_CLIENT.clone()));
+
+    let oauth2_server_token = MockOAuth2ServerTokenService::new().with_verify_access_token(
+        OAuth2ServerAccessToken::from("access token".to_owned()),
+        None,
+    );
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        oauth2_client,
+        oauth2_server_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.introspect(credentials(), "access token".into()).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), None);
+}
+
+#[tokio::test]
+async fn invalid_client() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let oauth2_client = MockOAuth2ClientService::new().with_authenticate(
+        credentials(),
+        Err(OAuth2ClientAuthenticateError::InvalidCredentials),
+    );
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        oauth2_client,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.introspect(credentials(), "access token".into()).await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerIntrospectError::InvalidClient));
+}
+
+fn credentials() -> OAuth2ClientCredentials {
+    OAuth2ClientCredentials {
+        client_id: PUBLIC_OAUTH2_CLIENT.id,
+        client_secret: None,
+    }
+}
+
+fn grant() -> OAuth2Grant {
+    OAuth2Grant {
+        session_id: FOO_1.id,
+        client_id: PUBLIC_OAUTH2_CLIENT.id,
+        scopes: [OAuth2Scope::Profile].into_iter().collect(),
+    }
+}
+
+fn token_info() -> OAuth2TokenInfo {
+    OAuth2TokenInfo {
+        user_id: FOO.user.id,
+        session_id: FOO_1.id,
+        client_id: PUBLIC_OAUTH2_CLIENT.id,
+        scopes: [OAuth2Scope::Profile].into_iter().collect(),
+    }
+}
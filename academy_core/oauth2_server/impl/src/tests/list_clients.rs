@@ -0,0 +1,89 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_oauth2_server_contracts::{
+    OAuth2ServerFeatureService, OAuth2ServerListClientsError,
+};
+use academy_demo::{
+    oauth2_server::ALL_OAUTH2_CLIENTS,
+    session::{ADMIN_1, FOO_1},
+    user::{ADMIN, FOO},
+};
+use academy_models::auth::{AuthError, AuthenticateError, AuthorizeError};
+use academy_persistence_contracts::{oauth2_server::MockOAuth2ServerRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, OAuth2ServerFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let expected = ALL_OAUTH2_CLIENTS
+        .iter()
+        .copied()
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new().with_list_clients(expected.clone());
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        auth,
+        oauth2_server_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.list_clients(&"token".into()).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn unauthenticated() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate_read(None);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.list_clients(&"token".into()).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(OAuth2ServerListClientsError::Auth(AuthError::Authenticate(
+            AuthenticateError::InvalidToken
+        )))
+    );
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.list_clients(&"token".into()).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(OAuth2ServerListClientsError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
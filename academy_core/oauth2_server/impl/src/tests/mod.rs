@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_oauth2_server_contracts::{
+    client::MockOAuth2ClientService, code::MockOAuth2AuthorizationCodeService,
+    token::MockOAuth2ServerTokenService,
+};
+use academy_demo::oauth2_server::TEST_OAUTH2_CLIENT;
+use academy_models::oauth2_server::{
+    OAuth2AuthorizeRequest, OAuth2ClientPkceCodeVerifier, OAuth2PkceCodeChallenge, OAuth2Scope,
+    OAuth2ServerRefreshToken,
+};
+use academy_persistence_contracts::{
+    oauth2_server::MockOAuth2ServerRepository, session::MockSessionRepository,
+    user::MockUserRepository, MockDatabase, MockTransaction,
+};
+use academy_shared_contracts::{id::MockIdService, time::MockTimeService};
+
+use crate::{OAuth2ServerFeatureConfig, OAuth2ServerFeatureServiceImpl};
+
+mod authorize;
+mod create_client;
+mod delete_client;
+mod get_consent;
+mod introspect;
+mod list_clients;
+mod revoke;
+mod token;
+mod userinfo;
+
+type Sut = OAuth2ServerFeatureServiceImpl<
+    MockDatabase,
+    MockAuthService<MockTransaction>,
+    MockIdService,
+    MockTimeService,
+    MockUserRepository<MockTransaction>,
+    MockSessionRepository<MockTransaction>,
+    MockOAuth2ServerRepository<MockTransaction>,
+    MockOAuth2ClientService<MockTransaction>,
+    MockOAuth2AuthorizationCodeService,
+    MockOAuth2ServerTokenService,
+    MockAuditService<MockTransaction>,
+>;
+
+impl Default for OAuth2ServerFeatureConfig {
+    fn default() -> Self {
+        Self {
+            issuer: "https://bootstrap.academy/".parse().unwrap(),
+            access_token_ttl: Duration::from_secs(300),
+            refresh_token_ttl: Duration::from_secs(30 * 24 * 3600),
+            code_ttl: Duration::from_secs(60),
+        }
+    }
+}
+
+fn authorize_request() -> OAuth2AuthorizeRequest {
+    OAuth2AuthorizeRequest {
+        client_id: TEST_OAUTH2_CLIENT.id,
+        redirect_uri: TEST_OAUTH2_CLIENT.redirect_uris[0].clone(),
+        scopes: [OAuth2Scope::Openid, OAuth2Scope::Profile]
+            .into_iter()
+            .collect(),
+        state: Some("some-state".try_into().unwrap()),
+        code_challenge: code_challenge(),
+        nonce: Some("some-nonce".try_into().unwrap()),
+    }
+}
+
+fn code_challenge() -> OAuth2PkceCodeChallenge {
+    "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        .try_into()
+        .unwrap()
+}
+
+fn code_verifier() -> OAuth2ClientPkceCodeVerifier {
+    "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"
+        .try_into()
+        .unwrap()
+}
+
+fn refresh_token() -> OAuth2ServerRefreshToken {
+    "BLfbrQj5YU0uVixnJrg9pH5eOiSfSDpzsxXTjxKJ3ZTq7GAedFvNI4nGuJhPbxG9"
+        .try_into()
+        .unwrap()
+}
@@ -0,0 +1,470 @@
+use std::time::Duration;
+
+use academy_core_oauth2_server_contracts::{
+    client::{MockOAuth2ClientService, OAuth2ClientAuthenticateError},
+    code::MockOAuth2AuthorizationCodeService,
+    token::{MockOAuth2ServerTokenService, OAuth2ServerTokens},
+    OAuth2ServerFeatureService, OAuth2ServerTokenError,
+};
+use academy_demo::{
+    oauth2_server::TEST_OAUTH2_CLIENT,
+    session::FOO_1,
+    user::{BAR, FOO},
+    SHA256HASH1, SHA256HASH2, UUID1,
+};
+use academy_models::{
+    oauth2_server::{
+        OAuth2Authorization, OAuth2ClientCredentials, OAuth2Grant, OAuth2Scope,
+        OAuth2ServerAuthorizationCode, OAuth2TokenRequest, OAuth2TokenResponse,
+    },
+    session::{DeviceName, Session, SessionLoginMethod, SessionPatch},
+};
+use academy_persistence_contracts::{
+    oauth2_server::MockOAuth2ServerRepository, session::MockSessionRepository,
+    user::MockUserRepository, MockDatabase,
+};
+use academy_shared_contracts::{id::MockIdService, time::MockTimeService};
+use academy_utils::assert_matches;
+
+use crate::{
+    tests::{code_challenge, code_verifier, refresh_token, Sut},
+    OAuth2ServerFeatureConfig, OAuth2ServerFeatureServiceImpl,
+};
+
+#[tokio::test]
+async fn ok_authorization_code() {
+    // Arrange
+    let authorization = authorization();
+    let now = FOO_1.updated_at;
+    let session = Session {
+        id: UUID1.into(),
+        user_id: FOO.user.id,
+        device_name: Some(DeviceName::try_new("Test Client").unwrap()),
+        ip: None,
+        device_fingerprint: None,
+        last_ip: None,
+        browser: None,
+        os: None,
+        login_method: SessionLoginMethod::OAuth2Client,
+        created_at: now,
+        updated_at: now,
+    };
+    let grant = OAuth2Grant {
+        session_id: session.id,
+        client_id: TEST_OAUTH2_CLIENT.id,
+        scopes: authorization.scopes.clone(),
+    };
+    let expected = response(&grant);
+
+    let db = MockDatabase::build(true);
+
+    let oauth2_client = MockOAuth2ClientService::new()
+        .with_authenticate(credentials(), Ok(TEST_OAUTH2_CLIENT.clone()));
+
+    let oauth2_authorization_code = MockOAuth2AuthorizationCodeService::new()
+        .with_redeem(code(), Some(authorization.clone()))
+        .with_verify_code_verifier(code_challenge(), code_verifier(), true);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let id = MockIdService::new().with_generate(session.id);
+    let time = MockTimeService::new().with_now(now);
+
+    let session_repo = MockSessionRepository::new().with_create(session);
+
+    let oauth2_server_token = MockOAuth2ServerTokenService::new().with_issue(
+        FOO.clone(),
+        grant.clone(),
+        authorization.nonce.clone(),
+        OAuth2ServerTokens {
+            response: expected.clone(),
+            refresh_token_hash: (*SHA256HASH1).into(),
+        },
+    );
+
+    let oauth2_server_repo =
+        MockOAuth2ServerRepository::new().with_create_grant(grant, (*SHA256HASH1).into());
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        id,
+        time,
+        user_repo,
+        session_repo,
+        oauth2_server_repo,
+        oauth2_client,
+        oauth2_authorization_code,
+        oauth2_server_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.token(credentials(), code_request()).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn invalid_client() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let oauth2_client = MockOAuth2ClientService::new().with_authenticate(
+        credentials(),
+        Err(OAuth2ClientAuthenticateError::InvalidCredentials),
+    );
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        oauth2_client,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.token(credentials(), code_request()).await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerTokenError::InvalidClient));
+}
+
+#[tokio::test]
+async fn invalid_code() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let oauth2_client = MockOAuth2ClientService::new()
+        .with_authenticate(credentials(), Ok(TEST_OAUTH2_CLIENT.clone()));
+
+    let oauth2_authorization_code =
+        MockOAuth2AuthorizationCodeService::new().with_redeem(code(), None);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        oauth2_client,
+        oauth2_authorization_code,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.token(credentials(), code_request()).await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerTokenError::InvalidGrant));
+}
+
+#[tokio::test]
+async fn code_redirect_uri_mismatch() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let oauth2_client = MockOAuth2ClientService::new()
+        .with_authenticate(credentials(), Ok(TEST_OAUTH2_CLIENT.clone()));
+
+    let oauth2_authorization_code =
+        MockOAuth2AuthorizationCodeService::new().with_redeem(code(), Some(authorization()));
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        oauth2_client,
+        oauth2_authorization_code,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .token(
+            credentials(),
+            OAuth2TokenRequest::AuthorizationCode {
+                code: code(),
+                redirect_uri: "https://client.example.com/other".parse().unwrap(),
+                code_verifier: code_verifier(),
+            },
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerTokenError::InvalidGrant));
+}
+
+#[tokio::test]
+async fn invalid_code_verifier() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let oauth2_client = MockOAuth2ClientService::new()
+        .with_authenticate(credentials(), Ok(TEST_OAUTH2_CLIENT.clone()));
+
+    let oauth2_authorization_code = MockOAuth2AuthorizationCodeService::new()
+        .with_redeem(code(), Some(authorization()))
+        .with_verify_code_verifier(code_challenge(), code_verifier(), false);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        oauth2_client,
+        oauth2_authorization_code,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.token(credentials(), code_request()).await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerTokenError::InvalidGrant));
+}
+
+#[tokio::test]
+async fn code_user_disabled() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let oauth2_client = MockOAuth2ClientService::new()
+        .with_authenticate(credentials(), Ok(TEST_OAUTH2_CLIENT.clone()));
+
+    let oauth2_authorization_code = MockOAuth2AuthorizationCodeService::new()
+        .with_redeem(
+            code(),
+            Some(OAuth2Authorization {
+                user_id: BAR.user.id,
+                ..authorization()
+            }),
+        )
+        .with_verify_code_verifier(code_challenge(), code_verifier(), true);
+
+    let user_repo = MockUserRepository::new().with_get_composite(BAR.user.id, Some(BAR.clone()));
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        user_repo,
+        oauth2_client,
+        oauth2_authorization_code,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.token(credentials(), code_request()).await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerTokenError::InvalidGrant));
+}
+
+#[tokio::test]
+async fn ok_refresh_token() {
+    // Arrange
+    let now = FOO_1.updated_at + Duration::from_secs(3600);
+    let grant = grant();
+    let expected = response(&grant);
+
+    let db = MockDatabase::build(true);
+
+    let oauth2_client = MockOAuth2ClientService::new()
+        .with_authenticate(credentials(), Ok(TEST_OAUTH2_CLIENT.clone()));
+
+    let oauth2_server_token = MockOAuth2ServerTokenService::new()
+        .with_hash_refresh_token(refresh_token(), (*SHA256HASH1).into())
+        .with_issue(
+            FOO.clone(),
+            grant.clone(),
+            None,
+            OAuth2ServerTokens {
+                response: expected.clone(),
+                refresh_token_hash: (*SHA256HASH2).into(),
+            },
+        );
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new()
+        .with_get_grant_by_refresh_token_hash((*SHA256HASH1).into(), Some(grant.clone()))
+        .with_update_grant_refresh_token_hash(FOO_1.id, (*SHA256HASH2).into());
+
+    let session_repo = MockSessionRepository::new()
+        .with_get(FOO_1.id, Some(FOO_1.clone()))
+        .with_update(FOO_1.id, SessionPatch::new().update_updated_at(now), true);
+
+    let time = MockTimeService::new().with_now(now).with_now(now);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        time,
+        user_repo,
+        session_repo,
+        oauth2_server_repo,
+        oauth2_client,
+        oauth2_server_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.token(credentials(), refresh_request()).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn invalid_refresh_token() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let oauth2_client = MockOAuth2ClientService::new()
+        .with_authenticate(credentials(), Ok(TEST_OAUTH2_CLIENT.clone()));
+
+    let oauth2_server_token = MockOAuth2ServerTokenService::new()
+        .with_hash_refresh_token(refresh_token(), (*SHA256HASH1).into());
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new()
+        .with_get_grant_by_refresh_token_hash((*SHA256HASH1).into(), None);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        oauth2_server_repo,
+        oauth2_client,
+        oauth2_server_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.token(credentials(), refresh_request()).await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerTokenError::InvalidGrant));
+}
+
+#[tokio::test]
+async fn refresh_token_of_other_client() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let oauth2_client = MockOAuth2ClientService::new()
+        .with_authenticate(credentials(), Ok(TEST_OAUTH2_CLIENT.clone()));
+
+    let oauth2_server_token = MockOAuth2ServerTokenService::new()
+        .with_hash_refresh_token(refresh_token(), (*SHA256HASH1).into());
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new()
+        .with_get_grant_by_refresh_token_hash(
+            (*SHA256HASH1).into(),
+            Some(OAuth2Grant {
+                client_id: UUID1.into(),
+                ..grant()
+            }),
+        );
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        oauth2_server_repo,
+        oauth2_client,
+        oauth2_server_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.token(credentials(), refresh_request()).await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerTokenError::InvalidGrant));
+}
+
+#[tokio::test]
+async fn refresh_token_expired() {
+    // Arrange
+    let config = OAuth2ServerFeatureConfig::default();
+    let now = FOO_1.updated_at + config.refresh_token_ttl;
+
+    let db = MockDatabase::build(false);
+
+    let oauth2_client = MockOAuth2ClientService::new()
+        .with_authenticate(credentials(), Ok(TEST_OAUTH2_CLIENT.clone()));
+
+    let oauth2_server_token = MockOAuth2ServerTokenService::new()
+        .with_hash_refresh_token(refresh_token(), (*SHA256HASH1).into());
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new()
+        .with_get_grant_by_refresh_token_hash((*SHA256HASH1).into(), Some(grant()));
+
+    let session_repo = MockSessionRepository::new().with_get(FOO_1.id, Some(FOO_1.clone()));
+
+    let time = MockTimeService::new().with_now(now);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        time,
+        session_repo,
+        oauth2_server_repo,
+        oauth2_client,
+        oauth2_server_token,
+        config,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.token(credentials(), refresh_request()).await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerTokenError::InvalidGrant));
+}
+
+fn credentials() -> OAuth2ClientCredentials {
+    OAuth2ClientCredentials {
+        client_id: TEST_OAUTH2_CLIENT.id,
+        client_secret: Some(
+            "6NvOuR4m3Mxn1E8GhBpTKE7XaN4OUZ4sVD1KSb5Kx5HdXlrGkOmlYZkUrm8GfSXq"
+                .try_into()
+                .unwrap(),
+        ),
+    }
+}
+
+fn code() -> OAuth2ServerAuthorizationCode {
+    "o6xbPG2Hk3SIRYtSn5kmFdeZ4Rj9rjv0cA6WITiGpB4a4ZgVWDFY6cXUEE2yN5kb"
+        .try_into()
+        .unwrap()
+}
+
+fn code_request() -> OAuth2TokenRequest {
+    OAuth2TokenRequest::AuthorizationCode {
+        code: code(),
+        redirect_uri: TEST_OAUTH2_CLIENT.redirect_uris[0].clone(),
+        code_verifier: code_verifier(),
+    }
+}
+
+fn refresh_request() -> OAuth2TokenRequest {
+    OAuth2TokenRequest::RefreshToken {
+        refresh_token: refresh_token(),
+    }
+}
+
+fn authorization() -> OAuth2Authorization {
+    OAuth2Authorization {
+        client_id: TEST_OAUTH2_CLIENT.id,
+        user_id: FOO.user.id,
+        redirect_uri: TEST_OAUTH2_CLIENT.redirect_uris[0].clone(),
+        scopes: [OAuth2Scope::Openid, OAuth2Scope::Email]
+            .into_iter()
+            .collect(),
+        code_challenge: code_challenge(),
+        nonce: Some("some-nonce".try_into().unwrap()),
+    }
+}
+
+fn grant() -> OAuth2Grant {
+    OAuth2Grant {
+        session_id: FOO_1.id,
+        client_id: TEST_OAUTH2_CLIENT.id,
+        scopes: [OAuth2Scope::Openid, OAuth2Scope::Email]
+            .into_iter()
+            .collect(),
+    }
+}
+
+fn response(grant: &OAuth2Grant) -> OAuth2TokenResponse {
+    OAuth2TokenResponse {
+        access_token: "access token".to_owned().into(),
+        refresh_token: refresh_token(),
+        id_token: Some("id token".to_owned().into()),
+        expires_in: OAuth2ServerFeatureConfig::default().access_token_ttl,
+        scopes: grant.scopes.clone(),
+    }
+}
@@ -0,0 +1,162 @@
+use academy_core_oauth2_server_contracts::{
+    token::MockOAuth2ServerTokenService, OAuth2ServerFeatureService, OAuth2ServerUserInfoError,
+};
+use academy_demo::{
+    oauth2_server::TEST_OAUTH2_CLIENT,
+    session::{BAR_1, FOO_1},
+    user::{BAR, FOO},
+};
+use academy_models::oauth2_server::{
+    OAuth2Grant, OAuth2Scope, OAuth2Scopes, OAuth2ServerAccessToken, OAuth2ServerUserInfo,
+    OAuth2TokenInfo,
+};
+use academy_persistence_contracts::{
+    oauth2_server::MockOAuth2ServerRepository, user::MockUserRepository, MockDatabase,
+};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, OAuth2ServerFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let scopes = [OAuth2Scope::Openid, OAuth2Scope::Email]
+        .into_iter()
+        .collect::<OAuth2Scopes>();
+
+    let oauth2_server_token = MockOAuth2ServerTokenService::new()
+        .with_verify_access_token(token(), Some(token_info(FOO.user.id, FOO_1.id, &scopes)));
+
+    let db = MockDatabase::build(false);
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new().with_get_grant(
+        FOO_1.id,
+        Some(OAuth2Grant {
+            session_id: FOO_1.id,
+            client_id: TEST_OAUTH2_CLIENT.id,
+            scopes: scopes.clone(),
+        }),
+    );
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        user_repo,
+        oauth2_server_repo,
+        oauth2_server_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.userinfo(&token()).await;
+
+    // Assert
+    assert_eq!(
+        result.unwrap(),
+        OAuth2ServerUserInfo {
+            sub: FOO.user.id,
+            preferred_username: None,
+            name: None,
+            email: FOO.user.email.clone(),
+            email_verified: Some(FOO.user.email_verified),
+        }
+    );
+}
+
+#[tokio::test]
+async fn invalid_token() {
+    // Arrange
+    let oauth2_server_token =
+        MockOAuth2ServerTokenService::new().with_verify_access_token(token(), None);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        oauth2_server_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.userinfo(&token()).await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerUserInfoError::InvalidToken));
+}
+
+#[tokio::test]
+async fn revoked() {
+    // Arrange
+    let scopes = [OAuth2Scope::Openid].into_iter().collect::<OAuth2Scopes>();
+
+    let oauth2_server_token = MockOAuth2ServerTokenService::new()
+        .with_verify_access_token(token(), Some(token_info(FOO.user.id, FOO_1.id, &scopes)));
+
+    let db = MockDatabase::build(false);
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new().with_get_grant(FOO_1.id, None);
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        oauth2_server_repo,
+        oauth2_server_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.userinfo(&token()).await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerUserInfoError::InvalidToken));
+}
+
+#[tokio::test]
+async fn user_disabled() {
+    // Arrange
+    let scopes = [OAuth2Scope::Openid].into_iter().collect::<OAuth2Scopes>();
+
+    let oauth2_server_token = MockOAuth2ServerTokenService::new()
+        .with_verify_access_token(token(), Some(token_info(BAR.user.id, BAR_1.id, &scopes)));
+
+    let db = MockDatabase::build(false);
+
+    let oauth2_server_repo = MockOAuth2ServerRepository::new().with_get_grant(
+        BAR_1.id,
+        Some(OAuth2Grant {
+            session_id: BAR_1.id,
+            client_id: TEST_OAUTH2_CLIENT.id,
+            scopes,
+        }),
+    );
+
+    let user_repo = MockUserRepository::new().with_get_composite(BAR.user.id, Some(BAR.clone()));
+
+    let sut = OAuth2ServerFeatureServiceImpl {
+        db,
+        user_repo,
+        oauth2_server_repo,
+        oauth2_server_token,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.userinfo(&token()).await;
+
+    // Assert
+    assert_matches!(result, Err(OAuth2ServerUserInfoError::InvalidToken));
+}
+
+fn token() -> OAuth2ServerAccessToken {
+    OAuth2ServerAccessToken::from("access token".to_owned())
+}
+
+fn token_info(
+    user_id: academy_models::user::UserId,
+    session_id: academy_models::session::SessionId,
+    scopes: &OAuth2Scopes,
+) -> OAuth2TokenInfo {
+    OAuth2TokenInfo {
+        user_id,
+        session_id,
+        client_id: TEST_OAUTH2_CLIENT.id,
+        scopes: scopes.clone(),
+    }
+}
@@ -0,0 +1,374 @@
+use academy_core_oauth2_server_contracts::token::{OAuth2ServerTokenService, OAuth2ServerTokens};
+use academy_di::Build;
+use academy_models::{
+    oauth2_server::{
+        OAuth2ClientId, OAuth2ClientNonce, OAuth2Grant, OAuth2Scope, OAuth2ServerAccessToken,
+        OAuth2ServerRefreshToken, OAuth2ServerRefreshTokenHash, OAuth2ServerUserInfo,
+        OAuth2TokenInfo, OAuth2TokenResponse,
+    },
+    session::SessionId,
+    url::Url,
+    user::{UserComposite, UserId},
+};
+use academy_shared_contracts::{
+    hash::HashService, jwt::JwtService, secret::SecretService, time::TimeService,
+};
+use academy_utils::trace_instrument;
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::OAuth2ServerFeatureConfig;
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct OAuth2ServerTokenServiceImpl<Jwt, Secret, Hash, Time> {
+    jwt: Jwt,
+    secret: Secret,
+    hash: Hash,
+    time: Time,
+    config: OAuth2ServerFeatureConfig,
+}
+
+impl<Jwt, Secret, Hash, Time> OAuth2ServerTokenService
+    for OAuth2ServerTokenServiceImpl<Jwt, Secret, Hash, Time>
+where
+    Jwt: JwtService,
+    Secret: SecretService,
+    Hash: HashService,
+    Time: TimeService,
+{
+    #[trace_instrument(skip(self))]
+    fn issue(
+        &self,
+        user_composite: &UserComposite,
+        grant: &OAuth2Grant,
+        nonce: Option<OAuth2ClientNonce>,
+    ) -> anyhow::Result<OAuth2ServerTokens> {
+        let access_token = self
+            .jwt
+            .sign(
+                AccessTokenClaims {
+                    sub: user_composite.user.id,
+                    sid: grant.session_id,
+                    cid: grant.client_id,
+                    scope: grant.scopes.to_string(),
+                },
+                self.config.access_token_ttl,
+            )
+            .context("Failed to sign OAuth2 access token")?;
+
+        let id_token = grant
+            .scopes
+            .contains(OAuth2Scope::Openid)
+            .then(|| {
+                self.jwt
+                    .sign(
+                        IdTokenClaims {
+                            iss: self.config.issuer.clone(),
+                            aud: grant.client_id,
+                            iat: self.time.now().timestamp(),
+                            nonce,
+                            userinfo: OAuth2ServerUserInfo::new(user_composite, &grant.scopes),
+                        },
+                        self.config.access_token_ttl,
+                    )
+                    .context("Failed to sign OAuth2 ID token")
+            })
+            .transpose()?;
+
+        let refresh_token = OAuth2ServerRefreshToken::try_new(
+            self.secret.generate(OAuth2ServerRefreshToken::LEN).0,
+        )
+        .unwrap();
+        let refresh_token_hash = self.hash_refresh_token(&refresh_token);
+
+        Ok(OAuth2ServerTokens {
+            response: OAuth2TokenResponse {
+                access_token,
+                refresh_token,
+                id_token,
+                expires_in: self.config.access_token_ttl,
+                scopes: grant.scopes.clone(),
+            },
+            refresh_token_hash,
+        })
+    }
+
+    #[trace_instrument(skip(self))]
+    fn verify_access_token(
+        &self,
+        access_token: &OAuth2ServerAccessToken,
+    ) -> Option<OAuth2TokenInfo> {
+        let claims = self.jwt.verify::<_, AccessTokenClaims>(access_token).ok()?;
+
+        Some(OAuth2TokenInfo {
+            user_id: claims.sub,
+            session_id: claims.sid,
+            client_id: claims.cid,
+            scopes: claims.scope.parse().ok()?,
+        })
+    }
+
+    #[trace_instrument(skip(self))]
+    fn hash_refresh_token(
+        &self,
+        refresh_token: &OAuth2ServerRefreshToken,
+    ) -> OAuth2ServerRefreshTokenHash {
+        self.hash.sha256(refresh_token).into()
+    }
+}
+
+/// The claims of an OAuth2 access token. These intentionally differ from the
+/// claims of our own access tokens, so that one cannot be used in place of
+/// the other.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct AccessTokenClaims {
+    sub: UserId,
+    sid: SessionId,
+    cid: OAuth2ClientId,
+    scope: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct IdTokenClaims {
+    iss: Url,
+    aud: OAuth2ClientId,
+    iat: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nonce: Option<OAuth2ClientNonce>,
+    #[serde(flatten)]
+    userinfo: OAuth2ServerUserInfo,
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_demo::{oauth2_server::TEST_OAUTH2_CLIENT, session::FOO_1, user::FOO, SHA256HASH1};
+    use academy_models::oauth2_server::{OAuth2Scopes, OAuth2ServerIdToken};
+    use academy_shared_contracts::{
+        hash::MockHashService, jwt::MockJwtService, secret::MockSecretService,
+        time::MockTimeService,
+    };
+
+    use super::*;
+
+    type Sut = OAuth2ServerTokenServiceImpl<
+        MockJwtService,
+        MockSecretService,
+        MockHashService,
+        MockTimeService,
+    >;
+
+    #[test]
+    fn issue_with_id_token() {
+        // Arrange
+        let config = OAuth2ServerFeatureConfig::default();
+        let grant = grant(
+            [OAuth2Scope::Openid, OAuth2Scope::Email]
+                .into_iter()
+                .collect(),
+        );
+        let nonce = OAuth2ClientNonce::try_new("nonce").unwrap();
+        let refresh_token = refresh_token();
+
+        let jwt = MockJwtService::new()
+            .with_sign(
+                AccessTokenClaims {
+                    sub: FOO.user.id,
+                    sid: FOO_1.id,
+                    cid: TEST_OAUTH2_CLIENT.id,
+                    scope: "openid email".into(),
+                },
+                config.access_token_ttl,
+                Ok(OAuth2ServerAccessToken::from("access token".to_owned())),
+            )
+            .with_sign(
+                IdTokenClaims {
+                    iss: config.issuer.clone(),
+                    aud: TEST_OAUTH2_CLIENT.id,
+                    iat: FOO.user.created_at.timestamp(),
+                    nonce: Some(nonce.clone()),
+                    userinfo: OAuth2ServerUserInfo::new(&FOO, &grant.scopes),
+                },
+                config.access_token_ttl,
+                Ok(OAuth2ServerIdToken::from("id token".to_owned())),
+            );
+
+        let secret = MockSecretService::new().with_generate(
+            OAuth2ServerRefreshToken::LEN,
+            refresh_token.clone().into_inner(),
+        );
+
+        let hash = MockHashService::new().with_sha256(refresh_token.clone(), *SHA256HASH1);
+
+        let time = MockTimeService::new().with_now(FOO.user.created_at);
+
+        let sut = OAuth2ServerTokenServiceImpl {
+            jwt,
+            secret,
+            hash,
+            time,
+            config: config.clone(),
+        };
+
+        // Act
+        let result = sut.issue(&FOO, &grant, Some(nonce));
+
+        // Assert
+        assert_eq!(
+            result.unwrap(),
+            OAuth2ServerTokens {
+                response: OAuth2TokenResponse {
+                    access_token: "access token".to_owned().into(),
+                    refresh_token,
+                    id_token: Some("id token".to_owned().into()),
+                    expires_in: config.access_token_ttl,
+                    scopes: grant.scopes,
+                },
+                refresh_token_hash: (*SHA256HASH1).into(),
+            }
+        );
+    }
+
+    #[test]
+    fn issue_without_id_token() {
+        // Arrange
+        let config = OAuth2ServerFeatureConfig::default();
+        let grant = grant([OAuth2Scope::Profile].into_iter().collect());
+        let refresh_token = refresh_token();
+
+        let jwt = MockJwtService::new().with_sign(
+            AccessTokenClaims {
+                sub: FOO.user.id,
+                sid: FOO_1.id,
+                cid: TEST_OAUTH2_CLIENT.id,
+                scope: "profile".into(),
+            },
+            config.access_token_ttl,
+            Ok(OAuth2ServerAccessToken::from("access token".to_owned())),
+        );
+
+        let secret = MockSecretService::new().with_generate(
+            OAuth2ServerRefreshToken::LEN,
+            refresh_token.clone().into_inner(),
+        );
+
+        let hash = MockHashService::new().with_sha256(refresh_token.clone(), *SHA256HASH1);
+
+        let sut = OAuth2ServerTokenServiceImpl {
+            jwt,
+            secret,
+            hash,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.issue(&FOO, &grant, None);
+
+        // Assert
+        assert_eq!(
+            result.unwrap(),
+            OAuth2ServerTokens {
+                response: OAuth2TokenResponse {
+                    access_token: "access token".to_owned().into(),
+                    refresh_token,
+                    id_token: None,
+                    expires_in: config.access_token_ttl,
+                    scopes: grant.scopes,
+                },
+                refresh_token_hash: (*SHA256HASH1).into(),
+            }
+        );
+    }
+
+    #[test]
+    fn verify_access_token_ok() {
+        // Arrange
+        let token = OAuth2ServerAccessToken::from("access token".to_owned());
+
+        let jwt = MockJwtService::new().with_verify(
+            token.clone(),
+            Ok(AccessTokenClaims {
+                sub: FOO.user.id,
+                sid: FOO_1.id,
+                cid: TEST_OAUTH2_CLIENT.id,
+                scope: "openid profile".into(),
+            }),
+        );
+
+        let sut = OAuth2ServerTokenServiceImpl {
+            jwt,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.verify_access_token(&token);
+
+        // Assert
+        assert_eq!(
+            result.unwrap(),
+            OAuth2TokenInfo {
+                user_id: FOO.user.id,
+                session_id: FOO_1.id,
+                client_id: TEST_OAUTH2_CLIENT.id,
+                scopes: [OAuth2Scope::Openid, OAuth2Scope::Profile]
+                    .into_iter()
+                    .collect(),
+            }
+        );
+    }
+
+    #[test]
+    fn verify_access_token_invalid() {
+        // Arrange
+        let token = OAuth2ServerAccessToken::from("access token".to_owned());
+
+        let jwt = MockJwtService::new().with_verify::<_, AccessTokenClaims>(
+            token.clone(),
+            Err(academy_shared_contracts::jwt::VerifyJwtError::Invalid),
+        );
+
+        let sut = OAuth2ServerTokenServiceImpl {
+            jwt,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.verify_access_token(&token);
+
+        // Assert
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn hash_refresh_token() {
+        // Arrange
+        let refresh_token = refresh_token();
+
+        let hash = MockHashService::new().with_sha256(refresh_token.clone(), *SHA256HASH1);
+
+        let sut = OAuth2ServerTokenServiceImpl {
+            hash,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.hash_refresh_token(&refresh_token);
+
+        // Assert
+        assert_eq!(result, (*SHA256HASH1).into());
+    }
+
+    fn grant(scopes: OAuth2Scopes) -> OAuth2Grant {
+        OAuth2Grant {
+            session_id: FOO_1.id,
+            client_id: TEST_OAUTH2_CLIENT.id,
+            scopes,
+        }
+    }
+
+    fn refresh_token() -> OAuth2ServerRefreshToken {
+        "BLfbrQj5YU0uVixnJrg9pH5eOiSfSDpzsxXTjxKJ3ZTq7GAedFvNI4nGuJhPbxG9"
+            .try_into()
+            .unwrap()
+    }
+}
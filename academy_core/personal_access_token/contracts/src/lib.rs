@@ -0,0 +1,94 @@
+use std::{collections::BTreeSet, future::Future};
+
+use academy_models::{
+    audit::AuditContext,
+    auth::{AccessToken, AuthError},
+    personal_access_token::{
+        PersonalAccessToken, PersonalAccessTokenId, PersonalAccessTokenName,
+        PersonalAccessTokenScope,
+    },
+    user::UserIdOrSelf,
+};
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+pub trait PersonalAccessTokenFeatureService: Send + Sync + 'static {
+    /// Return all personal access tokens of the given user.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    fn list_tokens(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+    ) -> impl Future<Output = Result<Vec<PersonalAccessToken>, PersonalAccessTokenListError>> + Send;
+
+    /// Create a new personal access token for the given user.
+    ///
+    /// The token itself is only returned once. Can only be used on the
+    /// authenticated user and requires a session (i.e. cannot be used with
+    /// another personal access token). The `admin` scope can only be requested
+    /// by administrators and users who have been granted any role permission.
+    fn create_token(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        request: PersonalAccessTokenCreateRequest,
+        ctx: &AuditContext,
+    ) -> impl Future<
+        Output = Result<PersonalAccessTokenCreateResponse, PersonalAccessTokenCreateError>,
+    > + Send;
+
+    /// Revoke the given personal access token.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    fn delete_token(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        token_id: PersonalAccessTokenId,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<(), PersonalAccessTokenDeleteError>> + Send;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonalAccessTokenCreateRequest {
+    pub name: PersonalAccessTokenName,
+    pub scopes: BTreeSet<PersonalAccessTokenScope>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonalAccessTokenCreateResponse {
+    pub personal_access_token: PersonalAccessToken,
+    pub token: AccessToken,
+}
+
+#[derive(Debug, Error)]
+pub enum PersonalAccessTokenListError {
+    #[error("The user does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum PersonalAccessTokenCreateError {
+    #[error("The expiry date is in the past.")]
+    InvalidExpiry,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum PersonalAccessTokenDeleteError {
+    #[error("The personal access token does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
@@ -0,0 +1,207 @@
+use academy_auth_contracts::{
+    personal_access_token::AuthPersonalAccessTokenService, AuthResultExt, AuthService,
+};
+use academy_core_audit_contracts::audit::AuditService;
+use academy_core_personal_access_token_contracts::{
+    PersonalAccessTokenCreateError, PersonalAccessTokenCreateRequest,
+    PersonalAccessTokenCreateResponse, PersonalAccessTokenDeleteError,
+    PersonalAccessTokenFeatureService, PersonalAccessTokenListError,
+};
+use academy_di::Build;
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AccessToken, AuthError, AuthorizeError},
+    personal_access_token::{PersonalAccessToken, PersonalAccessTokenId, PersonalAccessTokenScope},
+    user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{
+    personal_access_token::PersonalAccessTokenRepository, user::UserRepository, Database,
+    Transaction,
+};
+use academy_shared_contracts::{id::IdService, time::TimeService};
+use academy_utils::trace_instrument;
+use anyhow::Context;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct PersonalAccessTokenFeatureServiceImpl<
+    Db,
+    Auth,
+    Id,
+    Time,
+    AuthPersonalAccessToken,
+    UserRepo,
+    PersonalAccessTokenRepo,
+    Audit,
+> {
+    db: Db,
+    auth: Auth,
+    id: Id,
+    time: Time,
+    auth_personal_access_token: AuthPersonalAccessToken,
+    user_repo: UserRepo,
+    personal_access_token_repo: PersonalAccessTokenRepo,
+    audit: Audit,
+}
+
+impl<Db, Auth, Id, Time, AuthPersonalAccessToken, UserRepo, PersonalAccessTokenRepo, Audit>
+    PersonalAccessTokenFeatureService
+    for PersonalAccessTokenFeatureServiceImpl<
+        Db,
+        Auth,
+        Id,
+        Time,
+        AuthPersonalAccessToken,
+        UserRepo,
+        PersonalAccessTokenRepo,
+        Audit,
+    >
+where
+    Db: Database,
+    Auth: AuthService<Db::Transaction>,
+    Id: IdService,
+    Time: TimeService,
+    AuthPersonalAccessToken: AuthPersonalAccessTokenService,
+    UserRepo: UserRepository<Db::Transaction>,
+    PersonalAccessTokenRepo: PersonalAccessTokenRepository<Db::Transaction>,
+    Audit: AuditService<Db::Transaction>,
+{
+    #[trace_instrument(skip(self))]
+    async fn list_tokens(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+    ) -> Result<Vec<PersonalAccessToken>, PersonalAccessTokenListError> {
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        if !self
+            .user_repo
+            .exists(&mut txn, user_id)
+            .await
+            .context("Failed to check user existence")?
+        {
+            return Err(PersonalAccessTokenListError::NotFound);
+        }
+
+        self.personal_access_token_repo
+            .list_by_user(&mut txn, user_id)
+            .await
+            .context("Failed to get personal access tokens from database")
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn create_token(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        request: PersonalAccessTokenCreateRequest,
+        ctx: &AuditContext,
+    ) -> Result<PersonalAccessTokenCreateResponse, PersonalAccessTokenCreateError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        if user_id.unwrap_or(auth.user_id) != auth.user_id {
+            return Err(AuthError::Authorize(AuthorizeError::Admin).into());
+        }
+        auth.ensure_session().map_auth_err()?;
+        if request.scopes.contains(&PersonalAccessTokenScope::Admin)
+            && !auth.admin
+            && auth.permissions.is_empty()
+        {
+            return Err(AuthError::Authorize(AuthorizeError::Admin).into());
+        }
+
+        let now = self.time.now();
+        if request
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= now)
+        {
+            return Err(PersonalAccessTokenCreateError::InvalidExpiry);
+        }
+
+        let (token, hash) = self.auth_personal_access_token.issue();
+        let personal_access_token = PersonalAccessToken {
+            id: self.id.generate(),
+            user_id: auth.user_id,
+            name: request.name,
+            scopes: request.scopes,
+            created_at: now,
+            expires_at: request.expires_at,
+            last_used_at: None,
+        };
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        self.personal_access_token_repo
+            .create(&mut txn, &personal_access_token, hash)
+            .await
+            .context("Failed to create personal access token in database")?;
+
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                auth.user_id,
+                AuditAction::PersonalAccessTokenCreate,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        Ok(PersonalAccessTokenCreateResponse {
+            personal_access_token,
+            token,
+        })
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn delete_token(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        token_id: PersonalAccessTokenId,
+        ctx: &AuditContext,
+    ) -> Result<(), PersonalAccessTokenDeleteError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let personal_access_token = self
+            .personal_access_token_repo
+            .get(&mut txn, token_id)
+            .await
+            .context("Failed to get personal access token from database")?
+            .filter(|personal_access_token| personal_access_token.user_id == user_id)
+            .ok_or(PersonalAccessTokenDeleteError::NotFound)?;
+
+        self.personal_access_token_repo
+            .delete(&mut txn, personal_access_token.id)
+            .await
+            .context("Failed to delete personal access token from database")?;
+
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::PersonalAccessTokenDelete,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,373 @@
+use academy_core_audit_contracts::audit::MockAuditService;
+use std::time::Duration;
+
+use academy_auth_contracts::{
+    personal_access_token::MockAuthPersonalAccessTokenService, MockAuthService,
+};
+use academy_core_personal_access_token_contracts::{
+    PersonalAccessTokenCreateError, PersonalAccessTokenCreateRequest,
+    PersonalAccessTokenCreateResponse, PersonalAccessTokenFeatureService,
+};
+use academy_demo::{
+    personal_access_token::{ADMIN_PAT, FOO_PAT},
+    session::{ADMIN_1, FOO_1},
+    user::{ADMIN, FOO},
+    SHA256HASH1,
+};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthenticateError, AuthorizeError},
+    personal_access_token::{PersonalAccessToken, PersonalAccessTokenScope},
+    role::Permission,
+    user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{
+    personal_access_token::MockPersonalAccessTokenRepository, MockDatabase,
+};
+use academy_shared_contracts::{id::MockIdService, time::MockTimeService};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, PersonalAccessTokenFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let expected = PersonalAccessToken {
+        last_used_at: None,
+        ..FOO_PAT.clone()
+    };
+
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let id = MockIdService::new().with_generate(FOO_PAT.id);
+
+    let time = MockTimeService::new().with_now(FOO_PAT.created_at);
+
+    let auth_personal_access_token = MockAuthPersonalAccessTokenService::new()
+        .with_issue("bapat_secret".into(), (*SHA256HASH1).into());
+
+    let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+        .with_create(expected.clone(), (*SHA256HASH1).into());
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::PersonalAccessTokenCreate,
+    );
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        db,
+        auth,
+        id,
+        time,
+        auth_personal_access_token,
+        personal_access_token_repo,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_token(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            PersonalAccessTokenCreateRequest {
+                name: FOO_PAT.name.clone(),
+                scopes: FOO_PAT.scopes.clone(),
+                expires_at: FOO_PAT.expires_at,
+            },
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(
+        result.unwrap(),
+        PersonalAccessTokenCreateResponse {
+            personal_access_token: expected,
+            token: "bapat_secret".into(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn ok_admin_scope() {
+    // Arrange
+    let expected = PersonalAccessToken {
+        last_used_at: None,
+        ..ADMIN_PAT.clone()
+    };
+
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let id = MockIdService::new().with_generate(ADMIN_PAT.id);
+
+    let time = MockTimeService::new().with_now(ADMIN_PAT.created_at);
+
+    let auth_personal_access_token = MockAuthPersonalAccessTokenService::new()
+        .with_issue("bapat_secret".into(), (*SHA256HASH1).into());
+
+    let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+        .with_create(expected.clone(), (*SHA256HASH1).into());
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        ADMIN.user.id,
+        AuditAction::PersonalAccessTokenCreate,
+    );
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        db,
+        auth,
+        id,
+        time,
+        auth_personal_access_token,
+        personal_access_token_repo,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_token(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            PersonalAccessTokenCreateRequest {
+                name: ADMIN_PAT.name.clone(),
+                scopes: [PersonalAccessTokenScope::Admin].into(),
+                expires_at: None,
+            },
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap().personal_access_token, expected);
+}
+
+#[tokio::test]
+async fn ok_admin_scope_role_permissions() {
+    // Arrange
+    let expected = PersonalAccessToken {
+        scopes: [PersonalAccessTokenScope::Admin].into(),
+        last_used_at: None,
+        ..FOO_PAT.clone()
+    };
+
+    let auth = MockAuthService::new().with_authenticate_permissions(
+        FOO.user.clone(),
+        FOO_1.clone(),
+        [Permission::ReadUsers].into(),
+    );
+
+    let db = MockDatabase::build(true);
+
+    let id = MockIdService::new().with_generate(FOO_PAT.id);
+
+    let time = MockTimeService::new().with_now(FOO_PAT.created_at);
+
+    let auth_personal_access_token = MockAuthPersonalAccessTokenService::new()
+        .with_issue("bapat_secret".into(), (*SHA256HASH1).into());
+
+    let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+        .with_create(expected.clone(), (*SHA256HASH1).into());
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::PersonalAccessTokenCreate,
+    );
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        db,
+        auth,
+        id,
+        time,
+        auth_personal_access_token,
+        personal_access_token_repo,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_token(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            PersonalAccessTokenCreateRequest {
+                name: FOO_PAT.name.clone(),
+                scopes: [PersonalAccessTokenScope::Admin].into(),
+                expires_at: FOO_PAT.expires_at,
+            },
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap().personal_access_token, expected);
+}
+
+#[tokio::test]
+async fn unauthenticated() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(None);
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_token(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            request(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(PersonalAccessTokenCreateError::Auth(
+            AuthError::Authenticate(AuthenticateError::InvalidToken)
+        ))
+    );
+}
+
+#[tokio::test]
+async fn other_user() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_token(
+            &"token".into(),
+            FOO.user.id.into(),
+            request(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(PersonalAccessTokenCreateError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn personal_access_token_not_allowed() {
+    // Arrange
+    let auth = MockAuthService::new()
+        .with_authenticate_personal_access_token(Some((FOO.user.clone(), FOO_PAT.clone())));
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_token(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            request(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(PersonalAccessTokenCreateError::Auth(AuthError::Authorize(
+            AuthorizeError::Session
+        )))
+    );
+}
+
+#[tokio::test]
+async fn admin_scope_unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_token(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            PersonalAccessTokenCreateRequest {
+                scopes: [PersonalAccessTokenScope::Admin].into(),
+                ..request()
+            },
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(PersonalAccessTokenCreateError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn invalid_expiry() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let time =
+        MockTimeService::new().with_now(FOO_PAT.expires_at.unwrap() + Duration::from_secs(1));
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        auth,
+        time,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_token(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            request(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(PersonalAccessTokenCreateError::InvalidExpiry));
+}
+
+fn request() -> PersonalAccessTokenCreateRequest {
+    PersonalAccessTokenCreateRequest {
+        name: FOO_PAT.name.clone(),
+        scopes: FOO_PAT.scopes.clone(),
+        expires_at: FOO_PAT.expires_at,
+    }
+}
@@ -0,0 +1,223 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_personal_access_token_contracts::{
+    PersonalAccessTokenDeleteError, PersonalAccessTokenFeatureService,
+};
+use academy_demo::{
+    personal_access_token::{ADMIN_PAT, FOO_PAT},
+    session::{ADMIN_1, BAR_1, FOO_1},
+    user::{ADMIN, BAR, FOO},
+};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthenticateError, AuthorizeError},
+    user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{
+    personal_access_token::MockPersonalAccessTokenRepository, MockDatabase,
+};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, PersonalAccessTokenFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+        .with_get(FOO_PAT.id, Some(FOO_PAT.clone()))
+        .with_delete(FOO_PAT.id, true);
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::PersonalAccessTokenDelete,
+    );
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        db,
+        auth,
+        personal_access_token_repo,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_token(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            FOO_PAT.id,
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn ok_with_personal_access_token() {
+    // Arrange
+    let auth = MockAuthService::new()
+        .with_authenticate_personal_access_token(Some((FOO.user.clone(), FOO_PAT.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+        .with_get(FOO_PAT.id, Some(FOO_PAT.clone()))
+        .with_delete(FOO_PAT.id, true);
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::PersonalAccessTokenDelete,
+    );
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        db,
+        auth,
+        personal_access_token_repo,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_token(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            FOO_PAT.id,
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn unauthenticated() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(None);
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_token(
+            &"token".into(),
+            FOO.user.id.into(),
+            FOO_PAT.id,
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(PersonalAccessTokenDeleteError::Auth(
+            AuthError::Authenticate(AuthenticateError::InvalidToken)
+        ))
+    );
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_token(
+            &"token".into(),
+            FOO.user.id.into(),
+            FOO_PAT.id,
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(PersonalAccessTokenDeleteError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let personal_access_token_repo =
+        MockPersonalAccessTokenRepository::new().with_get(FOO_PAT.id, None);
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        db,
+        auth,
+        personal_access_token_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_token(
+            &"token".into(),
+            FOO.user.id.into(),
+            FOO_PAT.id,
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(PersonalAccessTokenDeleteError::NotFound));
+}
+
+#[tokio::test]
+async fn user_id_mismatch() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let personal_access_token_repo =
+        MockPersonalAccessTokenRepository::new().with_get(ADMIN_PAT.id, Some(ADMIN_PAT.clone()));
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        db,
+        auth,
+        personal_access_token_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_token(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            ADMIN_PAT.id,
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(PersonalAccessTokenDeleteError::NotFound));
+}
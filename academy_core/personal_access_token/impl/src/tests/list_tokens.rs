@@ -0,0 +1,145 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_personal_access_token_contracts::{
+    PersonalAccessTokenFeatureService, PersonalAccessTokenListError,
+};
+use academy_demo::{
+    personal_access_token::FOO_PAT,
+    session::{ADMIN_1, BAR_1, FOO_1},
+    user::{ADMIN, BAR, FOO},
+};
+use academy_models::{
+    auth::{AuthError, AuthenticateError, AuthorizeError},
+    user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{
+    personal_access_token::MockPersonalAccessTokenRepository, user::MockUserRepository,
+    MockDatabase,
+};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, PersonalAccessTokenFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_exists(FOO.user.id, true);
+
+    let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+        .with_list_by_user(FOO.user.id, vec![FOO_PAT.clone()]);
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        personal_access_token_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.list_tokens(&"token".into(), UserIdOrSelf::Slf).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), vec![FOO_PAT.clone()]);
+}
+
+#[tokio::test]
+async fn ok_admin() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_exists(FOO.user.id, true);
+
+    let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+        .with_list_by_user(FOO.user.id, vec![FOO_PAT.clone()]);
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        personal_access_token_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.list_tokens(&"token".into(), FOO.user.id.into()).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), vec![FOO_PAT.clone()]);
+}
+
+#[tokio::test]
+async fn unauthenticated() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate_read(None);
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.list_tokens(&"token".into(), FOO.user.id.into()).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(PersonalAccessTokenListError::Auth(AuthError::Authenticate(
+            AuthenticateError::InvalidToken
+        )))
+    );
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.list_tokens(&"token".into(), FOO.user.id.into()).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(PersonalAccessTokenListError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_exists(FOO.user.id, false);
+
+    let sut = PersonalAccessTokenFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.list_tokens(&"token".into(), FOO.user.id.into()).await;
+
+    // Assert
+    assert_matches!(result, Err(PersonalAccessTokenListError::NotFound));
+}
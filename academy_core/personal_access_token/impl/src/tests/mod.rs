@@ -0,0 +1,26 @@
+use academy_auth_contracts::{
+    personal_access_token::MockAuthPersonalAccessTokenService, MockAuthService,
+};
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_persistence_contracts::{
+    personal_access_token::MockPersonalAccessTokenRepository, user::MockUserRepository,
+    MockDatabase, MockTransaction,
+};
+use academy_shared_contracts::{id::MockIdService, time::MockTimeService};
+
+use crate::PersonalAccessTokenFeatureServiceImpl;
+
+mod create_token;
+mod delete_token;
+mod list_tokens;
+
+type Sut = PersonalAccessTokenFeatureServiceImpl<
+    MockDatabase,
+    MockAuthService<MockTransaction>,
+    MockIdService,
+    MockTimeService,
+    MockAuthPersonalAccessTokenService,
+    MockUserRepository<MockTransaction>,
+    MockPersonalAccessTokenRepository<MockTransaction>,
+    MockAuditService<MockTransaction>,
+>;
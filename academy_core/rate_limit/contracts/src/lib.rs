@@ -0,0 +1,25 @@
+use std::{future::Future, net::IpAddr, time::Duration};
+
+use academy_models::{auth::AccessToken, rate_limit::RateLimitGroup};
+use thiserror::Error;
+
+pub trait RateLimitFeatureService: Send + Sync + 'static {
+    /// Count a request to an endpoint of the given group and check whether the
+    /// configured rate limit has been exceeded.
+    ///
+    /// Groups without a configured policy are not limited.
+    fn check(
+        &self,
+        group: RateLimitGroup,
+        ip: IpAddr,
+        token: Option<&AccessToken>,
+    ) -> impl Future<Output = Result<(), RateLimitCheckError>> + Send;
+}
+
+#[derive(Debug, Error)]
+pub enum RateLimitCheckError {
+    #[error("Rate limit exceeded. Retry after {retry_after:?}.")]
+    Exceeded { retry_after: Duration },
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
@@ -0,0 +1,510 @@
+use std::{collections::HashMap, net::IpAddr, sync::Arc, time::Duration};
+
+use academy_auth_contracts::{
+    access_token::AuthAccessTokenService, personal_access_token::AuthPersonalAccessTokenService,
+};
+use academy_cache_contracts::CacheService;
+use academy_core_rate_limit_contracts::{RateLimitCheckError, RateLimitFeatureService};
+use academy_di::Build;
+use academy_models::{
+    auth::AccessToken,
+    rate_limit::{RateLimitGroup, RateLimitKey},
+    user::UserId,
+};
+use academy_persistence_contracts::{
+    personal_access_token::PersonalAccessTokenRepository, Database,
+};
+use academy_shared_contracts::time::TimeService;
+use academy_utils::trace_instrument;
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct RateLimitFeatureServiceImpl<
+    Db,
+    Time,
+    AuthAccessToken,
+    AuthPersonalAccessToken,
+    Cache,
+    PersonalAccessTokenRepo,
+> {
+    db: Db,
+    time: Time,
+    auth_access_token: AuthAccessToken,
+    auth_personal_access_token: AuthPersonalAccessToken,
+    cache: Cache,
+    personal_access_token_repo: PersonalAccessTokenRepo,
+    config: RateLimitFeatureConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitFeatureConfig {
+    pub policies: Arc<HashMap<RateLimitGroup, RateLimitPolicy>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitPolicy {
+    /// Maximum number of requests allowed within one window.
+    pub requests: u64,
+    pub window: Duration,
+    pub key: RateLimitKey,
+}
+
+impl<Db, Time, AuthAccessToken, AuthPersonalAccessToken, Cache, PersonalAccessTokenRepo>
+    RateLimitFeatureService
+    for RateLimitFeatureServiceImpl<
+        Db,
+        Time,
+        AuthAccessToken,
+        AuthPersonalAccessToken,
+        Cache,
+        PersonalAccessTokenRepo,
+    >
+where
+    Db: Database,
+    Time: TimeService,
+    AuthAccessToken: AuthAccessTokenService,
+    AuthPersonalAccessToken: AuthPersonalAccessTokenService,
+    Cache: CacheService,
+    PersonalAccessTokenRepo: PersonalAccessTokenRepository<Db::Transaction>,
+{
+    #[trace_instrument(skip(self))]
+    async fn check(
+        &self,
+        group: RateLimitGroup,
+        ip: IpAddr,
+        token: Option<&AccessToken>,
+    ) -> Result<(), RateLimitCheckError> {
+        let Some(policy) = self.config.policies.get(&group) else {
+            return Ok(());
+        };
+
+        let now = self.time.now();
+
+        let subject = match policy.key {
+            RateLimitKey::Ip => format!("ip:{ip}"),
+            RateLimitKey::User => match self.user_id(token, now).await? {
+                Some(user_id) => format!("user:{}", user_id.hyphenated()),
+                None => format!("ip:{ip}"),
+            },
+            RateLimitKey::Route => "route".into(),
+        };
+        let cache_key = |window: u64| format!("rate_limit:{}:{subject}:{window}", group.as_str());
+
+        // Sliding window approximation: the requests in the previous window are
+        // weighted by how much of it still overlaps with the sliding window
+        // that ends now.
+        let window_ms = (policy.window.as_millis() as u64).max(1);
+        let now_ms = now.timestamp_millis().max(0) as u64;
+        let window = now_ms / window_ms;
+        let elapsed_ms = now_ms % window_ms;
+
+        let current = self
+            .cache
+            .increment(&cache_key(window), Some(policy.window * 2))
+            .await
+            .context("Failed to increment rate limit counter")?;
+
+        let previous = match window.checked_sub(1) {
+            Some(previous_window) => self
+                .cache
+                .get_counter(&cache_key(previous_window))
+                .await
+                .context("Failed to get rate limit counter")?,
+            None => 0,
+        };
+
+        let remaining_ms = window_ms - elapsed_ms;
+        let estimate = current
+            .saturating_add((previous as u128 * remaining_ms as u128 / window_ms as u128) as u64);
+
+        if estimate > policy.requests {
+            return Err(RateLimitCheckError::Exceeded {
+                retry_after: Duration::from_millis(remaining_ms),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<Db, Time, AuthAccessToken, AuthPersonalAccessToken, Cache, PersonalAccessTokenRepo>
+    RateLimitFeatureServiceImpl<
+        Db,
+        Time,
+        AuthAccessToken,
+        AuthPersonalAccessToken,
+        Cache,
+        PersonalAccessTokenRepo,
+    >
+where
+    Db: Database,
+    AuthAccessToken: AuthAccessTokenService,
+    AuthPersonalAccessToken: AuthPersonalAccessTokenService,
+    PersonalAccessTokenRepo: PersonalAccessTokenRepository<Db::Transaction>,
+{
+    /// Return the id of the user the given access token or personal access
+    /// token belongs to, or `None` if the token is missing or invalid.
+    async fn user_id(
+        &self,
+        token: Option<&AccessToken>,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<Option<UserId>> {
+        let Some(token) = token else {
+            return Ok(None);
+        };
+
+        let Some(hash) = self.auth_personal_access_token.hash(token) else {
+            return Ok(self
+                .auth_access_token
+                .verify(token)
+                .map(|auth| auth.user_id));
+        };
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        Ok(self
+            .personal_access_token_repo
+            .get_by_hash(&mut txn, hash)
+            .await
+            .context("Failed to get personal access token from database")?
+            .filter(|token| !token.is_expired(now))
+            .map(|token| token.user_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_auth_contracts::{
+        access_token::MockAuthAccessTokenService,
+        personal_access_token::MockAuthPersonalAccessTokenService, Authentication,
+        AuthenticationSource,
+    };
+    use academy_cache_contracts::MockCacheService;
+    use academy_demo::{personal_access_token::FOO_PAT, user::FOO, SHA256HASH1, UUID1};
+    use academy_persistence_contracts::{
+        personal_access_token::MockPersonalAccessTokenRepository, MockDatabase, MockTransaction,
+    };
+    use academy_shared_contracts::time::MockTimeService;
+    use academy_utils::assert_matches;
+
+    use super::*;
+
+    type Sut = RateLimitFeatureServiceImpl<
+        MockDatabase,
+        MockTimeService,
+        MockAuthAccessTokenService,
+        MockAuthPersonalAccessTokenService,
+        MockCacheService,
+        MockPersonalAccessTokenRepository<MockTransaction>,
+    >;
+
+    const IP: IpAddr = IpAddr::V4(std::net::Ipv4Addr::new(1, 2, 3, 4));
+
+    #[tokio::test]
+    async fn ok_ip() {
+        // Arrange
+        let time = MockTimeService::new().with_now(time(42 * 60 + 15));
+
+        let cache = MockCacheService::new()
+            .with_increment(
+                "rate_limit:signup:ip:1.2.3.4:42".into(),
+                Some(Duration::from_secs(120)),
+                3,
+            )
+            .with_get_counter("rate_limit:signup:ip:1.2.3.4:41".into(), 8);
+
+        let sut = RateLimitFeatureServiceImpl {
+            time,
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.check(RateLimitGroup::Signup, IP, None).await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn ok_unlimited() {
+        // Arrange
+        let sut = Sut::default();
+
+        // Act
+        let result = sut.check(RateLimitGroup::Contact, IP, None).await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn ok_user() {
+        // Arrange
+        let time = MockTimeService::new().with_now(time(3600));
+
+        let auth_access_token = MockAuthAccessTokenService::new()
+            .with_verify("token".into(), Some(make_authentication()));
+
+        let auth_personal_access_token =
+            MockAuthPersonalAccessTokenService::new().with_hash("token".into(), None);
+
+        let cache = MockCacheService::new()
+            .with_increment(
+                format!(
+                    "rate_limit:verification_email:user:{}:1",
+                    FOO.user.id.hyphenated()
+                ),
+                Some(Duration::from_secs(7200)),
+                1,
+            )
+            .with_get_counter(
+                format!(
+                    "rate_limit:verification_email:user:{}:0",
+                    FOO.user.id.hyphenated()
+                ),
+                4,
+            );
+
+        let sut = RateLimitFeatureServiceImpl {
+            time,
+            auth_access_token,
+            auth_personal_access_token,
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .check(RateLimitGroup::VerificationEmail, IP, Some(&"token".into()))
+            .await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn ok_user_unauthenticated() {
+        // Arrange
+        let time = MockTimeService::new().with_now(time(0));
+
+        let auth_access_token = MockAuthAccessTokenService::new().with_verify("token".into(), None);
+
+        let auth_personal_access_token =
+            MockAuthPersonalAccessTokenService::new().with_hash("token".into(), None);
+
+        let cache = MockCacheService::new().with_increment(
+            "rate_limit:verification_email:ip:1.2.3.4:0".into(),
+            Some(Duration::from_secs(7200)),
+            1,
+        );
+
+        let sut = RateLimitFeatureServiceImpl {
+            time,
+            auth_access_token,
+            auth_personal_access_token,
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .check(RateLimitGroup::VerificationEmail, IP, Some(&"token".into()))
+            .await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn exceeded_user_personal_access_token() {
+        // Arrange
+        let db = MockDatabase::build(false);
+
+        let time = MockTimeService::new().with_now(time(3600));
+
+        let auth_personal_access_token = MockAuthPersonalAccessTokenService::new()
+            .with_hash("token".into(), Some((*SHA256HASH1).into()));
+
+        let personal_access_token_repo = MockPersonalAccessTokenRepository::new()
+            .with_get_by_hash((*SHA256HASH1).into(), Some(FOO_PAT.clone()));
+
+        let cache = MockCacheService::new()
+            .with_increment(
+                format!(
+                    "rate_limit:verification_email:user:{}:1",
+                    FOO.user.id.hyphenated()
+                ),
+                Some(Duration::from_secs(7200)),
+                6,
+            )
+            .with_get_counter(
+                format!(
+                    "rate_limit:verification_email:user:{}:0",
+                    FOO.user.id.hyphenated()
+                ),
+                0,
+            );
+
+        let sut = RateLimitFeatureServiceImpl {
+            db,
+            time,
+            auth_personal_access_token,
+            cache,
+            personal_access_token_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .check(RateLimitGroup::VerificationEmail, IP, Some(&"token".into()))
+            .await;
+
+        // Assert
+        assert_matches!(result, Err(RateLimitCheckError::Exceeded { retry_after }) if *retry_after == Duration::from_secs(3600));
+    }
+
+    #[tokio::test]
+    async fn ok_user_personal_access_token_invalid() {
+        // Arrange
+        let db = MockDatabase::build(false);
+
+        let time = MockTimeService::new().with_now(time(0));
+
+        let auth_personal_access_token = MockAuthPersonalAccessTokenService::new()
+            .with_hash("token".into(), Some((*SHA256HASH1).into()));
+
+        let personal_access_token_repo =
+            MockPersonalAccessTokenRepository::new().with_get_by_hash((*SHA256HASH1).into(), None);
+
+        let cache = MockCacheService::new().with_increment(
+            "rate_limit:verification_email:ip:1.2.3.4:0".into(),
+            Some(Duration::from_secs(7200)),
+            1,
+        );
+
+        let sut = RateLimitFeatureServiceImpl {
+            db,
+            time,
+            auth_personal_access_token,
+            cache,
+            personal_access_token_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .check(RateLimitGroup::VerificationEmail, IP, Some(&"token".into()))
+            .await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn exceeded_current_window() {
+        // Arrange
+        let time = MockTimeService::new().with_now(time(42 * 60 + 15));
+
+        let cache = MockCacheService::new()
+            .with_increment(
+                "rate_limit:signup:ip:1.2.3.4:42".into(),
+                Some(Duration::from_secs(120)),
+                11,
+            )
+            .with_get_counter("rate_limit:signup:ip:1.2.3.4:41".into(), 0);
+
+        let sut = RateLimitFeatureServiceImpl {
+            time,
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.check(RateLimitGroup::Signup, IP, None).await;
+
+        // Assert
+        assert_matches!(result, Err(RateLimitCheckError::Exceeded { retry_after }) if *retry_after == Duration::from_secs(45));
+    }
+
+    #[tokio::test]
+    async fn exceeded_previous_window() {
+        // Arrange
+        let time = MockTimeService::new().with_now(time(42 * 60 + 15));
+
+        let cache = MockCacheService::new()
+            .with_increment(
+                "rate_limit:login:route:42".into(),
+                Some(Duration::from_secs(120)),
+                4,
+            )
+            .with_get_counter("rate_limit:login:route:41".into(), 10);
+
+        let sut = RateLimitFeatureServiceImpl {
+            time,
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.check(RateLimitGroup::Login, IP, None).await;
+
+        // Assert
+        assert_matches!(result, Err(RateLimitCheckError::Exceeded { retry_after }) if *retry_after == Duration::from_secs(45));
+    }
+
+    impl Default for RateLimitFeatureConfig {
+        fn default() -> Self {
+            Self {
+                policies: Arc::new(
+                    [
+                        (
+                            RateLimitGroup::Login,
+                            RateLimitPolicy {
+                                requests: 10,
+                                window: Duration::from_secs(60),
+                                key: RateLimitKey::Route,
+                            },
+                        ),
+                        (
+                            RateLimitGroup::Signup,
+                            RateLimitPolicy {
+                                requests: 10,
+                                window: Duration::from_secs(60),
+                                key: RateLimitKey::Ip,
+                            },
+                        ),
+                        (
+                            RateLimitGroup::VerificationEmail,
+                            RateLimitPolicy {
+                                requests: 5,
+                                window: Duration::from_secs(3600),
+                                key: RateLimitKey::User,
+                            },
+                        ),
+                    ]
+                    .into(),
+                ),
+            }
+        }
+    }
+
+    fn time(secs: i64) -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::from_timestamp(secs, 0).unwrap()
+    }
+
+    fn make_authentication() -> Authentication {
+        Authentication {
+            user_id: FOO.user.id,
+            source: AuthenticationSource::Session {
+                session_id: UUID1.into(),
+                refresh_token_hash: (*SHA256HASH1).into(),
+            },
+            admin: FOO.user.admin,
+            email_verified: FOO.user.email_verified,
+            permissions: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::{collections::BTreeSet, future::Future};
+
+use academy_models::{
+    audit::AuditContext,
+    auth::{AccessToken, AuthError},
+    role::{Permission, Role, RoleId, RoleName, RolePatch},
+    user::UserIdOrSelf,
+};
+use thiserror::Error;
+
+pub trait RoleFeatureService: Send + Sync + 'static {
+    /// Return all roles.
+    ///
+    /// Requires admin privileges.
+    fn list_roles(
+        &self,
+        token: &AccessToken,
+    ) -> impl Future<Output = Result<Vec<Role>, RoleListError>> + Send;
+
+    /// Create a new role.
+    ///
+    /// Requires admin privileges.
+    fn create_role(
+        &self,
+        token: &AccessToken,
+        request: RoleCreateRequest,
+    ) -> impl Future<Output = Result<Role, RoleCreateError>> + Send;
+
+    /// Update an existing role.
+    ///
+    /// Requires admin privileges.
+    fn update_role(
+        &self,
+        token: &AccessToken,
+        role_id: RoleId,
+        patch: RolePatch,
+    ) -> impl Future<Output = Result<Role, RoleUpdateError>> + Send;
+
+    /// Delete a role and revoke it from all users.
+    ///
+    /// Requires admin privileges.
+    fn delete_role(
+        &self,
+        token: &AccessToken,
+        role_id: RoleId,
+    ) -> impl Future<Output = Result<(), RoleDeleteError>> + Send;
+
+    /// Return all roles granted to the given user.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    fn list_user_roles(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+    ) -> impl Future<Output = Result<Vec<Role>, RoleListUserRolesError>> + Send;
+
+    /// Grant a role to the given user.
+    ///
+    /// Requires admin privileges.
+    fn add_user_role(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        role_id: RoleId,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<(), RoleAddUserRoleError>> + Send;
+
+    /// Revoke a role from the given user.
+    ///
+    /// Requires admin privileges.
+    fn remove_user_role(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        role_id: RoleId,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<(), RoleRemoveUserRoleError>> + Send;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoleCreateRequest {
+    pub name: RoleName,
+    pub permissions: BTreeSet<Permission>,
+}
+
+#[derive(Debug, Error)]
+pub enum RoleListError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum RoleCreateError {
+    #[error("A role with the same name already exists.")]
+    NameConflict,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum RoleUpdateError {
+    #[error("The role does not exist.")]
+    NotFound,
+    #[error("A role with the same name already exists.")]
+    NameConflict,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum RoleDeleteError {
+    #[error("The role does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum RoleListUserRolesError {
+    #[error("The user does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum RoleAddUserRoleError {
+    #[error("The user does not exist.")]
+    UserNotFound,
+    #[error("The role does not exist.")]
+    RoleNotFound,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum RoleRemoveUserRoleError {
+    #[error("The user does not have this role.")]
+    NotFound,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
@@ -0,0 +1,325 @@
+use academy_auth_contracts::{AuthResultExt, AuthService};
+use academy_core_audit_contracts::audit::AuditService;
+use academy_core_role_contracts::{
+    RoleAddUserRoleError, RoleCreateError, RoleCreateRequest, RoleDeleteError, RoleFeatureService,
+    RoleListError, RoleListUserRolesError, RoleRemoveUserRoleError, RoleUpdateError,
+};
+use academy_di::Build;
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::AccessToken,
+    role::{Role, RoleId, RolePatch},
+    user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{
+    role::{RoleRepoError, RoleRepository},
+    user::UserRepository,
+    Database, Transaction,
+};
+use academy_shared_contracts::{id::IdService, time::TimeService};
+use academy_utils::{patch::Patch, trace_instrument};
+use anyhow::Context;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct RoleFeatureServiceImpl<Db, Auth, Id, Time, UserRepo, RoleRepo, Audit> {
+    db: Db,
+    auth: Auth,
+    id: Id,
+    time: Time,
+    user_repo: UserRepo,
+    role_repo: RoleRepo,
+    audit: Audit,
+}
+
+impl<Db, Auth, Id, Time, UserRepo, RoleRepo, Audit> RoleFeatureService
+    for RoleFeatureServiceImpl<Db, Auth, Id, Time, UserRepo, RoleRepo, Audit>
+where
+    Db: Database,
+    Auth: AuthService<Db::Transaction>,
+    Id: IdService,
+    Time: TimeService,
+    UserRepo: UserRepository<Db::Transaction>,
+    RoleRepo: RoleRepository<Db::Transaction>,
+    Audit: AuditService<Db::Transaction>,
+{
+    #[trace_instrument(skip(self))]
+    async fn list_roles(&self, token: &AccessToken) -> Result<Vec<Role>, RoleListError> {
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
+        auth.ensure_admin().map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        self.role_repo
+            .list(&mut txn)
+            .await
+            .context("Failed to get roles from database")
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn create_role(
+        &self,
+        token: &AccessToken,
+        RoleCreateRequest { name, permissions }: RoleCreateRequest,
+    ) -> Result<Role, RoleCreateError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_admin().map_auth_err()?;
+
+        let role = Role {
+            id: self.id.generate(),
+            name,
+            permissions,
+            created_at: self.time.now(),
+        };
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        self.role_repo
+            .create(&mut txn, &role)
+            .await
+            .map_err(|err| match err {
+                RoleRepoError::NameConflict => RoleCreateError::NameConflict,
+                RoleRepoError::Other(err) => {
+                    err.context("Failed to create role in database").into()
+                }
+            })?;
+
+        txn.commit().await?;
+
+        Ok(role)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn update_role(
+        &self,
+        token: &AccessToken,
+        role_id: RoleId,
+        patch: RolePatch,
+    ) -> Result<Role, RoleUpdateError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_admin().map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let role = self
+            .role_repo
+            .get(&mut txn, role_id)
+            .await
+            .context("Failed to get role from database")?
+            .ok_or(RoleUpdateError::NotFound)?;
+
+        let patch = patch.minimize(&role);
+        if patch.is_unchanged() {
+            return Ok(role);
+        }
+
+        self.role_repo
+            .update(&mut txn, role.id, patch.as_ref())
+            .await
+            .map_err(|err| match err {
+                RoleRepoError::NameConflict => RoleUpdateError::NameConflict,
+                RoleRepoError::Other(err) => {
+                    err.context("Failed to update role in database").into()
+                }
+            })?;
+
+        if patch.permissions.is_update() {
+            self.invalidate_role_users(&mut txn, role.id).await?;
+        }
+
+        txn.commit().await?;
+
+        Ok(role.update(patch))
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn delete_role(
+        &self,
+        token: &AccessToken,
+        role_id: RoleId,
+    ) -> Result<(), RoleDeleteError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_admin().map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        self.invalidate_role_users(&mut txn, role_id).await?;
+
+        if !self
+            .role_repo
+            .delete(&mut txn, role_id)
+            .await
+            .context("Failed to delete role from database")?
+        {
+            return Err(RoleDeleteError::NotFound);
+        }
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn list_user_roles(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+    ) -> Result<Vec<Role>, RoleListUserRolesError> {
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        if !self
+            .user_repo
+            .exists(&mut txn, user_id)
+            .await
+            .context("Failed to check user existence")?
+        {
+            return Err(RoleListUserRolesError::NotFound);
+        }
+
+        self.role_repo
+            .list_by_user(&mut txn, user_id)
+            .await
+            .context("Failed to get roles from database")
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn add_user_role(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        role_id: RoleId,
+        ctx: &AuditContext,
+    ) -> Result<(), RoleAddUserRoleError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_admin().map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        if !self
+            .user_repo
+            .exists(&mut txn, user_id)
+            .await
+            .context("Failed to check user existence")?
+        {
+            return Err(RoleAddUserRoleError::UserNotFound);
+        }
+
+        self.role_repo
+            .get(&mut txn, role_id)
+            .await
+            .context("Failed to get role from database")?
+            .ok_or(RoleAddUserRoleError::RoleNotFound)?;
+
+        if self
+            .role_repo
+            .add_to_user(&mut txn, user_id, role_id)
+            .await
+            .context("Failed to add role to user in database")?
+        {
+            self.auth
+                .invalidate_access_tokens(&mut txn, user_id)
+                .await
+                .context("Failed to invalidate access tokens")?;
+
+            self.audit
+                .record(
+                    &mut txn,
+                    ctx,
+                    Some(auth.user_id),
+                    user_id,
+                    AuditAction::RoleAssign,
+                )
+                .await
+                .context("Failed to record audit event")?;
+        }
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn remove_user_role(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        role_id: RoleId,
+        ctx: &AuditContext,
+    ) -> Result<(), RoleRemoveUserRoleError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_admin().map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        if !self
+            .role_repo
+            .remove_from_user(&mut txn, user_id, role_id)
+            .await
+            .context("Failed to remove role from user in database")?
+        {
+            return Err(RoleRemoveUserRoleError::NotFound);
+        }
+
+        self.auth
+            .invalidate_access_tokens(&mut txn, user_id)
+            .await
+            .context("Failed to invalidate access tokens")?;
+
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::RoleRemove,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+}
+
+impl<Db, Auth, Id, Time, UserRepo, RoleRepo, Audit>
+    RoleFeatureServiceImpl<Db, Auth, Id, Time, UserRepo, RoleRepo, Audit>
+where
+    Db: Database,
+    Auth: AuthService<Db::Transaction>,
+    RoleRepo: RoleRepository<Db::Transaction>,
+{
+    /// Invalidate the access tokens of all users who have been granted the
+    /// given role, so that changed permissions take effect immediately.
+    async fn invalidate_role_users(
+        &self,
+        txn: &mut Db::Transaction,
+        role_id: RoleId,
+    ) -> anyhow::Result<()> {
+        let user_ids = self
+            .role_repo
+            .list_users(txn, role_id)
+            .await
+            .context("Failed to get role users from database")?;
+
+        for user_id in user_ids {
+            self.auth
+                .invalidate_access_tokens(txn, user_id)
+                .await
+                .context("Failed to invalidate access tokens")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_role_contracts::{RoleCreateError, RoleCreateRequest, RoleFeatureService};
+use academy_demo::{
+    role::SUPPORT,
+    session::{ADMIN_1, FOO_1},
+    user::{ADMIN, FOO},
+};
+use academy_models::auth::{AuthError, AuthorizeError};
+use academy_persistence_contracts::{
+    role::{MockRoleRepository, RoleRepoError},
+    MockDatabase,
+};
+use academy_shared_contracts::{id::MockIdService, time::MockTimeService};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, RoleFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let id = MockIdService::new().with_generate(SUPPORT.id);
+
+    let time = MockTimeService::new().with_now(SUPPORT.created_at);
+
+    let role_repo = MockRoleRepository::new().with_create(SUPPORT.clone(), Ok(()));
+
+    let sut = RoleFeatureServiceImpl {
+        db,
+        auth,
+        id,
+        time,
+        role_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_role(
+            &"token".into(),
+            RoleCreateRequest {
+                name: SUPPORT.name.clone(),
+                permissions: SUPPORT.permissions.clone(),
+            },
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), *SUPPORT);
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = RoleFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_role(
+            &"token".into(),
+            RoleCreateRequest {
+                name: SUPPORT.name.clone(),
+                permissions: SUPPORT.permissions.clone(),
+            },
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(RoleCreateError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn name_conflict() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let id = MockIdService::new().with_generate(SUPPORT.id);
+
+    let time = MockTimeService::new().with_now(SUPPORT.created_at);
+
+    let role_repo =
+        MockRoleRepository::new().with_create(SUPPORT.clone(), Err(RoleRepoError::NameConflict));
+
+    let sut = RoleFeatureServiceImpl {
+        db,
+        auth,
+        id,
+        time,
+        role_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_role(
+            &"token".into(),
+            RoleCreateRequest {
+                name: SUPPORT.name.clone(),
+                permissions: SUPPORT.permissions.clone(),
+            },
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(RoleCreateError::NameConflict));
+}
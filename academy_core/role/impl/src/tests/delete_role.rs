@@ -0,0 +1,87 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_role_contracts::{RoleDeleteError, RoleFeatureService};
+use academy_demo::{
+    role::SUPPORT,
+    session::{ADMIN_1, FOO_1},
+    user::{ADMIN, FOO},
+};
+use academy_models::auth::{AuthError, AuthorizeError};
+use academy_persistence_contracts::{role::MockRoleRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, RoleFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let auth = MockAuthService::new()
+        .with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())))
+        .with_invalidate_access_tokens(FOO.user.id);
+
+    let db = MockDatabase::build(true);
+
+    let role_repo = MockRoleRepository::new()
+        .with_list_users(SUPPORT.id, vec![FOO.user.id])
+        .with_delete(SUPPORT.id, true);
+
+    let sut = RoleFeatureServiceImpl {
+        db,
+        auth,
+        role_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.delete_role(&"token".into(), SUPPORT.id).await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = RoleFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.delete_role(&"token".into(), SUPPORT.id).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(RoleDeleteError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let role_repo = MockRoleRepository::new()
+        .with_list_users(SUPPORT.id, vec![])
+        .with_delete(SUPPORT.id, false);
+
+    let sut = RoleFeatureServiceImpl {
+        db,
+        auth,
+        role_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.delete_role(&"token".into(), SUPPORT.id).await;
+
+    // Assert
+    assert_matches!(result, Err(RoleDeleteError::NotFound));
+}
@@ -0,0 +1,83 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_role_contracts::{RoleFeatureService, RoleListError};
+use academy_demo::{
+    role::ALL_ROLES,
+    session::{ADMIN_1, FOO_1},
+    user::{ADMIN, FOO},
+};
+use academy_models::auth::{AuthError, AuthenticateError, AuthorizeError};
+use academy_persistence_contracts::{role::MockRoleRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, RoleFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let expected = ALL_ROLES.iter().copied().cloned().collect::<Vec<_>>();
+
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let role_repo = MockRoleRepository::new().with_list(expected.clone());
+
+    let sut = RoleFeatureServiceImpl {
+        db,
+        auth,
+        role_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.list_roles(&"token".into()).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn unauthenticated() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate_read(None);
+
+    let sut = RoleFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.list_roles(&"token".into()).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(RoleListError::Auth(AuthError::Authenticate(
+            AuthenticateError::InvalidToken
+        )))
+    );
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = RoleFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.list_roles(&"token".into()).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(RoleListError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
@@ -0,0 +1,126 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_role_contracts::{RoleFeatureService, RoleListUserRolesError};
+use academy_demo::{
+    role::SUPPORT,
+    session::{ADMIN_1, BAR_1, FOO_1},
+    user::{ADMIN, BAR, FOO},
+};
+use academy_models::{
+    auth::{AuthError, AuthorizeError},
+    user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{
+    role::MockRoleRepository, user::MockUserRepository, MockDatabase,
+};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, RoleFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_exists(FOO.user.id, true);
+
+    let role_repo = MockRoleRepository::new().with_list_by_user(FOO.user.id, vec![SUPPORT.clone()]);
+
+    let sut = RoleFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        role_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_user_roles(&"token".into(), UserIdOrSelf::Slf)
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), vec![SUPPORT.clone()]);
+}
+
+#[tokio::test]
+async fn ok_admin() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_exists(FOO.user.id, true);
+
+    let role_repo = MockRoleRepository::new().with_list_by_user(FOO.user.id, vec![SUPPORT.clone()]);
+
+    let sut = RoleFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        role_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_user_roles(&"token".into(), FOO.user.id.into())
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), vec![SUPPORT.clone()]);
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let sut = RoleFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_user_roles(&"token".into(), FOO.user.id.into())
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(RoleListUserRolesError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_exists(FOO.user.id, false);
+
+    let sut = RoleFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_user_roles(&"token".into(), FOO.user.id.into())
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(RoleListUserRolesError::NotFound));
+}
@@ -0,0 +1,26 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_persistence_contracts::{
+    role::MockRoleRepository, user::MockUserRepository, MockDatabase, MockTransaction,
+};
+use academy_shared_contracts::{id::MockIdService, time::MockTimeService};
+
+use crate::RoleFeatureServiceImpl;
+
+mod add_user_role;
+mod create_role;
+mod delete_role;
+mod list_roles;
+mod list_user_roles;
+mod remove_user_role;
+mod update_role;
+
+type Sut = RoleFeatureServiceImpl<
+    MockDatabase,
+    MockAuthService<MockTransaction>,
+    MockIdService,
+    MockTimeService,
+    MockUserRepository<MockTransaction>,
+    MockRoleRepository<MockTransaction>,
+    MockAuditService<MockTransaction>,
+>;
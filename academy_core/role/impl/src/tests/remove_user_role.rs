@@ -0,0 +1,116 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_role_contracts::{RoleFeatureService, RoleRemoveUserRoleError};
+use academy_demo::{
+    role::{FINANCE, SUPPORT},
+    session::{ADMIN_1, FOO_1},
+    user::{ADMIN, FOO},
+};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthorizeError},
+};
+use academy_persistence_contracts::{role::MockRoleRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, RoleFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let auth = MockAuthService::new()
+        .with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())))
+        .with_invalidate_access_tokens(FOO.user.id);
+
+    let db = MockDatabase::build(true);
+
+    let role_repo = MockRoleRepository::new().with_remove_from_user(FOO.user.id, SUPPORT.id, true);
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        FOO.user.id,
+        AuditAction::RoleRemove,
+    );
+
+    let sut = RoleFeatureServiceImpl {
+        db,
+        auth,
+        role_repo,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .remove_user_role(
+            &"token".into(),
+            FOO.user.id.into(),
+            SUPPORT.id,
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = RoleFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .remove_user_role(
+            &"token".into(),
+            FOO.user.id.into(),
+            SUPPORT.id,
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(RoleRemoveUserRoleError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let role_repo = MockRoleRepository::new().with_remove_from_user(FOO.user.id, FINANCE.id, false);
+
+    let sut = RoleFeatureServiceImpl {
+        db,
+        auth,
+        role_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .remove_user_role(
+            &"token".into(),
+            FOO.user.id.into(),
+            FINANCE.id,
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(RoleRemoveUserRoleError::NotFound));
+}
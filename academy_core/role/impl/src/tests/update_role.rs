@@ -0,0 +1,220 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_role_contracts::{RoleFeatureService, RoleUpdateError};
+use academy_demo::{
+    role::{MODERATOR, SUPPORT},
+    session::{ADMIN_1, FOO_1},
+    user::{ADMIN, FOO},
+};
+use academy_models::{
+    auth::{AuthError, AuthorizeError},
+    role::{Permission, Role, RolePatch},
+};
+use academy_persistence_contracts::{
+    role::{MockRoleRepository, RoleRepoError},
+    MockDatabase,
+};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, RoleFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok_name() {
+    // Arrange
+    let expected = Role {
+        name: "helpdesk".try_into().unwrap(),
+        ..SUPPORT.clone()
+    };
+
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let role_repo = MockRoleRepository::new()
+        .with_get(SUPPORT.id, Some(SUPPORT.clone()))
+        .with_update(
+            SUPPORT.id,
+            RolePatch::new().update_name(expected.name.clone()),
+            Ok(true),
+        );
+
+    let sut = RoleFeatureServiceImpl {
+        db,
+        auth,
+        role_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_role(
+            &"token".into(),
+            SUPPORT.id,
+            RolePatch::new()
+                .update_name(expected.name.clone())
+                .update_permissions(SUPPORT.permissions.clone()),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn ok_permissions_invalidates_access_tokens() {
+    // Arrange
+    let expected = Role {
+        permissions: [Permission::ReadUsers].into(),
+        ..SUPPORT.clone()
+    };
+
+    let auth = MockAuthService::new()
+        .with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())))
+        .with_invalidate_access_tokens(FOO.user.id);
+
+    let db = MockDatabase::build(true);
+
+    let role_repo = MockRoleRepository::new()
+        .with_get(SUPPORT.id, Some(SUPPORT.clone()))
+        .with_update(
+            SUPPORT.id,
+            RolePatch::new().update_permissions(expected.permissions.clone()),
+            Ok(true),
+        )
+        .with_list_users(SUPPORT.id, vec![FOO.user.id]);
+
+    let sut = RoleFeatureServiceImpl {
+        db,
+        auth,
+        role_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_role(
+            &"token".into(),
+            SUPPORT.id,
+            RolePatch::new().update_permissions(expected.permissions.clone()),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn ok_no_changes() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let role_repo = MockRoleRepository::new().with_get(SUPPORT.id, Some(SUPPORT.clone()));
+
+    let sut = RoleFeatureServiceImpl {
+        db,
+        auth,
+        role_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_role(
+            &"token".into(),
+            SUPPORT.id,
+            RolePatch::new().update_name(SUPPORT.name.clone()),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), *SUPPORT);
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = RoleFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_role(&"token".into(), SUPPORT.id, RolePatch::new())
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(RoleUpdateError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let role_repo = MockRoleRepository::new().with_get(SUPPORT.id, None);
+
+    let sut = RoleFeatureServiceImpl {
+        db,
+        auth,
+        role_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_role(&"token".into(), SUPPORT.id, RolePatch::new())
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(RoleUpdateError::NotFound));
+}
+
+#[tokio::test]
+async fn name_conflict() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let role_repo = MockRoleRepository::new()
+        .with_get(SUPPORT.id, Some(SUPPORT.clone()))
+        .with_update(
+            SUPPORT.id,
+            RolePatch::new().update_name(MODERATOR.name.clone()),
+            Err(RoleRepoError::NameConflict),
+        );
+
+    let sut = RoleFeatureServiceImpl {
+        db,
+        auth,
+        role_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_role(
+            &"token".into(),
+            SUPPORT.id,
+            RolePatch::new().update_name(MODERATOR.name.clone()),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(RoleUpdateError::NameConflict));
+}
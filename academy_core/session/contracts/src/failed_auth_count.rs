@@ -1,51 +1,96 @@
-use std::future::Future;
+use std::{future::Future, net::IpAddr, time::Duration};
 
-use academy_models::user::UserNameOrEmailAddress;
+use academy_models::user::{UserId, UserNameOrEmailAddress};
+
+/// The login for which failed authentication attempts are counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailedAuthCountLogin {
+    /// An existing user account, no matter which name or email address has
+    /// been used to identify it.
+    User(UserId),
+    /// A name or email address which does not belong to any user account.
+    Unknown(UserNameOrEmailAddress),
+}
 
 #[cfg_attr(feature = "mock", mockall::automock)]
 pub trait SessionFailedAuthCountService: Send + Sync + 'static {
     /// Return the number of failed authentication attempts for the given login.
-    fn get(
+    fn get(&self, login: &FailedAuthCountLogin)
+        -> impl Future<Output = anyhow::Result<u64>> + Send;
+
+    /// Return the remaining lockout duration if either the given login or the
+    /// given client ip address is currently locked.
+    fn get_lockout(
         &self,
-        name_or_email: &UserNameOrEmailAddress,
-    ) -> impl Future<Output = anyhow::Result<u64>> + Send;
+        login: &FailedAuthCountLogin,
+        ip: Option<IpAddr>,
+    ) -> impl Future<Output = anyhow::Result<Option<Duration>>> + Send;
 
     /// Increment the number of failed authentication attempts for the given
-    /// login.
+    /// login and lock it if the configured threshold has been reached.
+    ///
+    /// Returns the lockout duration if the login has been locked.
     fn increment(
         &self,
-        name_or_email: &UserNameOrEmailAddress,
-    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+        login: &FailedAuthCountLogin,
+    ) -> impl Future<Output = anyhow::Result<Option<Duration>>> + Send;
 
-    /// Reset the number of failed authentication attempts for the given login.
+    /// Increment the number of failed authentication attempts for the given
+    /// client ip address and lock it if the configured threshold has been
+    /// reached.
+    fn increment_ip(&self, ip: IpAddr) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Reset the number of failed authentication attempts for the given login
+    /// and lift its lockout.
     fn reset(
         &self,
-        name_or_email: &UserNameOrEmailAddress,
+        login: &FailedAuthCountLogin,
     ) -> impl Future<Output = anyhow::Result<()>> + Send;
 }
 
 #[cfg(feature = "mock")]
 impl MockSessionFailedAuthCountService {
-    pub fn with_get(mut self, name_or_email: UserNameOrEmailAddress, result: u64) -> Self {
+    pub fn with_get(mut self, login: FailedAuthCountLogin, result: u64) -> Self {
         self.expect_get()
             .once()
-            .with(mockall::predicate::eq(name_or_email))
+            .with(mockall::predicate::eq(login))
             .return_once(move |_| Box::pin(std::future::ready(Ok(result))));
         self
     }
 
-    pub fn with_increment(mut self, name_or_email: UserNameOrEmailAddress) -> Self {
+    pub fn with_get_lockout(
+        mut self,
+        login: FailedAuthCountLogin,
+        ip: Option<IpAddr>,
+        result: Option<Duration>,
+    ) -> Self {
+        self.expect_get_lockout()
+            .once()
+            .with(mockall::predicate::eq(login), mockall::predicate::eq(ip))
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_increment(mut self, login: FailedAuthCountLogin, result: Option<Duration>) -> Self {
         self.expect_increment()
             .once()
-            .with(mockall::predicate::eq(name_or_email))
+            .with(mockall::predicate::eq(login))
+            .return_once(move |_| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_increment_ip(mut self, ip: IpAddr) -> Self {
+        self.expect_increment_ip()
+            .once()
+            .with(mockall::predicate::eq(ip))
             .return_once(|_| Box::pin(std::future::ready(Ok(()))));
         self
     }
 
-    pub fn with_reset(mut self, name_or_email: UserNameOrEmailAddress) -> Self {
+    pub fn with_reset(mut self, login: FailedAuthCountLogin) -> Self {
         self.expect_reset()
             .once()
-            .with(mockall::predicate::eq(name_or_email))
+            .with(mockall::predicate::eq(login))
             .return_once(|_| Box::pin(std::future::ready(Ok(()))));
         self
     }
@@ -1,15 +1,19 @@
-use std::future::Future;
+use std::{future::Future, net::IpAddr, time::Duration};
 
 use academy_models::{
+    audit::AuditContext,
     auth::{AccessToken, AuthError, Login, RefreshToken},
+    email_address::EmailAddress,
     mfa::MfaAuthentication,
-    session::{DeviceName, Session, SessionId},
+    session::{DeviceName, Session, SessionId, SessionLoginCode},
     user::{UserId, UserIdOrSelf, UserNameOrEmailAddress, UserPassword},
-    RecaptchaResponse,
+    RecaptchaResponse, VerificationCode,
 };
 use thiserror::Error;
 
 pub mod failed_auth_count;
+pub mod login_code;
+pub mod new_device;
 pub mod session;
 
 pub trait SessionFeatureService: Send + Sync + 'static {
@@ -19,23 +23,51 @@ pub trait SessionFeatureService: Send + Sync + 'static {
         token: &AccessToken,
     ) -> impl Future<Output = Result<Session, SessionGetCurrentError>> + Send;
 
-    /// Return all sessions of the given user.
+    /// Return all sessions of the given user, together with the id of the
+    /// currently authenticated session.
     ///
     /// Requires admin privileges if not used on the authenticated user.
     fn list_by_user(
         &self,
         token: &AccessToken,
         user_id: UserIdOrSelf,
-    ) -> impl Future<Output = Result<Vec<Session>, SessionListByUserError>> + Send;
+    ) -> impl Future<Output = Result<SessionListByUserResult, SessionListByUserError>> + Send;
 
     /// Create a new session by authenticating via username/password and MFA (if
     /// enabled).
+    ///
+    /// Too many failed login attempts for the same account or from the same
+    /// client ip address result in a temporary lockout.
     fn create_session(
         &self,
         cmd: SessionCreateCommand,
         recaptcha_response: Option<RecaptchaResponse>,
     ) -> impl Future<Output = Result<Login, SessionCreateError>> + Send;
 
+    /// Send a one-time login code to the given email address, which can be
+    /// used to create a session without a password.
+    ///
+    /// Succeeds even if there is no user with this email address, so that
+    /// this method cannot be used to find out which email addresses are
+    /// registered.
+    fn request_login_code(
+        &self,
+        email: EmailAddress,
+        recaptcha_response: Option<RecaptchaResponse>,
+    ) -> impl Future<Output = Result<(), SessionRequestLoginCodeError>> + Send;
+
+    /// Create a new session by authenticating via a one-time login code and
+    /// MFA (if enabled).
+    ///
+    /// The login code is invalidated by every attempt to use it. Failed
+    /// attempts count towards the same lockout as failed password
+    /// logins.
+    fn create_session_by_login_code(
+        &self,
+        cmd: SessionCreateByLoginCodeCommand,
+        recaptcha_response: Option<RecaptchaResponse>,
+    ) -> impl Future<Output = Result<Login, SessionCreateByLoginCodeError>> + Send;
+
     /// Impersonate a user by creating a new session for them.
     ///
     /// Requires admin privileges.
@@ -43,15 +75,18 @@ pub trait SessionFeatureService: Send + Sync + 'static {
         &self,
         token: &AccessToken,
         user_id: UserId,
+        ctx: &AuditContext,
     ) -> impl Future<Output = Result<Login, SessionImpersonateError>> + Send;
 
     /// Refresh a session using a refresh token.
     ///
     /// This will generate a new access and refresh token pair and invalidate
-    /// the previous one.
+    /// the previous one. If a refresh token which has already been replaced
+    /// is presented again, the affected session is revoked.
     fn refresh_session(
         &self,
         refresh_token: &RefreshToken,
+        ctx: &AuditContext,
     ) -> impl Future<Output = Result<Login, SessionRefreshError>> + Send;
 
     /// Delete the given session and invalidate the access and refresh tokens
@@ -81,6 +116,37 @@ pub trait SessionFeatureService: Send + Sync + 'static {
         token: &AccessToken,
         user_id: UserIdOrSelf,
     ) -> impl Future<Output = Result<(), SessionDeleteByUserError>> + Send;
+
+    /// Lift the lockout of the given user caused by too many failed login
+    /// attempts.
+    ///
+    /// Requires admin privileges.
+    fn unlock_user(
+        &self,
+        token: &AccessToken,
+        user_id: UserId,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<(), SessionUnlockUserError>> + Send;
+
+    /// Revoke a session which has been created from an unknown device using
+    /// the code from the new sign-in notification email.
+    ///
+    /// All sessions, personal access tokens and OAuth2 grants of the user are
+    /// deleted, the user's password is removed and a password reset email is
+    /// sent.
+    fn revoke_new_device_session(
+        &self,
+        code: VerificationCode,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<(), SessionRevokeNewDeviceSessionError>> + Send;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionListByUserResult {
+    pub sessions: Vec<Session>,
+    /// The id of the currently authenticated session, if the request has been
+    /// authenticated using a session.
+    pub current_session_id: Option<SessionId>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -89,6 +155,16 @@ pub struct SessionCreateCommand {
     pub password: UserPassword,
     pub mfa: MfaAuthentication,
     pub device_name: Option<DeviceName>,
+    pub ip: Option<IpAddr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionCreateByLoginCodeCommand {
+    pub email: EmailAddress,
+    pub code: SessionLoginCode,
+    pub mfa: MfaAuthentication,
+    pub device_name: Option<DeviceName>,
+    pub ip: Option<IpAddr>,
 }
 
 #[derive(Debug, Error)]
@@ -117,6 +193,32 @@ pub enum SessionCreateError {
     UserDisabled,
     #[error("Invalid recaptcha response")]
     Recaptcha,
+    #[error("Too many failed login attempts.")]
+    Locked { retry_after: Duration },
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SessionRequestLoginCodeError {
+    #[error("Invalid recaptcha response")]
+    Recaptcha,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SessionCreateByLoginCodeError {
+    #[error("The user does not exist or the login code is invalid.")]
+    InvalidCode,
+    #[error("The user has mfa enabled but no valid authentication was provided.")]
+    MfaFailed,
+    #[error("The user account has been disabled.")]
+    UserDisabled,
+    #[error("Invalid recaptcha response")]
+    Recaptcha,
+    #[error("Too many failed login attempts.")]
+    Locked { retry_after: Duration },
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -164,3 +266,21 @@ pub enum SessionDeleteByUserError {
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
+
+#[derive(Debug, Error)]
+pub enum SessionUnlockUserError {
+    #[error("The user does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SessionRevokeNewDeviceSessionError {
+    #[error("The revoke code is invalid.")]
+    InvalidCode,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
@@ -0,0 +1,53 @@
+use std::future::Future;
+
+use academy_models::{
+    email_address::EmailAddressWithName, session::SessionLoginCode, user::UserId,
+};
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait SessionLoginCodeService: Send + Sync + 'static {
+    /// Generate a new login code for the given user and send it to the given
+    /// email address.
+    ///
+    /// Any previously generated login code of the user becomes invalid.
+    fn send(
+        &self,
+        user_id: UserId,
+        email: EmailAddressWithName,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Invalidate the login code of the given user and return whether it
+    /// matched the given code.
+    ///
+    /// Each code can be redeemed at most once, even by concurrent callers.
+    fn redeem(
+        &self,
+        user_id: UserId,
+        code: &SessionLoginCode,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+}
+
+#[cfg(feature = "mock")]
+impl MockSessionLoginCodeService {
+    pub fn with_send(mut self, user_id: UserId, email: EmailAddressWithName) -> Self {
+        self.expect_send()
+            .once()
+            .with(
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(email),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_redeem(mut self, user_id: UserId, code: SessionLoginCode, result: bool) -> Self {
+        self.expect_redeem()
+            .once()
+            .with(
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(code),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+}
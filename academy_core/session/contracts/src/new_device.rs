@@ -0,0 +1,93 @@
+use std::future::Future;
+
+use academy_models::{
+    session::Session,
+    user::{UserComposite, UserId},
+    VerificationCode,
+};
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait SessionNewDeviceService<Txn: Send + Sync + 'static>: Send + Sync + 'static {
+    /// Remember the device of a newly created session and return whether the
+    /// user should be notified because they have never signed in from this
+    /// device or ip address before.
+    ///
+    /// Users who have not signed in before (e.g. directly after signing up) are
+    /// not notified.
+    fn handle_login(
+        &self,
+        txn: &mut Txn,
+        user_composite: &UserComposite,
+        session: &Session,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    /// Notify the user via email about a sign-in from a new device.
+    ///
+    /// The email contains a link to revoke all sessions of the user, so this
+    /// must only be called after the session has been committed to the
+    /// database.
+    fn notify(
+        &self,
+        user_composite: &UserComposite,
+        session: &Session,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Return the user associated with the given revoke code.
+    fn get_revoke_code(
+        &self,
+        code: &VerificationCode,
+    ) -> impl Future<Output = anyhow::Result<Option<UserId>>> + Send;
+
+    /// Invalidate the given revoke code.
+    fn remove_revoke_code(
+        &self,
+        code: &VerificationCode,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
+#[cfg(feature = "mock")]
+impl<Txn: Send + Sync + 'static> MockSessionNewDeviceService<Txn> {
+    pub fn with_handle_login(
+        mut self,
+        user_composite: UserComposite,
+        session: Session,
+        result: bool,
+    ) -> Self {
+        self.expect_handle_login()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_composite),
+                mockall::predicate::eq(session),
+            )
+            .return_once(move |_, _, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_notify(mut self, user_composite: UserComposite, session: Session) -> Self {
+        self.expect_notify()
+            .once()
+            .with(
+                mockall::predicate::eq(user_composite),
+                mockall::predicate::eq(session),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_get_revoke_code(mut self, code: VerificationCode, result: Option<UserId>) -> Self {
+        self.expect_get_revoke_code()
+            .once()
+            .with(mockall::predicate::eq(code))
+            .return_once(move |_| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_remove_revoke_code(mut self, code: VerificationCode) -> Self {
+        self.expect_remove_revoke_code()
+            .once()
+            .with(mockall::predicate::eq(code))
+            .return_once(|_| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+}
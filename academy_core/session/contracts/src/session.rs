@@ -1,8 +1,8 @@
-use std::future::Future;
+use std::{future::Future, net::IpAddr};
 
 use academy_models::{
     auth::Login,
-    session::{DeviceName, SessionId},
+    session::{DeviceName, SessionId, SessionLoginMethod},
     user::{UserComposite, UserId},
 };
 use thiserror::Error;
@@ -10,12 +10,16 @@ use thiserror::Error;
 #[cfg_attr(feature = "mock", mockall::automock)]
 pub trait SessionService<Txn: Send + Sync + 'static>: Send + Sync + 'static {
     /// Create a new session for the given user.
+    ///
+    /// Unless the session is created via impersonation, the user's last login
+    /// timestamp is also updated.
     fn create(
         &self,
         txn: &mut Txn,
         user_composite: UserComposite,
         device_name: Option<DeviceName>,
-        update_last_login: bool,
+        ip: Option<IpAddr>,
+        login_method: SessionLoginMethod,
     ) -> impl Future<Output = anyhow::Result<Login>> + Send;
 
     /// Refresh the given session by invalidating the current access/refresh
@@ -24,6 +28,7 @@ pub trait SessionService<Txn: Send + Sync + 'static>: Send + Sync + 'static {
         &self,
         txn: &mut Txn,
         session_id: SessionId,
+        ip: Option<IpAddr>,
     ) -> impl Future<Output = Result<Login, SessionRefreshError>> + Send;
 
     /// Delete the given session and invalidate the current access/refresh token
@@ -57,7 +62,8 @@ impl<Txn: Send + Sync + 'static> MockSessionService<Txn> {
         mut self,
         user_composite: UserComposite,
         device_name: Option<DeviceName>,
-        update_last_login: bool,
+        ip: Option<IpAddr>,
+        login_method: SessionLoginMethod,
         result: Login,
     ) -> Self {
         self.expect_create()
@@ -66,15 +72,17 @@ impl<Txn: Send + Sync + 'static> MockSessionService<Txn> {
                 mockall::predicate::always(),
                 mockall::predicate::eq(user_composite),
                 mockall::predicate::eq(device_name),
-                mockall::predicate::eq(update_last_login),
+                mockall::predicate::eq(ip),
+                mockall::predicate::eq(login_method),
             )
-            .return_once(|_, _, _, _| Box::pin(std::future::ready(Ok(result))));
+            .return_once(|_, _, _, _, _| Box::pin(std::future::ready(Ok(result))));
         self
     }
 
     pub fn with_refresh(
         mut self,
         session_id: SessionId,
+        ip: Option<IpAddr>,
         result: Result<Login, SessionRefreshError>,
     ) -> Self {
         self.expect_refresh()
@@ -82,8 +90,9 @@ impl<Txn: Send + Sync + 'static> MockSessionService<Txn> {
             .with(
                 mockall::predicate::always(),
                 mockall::predicate::eq(session_id),
+                mockall::predicate::eq(ip),
             )
-            .return_once(|_, _| Box::pin(std::future::ready(result)));
+            .return_once(|_, _, _| Box::pin(std::future::ready(result)));
         self
     }
 
@@ -1,62 +1,108 @@
+use std::{net::IpAddr, time::Duration};
+
 use academy_cache_contracts::CacheService;
-use academy_core_session_contracts::failed_auth_count::SessionFailedAuthCountService;
+use academy_core_session_contracts::failed_auth_count::{
+    FailedAuthCountLogin, SessionFailedAuthCountService,
+};
 use academy_di::Build;
-use academy_models::user::UserNameOrEmailAddress;
-use academy_shared_contracts::hash::HashService;
+use academy_models::user::{UserId, UserNameOrEmailAddress};
+use academy_shared_contracts::{hash::HashService, time::TimeService};
 use academy_utils::trace_instrument;
 use anyhow::Context;
+use chrono::{DateTime, Utc};
+
+use crate::SessionFeatureConfig;
 
 #[derive(Debug, Clone, Build)]
-pub struct SessionFailedAuthCountServiceImpl<Hash, Cache> {
+pub struct SessionFailedAuthCountServiceImpl<Hash, Time, Cache> {
     hash: Hash,
+    time: Time,
     cache: Cache,
+    config: SessionFeatureConfig,
 }
 
-impl<Hash, Cache> SessionFailedAuthCountService for SessionFailedAuthCountServiceImpl<Hash, Cache>
+impl<Hash, Time, Cache> SessionFailedAuthCountService
+    for SessionFailedAuthCountServiceImpl<Hash, Time, Cache>
 where
     Hash: HashService,
+    Time: TimeService,
     Cache: CacheService,
 {
     #[trace_instrument(skip(self))]
-    async fn get(&self, name_or_email: &UserNameOrEmailAddress) -> anyhow::Result<u64> {
+    async fn get(&self, login: &FailedAuthCountLogin) -> anyhow::Result<u64> {
         self.cache
-            .get(&self.cache_key(name_or_email))
+            .get_counter(&count_cache_key(&self.login_key(login)))
             .await
-            .map(|x| x.unwrap_or(0))
             .context("Failed to get failed auth count from cache")
     }
 
     #[trace_instrument(skip(self))]
-    async fn increment(&self, name_or_email: &UserNameOrEmailAddress) -> anyhow::Result<()> {
-        let cache_key = self.cache_key(name_or_email);
+    async fn get_lockout(
+        &self,
+        login: &FailedAuthCountLogin,
+        ip: Option<IpAddr>,
+    ) -> anyhow::Result<Option<Duration>> {
+        let keys = std::iter::once(self.login_key(login)).chain(ip.map(ip_key));
 
-        let count = self
-            .cache
-            .get(&cache_key)
-            .await
-            .context("Failed to get failed auth count from cache")?
-            .unwrap_or(0u64);
+        let now = self.time.now();
+        let mut result = None;
+        for key in keys {
+            let locked_until = self
+                .cache
+                .get::<DateTime<Utc>>(&lockout_cache_key(&key))
+                .await
+                .context("Failed to get lockout from cache")?;
+            let remaining = locked_until.and_then(|x| (x - now).to_std().ok());
+            result = result.max(remaining);
+        }
 
-        self.cache
-            .set(&cache_key, &(count + 1), None)
+        Ok(result)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn increment(&self, login: &FailedAuthCountLogin) -> anyhow::Result<Option<Duration>> {
+        self.increment_and_lock(
+            &self.login_key(login),
+            self.config.login_fails_before_lockout,
+        )
+        .await
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn increment_ip(&self, ip: IpAddr) -> anyhow::Result<()> {
+        self.increment_and_lock(&ip_key(ip), self.config.ip_login_fails_before_lockout)
             .await
-            .context("Failed to save failed auth count in cache")
+            .map(|_| ())
     }
 
     #[trace_instrument(skip(self))]
-    async fn reset(&self, name_or_email: &UserNameOrEmailAddress) -> anyhow::Result<()> {
+    async fn reset(&self, login: &FailedAuthCountLogin) -> anyhow::Result<()> {
+        let key = self.login_key(login);
+
+        self.cache
+            .remove(&count_cache_key(&key))
+            .await
+            .context("Failed to reset failed auth count in cache")?;
+
         self.cache
-            .remove(&self.cache_key(name_or_email))
+            .remove(&lockout_cache_key(&key))
             .await
-            .context("Failed to reset failed auth count in cache")
+            .context("Failed to remove lockout from cache")
     }
 }
 
-impl<Hash, Cache> SessionFailedAuthCountServiceImpl<Hash, Cache>
+impl<Hash, Time, Cache> SessionFailedAuthCountServiceImpl<Hash, Time, Cache>
 where
     Hash: HashService,
+    Time: TimeService,
+    Cache: CacheService,
 {
-    fn cache_key(&self, name_or_email: &UserNameOrEmailAddress) -> String {
+    fn login_key(&self, login: &FailedAuthCountLogin) -> String {
+        let name_or_email = match login {
+            FailedAuthCountLogin::User(user_id) => return user_key(*user_id),
+            FailedAuthCountLogin::Unknown(name_or_email) => name_or_email,
+        };
+
         let hash = self.hash.sha256(
             &match name_or_email {
                 UserNameOrEmailAddress::Name(name) => name,
@@ -64,62 +110,390 @@ where
             }
             .to_lowercase(),
         );
-        format!("failed_auth_attempts:{}", hex::encode(hash.0))
+        hex::encode(hash.0)
+    }
+
+    async fn increment_and_lock(
+        &self,
+        key: &str,
+        threshold: u64,
+    ) -> anyhow::Result<Option<Duration>> {
+        let count = self
+            .cache
+            .increment(
+                &count_cache_key(key),
+                Some(self.config.failed_login_attempts_ttl),
+            )
+            .await
+            .context("Failed to increment failed auth count in cache")?;
+
+        if count < threshold {
+            return Ok(None);
+        }
+
+        let duration = lockout_duration(
+            self.config.lockout_duration,
+            self.config.max_lockout_duration,
+            count - threshold,
+        );
+        let locked_until = self.time.now() + duration;
+
+        self.cache
+            .set(&lockout_cache_key(key), &locked_until, Some(duration))
+            .await
+            .context("Failed to save lockout in cache")?;
+
+        Ok(Some(duration))
     }
 }
 
+fn user_key(user_id: UserId) -> String {
+    format!("user:{}", user_id.hyphenated())
+}
+
+fn ip_key(ip: IpAddr) -> String {
+    format!("ip:{ip}")
+}
+
+fn count_cache_key(key: &str) -> String {
+    format!("failed_auth_attempts:{key}")
+}
+
+fn lockout_cache_key(key: &str) -> String {
+    format!("login_lockout:{key}")
+}
+
+/// Double the lockout duration for every failed attempt beyond the threshold.
+fn lockout_duration(base: Duration, max: Duration, excess_attempts: u64) -> Duration {
+    u32::try_from(excess_attempts)
+        .ok()
+        .and_then(|exp| 2u32.checked_pow(exp))
+        .and_then(|factor| base.checked_mul(factor))
+        .unwrap_or(max)
+        .min(max)
+}
+
 #[cfg(test)]
 mod tests {
+    use std::net::Ipv4Addr;
+
     use academy_cache_contracts::MockCacheService;
     use academy_demo::{user::FOO, SHA256HASH1, SHA256HASH1_HEX};
-    use academy_shared_contracts::hash::MockHashService;
+    use academy_shared_contracts::{hash::MockHashService, time::MockTimeService};
 
     use super::*;
 
+    type Sut =
+        SessionFailedAuthCountServiceImpl<MockHashService, MockTimeService, MockCacheService>;
+
+    const IP: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+
     #[tokio::test]
     async fn get() {
         // Arrange
         let hash =
             MockHashService::new().with_sha256(FOO.user.name.clone().into_inner(), *SHA256HASH1);
 
+        let cache = MockCacheService::new()
+            .with_get_counter(format!("failed_auth_attempts:{}", SHA256HASH1_HEX), 3);
+
+        let sut = Sut {
+            hash,
+            time: MockTimeService::new(),
+            cache,
+            config: Default::default(),
+        };
+
+        // Act
+        let result = sut
+            .get(&FailedAuthCountLogin::Unknown(
+                UserNameOrEmailAddress::Name(FOO.user.name.clone()),
+            ))
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn get_lockout_not_locked() {
+        // Arrange
+        let hash =
+            MockHashService::new().with_sha256(FOO.user.name.clone().into_inner(), *SHA256HASH1);
+
+        let time = MockTimeService::new().with_now(FOO.user.last_login.unwrap());
+
+        let cache = MockCacheService::new()
+            .with_get(
+                format!("login_lockout:{}", SHA256HASH1_HEX),
+                None::<DateTime<Utc>>,
+            )
+            .with_get(format!("login_lockout:ip:{IP}"), None::<DateTime<Utc>>);
+
+        let sut = Sut {
+            hash,
+            time,
+            cache,
+            config: Default::default(),
+        };
+
+        // Act
+        let result = sut
+            .get_lockout(
+                &FailedAuthCountLogin::Unknown(UserNameOrEmailAddress::Name(FOO.user.name.clone())),
+                Some(IP),
+            )
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn get_lockout_locked() {
+        // Arrange
+        let now = FOO.user.last_login.unwrap();
+
+        let hash =
+            MockHashService::new().with_sha256(FOO.user.name.clone().into_inner(), *SHA256HASH1);
+
+        let time = MockTimeService::new().with_now(now);
+
+        let cache = MockCacheService::new()
+            .with_get(
+                format!("login_lockout:{}", SHA256HASH1_HEX),
+                Some(now + Duration::from_secs(60)),
+            )
+            .with_get(
+                format!("login_lockout:ip:{IP}"),
+                Some(now + Duration::from_secs(300)),
+            );
+
+        let sut = Sut {
+            hash,
+            time,
+            cache,
+            config: Default::default(),
+        };
+
+        // Act
+        let result = sut
+            .get_lockout(
+                &FailedAuthCountLogin::Unknown(UserNameOrEmailAddress::Name(FOO.user.name.clone())),
+                Some(IP),
+            )
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), Some(Duration::from_secs(300)));
+    }
+
+    #[tokio::test]
+    async fn get_lockout_expired() {
+        // Arrange
+        let now = FOO.user.last_login.unwrap();
+
+        let hash =
+            MockHashService::new().with_sha256(FOO.user.name.clone().into_inner(), *SHA256HASH1);
+
+        let time = MockTimeService::new().with_now(now);
+
         let cache = MockCacheService::new().with_get(
-            format!("failed_auth_attempts:{}", SHA256HASH1_HEX),
-            Some(3u64),
+            format!("login_lockout:{}", SHA256HASH1_HEX),
+            Some(now - Duration::from_secs(1)),
         );
 
-        let sut = SessionFailedAuthCountServiceImpl { hash, cache };
+        let sut = Sut {
+            hash,
+            time,
+            cache,
+            config: Default::default(),
+        };
 
         // Act
         let result = sut
-            .get(&UserNameOrEmailAddress::Name(FOO.user.name.clone()))
+            .get_lockout(
+                &FailedAuthCountLogin::Unknown(UserNameOrEmailAddress::Name(FOO.user.name.clone())),
+                None,
+            )
             .await;
 
         // Assert
-        assert_eq!(result.unwrap(), 3);
+        assert_eq!(result.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn get_lockout_user() {
+        // Arrange
+        let now = FOO.user.last_login.unwrap();
+
+        let time = MockTimeService::new().with_now(now);
+
+        let cache = MockCacheService::new()
+            .with_get(
+                format!("login_lockout:user:{}", FOO.user.id.hyphenated()),
+                Some(now + Duration::from_secs(60)),
+            )
+            .with_get(format!("login_lockout:ip:{IP}"), None::<DateTime<Utc>>);
+
+        let sut = Sut {
+            hash: MockHashService::new(),
+            time,
+            cache,
+            config: Default::default(),
+        };
+
+        // Act
+        let result = sut
+            .get_lockout(&FailedAuthCountLogin::User(FOO.user.id), Some(IP))
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), Some(Duration::from_secs(60)));
     }
 
     #[tokio::test]
     async fn increment() {
         // Arrange
+        let config = SessionFeatureConfig::default();
+
         let hash = MockHashService::new().with_sha256(
             FOO.user.email.as_ref().unwrap().as_str().to_owned(),
             *SHA256HASH1,
         );
 
-        let cache_key = format!("failed_auth_attempts:{}", SHA256HASH1_HEX);
+        let cache = MockCacheService::new().with_increment(
+            format!("failed_auth_attempts:{}", SHA256HASH1_HEX),
+            Some(config.failed_login_attempts_ttl),
+            config.login_fails_before_lockout - 1,
+        );
+
+        let sut = Sut {
+            hash,
+            time: MockTimeService::new(),
+            cache,
+            config,
+        };
+
+        // Act
+        let result = sut
+            .increment(&FailedAuthCountLogin::Unknown(
+                UserNameOrEmailAddress::Email(FOO.user.email.clone().unwrap()),
+            ))
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn increment_lock() {
+        // Arrange
+        let config = SessionFeatureConfig::default();
+        let now = FOO.user.last_login.unwrap();
+        let expected = config.lockout_duration * 4;
+
+        let hash =
+            MockHashService::new().with_sha256(FOO.user.name.clone().into_inner(), *SHA256HASH1);
+
+        let time = MockTimeService::new().with_now(now);
+
         let cache = MockCacheService::new()
-            .with_get(cache_key.clone(), Some(3u64))
-            .with_set(cache_key, 4u64, None);
+            .with_increment(
+                format!("failed_auth_attempts:{}", SHA256HASH1_HEX),
+                Some(config.failed_login_attempts_ttl),
+                config.login_fails_before_lockout + 2,
+            )
+            .with_set(
+                format!("login_lockout:{}", SHA256HASH1_HEX),
+                now + expected,
+                Some(expected),
+            );
 
-        let sut = SessionFailedAuthCountServiceImpl { hash, cache };
+        let sut = Sut {
+            hash,
+            time,
+            cache,
+            config,
+        };
 
         // Act
         let result = sut
-            .increment(&UserNameOrEmailAddress::Email(
-                FOO.user.email.clone().unwrap(),
+            .increment(&FailedAuthCountLogin::Unknown(
+                UserNameOrEmailAddress::Name(FOO.user.name.clone()),
             ))
             .await;
 
+        // Assert
+        assert_eq!(result.unwrap(), Some(expected));
+    }
+
+    #[tokio::test]
+    async fn increment_lock_user() {
+        // Arrange
+        let config = SessionFeatureConfig::default();
+        let now = FOO.user.last_login.unwrap();
+
+        let time = MockTimeService::new().with_now(now);
+
+        let cache = MockCacheService::new()
+            .with_increment(
+                format!("failed_auth_attempts:user:{}", FOO.user.id.hyphenated()),
+                Some(config.failed_login_attempts_ttl),
+                config.login_fails_before_lockout,
+            )
+            .with_set(
+                format!("login_lockout:user:{}", FOO.user.id.hyphenated()),
+                now + config.lockout_duration,
+                Some(config.lockout_duration),
+            );
+
+        let sut = Sut {
+            hash: MockHashService::new(),
+            time,
+            cache,
+            config: config.clone(),
+        };
+
+        // Act
+        let result = sut
+            .increment(&FailedAuthCountLogin::User(FOO.user.id))
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), Some(config.lockout_duration));
+    }
+
+    #[tokio::test]
+    async fn increment_ip_lock() {
+        // Arrange
+        let config = SessionFeatureConfig::default();
+        let now = FOO.user.last_login.unwrap();
+
+        let time = MockTimeService::new().with_now(now);
+
+        let cache = MockCacheService::new()
+            .with_increment(
+                format!("failed_auth_attempts:ip:{IP}"),
+                Some(config.failed_login_attempts_ttl),
+                config.ip_login_fails_before_lockout,
+            )
+            .with_set(
+                format!("login_lockout:ip:{IP}"),
+                now + config.lockout_duration,
+                Some(config.lockout_duration),
+            );
+
+        let sut = Sut {
+            hash: MockHashService::new(),
+            time,
+            cache,
+            config,
+        };
+
+        // Act
+        let result = sut.increment_ip(IP).await;
+
         // Assert
         result.unwrap();
     }
@@ -131,16 +505,61 @@ mod tests {
             MockHashService::new().with_sha256(FOO.user.name.clone().into_inner(), *SHA256HASH1);
 
         let cache = MockCacheService::new()
-            .with_remove(format!("failed_auth_attempts:{}", SHA256HASH1_HEX));
+            .with_remove(format!("failed_auth_attempts:{}", SHA256HASH1_HEX))
+            .with_remove(format!("login_lockout:{}", SHA256HASH1_HEX));
 
-        let sut = SessionFailedAuthCountServiceImpl { hash, cache };
+        let sut = Sut {
+            hash,
+            time: MockTimeService::new(),
+            cache,
+            config: Default::default(),
+        };
 
         // Act
         let result = sut
-            .reset(&UserNameOrEmailAddress::Name(FOO.user.name.clone()))
+            .reset(&FailedAuthCountLogin::Unknown(
+                UserNameOrEmailAddress::Name(FOO.user.name.clone()),
+            ))
             .await;
 
         // Assert
         result.unwrap();
     }
+
+    #[tokio::test]
+    async fn reset_user() {
+        // Arrange
+        let cache = MockCacheService::new()
+            .with_remove(format!(
+                "failed_auth_attempts:user:{}",
+                FOO.user.id.hyphenated()
+            ))
+            .with_remove(format!("login_lockout:user:{}", FOO.user.id.hyphenated()));
+
+        let sut = Sut {
+            hash: MockHashService::new(),
+            time: MockTimeService::new(),
+            cache,
+            config: Default::default(),
+        };
+
+        // Act
+        let result = sut.reset(&FailedAuthCountLogin::User(FOO.user.id)).await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[test]
+    fn lockout_duration_backoff() {
+        let base = Duration::from_secs(60);
+        let max = Duration::from_secs(3600);
+
+        assert_eq!(lockout_duration(base, max, 0), base);
+        assert_eq!(lockout_duration(base, max, 1), base * 2);
+        assert_eq!(lockout_duration(base, max, 5), base * 32);
+        assert_eq!(lockout_duration(base, max, 6), max);
+        assert_eq!(lockout_duration(base, max, 1000), max);
+        assert_eq!(lockout_duration(base, max, u64::MAX), max);
+    }
 }
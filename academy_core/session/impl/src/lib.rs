@@ -1,30 +1,47 @@
+use std::{net::IpAddr, sync::Arc, time::Duration};
+
 use academy_auth_contracts::{
     AuthResultExt, AuthService, AuthenticateByPasswordError, AuthenticateByRefreshTokenError,
 };
+use academy_core_audit_contracts::audit::AuditService;
 use academy_core_mfa_contracts::authenticate::{
     MfaAuthenticateError, MfaAuthenticateResult, MfaAuthenticateService,
 };
 use academy_core_session_contracts::{
-    failed_auth_count::SessionFailedAuthCountService, session::SessionService,
-    SessionCreateCommand, SessionCreateError, SessionDeleteByUserError, SessionDeleteCurrentError,
-    SessionDeleteError, SessionFeatureService, SessionGetCurrentError, SessionImpersonateError,
-    SessionListByUserError, SessionRefreshError,
+    failed_auth_count::{FailedAuthCountLogin, SessionFailedAuthCountService},
+    login_code::SessionLoginCodeService,
+    new_device::SessionNewDeviceService,
+    session::SessionService,
+    SessionCreateByLoginCodeCommand, SessionCreateByLoginCodeError, SessionCreateCommand,
+    SessionCreateError, SessionDeleteByUserError, SessionDeleteCurrentError, SessionDeleteError,
+    SessionFeatureService, SessionGetCurrentError, SessionImpersonateError, SessionListByUserError,
+    SessionListByUserResult, SessionRefreshError, SessionRequestLoginCodeError,
+    SessionRevokeNewDeviceSessionError, SessionUnlockUserError,
 };
+use academy_core_user_contracts::email_confirmation::UserEmailConfirmationService;
 use academy_di::Build;
+use academy_email_contracts::template::TemplateEmailService;
 use academy_models::{
+    audit::{AuditAction, AuditContext},
     auth::{AccessToken, Login, RefreshToken},
-    session::{Session, SessionId},
-    user::{UserId, UserIdOrSelf, UserNameOrEmailAddress},
-    RecaptchaResponse,
+    email_address::EmailAddress,
+    session::{Session, SessionId, SessionLoginMethod},
+    user::{UserComposite, UserId, UserIdOrSelf, UserNameOrEmailAddress},
+    RecaptchaResponse, VerificationCode,
 };
 use academy_persistence_contracts::{
+    oauth2_server::OAuth2ServerRepository, personal_access_token::PersonalAccessTokenRepository,
     session::SessionRepository, user::UserRepository, Database, Transaction,
 };
 use academy_shared_contracts::captcha::{CaptchaCheckError, CaptchaService};
+use academy_templates_contracts::AccountLockedTemplate;
 use academy_utils::trace_instrument;
 use anyhow::{anyhow, Context};
+use tracing::{trace, warn};
 
 pub mod failed_auth_count;
+pub mod login_code;
+pub mod new_device;
 pub mod session;
 
 #[cfg(test)]
@@ -36,69 +53,114 @@ pub struct SessionFeatureServiceImpl<
     Db,
     Auth,
     Captcha,
+    TemplateEmail,
     Session,
     SessionFailedAuthCount,
+    SessionNewDevice,
+    SessionLoginCode,
     MfaAuthenticate,
+    UserEmailConfirmation,
+    Audit,
     UserRepo,
     SessionRepo,
+    PersonalAccessTokenRepo,
+    OAuth2ServerRepo,
 > {
     db: Db,
     auth: Auth,
     captcha: Captcha,
+    template_email: TemplateEmail,
     session: Session,
     session_failed_auth_count: SessionFailedAuthCount,
+    session_new_device: SessionNewDevice,
+    session_login_code: SessionLoginCode,
     mfa_authenticate: MfaAuthenticate,
+    user_email_confirmation: UserEmailConfirmation,
+    audit: Audit,
     user_repo: UserRepo,
     session_repo: SessionRepo,
+    personal_access_token_repo: PersonalAccessTokenRepo,
+    oauth2_server_repo: OAuth2ServerRepo,
     config: SessionFeatureConfig,
 }
 
 #[derive(Debug, Clone)]
 pub struct SessionFeatureConfig {
     pub login_fails_before_captcha: u64,
+    pub failed_login_attempts_ttl: Duration,
+    pub login_fails_before_lockout: u64,
+    pub ip_login_fails_before_lockout: u64,
+    pub lockout_duration: Duration,
+    pub max_lockout_duration: Duration,
+    pub new_device_revoke_redirect_url: Arc<String>,
+    pub new_device_revoke_code_ttl: Duration,
+    pub login_code_redirect_url: Arc<String>,
+    pub login_code_ttl: Duration,
 }
 
 impl<
         Db,
         Auth,
         Captcha,
+        TemplateEmail,
         SessionS,
         SessionFailedAuthCount,
+        SessionNewDevice,
+        SessionLoginCode,
         MfaAuthenticate,
+        UserEmailConfirmation,
+        Audit,
         UserRepo,
         SessionRepo,
+        PersonalAccessTokenRepo,
+        OAuth2ServerRepo,
     > SessionFeatureService
     for SessionFeatureServiceImpl<
         Db,
         Auth,
         Captcha,
+        TemplateEmail,
         SessionS,
         SessionFailedAuthCount,
+        SessionNewDevice,
+        SessionLoginCode,
         MfaAuthenticate,
+        UserEmailConfirmation,
+        Audit,
         UserRepo,
         SessionRepo,
+        PersonalAccessTokenRepo,
+        OAuth2ServerRepo,
     >
 where
     Db: Database,
     Auth: AuthService<Db::Transaction>,
     Captcha: CaptchaService,
+    TemplateEmail: TemplateEmailService,
     SessionS: SessionService<Db::Transaction>,
     SessionFailedAuthCount: SessionFailedAuthCountService,
+    SessionNewDevice: SessionNewDeviceService<Db::Transaction>,
+    SessionLoginCode: SessionLoginCodeService,
     MfaAuthenticate: MfaAuthenticateService<Db::Transaction>,
+    UserEmailConfirmation: UserEmailConfirmationService<Db::Transaction>,
+    Audit: AuditService<Db::Transaction>,
     UserRepo: UserRepository<Db::Transaction>,
     SessionRepo: SessionRepository<Db::Transaction>,
+    PersonalAccessTokenRepo: PersonalAccessTokenRepository<Db::Transaction>,
+    OAuth2ServerRepo: OAuth2ServerRepository<Db::Transaction>,
 {
     #[trace_instrument(skip(self))]
     async fn get_current_session(
         &self,
         token: &AccessToken,
     ) -> Result<Session, SessionGetCurrentError> {
-        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
+        let session_id = auth.ensure_session().map_auth_err()?;
 
         let mut txn = self.db.begin_transaction().await?;
 
         self.session_repo
-            .get(&mut txn, auth.session_id)
+            .get(&mut txn, session_id)
             .await?
             .ok_or_else(|| anyhow!("Failed to get authenticated session").into())
     }
@@ -108,18 +170,23 @@ where
         &self,
         token: &AccessToken,
         user_id: UserIdOrSelf,
-    ) -> Result<Vec<Session>, SessionListByUserError> {
-        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+    ) -> Result<SessionListByUserResult, SessionListByUserError> {
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
         let user_id = user_id.unwrap_or(auth.user_id);
         auth.ensure_self_or_admin(user_id).map_auth_err()?;
 
         let mut txn = self.db.begin_transaction().await?;
 
-        self.session_repo
+        let sessions = self
+            .session_repo
             .list_by_user(&mut txn, user_id)
             .await
-            .context("Failed to get sessions from database")
-            .map_err(Into::into)
+            .context("Failed to get sessions from database")?;
+
+        Ok(SessionListByUserResult {
+            sessions,
+            current_session_id: auth.ensure_session().ok(),
+        })
     }
 
     #[trace_instrument(skip(self))]
@@ -128,9 +195,27 @@ where
         cmd: SessionCreateCommand,
         recaptcha_response: Option<RecaptchaResponse>,
     ) -> Result<Login, SessionCreateError> {
+        let mut txn = self.db.begin_transaction().await?;
+
+        let user_composite = self
+            .user_repo
+            .get_composite_by_name_or_email(&mut txn, &cmd.name_or_email)
+            .await
+            .context("Failed to get user from database")?;
+        let login = failed_auth_count_login(user_composite.as_ref(), &cmd.name_or_email);
+
+        if let Some(retry_after) = self
+            .session_failed_auth_count
+            .get_lockout(&login, cmd.ip)
+            .await
+            .context("Failed to get lockout")?
+        {
+            return Err(SessionCreateError::Locked { retry_after });
+        }
+
         let failed_login_attempts = self
             .session_failed_auth_count
-            .get(&cmd.name_or_email)
+            .get(&login)
             .await
             .context("Failed to get failed auth count")?;
 
@@ -144,38 +229,13 @@ where
                 })?;
         }
 
-        let mut txn = self.db.begin_transaction().await?;
-
-        let mut user_composite = match self
-            .user_repo
-            .get_composite_by_name_or_email(&mut txn, &cmd.name_or_email)
-            .await
-            .context("Failed to get user from database")?
-        {
-            Some(user_composite) => user_composite,
-            None => {
-                self.session_failed_auth_count
-                    .increment(&cmd.name_or_email)
-                    .await
-                    .context("Failed to increment failed auth count")?;
-                return Err(SessionCreateError::InvalidCredentials);
-            }
-        };
-
-        let increment_failed_login_attempts = || async {
+        let Some(user_composite) = user_composite else {
             self.session_failed_auth_count
-                .increment(&UserNameOrEmailAddress::Name(
-                    user_composite.user.name.clone(),
-                ))
+                .increment(&login)
                 .await
-                .context("Failed to increment failed auth count for name")?;
-            if let Some(email) = user_composite.user.email.clone() {
-                self.session_failed_auth_count
-                    .increment(&UserNameOrEmailAddress::Email(email))
-                    .await
-                    .context("Failed to increment failed auth count for email")?;
-            }
-            anyhow::Ok(())
+                .context("Failed to increment failed auth count")?;
+            self.increment_failed_auth_count_ip(cmd.ip).await?;
+            return Err(SessionCreateError::InvalidCredentials);
         };
 
         match self
@@ -185,7 +245,8 @@ where
         {
             Ok(()) => {}
             Err(AuthenticateByPasswordError::InvalidCredentials) => {
-                increment_failed_login_attempts().await?;
+                self.increment_failed_auth_count_user(&user_composite, cmd.ip)
+                    .await?;
                 return Err(SessionCreateError::InvalidCredentials);
             }
             Err(AuthenticateByPasswordError::Other(err)) => {
@@ -202,9 +263,9 @@ where
                 .await
             {
                 Ok(MfaAuthenticateResult::Ok | MfaAuthenticateResult::Disabled) => (),
-                Ok(MfaAuthenticateResult::Reset) => user_composite.details.mfa_enabled = false,
                 Err(MfaAuthenticateError::Failed) => {
-                    increment_failed_login_attempts().await?;
+                    self.increment_failed_auth_count_user(&user_composite, cmd.ip)
+                        .await?;
                     return Err(SessionCreateError::MfaFailed);
                 }
                 Err(MfaAuthenticateError::Other(err)) => {
@@ -213,31 +274,202 @@ where
             }
         }
 
-        self.session_failed_auth_count
-            .reset(&UserNameOrEmailAddress::Name(
-                user_composite.user.name.clone(),
-            ))
+        self.reset_failed_auth_count_user(&user_composite).await?;
+
+        if !user_composite.user.is_active() {
+            return Err(SessionCreateError::UserDisabled);
+        }
+
+        let login = self
+            .session
+            .create(
+                &mut txn,
+                user_composite,
+                cmd.device_name,
+                cmd.ip,
+                SessionLoginMethod::Password,
+            )
+            .await
+            .context("Failed to create session")?;
+
+        let notify_new_device = self
+            .session_new_device
+            .handle_login(&mut txn, &login.user_composite, &login.session)
+            .await
+            .context("Failed to handle login from new device")?;
+
+        txn.commit().await?;
+
+        // the session has already been committed, so the login must not fail
+        // just because the notification cannot be sent
+        if notify_new_device {
+            if let Err(err) = self
+                .session_new_device
+                .notify(&login.user_composite, &login.session)
+                .await
+            {
+                warn!("Failed to notify user about login from new device: {err:?}");
+            }
+        }
+
+        Ok(login)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn request_login_code(
+        &self,
+        email: EmailAddress,
+        recaptcha_response: Option<RecaptchaResponse>,
+    ) -> Result<(), SessionRequestLoginCodeError> {
+        self.captcha
+            .check(recaptcha_response.as_deref().map(String::as_str))
+            .await
+            .map_err(|err| match err {
+                CaptchaCheckError::Failed => SessionRequestLoginCodeError::Recaptcha,
+                CaptchaCheckError::Other(err) => err.context("Failed to check captcha").into(),
+            })?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let Some(user_composite) = self
+            .user_repo
+            .get_composite_by_email(&mut txn, &email)
+            .await
+            .context("Failed to get user from database")?
+            .filter(|user_composite| user_composite.user.is_active())
+        else {
+            trace!("no enabled user with this email address");
+            return Ok(());
+        };
+
+        self.session_login_code
+            .send(
+                user_composite.user.id,
+                email.with_name(user_composite.profile.display_name.into_inner()),
+            )
             .await
-            .context("Failed to reset failed auth count for name")?;
-        if let Some(email) = user_composite.user.email.clone() {
+            .context("Failed to send login code")?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn create_session_by_login_code(
+        &self,
+        cmd: SessionCreateByLoginCodeCommand,
+        recaptcha_response: Option<RecaptchaResponse>,
+    ) -> Result<Login, SessionCreateByLoginCodeError> {
+        let mut txn = self.db.begin_transaction().await?;
+
+        let user_composite = self
+            .user_repo
+            .get_composite_by_email(&mut txn, &cmd.email)
+            .await
+            .context("Failed to get user from database")?;
+        let login = failed_auth_count_login(
+            user_composite.as_ref(),
+            &UserNameOrEmailAddress::Email(cmd.email.clone()),
+        );
+
+        if let Some(retry_after) = self
+            .session_failed_auth_count
+            .get_lockout(&login, cmd.ip)
+            .await
+            .context("Failed to get lockout")?
+        {
+            return Err(SessionCreateByLoginCodeError::Locked { retry_after });
+        }
+
+        let failed_login_attempts = self
+            .session_failed_auth_count
+            .get(&login)
+            .await
+            .context("Failed to get failed auth count")?;
+
+        if failed_login_attempts >= self.config.login_fails_before_captcha {
+            self.captcha
+                .check(recaptcha_response.as_deref().map(String::as_str))
+                .await
+                .map_err(|err| match err {
+                    CaptchaCheckError::Failed => SessionCreateByLoginCodeError::Recaptcha,
+                    CaptchaCheckError::Other(err) => err.context("Failed to check captcha").into(),
+                })?;
+        }
+
+        let Some(user_composite) = user_composite else {
             self.session_failed_auth_count
-                .reset(&UserNameOrEmailAddress::Email(email))
+                .increment(&login)
                 .await
-                .context("Failed to reset failed auth count for email")?;
+                .context("Failed to increment failed auth count")?;
+            self.increment_failed_auth_count_ip(cmd.ip).await?;
+            return Err(SessionCreateByLoginCodeError::InvalidCode);
+        };
+
+        if !self
+            .session_login_code
+            .redeem(user_composite.user.id, &cmd.code)
+            .await
+            .context("Failed to redeem login code")?
+        {
+            self.increment_failed_auth_count_user(&user_composite, cmd.ip)
+                .await?;
+            return Err(SessionCreateByLoginCodeError::InvalidCode);
         }
 
-        if !user_composite.user.enabled {
-            return Err(SessionCreateError::UserDisabled);
+        if user_composite.details.mfa_enabled {
+            match self
+                .mfa_authenticate
+                .authenticate(&mut txn, user_composite.user.id, cmd.mfa)
+                .await
+            {
+                Ok(MfaAuthenticateResult::Ok | MfaAuthenticateResult::Disabled) => (),
+                Err(MfaAuthenticateError::Failed) => {
+                    self.increment_failed_auth_count_user(&user_composite, cmd.ip)
+                        .await?;
+                    return Err(SessionCreateByLoginCodeError::MfaFailed);
+                }
+                Err(MfaAuthenticateError::Other(err)) => {
+                    return Err(err.context("Failed to perform MFA").into())
+                }
+            }
+        }
+
+        self.reset_failed_auth_count_user(&user_composite).await?;
+
+        if !user_composite.user.is_active() {
+            return Err(SessionCreateByLoginCodeError::UserDisabled);
         }
 
         let login = self
             .session
-            .create(&mut txn, user_composite, cmd.device_name, true)
+            .create(
+                &mut txn,
+                user_composite,
+                cmd.device_name,
+                cmd.ip,
+                SessionLoginMethod::LoginCode,
+            )
             .await
             .context("Failed to create session")?;
 
+        let notify_new_device = self
+            .session_new_device
+            .handle_login(&mut txn, &login.user_composite, &login.session)
+            .await
+            .context("Failed to handle login from new device")?;
+
         txn.commit().await?;
 
+        if notify_new_device {
+            if let Err(err) = self
+                .session_new_device
+                .notify(&login.user_composite, &login.session)
+                .await
+            {
+                warn!("Failed to notify user about login from new device: {err:?}");
+            }
+        }
+
         Ok(login)
     }
 
@@ -246,6 +478,7 @@ where
         &self,
         token: &AccessToken,
         user_id: UserId,
+        ctx: &AuditContext,
     ) -> Result<Login, SessionImpersonateError> {
         let auth = self.auth.authenticate(token).await.map_auth_err()?;
         auth.ensure_admin().map_auth_err()?;
@@ -261,10 +494,27 @@ where
 
         let login = self
             .session
-            .create(&mut txn, user_composite, None, false)
+            .create(
+                &mut txn,
+                user_composite,
+                None,
+                None,
+                SessionLoginMethod::Impersonation,
+            )
             .await
             .context("Failed to create session")?;
 
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::UserImpersonate,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
         txn.commit().await?;
 
         Ok(login)
@@ -274,6 +524,7 @@ where
     async fn refresh_session(
         &self,
         refresh_token: &RefreshToken,
+        ctx: &AuditContext,
     ) -> Result<Login, SessionRefreshError> {
         let mut txn = self.db.begin_transaction().await?;
 
@@ -293,6 +544,47 @@ where
                     .context("Failed to delete expired session")?;
                 return Err(SessionRefreshError::InvalidRefreshToken);
             }
+            Err(AuthenticateByRefreshTokenError::Reused(session_id)) => {
+                // either the legitimate client or an attacker is using a stolen refresh
+                // token, so we revoke the session and all of its tokens
+
+                let session = self
+                    .session_repo
+                    .get(&mut txn, session_id)
+                    .await
+                    .context("Failed to get session from database")?
+                    .ok_or(SessionRefreshError::InvalidRefreshToken)?;
+
+                warn!(
+                    "Refresh token reuse detected, revoking session {}",
+                    *session.id
+                );
+
+                self.auth
+                    .invalidate_access_tokens(&mut txn, session.user_id)
+                    .await
+                    .context("Failed to invalidate access tokens")?;
+
+                self.session
+                    .delete(&mut txn, session.id)
+                    .await
+                    .context("Failed to delete session")?;
+
+                self.audit
+                    .record(
+                        &mut txn,
+                        ctx,
+                        None,
+                        session.user_id,
+                        AuditAction::RefreshTokenReuse,
+                    )
+                    .await
+                    .context("Failed to record audit event")?;
+
+                txn.commit().await?;
+
+                return Err(SessionRefreshError::InvalidRefreshToken);
+            }
             Err(AuthenticateByRefreshTokenError::Other(err)) => {
                 return Err(err
                     .context("Failed to authenticate by refresh token")
@@ -302,7 +594,7 @@ where
 
         let login = self
             .session
-            .refresh(&mut txn, session_id)
+            .refresh(&mut txn, session_id, ctx.ip)
             .await
             .map_err(|err| {
                 use academy_core_session_contracts::session::SessionRefreshError as E;
@@ -354,11 +646,12 @@ where
         token: &AccessToken,
     ) -> Result<(), SessionDeleteCurrentError> {
         let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        let session_id = auth.ensure_session().map_auth_err()?;
 
         let mut txn = self.db.begin_transaction().await?;
 
         self.session
-            .delete(&mut txn, auth.session_id)
+            .delete(&mut txn, session_id)
             .await
             .context("Failed to delete session")?;
 
@@ -388,4 +681,215 @@ where
 
         Ok(())
     }
+
+    #[trace_instrument(skip(self))]
+    async fn unlock_user(
+        &self,
+        token: &AccessToken,
+        user_id: UserId,
+        ctx: &AuditContext,
+    ) -> Result<(), SessionUnlockUserError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_admin().map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        if !self
+            .user_repo
+            .exists(&mut txn, user_id)
+            .await
+            .context("Failed to check user existence")?
+        {
+            return Err(SessionUnlockUserError::NotFound);
+        }
+
+        self.session_failed_auth_count
+            .reset(&FailedAuthCountLogin::User(user_id))
+            .await
+            .context("Failed to reset failed auth count")?;
+
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::UserUnlock,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn revoke_new_device_session(
+        &self,
+        code: VerificationCode,
+        ctx: &AuditContext,
+    ) -> Result<(), SessionRevokeNewDeviceSessionError> {
+        let user_id = self
+            .session_new_device
+            .get_revoke_code(&code)
+            .await
+            .context("Failed to get revoke code")?
+            .ok_or(SessionRevokeNewDeviceSessionError::InvalidCode)?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let user_composite = self
+            .user_repo
+            .get_composite(&mut txn, user_id)
+            .await
+            .context("Failed to get user from database")?
+            .ok_or(SessionRevokeNewDeviceSessionError::InvalidCode)?;
+
+        self.personal_access_token_repo
+            .delete_by_user(&mut txn, user_id)
+            .await
+            .context("Failed to delete personal access tokens from database")?;
+
+        self.oauth2_server_repo
+            .delete_grants_by_user(&mut txn, user_id)
+            .await
+            .context("Failed to delete OAuth2 grants from database")?;
+
+        self.session
+            .delete_by_user(&mut txn, user_id)
+            .await
+            .context("Failed to delete sessions")?;
+
+        self.user_repo
+            .remove_password_hash(&mut txn, user_id)
+            .await
+            .context("Failed to remove password hash from database")?;
+
+        self.audit
+            .record(&mut txn, ctx, None, user_id, AuditAction::SessionRevoke)
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        self.session_new_device
+            .remove_revoke_code(&code)
+            .await
+            .context("Failed to remove revoke code")?;
+
+        if let Some(email) = user_composite.user.email {
+            self.user_email_confirmation
+                .request_password_reset(
+                    user_id,
+                    email.with_name(user_composite.profile.display_name.into_inner()),
+                )
+                .await
+                .context("Failed to request password reset")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<
+        Db,
+        Auth,
+        Captcha,
+        TemplateEmail,
+        SessionS,
+        SessionFailedAuthCount,
+        SessionNewDevice,
+        SessionLoginCode,
+        MfaAuthenticate,
+        UserEmailConfirmation,
+        Audit,
+        UserRepo,
+        SessionRepo,
+        PersonalAccessTokenRepo,
+        OAuth2ServerRepo,
+    >
+    SessionFeatureServiceImpl<
+        Db,
+        Auth,
+        Captcha,
+        TemplateEmail,
+        SessionS,
+        SessionFailedAuthCount,
+        SessionNewDevice,
+        SessionLoginCode,
+        MfaAuthenticate,
+        UserEmailConfirmation,
+        Audit,
+        UserRepo,
+        SessionRepo,
+        PersonalAccessTokenRepo,
+        OAuth2ServerRepo,
+    >
+where
+    TemplateEmail: TemplateEmailService,
+    SessionFailedAuthCount: SessionFailedAuthCountService,
+{
+    async fn increment_failed_auth_count_ip(&self, ip: Option<IpAddr>) -> anyhow::Result<()> {
+        if let Some(ip) = ip {
+            self.session_failed_auth_count
+                .increment_ip(ip)
+                .await
+                .context("Failed to increment failed auth count for ip")?;
+        }
+        Ok(())
+    }
+
+    /// Increment the failed auth counts for the given user account and the
+    /// client ip address and notify the user if their account has been locked
+    /// as a result.
+    async fn increment_failed_auth_count_user(
+        &self,
+        user_composite: &UserComposite,
+        ip: Option<IpAddr>,
+    ) -> anyhow::Result<()> {
+        let lockout = self
+            .session_failed_auth_count
+            .increment(&FailedAuthCountLogin::User(user_composite.user.id))
+            .await
+            .context("Failed to increment failed auth count")?;
+        self.increment_failed_auth_count_ip(ip).await?;
+
+        if let Some((lockout, email)) = lockout.zip(user_composite.user.email.clone()) {
+            self.template_email
+                .send_account_locked_email(
+                    email.with_name(user_composite.profile.display_name.clone().into_inner()),
+                    &AccountLockedTemplate {
+                        minutes: lockout.as_secs().div_ceil(60),
+                    },
+                )
+                .await
+                .context("Failed to send account locked email")?;
+        }
+
+        Ok(())
+    }
+
+    async fn reset_failed_auth_count_user(
+        &self,
+        user_composite: &UserComposite,
+    ) -> anyhow::Result<()> {
+        self.session_failed_auth_count
+            .reset(&FailedAuthCountLogin::User(user_composite.user.id))
+            .await
+            .context("Failed to reset failed auth count")
+    }
+}
+
+/// Count failed authentication attempts per user account once the login has
+/// been resolved, so that switching between the name and the email addresses
+/// of a user does not circumvent the lockout.
+fn failed_auth_count_login(
+    user_composite: Option<&UserComposite>,
+    name_or_email: &UserNameOrEmailAddress,
+) -> FailedAuthCountLogin {
+    match user_composite {
+        Some(user_composite) => FailedAuthCountLogin::User(user_composite.user.id),
+        None => FailedAuthCountLogin::Unknown(name_or_email.clone()),
+    }
 }
@@ -0,0 +1,204 @@
+use academy_cache_contracts::CacheService;
+use academy_core_session_contracts::login_code::SessionLoginCodeService;
+use academy_di::Build;
+use academy_email_contracts::template::TemplateEmailService;
+use academy_models::{
+    email_address::EmailAddressWithName, session::SessionLoginCode, user::UserId,
+};
+use academy_shared_contracts::secret::SecretService;
+use academy_templates_contracts::LoginCodeTemplate;
+use academy_utils::trace_instrument;
+use anyhow::Context;
+use url::Url;
+
+use crate::SessionFeatureConfig;
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct SessionLoginCodeServiceImpl<Secret, Cache, TemplateEmail> {
+    secret: Secret,
+    cache: Cache,
+    template_email: TemplateEmail,
+    config: SessionFeatureConfig,
+}
+
+impl<Secret, Cache, TemplateEmail> SessionLoginCodeService
+    for SessionLoginCodeServiceImpl<Secret, Cache, TemplateEmail>
+where
+    Secret: SecretService,
+    Cache: CacheService,
+    TemplateEmail: TemplateEmailService,
+{
+    #[trace_instrument(skip(self))]
+    async fn send(&self, user_id: UserId, email: EmailAddressWithName) -> anyhow::Result<()> {
+        let code = self.secret.generate_session_login_code();
+
+        self.cache
+            .set(
+                &login_code_cache_key(user_id),
+                &code,
+                Some(self.config.login_code_ttl),
+            )
+            .await
+            .context("Failed to save login code in cache")?;
+
+        let url = Url::parse_with_params(
+            &self.config.login_code_redirect_url,
+            [
+                ("email", email.clone().into_email_address().as_str()),
+                ("code", code.as_str()),
+            ],
+        )
+        .context("Failed to build login link")?;
+
+        self.template_email
+            .send_login_code_email(
+                email,
+                &LoginCodeTemplate {
+                    code: code.into_inner(),
+                    url: url.into(),
+                },
+            )
+            .await
+            .context("Failed to send login code email")?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn redeem(&self, user_id: UserId, code: &SessionLoginCode) -> anyhow::Result<bool> {
+        let expected_code = self
+            .cache
+            .take::<SessionLoginCode>(&login_code_cache_key(user_id))
+            .await
+            .context("Failed to take expected login code from cache")?;
+
+        Ok(expected_code.as_ref() == Some(code))
+    }
+}
+
+fn login_code_cache_key(user_id: UserId) -> String {
+    format!("session_login_code:{}", user_id.hyphenated())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use academy_cache_contracts::MockCacheService;
+    use academy_demo::user::FOO;
+    use academy_email_contracts::template::MockTemplateEmailService;
+    use academy_shared_contracts::secret::MockSecretService;
+
+    use super::*;
+
+    type Sut =
+        SessionLoginCodeServiceImpl<MockSecretService, MockCacheService, MockTemplateEmailService>;
+
+    fn code() -> SessionLoginCode {
+        SessionLoginCode::try_new("012345").unwrap()
+    }
+
+    #[tokio::test]
+    async fn send() {
+        // Arrange
+        let email = FOO
+            .user
+            .email
+            .clone()
+            .unwrap()
+            .with_name(FOO.profile.display_name.clone().into_inner());
+
+        let secret = MockSecretService::new().with_generate_session_login_code(code());
+
+        let cache = MockCacheService::new().with_set(
+            format!("session_login_code:{}", FOO.user.id.hyphenated()),
+            code(),
+            Some(Duration::from_secs(15 * 60)),
+        );
+
+        let template_email = MockTemplateEmailService::new().with_send_login_code_email(
+            email.clone(),
+            LoginCodeTemplate {
+                code: "012345".into(),
+                url:
+                    "https://bootstrap.academy/auth/login-code?email=foo%40example.com&code=012345"
+                        .into(),
+            },
+            true,
+        );
+
+        let sut = SessionLoginCodeServiceImpl {
+            secret,
+            cache,
+            template_email,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.send(FOO.user.id, email).await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn redeem_ok() {
+        // Arrange
+        let cache = MockCacheService::new().with_take(
+            format!("session_login_code:{}", FOO.user.id.hyphenated()),
+            Some(code()),
+        );
+
+        let sut = SessionLoginCodeServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.redeem(FOO.user.id, &code()).await;
+
+        // Assert
+        assert!(result.unwrap());
+    }
+
+    #[tokio::test]
+    async fn redeem_invalid() {
+        // Arrange
+        let cache = MockCacheService::new().with_take(
+            format!("session_login_code:{}", FOO.user.id.hyphenated()),
+            Some(SessionLoginCode::try_new("999999").unwrap()),
+        );
+
+        let sut = SessionLoginCodeServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.redeem(FOO.user.id, &code()).await;
+
+        // Assert
+        assert!(!result.unwrap());
+    }
+
+    #[tokio::test]
+    async fn redeem_no_code() {
+        // Arrange
+        let cache = MockCacheService::new().with_take(
+            format!("session_login_code:{}", FOO.user.id.hyphenated()),
+            None::<SessionLoginCode>,
+        );
+
+        let sut = SessionLoginCodeServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.redeem(FOO.user.id, &code()).await;
+
+        // Assert
+        assert!(!result.unwrap());
+    }
+}
@@ -0,0 +1,305 @@
+use academy_cache_contracts::CacheService;
+use academy_core_session_contracts::new_device::SessionNewDeviceService;
+use academy_di::Build;
+use academy_email_contracts::template::TemplateEmailService;
+use academy_models::{
+    session::Session,
+    user::{UserComposite, UserId},
+    VerificationCode,
+};
+use academy_persistence_contracts::session::SessionRepository;
+use academy_shared_contracts::secret::SecretService;
+use academy_templates_contracts::NewSignInTemplate;
+use academy_utils::trace_instrument;
+use anyhow::Context;
+
+use crate::SessionFeatureConfig;
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct SessionNewDeviceServiceImpl<Secret, Cache, TemplateEmail, SessionRepo> {
+    secret: Secret,
+    cache: Cache,
+    template_email: TemplateEmail,
+    session_repo: SessionRepo,
+    config: SessionFeatureConfig,
+}
+
+impl<Txn, Secret, Cache, TemplateEmail, SessionRepo> SessionNewDeviceService<Txn>
+    for SessionNewDeviceServiceImpl<Secret, Cache, TemplateEmail, SessionRepo>
+where
+    Txn: Send + Sync + 'static,
+    Secret: SecretService,
+    Cache: CacheService,
+    TemplateEmail: TemplateEmailService,
+    SessionRepo: SessionRepository<Txn>,
+{
+    #[trace_instrument(skip(self, txn))]
+    async fn handle_login(
+        &self,
+        txn: &mut Txn,
+        user_composite: &UserComposite,
+        session: &Session,
+    ) -> anyhow::Result<bool> {
+        let user_id = user_composite.user.id;
+        let fingerprint = session.device_fingerprint;
+
+        let notify = self
+            .session_repo
+            .has_known_devices(txn, user_id)
+            .await
+            .context("Failed to check for known devices in database")?
+            && !self
+                .session_repo
+                .is_known_device(txn, user_id, fingerprint, session.ip)
+                .await
+                .context("Failed to check for known device in database")?;
+
+        self.session_repo
+            .save_known_device(txn, user_id, fingerprint, session.ip)
+            .await
+            .context("Failed to save known device in database")?;
+
+        Ok(notify)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn notify(
+        &self,
+        user_composite: &UserComposite,
+        session: &Session,
+    ) -> anyhow::Result<()> {
+        let Some(email) = user_composite.user.email.clone() else {
+            return Ok(());
+        };
+
+        let code = self.secret.generate_verification_code();
+        self.cache
+            .set(
+                &revoke_code_cache_key(&code),
+                &user_composite.user.id,
+                Some(self.config.new_device_revoke_code_ttl),
+            )
+            .await
+            .context("Failed to save revoke code in cache")?;
+
+        self.template_email
+            .send_new_sign_in_email(
+                email.with_name(user_composite.profile.display_name.clone().into_inner()),
+                &NewSignInTemplate {
+                    device_name: session.device_name.clone().map(|x| x.into_inner()),
+                    ip: session.ip.map(|x| x.to_string()),
+                    time: session.created_at.format("%d.%m.%Y %H:%M UTC").to_string(),
+                    url: format!(
+                        "{}?code={}",
+                        self.config.new_device_revoke_redirect_url,
+                        code.into_inner()
+                    ),
+                },
+            )
+            .await
+            .context("Failed to send new sign-in email")?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn get_revoke_code(&self, code: &VerificationCode) -> anyhow::Result<Option<UserId>> {
+        self.cache
+            .get(&revoke_code_cache_key(code))
+            .await
+            .context("Failed to get revoke code from cache")
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn remove_revoke_code(&self, code: &VerificationCode) -> anyhow::Result<()> {
+        self.cache
+            .remove(&revoke_code_cache_key(code))
+            .await
+            .context("Failed to remove revoke code from cache")
+    }
+}
+
+fn revoke_code_cache_key(code: &VerificationCode) -> String {
+    format!("session_revoke_code:{}", **code)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use academy_cache_contracts::MockCacheService;
+    use academy_demo::{
+        session::{BAR_1, FOO_1},
+        user::{BAR, FOO},
+        VERIFICATION_CODE_1,
+    };
+    use academy_email_contracts::template::MockTemplateEmailService;
+    use academy_persistence_contracts::session::MockSessionRepository;
+    use academy_shared_contracts::secret::MockSecretService;
+
+    use super::*;
+
+    type Sut = SessionNewDeviceServiceImpl<
+        MockSecretService,
+        MockCacheService,
+        MockTemplateEmailService,
+        MockSessionRepository<()>,
+    >;
+
+    #[tokio::test]
+    async fn handle_login_known_device() {
+        // Arrange
+        let session_repo = MockSessionRepository::new()
+            .with_has_known_devices(FOO.user.id, true)
+            .with_is_known_device(FOO.user.id, FOO_1.device_fingerprint, FOO_1.ip, true)
+            .with_save_known_device(FOO.user.id, FOO_1.device_fingerprint, FOO_1.ip);
+
+        let sut = SessionNewDeviceServiceImpl {
+            session_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.handle_login(&mut (), &FOO, &FOO_1).await;
+
+        // Assert
+        assert!(!result.unwrap());
+    }
+
+    #[tokio::test]
+    async fn handle_login_first_device() {
+        // Arrange
+        let session_repo = MockSessionRepository::new()
+            .with_has_known_devices(FOO.user.id, false)
+            .with_save_known_device(FOO.user.id, FOO_1.device_fingerprint, FOO_1.ip);
+
+        let sut = SessionNewDeviceServiceImpl {
+            session_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.handle_login(&mut (), &FOO, &FOO_1).await;
+
+        // Assert
+        assert!(!result.unwrap());
+    }
+
+    #[tokio::test]
+    async fn handle_login_new_device() {
+        // Arrange
+        let session_repo = MockSessionRepository::new()
+            .with_has_known_devices(FOO.user.id, true)
+            .with_is_known_device(FOO.user.id, FOO_1.device_fingerprint, FOO_1.ip, false)
+            .with_save_known_device(FOO.user.id, FOO_1.device_fingerprint, FOO_1.ip);
+
+        let sut = SessionNewDeviceServiceImpl {
+            session_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.handle_login(&mut (), &FOO, &FOO_1).await;
+
+        // Assert
+        assert!(result.unwrap());
+    }
+
+    #[tokio::test]
+    async fn notify() {
+        // Arrange
+        let secret =
+            MockSecretService::new().with_generate_verification_code(VERIFICATION_CODE_1.clone());
+
+        let cache = MockCacheService::new().with_set(
+            format!("session_revoke_code:{}", **VERIFICATION_CODE_1),
+            FOO.user.id,
+            Some(Duration::from_secs(7 * 24 * 3600)),
+        );
+
+        let template_email = MockTemplateEmailService::new().with_send_new_sign_in_email(
+            FOO.user
+                .email
+                .clone()
+                .unwrap()
+                .with_name(FOO.profile.display_name.clone().into_inner()),
+            NewSignInTemplate {
+                device_name: FOO_1.device_name.clone().map(|x| x.into_inner()),
+                ip: FOO_1.ip.map(|x| x.to_string()),
+                time: FOO_1.created_at.format("%d.%m.%Y %H:%M UTC").to_string(),
+                url: format!(
+                    "https://bootstrap.academy/auth/revoke-session?code={}",
+                    **VERIFICATION_CODE_1
+                ),
+            },
+            true,
+        );
+
+        let sut = SessionNewDeviceServiceImpl {
+            secret,
+            cache,
+            template_email,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.notify(&FOO, &FOO_1).await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn notify_no_email() {
+        // Arrange
+        let mut user_composite = BAR.clone();
+        user_composite.user.email = None;
+
+        let sut = Sut::default();
+
+        // Act
+        let result = sut.notify(&user_composite, &BAR_1).await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn get_revoke_code() {
+        // Arrange
+        let cache = MockCacheService::new().with_get(
+            format!("session_revoke_code:{}", **VERIFICATION_CODE_1),
+            Some(FOO.user.id),
+        );
+
+        let sut = SessionNewDeviceServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.get_revoke_code(&VERIFICATION_CODE_1).await;
+
+        // Assert
+        assert_eq!(result.unwrap(), Some(FOO.user.id));
+    }
+
+    #[tokio::test]
+    async fn remove_revoke_code() {
+        // Arrange
+        let cache = MockCacheService::new()
+            .with_remove(format!("session_revoke_code:{}", **VERIFICATION_CODE_1));
+
+        let sut = SessionNewDeviceServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.remove_revoke_code(&VERIFICATION_CODE_1).await;
+
+        // Assert
+        result.unwrap();
+    }
+}
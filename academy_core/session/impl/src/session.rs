@@ -1,32 +1,50 @@
+use std::net::IpAddr;
+
 use academy_auth_contracts::{access_token::AuthAccessTokenService, AuthService};
 use academy_core_session_contracts::session::{SessionRefreshError, SessionService};
 use academy_di::Build;
 use academy_models::{
     auth::Login,
-    session::{DeviceName, Session, SessionId, SessionPatch},
+    session::{DeviceName, Session, SessionId, SessionLoginMethod, SessionPatch},
     user::{UserComposite, UserId, UserPatch},
 };
 use academy_persistence_contracts::{session::SessionRepository, user::UserRepository};
-use academy_shared_contracts::{id::IdService, time::TimeService};
+use academy_shared_contracts::{
+    hash::HashService, id::IdService, time::TimeService, user_agent::UserAgentService,
+};
 use academy_utils::{patch::Patch, trace_instrument};
 use anyhow::Context;
 
 #[derive(Debug, Clone, Build, Default)]
-pub struct SessionServiceImpl<Id, Time, Auth, AuthAccessToken, SessionRepo, UserRepo> {
+pub struct SessionServiceImpl<
+    Id,
+    Time,
+    Hash,
+    UserAgent,
+    Auth,
+    AuthAccessToken,
+    SessionRepo,
+    UserRepo,
+> {
     id: Id,
     time: Time,
+    hash: Hash,
+    user_agent: UserAgent,
     auth: Auth,
     auth_access_token: AuthAccessToken,
     session_repo: SessionRepo,
     user_repo: UserRepo,
 }
 
-impl<Txn, Id, Time, Auth, AuthAccessToken, SessionRepo, UserRepo> SessionService<Txn>
-    for SessionServiceImpl<Id, Time, Auth, AuthAccessToken, SessionRepo, UserRepo>
+impl<Txn, Id, Time, Hash, UserAgent, Auth, AuthAccessToken, SessionRepo, UserRepo>
+    SessionService<Txn>
+    for SessionServiceImpl<Id, Time, Hash, UserAgent, Auth, AuthAccessToken, SessionRepo, UserRepo>
 where
     Txn: Send + Sync + 'static,
     Id: IdService,
     Time: TimeService,
+    Hash: HashService,
+    UserAgent: UserAgentService,
     Auth: AuthService<Txn>,
     AuthAccessToken: AuthAccessTokenService,
     SessionRepo: SessionRepository<Txn>,
@@ -38,22 +56,39 @@ where
         txn: &mut Txn,
         mut user_composite: UserComposite,
         device_name: Option<DeviceName>,
-        update_last_login: bool,
+        ip: Option<IpAddr>,
+        login_method: SessionLoginMethod,
     ) -> anyhow::Result<Login> {
         let id = self.id.generate();
         let now = self.time.now();
 
+        let device_fingerprint = device_name
+            .as_ref()
+            .map(|device_name| self.hash.sha256(&device_name.clone().into_inner()).into());
+        let user_agent = device_name
+            .as_ref()
+            .map(|device_name| self.user_agent.parse(device_name))
+            .unwrap_or_default();
+        let update_last_login = login_method != SessionLoginMethod::Impersonation;
+
         let session = Session {
             id,
             user_id: user_composite.user.id,
             device_name,
+            ip,
+            device_fingerprint,
+            last_ip: ip,
+            browser: user_agent.browser,
+            os: user_agent.os,
+            login_method,
             created_at: now,
             updated_at: now,
         };
 
         let tokens = self
             .auth
-            .issue_tokens(&user_composite.user, session.id)
+            .issue_tokens(txn, &user_composite.user, session.id)
+            .await
             .context("Failed to issue tokens")?;
 
         self.session_repo
@@ -87,6 +122,7 @@ where
         &self,
         txn: &mut Txn,
         session_id: SessionId,
+        ip: Option<IpAddr>,
     ) -> Result<Login, SessionRefreshError> {
         // get session and user from database
         let refresh_token_hash = self
@@ -119,11 +155,16 @@ where
         // issue new token pair
         let tokens = self
             .auth
-            .issue_tokens(&user_composite.user, session_id)
+            .issue_tokens(txn, &user_composite.user, session_id)
+            .await
             .context("Failed to issue tokens")?;
 
         // update session
-        let patch = SessionPatch::new().update_updated_at(self.time.now());
+        let now = self.time.now();
+        let mut patch = SessionPatch::new().update_updated_at(now);
+        if let Some(ip) = ip {
+            patch = patch.update_last_ip(Some(ip));
+        }
         self.session_repo
             .update(txn, session.id, patch.as_ref())
             .await
@@ -135,6 +176,12 @@ where
             .await
             .context("Failed to update session refresh token hash in database")?;
 
+        // remember the old refresh token to detect reuse
+        self.session_repo
+            .save_superseded_refresh_token_hash(txn, session.id, refresh_token_hash, now)
+            .await
+            .context("Failed to save superseded refresh token hash in database")?;
+
         Ok(Login {
             user_composite,
             session,
@@ -189,7 +236,12 @@ mod tests {
     use academy_demo::{session::FOO_1, user::FOO, SHA256HASH1, SHA256HASH2};
     use academy_models::user::{User, UserPatch};
     use academy_persistence_contracts::{session::MockSessionRepository, user::MockUserRepository};
-    use academy_shared_contracts::{id::MockIdService, time::MockTimeService};
+    use academy_shared_contracts::{
+        hash::MockHashService,
+        id::MockIdService,
+        time::MockTimeService,
+        user_agent::{MockUserAgentService, UserAgentInfo},
+    };
     use academy_utils::assert_matches;
 
     use super::*;
@@ -197,14 +249,18 @@ mod tests {
     type Sut = SessionServiceImpl<
         MockIdService,
         MockTimeService,
+        MockHashService,
+        MockUserAgentService,
         MockAuthService<()>,
         MockAuthAccessTokenService,
         MockSessionRepository<()>,
         MockUserRepository<()>,
     >;
 
+    const IP: IpAddr = IpAddr::V4(std::net::Ipv4Addr::new(203, 0, 113, 7));
+
     #[tokio::test]
-    async fn create_update_last_login() {
+    async fn create_password() {
         // Arrange
         let tokens = Tokens {
             access_token: "the access token".into(),
@@ -224,6 +280,12 @@ mod tests {
                 id: FOO_1.id,
                 user_id: FOO.user.id,
                 device_name: FOO_1.device_name.clone(),
+                ip: FOO_1.ip,
+                device_fingerprint: FOO_1.device_fingerprint,
+                last_ip: FOO_1.ip,
+                browser: FOO_1.browser.clone(),
+                os: FOO_1.os.clone(),
+                login_method: SessionLoginMethod::Password,
                 created_at: FOO_1.created_at,
                 updated_at: FOO_1.created_at,
             },
@@ -233,6 +295,14 @@ mod tests {
 
         let id = MockIdService::new().with_generate(FOO_1.id);
         let time = MockTimeService::new().with_now(FOO_1.created_at);
+        let hash = MockHashService::new().with_sha256("desktop".to_owned(), *SHA256HASH2);
+        let user_agent = MockUserAgentService::new().with_parse(
+            "desktop".into(),
+            UserAgentInfo {
+                browser: FOO_1.browser.clone(),
+                os: FOO_1.os.clone(),
+            },
+        );
         let auth =
             MockAuthService::new().with_issue_tokens(FOO.user.clone(), FOO_1.id, tokens.clone());
         let session_repo = MockSessionRepository::new()
@@ -248,6 +318,8 @@ mod tests {
         let sut = SessionServiceImpl {
             id,
             time,
+            hash,
+            user_agent,
             auth,
             session_repo,
             user_repo,
@@ -256,7 +328,13 @@ mod tests {
 
         // Act
         let result = sut
-            .create(&mut (), FOO.clone(), FOO_1.device_name.clone(), true)
+            .create(
+                &mut (),
+                FOO.clone(),
+                FOO_1.device_name.clone(),
+                FOO_1.ip,
+                SessionLoginMethod::Password,
+            )
             .await;
 
         // Assert
@@ -264,7 +342,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn create_dont_update_last_login() {
+    async fn create_impersonation() {
         // Arrange
         let tokens = Tokens {
             access_token: "the access token".into(),
@@ -278,6 +356,12 @@ mod tests {
                 id: FOO_1.id,
                 user_id: FOO.user.id,
                 device_name: FOO_1.device_name.clone(),
+                ip: FOO_1.ip,
+                device_fingerprint: FOO_1.device_fingerprint,
+                last_ip: FOO_1.ip,
+                browser: FOO_1.browser.clone(),
+                os: FOO_1.os.clone(),
+                login_method: SessionLoginMethod::Impersonation,
                 created_at: FOO_1.created_at,
                 updated_at: FOO_1.created_at,
             },
@@ -287,6 +371,14 @@ mod tests {
 
         let id = MockIdService::new().with_generate(FOO_1.id);
         let time = MockTimeService::new().with_now(FOO_1.created_at);
+        let hash = MockHashService::new().with_sha256("desktop".to_owned(), *SHA256HASH2);
+        let user_agent = MockUserAgentService::new().with_parse(
+            "desktop".into(),
+            UserAgentInfo {
+                browser: FOO_1.browser.clone(),
+                os: FOO_1.os.clone(),
+            },
+        );
         let auth =
             MockAuthService::new().with_issue_tokens(FOO.user.clone(), FOO_1.id, tokens.clone());
         let session_repo = MockSessionRepository::new()
@@ -298,6 +390,8 @@ mod tests {
         let sut = SessionServiceImpl {
             id,
             time,
+            hash,
+            user_agent,
             auth,
             session_repo,
             user_repo,
@@ -306,7 +400,13 @@ mod tests {
 
         // Act
         let result = sut
-            .create(&mut (), FOO.clone(), FOO_1.device_name.clone(), false)
+            .create(
+                &mut (),
+                FOO.clone(),
+                FOO_1.device_name.clone(),
+                FOO_1.ip,
+                SessionLoginMethod::Impersonation,
+            )
             .await;
 
         // Assert
@@ -325,6 +425,7 @@ mod tests {
         let expected = Login {
             user_composite: FOO.clone(),
             session: Session {
+                last_ip: Some(IP),
                 updated_at: FOO_1.updated_at + Duration::from_secs(3600),
                 ..FOO_1.clone()
             },
@@ -346,10 +447,17 @@ mod tests {
             .with_get(FOO_1.id, Some(FOO_1.clone()))
             .with_update(
                 FOO_1.id,
-                SessionPatch::new().update_updated_at(expected.session.updated_at),
+                SessionPatch::new()
+                    .update_updated_at(expected.session.updated_at)
+                    .update_last_ip(Some(IP)),
                 true,
             )
-            .with_save_refresh_token_hash(FOO_1.id, (*SHA256HASH2).into());
+            .with_save_refresh_token_hash(FOO_1.id, (*SHA256HASH2).into())
+            .with_save_superseded_refresh_token_hash(
+                FOO_1.id,
+                (*SHA256HASH1).into(),
+                expected.session.updated_at,
+            );
 
         let sut = SessionServiceImpl {
             auth,
@@ -361,7 +469,7 @@ mod tests {
         };
 
         // Act
-        let result = sut.refresh(&mut (), FOO_1.id).await;
+        let result = sut.refresh(&mut (), FOO_1.id, Some(IP)).await;
 
         // Assert
         assert_eq!(result.unwrap(), expected);
@@ -378,7 +486,7 @@ mod tests {
         };
 
         // Act
-        let result = sut.refresh(&mut (), FOO_1.id).await;
+        let result = sut.refresh(&mut (), FOO_1.id, Some(IP)).await;
 
         // Assert
         assert_matches!(result, Err(SessionRefreshError::NotFound));
@@ -397,7 +505,7 @@ mod tests {
         };
 
         // Act
-        let result = sut.refresh(&mut (), FOO_1.id).await;
+        let result = sut.refresh(&mut (), FOO_1.id, Some(IP)).await;
 
         // Assert
         assert_matches!(result, Err(SessionRefreshError::NotFound));
@@ -418,7 +526,7 @@ mod tests {
         };
 
         // Act
-        let result = sut.refresh(&mut (), FOO_1.id).await;
+        let result = sut.refresh(&mut (), FOO_1.id, Some(IP)).await;
 
         // Assert
         assert_matches!(result, Err(SessionRefreshError::NotFound));
@@ -1,22 +1,36 @@
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    time::Duration,
+};
+
 use academy_auth_contracts::MockAuthService;
 use academy_core_mfa_contracts::authenticate::{
     MfaAuthenticateError, MfaAuthenticateResult, MockMfaAuthenticateService,
 };
 use academy_core_session_contracts::{
-    failed_auth_count::MockSessionFailedAuthCountService, session::MockSessionService,
+    failed_auth_count::{FailedAuthCountLogin, MockSessionFailedAuthCountService},
+    new_device::MockSessionNewDeviceService,
+    session::MockSessionService,
     SessionCreateCommand, SessionCreateError, SessionFeatureService,
 };
 use academy_demo::{
     session::{BAR_1, FOO_1},
-    user::{BAR, BAR_PASSWORD, FOO, FOO_PASSWORD},
+    user::{BAR, BAR_PASSWORD, FOO, FOO_EMAIL_1, FOO_PASSWORD},
+};
+use academy_email_contracts::template::MockTemplateEmailService;
+use academy_models::{
+    auth::Login, mfa::MfaAuthentication, session::SessionLoginMethod, user::UserNameOrEmailAddress,
 };
-use academy_models::{auth::Login, mfa::MfaAuthentication, user::UserNameOrEmailAddress};
-use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase, MockTransaction};
 use academy_shared_contracts::captcha::{CaptchaCheckError, MockCaptchaService};
+use academy_templates_contracts::AccountLockedTemplate;
 use academy_utils::{assert_matches, Apply};
+use anyhow::anyhow;
 
 use crate::{tests::Sut, SessionFeatureServiceImpl};
 
+const IP: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+
 #[tokio::test]
 async fn ok() {
     // Arrange
@@ -24,6 +38,7 @@ async fn ok() {
         name_or_email: UserNameOrEmailAddress::Name(FOO.user.name.clone()),
         password: FOO_PASSWORD.clone(),
         device_name: FOO_1.device_name.clone(),
+        ip: None,
         mfa: MfaAuthentication::default(),
     };
 
@@ -37,11 +52,9 @@ async fn ok() {
     let db = MockDatabase::build(true);
 
     let session_failed_auth_count = MockSessionFailedAuthCountService::new()
-        .with_get(cmd.name_or_email.clone(), 1)
-        .with_reset(UserNameOrEmailAddress::Name(FOO.user.name.clone()))
-        .with_reset(UserNameOrEmailAddress::Email(
-            FOO.user.email.clone().unwrap(),
-        ));
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 1)
+        .with_reset(FailedAuthCountLogin::User(FOO.user.id));
 
     let user_repo = MockUserRepository::new()
         .with_get_composite_by_name_or_email(cmd.name_or_email.clone(), Some(FOO.clone()));
@@ -55,15 +68,23 @@ async fn ok() {
     let session = MockSessionService::new().with_create(
         FOO.clone(),
         cmd.device_name.clone(),
-        true,
+        cmd.ip,
+        SessionLoginMethod::Password,
         expected.clone(),
     );
 
+    let session_new_device = MockSessionNewDeviceService::new().with_handle_login(
+        expected.user_composite.clone(),
+        expected.session.clone(),
+        false,
+    );
+
     let sut = SessionFeatureServiceImpl {
         db,
         session_failed_auth_count,
         auth,
         session,
+        session_new_device,
         user_repo,
         ..Sut::default()
     };
@@ -76,20 +97,18 @@ async fn ok() {
 }
 
 #[tokio::test]
-async fn ok_mfa() {
+async fn ok_new_device() {
     // Arrange
     let cmd = SessionCreateCommand {
         name_or_email: UserNameOrEmailAddress::Name(FOO.user.name.clone()),
         password: FOO_PASSWORD.clone(),
         device_name: FOO_1.device_name.clone(),
-        mfa: MfaAuthentication {
-            totp_code: Some("123456".try_into().unwrap()),
-            recovery_code: None,
-        },
+        ip: None,
+        mfa: MfaAuthentication::default(),
     };
 
     let expected = Login {
-        user_composite: FOO.clone().with(|u| u.details.mfa_enabled = true),
+        user_composite: FOO.clone(),
         session: FOO_1.clone(),
         access_token: "the access token".into(),
         refresh_token: "some refresh token".into(),
@@ -98,16 +117,12 @@ async fn ok_mfa() {
     let db = MockDatabase::build(true);
 
     let session_failed_auth_count = MockSessionFailedAuthCountService::new()
-        .with_get(cmd.name_or_email.clone(), 1)
-        .with_reset(UserNameOrEmailAddress::Name(FOO.user.name.clone()))
-        .with_reset(UserNameOrEmailAddress::Email(
-            FOO.user.email.clone().unwrap(),
-        ));
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 1)
+        .with_reset(FailedAuthCountLogin::User(FOO.user.id));
 
-    let user_repo = MockUserRepository::new().with_get_composite_by_name_or_email(
-        cmd.name_or_email.clone(),
-        Some(expected.user_composite.clone()),
-    );
+    let user_repo = MockUserRepository::new()
+        .with_get_composite_by_name_or_email(cmd.name_or_email.clone(), Some(FOO.clone()));
 
     let auth = MockAuthService::new().with_authenticate_by_password(
         FOO.user.id,
@@ -115,25 +130,234 @@ async fn ok_mfa() {
         true,
     );
 
-    let mfa_authenticate = MockMfaAuthenticateService::new().with_authenticate(
+    let session = MockSessionService::new().with_create(
+        FOO.clone(),
+        cmd.device_name.clone(),
+        cmd.ip,
+        SessionLoginMethod::Password,
+        expected.clone(),
+    );
+
+    let session_new_device = MockSessionNewDeviceService::new()
+        .with_handle_login(
+            expected.user_composite.clone(),
+            expected.session.clone(),
+            true,
+        )
+        .with_notify(expected.user_composite.clone(), expected.session.clone());
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        auth,
+        session,
+        session_new_device,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session(cmd, None).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn ok_new_device_notify_failed() {
+    // Arrange
+    let cmd = SessionCreateCommand {
+        name_or_email: UserNameOrEmailAddress::Name(FOO.user.name.clone()),
+        password: FOO_PASSWORD.clone(),
+        device_name: FOO_1.device_name.clone(),
+        ip: None,
+        mfa: MfaAuthentication::default(),
+    };
+
+    let expected = Login {
+        user_composite: FOO.clone(),
+        session: FOO_1.clone(),
+        access_token: "the access token".into(),
+        refresh_token: "some refresh token".into(),
+    };
+
+    let db = MockDatabase::build(true);
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new()
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 1)
+        .with_reset(FailedAuthCountLogin::User(FOO.user.id));
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite_by_name_or_email(cmd.name_or_email.clone(), Some(FOO.clone()));
+
+    let auth = MockAuthService::new().with_authenticate_by_password(
         FOO.user.id,
-        cmd.mfa.clone(),
-        Ok(MfaAuthenticateResult::Ok),
+        cmd.password.clone(),
+        true,
     );
 
     let session = MockSessionService::new().with_create(
+        FOO.clone(),
+        cmd.device_name.clone(),
+        cmd.ip,
+        SessionLoginMethod::Password,
+        expected.clone(),
+    );
+
+    let mut session_new_device = MockSessionNewDeviceService::new().with_handle_login(
         expected.user_composite.clone(),
+        expected.session.clone(),
+        true,
+    );
+    session_new_device
+        .expect_notify()
+        .once()
+        .return_once(|_, _| Box::pin(std::future::ready(Err(anyhow!("smtp failed")))));
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        auth,
+        session,
+        session_new_device,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session(cmd, None).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn commit_failed_no_new_device_notification() {
+    // Arrange
+    let cmd = SessionCreateCommand {
+        name_or_email: UserNameOrEmailAddress::Name(FOO.user.name.clone()),
+        password: FOO_PASSWORD.clone(),
+        device_name: FOO_1.device_name.clone(),
+        ip: None,
+        mfa: MfaAuthentication::default(),
+    };
+
+    let expected = Login {
+        user_composite: FOO.clone(),
+        session: FOO_1.clone(),
+        access_token: "the access token".into(),
+        refresh_token: "some refresh token".into(),
+    };
+
+    let mut txn = MockTransaction::new();
+    txn.expect_commit()
+        .once()
+        .return_once(|| Box::pin(std::future::ready(Err(anyhow!("commit failed")))));
+    let mut db = MockDatabase::new();
+    db.expect_begin_transaction()
+        .once()
+        .return_once(|| Box::pin(std::future::ready(Ok(txn))));
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new()
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 1)
+        .with_reset(FailedAuthCountLogin::User(FOO.user.id));
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite_by_name_or_email(cmd.name_or_email.clone(), Some(FOO.clone()));
+
+    let auth = MockAuthService::new().with_authenticate_by_password(
+        FOO.user.id,
+        cmd.password.clone(),
+        true,
+    );
+
+    let session = MockSessionService::new().with_create(
+        FOO.clone(),
         cmd.device_name.clone(),
+        cmd.ip,
+        SessionLoginMethod::Password,
+        expected.clone(),
+    );
+
+    let session_new_device = MockSessionNewDeviceService::new().with_handle_login(
+        expected.user_composite.clone(),
+        expected.session.clone(),
         true,
+    );
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        auth,
+        session,
+        session_new_device,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session(cmd, None).await;
+
+    // Assert
+    assert_matches!(result, Err(SessionCreateError::Other(_)));
+}
+
+#[tokio::test]
+async fn ok_secondary_email() {
+    // Arrange
+    let cmd = SessionCreateCommand {
+        name_or_email: UserNameOrEmailAddress::Email(FOO_EMAIL_1.email.clone()),
+        password: FOO_PASSWORD.clone(),
+        device_name: FOO_1.device_name.clone(),
+        ip: None,
+        mfa: MfaAuthentication::default(),
+    };
+
+    let expected = Login {
+        user_composite: FOO.clone(),
+        session: FOO_1.clone(),
+        access_token: "the access token".into(),
+        refresh_token: "some refresh token".into(),
+    };
+
+    let db = MockDatabase::build(true);
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new()
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 1)
+        .with_reset(FailedAuthCountLogin::User(FOO.user.id));
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite_by_name_or_email(cmd.name_or_email.clone(), Some(FOO.clone()));
+
+    let auth = MockAuthService::new().with_authenticate_by_password(
+        FOO.user.id,
+        cmd.password.clone(),
+        true,
+    );
+
+    let session = MockSessionService::new().with_create(
+        FOO.clone(),
+        cmd.device_name.clone(),
+        cmd.ip,
+        SessionLoginMethod::Password,
         expected.clone(),
     );
 
+    let session_new_device = MockSessionNewDeviceService::new().with_handle_login(
+        expected.user_composite.clone(),
+        expected.session.clone(),
+        false,
+    );
+
     let sut = SessionFeatureServiceImpl {
         db,
         session_failed_auth_count,
         auth,
         session,
-        mfa_authenticate,
+        session_new_device,
         user_repo,
         ..Sut::default()
     };
@@ -146,20 +370,22 @@ async fn ok_mfa() {
 }
 
 #[tokio::test]
-async fn ok_mfa_reset() {
+async fn ok_mfa() {
     // Arrange
     let cmd = SessionCreateCommand {
         name_or_email: UserNameOrEmailAddress::Name(FOO.user.name.clone()),
         password: FOO_PASSWORD.clone(),
         device_name: FOO_1.device_name.clone(),
+        ip: None,
         mfa: MfaAuthentication {
             totp_code: Some("123456".try_into().unwrap()),
             recovery_code: None,
+            webauthn_assertion: None,
         },
     };
 
     let expected = Login {
-        user_composite: FOO.clone(),
+        user_composite: FOO.clone().with(|u| u.details.mfa_enabled = true),
         session: FOO_1.clone(),
         access_token: "the access token".into(),
         refresh_token: "some refresh token".into(),
@@ -168,20 +394,13 @@ async fn ok_mfa_reset() {
     let db = MockDatabase::build(true);
 
     let session_failed_auth_count = MockSessionFailedAuthCountService::new()
-        .with_get(cmd.name_or_email.clone(), 1)
-        .with_reset(UserNameOrEmailAddress::Name(FOO.user.name.clone()))
-        .with_reset(UserNameOrEmailAddress::Email(
-            FOO.user.email.clone().unwrap(),
-        ));
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 1)
+        .with_reset(FailedAuthCountLogin::User(FOO.user.id));
 
     let user_repo = MockUserRepository::new().with_get_composite_by_name_or_email(
         cmd.name_or_email.clone(),
-        Some(
-            expected
-                .user_composite
-                .clone()
-                .with(|u| u.details.mfa_enabled = true),
-        ),
+        Some(expected.user_composite.clone()),
     );
 
     let auth = MockAuthService::new().with_authenticate_by_password(
@@ -193,21 +412,29 @@ async fn ok_mfa_reset() {
     let mfa_authenticate = MockMfaAuthenticateService::new().with_authenticate(
         FOO.user.id,
         cmd.mfa.clone(),
-        Ok(MfaAuthenticateResult::Reset),
+        Ok(MfaAuthenticateResult::Ok),
     );
 
     let session = MockSessionService::new().with_create(
         expected.user_composite.clone(),
         cmd.device_name.clone(),
-        true,
+        cmd.ip,
+        SessionLoginMethod::Password,
         expected.clone(),
     );
 
+    let session_new_device = MockSessionNewDeviceService::new().with_handle_login(
+        expected.user_composite.clone(),
+        expected.session.clone(),
+        false,
+    );
+
     let sut = SessionFeatureServiceImpl {
         db,
         session_failed_auth_count,
         auth,
         session,
+        session_new_device,
         mfa_authenticate,
         user_repo,
         ..Sut::default()
@@ -227,6 +454,7 @@ async fn ok_captcha() {
         name_or_email: UserNameOrEmailAddress::Name(FOO.user.name.clone()),
         password: FOO_PASSWORD.clone(),
         device_name: FOO_1.device_name.clone(),
+        ip: None,
         mfa: MfaAuthentication::default(),
     };
 
@@ -240,11 +468,9 @@ async fn ok_captcha() {
     let db = MockDatabase::build(true);
 
     let session_failed_auth_count = MockSessionFailedAuthCountService::new()
-        .with_get(cmd.name_or_email.clone(), 3)
-        .with_reset(UserNameOrEmailAddress::Name(FOO.user.name.clone()))
-        .with_reset(UserNameOrEmailAddress::Email(
-            FOO.user.email.clone().unwrap(),
-        ));
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 3)
+        .with_reset(FailedAuthCountLogin::User(FOO.user.id));
 
     let captcha = MockCaptchaService::new().with_check(Some("resp"), Ok(()));
 
@@ -260,16 +486,24 @@ async fn ok_captcha() {
     let session = MockSessionService::new().with_create(
         FOO.clone(),
         cmd.device_name.clone(),
-        true,
+        cmd.ip,
+        SessionLoginMethod::Password,
         expected.clone(),
     );
 
+    let session_new_device = MockSessionNewDeviceService::new().with_handle_login(
+        expected.user_composite.clone(),
+        expected.session.clone(),
+        false,
+    );
+
     let sut = SessionFeatureServiceImpl {
         db,
         session_failed_auth_count,
         captcha,
         auth,
         session,
+        session_new_device,
         user_repo,
         ..Sut::default()
     };
@@ -290,18 +524,27 @@ async fn invalid_recaptcha_response() {
         name_or_email: UserNameOrEmailAddress::Name(FOO.user.name.clone()),
         password: FOO_PASSWORD.clone(),
         device_name: FOO_1.device_name.clone(),
+        ip: None,
         mfa: MfaAuthentication::default(),
     };
 
-    let session_failed_auth_count =
-        MockSessionFailedAuthCountService::new().with_get(cmd.name_or_email.clone(), 4);
+    let db = MockDatabase::build(false);
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new()
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 4);
 
     let captcha =
         MockCaptchaService::new().with_check(Some("resp"), Err(CaptchaCheckError::Failed));
 
+    let user_repo = MockUserRepository::new()
+        .with_get_composite_by_name_or_email(cmd.name_or_email.clone(), Some(FOO.clone()));
+
     let sut = SessionFeatureServiceImpl {
+        db,
         session_failed_auth_count,
         captcha,
+        user_repo,
         ..Sut::default()
     };
 
@@ -321,14 +564,24 @@ async fn user_not_found() {
         name_or_email: UserNameOrEmailAddress::Name(FOO.user.name.clone()),
         password: FOO_PASSWORD.clone(),
         device_name: FOO_1.device_name.clone(),
+        ip: Some(IP),
         mfa: MfaAuthentication::default(),
     };
 
     let db = MockDatabase::build(false);
 
     let session_failed_auth_count = MockSessionFailedAuthCountService::new()
-        .with_get(cmd.name_or_email.clone(), 1)
-        .with_increment(cmd.name_or_email.clone());
+        .with_get_lockout(
+            FailedAuthCountLogin::Unknown(cmd.name_or_email.clone()),
+            cmd.ip,
+            None,
+        )
+        .with_get(FailedAuthCountLogin::Unknown(cmd.name_or_email.clone()), 1)
+        .with_increment(
+            FailedAuthCountLogin::Unknown(cmd.name_or_email.clone()),
+            None,
+        )
+        .with_increment_ip(IP);
 
     let user_repo = MockUserRepository::new()
         .with_get_composite_by_name_or_email(cmd.name_or_email.clone(), None);
@@ -354,17 +607,17 @@ async fn wrong_password() {
         name_or_email: UserNameOrEmailAddress::Name(FOO.user.name.clone()),
         password: FOO_PASSWORD.clone(),
         device_name: FOO_1.device_name.clone(),
+        ip: Some(IP),
         mfa: MfaAuthentication::default(),
     };
 
     let db = MockDatabase::build(false);
 
     let session_failed_auth_count = MockSessionFailedAuthCountService::new()
-        .with_get(cmd.name_or_email.clone(), 1)
-        .with_increment(UserNameOrEmailAddress::Name(FOO.user.name.clone()))
-        .with_increment(UserNameOrEmailAddress::Email(
-            FOO.user.email.clone().unwrap(),
-        ));
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 1)
+        .with_increment(FailedAuthCountLogin::User(FOO.user.id), None)
+        .with_increment_ip(IP);
 
     let user_repo = MockUserRepository::new().with_get_composite_by_name_or_email(
         cmd.name_or_email.clone(),
@@ -399,20 +652,20 @@ async fn mfa_failed() {
         name_or_email: UserNameOrEmailAddress::Name(FOO.user.name.clone()),
         password: FOO_PASSWORD.clone(),
         device_name: BAR_1.device_name.clone(),
+        ip: None,
         mfa: MfaAuthentication {
             totp_code: Some("123456".try_into().unwrap()),
             recovery_code: None,
+            webauthn_assertion: None,
         },
     };
 
     let db = MockDatabase::build(false);
 
     let session_failed_auth_count = MockSessionFailedAuthCountService::new()
-        .with_get(cmd.name_or_email.clone(), 1)
-        .with_increment(UserNameOrEmailAddress::Name(FOO.user.name.clone()))
-        .with_increment(UserNameOrEmailAddress::Email(
-            FOO.user.email.clone().unwrap(),
-        ));
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 1)
+        .with_increment(FailedAuthCountLogin::User(FOO.user.id), None);
 
     let user_repo = MockUserRepository::new().with_get_composite_by_name_or_email(
         cmd.name_or_email.clone(),
@@ -454,14 +707,16 @@ async fn user_disabled() {
         name_or_email: UserNameOrEmailAddress::Name(BAR.user.name.clone()),
         password: BAR_PASSWORD.clone(),
         device_name: BAR_1.device_name.clone(),
+        ip: None,
         mfa: MfaAuthentication::default(),
     };
 
     let db = MockDatabase::build(false);
 
     let session_failed_auth_count = MockSessionFailedAuthCountService::new()
-        .with_get(cmd.name_or_email.clone(), 1)
-        .with_reset(UserNameOrEmailAddress::Name(BAR.user.name.clone()));
+        .with_get_lockout(FailedAuthCountLogin::User(BAR.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(BAR.user.id), 1)
+        .with_reset(FailedAuthCountLogin::User(BAR.user.id));
 
     let user_repo = MockUserRepository::new()
         .with_get_composite_by_name_or_email(cmd.name_or_email.clone(), Some(BAR.clone()));
@@ -486,3 +741,240 @@ async fn user_disabled() {
     // Assert
     assert_matches!(result, Err(SessionCreateError::UserDisabled));
 }
+
+#[tokio::test]
+async fn locked() {
+    // Arrange
+    let cmd = SessionCreateCommand {
+        name_or_email: UserNameOrEmailAddress::Name(FOO.user.name.clone()),
+        password: FOO_PASSWORD.clone(),
+        device_name: FOO_1.device_name.clone(),
+        ip: Some(IP),
+        mfa: MfaAuthentication::default(),
+    };
+
+    let db = MockDatabase::build(false);
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new().with_get_lockout(
+        FailedAuthCountLogin::User(FOO.user.id),
+        cmd.ip,
+        Some(Duration::from_secs(120)),
+    );
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite_by_name_or_email(cmd.name_or_email.clone(), Some(FOO.clone()));
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session(cmd, None).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(SessionCreateError::Locked { retry_after }) if *retry_after == Duration::from_secs(120)
+    );
+}
+
+#[tokio::test]
+async fn locked_by_email_after_failures_by_name() {
+    // Arrange
+    let cmd = SessionCreateCommand {
+        name_or_email: UserNameOrEmailAddress::Email(FOO.user.email.clone().unwrap()),
+        password: FOO_PASSWORD.clone(),
+        device_name: FOO_1.device_name.clone(),
+        ip: Some(IP),
+        mfa: MfaAuthentication::default(),
+    };
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite_by_name_or_email(cmd.name_or_email.clone(), Some(FOO.clone()));
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new().with_get_lockout(
+        FailedAuthCountLogin::User(FOO.user.id),
+        cmd.ip,
+        Some(Duration::from_secs(120)),
+    );
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session(cmd, None).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(SessionCreateError::Locked { retry_after }) if *retry_after == Duration::from_secs(120)
+    );
+}
+
+#[tokio::test]
+async fn locked_secondary_email() {
+    // Arrange
+    let cmd = SessionCreateCommand {
+        name_or_email: UserNameOrEmailAddress::Email(FOO_EMAIL_1.email.clone()),
+        password: FOO_PASSWORD.clone(),
+        device_name: FOO_1.device_name.clone(),
+        ip: Some(IP),
+        mfa: MfaAuthentication::default(),
+    };
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite_by_name_or_email(cmd.name_or_email.clone(), Some(FOO.clone()));
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new().with_get_lockout(
+        FailedAuthCountLogin::User(FOO.user.id),
+        cmd.ip,
+        Some(Duration::from_secs(120)),
+    );
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session(cmd, None).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(SessionCreateError::Locked { retry_after }) if *retry_after == Duration::from_secs(120)
+    );
+}
+
+#[tokio::test]
+async fn wrong_password_lockout() {
+    // Arrange
+    let cmd = SessionCreateCommand {
+        name_or_email: UserNameOrEmailAddress::Email(FOO.user.email.clone().unwrap()),
+        password: FOO_PASSWORD.clone(),
+        device_name: FOO_1.device_name.clone(),
+        ip: Some(IP),
+        mfa: MfaAuthentication::default(),
+    };
+
+    let db = MockDatabase::build(false);
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new()
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 7)
+        .with_increment(
+            FailedAuthCountLogin::User(FOO.user.id),
+            Some(Duration::from_secs(240)),
+        )
+        .with_increment_ip(IP);
+
+    let captcha = MockCaptchaService::new().with_check(None, Ok(()));
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite_by_name_or_email(cmd.name_or_email.clone(), Some(FOO.clone()));
+
+    let auth = MockAuthService::new().with_authenticate_by_password(
+        FOO.user.id,
+        cmd.password.clone(),
+        false,
+    );
+
+    let template_email = MockTemplateEmailService::new().with_send_account_locked_email(
+        FOO.user
+            .email
+            .clone()
+            .unwrap()
+            .with_name(FOO.profile.display_name.clone().into_inner()),
+        AccountLockedTemplate { minutes: 4 },
+        true,
+    );
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        captcha,
+        auth,
+        template_email,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session(cmd, None).await;
+
+    // Assert
+    assert_matches!(result, Err(SessionCreateError::InvalidCredentials));
+}
+
+#[tokio::test]
+async fn wrong_password_lockout_secondary_email() {
+    // Arrange
+    let cmd = SessionCreateCommand {
+        name_or_email: UserNameOrEmailAddress::Email(FOO_EMAIL_1.email.clone()),
+        password: FOO_PASSWORD.clone(),
+        device_name: FOO_1.device_name.clone(),
+        ip: Some(IP),
+        mfa: MfaAuthentication::default(),
+    };
+
+    let db = MockDatabase::build(false);
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new()
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 7)
+        .with_increment(
+            FailedAuthCountLogin::User(FOO.user.id),
+            Some(Duration::from_secs(240)),
+        )
+        .with_increment_ip(IP);
+
+    let captcha = MockCaptchaService::new().with_check(None, Ok(()));
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite_by_name_or_email(cmd.name_or_email.clone(), Some(FOO.clone()));
+
+    let auth = MockAuthService::new().with_authenticate_by_password(
+        FOO.user.id,
+        cmd.password.clone(),
+        false,
+    );
+
+    let template_email = MockTemplateEmailService::new().with_send_account_locked_email(
+        FOO.user
+            .email
+            .clone()
+            .unwrap()
+            .with_name(FOO.profile.display_name.clone().into_inner()),
+        AccountLockedTemplate { minutes: 4 },
+        true,
+    );
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        captcha,
+        auth,
+        template_email,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session(cmd, None).await;
+
+    // Assert
+    assert_matches!(result, Err(SessionCreateError::InvalidCredentials));
+}
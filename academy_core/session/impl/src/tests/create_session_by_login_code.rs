@@ -0,0 +1,425 @@
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    time::Duration,
+};
+
+use academy_core_mfa_contracts::authenticate::{
+    MfaAuthenticateError, MfaAuthenticateResult, MockMfaAuthenticateService,
+};
+use academy_core_session_contracts::{
+    failed_auth_count::{FailedAuthCountLogin, MockSessionFailedAuthCountService},
+    login_code::MockSessionLoginCodeService,
+    new_device::MockSessionNewDeviceService,
+    session::MockSessionService,
+    SessionCreateByLoginCodeCommand, SessionCreateByLoginCodeError, SessionFeatureService,
+};
+use academy_demo::{
+    session::{BAR_1, FOO_1},
+    user::{BAR, FOO},
+};
+use academy_models::{
+    auth::Login,
+    mfa::MfaAuthentication,
+    session::{SessionLoginCode, SessionLoginMethod},
+    user::UserNameOrEmailAddress,
+};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_shared_contracts::captcha::{CaptchaCheckError, MockCaptchaService};
+use academy_utils::{assert_matches, Apply};
+
+use crate::{tests::Sut, SessionFeatureServiceImpl};
+
+const IP: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+
+fn code() -> SessionLoginCode {
+    SessionLoginCode::try_new("012345").unwrap()
+}
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let cmd = SessionCreateByLoginCodeCommand {
+        email: FOO.user.email.clone().unwrap(),
+        code: code(),
+        device_name: FOO_1.device_name.clone(),
+        ip: None,
+        mfa: MfaAuthentication::default(),
+    };
+
+    let expected = Login {
+        user_composite: FOO.clone(),
+        session: FOO_1.clone(),
+        access_token: "the access token".into(),
+        refresh_token: "some refresh token".into(),
+    };
+
+    let db = MockDatabase::build(true);
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new()
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 1)
+        .with_reset(FailedAuthCountLogin::User(FOO.user.id));
+
+    let user_repo =
+        MockUserRepository::new().with_get_composite_by_email(cmd.email.clone(), Some(FOO.clone()));
+
+    let session_login_code =
+        MockSessionLoginCodeService::new().with_redeem(FOO.user.id, code(), true);
+
+    let session = MockSessionService::new().with_create(
+        FOO.clone(),
+        cmd.device_name.clone(),
+        cmd.ip,
+        SessionLoginMethod::LoginCode,
+        expected.clone(),
+    );
+
+    let session_new_device = MockSessionNewDeviceService::new().with_handle_login(
+        expected.user_composite.clone(),
+        expected.session.clone(),
+        false,
+    );
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        session_login_code,
+        session,
+        session_new_device,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session_by_login_code(cmd, None).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn ok_mfa() {
+    // Arrange
+    let cmd = SessionCreateByLoginCodeCommand {
+        email: FOO.user.email.clone().unwrap(),
+        code: code(),
+        device_name: FOO_1.device_name.clone(),
+        ip: None,
+        mfa: MfaAuthentication {
+            totp_code: Some("123456".try_into().unwrap()),
+            recovery_code: None,
+            webauthn_assertion: None,
+        },
+    };
+
+    let expected = Login {
+        user_composite: FOO.clone().with(|u| u.details.mfa_enabled = true),
+        session: FOO_1.clone(),
+        access_token: "the access token".into(),
+        refresh_token: "some refresh token".into(),
+    };
+
+    let db = MockDatabase::build(true);
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new()
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 1)
+        .with_reset(FailedAuthCountLogin::User(FOO.user.id));
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite_by_email(cmd.email.clone(), Some(expected.user_composite.clone()));
+
+    let session_login_code =
+        MockSessionLoginCodeService::new().with_redeem(FOO.user.id, code(), true);
+
+    let mfa_authenticate = MockMfaAuthenticateService::new().with_authenticate(
+        FOO.user.id,
+        cmd.mfa.clone(),
+        Ok(MfaAuthenticateResult::Ok),
+    );
+
+    let session = MockSessionService::new().with_create(
+        expected.user_composite.clone(),
+        cmd.device_name.clone(),
+        cmd.ip,
+        SessionLoginMethod::LoginCode,
+        expected.clone(),
+    );
+
+    let session_new_device = MockSessionNewDeviceService::new().with_handle_login(
+        expected.user_composite.clone(),
+        expected.session.clone(),
+        false,
+    );
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        session_login_code,
+        session,
+        session_new_device,
+        mfa_authenticate,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session_by_login_code(cmd, None).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn invalid_recaptcha_response() {
+    // Arrange
+    let cmd = SessionCreateByLoginCodeCommand {
+        email: FOO.user.email.clone().unwrap(),
+        code: code(),
+        device_name: FOO_1.device_name.clone(),
+        ip: None,
+        mfa: MfaAuthentication::default(),
+    };
+
+    let db = MockDatabase::build(false);
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new()
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 4);
+
+    let captcha =
+        MockCaptchaService::new().with_check(Some("resp"), Err(CaptchaCheckError::Failed));
+
+    let user_repo =
+        MockUserRepository::new().with_get_composite_by_email(cmd.email.clone(), Some(FOO.clone()));
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        captcha,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_session_by_login_code(cmd, Some("resp".try_into().unwrap()))
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(SessionCreateByLoginCodeError::Recaptcha));
+}
+
+#[tokio::test]
+async fn user_not_found() {
+    // Arrange
+    let cmd = SessionCreateByLoginCodeCommand {
+        email: FOO.user.email.clone().unwrap(),
+        code: code(),
+        device_name: FOO_1.device_name.clone(),
+        ip: Some(IP),
+        mfa: MfaAuthentication::default(),
+    };
+    let name_or_email = UserNameOrEmailAddress::Email(cmd.email.clone());
+
+    let db = MockDatabase::build(false);
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new()
+        .with_get_lockout(
+            FailedAuthCountLogin::Unknown(name_or_email.clone()),
+            cmd.ip,
+            None,
+        )
+        .with_get(FailedAuthCountLogin::Unknown(name_or_email.clone()), 1)
+        .with_increment(FailedAuthCountLogin::Unknown(name_or_email), None)
+        .with_increment_ip(IP);
+
+    let user_repo = MockUserRepository::new().with_get_composite_by_email(cmd.email.clone(), None);
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session_by_login_code(cmd, None).await;
+
+    // Assert
+    assert_matches!(result, Err(SessionCreateByLoginCodeError::InvalidCode));
+}
+
+#[tokio::test]
+async fn invalid_code() {
+    // Arrange
+    let cmd = SessionCreateByLoginCodeCommand {
+        email: FOO.user.email.clone().unwrap(),
+        code: code(),
+        device_name: FOO_1.device_name.clone(),
+        ip: Some(IP),
+        mfa: MfaAuthentication::default(),
+    };
+
+    let db = MockDatabase::build(false);
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new()
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 1)
+        .with_increment(FailedAuthCountLogin::User(FOO.user.id), None)
+        .with_increment_ip(IP);
+
+    let user_repo =
+        MockUserRepository::new().with_get_composite_by_email(cmd.email.clone(), Some(FOO.clone()));
+
+    let session_login_code =
+        MockSessionLoginCodeService::new().with_redeem(FOO.user.id, code(), false);
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        session_login_code,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session_by_login_code(cmd, None).await;
+
+    // Assert
+    assert_matches!(result, Err(SessionCreateByLoginCodeError::InvalidCode));
+}
+
+#[tokio::test]
+async fn mfa_failed() {
+    // Arrange
+    let cmd = SessionCreateByLoginCodeCommand {
+        email: FOO.user.email.clone().unwrap(),
+        code: code(),
+        device_name: FOO_1.device_name.clone(),
+        ip: None,
+        mfa: MfaAuthentication {
+            totp_code: Some("123456".try_into().unwrap()),
+            recovery_code: None,
+            webauthn_assertion: None,
+        },
+    };
+
+    let db = MockDatabase::build(false);
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new()
+        .with_get_lockout(FailedAuthCountLogin::User(FOO.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(FOO.user.id), 1)
+        .with_increment(FailedAuthCountLogin::User(FOO.user.id), None);
+
+    let user_repo = MockUserRepository::new().with_get_composite_by_email(
+        cmd.email.clone(),
+        Some(FOO.clone().with(|u| u.details.mfa_enabled = true)),
+    );
+
+    let session_login_code =
+        MockSessionLoginCodeService::new().with_redeem(FOO.user.id, code(), true);
+
+    let mfa_authenticate = MockMfaAuthenticateService::new().with_authenticate(
+        FOO.user.id,
+        cmd.mfa.clone(),
+        Err(MfaAuthenticateError::Failed),
+    );
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        session_login_code,
+        mfa_authenticate,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session_by_login_code(cmd, None).await;
+
+    // Assert
+    assert_matches!(result, Err(SessionCreateByLoginCodeError::MfaFailed));
+}
+
+#[tokio::test]
+async fn user_disabled() {
+    // Arrange
+    let user_composite = BAR
+        .clone()
+        .with(|u| u.user.email = Some("bar@example.com".parse().unwrap()));
+    let cmd = SessionCreateByLoginCodeCommand {
+        email: user_composite.user.email.clone().unwrap(),
+        code: code(),
+        device_name: BAR_1.device_name.clone(),
+        ip: None,
+        mfa: MfaAuthentication::default(),
+    };
+
+    let db = MockDatabase::build(false);
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new()
+        .with_get_lockout(FailedAuthCountLogin::User(BAR.user.id), cmd.ip, None)
+        .with_get(FailedAuthCountLogin::User(BAR.user.id), 1)
+        .with_reset(FailedAuthCountLogin::User(BAR.user.id));
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite_by_email(cmd.email.clone(), Some(user_composite));
+
+    let session_login_code =
+        MockSessionLoginCodeService::new().with_redeem(BAR.user.id, code(), true);
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        session_login_code,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session_by_login_code(cmd, None).await;
+
+    // Assert
+    assert_matches!(result, Err(SessionCreateByLoginCodeError::UserDisabled));
+}
+
+#[tokio::test]
+async fn locked() {
+    // Arrange
+    let cmd = SessionCreateByLoginCodeCommand {
+        email: FOO.user.email.clone().unwrap(),
+        code: code(),
+        device_name: FOO_1.device_name.clone(),
+        ip: Some(IP),
+        mfa: MfaAuthentication::default(),
+    };
+
+    let db = MockDatabase::build(false);
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new().with_get_lockout(
+        FailedAuthCountLogin::User(FOO.user.id),
+        cmd.ip,
+        Some(Duration::from_secs(120)),
+    );
+
+    let user_repo =
+        MockUserRepository::new().with_get_composite_by_email(cmd.email.clone(), Some(FOO.clone()));
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_failed_auth_count,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_session_by_login_code(cmd, None).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(SessionCreateByLoginCodeError::Locked { retry_after })
+            if *retry_after == Duration::from_secs(120)
+    );
+}
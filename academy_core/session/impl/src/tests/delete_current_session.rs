@@ -2,8 +2,8 @@ use academy_auth_contracts::MockAuthService;
 use academy_core_session_contracts::{
     session::MockSessionService, SessionDeleteCurrentError, SessionFeatureService,
 };
-use academy_demo::{session::FOO_1, user::FOO};
-use academy_models::auth::{AuthError, AuthenticateError};
+use academy_demo::{personal_access_token::FOO_PAT, session::FOO_1, user::FOO};
+use academy_models::auth::{AuthError, AuthenticateError, AuthorizeError};
 use academy_persistence_contracts::MockDatabase;
 use academy_utils::assert_matches;
 
@@ -54,3 +54,26 @@ async fn unauthenticated() {
         )))
     );
 }
+
+#[tokio::test]
+async fn personal_access_token() {
+    // Arrange
+    let auth = MockAuthService::new()
+        .with_authenticate_personal_access_token(Some((FOO.user.clone(), FOO_PAT.clone())));
+
+    let sut = SessionFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.delete_current_session(&"token".into()).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(SessionDeleteCurrentError::Auth(AuthError::Authorize(
+            AuthorizeError::Session
+        )))
+    );
+}
@@ -1,7 +1,7 @@
 use academy_auth_contracts::MockAuthService;
 use academy_core_session_contracts::{SessionFeatureService, SessionGetCurrentError};
-use academy_demo::{session::FOO_1, user::FOO};
-use academy_models::auth::{AuthError, AuthenticateError};
+use academy_demo::{personal_access_token::FOO_PAT, session::FOO_1, user::FOO};
+use academy_models::auth::{AuthError, AuthenticateError, AuthorizeError};
 use academy_persistence_contracts::{session::MockSessionRepository, MockDatabase};
 use academy_utils::assert_matches;
 
@@ -10,7 +10,8 @@ use crate::{tests::Sut, SessionFeatureServiceImpl};
 #[tokio::test]
 async fn ok() {
     // Arrange
-    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
 
     let db = MockDatabase::build(false);
 
@@ -33,7 +34,7 @@ async fn ok() {
 #[tokio::test]
 async fn unauthenticated() {
     // Arrange
-    let auth = MockAuthService::new().with_authenticate(None);
+    let auth = MockAuthService::new().with_authenticate_read(None);
 
     let sut = SessionFeatureServiceImpl {
         auth,
@@ -51,3 +52,26 @@ async fn unauthenticated() {
         )))
     );
 }
+
+#[tokio::test]
+async fn personal_access_token() {
+    // Arrange
+    let auth = MockAuthService::new()
+        .with_authenticate_read_personal_access_token(Some((FOO.user.clone(), FOO_PAT.clone())));
+
+    let sut = SessionFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.get_current_session(&"token".into()).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(SessionGetCurrentError::Auth(AuthError::Authorize(
+            AuthorizeError::Session
+        )))
+    );
+}
@@ -1,4 +1,5 @@
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_session_contracts::{
     session::MockSessionService, SessionFeatureService, SessionImpersonateError,
 };
@@ -6,7 +7,11 @@ use academy_demo::{
     session::{ADMIN_1, BAR_1, FOO_1},
     user::{ADMIN, BAR, FOO},
 };
-use academy_models::auth::{AuthError, AuthenticateError, AuthorizeError, Login};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthenticateError, AuthorizeError, Login},
+    session::SessionLoginMethod,
+};
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
 use academy_utils::assert_matches;
 
@@ -29,9 +34,23 @@ async fn ok() {
 
     let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
 
-    let session = MockSessionService::new().with_create(FOO.clone(), None, false, expected.clone());
+    let session = MockSessionService::new().with_create(
+        FOO.clone(),
+        None,
+        None,
+        SessionLoginMethod::Impersonation,
+        expected.clone(),
+    );
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        FOO.user.id,
+        AuditAction::UserImpersonate,
+    );
 
     let sut = SessionFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_repo,
@@ -40,7 +59,9 @@ async fn ok() {
     };
 
     // Act
-    let result = sut.impersonate(&"token".into(), FOO.user.id).await;
+    let result = sut
+        .impersonate(&"token".into(), FOO.user.id, &AuditContext::default())
+        .await;
 
     // Assert
     assert_eq!(result.unwrap(), expected);
@@ -57,7 +78,9 @@ async fn unauthenticated() {
     };
 
     // Act
-    let result = sut.impersonate(&"token".into(), FOO.user.id).await;
+    let result = sut
+        .impersonate(&"token".into(), FOO.user.id, &AuditContext::default())
+        .await;
 
     // Assert
     assert_matches!(
@@ -79,7 +102,9 @@ async fn unauthorized() {
     };
 
     // Act
-    let result = sut.impersonate(&"token".into(), FOO.user.id).await;
+    let result = sut
+        .impersonate(&"token".into(), FOO.user.id, &AuditContext::default())
+        .await;
 
     // Assert
     assert_matches!(
@@ -108,7 +133,9 @@ async fn user_not_found() {
     };
 
     // Act
-    let result = sut.impersonate(&"token".into(), FOO.user.id).await;
+    let result = sut
+        .impersonate(&"token".into(), FOO.user.id, &AuditContext::default())
+        .await;
 
     // Assert
     assert_matches!(result, Err(SessionImpersonateError::NotFound));
@@ -1,5 +1,7 @@
 use academy_auth_contracts::MockAuthService;
-use academy_core_session_contracts::{SessionFeatureService, SessionListByUserError};
+use academy_core_session_contracts::{
+    SessionFeatureService, SessionListByUserError, SessionListByUserResult,
+};
 use academy_demo::{
     session::{ADMIN_1, BAR_1, FOO_1, FOO_2},
     user::{ADMIN, BAR, FOO},
@@ -18,7 +20,8 @@ async fn ok_self() {
     // Arrange
     let expected = vec![FOO_1.clone(), FOO_2.clone()];
 
-    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
 
     let db = MockDatabase::build(false);
 
@@ -36,7 +39,13 @@ async fn ok_self() {
     let result = sut.list_by_user(&"token".into(), UserIdOrSelf::Slf).await;
 
     // Assert
-    assert_eq!(result.unwrap(), expected);
+    assert_eq!(
+        result.unwrap(),
+        SessionListByUserResult {
+            sessions: expected,
+            current_session_id: Some(FOO_1.id),
+        }
+    );
 }
 
 #[tokio::test]
@@ -45,7 +54,7 @@ async fn ok_admin() {
     let expected = vec![FOO_1.clone(), FOO_2.clone()];
 
     let auth =
-        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
 
     let db = MockDatabase::build(false);
 
@@ -65,13 +74,19 @@ async fn ok_admin() {
         .await;
 
     // Assert
-    assert_eq!(result.unwrap(), expected);
+    assert_eq!(
+        result.unwrap(),
+        SessionListByUserResult {
+            sessions: expected,
+            current_session_id: Some(ADMIN_1.id),
+        }
+    );
 }
 
 #[tokio::test]
 async fn unauthenticated() {
     // Arrange
-    let auth = MockAuthService::new().with_authenticate(None);
+    let auth = MockAuthService::new().with_authenticate_read(None);
     let sut = SessionFeatureServiceImpl {
         auth,
         ..Sut::default()
@@ -94,7 +109,8 @@ async fn unauthenticated() {
 #[tokio::test]
 async fn unauthorized() {
     // Arrange
-    let auth = MockAuthService::new().with_authenticate(Some((BAR.user.clone(), BAR_1.clone())));
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((BAR.user.clone(), BAR_1.clone())));
     let sut = SessionFeatureServiceImpl {
         auth,
         ..Sut::default()
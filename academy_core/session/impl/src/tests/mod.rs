@@ -1,16 +1,25 @@
+use std::time::Duration;
+
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_mfa_contracts::authenticate::MockMfaAuthenticateService;
 use academy_core_session_contracts::{
-    failed_auth_count::MockSessionFailedAuthCountService, session::MockSessionService,
+    failed_auth_count::MockSessionFailedAuthCountService, login_code::MockSessionLoginCodeService,
+    new_device::MockSessionNewDeviceService, session::MockSessionService,
 };
+use academy_core_user_contracts::email_confirmation::MockUserEmailConfirmationService;
+use academy_email_contracts::template::MockTemplateEmailService;
 use academy_persistence_contracts::{
-    session::MockSessionRepository, user::MockUserRepository, MockDatabase, MockTransaction,
+    oauth2_server::MockOAuth2ServerRepository,
+    personal_access_token::MockPersonalAccessTokenRepository, session::MockSessionRepository,
+    user::MockUserRepository, MockDatabase, MockTransaction,
 };
 use academy_shared_contracts::captcha::MockCaptchaService;
 
 use crate::{SessionFeatureConfig, SessionFeatureServiceImpl};
 
 mod create_session;
+mod create_session_by_login_code;
 mod delete_by_user;
 mod delete_current_session;
 mod delete_session;
@@ -18,22 +27,45 @@ mod get_current_session;
 mod impersonate;
 mod list_by_user;
 mod refresh;
+mod request_login_code;
+mod revoke_new_device_session;
+mod unlock_user;
 
 type Sut = SessionFeatureServiceImpl<
     MockDatabase,
     MockAuthService<MockTransaction>,
     MockCaptchaService,
+    MockTemplateEmailService,
     MockSessionService<MockTransaction>,
     MockSessionFailedAuthCountService,
+    MockSessionNewDeviceService<MockTransaction>,
+    MockSessionLoginCodeService,
     MockMfaAuthenticateService<MockTransaction>,
+    MockUserEmailConfirmationService<MockTransaction>,
+    MockAuditService<MockTransaction>,
     MockUserRepository<MockTransaction>,
     MockSessionRepository<MockTransaction>,
+    MockPersonalAccessTokenRepository<MockTransaction>,
+    MockOAuth2ServerRepository<MockTransaction>,
 >;
 
 impl Default for SessionFeatureConfig {
     fn default() -> Self {
         Self {
             login_fails_before_captcha: 3,
+            failed_login_attempts_ttl: Duration::from_secs(3600),
+            login_fails_before_lockout: 5,
+            ip_login_fails_before_lockout: 50,
+            lockout_duration: Duration::from_secs(60),
+            max_lockout_duration: Duration::from_secs(3600),
+            new_device_revoke_redirect_url: "https://bootstrap.academy/auth/revoke-session"
+                .to_owned()
+                .into(),
+            new_device_revoke_code_ttl: Duration::from_secs(7 * 24 * 3600),
+            login_code_redirect_url: "https://bootstrap.academy/auth/login-code"
+                .to_owned()
+                .into(),
+            login_code_ttl: Duration::from_secs(15 * 60),
         }
     }
 }
@@ -1,22 +1,40 @@
-use std::time::Duration;
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    time::Duration,
+};
 
 use academy_auth_contracts::{AuthenticateByRefreshTokenError, MockAuthService};
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_session_contracts::{
     session::MockSessionService, SessionFeatureService, SessionRefreshError,
 };
 use academy_demo::{session::FOO_1, user::FOO};
-use academy_models::{auth::Login, session::Session};
-use academy_persistence_contracts::MockDatabase;
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::Login,
+    session::Session,
+};
+use academy_persistence_contracts::{session::MockSessionRepository, MockDatabase};
 use academy_utils::assert_matches;
 
 use crate::{tests::Sut, SessionFeatureServiceImpl};
 
+const IP: IpAddr = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 7));
+
+fn ctx() -> AuditContext {
+    AuditContext {
+        ip: Some(IP),
+        ..Default::default()
+    }
+}
+
 #[tokio::test]
 async fn ok() {
     // Arrange
     let expected = Login {
         user_composite: FOO.clone(),
         session: Session {
+            last_ip: Some(IP),
             updated_at: FOO_1.updated_at + Duration::from_secs(1234),
             ..FOO_1.clone()
         },
@@ -29,7 +47,7 @@ async fn ok() {
     let auth = MockAuthService::new()
         .with_authenticate_by_refresh_token("refresh token".into(), Ok(FOO_1.id));
 
-    let session = MockSessionService::new().with_refresh(FOO_1.id, Ok(expected.clone()));
+    let session = MockSessionService::new().with_refresh(FOO_1.id, Some(IP), Ok(expected.clone()));
 
     let sut = SessionFeatureServiceImpl {
         db,
@@ -39,7 +57,7 @@ async fn ok() {
     };
 
     // Act
-    let result = sut.refresh_session(&"refresh token".into()).await;
+    let result = sut.refresh_session(&"refresh token".into(), &ctx()).await;
 
     // Assert
     assert_eq!(result.unwrap(), expected);
@@ -62,7 +80,7 @@ async fn invalid_token() {
     };
 
     // Act
-    let result = sut.refresh_session(&"refresh token".into()).await;
+    let result = sut.refresh_session(&"refresh token".into(), &ctx()).await;
 
     // Assert
     assert_matches!(result, Err(SessionRefreshError::InvalidRefreshToken));
@@ -88,7 +106,46 @@ async fn expired() {
     };
 
     // Act
-    let result = sut.refresh_session(&"refresh token".into()).await;
+    let result = sut.refresh_session(&"refresh token".into(), &ctx()).await;
+
+    // Assert
+    assert_matches!(result, Err(SessionRefreshError::InvalidRefreshToken));
+}
+
+#[tokio::test]
+async fn reused() {
+    // Arrange
+    let db = MockDatabase::build(true);
+
+    let auth = MockAuthService::new()
+        .with_authenticate_by_refresh_token(
+            "refresh token".into(),
+            Err(AuthenticateByRefreshTokenError::Reused(FOO_1.id)),
+        )
+        .with_invalidate_access_tokens(FOO.user.id);
+
+    let session_repo = MockSessionRepository::new().with_get(FOO_1.id, Some(FOO_1.clone()));
+
+    let session = MockSessionService::new().with_delete(FOO_1.id, true);
+
+    let audit = MockAuditService::new().with_record(
+        ctx(),
+        None,
+        FOO.user.id,
+        AuditAction::RefreshTokenReuse,
+    );
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        auth,
+        session,
+        audit,
+        session_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.refresh_session(&"refresh token".into(), &ctx()).await;
 
     // Assert
     assert_matches!(result, Err(SessionRefreshError::InvalidRefreshToken));
@@ -104,6 +161,7 @@ async fn not_found() {
 
     let session = MockSessionService::new().with_refresh(
         FOO_1.id,
+        Some(IP),
         Err(academy_core_session_contracts::session::SessionRefreshError::NotFound),
     );
 
@@ -115,7 +173,7 @@ async fn not_found() {
     };
 
     // Act
-    let result = sut.refresh_session(&"refresh token".into()).await;
+    let result = sut.refresh_session(&"refresh token".into(), &ctx()).await;
 
     // Assert
     assert_matches!(result, Err(SessionRefreshError::InvalidRefreshToken));
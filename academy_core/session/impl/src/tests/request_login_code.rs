@@ -0,0 +1,121 @@
+use academy_core_session_contracts::{
+    login_code::MockSessionLoginCodeService, SessionFeatureService, SessionRequestLoginCodeError,
+};
+use academy_demo::user::{BAR, FOO};
+use academy_models::email_address::EmailAddress;
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_shared_contracts::captcha::{CaptchaCheckError, MockCaptchaService};
+use academy_utils::{assert_matches, Apply};
+
+use crate::{tests::Sut, SessionFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let email = FOO.user.email.clone().unwrap();
+
+    let captcha = MockCaptchaService::new().with_check(Some("resp"), Ok(()));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo =
+        MockUserRepository::new().with_get_composite_by_email(email.clone(), Some(FOO.clone()));
+
+    let session_login_code = MockSessionLoginCodeService::new().with_send(
+        FOO.user.id,
+        email
+            .clone()
+            .with_name(FOO.profile.display_name.clone().into_inner()),
+    );
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        captcha,
+        session_login_code,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .request_login_code(email, Some("resp".try_into().unwrap()))
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn user_not_found() {
+    // Arrange
+    let email = FOO.user.email.clone().unwrap();
+
+    let captcha = MockCaptchaService::new().with_check(None, Ok(()));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite_by_email(email.clone(), None);
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        captcha,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.request_login_code(email, None).await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn user_disabled() {
+    // Arrange
+    let email = "bar@example.com".parse::<EmailAddress>().unwrap();
+    let user_composite = BAR.clone().with(|u| u.user.email = Some(email.clone()));
+
+    let captcha = MockCaptchaService::new().with_check(None, Ok(()));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo =
+        MockUserRepository::new().with_get_composite_by_email(email.clone(), Some(user_composite));
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        captcha,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.request_login_code(email, None).await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn invalid_recaptcha_response() {
+    // Arrange
+    let captcha =
+        MockCaptchaService::new().with_check(Some("resp"), Err(CaptchaCheckError::Failed));
+
+    let sut = SessionFeatureServiceImpl {
+        captcha,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .request_login_code(
+            FOO.user.email.clone().unwrap(),
+            Some("resp".try_into().unwrap()),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(SessionRequestLoginCodeError::Recaptcha));
+}
@@ -0,0 +1,175 @@
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_session_contracts::{
+    new_device::MockSessionNewDeviceService, session::MockSessionService, SessionFeatureService,
+    SessionRevokeNewDeviceSessionError,
+};
+use academy_core_user_contracts::email_confirmation::MockUserEmailConfirmationService;
+use academy_demo::{
+    user::{BAR, FOO},
+    VERIFICATION_CODE_1,
+};
+use academy_models::audit::{AuditAction, AuditContext};
+use academy_persistence_contracts::{
+    oauth2_server::MockOAuth2ServerRepository,
+    personal_access_token::MockPersonalAccessTokenRepository, user::MockUserRepository,
+    MockDatabase,
+};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, SessionFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let session_new_device = MockSessionNewDeviceService::new()
+        .with_get_revoke_code(VERIFICATION_CODE_1.clone(), Some(FOO.user.id))
+        .with_remove_revoke_code(VERIFICATION_CODE_1.clone());
+
+    let db = MockDatabase::build(true);
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite(FOO.user.id, Some(FOO.clone()))
+        .with_remove_password_hash(FOO.user.id, true);
+
+    let personal_access_token_repo =
+        MockPersonalAccessTokenRepository::new().with_delete_by_user(FOO.user.id);
+
+    let oauth2_server_repo =
+        MockOAuth2ServerRepository::new().with_delete_grants_by_user(FOO.user.id);
+
+    let session = MockSessionService::new().with_delete_by_user(FOO.user.id);
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        None,
+        FOO.user.id,
+        AuditAction::SessionRevoke,
+    );
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new()
+        .with_request_password_reset(
+            FOO.user.id,
+            FOO.user
+                .email
+                .clone()
+                .unwrap()
+                .with_name(FOO.profile.display_name.clone().into_inner()),
+        );
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session,
+        session_new_device,
+        user_email_confirmation,
+        audit,
+        user_repo,
+        personal_access_token_repo,
+        oauth2_server_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .revoke_new_device_session(VERIFICATION_CODE_1.clone(), &AuditContext::default())
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn ok_no_email() {
+    // Arrange
+    let mut user_composite = BAR.clone();
+    user_composite.user.email = None;
+
+    let session_new_device = MockSessionNewDeviceService::new()
+        .with_get_revoke_code(VERIFICATION_CODE_1.clone(), Some(BAR.user.id))
+        .with_remove_revoke_code(VERIFICATION_CODE_1.clone());
+
+    let db = MockDatabase::build(true);
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite(BAR.user.id, Some(user_composite))
+        .with_remove_password_hash(BAR.user.id, true);
+
+    let personal_access_token_repo =
+        MockPersonalAccessTokenRepository::new().with_delete_by_user(BAR.user.id);
+
+    let oauth2_server_repo =
+        MockOAuth2ServerRepository::new().with_delete_grants_by_user(BAR.user.id);
+
+    let session = MockSessionService::new().with_delete_by_user(BAR.user.id);
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        None,
+        BAR.user.id,
+        AuditAction::SessionRevoke,
+    );
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session,
+        session_new_device,
+        audit,
+        user_repo,
+        personal_access_token_repo,
+        oauth2_server_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .revoke_new_device_session(VERIFICATION_CODE_1.clone(), &AuditContext::default())
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn invalid_code() {
+    // Arrange
+    let session_new_device =
+        MockSessionNewDeviceService::new().with_get_revoke_code(VERIFICATION_CODE_1.clone(), None);
+
+    let sut = SessionFeatureServiceImpl {
+        session_new_device,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .revoke_new_device_session(VERIFICATION_CODE_1.clone(), &AuditContext::default())
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(SessionRevokeNewDeviceSessionError::InvalidCode));
+}
+
+#[tokio::test]
+async fn user_not_found() {
+    // Arrange
+    let session_new_device = MockSessionNewDeviceService::new()
+        .with_get_revoke_code(VERIFICATION_CODE_1.clone(), Some(FOO.user.id));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, None);
+
+    let sut = SessionFeatureServiceImpl {
+        db,
+        session_new_device,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .revoke_new_device_session(VERIFICATION_CODE_1.clone(), &AuditContext::default())
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(SessionRevokeNewDeviceSessionError::InvalidCode));
+}
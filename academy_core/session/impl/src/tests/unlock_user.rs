@@ -0,0 +1,130 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_session_contracts::{
+    failed_auth_count::{FailedAuthCountLogin, MockSessionFailedAuthCountService},
+    SessionFeatureService, SessionUnlockUserError,
+};
+use academy_demo::{
+    session::{ADMIN_1, FOO_1},
+    user::{ADMIN, FOO},
+};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthenticateError, AuthorizeError},
+};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, SessionFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let user_repo = MockUserRepository::new().with_exists(FOO.user.id, true);
+
+    let session_failed_auth_count = MockSessionFailedAuthCountService::new()
+        .with_reset(FailedAuthCountLogin::User(FOO.user.id));
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        FOO.user.id,
+        AuditAction::UserUnlock,
+    );
+
+    let sut = SessionFeatureServiceImpl {
+        auth,
+        db,
+        session_failed_auth_count,
+        audit,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .unlock_user(&"token".into(), FOO.user.id, &AuditContext::default())
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn unauthenticated() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(None);
+
+    let sut = SessionFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .unlock_user(&"token".into(), FOO.user.id, &AuditContext::default())
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(SessionUnlockUserError::Auth(AuthError::Authenticate(
+            AuthenticateError::InvalidToken
+        )))
+    );
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = SessionFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .unlock_user(&"token".into(), FOO.user.id, &AuditContext::default())
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(SessionUnlockUserError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_exists(FOO.user.id, false);
+
+    let sut = SessionFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .unlock_user(&"token".into(), FOO.user.id, &AuditContext::default())
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(SessionUnlockUserError::NotFound));
+}
@@ -0,0 +1,59 @@
+use std::future::Future;
+
+use academy_models::{url::Url, user::UserId};
+use thiserror::Error;
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait UserAvatarService: Send + Sync + 'static {
+    /// Validate the given image, crop it to a square and store it in all
+    /// avatar sizes, replacing the user's previous avatar.
+    ///
+    /// Returns the URL of the avatar in the default size.
+    fn upload(
+        &self,
+        user_id: UserId,
+        data: Vec<u8>,
+    ) -> impl Future<Output = Result<Url, UserAvatarUploadError>> + Send;
+
+    /// Remove all stored sizes of the user's avatar.
+    fn remove(&self, user_id: UserId) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
+#[derive(Debug, Error)]
+pub enum UserAvatarUploadError {
+    #[error("The image is too large.")]
+    TooLarge,
+    #[error("The image format is not supported.")]
+    UnsupportedFormat,
+    #[error("The image is invalid.")]
+    InvalidImage,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[cfg(feature = "mock")]
+impl MockUserAvatarService {
+    pub fn with_upload(
+        mut self,
+        user_id: UserId,
+        data: Vec<u8>,
+        result: Result<Url, UserAvatarUploadError>,
+    ) -> Self {
+        self.expect_upload()
+            .once()
+            .with(
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(data),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(result)));
+        self
+    }
+
+    pub fn with_remove(mut self, user_id: UserId) -> Self {
+        self.expect_remove()
+            .once()
+            .with(mockall::predicate::eq(user_id))
+            .return_once(|_| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+}
@@ -0,0 +1,115 @@
+use std::future::Future;
+
+use academy_models::{email_address::EmailAddressWithName, user::UserId, VerificationCode};
+use thiserror::Error;
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait UserDeletionService<Txn: Send + Sync + 'static>: Send + Sync + 'static {
+    /// Mark the given user as deleted and return a code which can be used to
+    /// restore the account before the deletion grace period expires.
+    fn schedule(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+    ) -> impl Future<Output = anyhow::Result<VerificationCode>> + Send;
+
+    /// Send a link to restore the account of the given user to the given email
+    /// address.
+    ///
+    /// This must only be called after the deletion has been committed to the
+    /// database.
+    fn notify(
+        &self,
+        user_id: UserId,
+        email: EmailAddressWithName,
+        code: VerificationCode,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Restore a user that has been marked as deleted using the code sent via
+    /// email.
+    ///
+    /// The code remains valid until the user is restored or purged.
+    fn restore(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        code: VerificationCode,
+    ) -> impl Future<Output = Result<(), UserDeletionRestoreError>> + Send;
+
+    /// Restore a user that has been marked as deleted without requiring the
+    /// restore code.
+    ///
+    /// Returns `false` if the user does not exist.
+    fn force_restore(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+}
+
+#[derive(Debug, Error)]
+pub enum UserDeletionRestoreError {
+    #[error("The restore code is invalid or has expired.")]
+    InvalidCode,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[cfg(feature = "mock")]
+impl<Txn: Send + Sync + 'static> MockUserDeletionService<Txn> {
+    pub fn with_schedule(mut self, user_id: UserId, result: VerificationCode) -> Self {
+        self.expect_schedule()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_notify(
+        mut self,
+        user_id: UserId,
+        email: EmailAddressWithName,
+        code: VerificationCode,
+    ) -> Self {
+        self.expect_notify()
+            .once()
+            .with(
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(email),
+                mockall::predicate::eq(code),
+            )
+            .return_once(|_, _, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_restore(
+        mut self,
+        user_id: UserId,
+        code: VerificationCode,
+        result: Result<(), UserDeletionRestoreError>,
+    ) -> Self {
+        self.expect_restore()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(code),
+            )
+            .return_once(|_, _, _| Box::pin(std::future::ready(result)));
+        self
+    }
+
+    pub fn with_force_restore(mut self, user_id: UserId, result: bool) -> Self {
+        self.expect_force_restore()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+}
@@ -1,8 +1,8 @@
 use std::future::Future;
 
 use academy_models::{
-    email_address::EmailAddressWithName,
-    user::{UserComposite, UserId, UserPassword},
+    email_address::{EmailAddress, EmailAddressWithName},
+    user::{UserComposite, UserEmail, UserId, UserPassword},
     VerificationCode,
 };
 use thiserror::Error;
@@ -52,6 +52,81 @@ pub trait UserEmailConfirmationService<Txn: Send + Sync + 'static>: Send + Sync
         user_id: UserId,
         code: VerificationCode,
     ) -> impl Future<Output = Result<(), UserEmailConfirmationSubscribeToNewsletterError>> + Send;
+
+    /// Send a confirmation code to the new email address of a user and, if
+    /// the user already has an email address, a notice with a link to cancel
+    /// the change to the old one.
+    ///
+    /// Any previously requested email address change of the user is replaced.
+    fn request_email_change(
+        &self,
+        user_id: UserId,
+        old_email: Option<EmailAddressWithName>,
+        new_email: EmailAddressWithName,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Replace a user's email address with the pending one using the
+    /// confirmation code sent to the new address.
+    ///
+    /// Returns the new email address, which is considered verified.
+    fn confirm_email_change(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        code: VerificationCode,
+    ) -> impl Future<Output = Result<EmailAddress, UserEmailConfirmationChangeEmailError>> + Send;
+
+    /// Cancel an email address change using the code sent to the old
+    /// address.
+    ///
+    /// If the change has already been confirmed, the old email address is
+    /// restored. Returns whether the user has been updated.
+    fn cancel_email_change(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        code: VerificationCode,
+    ) -> impl Future<Output = Result<bool, UserEmailConfirmationCancelEmailChangeError>> + Send;
+
+    /// Invalidate the confirmation code of a user's pending email address
+    /// change.
+    ///
+    /// Must be called after the transaction in which the change has been
+    /// confirmed has been committed.
+    fn remove_email_change(
+        &self,
+        user_id: UserId,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Invalidate a user's pending email address change together with the
+    /// code to cancel it.
+    ///
+    /// Must be called after the transaction in which the change has been
+    /// cancelled has been committed.
+    fn remove_cancel_email_change(
+        &self,
+        user_id: UserId,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Send a confirmation code to an email address which should be added as
+    /// an additional email address of a user.
+    ///
+    /// Any previously requested email address addition of the user is
+    /// replaced.
+    fn request_email_addition(
+        &self,
+        user_id: UserId,
+        email: EmailAddressWithName,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Add the pending email address as an additional email address of a
+    /// user using the confirmation code sent to this address.
+    fn confirm_email_addition(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        code: VerificationCode,
+    ) -> impl Future<Output = Result<UserEmail, UserEmailConfirmationAddEmailError>> + Send;
 }
 
 #[derive(Debug, Error)]
@@ -80,6 +155,36 @@ pub enum UserEmailConfirmationSubscribeToNewsletterError {
     Other(#[from] anyhow::Error),
 }
 
+#[derive(Debug, Error)]
+pub enum UserEmailConfirmationChangeEmailError {
+    #[error("The verification code is invalid.")]
+    InvalidCode,
+    #[error("A user with the same email address already exists.")]
+    Conflict,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UserEmailConfirmationCancelEmailChangeError {
+    #[error("The verification code is invalid.")]
+    InvalidCode,
+    #[error("A user with the same email address already exists.")]
+    Conflict,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UserEmailConfirmationAddEmailError {
+    #[error("The verification code is invalid.")]
+    InvalidCode,
+    #[error("A user with the same email address already exists.")]
+    Conflict,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
 #[cfg(feature = "mock")]
 impl<Txn: Send + Sync + 'static> MockUserEmailConfirmationService<Txn> {
     pub fn with_request_verification(mut self, email: EmailAddressWithName) -> Self {
@@ -170,4 +275,103 @@ impl<Txn: Send + Sync + 'static> MockUserEmailConfirmationService<Txn> {
             .return_once(|_, _, _| Box::pin(std::future::ready(result)));
         self
     }
+
+    pub fn with_request_email_change(
+        mut self,
+        user_id: UserId,
+        old_email: Option<EmailAddressWithName>,
+        new_email: EmailAddressWithName,
+    ) -> Self {
+        self.expect_request_email_change()
+            .once()
+            .with(
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(old_email),
+                mockall::predicate::eq(new_email),
+            )
+            .return_once(|_, _, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_confirm_email_change(
+        mut self,
+        user_id: UserId,
+        code: VerificationCode,
+        result: Result<EmailAddress, UserEmailConfirmationChangeEmailError>,
+    ) -> Self {
+        self.expect_confirm_email_change()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(code),
+            )
+            .return_once(|_, _, _| Box::pin(std::future::ready(result)));
+        self
+    }
+
+    pub fn with_cancel_email_change(
+        mut self,
+        user_id: UserId,
+        code: VerificationCode,
+        result: Result<bool, UserEmailConfirmationCancelEmailChangeError>,
+    ) -> Self {
+        self.expect_cancel_email_change()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(code),
+            )
+            .return_once(|_, _, _| Box::pin(std::future::ready(result)));
+        self
+    }
+
+    pub fn with_remove_email_change(mut self, user_id: UserId) -> Self {
+        self.expect_remove_email_change()
+            .once()
+            .with(mockall::predicate::eq(user_id))
+            .return_once(|_| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_remove_cancel_email_change(mut self, user_id: UserId) -> Self {
+        self.expect_remove_cancel_email_change()
+            .once()
+            .with(mockall::predicate::eq(user_id))
+            .return_once(|_| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_request_email_addition(
+        mut self,
+        user_id: UserId,
+        email: EmailAddressWithName,
+    ) -> Self {
+        self.expect_request_email_addition()
+            .once()
+            .with(
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(email),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_confirm_email_addition(
+        mut self,
+        user_id: UserId,
+        code: VerificationCode,
+        result: Result<UserEmail, UserEmailConfirmationAddEmailError>,
+    ) -> Self {
+        self.expect_confirm_email_addition()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(code),
+            )
+            .return_once(|_, _, _| Box::pin(std::future::ready(result)));
+        self
+    }
 }
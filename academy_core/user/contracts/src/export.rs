@@ -0,0 +1,58 @@
+use std::future::Future;
+
+use academy_models::{user::UserId, user_export::UserExport, VerificationCode};
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait UserExportService: Send + Sync + 'static {
+    /// Collect all data stored about the given user.
+    ///
+    /// Returns `None` if the user does not exist.
+    fn generate(
+        &self,
+        user_id: UserId,
+    ) -> impl Future<Output = anyhow::Result<Option<UserExport>>> + Send;
+
+    /// Generate the exports requested by users and send them a download link
+    /// via email.
+    ///
+    /// Requests that cannot be processed are kept and retried on the next
+    /// invocation.
+    fn process_requests(&self) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Return the export of the given user which can be downloaded using the
+    /// given code.
+    ///
+    /// Returns `None` if the code is invalid or has expired.
+    fn get(
+        &self,
+        user_id: UserId,
+        code: &VerificationCode,
+    ) -> impl Future<Output = anyhow::Result<Option<UserExport>>> + Send;
+}
+
+#[cfg(feature = "mock")]
+impl MockUserExportService {
+    pub fn with_generate(mut self, user_id: UserId, result: Option<UserExport>) -> Self {
+        self.expect_generate()
+            .once()
+            .with(mockall::predicate::eq(user_id))
+            .return_once(|_| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_get(
+        mut self,
+        user_id: UserId,
+        code: VerificationCode,
+        result: Option<UserExport>,
+    ) -> Self {
+        self.expect_get()
+            .once()
+            .with(
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(code),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+}
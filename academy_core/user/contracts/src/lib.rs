@@ -1,29 +1,38 @@
-use std::future::Future;
+use std::{future::Future, net::IpAddr};
 
 use academy_models::{
+    audit::AuditContext,
     auth::{AccessToken, AuthError, Login},
     email_address::EmailAddress,
     oauth2::OAuth2RegistrationToken,
     session::DeviceName,
     user::{
-        UserComposite, UserDisplayName, UserIdOrSelf, UserInvoiceInfo, UserName, UserPassword,
-        UserProfilePatch,
+        UserComposite, UserDisplayName, UserEmail, UserId, UserIdOrSelf, UserInvoiceInfo, UserName,
+        UserNameHistoryEntry, UserPassword, UserProfilePatch,
     },
+    user_export::UserExport,
     RecaptchaResponse, VerificationCode,
 };
 use academy_utils::patch::PatchValue;
 use chrono::{DateTime, Utc};
+use name_policy::UserNamePolicyViolation;
+use password_policy::UserPasswordPolicyViolation;
 use thiserror::Error;
 use user::{UserListQuery, UserListResult};
 
+pub mod avatar;
+pub mod deletion;
 pub mod email_confirmation;
+pub mod export;
+pub mod name_policy;
+pub mod password_policy;
 pub mod update;
 pub mod user;
 
 pub trait UserFeatureService: Send + Sync + 'static {
     /// Return all users matching the given query.
     ///
-    /// Requires admin privileges.
+    /// Requires the `read_users` permission.
     fn list_users(
         &self,
         token: &AccessToken,
@@ -32,7 +41,8 @@ pub trait UserFeatureService: Send + Sync + 'static {
 
     /// Return the user with the given id.
     ///
-    /// Requires admin privileges if not used on the authenticated user.
+    /// Requires the `read_users` permission if not used on the authenticated
+    /// user.
     fn get_user(
         &self,
         token: &AccessToken,
@@ -44,46 +54,109 @@ pub trait UserFeatureService: Send + Sync + 'static {
         &self,
         request: UserCreateRequest,
         device_name: Option<DeviceName>,
+        ip: Option<IpAddr>,
         recaptcha_response: Option<RecaptchaResponse>,
     ) -> impl Future<Output = Result<Login, UserCreateError>> + Send;
 
     /// Update a user.
     ///
-    /// - Changing the email address will also set `email_verified` to `false`.
+    /// - Changing the email address as an administrator will also set
+    ///   `email_verified` to `false`.
     /// - Disabling a user will also log them out.
     /// - A user can never change their own admin status.
     /// - A user can never disable themselves.
     ///
     /// If the authenticated user is not an administrator:
-    /// - Only the authenticated user itself can be updated.
+    /// - Other users can only be updated with the `disable_users` permission
+    ///   (`enabled` field of non-administrators only) or the
+    ///   `edit_invoice_info` permission (invoice info only).
     /// - Changing the `name` is rate-limited.
     /// - Changing the `newsletter` field from `false` to `true` does not
     ///   immediately update the field's value but rather results in a
     ///   verification email being sent to the user.
+    /// - Changing the `email` field does not immediately update the field's
+    ///   value but rather results in a confirmation code being sent to the
+    ///   new email address and a notice being sent to the old one.
     /// - Changing any of the following fields is not allowed:
-    ///   - `enabled`
+    ///   - `enabled` (unless the `disable_users` permission has been granted)
     ///   - `admin`
     ///   - `email_verified`
+    ///
+    /// Changing `password`, `email`, `email_verified` or `admin` requires a
+    /// session, i.e. cannot be done using a personal access token.
     fn update_user(
         &self,
         token: &AccessToken,
         user_id: UserIdOrSelf,
         request: UserUpdateRequest,
+        ctx: &AuditContext,
     ) -> impl Future<Output = Result<UserComposite, UserUpdateError>> + Send;
 
-    /// Delete a user.
+    /// Upload a new avatar image for a user.
+    ///
+    /// The image is cropped to a square and stored in several standard sizes.
+    /// Supported formats are PNG, JPEG, WebP and GIF (only the first frame is
+    /// used).
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    fn upload_user_avatar(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        data: Vec<u8>,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<UserComposite, UserUploadAvatarError>> + Send;
+
+    /// Remove the uploaded avatar of a user.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    fn delete_user_avatar(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<UserComposite, UserDeleteAvatarError>> + Send;
+
+    /// Mark a user as deleted and log them out.
+    ///
+    /// The account can be restored using the code sent via email or by an
+    /// administrator until the deletion grace period expires.
     ///
     /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
     fn delete_user(
         &self,
         token: &AccessToken,
         user_id: UserIdOrSelf,
+        ctx: &AuditContext,
     ) -> impl Future<Output = Result<(), UserDeleteError>> + Send;
 
+    /// Restore a user that has been marked as deleted using the code sent via
+    /// email.
+    fn restore_user(
+        &self,
+        user_id: UserId,
+        code: VerificationCode,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<(), UserRestoreError>> + Send;
+
+    /// Restore a user that has been marked as deleted without requiring the
+    /// code sent via email.
+    ///
+    /// Requires admin privileges.
+    fn force_restore_user(
+        &self,
+        token: &AccessToken,
+        user_id: UserId,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<(), UserForceRestoreError>> + Send;
+
     /// Request an email with a verification code to verify a user's email
     /// address.
     ///
-    /// Requires admin privileges if not used on the authenticated user.
+    /// Requires the `resend_verification_emails` permission if not used on the
+    /// authenticated user.
     fn request_verification_email(
         &self,
         token: &AccessToken,
@@ -96,6 +169,97 @@ pub trait UserFeatureService: Send + Sync + 'static {
         code: VerificationCode,
     ) -> impl Future<Output = Result<(), UserVerifyEmailError>> + Send;
 
+    /// Replace a user's email address with the pending one using the
+    /// confirmation code sent to the new address.
+    fn confirm_email_change(
+        &self,
+        user_id: UserId,
+        code: VerificationCode,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<(), UserConfirmEmailChangeError>> + Send;
+
+    /// Cancel an email address change using the code sent to the old
+    /// address.
+    ///
+    /// If the change has already been confirmed, the old email address is
+    /// restored.
+    fn cancel_email_change(
+        &self,
+        user_id: UserId,
+        code: VerificationCode,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<(), UserCancelEmailChangeError>> + Send;
+
+    /// Return the additional email addresses of a user.
+    ///
+    /// Requires the `read_users` permission if not used on the authenticated
+    /// user.
+    fn list_user_emails(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+    ) -> impl Future<Output = Result<Vec<UserEmail>, UserListEmailsError>> + Send;
+
+    /// Request an email with a confirmation code to add an additional email
+    /// address to a user.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
+    fn add_user_email(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        email: EmailAddress,
+    ) -> impl Future<Output = Result<(), UserAddEmailError>> + Send;
+
+    /// Add the pending additional email address to a user using the
+    /// confirmation code sent to this address.
+    fn confirm_user_email(
+        &self,
+        user_id: UserId,
+        code: VerificationCode,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<UserEmail, UserConfirmEmailError>> + Send;
+
+    /// Remove an additional email address from a user.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
+    fn remove_user_email(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        email: EmailAddress,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<(), UserRemoveEmailError>> + Send;
+
+    /// Make one of a user's additional email addresses the primary one.
+    ///
+    /// The previous primary email address is kept as an additional email
+    /// address if it has been verified.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    ///
+    /// Requires a session, i.e. cannot be used with a personal access token.
+    fn set_primary_user_email(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        email: EmailAddress,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<UserComposite, UserSetPrimaryEmailError>> + Send;
+
+    /// Return the previous names of a user, most recent first.
+    ///
+    /// Requires admin privileges.
+    fn list_user_name_history(
+        &self,
+        token: &AccessToken,
+        user_id: UserId,
+    ) -> impl Future<Output = Result<Vec<UserNameHistoryEntry>, UserListNameHistoryError>> + Send;
+
     /// Verifie the newsletter subscription using the verification code sent
     /// via email.
     ///
@@ -120,7 +284,28 @@ pub trait UserFeatureService: Send + Sync + 'static {
         email: EmailAddress,
         code: VerificationCode,
         new_password: UserPassword,
+        ctx: &AuditContext,
     ) -> impl Future<Output = Result<UserComposite, UserResetPasswordError>> + Send;
+
+    /// Request an export of all data stored about a user.
+    ///
+    /// The export is generated in the background and a download link is sent
+    /// to the user's email address once it is ready.
+    ///
+    /// Requires admin privileges if not used on the authenticated user.
+    fn request_user_export(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        ctx: &AuditContext,
+    ) -> impl Future<Output = Result<(), UserRequestExportError>> + Send;
+
+    /// Return a user's data export using the code sent via email.
+    fn get_user_export(
+        &self,
+        user_id: UserId,
+        code: VerificationCode,
+    ) -> impl Future<Output = Result<UserExport, UserGetExportError>> + Send;
 }
 
 #[derive(Debug, Error)]
@@ -165,6 +350,10 @@ pub enum UserCreateError {
     #[error("The remote user has already been linked.")]
     RemoteAlreadyLinked,
     #[error(transparent)]
+    NamePolicy(UserNamePolicyViolation),
+    #[error(transparent)]
+    PasswordPolicy(UserPasswordPolicyViolation),
+    #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
@@ -218,6 +407,36 @@ pub enum UserUpdateError {
     #[error("The vat id is invalid.")]
     InvalidVatId,
     #[error(transparent)]
+    NamePolicy(UserNamePolicyViolation),
+    #[error(transparent)]
+    PasswordPolicy(UserPasswordPolicyViolation),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UserUploadAvatarError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error("The user does not exist.")]
+    NotFound,
+    #[error("The image is too large.")]
+    TooLarge,
+    #[error("The image format is not supported.")]
+    UnsupportedFormat,
+    #[error("The image is invalid.")]
+    InvalidImage,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UserDeleteAvatarError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error("The user does not exist.")]
+    NotFound,
+    #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
@@ -231,6 +450,24 @@ pub enum UserDeleteError {
     Other(#[from] anyhow::Error),
 }
 
+#[derive(Debug, Error)]
+pub enum UserRestoreError {
+    #[error("The restore code is invalid or has expired.")]
+    InvalidCode,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UserForceRestoreError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error("The user does not exist or has not been deleted.")]
+    NotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
 #[derive(Debug, Error)]
 pub enum UserRequestVerificationEmailError {
     #[error(transparent)]
@@ -253,6 +490,90 @@ pub enum UserVerifyEmailError {
     Other(#[from] anyhow::Error),
 }
 
+#[derive(Debug, Error)]
+pub enum UserConfirmEmailChangeError {
+    #[error("The verification code is invalid.")]
+    InvalidCode,
+    #[error("A user with the same email address already exists.")]
+    Conflict,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UserCancelEmailChangeError {
+    #[error("The verification code is invalid.")]
+    InvalidCode,
+    #[error("A user with the same email address already exists.")]
+    Conflict,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UserListEmailsError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error("The user does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UserAddEmailError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error("The user does not exist.")]
+    NotFound,
+    #[error("A user with the same email address already exists.")]
+    Conflict,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UserConfirmEmailError {
+    #[error("The verification code is invalid.")]
+    InvalidCode,
+    #[error("A user with the same email address already exists.")]
+    Conflict,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UserRemoveEmailError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error("The user does not have this additional email address.")]
+    NotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UserSetPrimaryEmailError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error("The user does not exist.")]
+    NotFound,
+    #[error("The user does not have this additional email address.")]
+    EmailNotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UserListNameHistoryError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error("The user does not exist.")]
+    NotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
 #[derive(Debug, Error)]
 pub enum UserVerifyNewsletterSubscriptionError {
     #[error(transparent)]
@@ -280,5 +601,27 @@ pub enum UserResetPasswordError {
     #[error("The email or verification code is invalid.")]
     Failed,
     #[error(transparent)]
+    PasswordPolicy(UserPasswordPolicyViolation),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UserRequestExportError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error("The user does not exist.")]
+    NotFound,
+    #[error("The user does not have an email address.")]
+    NoEmail,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UserGetExportError {
+    #[error("The code is invalid or has expired.")]
+    InvalidCode,
+    #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
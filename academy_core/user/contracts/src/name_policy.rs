@@ -0,0 +1,58 @@
+use std::future::Future;
+
+use academy_models::user::{UserId, UserName};
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait UserNamePolicyService<Txn: Send + Sync + 'static>: Send + Sync + 'static {
+    /// Check whether the given name may be used by the user with the given id
+    /// (or by a new user if `user_id` is `None`).
+    ///
+    /// A name is rejected if it is reserved, matches the blocklist or has been
+    /// used by another user within the configured quarantine period.
+    fn check(
+        &self,
+        txn: &mut Txn,
+        name: &UserName,
+        user_id: Option<UserId>,
+    ) -> impl Future<Output = Result<(), UserNamePolicyCheckError>> + Send;
+}
+
+#[derive(Debug, Error)]
+pub enum UserNamePolicyCheckError {
+    #[error(transparent)]
+    Violation(#[from] UserNamePolicyViolation),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum UserNamePolicyViolation {
+    #[error("The name is reserved.")]
+    Reserved,
+    #[error("The name is not allowed.")]
+    Blocked,
+    #[error("The name has recently been used by another user and is available again at {until}.")]
+    Quarantined { until: DateTime<Utc> },
+}
+
+#[cfg(feature = "mock")]
+impl<Txn: Send + Sync + 'static> MockUserNamePolicyService<Txn> {
+    pub fn with_check(
+        mut self,
+        name: UserName,
+        user_id: Option<UserId>,
+        result: Result<(), UserNamePolicyViolation>,
+    ) -> Self {
+        self.expect_check()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(name),
+                mockall::predicate::eq(user_id),
+            )
+            .return_once(move |_, _, _| Box::pin(std::future::ready(result.map_err(Into::into))));
+        self
+    }
+}
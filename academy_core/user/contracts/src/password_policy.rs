@@ -0,0 +1,60 @@
+use std::future::Future;
+
+use academy_models::{
+    email_address::EmailAddress,
+    user::{UserName, UserPassword},
+};
+use thiserror::Error;
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait UserPasswordPolicyService: Send + Sync + 'static {
+    /// Check whether the given password satisfies the configured password
+    /// policy for a user with the given name and email address.
+    #[allow(
+        clippy::needless_lifetimes,
+        reason = "explicit lifetime needed for automock"
+    )]
+    fn check<'a>(
+        &self,
+        password: &UserPassword,
+        name: &UserName,
+        email: Option<&'a EmailAddress>,
+    ) -> impl Future<Output = Result<(), UserPasswordPolicyCheckError>> + Send;
+}
+
+#[derive(Debug, Error)]
+pub enum UserPasswordPolicyCheckError {
+    #[error(transparent)]
+    Violation(#[from] UserPasswordPolicyViolation),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum UserPasswordPolicyViolation {
+    #[error("The password must contain at least {min_length} characters.")]
+    TooShort { min_length: usize },
+    #[error("The password must contain at least {min_character_classes} character classes.")]
+    TooWeak { min_character_classes: usize },
+    #[error("The password must not match the user's name or email address.")]
+    MatchesUserData,
+    #[error("The password has appeared in a data breach.")]
+    Breached,
+}
+
+#[cfg(feature = "mock")]
+impl MockUserPasswordPolicyService {
+    pub fn with_check(
+        mut self,
+        password: UserPassword,
+        name: UserName,
+        email: Option<EmailAddress>,
+        result: Result<(), UserPasswordPolicyViolation>,
+    ) -> Self {
+        self.expect_check()
+            .once()
+            .withf(move |p, n, e| *p == password && *n == name && *e == email.as_ref())
+            .return_once(move |_, _, _| Box::pin(std::future::ready(result.map_err(Into::into))));
+        self
+    }
+}
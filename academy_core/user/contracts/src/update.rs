@@ -27,6 +27,17 @@ pub trait UserUpdateService<Txn: Send + Sync + 'static>: Send + Sync + 'static {
         email_verified: bool,
     ) -> impl Future<Output = Result<bool, UserUpdateEmailError>> + Send;
 
+    /// Make one of a user's additional email addresses the primary one.
+    ///
+    /// The previous primary email address is kept as an additional email
+    /// address if it has been verified.
+    fn update_primary_email(
+        &self,
+        txn: &mut Txn,
+        user: User,
+        email: EmailAddress,
+    ) -> impl Future<Output = Result<User, UserUpdatePrimaryEmailError>> + Send;
+
     /// Update a user's password.
     fn update_password(
         &self,
@@ -85,6 +96,14 @@ pub enum UserUpdateEmailError {
     Other(#[from] anyhow::Error),
 }
 
+#[derive(Debug, Error)]
+pub enum UserUpdatePrimaryEmailError {
+    #[error("The email address is not an additional email address of the user.")]
+    NotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
 #[cfg(feature = "mock")]
 impl<Txn: Send + Sync + 'static> MockUserUpdateService<Txn> {
     pub fn with_update_name(
@@ -125,6 +144,23 @@ impl<Txn: Send + Sync + 'static> MockUserUpdateService<Txn> {
         self
     }
 
+    pub fn with_update_primary_email(
+        mut self,
+        user: User,
+        email: EmailAddress,
+        result: Result<User, UserUpdatePrimaryEmailError>,
+    ) -> Self {
+        self.expect_update_primary_email()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user),
+                mockall::predicate::eq(email),
+            )
+            .return_once(|_, _, _| Box::pin(std::future::ready(result)));
+        self
+    }
+
     pub fn with_update_password(mut self, user_id: UserId, password: UserPassword) -> Self {
         self.expect_update_password()
             .once()
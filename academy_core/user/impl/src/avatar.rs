@@ -0,0 +1,303 @@
+use std::io::Cursor;
+
+use academy_core_user_contracts::avatar::{UserAvatarService, UserAvatarUploadError};
+use academy_di::Build;
+use academy_models::{url::Url, user::UserId};
+use academy_shared_contracts::time::TimeService;
+use academy_storage_contracts::StorageService;
+use academy_utils::trace_instrument;
+use anyhow::Context;
+use image::{imageops::FilterType, DynamicImage, ImageError, ImageFormat, ImageReader, Limits};
+
+use crate::UserFeatureConfig;
+
+/// Edge lengths (in pixels) of the square avatar images that are stored for
+/// every upload.
+pub const AVATAR_SIZES: [u32; 4] = [64, 128, 256, 512];
+
+/// Size of the avatar image referenced by the user's `avatar_url`.
+pub const AVATAR_DEFAULT_SIZE: u32 = 256;
+
+const SUPPORTED_FORMATS: [ImageFormat; 4] = [
+    ImageFormat::Png,
+    ImageFormat::Jpeg,
+    ImageFormat::WebP,
+    ImageFormat::Gif,
+];
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct UserAvatarServiceImpl<Time, Storage> {
+    time: Time,
+    storage: Storage,
+    config: UserFeatureConfig,
+}
+
+impl<Time, Storage> UserAvatarService for UserAvatarServiceImpl<Time, Storage>
+where
+    Time: TimeService,
+    Storage: StorageService,
+{
+    #[trace_instrument(skip(self, data))]
+    async fn upload(&self, user_id: UserId, data: Vec<u8>) -> Result<Url, UserAvatarUploadError> {
+        if data.len() > self.config.avatar_max_size {
+            return Err(UserAvatarUploadError::TooLarge);
+        }
+
+        let format = image::guess_format(&data)
+            .ok()
+            .filter(|format| SUPPORTED_FORMATS.contains(format))
+            .ok_or(UserAvatarUploadError::UnsupportedFormat)?;
+
+        let max_dimension = self.config.avatar_max_dimension;
+        let images = tokio::task::spawn_blocking(move || resize(&data, format, max_dimension))
+            .await
+            .context("Failed to join avatar resize task")??;
+
+        for (size, image) in images {
+            self.storage
+                .put(&avatar_key(user_id, size), "image/png", image)
+                .await
+                .context("Failed to store avatar")?;
+        }
+
+        // the keys do not change between uploads, so the url has to change
+        // for clients to notice the new image
+        let mut url = self.storage.url(&avatar_key(user_id, AVATAR_DEFAULT_SIZE));
+        url.query_pairs_mut()
+            .append_pair("v", &self.time.now().timestamp().to_string());
+
+        Ok(url)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn remove(&self, user_id: UserId) -> anyhow::Result<()> {
+        for size in AVATAR_SIZES {
+            self.storage
+                .remove(&avatar_key(user_id, size))
+                .await
+                .context("Failed to remove avatar")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn avatar_key(user_id: UserId, size: u32) -> String {
+    format!("avatars/{}/{size}.png", user_id.hyphenated())
+}
+
+/// Decode the given image, crop it to a square and encode it as PNG in all
+/// avatar sizes.
+fn resize(
+    data: &[u8],
+    format: ImageFormat,
+    max_dimension: u32,
+) -> Result<Vec<(u32, Vec<u8>)>, UserAvatarUploadError> {
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(max_dimension);
+    limits.max_image_height = Some(max_dimension);
+
+    let mut reader = ImageReader::with_format(Cursor::new(data), format);
+    reader.limits(limits);
+    let image = reader.decode().map_err(|err| match err {
+        ImageError::Limits(_) => UserAvatarUploadError::TooLarge,
+        _ => UserAvatarUploadError::InvalidImage,
+    })?;
+
+    let (width, height) = (image.width(), image.height());
+    let side = width.min(height);
+    let image = DynamicImage::from(
+        image
+            .crop_imm((width - side) / 2, (height - side) / 2, side, side)
+            .into_rgba8(),
+    );
+
+    AVATAR_SIZES
+        .into_iter()
+        .map(|size| {
+            let mut buf = Vec::new();
+            image
+                .resize_exact(size, size, FilterType::Lanczos3)
+                .write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)
+                .context("Failed to encode avatar")?;
+            Ok((size, buf))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_demo::user::FOO;
+    use academy_shared_contracts::time::MockTimeService;
+    use academy_storage_contracts::MockStorageService;
+    use academy_utils::assert_matches;
+    use image::{GenericImageView, ImageBuffer, Rgba};
+
+    use super::*;
+
+    type Sut = UserAvatarServiceImpl<MockTimeService, MockStorageService>;
+
+    fn make_image(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+        let image = ImageBuffer::from_fn(width, height, |x, _| {
+            if x < width / 2 {
+                Rgba([255u8, 0, 0, 255])
+            } else {
+                Rgba([0, 0, 255, 255])
+            }
+        });
+        let mut buf = Vec::new();
+        DynamicImage::from(image)
+            .to_rgb8()
+            .write_to(&mut Cursor::new(&mut buf), format)
+            .unwrap();
+        buf
+    }
+
+    #[tokio::test]
+    async fn upload_ok() {
+        // Arrange
+        let data = make_image(300, 200, ImageFormat::Png);
+        let now = FOO.user.created_at;
+
+        let time = MockTimeService::new().with_now(now);
+
+        let mut storage = MockStorageService::new();
+        for size in AVATAR_SIZES {
+            storage = storage.with_put(
+                format!("avatars/{}/{size}.png", FOO.user.id.hyphenated()),
+                "image/png".into(),
+            );
+        }
+        let storage = storage.with_url(
+            format!("avatars/{}/256.png", FOO.user.id.hyphenated()),
+            format!(
+                "https://cdn.bootstrap.academy/avatars/{}/256.png",
+                FOO.user.id.hyphenated()
+            )
+            .parse()
+            .unwrap(),
+        );
+
+        let sut = Sut {
+            time,
+            storage,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.upload(FOO.user.id, data).await;
+
+        // Assert
+        assert_eq!(
+            result.unwrap().as_str(),
+            format!(
+                "https://cdn.bootstrap.academy/avatars/{}/256.png?v={}",
+                FOO.user.id.hyphenated(),
+                now.timestamp()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn upload_too_large() {
+        // Arrange
+        let sut = Sut::default();
+        let data = vec![0; sut.config.avatar_max_size + 1];
+
+        // Act
+        let result = sut.upload(FOO.user.id, data).await;
+
+        // Assert
+        assert_matches!(result, Err(UserAvatarUploadError::TooLarge));
+    }
+
+    #[tokio::test]
+    async fn upload_dimensions_too_large() {
+        // Arrange
+        let sut = Sut::default();
+        let data = make_image(sut.config.avatar_max_dimension + 1, 1, ImageFormat::Png);
+
+        // Act
+        let result = sut.upload(FOO.user.id, data).await;
+
+        // Assert
+        assert_matches!(result, Err(UserAvatarUploadError::TooLarge));
+    }
+
+    #[tokio::test]
+    async fn upload_unsupported_format() {
+        // Arrange
+        let sut = Sut::default();
+
+        for data in [
+            b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>".to_vec(),
+            b"BM\x00\x00\x00\x00".to_vec(),
+        ] {
+            // Act
+            let result = sut.upload(FOO.user.id, data).await;
+
+            // Assert
+            assert_matches!(result, Err(UserAvatarUploadError::UnsupportedFormat));
+        }
+    }
+
+    #[tokio::test]
+    async fn upload_invalid_image() {
+        // Arrange
+        let sut = Sut::default();
+        let mut data = make_image(16, 16, ImageFormat::Png);
+        data.truncate(data.len() / 2);
+
+        // Act
+        let result = sut.upload(FOO.user.id, data).await;
+
+        // Assert
+        assert_matches!(result, Err(UserAvatarUploadError::InvalidImage));
+    }
+
+    #[tokio::test]
+    async fn remove() {
+        // Arrange
+        let mut storage = MockStorageService::new();
+        for size in AVATAR_SIZES {
+            storage =
+                storage.with_remove(format!("avatars/{}/{size}.png", FOO.user.id.hyphenated()));
+        }
+
+        let sut = Sut {
+            storage,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.remove(FOO.user.id).await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[test]
+    fn resize_crops_to_square() {
+        // Arrange
+        let data = make_image(300, 100, ImageFormat::Jpeg);
+
+        // Act
+        let result = resize(&data, ImageFormat::Jpeg, 1000).unwrap();
+
+        // Assert
+        assert_eq!(
+            result.iter().map(|&(size, _)| size).collect::<Vec<_>>(),
+            AVATAR_SIZES
+        );
+        for (size, data) in result {
+            let image = image::load_from_memory_with_format(&data, ImageFormat::Png).unwrap();
+            assert_eq!(image.dimensions(), (size, size));
+            // the center of the original image consists of both colors
+            let left = image.get_pixel(0, size / 2);
+            let right = image.get_pixel(size - 1, size / 2);
+            assert!(left[0] > 200 && left[2] < 50, "{left:?}");
+            assert!(right[2] > 200 && right[0] < 50, "{right:?}");
+        }
+    }
+}
@@ -0,0 +1,360 @@
+use academy_core_user_contracts::deletion::{UserDeletionRestoreError, UserDeletionService};
+use academy_di::Build;
+use academy_email_contracts::template::TemplateEmailService;
+use academy_models::{
+    email_address::EmailAddressWithName,
+    user::{UserId, UserPatch},
+    VerificationCode,
+};
+use academy_persistence_contracts::user::UserRepository;
+use academy_shared_contracts::{hash::HashService, secret::SecretService, time::TimeService};
+use academy_templates_contracts::UserDeletedTemplate;
+use academy_utils::trace_instrument;
+use anyhow::Context;
+use url::Url;
+
+use crate::UserFeatureConfig;
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct UserDeletionServiceImpl<Time, Secret, Hash, TemplateEmail, UserRepo> {
+    time: Time,
+    secret: Secret,
+    hash: Hash,
+    template_email: TemplateEmail,
+    user_repo: UserRepo,
+    config: UserFeatureConfig,
+}
+
+impl<Txn, Time, Secret, Hash, TemplateEmail, UserRepo> UserDeletionService<Txn>
+    for UserDeletionServiceImpl<Time, Secret, Hash, TemplateEmail, UserRepo>
+where
+    Txn: Send + Sync + 'static,
+    Time: TimeService,
+    Secret: SecretService,
+    Hash: HashService,
+    TemplateEmail: TemplateEmailService,
+    UserRepo: UserRepository<Txn>,
+{
+    #[trace_instrument(skip(self, txn))]
+    async fn schedule(&self, txn: &mut Txn, user_id: UserId) -> anyhow::Result<VerificationCode> {
+        self.user_repo
+            .update(
+                txn,
+                user_id,
+                UserPatch::new()
+                    .update_deleted_at(Some(self.time.now()))
+                    .as_ref(),
+            )
+            .await
+            .context("Failed to mark user as deleted in database")?;
+
+        let code = self.secret.generate_verification_code();
+
+        self.user_repo
+            .save_restore_code_hash(txn, user_id, Some(self.hash.sha256(&code).into()))
+            .await
+            .context("Failed to save restore code hash in database")?;
+
+        Ok(code)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn notify(
+        &self,
+        user_id: UserId,
+        email: EmailAddressWithName,
+        code: VerificationCode,
+    ) -> anyhow::Result<()> {
+        let url = Url::parse_with_params(
+            &self.config.restore_redirect_url,
+            [
+                ("user_id", user_id.hyphenated().to_string()),
+                ("code", code.into_inner()),
+            ],
+        )
+        .context("Failed to build restore link")?;
+
+        let deadline = self.time.now() + self.config.deletion_grace_period;
+
+        self.template_email
+            .send_user_deleted_email(
+                email,
+                &UserDeletedTemplate {
+                    url: url.into(),
+                    deadline: deadline.format("%d.%m.%Y %H:%M UTC").to_string(),
+                },
+            )
+            .await
+            .context("Failed to send user deleted email")?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn restore(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        code: VerificationCode,
+    ) -> Result<(), UserDeletionRestoreError> {
+        let expected_hash = self
+            .user_repo
+            .get_restore_code_hash(txn, user_id)
+            .await
+            .context("Failed to get restore code hash from database")?;
+        if expected_hash != Some(self.hash.sha256(&code).into()) {
+            return Err(UserDeletionRestoreError::InvalidCode);
+        }
+
+        if !self.force_restore(txn, user_id).await? {
+            // the user has already been purged
+            return Err(UserDeletionRestoreError::InvalidCode);
+        }
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn force_restore(&self, txn: &mut Txn, user_id: UserId) -> anyhow::Result<bool> {
+        if !self
+            .user_repo
+            .update(
+                txn,
+                user_id,
+                UserPatch::new().update_deleted_at(None).as_ref(),
+            )
+            .await
+            .context("Failed to restore user in database")?
+        {
+            return Ok(false);
+        }
+
+        self.user_repo
+            .save_restore_code_hash(txn, user_id, None)
+            .await
+            .context("Failed to remove restore code hash from database")?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_demo::{user::FOO, SHA256HASH1, SHA256HASH2, VERIFICATION_CODE_1};
+    use academy_email_contracts::template::MockTemplateEmailService;
+    use academy_persistence_contracts::user::MockUserRepository;
+    use academy_shared_contracts::{
+        hash::MockHashService, secret::MockSecretService, time::MockTimeService,
+    };
+    use academy_utils::assert_matches;
+
+    use super::*;
+
+    type Sut = UserDeletionServiceImpl<
+        MockTimeService,
+        MockSecretService,
+        MockHashService,
+        MockTemplateEmailService,
+        MockUserRepository<()>,
+    >;
+
+    #[tokio::test]
+    async fn schedule_ok() {
+        // Arrange
+        let now = FOO.user.created_at;
+
+        let time = MockTimeService::new().with_now(now);
+
+        let user_repo = MockUserRepository::new()
+            .with_update(
+                FOO.user.id,
+                UserPatch::new().update_deleted_at(Some(now)),
+                Ok(true),
+            )
+            .with_save_restore_code_hash(FOO.user.id, Some((*SHA256HASH1).into()));
+
+        let secret =
+            MockSecretService::new().with_generate_verification_code(VERIFICATION_CODE_1.clone());
+
+        let hash = MockHashService::new().with_sha256(VERIFICATION_CODE_1.clone(), *SHA256HASH1);
+
+        let sut = UserDeletionServiceImpl {
+            time,
+            secret,
+            hash,
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.schedule(&mut (), FOO.user.id).await;
+
+        // Assert
+        assert_eq!(result.unwrap(), *VERIFICATION_CODE_1);
+    }
+
+    #[tokio::test]
+    async fn notify_ok() {
+        // Arrange
+        let config = UserFeatureConfig::default();
+        let now = FOO.user.created_at;
+
+        let email = FOO
+            .user
+            .email
+            .clone()
+            .unwrap()
+            .with_name(FOO.profile.display_name.clone().into_inner());
+
+        let time = MockTimeService::new().with_now(now);
+
+        let template_email = MockTemplateEmailService::new().with_send_user_deleted_email(
+            email.clone(),
+            UserDeletedTemplate {
+                url: format!(
+                    "https://bootstrap.academy/account/restore?user_id={}&code={}",
+                    FOO.user.id.hyphenated(),
+                    **VERIFICATION_CODE_1
+                ),
+                deadline: (now + config.deletion_grace_period)
+                    .format("%d.%m.%Y %H:%M UTC")
+                    .to_string(),
+            },
+            true,
+        );
+
+        let sut = UserDeletionServiceImpl {
+            time,
+            template_email,
+            config,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .notify(FOO.user.id, email, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn restore_ok() {
+        // Arrange
+        let hash = MockHashService::new().with_sha256(VERIFICATION_CODE_1.clone(), *SHA256HASH1);
+
+        let user_repo = MockUserRepository::new()
+            .with_get_restore_code_hash(FOO.user.id, Some((*SHA256HASH1).into()))
+            .with_update(
+                FOO.user.id,
+                UserPatch::new().update_deleted_at(None),
+                Ok(true),
+            )
+            .with_save_restore_code_hash(FOO.user.id, None);
+
+        let sut = UserDeletionServiceImpl {
+            hash,
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .restore(&mut (), FOO.user.id, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn restore_no_code() {
+        // Arrange
+        let hash = MockHashService::new().with_sha256(VERIFICATION_CODE_1.clone(), *SHA256HASH1);
+
+        let user_repo = MockUserRepository::new().with_get_restore_code_hash(FOO.user.id, None);
+
+        let sut = UserDeletionServiceImpl {
+            hash,
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .restore(&mut (), FOO.user.id, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        assert_matches!(result, Err(UserDeletionRestoreError::InvalidCode));
+    }
+
+    #[tokio::test]
+    async fn restore_invalid_code() {
+        // Arrange
+        let hash = MockHashService::new().with_sha256(VERIFICATION_CODE_1.clone(), *SHA256HASH1);
+
+        let user_repo = MockUserRepository::new()
+            .with_get_restore_code_hash(FOO.user.id, Some((*SHA256HASH2).into()));
+
+        let sut = UserDeletionServiceImpl {
+            hash,
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .restore(&mut (), FOO.user.id, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        assert_matches!(result, Err(UserDeletionRestoreError::InvalidCode));
+    }
+
+    #[tokio::test]
+    async fn force_restore_ok() {
+        // Arrange
+        let user_repo = MockUserRepository::new()
+            .with_update(
+                FOO.user.id,
+                UserPatch::new().update_deleted_at(None),
+                Ok(true),
+            )
+            .with_save_restore_code_hash(FOO.user.id, None);
+
+        let sut = UserDeletionServiceImpl {
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.force_restore(&mut (), FOO.user.id).await;
+
+        // Assert
+        assert!(result.unwrap());
+    }
+
+    #[tokio::test]
+    async fn force_restore_user_purged() {
+        // Arrange
+        let user_repo = MockUserRepository::new().with_update(
+            FOO.user.id,
+            UserPatch::new().update_deleted_at(None),
+            Ok(false),
+        );
+
+        let sut = UserDeletionServiceImpl {
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.force_restore(&mut (), FOO.user.id).await;
+
+        // Assert
+        assert!(!result.unwrap());
+    }
+}
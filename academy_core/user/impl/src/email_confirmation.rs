@@ -1,31 +1,45 @@
 use academy_auth_contracts::AuthService;
 use academy_cache_contracts::CacheService;
 use academy_core_user_contracts::email_confirmation::{
-    UserEmailConfirmationResetPasswordError, UserEmailConfirmationService,
-    UserEmailConfirmationSubscribeToNewsletterError, UserEmailConfirmationVerifyEmailError,
+    UserEmailConfirmationAddEmailError, UserEmailConfirmationCancelEmailChangeError,
+    UserEmailConfirmationChangeEmailError, UserEmailConfirmationResetPasswordError,
+    UserEmailConfirmationService, UserEmailConfirmationSubscribeToNewsletterError,
+    UserEmailConfirmationVerifyEmailError,
 };
 use academy_di::Build;
 use academy_email_contracts::template::TemplateEmailService;
 use academy_models::{
-    email_address::EmailAddressWithName,
-    user::{UserComposite, UserId, UserPassword, UserPatchRef},
+    email_address::{EmailAddress, EmailAddressWithName},
+    user::{UserComposite, UserEmail, UserId, UserPassword, UserPatchRef},
     VerificationCode,
 };
-use academy_persistence_contracts::user::UserRepository;
-use academy_shared_contracts::{password::PasswordService, secret::SecretService};
+use academy_persistence_contracts::user::{UserRepoError, UserRepository};
+use academy_shared_contracts::{
+    password::PasswordService, secret::SecretService, time::TimeService,
+};
 use academy_templates_contracts::{
-    ResetPasswordTemplate, SubscribeNewsletterTemplate, VerifyEmailTemplate,
+    AddEmailTemplate, ChangeEmailTemplate, EmailChangeNoticeTemplate, ResetPasswordTemplate,
+    SubscribeNewsletterTemplate, VerifyEmailTemplate,
 };
 use academy_utils::trace_instrument;
 use anyhow::{anyhow, Context};
+use url::Url;
 
 use crate::UserFeatureConfig;
 
 #[derive(Debug, Clone, Build)]
 #[cfg_attr(test, derive(Default))]
-pub struct UserEmailConfirmationServiceImpl<Auth, Secret, TemplateEmail, Cache, Password, UserRepo>
-{
+pub struct UserEmailConfirmationServiceImpl<
+    Auth,
+    Time,
+    Secret,
+    TemplateEmail,
+    Cache,
+    Password,
+    UserRepo,
+> {
     auth: Auth,
+    time: Time,
     secret: Secret,
     template_email: TemplateEmail,
     cache: Cache,
@@ -34,11 +48,21 @@ pub struct UserEmailConfirmationServiceImpl<Auth, Secret, TemplateEmail, Cache,
     config: UserFeatureConfig,
 }
 
-impl<Txn, Auth, Secret, TemplateEmail, Cache, Password, UserRepo> UserEmailConfirmationService<Txn>
-    for UserEmailConfirmationServiceImpl<Auth, Secret, TemplateEmail, Cache, Password, UserRepo>
+impl<Txn, Auth, Time, Secret, TemplateEmail, Cache, Password, UserRepo>
+    UserEmailConfirmationService<Txn>
+    for UserEmailConfirmationServiceImpl<
+        Auth,
+        Time,
+        Secret,
+        TemplateEmail,
+        Cache,
+        Password,
+        UserRepo,
+    >
 where
     Txn: Send + Sync + 'static,
     Auth: AuthService<Txn>,
+    Time: TimeService,
     Secret: SecretService,
     TemplateEmail: TemplateEmailService,
     Cache: CacheService,
@@ -256,12 +280,288 @@ where
 
         Ok(())
     }
+
+    #[trace_instrument(skip(self))]
+    async fn request_email_change(
+        &self,
+        user_id: UserId,
+        old_email: Option<EmailAddressWithName>,
+        new_email: EmailAddressWithName,
+    ) -> anyhow::Result<()> {
+        let code = self.secret.generate_verification_code();
+
+        self.cache
+            .set(
+                &change_email_cache_key(user_id),
+                &(new_email.clone().into_email_address(), code.clone()),
+                Some(self.config.email_change_verification_code_ttl),
+            )
+            .await
+            .context("Failed to save code in cache")?;
+
+        self.template_email
+            .send_change_email_email(
+                new_email.clone(),
+                &ChangeEmailTemplate {
+                    code: code.into_inner(),
+                    url: self.config.email_change_redirect_url.to_string(),
+                },
+            )
+            .await
+            .context("Failed to send email")?;
+
+        let Some(old_email) = old_email else {
+            return Ok(());
+        };
+
+        let cancel_code = self.secret.generate_verification_code();
+
+        self.cache
+            .set(
+                &cancel_email_change_cache_key(user_id),
+                &(
+                    cancel_code.clone(),
+                    old_email.clone().into_email_address(),
+                    new_email.clone().into_email_address(),
+                ),
+                Some(self.config.email_change_verification_code_ttl),
+            )
+            .await
+            .context("Failed to save cancel code in cache")?;
+
+        let url = Url::parse_with_params(
+            &self.config.email_change_cancel_redirect_url,
+            [
+                ("user_id", user_id.hyphenated().to_string()),
+                ("code", cancel_code.into_inner()),
+            ],
+        )
+        .context("Failed to build cancel link")?;
+
+        self.template_email
+            .send_email_change_notice_email(
+                old_email,
+                &EmailChangeNoticeTemplate {
+                    email: new_email.into_email_address().as_str().into(),
+                    url: url.into(),
+                },
+            )
+            .await
+            .context("Failed to send email")?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn confirm_email_change(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        code: VerificationCode,
+    ) -> Result<EmailAddress, UserEmailConfirmationChangeEmailError> {
+        let cache_key = change_email_cache_key(user_id);
+
+        let (email, expected_code) = self
+            .cache
+            .get::<(EmailAddress, VerificationCode)>(&cache_key)
+            .await
+            .context("Failed to get pending email change from cache")?
+            .ok_or(UserEmailConfirmationChangeEmailError::InvalidCode)?;
+        if expected_code != code {
+            return Err(UserEmailConfirmationChangeEmailError::InvalidCode);
+        }
+
+        if !self
+            .user_repo
+            .update(
+                txn,
+                user_id,
+                UserPatchRef::new()
+                    .update_email(&Some(email.clone()))
+                    .update_email_verified(&true),
+            )
+            .await
+            .map_err(|err| match err {
+                UserRepoError::EmailConflict => UserEmailConfirmationChangeEmailError::Conflict,
+                err => anyhow!(err)
+                    .context("Failed to update user in database")
+                    .into(),
+            })?
+        {
+            return Err(UserEmailConfirmationChangeEmailError::InvalidCode);
+        }
+
+        // access tokens contain the `email_verified` field, so we need to invalidate
+        // them when changing this value
+        self.auth
+            .invalidate_access_tokens(txn, user_id)
+            .await
+            .context("Failed to invalidate access tokens")?;
+
+        Ok(email)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn cancel_email_change(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        code: VerificationCode,
+    ) -> Result<bool, UserEmailConfirmationCancelEmailChangeError> {
+        let cache_key = cancel_email_change_cache_key(user_id);
+
+        let (expected_code, old_email, new_email) = self
+            .cache
+            .get::<(VerificationCode, EmailAddress, EmailAddress)>(&cache_key)
+            .await
+            .context("Failed to get expected cancel code from cache")?
+            .ok_or(UserEmailConfirmationCancelEmailChangeError::InvalidCode)?;
+        if expected_code != code {
+            return Err(UserEmailConfirmationCancelEmailChangeError::InvalidCode);
+        }
+
+        let current_email = self
+            .user_repo
+            .get_composite(txn, user_id)
+            .await
+            .context("Failed to get user from database")?
+            .and_then(|user_composite| user_composite.user.email);
+
+        // the change has already been confirmed, so the old email address needs to be
+        // restored
+        let restore = current_email.as_ref() == Some(&new_email);
+        if restore {
+            self.user_repo
+                .update(
+                    txn,
+                    user_id,
+                    UserPatchRef::new()
+                        .update_email(&Some(old_email))
+                        .update_email_verified(&true),
+                )
+                .await
+                .map_err(|err| match err {
+                    UserRepoError::EmailConflict => {
+                        UserEmailConfirmationCancelEmailChangeError::Conflict
+                    }
+                    err => anyhow!(err)
+                        .context("Failed to update user in database")
+                        .into(),
+                })?;
+
+            self.auth
+                .invalidate_access_tokens(txn, user_id)
+                .await
+                .context("Failed to invalidate access tokens")?;
+        }
+
+        Ok(restore)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn remove_email_change(&self, user_id: UserId) -> anyhow::Result<()> {
+        self.cache
+            .remove(&change_email_cache_key(user_id))
+            .await
+            .context("Failed to remove pending email change from cache")
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn remove_cancel_email_change(&self, user_id: UserId) -> anyhow::Result<()> {
+        self.cache
+            .remove(&change_email_cache_key(user_id))
+            .await
+            .context("Failed to remove pending email change from cache")?;
+
+        self.cache
+            .remove(&cancel_email_change_cache_key(user_id))
+            .await
+            .context("Failed to remove cancel code from cache")
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn request_email_addition(
+        &self,
+        user_id: UserId,
+        email: EmailAddressWithName,
+    ) -> anyhow::Result<()> {
+        let code = self.secret.generate_verification_code();
+
+        self.cache
+            .set(
+                &add_email_cache_key(user_id),
+                &(email.clone().into_email_address(), code.clone()),
+                Some(self.config.additional_email_verification_code_ttl),
+            )
+            .await
+            .context("Failed to save code in cache")?;
+
+        self.template_email
+            .send_add_email_email(
+                email,
+                &AddEmailTemplate {
+                    code: code.into_inner(),
+                    url: self.config.additional_email_redirect_url.to_string(),
+                },
+            )
+            .await
+            .context("Failed to send email")?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn confirm_email_addition(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        code: VerificationCode,
+    ) -> Result<UserEmail, UserEmailConfirmationAddEmailError> {
+        let cache_key = add_email_cache_key(user_id);
+
+        let (email, expected_code) = self
+            .cache
+            .get::<(EmailAddress, VerificationCode)>(&cache_key)
+            .await
+            .context("Failed to get pending email address from cache")?
+            .ok_or(UserEmailConfirmationAddEmailError::InvalidCode)?;
+        if expected_code != code {
+            return Err(UserEmailConfirmationAddEmailError::InvalidCode);
+        }
+
+        let user_email = UserEmail {
+            user_id,
+            email,
+            created_at: self.time.now(),
+        };
+
+        self.user_repo
+            .add_email(txn, &user_email)
+            .await
+            .map_err(|err| match err {
+                UserRepoError::EmailConflict => UserEmailConfirmationAddEmailError::Conflict,
+                err => anyhow!(err)
+                    .context("Failed to add email address in database")
+                    .into(),
+            })?;
+
+        self.cache
+            .remove(&cache_key)
+            .await
+            .context("Failed to remove code from cache")?;
+
+        Ok(user_email)
+    }
 }
 
 fn verification_cache_key(verification_code: &VerificationCode) -> String {
     format!("verification:{}", **verification_code)
 }
 
+fn add_email_cache_key(user_id: UserId) -> String {
+    format!("add_email_code:{}", user_id.hyphenated())
+}
+
 fn subscribe_newsletter_cache_key(user_id: UserId) -> String {
     format!("subscribe_newsletter_code:{}", user_id.hyphenated())
 }
@@ -270,24 +570,35 @@ fn reset_password_cache_key(user_id: UserId) -> String {
     format!("reset_password_code:{}", user_id.hyphenated())
 }
 
+fn change_email_cache_key(user_id: UserId) -> String {
+    format!("change_email_code:{}", user_id.hyphenated())
+}
+
+fn cancel_email_change_cache_key(user_id: UserId) -> String {
+    format!("cancel_email_change_code:{}", user_id.hyphenated())
+}
+
 #[cfg(test)]
 mod tests {
     use academy_auth_contracts::MockAuthService;
     use academy_cache_contracts::MockCacheService;
     use academy_demo::{
-        user::{FOO, FOO_PASSWORD},
+        user::{FOO, FOO_EMAIL_1, FOO_PASSWORD},
         VERIFICATION_CODE_1, VERIFICATION_CODE_2,
     };
     use academy_email_contracts::template::MockTemplateEmailService;
     use academy_models::{email_address::EmailAddress, user::UserPatch};
     use academy_persistence_contracts::user::MockUserRepository;
-    use academy_shared_contracts::{password::MockPasswordService, secret::MockSecretService};
+    use academy_shared_contracts::{
+        password::MockPasswordService, secret::MockSecretService, time::MockTimeService,
+    };
     use academy_utils::{assert_matches, Apply};
 
     use super::*;
 
     type Sut = UserEmailConfirmationServiceImpl<
         MockAuthService<()>,
+        MockTimeService,
         MockSecretService,
         MockTemplateEmailService,
         MockCacheService,
@@ -746,4 +1057,596 @@ mod tests {
             Err(UserEmailConfirmationSubscribeToNewsletterError::InvalidCode)
         );
     }
+
+    #[tokio::test]
+    async fn request_email_change_with_old_email() {
+        // Arrange
+        let config = UserFeatureConfig::default();
+
+        let old_email = FOO
+            .user
+            .email
+            .clone()
+            .unwrap()
+            .with_name(FOO.profile.display_name.clone().into_inner());
+        let new_email = "new@example.com"
+            .parse::<EmailAddress>()
+            .unwrap()
+            .with_name(FOO.profile.display_name.clone().into_inner());
+
+        let secret = MockSecretService::new()
+            .with_generate_verification_code(VERIFICATION_CODE_1.clone())
+            .with_generate_verification_code(VERIFICATION_CODE_2.clone());
+
+        let cache = MockCacheService::new()
+            .with_set(
+                format!("change_email_code:{}", FOO.user.id.hyphenated()),
+                (
+                    new_email.clone().into_email_address(),
+                    VERIFICATION_CODE_1.clone(),
+                ),
+                Some(config.email_change_verification_code_ttl),
+            )
+            .with_set(
+                format!("cancel_email_change_code:{}", FOO.user.id.hyphenated()),
+                (
+                    VERIFICATION_CODE_2.clone(),
+                    old_email.clone().into_email_address(),
+                    new_email.clone().into_email_address(),
+                ),
+                Some(config.email_change_verification_code_ttl),
+            );
+
+        let template_email = MockTemplateEmailService::new()
+            .with_send_change_email_email(
+                new_email.clone(),
+                ChangeEmailTemplate {
+                    code: VERIFICATION_CODE_1.clone().into_inner(),
+                    url: (*config.email_change_redirect_url).clone(),
+                },
+                true,
+            )
+            .with_send_email_change_notice_email(
+                old_email.clone(),
+                EmailChangeNoticeTemplate {
+                    email: "new@example.com".into(),
+                    url: format!(
+                        "https://bootstrap.academy/account/cancel-email-change?user_id={}&code={}",
+                        FOO.user.id.hyphenated(),
+                        **VERIFICATION_CODE_2
+                    ),
+                },
+                true,
+            );
+
+        let sut = UserEmailConfirmationServiceImpl {
+            secret,
+            cache,
+            template_email,
+            config,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .request_email_change(FOO.user.id, Some(old_email), new_email)
+            .await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn request_email_change_without_old_email() {
+        // Arrange
+        let config = UserFeatureConfig::default();
+
+        let new_email = "new@example.com"
+            .parse::<EmailAddress>()
+            .unwrap()
+            .with_name(FOO.profile.display_name.clone().into_inner());
+
+        let secret =
+            MockSecretService::new().with_generate_verification_code(VERIFICATION_CODE_1.clone());
+
+        let cache = MockCacheService::new().with_set(
+            format!("change_email_code:{}", FOO.user.id.hyphenated()),
+            (
+                new_email.clone().into_email_address(),
+                VERIFICATION_CODE_1.clone(),
+            ),
+            Some(config.email_change_verification_code_ttl),
+        );
+
+        let template_email = MockTemplateEmailService::new().with_send_change_email_email(
+            new_email.clone(),
+            ChangeEmailTemplate {
+                code: VERIFICATION_CODE_1.clone().into_inner(),
+                url: (*config.email_change_redirect_url).clone(),
+            },
+            true,
+        );
+
+        let sut = UserEmailConfirmationServiceImpl {
+            secret,
+            cache,
+            template_email,
+            config,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.request_email_change(FOO.user.id, None, new_email).await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn confirm_email_change_ok() {
+        // Arrange
+        let new_email = "new@example.com".parse::<EmailAddress>().unwrap();
+
+        let auth = MockAuthService::new().with_invalidate_access_tokens(FOO.user.id);
+
+        let cache_key = format!("change_email_code:{}", FOO.user.id.hyphenated());
+        let cache = MockCacheService::new().with_get(
+            cache_key,
+            Some((new_email.clone(), VERIFICATION_CODE_1.clone())),
+        );
+
+        let user_repo = MockUserRepository::new().with_update(
+            FOO.user.id,
+            UserPatch::new()
+                .update_email(Some(new_email.clone()))
+                .update_email_verified(true),
+            Ok(true),
+        );
+
+        let sut = UserEmailConfirmationServiceImpl {
+            auth,
+            cache,
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .confirm_email_change(&mut (), FOO.user.id, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), new_email);
+    }
+
+    #[tokio::test]
+    async fn confirm_email_change_no_code() {
+        // Arrange
+        let cache = MockCacheService::new().with_get(
+            format!("change_email_code:{}", FOO.user.id.hyphenated()),
+            None::<(EmailAddress, VerificationCode)>,
+        );
+
+        let sut = UserEmailConfirmationServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .confirm_email_change(&mut (), FOO.user.id, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(UserEmailConfirmationChangeEmailError::InvalidCode)
+        );
+    }
+
+    #[tokio::test]
+    async fn confirm_email_change_invalid_code() {
+        // Arrange
+        let new_email = "new@example.com".parse::<EmailAddress>().unwrap();
+
+        let cache = MockCacheService::new().with_get(
+            format!("change_email_code:{}", FOO.user.id.hyphenated()),
+            Some((new_email, VERIFICATION_CODE_2.clone())),
+        );
+
+        let sut = UserEmailConfirmationServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .confirm_email_change(&mut (), FOO.user.id, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(UserEmailConfirmationChangeEmailError::InvalidCode)
+        );
+    }
+
+    #[tokio::test]
+    async fn confirm_email_change_conflict() {
+        // Arrange
+        let new_email = "new@example.com".parse::<EmailAddress>().unwrap();
+
+        let cache = MockCacheService::new().with_get(
+            format!("change_email_code:{}", FOO.user.id.hyphenated()),
+            Some((new_email.clone(), VERIFICATION_CODE_1.clone())),
+        );
+
+        let user_repo = MockUserRepository::new().with_update(
+            FOO.user.id,
+            UserPatch::new()
+                .update_email(Some(new_email))
+                .update_email_verified(true),
+            Err(UserRepoError::EmailConflict),
+        );
+
+        let sut = UserEmailConfirmationServiceImpl {
+            cache,
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .confirm_email_change(&mut (), FOO.user.id, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        assert_matches!(result, Err(UserEmailConfirmationChangeEmailError::Conflict));
+    }
+
+    #[tokio::test]
+    async fn cancel_email_change_pending() {
+        // Arrange
+        let new_email = "new@example.com".parse::<EmailAddress>().unwrap();
+
+        let cache_key = format!("cancel_email_change_code:{}", FOO.user.id.hyphenated());
+        let cache = MockCacheService::new().with_get(
+            cache_key,
+            Some((
+                VERIFICATION_CODE_1.clone(),
+                FOO.user.email.clone().unwrap(),
+                new_email,
+            )),
+        );
+
+        let user_repo =
+            MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+        let sut = UserEmailConfirmationServiceImpl {
+            cache,
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .cancel_email_change(&mut (), FOO.user.id, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        assert!(!result.unwrap());
+    }
+
+    #[tokio::test]
+    async fn cancel_email_change_confirmed() {
+        // Arrange
+        let new_email = "new@example.com".parse::<EmailAddress>().unwrap();
+
+        let auth = MockAuthService::new().with_invalidate_access_tokens(FOO.user.id);
+
+        let cache_key = format!("cancel_email_change_code:{}", FOO.user.id.hyphenated());
+        let cache = MockCacheService::new().with_get(
+            cache_key,
+            Some((
+                VERIFICATION_CODE_1.clone(),
+                FOO.user.email.clone().unwrap(),
+                new_email.clone(),
+            )),
+        );
+
+        let user_repo = MockUserRepository::new()
+            .with_get_composite(
+                FOO.user.id,
+                Some(FOO.clone().with(|u| u.user.email = Some(new_email))),
+            )
+            .with_update(
+                FOO.user.id,
+                UserPatch::new()
+                    .update_email(FOO.user.email.clone())
+                    .update_email_verified(true),
+                Ok(true),
+            );
+
+        let sut = UserEmailConfirmationServiceImpl {
+            auth,
+            cache,
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .cancel_email_change(&mut (), FOO.user.id, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        assert!(result.unwrap());
+    }
+
+    #[tokio::test]
+    async fn cancel_email_change_confirmed_conflict() {
+        // Arrange
+        let new_email = "new@example.com".parse::<EmailAddress>().unwrap();
+
+        let cache_key = format!("cancel_email_change_code:{}", FOO.user.id.hyphenated());
+        let cache = MockCacheService::new().with_get(
+            cache_key,
+            Some((
+                VERIFICATION_CODE_1.clone(),
+                FOO.user.email.clone().unwrap(),
+                new_email.clone(),
+            )),
+        );
+
+        let user_repo = MockUserRepository::new()
+            .with_get_composite(
+                FOO.user.id,
+                Some(FOO.clone().with(|u| u.user.email = Some(new_email))),
+            )
+            .with_update(
+                FOO.user.id,
+                UserPatch::new()
+                    .update_email(FOO.user.email.clone())
+                    .update_email_verified(true),
+                Err(UserRepoError::EmailConflict),
+            );
+
+        let sut = UserEmailConfirmationServiceImpl {
+            cache,
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .cancel_email_change(&mut (), FOO.user.id, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(UserEmailConfirmationCancelEmailChangeError::Conflict)
+        );
+    }
+
+    #[tokio::test]
+    async fn cancel_email_change_no_code() {
+        // Arrange
+        let cache = MockCacheService::new().with_get(
+            format!("cancel_email_change_code:{}", FOO.user.id.hyphenated()),
+            None::<(VerificationCode, EmailAddress, EmailAddress)>,
+        );
+
+        let sut = UserEmailConfirmationServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .cancel_email_change(&mut (), FOO.user.id, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(UserEmailConfirmationCancelEmailChangeError::InvalidCode)
+        );
+    }
+
+    #[tokio::test]
+    async fn cancel_email_change_invalid_code() {
+        // Arrange
+        let new_email = "new@example.com".parse::<EmailAddress>().unwrap();
+
+        let cache = MockCacheService::new().with_get(
+            format!("cancel_email_change_code:{}", FOO.user.id.hyphenated()),
+            Some((
+                VERIFICATION_CODE_2.clone(),
+                FOO.user.email.clone().unwrap(),
+                new_email,
+            )),
+        );
+
+        let sut = UserEmailConfirmationServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .cancel_email_change(&mut (), FOO.user.id, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(UserEmailConfirmationCancelEmailChangeError::InvalidCode)
+        );
+    }
+
+    #[tokio::test]
+    async fn remove_email_change() {
+        // Arrange
+        let cache = MockCacheService::new()
+            .with_remove(format!("change_email_code:{}", FOO.user.id.hyphenated()));
+
+        let sut = UserEmailConfirmationServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.remove_email_change(FOO.user.id).await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn remove_cancel_email_change() {
+        // Arrange
+        let cache = MockCacheService::new()
+            .with_remove(format!("change_email_code:{}", FOO.user.id.hyphenated()))
+            .with_remove(format!(
+                "cancel_email_change_code:{}",
+                FOO.user.id.hyphenated()
+            ));
+
+        let sut = UserEmailConfirmationServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.remove_cancel_email_change(FOO.user.id).await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn request_email_addition() {
+        // Arrange
+        let config = UserFeatureConfig::default();
+
+        let email = FOO_EMAIL_1
+            .email
+            .clone()
+            .with_name(FOO.profile.display_name.clone().into_inner());
+
+        let secret =
+            MockSecretService::new().with_generate_verification_code(VERIFICATION_CODE_1.clone());
+
+        let cache = MockCacheService::new().with_set(
+            format!("add_email_code:{}", FOO.user.id.hyphenated()),
+            (FOO_EMAIL_1.email.clone(), VERIFICATION_CODE_1.clone()),
+            Some(config.additional_email_verification_code_ttl),
+        );
+
+        let template_email = MockTemplateEmailService::new().with_send_add_email_email(
+            email.clone(),
+            AddEmailTemplate {
+                code: VERIFICATION_CODE_1.clone().into_inner(),
+                url: (*config.additional_email_redirect_url).clone(),
+            },
+            true,
+        );
+
+        let sut = UserEmailConfirmationServiceImpl {
+            secret,
+            cache,
+            template_email,
+            config,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.request_email_addition(FOO.user.id, email).await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn confirm_email_addition_ok() {
+        // Arrange
+        let time = MockTimeService::new().with_now(FOO_EMAIL_1.created_at);
+
+        let cache_key = format!("add_email_code:{}", FOO.user.id.hyphenated());
+        let cache = MockCacheService::new()
+            .with_get(
+                cache_key.clone(),
+                Some((FOO_EMAIL_1.email.clone(), VERIFICATION_CODE_1.clone())),
+            )
+            .with_remove(cache_key);
+
+        let user_repo = MockUserRepository::new().with_add_email(FOO_EMAIL_1.clone(), Ok(()));
+
+        let sut = UserEmailConfirmationServiceImpl {
+            time,
+            cache,
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .confirm_email_addition(&mut (), FOO.user.id, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), *FOO_EMAIL_1);
+    }
+
+    #[tokio::test]
+    async fn confirm_email_addition_invalid_code() {
+        // Arrange
+        let cache = MockCacheService::new().with_get(
+            format!("add_email_code:{}", FOO.user.id.hyphenated()),
+            Some((FOO_EMAIL_1.email.clone(), VERIFICATION_CODE_2.clone())),
+        );
+
+        let sut = UserEmailConfirmationServiceImpl {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .confirm_email_addition(&mut (), FOO.user.id, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        assert_matches!(result, Err(UserEmailConfirmationAddEmailError::InvalidCode));
+    }
+
+    #[tokio::test]
+    async fn confirm_email_addition_conflict() {
+        // Arrange
+        let time = MockTimeService::new().with_now(FOO_EMAIL_1.created_at);
+
+        let cache = MockCacheService::new().with_get(
+            format!("add_email_code:{}", FOO.user.id.hyphenated()),
+            Some((FOO_EMAIL_1.email.clone(), VERIFICATION_CODE_1.clone())),
+        );
+
+        let user_repo = MockUserRepository::new()
+            .with_add_email(FOO_EMAIL_1.clone(), Err(UserRepoError::EmailConflict));
+
+        let sut = UserEmailConfirmationServiceImpl {
+            time,
+            cache,
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .confirm_email_addition(&mut (), FOO.user.id, VERIFICATION_CODE_1.clone())
+            .await;
+
+        // Assert
+        assert_matches!(result, Err(UserEmailConfirmationAddEmailError::Conflict));
+    }
 }
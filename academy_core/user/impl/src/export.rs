@@ -0,0 +1,651 @@
+use academy_cache_contracts::CacheService;
+use academy_core_user_contracts::export::UserExportService;
+use academy_di::Build;
+use academy_email_contracts::template::TemplateEmailService;
+use academy_models::{
+    audit::AuditEventFilter,
+    pagination::{PaginationLimit, PaginationSlice},
+    user::UserId,
+    user_export::UserExport,
+    VerificationCode,
+};
+use academy_persistence_contracts::{
+    audit::AuditEventRepository, mfa::MfaRepository, oauth2::OAuth2Repository,
+    session::SessionRepository, user::UserRepository, Database, Transaction,
+};
+use academy_shared_contracts::{secret::SecretService, time::TimeService};
+use academy_templates_contracts::UserExportTemplate;
+use academy_utils::trace_instrument;
+use anyhow::Context;
+use tracing::error;
+use url::Url;
+
+use crate::UserFeatureConfig;
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct UserExportServiceImpl<
+    Db,
+    Time,
+    Secret,
+    Cache,
+    TemplateEmail,
+    UserRepo,
+    SessionRepo,
+    OAuth2Repo,
+    MfaRepo,
+    AuditEventRepo,
+> {
+    db: Db,
+    time: Time,
+    secret: Secret,
+    cache: Cache,
+    template_email: TemplateEmail,
+    user_repo: UserRepo,
+    session_repo: SessionRepo,
+    oauth2_repo: OAuth2Repo,
+    mfa_repo: MfaRepo,
+    audit_event_repo: AuditEventRepo,
+    config: UserFeatureConfig,
+}
+
+impl<
+        Db,
+        Time,
+        Secret,
+        Cache,
+        TemplateEmail,
+        UserRepo,
+        SessionRepo,
+        OAuth2Repo,
+        MfaRepo,
+        AuditEventRepo,
+    > UserExportService
+    for UserExportServiceImpl<
+        Db,
+        Time,
+        Secret,
+        Cache,
+        TemplateEmail,
+        UserRepo,
+        SessionRepo,
+        OAuth2Repo,
+        MfaRepo,
+        AuditEventRepo,
+    >
+where
+    Db: Database,
+    Time: TimeService,
+    Secret: SecretService,
+    Cache: CacheService,
+    TemplateEmail: TemplateEmailService,
+    UserRepo: UserRepository<Db::Transaction>,
+    SessionRepo: SessionRepository<Db::Transaction>,
+    OAuth2Repo: OAuth2Repository<Db::Transaction>,
+    MfaRepo: MfaRepository<Db::Transaction>,
+    AuditEventRepo: AuditEventRepository<Db::Transaction>,
+{
+    #[trace_instrument(skip(self))]
+    async fn generate(&self, user_id: UserId) -> anyhow::Result<Option<UserExport>> {
+        let mut txn = self.db.begin_transaction().await?;
+
+        let Some(user_composite) = self
+            .user_repo
+            .get_composite(&mut txn, user_id)
+            .await
+            .context("Failed to get user from database")?
+        else {
+            return Ok(None);
+        };
+
+        let emails = self
+            .user_repo
+            .list_emails(&mut txn, user_id)
+            .await
+            .context("Failed to get email addresses from database")?;
+
+        let name_history = self
+            .user_repo
+            .list_name_history(&mut txn, user_id)
+            .await
+            .context("Failed to get name history from database")?;
+
+        let sessions = self
+            .session_repo
+            .list_by_user(&mut txn, user_id)
+            .await
+            .context("Failed to get sessions from database")?;
+
+        let oauth2_links = self
+            .oauth2_repo
+            .list_links_by_user(&mut txn, user_id)
+            .await
+            .context("Failed to get oauth2 links from database")?;
+
+        let totp_devices = self
+            .mfa_repo
+            .list_totp_devices_by_user(&mut txn, user_id)
+            .await
+            .context("Failed to get totp devices from database")?;
+
+        let webauthn_devices = self
+            .mfa_repo
+            .list_webauthn_devices_by_user(&mut txn, user_id)
+            .await
+            .context("Failed to get webauthn devices from database")?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        let filter = AuditEventFilter {
+            user_id: Some(user_id),
+            ..Default::default()
+        };
+        let mut audit_events = Vec::new();
+        loop {
+            let page = self
+                .audit_event_repo
+                .list(
+                    &mut txn,
+                    &filter,
+                    PaginationSlice {
+                        limit: PaginationLimit::max(),
+                        offset: audit_events.len() as u64,
+                    },
+                )
+                .await
+                .context("Failed to get audit events from database")?;
+            let done = (page.len() as u64) < PaginationLimit::MAX;
+            audit_events.extend(page);
+            if done {
+                break;
+            }
+        }
+
+        Ok(Some(UserExport {
+            created_at: self.time.now(),
+            user_composite,
+            emails,
+            name_history,
+            sessions,
+            oauth2_links,
+            totp_devices,
+            webauthn_devices,
+            audit_events,
+        }))
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn process_requests(&self) -> anyhow::Result<()> {
+        let mut txn = self.db.begin_transaction().await?;
+
+        let user_ids = self
+            .user_repo
+            .list_export_requests(&mut txn)
+            .await
+            .context("Failed to get export requests from database")?;
+
+        drop(txn);
+
+        for user_id in user_ids {
+            if let Err(err) = self.process_request(user_id).await {
+                error!("Failed to process export request of user {user_id:?}: {err:#}");
+            }
+        }
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn get(
+        &self,
+        user_id: UserId,
+        code: &VerificationCode,
+    ) -> anyhow::Result<Option<UserExport>> {
+        self.cache
+            .get(&export_cache_key(user_id, code))
+            .await
+            .context("Failed to get user export from cache")
+    }
+}
+
+impl<
+        Db,
+        Time,
+        Secret,
+        Cache,
+        TemplateEmail,
+        UserRepo,
+        SessionRepo,
+        OAuth2Repo,
+        MfaRepo,
+        AuditEventRepo,
+    >
+    UserExportServiceImpl<
+        Db,
+        Time,
+        Secret,
+        Cache,
+        TemplateEmail,
+        UserRepo,
+        SessionRepo,
+        OAuth2Repo,
+        MfaRepo,
+        AuditEventRepo,
+    >
+where
+    Db: Database,
+    Time: TimeService,
+    Secret: SecretService,
+    Cache: CacheService,
+    TemplateEmail: TemplateEmailService,
+    UserRepo: UserRepository<Db::Transaction>,
+    SessionRepo: SessionRepository<Db::Transaction>,
+    OAuth2Repo: OAuth2Repository<Db::Transaction>,
+    MfaRepo: MfaRepository<Db::Transaction>,
+    AuditEventRepo: AuditEventRepository<Db::Transaction>,
+{
+    #[trace_instrument(skip(self))]
+    async fn process_request(&self, user_id: UserId) -> anyhow::Result<()> {
+        let Some(export) = self.generate(user_id).await? else {
+            // the user has been deleted in the meantime
+            return Ok(());
+        };
+
+        if let Some(email) = export.user_composite.user.email.clone() {
+            let code = self.secret.generate_verification_code();
+
+            self.cache
+                .set(
+                    &export_cache_key(user_id, &code),
+                    &export,
+                    Some(self.config.export_code_ttl),
+                )
+                .await
+                .context("Failed to save user export in cache")?;
+
+            let url = Url::parse_with_params(
+                &self.config.export_redirect_url,
+                [
+                    ("user_id", user_id.hyphenated().to_string()),
+                    ("code", code.into_inner()),
+                ],
+            )
+            .context("Failed to build user export download link")?;
+
+            let expires_at = export.created_at + self.config.export_code_ttl;
+
+            self.template_email
+                .send_user_export_email(
+                    email.with_name(
+                        export
+                            .user_composite
+                            .profile
+                            .display_name
+                            .clone()
+                            .into_inner(),
+                    ),
+                    &UserExportTemplate {
+                        url: url.into(),
+                        expires_at: expires_at.format("%d.%m.%Y %H:%M UTC").to_string(),
+                    },
+                )
+                .await
+                .context("Failed to send user export email")?;
+        }
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        self.user_repo
+            .save_export_requested(&mut txn, user_id, false)
+            .await
+            .context("Failed to remove export request from database")?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+}
+
+fn export_cache_key(user_id: UserId, code: &VerificationCode) -> String {
+    format!("user_export:{}:{}", user_id.hyphenated(), **code)
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_cache_contracts::MockCacheService;
+    use academy_demo::{
+        audit::{ADMIN_UPDATE_FOO, FOO_PASSWORD_RESET},
+        mfa::{ADMIN2_WEBAUTHN_1, FOO_TOTP_1},
+        oauth2::FOO_OAUTH2_LINK_1,
+        session::{FOO_1, FOO_2},
+        user::{FOO, FOO_EMAIL_1, FOO_NAME_HISTORY_1},
+    };
+    use academy_email_contracts::template::MockTemplateEmailService;
+    use academy_models::user_export::UserExportWebauthnDevice;
+    use academy_persistence_contracts::{
+        audit::MockAuditEventRepository, mfa::MockMfaRepository, oauth2::MockOAuth2Repository,
+        session::MockSessionRepository, user::MockUserRepository, MockDatabase, MockTransaction,
+    };
+    use academy_shared_contracts::{secret::MockSecretService, time::MockTimeService};
+    use academy_utils::assert_matches;
+    use anyhow::anyhow;
+
+    use super::*;
+
+    type Sut = UserExportServiceImpl<
+        MockDatabase,
+        MockTimeService,
+        MockSecretService,
+        MockCacheService,
+        MockTemplateEmailService,
+        MockUserRepository<MockTransaction>,
+        MockSessionRepository<MockTransaction>,
+        MockOAuth2Repository<MockTransaction>,
+        MockMfaRepository<MockTransaction>,
+        MockAuditEventRepository<MockTransaction>,
+    >;
+
+    #[tokio::test]
+    async fn generate_ok() {
+        // Arrange
+        let expected = make_export();
+
+        let db = MockDatabase::build(false);
+
+        let time = MockTimeService::new().with_now(expected.created_at);
+
+        let user_repo = MockUserRepository::new()
+            .with_get_composite(FOO.user.id, Some(FOO.clone()))
+            .with_list_emails(FOO.user.id, vec![FOO_EMAIL_1.clone()])
+            .with_list_name_history(FOO.user.id, vec![FOO_NAME_HISTORY_1.clone()]);
+
+        let session_repo = MockSessionRepository::new()
+            .with_list_by_user(FOO.user.id, vec![FOO_1.clone(), FOO_2.clone()]);
+
+        let oauth2_repo = MockOAuth2Repository::new()
+            .with_list_links_by_user(FOO.user.id, vec![FOO_OAUTH2_LINK_1.clone()]);
+
+        let mfa_repo = MockMfaRepository::new()
+            .with_list_totp_devices_by_user(FOO.user.id, vec![FOO_TOTP_1.clone()])
+            .with_list_webauthn_devices_by_user(FOO.user.id, vec![]);
+
+        let audit_event_repo = MockAuditEventRepository::new().with_list(
+            AuditEventFilter {
+                user_id: Some(FOO.user.id),
+                ..Default::default()
+            },
+            PaginationSlice::default(),
+            expected.audit_events.clone(),
+        );
+
+        let sut = Sut {
+            db,
+            time,
+            user_repo,
+            session_repo,
+            oauth2_repo,
+            mfa_repo,
+            audit_event_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.generate(FOO.user.id).await;
+
+        // Assert
+        assert_eq!(result.unwrap(), Some(expected));
+    }
+
+    #[tokio::test]
+    async fn generate_not_found() {
+        // Arrange
+        let db = MockDatabase::build(false);
+
+        let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, None);
+
+        let sut = Sut {
+            db,
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.generate(FOO.user.id).await;
+
+        // Assert
+        assert_matches!(result, Ok(None));
+    }
+
+    #[tokio::test]
+    async fn process_requests_ok() {
+        // Arrange
+        let config = UserFeatureConfig::default();
+        let expected = make_export();
+        let code = VerificationCode::try_new("JRHZ-3G4B-MIBG-PPYI").unwrap();
+
+        let db = make_db(&[false, false, true]);
+
+        let time = MockTimeService::new().with_now(expected.created_at);
+
+        let secret = MockSecretService::new().with_generate_verification_code(code.clone());
+
+        let cache = MockCacheService::new().with_set(
+            export_cache_key(FOO.user.id, &code),
+            expected.clone(),
+            Some(config.export_code_ttl),
+        );
+
+        let template_email = MockTemplateEmailService::new().with_send_user_export_email(
+            FOO.user
+                .email
+                .clone()
+                .unwrap()
+                .with_name(FOO.profile.display_name.clone().into_inner()),
+            UserExportTemplate {
+                url: format!(
+                    "{}?user_id={}&code={}",
+                    config.export_redirect_url,
+                    FOO.user.id.hyphenated(),
+                    *code
+                ),
+                expires_at: (expected.created_at + config.export_code_ttl)
+                    .format("%d.%m.%Y %H:%M UTC")
+                    .to_string(),
+            },
+            true,
+        );
+
+        let user_repo = MockUserRepository::new()
+            .with_list_export_requests(vec![FOO.user.id])
+            .with_get_composite(FOO.user.id, Some(FOO.clone()))
+            .with_list_emails(FOO.user.id, vec![FOO_EMAIL_1.clone()])
+            .with_list_name_history(FOO.user.id, vec![FOO_NAME_HISTORY_1.clone()])
+            .with_save_export_requested(FOO.user.id, false);
+
+        let sut = Sut {
+            db,
+            time,
+            secret,
+            cache,
+            template_email,
+            user_repo,
+            session_repo: make_session_repo(),
+            oauth2_repo: make_oauth2_repo(),
+            mfa_repo: make_mfa_repo(),
+            audit_event_repo: make_audit_event_repo(&expected),
+            config,
+        };
+
+        // Act
+        let result = sut.process_requests().await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn process_requests_send_failed() {
+        // Arrange
+        let config = UserFeatureConfig::default();
+        let expected = make_export();
+        let code = VerificationCode::try_new("JRHZ-3G4B-MIBG-PPYI").unwrap();
+
+        let db = make_db(&[false, false]);
+
+        let time = MockTimeService::new().with_now(expected.created_at);
+
+        let secret = MockSecretService::new().with_generate_verification_code(code.clone());
+
+        let cache = MockCacheService::new().with_set(
+            export_cache_key(FOO.user.id, &code),
+            expected.clone(),
+            Some(config.export_code_ttl),
+        );
+
+        let mut template_email = MockTemplateEmailService::new();
+        template_email
+            .expect_send_user_export_email()
+            .once()
+            .return_once(|_, _| Box::pin(std::future::ready(Err(anyhow!("send failed")))));
+
+        let user_repo = MockUserRepository::new()
+            .with_list_export_requests(vec![FOO.user.id])
+            .with_get_composite(FOO.user.id, Some(FOO.clone()))
+            .with_list_emails(FOO.user.id, vec![FOO_EMAIL_1.clone()])
+            .with_list_name_history(FOO.user.id, vec![FOO_NAME_HISTORY_1.clone()]);
+
+        let sut = Sut {
+            db,
+            time,
+            secret,
+            cache,
+            template_email,
+            user_repo,
+            session_repo: make_session_repo(),
+            oauth2_repo: make_oauth2_repo(),
+            mfa_repo: make_mfa_repo(),
+            audit_event_repo: make_audit_event_repo(&expected),
+            config,
+        };
+
+        // Act
+        let result = sut.process_requests().await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn get_ok() {
+        // Arrange
+        let export = make_export();
+        let code = VerificationCode::try_new("JRHZ-3G4B-MIBG-PPYI").unwrap();
+
+        let cache = MockCacheService::new()
+            .with_get(export_cache_key(FOO.user.id, &code), Some(export.clone()));
+
+        let sut = Sut {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.get(FOO.user.id, &code).await;
+
+        // Assert
+        assert_eq!(result.unwrap(), Some(export));
+    }
+
+    #[tokio::test]
+    async fn get_invalid_code() {
+        // Arrange
+        let code = VerificationCode::try_new("JRHZ-3G4B-MIBG-PPYI").unwrap();
+
+        let cache = MockCacheService::new()
+            .with_get::<UserExport>(export_cache_key(FOO.user.id, &code), None);
+
+        let sut = Sut {
+            cache,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.get(FOO.user.id, &code).await;
+
+        // Assert
+        assert_matches!(result, Ok(None));
+    }
+
+    #[test]
+    fn webauthn_device_metadata() {
+        // Arrange
+        let device = ADMIN2_WEBAUTHN_1.clone();
+
+        // Act
+        let result = UserExportWebauthnDevice::from(device.clone());
+
+        // Assert
+        assert_eq!(result.id, device.id);
+        assert_eq!(result.name, device.name);
+        assert_eq!(result.created_at, device.created_at);
+        assert_eq!(result.last_used_at, device.last_used_at);
+    }
+
+    fn make_export() -> UserExport {
+        UserExport {
+            created_at: FOO.user.created_at,
+            user_composite: FOO.clone(),
+            emails: vec![FOO_EMAIL_1.clone()],
+            name_history: vec![FOO_NAME_HISTORY_1.clone()],
+            sessions: vec![FOO_1.clone(), FOO_2.clone()],
+            oauth2_links: vec![FOO_OAUTH2_LINK_1.clone()],
+            totp_devices: vec![FOO_TOTP_1.clone()],
+            webauthn_devices: vec![],
+            audit_events: vec![ADMIN_UPDATE_FOO.clone(), FOO_PASSWORD_RESET.clone()],
+        }
+    }
+
+    fn make_db(expect_commits: &[bool]) -> MockDatabase {
+        let mut db = MockDatabase::new();
+        for &expect_commit in expect_commits {
+            let mut txn = MockTransaction::new();
+            if expect_commit {
+                txn.expect_commit()
+                    .once()
+                    .return_once(|| Box::pin(std::future::ready(Ok(()))));
+            }
+            db.expect_begin_transaction()
+                .once()
+                .return_once(|| Box::pin(std::future::ready(Ok(txn))));
+        }
+        db
+    }
+
+    fn make_session_repo() -> MockSessionRepository<MockTransaction> {
+        MockSessionRepository::new()
+            .with_list_by_user(FOO.user.id, vec![FOO_1.clone(), FOO_2.clone()])
+    }
+
+    fn make_oauth2_repo() -> MockOAuth2Repository<MockTransaction> {
+        MockOAuth2Repository::new()
+            .with_list_links_by_user(FOO.user.id, vec![FOO_OAUTH2_LINK_1.clone()])
+    }
+
+    fn make_mfa_repo() -> MockMfaRepository<MockTransaction> {
+        MockMfaRepository::new()
+            .with_list_totp_devices_by_user(FOO.user.id, vec![FOO_TOTP_1.clone()])
+            .with_list_webauthn_devices_by_user(FOO.user.id, vec![])
+    }
+
+    fn make_audit_event_repo(export: &UserExport) -> MockAuditEventRepository<MockTransaction> {
+        MockAuditEventRepository::new().with_list(
+            AuditEventFilter {
+                user_id: Some(FOO.user.id),
+                ..Default::default()
+            },
+            PaginationSlice::default(),
+            export.audit_events.clone(),
+        )
+    }
+}
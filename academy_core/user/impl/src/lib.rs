@@ -1,29 +1,50 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashSet, net::IpAddr, sync::Arc, time::Duration};
 
 use academy_auth_contracts::{AuthResultExt, AuthService};
+use academy_core_audit_contracts::audit::AuditService;
 use academy_core_oauth2_contracts::registration::OAuth2RegistrationService;
-use academy_core_session_contracts::session::SessionService;
+use academy_core_session_contracts::{
+    new_device::SessionNewDeviceService, session::SessionService,
+};
 use academy_core_user_contracts::{
+    avatar::{UserAvatarService, UserAvatarUploadError},
+    deletion::{UserDeletionRestoreError, UserDeletionService},
     email_confirmation::{
-        UserEmailConfirmationResetPasswordError, UserEmailConfirmationService,
-        UserEmailConfirmationSubscribeToNewsletterError, UserEmailConfirmationVerifyEmailError,
+        UserEmailConfirmationAddEmailError, UserEmailConfirmationCancelEmailChangeError,
+        UserEmailConfirmationChangeEmailError, UserEmailConfirmationResetPasswordError,
+        UserEmailConfirmationService, UserEmailConfirmationSubscribeToNewsletterError,
+        UserEmailConfirmationVerifyEmailError,
     },
+    export::UserExportService,
+    name_policy::{UserNamePolicyCheckError, UserNamePolicyService},
+    password_policy::{UserPasswordPolicyCheckError, UserPasswordPolicyService},
     update::{
-        UserUpdateEmailError, UserUpdateNameError, UserUpdateNameRateLimitPolicy, UserUpdateService,
+        UserUpdateEmailError, UserUpdateNameError, UserUpdateNameRateLimitPolicy,
+        UserUpdatePrimaryEmailError, UserUpdateService,
     },
     user::{UserCreateCommand, UserListQuery, UserListResult, UserService},
-    PasswordUpdate, UserCreateError, UserCreateRequest, UserDeleteError, UserFeatureService,
-    UserGetError, UserListError, UserRequestPasswordResetError, UserRequestVerificationEmailError,
-    UserResetPasswordError, UserUpdateError, UserUpdateRequest, UserUpdateUserRequest,
-    UserVerifyEmailError, UserVerifyNewsletterSubscriptionError,
+    PasswordUpdate, UserAddEmailError, UserCancelEmailChangeError, UserConfirmEmailChangeError,
+    UserConfirmEmailError, UserCreateError, UserCreateRequest, UserDeleteAvatarError,
+    UserDeleteError, UserFeatureService, UserForceRestoreError, UserGetError, UserGetExportError,
+    UserListEmailsError, UserListError, UserListNameHistoryError, UserRemoveEmailError,
+    UserRequestExportError, UserRequestPasswordResetError, UserRequestVerificationEmailError,
+    UserResetPasswordError, UserRestoreError, UserSetPrimaryEmailError, UserUpdateError,
+    UserUpdateRequest, UserUpdateUserRequest, UserUploadAvatarError, UserVerifyEmailError,
+    UserVerifyNewsletterSubscriptionError,
 };
 use academy_di::Build;
 use academy_extern_contracts::{internal::InternalApiService, vat::VatApiService};
 use academy_models::{
-    auth::{AccessToken, Login},
+    audit::{AuditAction, AuditContext},
+    auth::{AccessToken, AuthError, AuthorizeError, Login},
     email_address::EmailAddress,
-    session::DeviceName,
-    user::{UserComposite, UserIdOrSelf, UserInvoiceInfoPatch, UserPassword, UserPatchRef},
+    role::Permission,
+    session::{DeviceName, SessionLoginMethod},
+    user::{
+        UserComposite, UserEmail, UserId, UserIdOrSelf, UserInvoiceInfoPatch, UserNameHistoryEntry,
+        UserPassword, UserPatchRef, UserProfilePatch,
+    },
+    user_export::UserExport,
     RecaptchaResponse, VerificationCode,
 };
 use academy_persistence_contracts::{user::UserRepository, Database, Transaction};
@@ -33,8 +54,15 @@ use academy_utils::{
     trace_instrument,
 };
 use anyhow::{anyhow, Context};
+use regex::bytes::RegexSet;
+use tracing::warn;
 
+pub mod avatar;
+pub mod deletion;
 pub mod email_confirmation;
+pub mod export;
+pub mod name_policy;
+pub mod password_policy;
 pub mod update;
 pub mod user;
 
@@ -51,8 +79,15 @@ pub struct UserFeatureServiceImpl<
     User,
     UserEmailConfirmation,
     UserUpdate,
+    UserPasswordPolicy,
+    UserNamePolicy,
+    UserAvatar,
+    UserExport,
+    UserDeletion,
     Session,
+    SessionNewDevice,
     OAuth2Registration,
+    Audit,
     UserRepo,
 > {
     db: Db,
@@ -63,20 +98,54 @@ pub struct UserFeatureServiceImpl<
     user: User,
     user_email_confirmation: UserEmailConfirmation,
     user_update: UserUpdate,
+    user_password_policy: UserPasswordPolicy,
+    user_name_policy: UserNamePolicy,
+    user_avatar: UserAvatar,
+    user_export: UserExport,
+    user_deletion: UserDeletion,
     session: Session,
+    session_new_device: SessionNewDevice,
     oauth2_registration: OAuth2Registration,
+    audit: Audit,
     user_repo: UserRepo,
 }
 
 #[derive(Debug, Clone)]
 pub struct UserFeatureConfig {
     pub name_change_rate_limit: Duration,
+    pub name_quarantine: Duration,
+    /// Lowercase names that cannot be used by non-admin users.
+    pub reserved_names: Arc<HashSet<String>>,
+    /// Patterns matched against the lowercase name. Matching names cannot be
+    /// used by non-admin users.
+    pub blocked_names: RegexSet,
     pub verification_redirect_url: Arc<String>,
     pub verification_verification_code_ttl: Duration,
     pub password_reset_redirect_url: Arc<String>,
     pub password_reset_verification_code_ttl: Duration,
     pub newsletter_subscription_redirect_url: Arc<String>,
     pub newsletter_subscription_verification_code_ttl: Duration,
+    pub email_change_redirect_url: Arc<String>,
+    pub email_change_cancel_redirect_url: Arc<String>,
+    pub email_change_verification_code_ttl: Duration,
+    pub additional_email_redirect_url: Arc<String>,
+    pub additional_email_verification_code_ttl: Duration,
+    pub password_policy: Option<UserPasswordPolicyConfig>,
+    pub export_redirect_url: Arc<String>,
+    pub export_code_ttl: Duration,
+    pub deletion_grace_period: Duration,
+    pub restore_redirect_url: Arc<String>,
+    /// Maximum size of uploaded avatar images in bytes.
+    pub avatar_max_size: usize,
+    /// Maximum width and height of uploaded avatar images in pixels.
+    pub avatar_max_dimension: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct UserPasswordPolicyConfig {
+    pub min_length: usize,
+    pub min_character_classes: usize,
+    pub check_breached: bool,
 }
 
 impl<
@@ -88,8 +157,15 @@ impl<
         UserS,
         UserEmailConfirmation,
         UserUpdate,
+        UserPasswordPolicy,
+        UserNamePolicy,
+        UserAvatar,
+        UserExportS,
+        UserDeletion,
         Session,
+        SessionNewDevice,
         OAuth2RegistrationS,
+        Audit,
         UserRepo,
     > UserFeatureService
     for UserFeatureServiceImpl<
@@ -101,8 +177,15 @@ impl<
         UserS,
         UserEmailConfirmation,
         UserUpdate,
+        UserPasswordPolicy,
+        UserNamePolicy,
+        UserAvatar,
+        UserExportS,
+        UserDeletion,
         Session,
+        SessionNewDevice,
         OAuth2RegistrationS,
+        Audit,
         UserRepo,
     >
 where
@@ -114,8 +197,15 @@ where
     UserS: UserService<Db::Transaction>,
     UserEmailConfirmation: UserEmailConfirmationService<Db::Transaction>,
     UserUpdate: UserUpdateService<Db::Transaction>,
+    UserPasswordPolicy: UserPasswordPolicyService,
+    UserNamePolicy: UserNamePolicyService<Db::Transaction>,
+    UserAvatar: UserAvatarService,
+    UserExportS: UserExportService,
+    UserDeletion: UserDeletionService<Db::Transaction>,
     Session: SessionService<Db::Transaction>,
+    SessionNewDevice: SessionNewDeviceService<Db::Transaction>,
     OAuth2RegistrationS: OAuth2RegistrationService,
+    Audit: AuditService<Db::Transaction>,
     UserRepo: UserRepository<Db::Transaction>,
 {
     #[trace_instrument(skip(self))]
@@ -124,8 +214,9 @@ where
         token: &AccessToken,
         query: UserListQuery,
     ) -> Result<UserListResult, UserListError> {
-        let auth = self.auth.authenticate(token).await.map_auth_err()?;
-        auth.ensure_admin().map_auth_err()?;
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
+        auth.ensure_permission(Permission::ReadUsers)
+            .map_auth_err()?;
 
         let mut txn = self.db.begin_transaction().await.unwrap();
 
@@ -142,9 +233,10 @@ where
         token: &AccessToken,
         user_id: UserIdOrSelf,
     ) -> Result<UserComposite, UserGetError> {
-        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
         let user_id = user_id.unwrap_or(auth.user_id);
-        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+        auth.ensure_self_or_permission(user_id, Permission::ReadUsers)
+            .map_auth_err()?;
 
         let mut txn = self.db.begin_transaction().await.unwrap();
 
@@ -160,6 +252,7 @@ where
         &self,
         request: UserCreateRequest,
         device_name: Option<DeviceName>,
+        ip: Option<IpAddr>,
         recaptcha_response: Option<RecaptchaResponse>,
     ) -> Result<Login, UserCreateError> {
         if request.password.is_none() && request.oauth2_registration_token.is_none() {
@@ -174,6 +267,20 @@ where
                 CaptchaCheckError::Other(err) => err.context("Failed to check captcha").into(),
             })?;
 
+        if let Some(password) = &request.password {
+            self.user_password_policy
+                .check(password, &request.name, Some(&request.email))
+                .await
+                .map_err(|err| match err {
+                    UserPasswordPolicyCheckError::Violation(violation) => {
+                        UserCreateError::PasswordPolicy(violation)
+                    }
+                    UserPasswordPolicyCheckError::Other(err) => {
+                        err.context("Failed to check password policy").into()
+                    }
+                })?;
+        }
+
         let oauth2_registration = match &request.oauth2_registration_token {
             Some(oauth2_registration_token) => Some(
                 self.oauth2_registration
@@ -185,8 +292,31 @@ where
             None => None,
         };
 
+        // trust the email address only if the provider has verified it
+        let email_verified = oauth2_registration.as_ref().is_some_and(|registration| {
+            registration.remote_user.email_verified
+                && registration.remote_user.email.as_ref() == Some(&request.email)
+        });
+
+        let login_method = match &oauth2_registration {
+            Some(registration) => SessionLoginMethod::OAuth2(registration.provider_id.clone()),
+            None => SessionLoginMethod::Password,
+        };
+
         let mut txn = self.db.begin_transaction().await.unwrap();
 
+        self.user_name_policy
+            .check(&mut txn, &request.name, None)
+            .await
+            .map_err(|err| match err {
+                UserNamePolicyCheckError::Violation(violation) => {
+                    UserCreateError::NamePolicy(violation)
+                }
+                UserNamePolicyCheckError::Other(err) => {
+                    err.context("Failed to check name policy").into()
+                }
+            })?;
+
         let cmd = UserCreateCommand {
             name: request.name,
             display_name: request.display_name,
@@ -194,7 +324,7 @@ where
             password: request.password,
             admin: false,
             enabled: true,
-            email_verified: false,
+            email_verified,
             oauth2_registration,
         };
 
@@ -210,10 +340,17 @@ where
 
         let result = self
             .session
-            .create(&mut txn, user, device_name, true)
+            .create(&mut txn, user, device_name, ip, login_method)
             .await
             .context("Failed to create session")?;
 
+        // the user has no known devices yet, so this only remembers the device and
+        // never results in a notification
+        self.session_new_device
+            .handle_login(&mut txn, &result.user_composite, &result.session)
+            .await
+            .context("Failed to handle login from new device")?;
+
         if let Some(oauth2_registration_token) = request.oauth2_registration_token {
             self.oauth2_registration
                 .remove(&oauth2_registration_token)
@@ -245,10 +382,17 @@ where
             profile: profile_update,
             invoice_info: invoice_info_update,
         }: UserUpdateRequest,
+        ctx: &AuditContext,
     ) -> Result<UserComposite, UserUpdateError> {
         let auth = self.auth.authenticate(token).await.map_auth_err()?;
         let user_id = user_id.unwrap_or(auth.user_id);
-        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+        let is_self = user_id == auth.user_id;
+        if !is_self
+            && !auth.has_permission(Permission::DisableUsers)
+            && !auth.has_permission(Permission::EditInvoiceInfo)
+        {
+            return Err(AuthError::Authorize(AuthorizeError::Admin).into());
+        }
 
         let mut txn = self.db.begin_transaction().await?;
 
@@ -291,18 +435,82 @@ where
         .minimize(&invoice_info);
 
         // Validate patch
-        if email_verified.is_update() || enabled.is_update() || admin.is_update() {
+        if password.is_update()
+            || email.is_update()
+            || email_verified.is_update()
+            || admin.is_update()
+        {
+            auth.ensure_session().map_auth_err()?;
+        }
+
+        if email_verified.is_update() || admin.is_update() {
             auth.ensure_admin().map_auth_err()?;
         }
 
-        if enabled == PatchValue::Update(false) && user_id == auth.user_id {
+        if enabled.is_update() {
+            auth.ensure_permission(Permission::DisableUsers)
+                .map_auth_err()?;
+            if user.admin {
+                auth.ensure_admin().map_auth_err()?;
+            }
+        }
+
+        if !is_self {
+            if name.is_update()
+                || email.is_update()
+                || password.is_update()
+                || newsletter.is_update()
+                || profile_update.is_update()
+            {
+                auth.ensure_admin().map_auth_err()?;
+            }
+            if invoice_info_update.is_update() {
+                auth.ensure_permission(Permission::EditInvoiceInfo)
+                    .map_auth_err()?;
+            }
+        }
+
+        if enabled == PatchValue::Update(false) && is_self {
             return Err(UserUpdateError::CannotDisableSelf);
         }
 
-        if admin.is_update() && user_id == auth.user_id {
+        if admin.is_update() && is_self {
             return Err(UserUpdateError::CannotDemoteSelf);
         }
 
+        // admins may use reserved names and bypass the name quarantine
+        if let PatchValue::Update(name) = &name {
+            if !auth.admin {
+                self.user_name_policy
+                    .check(&mut txn, name, Some(user_id))
+                    .await
+                    .map_err(|err| match err {
+                        UserNamePolicyCheckError::Violation(violation) => {
+                            UserUpdateError::NamePolicy(violation)
+                        }
+                        UserNamePolicyCheckError::Other(err) => {
+                            err.context("Failed to check name policy").into()
+                        }
+                    })?;
+            }
+        }
+
+        if let PatchValue::Update(PasswordUpdate::Change(password)) = &password {
+            let name = name.as_ref().update(&user.name);
+            let email = email.as_ref().update(&user.email).as_ref();
+            self.user_password_policy
+                .check(password, name, email)
+                .await
+                .map_err(|err| match err {
+                    UserPasswordPolicyCheckError::Violation(violation) => {
+                        UserUpdateError::PasswordPolicy(violation)
+                    }
+                    UserPasswordPolicyCheckError::Other(err) => {
+                        err.context("Failed to check password policy").into()
+                    }
+                })?;
+        }
+
         if let PatchValue::Update(Some(vat_id)) = &invoice_info_update.vat_id {
             if !self
                 .vat_api
@@ -346,7 +554,33 @@ where
             commit = true;
         }
 
-        if email.is_update() || email_verified.is_update() {
+        if email.is_update() && !auth.admin {
+            if let PatchValue::Update(Some(new_email)) = email {
+                if self
+                    .user_repo
+                    .get_composite_by_email(&mut txn, &new_email)
+                    .await
+                    .context("Failed to get user from database")?
+                    .is_some()
+                {
+                    return Err(UserUpdateError::EmailConflict);
+                }
+
+                let old_email = user
+                    .email
+                    .clone()
+                    .map(|email| email.with_name(profile.display_name.clone().into_inner()));
+                self.user_email_confirmation
+                    .request_email_change(
+                        user_id,
+                        old_email,
+                        new_email.with_name(profile.display_name.clone().into_inner()),
+                    )
+                    .await
+                    .context("Failed to request email change")?;
+                commit = true;
+            }
+        } else if email.is_update() || email_verified.is_update() {
             user.email_verified =
                 email_verified.update(user.email_verified && email.is_unchanged());
             user.email = email.update(user.email);
@@ -440,6 +674,17 @@ where
         }
 
         if commit {
+            self.audit
+                .record(
+                    &mut txn,
+                    ctx,
+                    Some(auth.user_id),
+                    user_id,
+                    AuditAction::UserUpdate,
+                )
+                .await
+                .context("Failed to record audit event")?;
+
             txn.commit().await?;
         }
 
@@ -460,32 +705,256 @@ where
         Ok(user_composite)
     }
 
+    #[trace_instrument(skip(self, data))]
+    async fn upload_user_avatar(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        data: Vec<u8>,
+        ctx: &AuditContext,
+    ) -> Result<UserComposite, UserUploadAvatarError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let mut user_composite = self
+            .user_repo
+            .get_composite(&mut txn, user_id)
+            .await
+            .context("Failed to get user from database")?
+            .ok_or(UserUploadAvatarError::NotFound)?;
+
+        let avatar_url = self
+            .user_avatar
+            .upload(user_id, data)
+            .await
+            .map_err(|err| match err {
+                UserAvatarUploadError::TooLarge => UserUploadAvatarError::TooLarge,
+                UserAvatarUploadError::UnsupportedFormat => {
+                    UserUploadAvatarError::UnsupportedFormat
+                }
+                UserAvatarUploadError::InvalidImage => UserUploadAvatarError::InvalidImage,
+                UserAvatarUploadError::Other(err) => err.context("Failed to upload avatar").into(),
+            })?;
+
+        let profile_update = UserProfilePatch::new().update_avatar_url(Some(avatar_url));
+        self.user_repo
+            .update_profile(&mut txn, user_id, profile_update.as_ref())
+            .await
+            .context("Failed to update user profile")?;
+        user_composite.profile = user_composite.profile.update(profile_update);
+
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::UserUpdate,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        Ok(user_composite)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn delete_user_avatar(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        ctx: &AuditContext,
+    ) -> Result<UserComposite, UserDeleteAvatarError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let mut user_composite = self
+            .user_repo
+            .get_composite(&mut txn, user_id)
+            .await
+            .context("Failed to get user from database")?
+            .ok_or(UserDeleteAvatarError::NotFound)?;
+
+        if user_composite.profile.avatar_url.is_none() {
+            return Ok(user_composite);
+        }
+
+        let profile_update = UserProfilePatch::new().update_avatar_url(None);
+        self.user_repo
+            .update_profile(&mut txn, user_id, profile_update.as_ref())
+            .await
+            .context("Failed to update user profile")?;
+        user_composite.profile = user_composite.profile.update(profile_update);
+
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::UserUpdate,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        // the files are only removed after the user no longer references them
+        self.user_avatar
+            .remove(user_id)
+            .await
+            .context("Failed to remove avatar")?;
+
+        Ok(user_composite)
+    }
+
     #[trace_instrument(skip(self))]
     async fn delete_user(
         &self,
         token: &AccessToken,
         user_id: UserIdOrSelf,
+        ctx: &AuditContext,
     ) -> Result<(), UserDeleteError> {
         let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
         let user_id = user_id.unwrap_or(auth.user_id);
         auth.ensure_self_or_admin(user_id).map_auth_err()?;
 
         let mut txn = self.db.begin_transaction().await?;
 
-        self.auth
-            .invalidate_access_tokens(&mut txn, user_id)
+        let user_composite = self
+            .user_repo
+            .get_composite(&mut txn, user_id)
+            .await
+            .context("Failed to get user from database")?
+            .filter(|user_composite| user_composite.user.deleted_at.is_none())
+            .ok_or(UserDeleteError::NotFound)?;
+
+        self.session
+            .delete_by_user(&mut txn, user_id)
+            .await
+            .context("Failed to delete sessions")?;
+
+        let email = user_composite
+            .user
+            .email
+            .map(|email| email.with_name(user_composite.profile.display_name.into_inner()));
+
+        let restore_code = self
+            .user_deletion
+            .schedule(&mut txn, user_id)
+            .await
+            .context("Failed to mark user as deleted")?;
+
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::UserDelete,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        // the account has already been deleted, so a failure to send the restore
+        // link must not fail the request
+        if let Some(email) = email {
+            if let Err(err) = self
+                .user_deletion
+                .notify(user_id, email, restore_code)
+                .await
+            {
+                warn!("Failed to send restore link to deleted user: {err:?}");
+            }
+        }
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn restore_user(
+        &self,
+        user_id: UserId,
+        code: VerificationCode,
+        ctx: &AuditContext,
+    ) -> Result<(), UserRestoreError> {
+        let mut txn = self.db.begin_transaction().await?;
+
+        self.user_deletion
+            .restore(&mut txn, user_id, code)
+            .await
+            .map_err(|err| match err {
+                UserDeletionRestoreError::InvalidCode => UserRestoreError::InvalidCode,
+                UserDeletionRestoreError::Other(err) => {
+                    err.context("Failed to restore user").into()
+                }
+            })?;
+
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(user_id),
+                user_id,
+                AuditAction::UserRestore,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn force_restore_user(
+        &self,
+        token: &AccessToken,
+        user_id: UserId,
+        ctx: &AuditContext,
+    ) -> Result<(), UserForceRestoreError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_admin().map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        self.user_repo
+            .get_composite(&mut txn, user_id)
             .await
-            .context("Failed to invalidate access tokens")?;
+            .context("Failed to get user from database")?
+            .filter(|user_composite| user_composite.user.deleted_at.is_some())
+            .ok_or(UserForceRestoreError::NotFound)?;
 
         if !self
-            .user_repo
-            .delete(&mut txn, user_id)
+            .user_deletion
+            .force_restore(&mut txn, user_id)
             .await
-            .context("Failed to delete user from database")?
+            .context("Failed to restore user")?
         {
-            return Err(UserDeleteError::NotFound);
+            return Err(UserForceRestoreError::NotFound);
         }
 
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::UserRestore,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
         txn.commit().await?;
 
         Ok(())
@@ -499,7 +968,8 @@ where
     ) -> Result<(), UserRequestVerificationEmailError> {
         let auth = self.auth.authenticate(token).await.map_auth_err()?;
         let user_id = user_id.unwrap_or(auth.user_id);
-        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+        auth.ensure_self_or_permission(user_id, Permission::ResendVerificationEmails)
+            .map_auth_err()?;
 
         let mut txn = self.db.begin_transaction().await?;
 
@@ -550,6 +1020,328 @@ where
         }
     }
 
+    #[trace_instrument(skip(self))]
+    async fn confirm_email_change(
+        &self,
+        user_id: UserId,
+        code: VerificationCode,
+        ctx: &AuditContext,
+    ) -> Result<(), UserConfirmEmailChangeError> {
+        let mut txn = self.db.begin_transaction().await?;
+
+        self.user_email_confirmation
+            .confirm_email_change(&mut txn, user_id, code)
+            .await
+            .map_err(|err| match err {
+                UserEmailConfirmationChangeEmailError::InvalidCode => {
+                    UserConfirmEmailChangeError::InvalidCode
+                }
+                UserEmailConfirmationChangeEmailError::Conflict => {
+                    UserConfirmEmailChangeError::Conflict
+                }
+                UserEmailConfirmationChangeEmailError::Other(err) => {
+                    err.context("Failed to change email address").into()
+                }
+            })?;
+
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(user_id),
+                user_id,
+                AuditAction::UserUpdate,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        self.user_email_confirmation
+            .remove_email_change(user_id)
+            .await
+            .context("Failed to remove email change code")?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn cancel_email_change(
+        &self,
+        user_id: UserId,
+        code: VerificationCode,
+        ctx: &AuditContext,
+    ) -> Result<(), UserCancelEmailChangeError> {
+        let mut txn = self.db.begin_transaction().await?;
+
+        let restored = self
+            .user_email_confirmation
+            .cancel_email_change(&mut txn, user_id, code)
+            .await
+            .map_err(|err| match err {
+                UserEmailConfirmationCancelEmailChangeError::InvalidCode => {
+                    UserCancelEmailChangeError::InvalidCode
+                }
+                UserEmailConfirmationCancelEmailChangeError::Conflict => {
+                    UserCancelEmailChangeError::Conflict
+                }
+                UserEmailConfirmationCancelEmailChangeError::Other(err) => {
+                    err.context("Failed to cancel email change").into()
+                }
+            })?;
+
+        if restored {
+            self.audit
+                .record(
+                    &mut txn,
+                    ctx,
+                    Some(user_id),
+                    user_id,
+                    AuditAction::UserUpdate,
+                )
+                .await
+                .context("Failed to record audit event")?;
+
+            txn.commit().await?;
+        }
+
+        self.user_email_confirmation
+            .remove_cancel_email_change(user_id)
+            .await
+            .context("Failed to remove cancel email change code")?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn list_user_emails(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+    ) -> Result<Vec<UserEmail>, UserListEmailsError> {
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_permission(user_id, Permission::ReadUsers)
+            .map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        if !self
+            .user_repo
+            .exists(&mut txn, user_id)
+            .await
+            .context("Failed to check user existence")?
+        {
+            return Err(UserListEmailsError::NotFound);
+        }
+
+        self.user_repo
+            .list_emails(&mut txn, user_id)
+            .await
+            .context("Failed to get email addresses from database")
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn add_user_email(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        email: EmailAddress,
+    ) -> Result<(), UserAddEmailError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let user_composite = self
+            .user_repo
+            .get_composite(&mut txn, user_id)
+            .await
+            .context("Failed to get user from database")?
+            .ok_or(UserAddEmailError::NotFound)?;
+
+        if self
+            .user_repo
+            .get_composite_by_email(&mut txn, &email)
+            .await
+            .context("Failed to get user from database")?
+            .is_some()
+        {
+            return Err(UserAddEmailError::Conflict);
+        }
+
+        self.user_email_confirmation
+            .request_email_addition(
+                user_id,
+                email.with_name(user_composite.profile.display_name.into_inner()),
+            )
+            .await
+            .context("Failed to request email address addition")?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn confirm_user_email(
+        &self,
+        user_id: UserId,
+        code: VerificationCode,
+        ctx: &AuditContext,
+    ) -> Result<UserEmail, UserConfirmEmailError> {
+        let mut txn = self.db.begin_transaction().await?;
+
+        let email = self
+            .user_email_confirmation
+            .confirm_email_addition(&mut txn, user_id, code)
+            .await
+            .map_err(|err| match err {
+                UserEmailConfirmationAddEmailError::InvalidCode => {
+                    UserConfirmEmailError::InvalidCode
+                }
+                UserEmailConfirmationAddEmailError::Conflict => UserConfirmEmailError::Conflict,
+                UserEmailConfirmationAddEmailError::Other(err) => {
+                    err.context("Failed to add email address").into()
+                }
+            })?;
+
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(user_id),
+                user_id,
+                AuditAction::UserUpdate,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        Ok(email)
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn remove_user_email(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        email: EmailAddress,
+        ctx: &AuditContext,
+    ) -> Result<(), UserRemoveEmailError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        if !self
+            .user_repo
+            .remove_email(&mut txn, user_id, &email)
+            .await
+            .context("Failed to remove email address from database")?
+        {
+            return Err(UserRemoveEmailError::NotFound);
+        }
+
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::UserUpdate,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn set_primary_user_email(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        email: EmailAddress,
+        ctx: &AuditContext,
+    ) -> Result<UserComposite, UserSetPrimaryEmailError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        auth.ensure_session().map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let user_composite = self
+            .user_repo
+            .get_composite(&mut txn, user_id)
+            .await
+            .context("Failed to get user from database")?
+            .ok_or(UserSetPrimaryEmailError::NotFound)?;
+
+        let user = self
+            .user_update
+            .update_primary_email(&mut txn, user_composite.user, email)
+            .await
+            .map_err(|err| match err {
+                UserUpdatePrimaryEmailError::NotFound => UserSetPrimaryEmailError::EmailNotFound,
+                UserUpdatePrimaryEmailError::Other(err) => {
+                    err.context("Failed to update primary email address").into()
+                }
+            })?;
+
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::UserUpdate,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        Ok(UserComposite {
+            user,
+            ..user_composite
+        })
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn list_user_name_history(
+        &self,
+        token: &AccessToken,
+        user_id: UserId,
+    ) -> Result<Vec<UserNameHistoryEntry>, UserListNameHistoryError> {
+        let auth = self.auth.authenticate_read(token).await.map_auth_err()?;
+        auth.ensure_admin().map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        if !self
+            .user_repo
+            .exists(&mut txn, user_id)
+            .await
+            .context("Failed to check user existence")?
+        {
+            return Err(UserListNameHistoryError::NotFound);
+        }
+
+        self.user_repo
+            .list_name_history(&mut txn, user_id)
+            .await
+            .context("Failed to get name history from database")
+            .map_err(Into::into)
+    }
+
     #[trace_instrument(skip(self))]
     async fn verify_newsletter_subscription(
         &self,
@@ -615,13 +1407,8 @@ where
             .await
             .context("Failed to get user from database")?
         {
-            let email = user_composite.user.email.ok_or_else(|| {
-                anyhow!(
-                    "User {} fetched by email {} has no email address",
-                    user_composite.user.id.hyphenated(),
-                    email.as_str()
-                )
-            })?;
+            // the user may have entered one of their additional email addresses,
+            // so the code is sent to the address they have actually entered
             self.user_email_confirmation
                 .request_password_reset(
                     user_composite.user.id,
@@ -640,6 +1427,7 @@ where
         email: EmailAddress,
         code: VerificationCode,
         new_password: UserPassword,
+        ctx: &AuditContext,
     ) -> Result<UserComposite, UserResetPasswordError> {
         let mut txn = self.db.begin_transaction().await?;
 
@@ -650,6 +1438,22 @@ where
             .context("Failed to get user from database")?
             .ok_or(UserResetPasswordError::Failed)?;
 
+        self.user_password_policy
+            .check(
+                &new_password,
+                &user_composite.user.name,
+                user_composite.user.email.as_ref(),
+            )
+            .await
+            .map_err(|err| match err {
+                UserPasswordPolicyCheckError::Violation(violation) => {
+                    UserResetPasswordError::PasswordPolicy(violation)
+                }
+                UserPasswordPolicyCheckError::Other(err) => {
+                    err.context("Failed to check password policy").into()
+                }
+            })?;
+
         self.user_email_confirmation
             .reset_password(&mut txn, user_composite.user.id, code, new_password)
             .await
@@ -662,8 +1466,77 @@ where
                 }
             })?;
 
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(user_composite.user.id),
+                user_composite.user.id,
+                AuditAction::PasswordReset,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
         txn.commit().await?;
 
         Ok(user_composite)
     }
+
+    #[trace_instrument(skip(self))]
+    async fn request_user_export(
+        &self,
+        token: &AccessToken,
+        user_id: UserIdOrSelf,
+        ctx: &AuditContext,
+    ) -> Result<(), UserRequestExportError> {
+        let auth = self.auth.authenticate(token).await.map_auth_err()?;
+        let user_id = user_id.unwrap_or(auth.user_id);
+        auth.ensure_self_or_admin(user_id).map_auth_err()?;
+
+        let mut txn = self.db.begin_transaction().await?;
+
+        let user_composite = self
+            .user_repo
+            .get_composite(&mut txn, user_id)
+            .await
+            .context("Failed to get user from database")?
+            .ok_or(UserRequestExportError::NotFound)?;
+
+        if user_composite.user.email.is_none() {
+            return Err(UserRequestExportError::NoEmail);
+        }
+
+        self.user_repo
+            .save_export_requested(&mut txn, user_id, true)
+            .await
+            .context("Failed to save export request in database")?;
+
+        self.audit
+            .record(
+                &mut txn,
+                ctx,
+                Some(auth.user_id),
+                user_id,
+                AuditAction::UserExport,
+            )
+            .await
+            .context("Failed to record audit event")?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn get_user_export(
+        &self,
+        user_id: UserId,
+        code: VerificationCode,
+    ) -> Result<UserExport, UserGetExportError> {
+        self.user_export
+            .get(user_id, &code)
+            .await
+            .context("Failed to get user export")?
+            .ok_or(UserGetExportError::InvalidCode)
+    }
 }
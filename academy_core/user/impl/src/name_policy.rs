@@ -0,0 +1,212 @@
+use academy_core_user_contracts::name_policy::{
+    UserNamePolicyCheckError, UserNamePolicyService, UserNamePolicyViolation,
+};
+use academy_di::Build;
+use academy_models::user::{UserId, UserName};
+use academy_persistence_contracts::user::UserRepository;
+use academy_shared_contracts::time::TimeService;
+use academy_utils::trace_instrument;
+use anyhow::Context;
+
+use crate::UserFeatureConfig;
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct UserNamePolicyServiceImpl<Time, UserRepo> {
+    time: Time,
+    user_repo: UserRepo,
+    config: UserFeatureConfig,
+}
+
+impl<Txn, Time, UserRepo> UserNamePolicyService<Txn> for UserNamePolicyServiceImpl<Time, UserRepo>
+where
+    Txn: Send + Sync + 'static,
+    Time: TimeService,
+    UserRepo: UserRepository<Txn>,
+{
+    #[trace_instrument(skip(self, txn))]
+    async fn check(
+        &self,
+        txn: &mut Txn,
+        name: &UserName,
+        user_id: Option<UserId>,
+    ) -> Result<(), UserNamePolicyCheckError> {
+        let name_lower = name.to_lowercase();
+
+        if self.config.reserved_names.contains(&name_lower) {
+            return Err(UserNamePolicyViolation::Reserved.into());
+        }
+
+        if self.config.blocked_names.is_match(name_lower.as_bytes()) {
+            return Err(UserNamePolicyViolation::Blocked.into());
+        }
+
+        let latest_entry = self
+            .user_repo
+            .get_latest_name_history_entry(txn, name)
+            .await
+            .context("Failed to get name history from database")?;
+
+        // users may always reclaim a name they have used before, unless it has
+        // been used by someone else since then
+        if let Some(entry) = latest_entry.filter(|entry| Some(entry.user_id) != user_id) {
+            let until = entry.changed_at + self.config.name_quarantine;
+            if self.time.now() < until {
+                return Err(UserNamePolicyViolation::Quarantined { until }.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use academy_demo::user::{BAR, FOO, FOO_NAME_HISTORY_1};
+    use academy_persistence_contracts::user::MockUserRepository;
+    use academy_shared_contracts::time::MockTimeService;
+    use academy_utils::assert_matches;
+
+    use super::*;
+
+    type Sut = UserNamePolicyServiceImpl<MockTimeService, MockUserRepository<()>>;
+
+    #[tokio::test]
+    async fn ok() {
+        // Arrange
+        let user_repo = MockUserRepository::new()
+            .with_get_latest_name_history_entry(BAR.user.name.clone(), None);
+
+        let sut = Sut {
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.check(&mut (), &BAR.user.name, None).await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn reserved() {
+        // Arrange
+        let sut = Sut::default();
+
+        // Act
+        let result = sut
+            .check(&mut (), &"Support".try_into().unwrap(), Some(FOO.user.id))
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(UserNamePolicyCheckError::Violation(
+                UserNamePolicyViolation::Reserved
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn blocked() {
+        // Arrange
+        let sut = Sut::default();
+
+        // Act
+        let result = sut
+            .check(&mut (), &"Bootstrap_Academy42".try_into().unwrap(), None)
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(UserNamePolicyCheckError::Violation(
+                UserNamePolicyViolation::Blocked
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn quarantined() {
+        // Arrange
+        let config = UserFeatureConfig::default();
+        let until = FOO_NAME_HISTORY_1.changed_at + config.name_quarantine;
+
+        let time = MockTimeService::new().with_now(until - Duration::from_secs(1));
+
+        let user_repo = MockUserRepository::new().with_get_latest_name_history_entry(
+            FOO_NAME_HISTORY_1.name.clone(),
+            Some(FOO_NAME_HISTORY_1.clone()),
+        );
+
+        let sut = Sut {
+            time,
+            user_repo,
+            config,
+        };
+
+        // Act
+        let result = sut
+            .check(&mut (), &FOO_NAME_HISTORY_1.name, Some(BAR.user.id))
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(UserNamePolicyCheckError::Violation(
+                UserNamePolicyViolation::Quarantined { until: u }
+            )) if *u == until
+        );
+    }
+
+    #[tokio::test]
+    async fn quarantine_expired() {
+        // Arrange
+        let config = UserFeatureConfig::default();
+        let until = FOO_NAME_HISTORY_1.changed_at + config.name_quarantine;
+
+        let time = MockTimeService::new().with_now(until);
+
+        let user_repo = MockUserRepository::new().with_get_latest_name_history_entry(
+            FOO_NAME_HISTORY_1.name.clone(),
+            Some(FOO_NAME_HISTORY_1.clone()),
+        );
+
+        let sut = Sut {
+            time,
+            user_repo,
+            config,
+        };
+
+        // Act
+        let result = sut.check(&mut (), &FOO_NAME_HISTORY_1.name, None).await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn reclaim_own_name() {
+        // Arrange
+        let user_repo = MockUserRepository::new().with_get_latest_name_history_entry(
+            FOO_NAME_HISTORY_1.name.clone(),
+            Some(FOO_NAME_HISTORY_1.clone()),
+        );
+
+        let sut = Sut {
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .check(&mut (), &FOO_NAME_HISTORY_1.name, Some(FOO.user.id))
+            .await;
+
+        // Assert
+        result.unwrap();
+    }
+}
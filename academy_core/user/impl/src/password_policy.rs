@@ -0,0 +1,302 @@
+use academy_core_user_contracts::password_policy::{
+    UserPasswordPolicyCheckError, UserPasswordPolicyService, UserPasswordPolicyViolation,
+};
+use academy_di::Build;
+use academy_extern_contracts::pwned_passwords::PwnedPasswordsApiService;
+use academy_models::{
+    email_address::EmailAddress,
+    user::{UserName, UserPassword},
+};
+use academy_utils::trace_instrument;
+use anyhow::Context;
+use tracing::trace;
+
+use crate::UserFeatureConfig;
+
+#[derive(Debug, Clone, Build)]
+#[cfg_attr(test, derive(Default))]
+pub struct UserPasswordPolicyServiceImpl<PwnedPasswordsApi> {
+    pwned_passwords_api: PwnedPasswordsApi,
+    config: UserFeatureConfig,
+}
+
+impl<PwnedPasswordsApi> UserPasswordPolicyService
+    for UserPasswordPolicyServiceImpl<PwnedPasswordsApi>
+where
+    PwnedPasswordsApi: PwnedPasswordsApiService,
+{
+    #[trace_instrument(skip(self, password))]
+    async fn check(
+        &self,
+        password: &UserPassword,
+        name: &UserName,
+        email: Option<&EmailAddress>,
+    ) -> Result<(), UserPasswordPolicyCheckError> {
+        let Some(policy) = &self.config.password_policy else {
+            return Ok(());
+        };
+
+        if password.chars().count() < policy.min_length {
+            return Err(UserPasswordPolicyViolation::TooShort {
+                min_length: policy.min_length,
+            }
+            .into());
+        }
+
+        if count_character_classes(password) < policy.min_character_classes {
+            return Err(UserPasswordPolicyViolation::TooWeak {
+                min_character_classes: policy.min_character_classes,
+            }
+            .into());
+        }
+
+        let password_lower = password.to_lowercase();
+        let matches_user_data = password_lower == name.to_lowercase()
+            || email.is_some_and(|email| {
+                password_lower == email.as_str().to_lowercase()
+                    || password_lower == email.0.user().to_lowercase()
+            });
+        if matches_user_data {
+            return Err(UserPasswordPolicyViolation::MatchesUserData.into());
+        }
+
+        if policy.check_breached {
+            let breach_count = self
+                .pwned_passwords_api
+                .get_breach_count(password)
+                .await
+                .context("Failed to check whether the password has been breached")?;
+            if breach_count > 0 {
+                trace!(breach_count, "password has been breached");
+                return Err(UserPasswordPolicyViolation::Breached.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Count how many of the character classes lowercase letters, uppercase
+/// letters, digits and other characters appear in the given password.
+fn count_character_classes(password: &str) -> usize {
+    let (mut lower, mut upper, mut digit, mut other) = (false, false, false, false);
+    for c in password.chars() {
+        if c.is_lowercase() {
+            lower = true;
+        } else if c.is_uppercase() {
+            upper = true;
+        } else if c.is_ascii_digit() {
+            digit = true;
+        } else {
+            other = true;
+        }
+    }
+
+    [lower, upper, digit, other]
+        .into_iter()
+        .filter(|&x| x)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_demo::user::FOO;
+    use academy_extern_contracts::pwned_passwords::MockPwnedPasswordsApiService;
+    use academy_utils::assert_matches;
+
+    use super::*;
+    use crate::UserPasswordPolicyConfig;
+
+    type Sut = UserPasswordPolicyServiceImpl<MockPwnedPasswordsApiService>;
+
+    #[tokio::test]
+    async fn ok() {
+        // Arrange
+        let password = UserPassword::try_new("Tr0ub4dor&3").unwrap();
+
+        let pwned_passwords_api = MockPwnedPasswordsApiService::new()
+            .with_get_breach_count(password.clone().into_inner(), 0);
+
+        let sut = Sut {
+            pwned_passwords_api,
+            config: make_config(true),
+        };
+
+        // Act
+        let result = sut
+            .check(&password, &FOO.user.name, FOO.user.email.as_ref())
+            .await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn disabled() {
+        // Arrange
+        let password = UserPassword::try_new("x").unwrap();
+
+        let sut = Sut::default();
+
+        // Act
+        let result = sut
+            .check(&password, &FOO.user.name, FOO.user.email.as_ref())
+            .await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn too_short() {
+        // Arrange
+        let password = UserPassword::try_new("Ab1!").unwrap();
+
+        let sut = Sut {
+            config: make_config(true),
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .check(&password, &FOO.user.name, FOO.user.email.as_ref())
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(UserPasswordPolicyCheckError::Violation(
+                UserPasswordPolicyViolation::TooShort { min_length: 8 }
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn too_weak() {
+        // Arrange
+        let password = UserPassword::try_new("abcdefgh1234").unwrap();
+
+        let sut = Sut {
+            config: make_config(true),
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .check(&password, &FOO.user.name, FOO.user.email.as_ref())
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(UserPasswordPolicyCheckError::Violation(
+                UserPasswordPolicyViolation::TooWeak {
+                    min_character_classes: 3
+                }
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn matches_name() {
+        // Arrange
+        let name = UserName::try_new("Secret-P4ss").unwrap();
+        let password = UserPassword::try_new("secret-p4ss").unwrap();
+
+        let sut = Sut {
+            config: make_config(true),
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.check(&password, &name, None).await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(UserPasswordPolicyCheckError::Violation(
+                UserPasswordPolicyViolation::MatchesUserData
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn matches_email() {
+        // Arrange
+        let email = "Secret-P4ss@example.com".parse::<EmailAddress>().unwrap();
+        let password = UserPassword::try_new("secret-p4ss@example.com").unwrap();
+
+        let sut = Sut {
+            config: make_config(true),
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut.check(&password, &FOO.user.name, Some(&email)).await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(UserPasswordPolicyCheckError::Violation(
+                UserPasswordPolicyViolation::MatchesUserData
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn breached() {
+        // Arrange
+        let password = UserPassword::try_new("Password1!").unwrap();
+
+        let pwned_passwords_api = MockPwnedPasswordsApiService::new()
+            .with_get_breach_count(password.clone().into_inner(), 7);
+
+        let sut = Sut {
+            pwned_passwords_api,
+            config: make_config(true),
+        };
+
+        // Act
+        let result = sut
+            .check(&password, &FOO.user.name, FOO.user.email.as_ref())
+            .await;
+
+        // Assert
+        assert_matches!(
+            result,
+            Err(UserPasswordPolicyCheckError::Violation(
+                UserPasswordPolicyViolation::Breached
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn breached_check_disabled() {
+        // Arrange
+        let password = UserPassword::try_new("Password1!").unwrap();
+
+        let sut = Sut {
+            config: make_config(false),
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .check(&password, &FOO.user.name, FOO.user.email.as_ref())
+            .await;
+
+        // Assert
+        result.unwrap();
+    }
+
+    fn make_config(check_breached: bool) -> UserFeatureConfig {
+        UserFeatureConfig {
+            password_policy: Some(UserPasswordPolicyConfig {
+                min_length: 8,
+                min_character_classes: 3,
+                check_breached,
+            }),
+            ..Default::default()
+        }
+    }
+}
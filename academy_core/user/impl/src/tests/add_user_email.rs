@@ -0,0 +1,139 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_user_contracts::{
+    email_confirmation::MockUserEmailConfirmationService, UserAddEmailError, UserFeatureService,
+};
+use academy_demo::{
+    session::{ADMIN_1, BAR_1, FOO_1},
+    user::{ADMIN, BAR, FOO},
+};
+use academy_models::{
+    auth::{AuthError, AuthorizeError},
+    email_address::EmailAddress,
+    user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, UserFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let email: EmailAddress = "foo@work.example".parse().unwrap();
+
+    let db = MockDatabase::build(false);
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite(FOO.user.id, Some(FOO.clone()))
+        .with_get_composite_by_email(email.clone(), None);
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new()
+        .with_request_email_addition(
+            FOO.user.id,
+            email
+                .clone()
+                .with_name(FOO.profile.display_name.clone().into_inner()),
+        );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        user_email_confirmation,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .add_user_email(&"token".into(), UserIdOrSelf::Slf, email)
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .add_user_email(
+            &"token".into(),
+            FOO.user.id.into(),
+            "foo@work.example".parse().unwrap(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserAddEmailError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let db = MockDatabase::build(false);
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, None);
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .add_user_email(
+            &"token".into(),
+            FOO.user.id.into(),
+            "foo@work.example".parse().unwrap(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserAddEmailError::NotFound));
+}
+
+#[tokio::test]
+async fn conflict() {
+    // Arrange
+    let email = ADMIN.user.email.clone().unwrap();
+
+    let db = MockDatabase::build(false);
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite(FOO.user.id, Some(FOO.clone()))
+        .with_get_composite_by_email(email.clone(), Some(ADMIN.clone()));
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .add_user_email(&"token".into(), UserIdOrSelf::Slf, email)
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserAddEmailError::Conflict));
+}
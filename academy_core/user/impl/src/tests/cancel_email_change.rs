@@ -0,0 +1,137 @@
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_user_contracts::{
+    email_confirmation::{
+        MockUserEmailConfirmationService, UserEmailConfirmationCancelEmailChangeError,
+    },
+    UserCancelEmailChangeError, UserFeatureService,
+};
+use academy_demo::{user::FOO, VERIFICATION_CODE_1};
+use academy_models::audit::{AuditAction, AuditContext};
+use academy_persistence_contracts::MockDatabase;
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, UserFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok_pending() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new()
+        .with_cancel_email_change(FOO.user.id, VERIFICATION_CODE_1.clone(), Ok(false))
+        .with_remove_cancel_email_change(FOO.user.id);
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        user_email_confirmation,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .cancel_email_change(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn ok_confirmed() {
+    // Arrange
+    let db = MockDatabase::build(true);
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new()
+        .with_cancel_email_change(FOO.user.id, VERIFICATION_CODE_1.clone(), Ok(true))
+        .with_remove_cancel_email_change(FOO.user.id);
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        user_email_confirmation,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .cancel_email_change(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn invalid_code() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new().with_cancel_email_change(
+        FOO.user.id,
+        VERIFICATION_CODE_1.clone(),
+        Err(UserEmailConfirmationCancelEmailChangeError::InvalidCode),
+    );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        user_email_confirmation,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .cancel_email_change(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserCancelEmailChangeError::InvalidCode));
+}
+
+#[tokio::test]
+async fn conflict() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new().with_cancel_email_change(
+        FOO.user.id,
+        VERIFICATION_CODE_1.clone(),
+        Err(UserEmailConfirmationCancelEmailChangeError::Conflict),
+    );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        user_email_confirmation,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .cancel_email_change(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserCancelEmailChangeError::Conflict));
+}
@@ -0,0 +1,160 @@
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_user_contracts::{
+    email_confirmation::{MockUserEmailConfirmationService, UserEmailConfirmationChangeEmailError},
+    UserConfirmEmailChangeError, UserFeatureService,
+};
+use academy_demo::{user::FOO, VERIFICATION_CODE_1};
+use academy_models::audit::{AuditAction, AuditContext};
+use academy_persistence_contracts::{MockDatabase, MockTransaction};
+use academy_utils::assert_matches;
+use anyhow::anyhow;
+
+use crate::{tests::Sut, UserFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let db = MockDatabase::build(true);
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new()
+        .with_confirm_email_change(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            Ok(FOO.user.email.clone().unwrap()),
+        )
+        .with_remove_email_change(FOO.user.id);
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        user_email_confirmation,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .confirm_email_change(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn commit_failed() {
+    // Arrange
+    let mut txn = MockTransaction::new();
+    txn.expect_commit()
+        .once()
+        .return_once(|| Box::pin(std::future::ready(Err(anyhow!("commit failed")))));
+    let mut db = MockDatabase::new();
+    db.expect_begin_transaction()
+        .once()
+        .return_once(|| Box::pin(std::future::ready(Ok(txn))));
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new()
+        .with_confirm_email_change(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            Ok(FOO.user.email.clone().unwrap()),
+        );
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        user_email_confirmation,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .confirm_email_change(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserConfirmEmailChangeError::Other(_)));
+}
+
+#[tokio::test]
+async fn invalid_code() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new()
+        .with_confirm_email_change(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            Err(UserEmailConfirmationChangeEmailError::InvalidCode),
+        );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        user_email_confirmation,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .confirm_email_change(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserConfirmEmailChangeError::InvalidCode));
+}
+
+#[tokio::test]
+async fn conflict() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new()
+        .with_confirm_email_change(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            Err(UserEmailConfirmationChangeEmailError::Conflict),
+        );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        user_email_confirmation,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .confirm_email_change(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserConfirmEmailChangeError::Conflict));
+}
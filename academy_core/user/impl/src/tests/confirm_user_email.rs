@@ -0,0 +1,115 @@
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_user_contracts::{
+    email_confirmation::{MockUserEmailConfirmationService, UserEmailConfirmationAddEmailError},
+    UserConfirmEmailError, UserFeatureService,
+};
+use academy_demo::{
+    user::{FOO, FOO_EMAIL_1},
+    VERIFICATION_CODE_1,
+};
+use academy_models::audit::{AuditAction, AuditContext};
+use academy_persistence_contracts::MockDatabase;
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, UserFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let db = MockDatabase::build(true);
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new()
+        .with_confirm_email_addition(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            Ok(FOO_EMAIL_1.clone()),
+        );
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        user_email_confirmation,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .confirm_user_email(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), *FOO_EMAIL_1);
+}
+
+#[tokio::test]
+async fn invalid_code() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new()
+        .with_confirm_email_addition(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            Err(UserEmailConfirmationAddEmailError::InvalidCode),
+        );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        user_email_confirmation,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .confirm_user_email(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserConfirmEmailError::InvalidCode));
+}
+
+#[tokio::test]
+async fn conflict() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new()
+        .with_confirm_email_addition(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            Err(UserEmailConfirmationAddEmailError::Conflict),
+        );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        user_email_confirmation,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .confirm_user_email(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserConfirmEmailError::Conflict));
+}
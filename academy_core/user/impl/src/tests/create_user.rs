@@ -1,6 +1,10 @@
 use academy_core_oauth2_contracts::registration::MockOAuth2RegistrationService;
-use academy_core_session_contracts::session::MockSessionService;
+use academy_core_session_contracts::{
+    new_device::MockSessionNewDeviceService, session::MockSessionService,
+};
 use academy_core_user_contracts::{
+    name_policy::{MockUserNamePolicyService, UserNamePolicyViolation},
+    password_policy::{MockUserPasswordPolicyService, UserPasswordPolicyViolation},
     user::{MockUserService, UserCreateCommand},
     UserCreateError, UserCreateRequest, UserFeatureService,
 };
@@ -11,7 +15,9 @@ use academy_demo::{
 };
 use academy_models::{
     auth::Login,
-    oauth2::{OAuth2Registration, OAuth2RegistrationToken},
+    oauth2::{OAuth2Registration, OAuth2RegistrationToken, OAuth2UserInfo},
+    session::SessionLoginMethod,
+    user::{User, UserComposite},
 };
 use academy_persistence_contracts::MockDatabase;
 use academy_shared_contracts::captcha::{CaptchaCheckError, MockCaptchaService};
@@ -39,22 +45,42 @@ async fn ok() {
 
     let db = MockDatabase::build(true);
 
+    let user_name_policy =
+        MockUserNamePolicyService::new().with_check(request.name.clone(), None, Ok(()));
+
     let captcha = MockCaptchaService::new().with_check(Some("resp"), Ok(()));
 
+    let user_password_policy = MockUserPasswordPolicyService::new().with_check(
+        request.password.clone().unwrap(),
+        request.name.clone(),
+        Some(request.email.clone()),
+        Ok(()),
+    );
+
     let user = MockUserService::new().with_create(req_to_cmd(&request), Ok(FOO.clone()));
 
     let session = MockSessionService::new().with_create(
         FOO.clone(),
         FOO_1.device_name.clone(),
-        true,
+        FOO_1.ip,
+        SessionLoginMethod::Password,
         expected.clone(),
     );
 
+    let session_new_device = MockSessionNewDeviceService::new().with_handle_login(
+        expected.user_composite.clone(),
+        expected.session.clone(),
+        false,
+    );
+
     let sut = UserFeatureServiceImpl {
         db,
+        user_name_policy,
         captcha,
         user,
+        user_password_policy,
         session,
+        session_new_device,
         ..Sut::default()
     };
 
@@ -63,6 +89,7 @@ async fn ok() {
         .create_user(
             request,
             FOO_1.device_name.clone(),
+            FOO_1.ip,
             Some("resp".try_into().unwrap()),
         )
         .await;
@@ -96,6 +123,9 @@ async fn ok_oauth2() {
 
     let db = MockDatabase::build(true);
 
+    let user_name_policy =
+        MockUserNamePolicyService::new().with_check(request.name.clone(), None, Ok(()));
+
     let captcha = MockCaptchaService::new().with_check(Some("resp"), Ok(()));
 
     let oauth2_registration = MockOAuth2RegistrationService::new()
@@ -113,16 +143,122 @@ async fn ok_oauth2() {
     let session = MockSessionService::new().with_create(
         FOO.clone(),
         FOO_1.device_name.clone(),
-        true,
+        FOO_1.ip,
+        SessionLoginMethod::OAuth2(TEST_OAUTH2_PROVIDER_ID.clone()),
+        expected.clone(),
+    );
+
+    let session_new_device = MockSessionNewDeviceService::new().with_handle_login(
+        expected.user_composite.clone(),
+        expected.session.clone(),
+        false,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        user_name_policy,
+        captcha,
+        user,
+        oauth2_registration,
+        session,
+        session_new_device,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_user(
+            request,
+            FOO_1.device_name.clone(),
+            FOO_1.ip,
+            Some("resp".try_into().unwrap()),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn ok_oauth2_verified_email() {
+    // Arrange
+    let token = OAuth2RegistrationToken::try_new(
+        "K7oACiokVoyttnGgYxJwCc2VCvDbQI10Bewthc5exlyQly2JZCViycDereak92oB",
+    )
+    .unwrap();
+
+    let request = UserCreateRequest {
+        name: FOO.user.name.clone(),
+        display_name: FOO.profile.display_name.clone(),
+        email: FOO.user.email.clone().unwrap(),
+        password: None,
+        oauth2_registration_token: Some(token.clone()),
+    };
+
+    let oauth2_registration = OAuth2Registration {
+        provider_id: TEST_OAUTH2_PROVIDER_ID.clone(),
+        remote_user: OAuth2UserInfo {
+            email: FOO.user.email.clone(),
+            email_verified: true,
+            ..FOO_OAUTH2_LINK_1.remote_user.clone()
+        },
+    };
+
+    let expected = Login {
+        user_composite: UserComposite {
+            user: User {
+                email_verified: true,
+                ..FOO.user.clone()
+            },
+            ..FOO.clone()
+        },
+        session: FOO_1.clone(),
+        access_token: "the access token".into(),
+        refresh_token: "some refresh token".into(),
+    };
+
+    let db = MockDatabase::build(true);
+
+    let user_name_policy =
+        MockUserNamePolicyService::new().with_check(request.name.clone(), None, Ok(()));
+
+    let captcha = MockCaptchaService::new().with_check(Some("resp"), Ok(()));
+
+    let user = MockUserService::new().with_create(
+        UserCreateCommand {
+            email_verified: true,
+            oauth2_registration: Some(oauth2_registration.clone()),
+            ..req_to_cmd(&request)
+        },
+        Ok(expected.user_composite.clone()),
+    );
+
+    let oauth2_registration = MockOAuth2RegistrationService::new()
+        .with_get(token.clone(), Some(oauth2_registration))
+        .with_remove(token);
+
+    let session = MockSessionService::new().with_create(
+        expected.user_composite.clone(),
+        FOO_1.device_name.clone(),
+        FOO_1.ip,
+        SessionLoginMethod::OAuth2(TEST_OAUTH2_PROVIDER_ID.clone()),
         expected.clone(),
     );
 
+    let session_new_device = MockSessionNewDeviceService::new().with_handle_login(
+        expected.user_composite.clone(),
+        expected.session.clone(),
+        false,
+    );
+
     let sut = UserFeatureServiceImpl {
         db,
+        user_name_policy,
         captcha,
         user,
         oauth2_registration,
         session,
+        session_new_device,
         ..Sut::default()
     };
 
@@ -131,6 +267,7 @@ async fn ok_oauth2() {
         .create_user(
             request,
             FOO_1.device_name.clone(),
+            FOO_1.ip,
             Some("resp".try_into().unwrap()),
         )
         .await;
@@ -154,7 +291,7 @@ async fn no_login_method() {
 
     // Act
     let result = sut
-        .create_user(request, FOO_1.device_name.clone(), None)
+        .create_user(request, FOO_1.device_name.clone(), FOO_1.ip, None)
         .await;
 
     // Assert
@@ -185,6 +322,7 @@ async fn invalid_recaptcha_response() {
         .create_user(
             request,
             FOO_1.device_name.clone(),
+            FOO_1.ip,
             Some("resp".try_into().unwrap()),
         )
         .await;
@@ -193,6 +331,46 @@ async fn invalid_recaptcha_response() {
     assert_matches!(result, Err(UserCreateError::Recaptcha));
 }
 
+#[tokio::test]
+async fn password_policy_violation() {
+    // Arrange
+    let request = UserCreateRequest {
+        name: FOO.user.name.clone(),
+        display_name: FOO.profile.display_name.clone(),
+        email: FOO.user.email.clone().unwrap(),
+        password: Some("password".try_into().unwrap()),
+        oauth2_registration_token: None,
+    };
+
+    let captcha = MockCaptchaService::new().with_check(None, Ok(()));
+
+    let user_password_policy = MockUserPasswordPolicyService::new().with_check(
+        request.password.clone().unwrap(),
+        request.name.clone(),
+        Some(request.email.clone()),
+        Err(UserPasswordPolicyViolation::Breached),
+    );
+
+    let sut = UserFeatureServiceImpl {
+        captcha,
+        user_password_policy,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .create_user(request, FOO_1.device_name.clone(), FOO_1.ip, None)
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserCreateError::PasswordPolicy(
+            UserPasswordPolicyViolation::Breached
+        ))
+    );
+}
+
 #[tokio::test]
 async fn name_conflict() {
     // Arrange
@@ -206,8 +384,18 @@ async fn name_conflict() {
 
     let db = MockDatabase::build(false);
 
+    let user_name_policy =
+        MockUserNamePolicyService::new().with_check(request.name.clone(), None, Ok(()));
+
     let captcha = MockCaptchaService::new().with_check(None, Ok(()));
 
+    let user_password_policy = MockUserPasswordPolicyService::new().with_check(
+        request.password.clone().unwrap(),
+        request.name.clone(),
+        Some(request.email.clone()),
+        Ok(()),
+    );
+
     let user = MockUserService::new().with_create(
         req_to_cmd(&request),
         Err(academy_core_user_contracts::user::UserCreateError::NameConflict),
@@ -215,14 +403,16 @@ async fn name_conflict() {
 
     let sut = UserFeatureServiceImpl {
         db,
+        user_name_policy,
         captcha,
         user,
+        user_password_policy,
         ..Sut::default()
     };
 
     // Act
     let result = sut
-        .create_user(request, FOO_1.device_name.clone(), None)
+        .create_user(request, FOO_1.device_name.clone(), FOO_1.ip, None)
         .await;
 
     // Assert
@@ -242,8 +432,18 @@ async fn email_conflict() {
 
     let db = MockDatabase::build(false);
 
+    let user_name_policy =
+        MockUserNamePolicyService::new().with_check(request.name.clone(), None, Ok(()));
+
     let captcha = MockCaptchaService::new().with_check(None, Ok(()));
 
+    let user_password_policy = MockUserPasswordPolicyService::new().with_check(
+        request.password.clone().unwrap(),
+        request.name.clone(),
+        Some(request.email.clone()),
+        Ok(()),
+    );
+
     let user = MockUserService::new().with_create(
         req_to_cmd(&request),
         Err(academy_core_user_contracts::user::UserCreateError::EmailConflict),
@@ -251,14 +451,16 @@ async fn email_conflict() {
 
     let sut = UserFeatureServiceImpl {
         db,
+        user_name_policy,
         captcha,
         user,
+        user_password_policy,
         ..Sut::default()
     };
 
     // Act
     let result = sut
-        .create_user(request, FOO_1.device_name.clone(), None)
+        .create_user(request, FOO_1.device_name.clone(), FOO_1.ip, None)
         .await;
 
     // Assert
@@ -295,6 +497,7 @@ async fn oauth2_invalid_registration_token() {
         .create_user(
             request,
             FOO_1.device_name.clone(),
+            FOO_1.ip,
             Some("resp".try_into().unwrap()),
         )
         .await;
@@ -324,6 +527,9 @@ async fn oauth2_remote_already_linked() {
 
     let db = MockDatabase::build(false);
 
+    let user_name_policy =
+        MockUserNamePolicyService::new().with_check(request.name.clone(), None, Ok(()));
+
     let captcha = MockCaptchaService::new().with_check(Some("resp"), Ok(()));
 
     let oauth2_registration = MockOAuth2RegistrationService::new().with_get(
@@ -341,6 +547,7 @@ async fn oauth2_remote_already_linked() {
 
     let sut = UserFeatureServiceImpl {
         db,
+        user_name_policy,
         captcha,
         user,
         oauth2_registration,
@@ -352,6 +559,7 @@ async fn oauth2_remote_already_linked() {
         .create_user(
             request,
             FOO_1.device_name.clone(),
+            FOO_1.ip,
             Some("resp".try_into().unwrap()),
         )
         .await;
@@ -378,3 +586,51 @@ fn req_to_cmd(req: &UserCreateRequest) -> UserCreateCommand {
             }),
     }
 }
+
+#[tokio::test]
+async fn name_policy_violation() {
+    // Arrange
+    let request = UserCreateRequest {
+        name: "support".try_into().unwrap(),
+        display_name: FOO.profile.display_name.clone(),
+        email: FOO.user.email.clone().unwrap(),
+        password: Some("secure password".try_into().unwrap()),
+        oauth2_registration_token: None,
+    };
+
+    let db = MockDatabase::build(false);
+
+    let captcha = MockCaptchaService::new().with_check(None, Ok(()));
+
+    let user_password_policy = MockUserPasswordPolicyService::new().with_check(
+        request.password.clone().unwrap(),
+        request.name.clone(),
+        Some(request.email.clone()),
+        Ok(()),
+    );
+
+    let user_name_policy = MockUserNamePolicyService::new().with_check(
+        request.name.clone(),
+        None,
+        Err(UserNamePolicyViolation::Reserved),
+    );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        captcha,
+        user_password_policy,
+        user_name_policy,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.create_user(request, None, None, None).await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserCreateError::NamePolicy(
+            UserNamePolicyViolation::Reserved
+        ))
+    );
+}
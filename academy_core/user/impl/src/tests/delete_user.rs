@@ -1,38 +1,70 @@
 use academy_auth_contracts::MockAuthService;
-use academy_core_user_contracts::{UserDeleteError, UserFeatureService};
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_session_contracts::session::MockSessionService;
+use academy_core_user_contracts::{
+    deletion::MockUserDeletionService, UserDeleteError, UserFeatureService,
+};
 use academy_demo::{
+    personal_access_token::FOO_PAT,
     session::{ADMIN_1, BAR_1, FOO_1},
     user::{ADMIN, BAR, FOO},
+    VERIFICATION_CODE_1,
 };
 use academy_models::{
+    audit::{AuditAction, AuditContext},
     auth::{AuthError, AuthenticateError, AuthorizeError},
-    user::UserIdOrSelf,
+    user::{User, UserComposite, UserIdOrSelf},
 };
-use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase, MockTransaction};
 use academy_utils::assert_matches;
+use anyhow::anyhow;
 
 use crate::{tests::Sut, UserFeatureServiceImpl};
 
 #[tokio::test]
 async fn ok_self() {
     // Arrange
-    let auth = MockAuthService::new()
-        .with_authenticate(Some((FOO.user.clone(), FOO_1.clone())))
-        .with_invalidate_access_tokens(FOO.user.id);
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
 
     let db = MockDatabase::build(true);
 
-    let user_repo = MockUserRepository::new().with_delete(FOO.user.id, true);
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let session = MockSessionService::new().with_delete_by_user(FOO.user.id);
+
+    let user_deletion = MockUserDeletionService::new()
+        .with_schedule(FOO.user.id, VERIFICATION_CODE_1.clone())
+        .with_notify(
+            FOO.user.id,
+            FOO.user
+                .email
+                .clone()
+                .unwrap()
+                .with_name(FOO.profile.display_name.clone().into_inner()),
+            VERIFICATION_CODE_1.clone(),
+        );
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserDelete,
+    );
 
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_repo,
+        session,
+        user_deletion,
         ..Sut::default()
     };
 
     // Act
-    let result = sut.delete_user(&"token".into(), UserIdOrSelf::Slf).await;
+    let result = sut
+        .delete_user(&"token".into(), UserIdOrSelf::Slf, &AuditContext::default())
+        .await;
 
     // Assert
     result.unwrap();
@@ -41,28 +73,193 @@ async fn ok_self() {
 #[tokio::test]
 async fn ok_admin() {
     // Arrange
-    let auth = MockAuthService::new()
-        .with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())))
-        .with_invalidate_access_tokens(FOO.user.id);
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
 
     let db = MockDatabase::build(true);
 
-    let user_repo = MockUserRepository::new().with_delete(FOO.user.id, true);
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let session = MockSessionService::new().with_delete_by_user(FOO.user.id);
+
+    let user_deletion = MockUserDeletionService::new()
+        .with_schedule(FOO.user.id, VERIFICATION_CODE_1.clone())
+        .with_notify(
+            FOO.user.id,
+            FOO.user
+                .email
+                .clone()
+                .unwrap()
+                .with_name(FOO.profile.display_name.clone().into_inner()),
+            VERIFICATION_CODE_1.clone(),
+        );
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        FOO.user.id,
+        AuditAction::UserDelete,
+    );
 
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_repo,
+        session,
+        user_deletion,
         ..Sut::default()
     };
 
     // Act
-    let result = sut.delete_user(&"token".into(), FOO.user.id.into()).await;
+    let result = sut
+        .delete_user(
+            &"token".into(),
+            FOO.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
 
     // Assert
     result.unwrap();
 }
 
+#[tokio::test]
+async fn ok_no_email() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let user_repo = MockUserRepository::new().with_get_composite(BAR.user.id, Some(BAR.clone()));
+
+    let session = MockSessionService::new().with_delete_by_user(BAR.user.id);
+
+    let user_deletion =
+        MockUserDeletionService::new().with_schedule(BAR.user.id, VERIFICATION_CODE_1.clone());
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        BAR.user.id,
+        AuditAction::UserDelete,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        audit,
+        auth,
+        db,
+        user_repo,
+        session,
+        user_deletion,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_user(
+            &"token".into(),
+            BAR.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn ok_notify_failed() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let session = MockSessionService::new().with_delete_by_user(FOO.user.id);
+
+    let mut user_deletion =
+        MockUserDeletionService::new().with_schedule(FOO.user.id, VERIFICATION_CODE_1.clone());
+    user_deletion
+        .expect_notify()
+        .once()
+        .return_once(|_, _, _| Box::pin(std::future::ready(Err(anyhow!("send failed")))));
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserDelete,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        audit,
+        auth,
+        db,
+        user_repo,
+        session,
+        user_deletion,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_user(&"token".into(), UserIdOrSelf::Slf, &AuditContext::default())
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn commit_failed() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let mut txn = MockTransaction::new();
+    txn.expect_commit()
+        .once()
+        .return_once(|| Box::pin(std::future::ready(Err(anyhow!("commit failed")))));
+    let mut db = MockDatabase::new();
+    db.expect_begin_transaction()
+        .once()
+        .return_once(|| Box::pin(std::future::ready(Ok(txn))));
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let session = MockSessionService::new().with_delete_by_user(FOO.user.id);
+
+    let user_deletion =
+        MockUserDeletionService::new().with_schedule(FOO.user.id, VERIFICATION_CODE_1.clone());
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserDelete,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        audit,
+        auth,
+        db,
+        user_repo,
+        session,
+        user_deletion,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_user(&"token".into(), UserIdOrSelf::Slf, &AuditContext::default())
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserDeleteError::Other(_)));
+}
+
 #[tokio::test]
 async fn unauthenticated() {
     // Arrange
@@ -74,7 +271,13 @@ async fn unauthenticated() {
     };
 
     // Act
-    let result = sut.delete_user(&"token".into(), FOO.user.id.into()).await;
+    let result = sut
+        .delete_user(
+            &"token".into(),
+            FOO.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
 
     // Assert
     assert_matches!(
@@ -96,7 +299,13 @@ async fn unauthorized() {
     };
 
     // Act
-    let result = sut.delete_user(&"token".into(), FOO.user.id.into()).await;
+    let result = sut
+        .delete_user(
+            &"token".into(),
+            FOO.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
 
     // Assert
     assert_matches!(
@@ -108,15 +317,80 @@ async fn unauthorized() {
 }
 
 #[tokio::test]
-async fn not_found() {
+async fn personal_access_token_not_allowed() {
     // Arrange
     let auth = MockAuthService::new()
-        .with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())))
-        .with_invalidate_access_tokens(FOO.user.id);
+        .with_authenticate_personal_access_token(Some((FOO.user.clone(), FOO_PAT.clone())));
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_user(
+            &"token".into(),
+            FOO.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserDeleteError::Auth(AuthError::Authorize(
+            AuthorizeError::Session
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
 
     let db = MockDatabase::build(false);
 
-    let user_repo = MockUserRepository::new().with_delete(FOO.user.id, false);
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, None);
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_user(
+            &"token".into(),
+            FOO.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserDeleteError::NotFound));
+}
+
+#[tokio::test]
+async fn already_deleted() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let foo = UserComposite {
+        user: User {
+            deleted_at: Some(FOO.user.created_at),
+            ..FOO.user.clone()
+        },
+        ..FOO.clone()
+    };
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(foo));
 
     let sut = UserFeatureServiceImpl {
         auth,
@@ -126,7 +400,13 @@ async fn not_found() {
     };
 
     // Act
-    let result = sut.delete_user(&"token".into(), FOO.user.id.into()).await;
+    let result = sut
+        .delete_user(
+            &"token".into(),
+            FOO.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
 
     // Assert
     assert_matches!(result, Err(UserDeleteError::NotFound));
@@ -0,0 +1,148 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_user_contracts::{
+    avatar::MockUserAvatarService, UserDeleteAvatarError, UserFeatureService,
+};
+use academy_demo::{
+    session::{ADMIN_1, BAR_1, FOO_1},
+    user::{ADMIN, BAR, FOO},
+};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthorizeError},
+    user::{UserComposite, UserIdOrSelf, UserProfile, UserProfilePatch},
+};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, UserFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let expected = UserComposite {
+        profile: UserProfile {
+            avatar_url: None,
+            ..BAR.profile.clone()
+        },
+        ..BAR.clone()
+    };
+
+    let db = MockDatabase::build(true);
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite(BAR.user.id, Some(BAR.clone()))
+        .with_update_profile(
+            BAR.user.id,
+            UserProfilePatch::new().update_avatar_url(None),
+            true,
+        );
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        BAR.user.id,
+        AuditAction::UserUpdate,
+    );
+
+    let user_avatar = MockUserAvatarService::new().with_remove(BAR.user.id);
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        audit,
+        user_avatar,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_user_avatar(
+            &"token".into(),
+            BAR.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn no_avatar() {
+    // Arrange
+    let db = MockDatabase::build(false);
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_user_avatar(&"token".into(), UserIdOrSelf::Slf, &AuditContext::default())
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), *FOO);
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_user_avatar(
+            &"token".into(),
+            BAR.user.id.into(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserDeleteAvatarError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let db = MockDatabase::build(false);
+    let auth = MockAuthService::new().with_authenticate(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let user_repo = MockUserRepository::new().with_get_composite(BAR.user.id, None);
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .delete_user_avatar(&"token".into(), UserIdOrSelf::Slf, &AuditContext::default())
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserDeleteAvatarError::NotFound));
+}
@@ -0,0 +1,134 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_user_contracts::{
+    deletion::MockUserDeletionService, UserFeatureService, UserForceRestoreError,
+};
+use academy_demo::{
+    session::{ADMIN_1, BAR_1},
+    user::{ADMIN, BAR, FOO},
+};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthorizeError},
+};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_utils::{assert_matches, Apply};
+
+use crate::{tests::Sut, UserFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let deleted = FOO
+        .clone()
+        .with(|u| u.user.deleted_at = Some(FOO.user.created_at));
+
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(deleted));
+
+    let user_deletion = MockUserDeletionService::new().with_force_restore(FOO.user.id, true);
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        FOO.user.id,
+        AuditAction::UserRestore,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        user_deletion,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .force_restore_user(&"token".into(), FOO.user.id, &AuditContext::default())
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .force_restore_user(&"token".into(), FOO.user.id, &AuditContext::default())
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserForceRestoreError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, None);
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .force_restore_user(&"token".into(), FOO.user.id, &AuditContext::default())
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserForceRestoreError::NotFound));
+}
+
+#[tokio::test]
+async fn not_deleted() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .force_restore_user(&"token".into(), FOO.user.id, &AuditContext::default())
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserForceRestoreError::NotFound));
+}
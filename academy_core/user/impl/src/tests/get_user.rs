@@ -6,6 +6,7 @@ use academy_demo::{
 };
 use academy_models::{
     auth::{AuthError, AuthenticateError, AuthorizeError},
+    role::Permission,
     user::UserIdOrSelf,
 };
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
@@ -17,7 +18,8 @@ use crate::{tests::Sut, UserFeatureServiceImpl};
 async fn ok_self() {
     // Arrange
     let db = MockDatabase::build(false);
-    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
 
     let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
 
@@ -40,7 +42,33 @@ async fn ok_admin() {
     // Arrange
     let db = MockDatabase::build(false);
     let auth =
-        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.get_user(&"token".into(), FOO.user.id.into()).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), *FOO);
+}
+
+#[tokio::test]
+async fn ok_permission() {
+    // Arrange
+    let db = MockDatabase::build(false);
+    let auth = MockAuthService::new().with_authenticate_read_permissions(
+        BAR.user.clone(),
+        BAR_1.clone(),
+        [Permission::ReadUsers].into(),
+    );
 
     let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
 
@@ -61,7 +89,7 @@ async fn ok_admin() {
 #[tokio::test]
 async fn unauthenticated() {
     // Arrange
-    let auth = MockAuthService::new().with_authenticate(None);
+    let auth = MockAuthService::new().with_authenticate_read(None);
 
     let sut = UserFeatureServiceImpl {
         auth,
@@ -83,7 +111,8 @@ async fn unauthenticated() {
 #[tokio::test]
 async fn unauthorized() {
     // Arrange
-    let auth = MockAuthService::new().with_authenticate(Some((BAR.user.clone(), BAR_1.clone())));
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((BAR.user.clone(), BAR_1.clone())));
 
     let sut = UserFeatureServiceImpl {
         auth,
@@ -97,7 +126,7 @@ async fn unauthorized() {
     assert_matches!(
         result,
         Err(UserGetError::Auth(AuthError::Authorize(
-            AuthorizeError::Admin
+            AuthorizeError::Permission(Permission::ReadUsers)
         )))
     );
 }
@@ -107,7 +136,7 @@ async fn not_found() {
     // Arrange
     let db = MockDatabase::build(false);
     let auth =
-        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
 
     let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, None);
 
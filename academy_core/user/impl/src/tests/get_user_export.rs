@@ -0,0 +1,58 @@
+use academy_core_user_contracts::{
+    export::MockUserExportService, UserFeatureService, UserGetExportError,
+};
+use academy_demo::{session::FOO_1, user::FOO};
+use academy_models::{user_export::UserExport, VerificationCode};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, UserFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let code = VerificationCode::try_new("JRHZ-3G4B-MIBG-PPYI").unwrap();
+    let expected = UserExport {
+        created_at: FOO.user.created_at,
+        user_composite: FOO.clone(),
+        emails: vec![],
+        name_history: vec![],
+        sessions: vec![FOO_1.clone()],
+        oauth2_links: vec![],
+        totp_devices: vec![],
+        webauthn_devices: vec![],
+        audit_events: vec![],
+    };
+
+    let user_export =
+        MockUserExportService::new().with_get(FOO.user.id, code.clone(), Some(expected.clone()));
+
+    let sut = UserFeatureServiceImpl {
+        user_export,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.get_user_export(FOO.user.id, code).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn invalid_code() {
+    // Arrange
+    let code = VerificationCode::try_new("JRHZ-3G4B-MIBG-PPYI").unwrap();
+
+    let user_export = MockUserExportService::new().with_get(FOO.user.id, code.clone(), None);
+
+    let sut = UserFeatureServiceImpl {
+        user_export,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.get_user_export(FOO.user.id, code).await;
+
+    // Assert
+    assert_matches!(result, Err(UserGetExportError::InvalidCode));
+}
@@ -0,0 +1,92 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_user_contracts::{UserFeatureService, UserListEmailsError};
+use academy_demo::{
+    session::{ADMIN_1, BAR_1, FOO_1},
+    user::{ADMIN, BAR, FOO, FOO_EMAIL_1},
+};
+use academy_models::{
+    auth::{AuthError, AuthorizeError},
+    role::Permission,
+    user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, UserFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let db = MockDatabase::build(false);
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let user_repo = MockUserRepository::new()
+        .with_exists(FOO.user.id, true)
+        .with_list_emails(FOO.user.id, vec![FOO_EMAIL_1.clone()]);
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_user_emails(&"token".into(), UserIdOrSelf::Slf)
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), vec![FOO_EMAIL_1.clone()]);
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_user_emails(&"token".into(), FOO.user.id.into())
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserListEmailsError::Auth(AuthError::Authorize(
+            AuthorizeError::Permission(Permission::ReadUsers)
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let db = MockDatabase::build(false);
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let user_repo = MockUserRepository::new().with_exists(FOO.user.id, false);
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_user_emails(&"token".into(), FOO.user.id.into())
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserListEmailsError::NotFound));
+}
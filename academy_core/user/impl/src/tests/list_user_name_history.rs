@@ -0,0 +1,88 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_user_contracts::{UserFeatureService, UserListNameHistoryError};
+use academy_demo::{
+    session::{ADMIN_1, FOO_1},
+    user::{ADMIN, FOO, FOO_NAME_HISTORY_1},
+};
+use academy_models::auth::{AuthError, AuthorizeError};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, UserFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let db = MockDatabase::build(false);
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let user_repo = MockUserRepository::new()
+        .with_exists(FOO.user.id, true)
+        .with_list_name_history(FOO.user.id, vec![FOO_NAME_HISTORY_1.clone()]);
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_user_name_history(&"token".into(), FOO.user.id)
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), vec![FOO_NAME_HISTORY_1.clone()]);
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_user_name_history(&"token".into(), FOO.user.id)
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserListNameHistoryError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let db = MockDatabase::build(false);
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let user_repo = MockUserRepository::new().with_exists(FOO.user.id, false);
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .list_user_name_history(&"token".into(), FOO.user.id)
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserListNameHistoryError::NotFound));
+}
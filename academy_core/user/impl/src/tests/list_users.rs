@@ -10,6 +10,7 @@ use academy_demo::{
 use academy_models::{
     auth::{AuthError, AuthenticateError, AuthorizeError},
     pagination::PaginationSlice,
+    role::Permission,
     user::UserFilter,
 };
 use academy_persistence_contracts::MockDatabase;
@@ -29,7 +30,40 @@ async fn ok() {
 
     let db = MockDatabase::build(false);
     let auth =
-        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+        MockAuthService::new().with_authenticate_read(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let user = MockUserService::new().with_list(query.clone(), expected.clone());
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut.list_users(&"token".into(), query).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn ok_permission() {
+    // Arrange
+    let query = build_query();
+
+    let expected = UserListResult {
+        total: 42,
+        user_composites: ALL_USERS.iter().copied().cloned().collect(),
+    };
+
+    let db = MockDatabase::build(false);
+    let auth = MockAuthService::new().with_authenticate_read_permissions(
+        FOO.user.clone(),
+        FOO_1.clone(),
+        [Permission::ReadUsers].into(),
+    );
 
     let user = MockUserService::new().with_list(query.clone(), expected.clone());
 
@@ -52,7 +86,7 @@ async fn unauthenticated() {
     // Arrange
     let query = build_query();
 
-    let auth = MockAuthService::new().with_authenticate(None);
+    let auth = MockAuthService::new().with_authenticate_read(None);
 
     let sut = UserFeatureServiceImpl {
         auth,
@@ -76,7 +110,8 @@ async fn unauthorized() {
     // Arrange
     let query = build_query();
 
-    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+    let auth =
+        MockAuthService::new().with_authenticate_read(Some((FOO.user.clone(), FOO_1.clone())));
 
     let sut = UserFeatureServiceImpl {
         auth,
@@ -90,7 +125,7 @@ async fn unauthorized() {
     assert_matches!(
         result,
         Err(UserListError::Auth(AuthError::Authorize(
-            AuthorizeError::Admin
+            AuthorizeError::Permission(Permission::ReadUsers)
         )))
     );
 }
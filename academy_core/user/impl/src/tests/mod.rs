@@ -1,26 +1,46 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_oauth2_contracts::registration::MockOAuth2RegistrationService;
-use academy_core_session_contracts::session::MockSessionService;
+use academy_core_session_contracts::{
+    new_device::MockSessionNewDeviceService, session::MockSessionService,
+};
 use academy_core_user_contracts::{
-    email_confirmation::MockUserEmailConfirmationService, update::MockUserUpdateService,
-    user::MockUserService,
+    avatar::MockUserAvatarService, deletion::MockUserDeletionService,
+    email_confirmation::MockUserEmailConfirmationService, export::MockUserExportService,
+    name_policy::MockUserNamePolicyService, password_policy::MockUserPasswordPolicyService,
+    update::MockUserUpdateService, user::MockUserService,
 };
 use academy_extern_contracts::{internal::MockInternalApiService, vat::MockVatApiService};
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase, MockTransaction};
 use academy_shared_contracts::captcha::MockCaptchaService;
+use regex::bytes::RegexSet;
 
 use crate::{UserFeatureConfig, UserFeatureServiceImpl};
 
+mod add_user_email;
+mod cancel_email_change;
+mod confirm_email_change;
+mod confirm_user_email;
 mod create_user;
 mod delete_user;
+mod delete_user_avatar;
+mod force_restore_user;
 mod get_user;
+mod get_user_export;
+mod list_user_emails;
+mod list_user_name_history;
 mod list_users;
+mod remove_user_email;
 mod request_password_reset;
+mod request_user_export;
 mod request_verification_email;
 mod reset_password;
+mod restore_user;
+mod set_primary_user_email;
 mod update_user;
+mod upload_user_avatar;
 mod verify_email;
 mod verify_newsletter_subscription;
 
@@ -33,8 +53,15 @@ type Sut = UserFeatureServiceImpl<
     MockUserService<MockTransaction>,
     MockUserEmailConfirmationService<MockTransaction>,
     MockUserUpdateService<MockTransaction>,
+    MockUserPasswordPolicyService,
+    MockUserNamePolicyService<MockTransaction>,
+    MockUserAvatarService,
+    MockUserExportService,
+    MockUserDeletionService<MockTransaction>,
     MockSessionService<MockTransaction>,
+    MockSessionNewDeviceService<MockTransaction>,
     MockOAuth2RegistrationService,
+    MockAuditService<MockTransaction>,
     MockUserRepository<MockTransaction>,
 >;
 
@@ -42,6 +69,9 @@ impl Default for UserFeatureConfig {
     fn default() -> Self {
         Self {
             name_change_rate_limit: Duration::from_secs(30 * 24 * 3600),
+            name_quarantine: Duration::from_secs(180 * 24 * 3600),
+            reserved_names: Arc::new(["admin".into(), "support".into()].into()),
+            blocked_names: RegexSet::new(["^bootstrap[_-]?academy"]).unwrap(),
             verification_redirect_url: "https://bootstrap.academy/auth/verify-account"
                 .to_owned()
                 .into(),
@@ -54,6 +84,27 @@ impl Default for UserFeatureConfig {
                 .to_owned()
                 .into(),
             newsletter_subscription_verification_code_ttl: Duration::from_secs(3600),
+            email_change_redirect_url: "https://bootstrap.academy/account/change-email"
+                .to_owned()
+                .into(),
+            email_change_cancel_redirect_url:
+                "https://bootstrap.academy/account/cancel-email-change"
+                    .to_owned()
+                    .into(),
+            email_change_verification_code_ttl: Duration::from_secs(3600),
+            additional_email_redirect_url: "https://bootstrap.academy/account/add-email"
+                .to_owned()
+                .into(),
+            additional_email_verification_code_ttl: Duration::from_secs(3600),
+            password_policy: None,
+            export_redirect_url: "https://bootstrap.academy/account/export".to_owned().into(),
+            export_code_ttl: Duration::from_secs(7 * 24 * 3600),
+            deletion_grace_period: Duration::from_secs(30 * 24 * 3600),
+            restore_redirect_url: "https://bootstrap.academy/account/restore"
+                .to_owned()
+                .into(),
+            avatar_max_size: 1024 * 1024,
+            avatar_max_dimension: 1024,
         }
     }
 }
@@ -0,0 +1,113 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_user_contracts::{UserFeatureService, UserRemoveEmailError};
+use academy_demo::{
+    session::{BAR_1, FOO_1},
+    user::{BAR, FOO, FOO_EMAIL_1},
+};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthorizeError},
+    user::UserIdOrSelf,
+};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, UserFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let db = MockDatabase::build(true);
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let user_repo =
+        MockUserRepository::new().with_remove_email(FOO.user.id, FOO_EMAIL_1.email.clone(), true);
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .remove_user_email(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            FOO_EMAIL_1.email.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .remove_user_email(
+            &"token".into(),
+            FOO.user.id.into(),
+            FOO_EMAIL_1.email.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserRemoveEmailError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let db = MockDatabase::build(false);
+    let auth = MockAuthService::new().with_authenticate(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let user_repo =
+        MockUserRepository::new().with_remove_email(BAR.user.id, FOO_EMAIL_1.email.clone(), false);
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .remove_user_email(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            FOO_EMAIL_1.email.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserRemoveEmailError::NotFound));
+}
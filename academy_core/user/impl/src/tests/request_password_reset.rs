@@ -2,7 +2,7 @@ use academy_core_user_contracts::{
     email_confirmation::MockUserEmailConfirmationService, UserFeatureService,
     UserRequestPasswordResetError,
 };
-use academy_demo::user::FOO;
+use academy_demo::user::{FOO, FOO_EMAIL_1};
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
 use academy_shared_contracts::captcha::{CaptchaCheckError, MockCaptchaService};
 use academy_utils::assert_matches;
@@ -49,6 +49,42 @@ async fn ok() {
     result.unwrap();
 }
 
+#[tokio::test]
+async fn ok_additional_email() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let captcha = MockCaptchaService::new().with_check(Some("resp"), Ok(()));
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite_by_email(FOO_EMAIL_1.email.clone(), Some(FOO.clone()));
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new()
+        .with_request_password_reset(
+            FOO.user.id,
+            FOO_EMAIL_1
+                .email
+                .clone()
+                .with_name(FOO.profile.display_name.clone().into_inner()),
+        );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        captcha,
+        user_repo,
+        user_email_confirmation,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .request_password_reset(FOO_EMAIL_1.email.clone(), Some("resp".try_into().unwrap()))
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
 #[tokio::test]
 async fn invalid_captcha_response() {
     // Arrange
@@ -9,6 +9,7 @@ use academy_demo::{
 };
 use academy_models::{
     auth::{AuthError, AuthenticateError, AuthorizeError},
+    role::Permission,
     user::UserIdOrSelf,
 };
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
@@ -93,6 +94,48 @@ async fn ok_admin() {
     result.unwrap();
 }
 
+#[tokio::test]
+async fn ok_permission() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate_permissions(
+        BAR.user.clone(),
+        BAR_1.clone(),
+        [Permission::ResendVerificationEmails].into(),
+    );
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite(
+        FOO.user.id,
+        Some(FOO.clone().with(|u| u.user.email_verified = false)),
+    );
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new()
+        .with_request_verification(
+            FOO.user
+                .email
+                .clone()
+                .unwrap()
+                .with_name(FOO.profile.display_name.clone().into_inner()),
+        );
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        user_email_confirmation,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .request_verification_email(&"token".into(), FOO.user.id.into())
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
 #[tokio::test]
 async fn unauthenticated() {
     // Arrange
@@ -136,7 +179,9 @@ async fn unauthorized() {
     assert_matches!(
         result,
         Err(UserRequestVerificationEmailError::Auth(
-            AuthError::Authorize(AuthorizeError::Admin)
+            AuthError::Authorize(AuthorizeError::Permission(
+                Permission::ResendVerificationEmails
+            ))
         ))
     );
 }
@@ -1,13 +1,16 @@
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_user_contracts::{
     email_confirmation::{
         MockUserEmailConfirmationService, UserEmailConfirmationResetPasswordError,
     },
+    password_policy::{MockUserPasswordPolicyService, UserPasswordPolicyViolation},
     UserFeatureService, UserResetPasswordError,
 };
 use academy_demo::{
     user::{FOO, FOO_PASSWORD},
     VERIFICATION_CODE_1,
 };
+use academy_models::audit::{AuditAction, AuditContext};
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
 use academy_utils::assert_matches;
 
@@ -21,6 +24,13 @@ async fn ok() {
     let user_repo = MockUserRepository::new()
         .with_get_composite_by_email(FOO.user.email.clone().unwrap(), Some(FOO.clone()));
 
+    let user_password_policy = MockUserPasswordPolicyService::new().with_check(
+        FOO_PASSWORD.clone(),
+        FOO.user.name.clone(),
+        FOO.user.email.clone(),
+        Ok(()),
+    );
+
     let user_email_confirmation = MockUserEmailConfirmationService::new().with_reset_password(
         FOO.user.id,
         VERIFICATION_CODE_1.clone(),
@@ -28,10 +38,19 @@ async fn ok() {
         Ok(()),
     );
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::PasswordReset,
+    );
+
     let sut = UserFeatureServiceImpl {
+        audit,
         db,
         user_repo,
         user_email_confirmation,
+        user_password_policy,
         ..Sut::default()
     };
 
@@ -41,6 +60,7 @@ async fn ok() {
             FOO.user.email.clone().unwrap(),
             VERIFICATION_CODE_1.clone(),
             FOO_PASSWORD.clone(),
+            &AuditContext::default(),
         )
         .await;
 
@@ -68,6 +88,7 @@ async fn user_not_found() {
             FOO.user.email.clone().unwrap(),
             VERIFICATION_CODE_1.clone(),
             FOO_PASSWORD.clone(),
+            &AuditContext::default(),
         )
         .await;
 
@@ -83,6 +104,13 @@ async fn invalid_code() {
     let user_repo = MockUserRepository::new()
         .with_get_composite_by_email(FOO.user.email.clone().unwrap(), Some(FOO.clone()));
 
+    let user_password_policy = MockUserPasswordPolicyService::new().with_check(
+        FOO_PASSWORD.clone(),
+        FOO.user.name.clone(),
+        FOO.user.email.clone(),
+        Ok(()),
+    );
+
     let user_email_confirmation = MockUserEmailConfirmationService::new().with_reset_password(
         FOO.user.id,
         VERIFICATION_CODE_1.clone(),
@@ -94,6 +122,7 @@ async fn invalid_code() {
         db,
         user_repo,
         user_email_confirmation,
+        user_password_policy,
         ..Sut::default()
     };
 
@@ -103,9 +132,51 @@ async fn invalid_code() {
             FOO.user.email.clone().unwrap(),
             VERIFICATION_CODE_1.clone(),
             FOO_PASSWORD.clone(),
+            &AuditContext::default(),
         )
         .await;
 
     // Act
     assert_matches!(result, Err(UserResetPasswordError::Failed));
 }
+
+#[tokio::test]
+async fn password_policy_violation() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite_by_email(FOO.user.email.clone().unwrap(), Some(FOO.clone()));
+
+    let user_password_policy = MockUserPasswordPolicyService::new().with_check(
+        FOO_PASSWORD.clone(),
+        FOO.user.name.clone(),
+        FOO.user.email.clone(),
+        Err(UserPasswordPolicyViolation::TooShort { min_length: 8 }),
+    );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        user_repo,
+        user_password_policy,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .reset_password(
+            FOO.user.email.clone().unwrap(),
+            VERIFICATION_CODE_1.clone(),
+            FOO_PASSWORD.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Act
+    assert_matches!(
+        result,
+        Err(UserResetPasswordError::PasswordPolicy(
+            UserPasswordPolicyViolation::TooShort { min_length: 8 }
+        ))
+    );
+}
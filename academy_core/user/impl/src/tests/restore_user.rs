@@ -0,0 +1,79 @@
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_user_contracts::{
+    deletion::{MockUserDeletionService, UserDeletionRestoreError},
+    UserFeatureService, UserRestoreError,
+};
+use academy_demo::{user::FOO, VERIFICATION_CODE_1};
+use academy_models::audit::{AuditAction, AuditContext};
+use academy_persistence_contracts::MockDatabase;
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, UserFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let db = MockDatabase::build(true);
+
+    let user_deletion = MockUserDeletionService::new().with_restore(
+        FOO.user.id,
+        VERIFICATION_CODE_1.clone(),
+        Ok(()),
+    );
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserRestore,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        user_deletion,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .restore_user(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn invalid_code() {
+    // Arrange
+    let db = MockDatabase::build(false);
+
+    let user_deletion = MockUserDeletionService::new().with_restore(
+        FOO.user.id,
+        VERIFICATION_CODE_1.clone(),
+        Err(UserDeletionRestoreError::InvalidCode),
+    );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        user_deletion,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .restore_user(
+            FOO.user.id,
+            VERIFICATION_CODE_1.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserRestoreError::InvalidCode));
+}
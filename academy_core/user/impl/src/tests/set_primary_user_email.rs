@@ -0,0 +1,166 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_user_contracts::{
+    update::{MockUserUpdateService, UserUpdatePrimaryEmailError},
+    UserFeatureService, UserSetPrimaryEmailError,
+};
+use academy_demo::{
+    session::{ADMIN_1, BAR_1, FOO_1},
+    user::{ADMIN, BAR, FOO, FOO_EMAIL_1},
+};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthorizeError},
+    user::{User, UserComposite, UserIdOrSelf},
+};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, UserFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let expected = UserComposite {
+        user: User {
+            email: Some(FOO_EMAIL_1.email.clone()),
+            ..FOO.user.clone()
+        },
+        ..FOO.clone()
+    };
+
+    let db = MockDatabase::build(true);
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let user_update = MockUserUpdateService::new().with_update_primary_email(
+        FOO.user.clone(),
+        FOO_EMAIL_1.email.clone(),
+        Ok(expected.user.clone()),
+    );
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        user_update,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .set_primary_user_email(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            FOO_EMAIL_1.email.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .set_primary_user_email(
+            &"token".into(),
+            FOO.user.id.into(),
+            FOO_EMAIL_1.email.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserSetPrimaryEmailError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let db = MockDatabase::build(false);
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, None);
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .set_primary_user_email(
+            &"token".into(),
+            FOO.user.id.into(),
+            FOO_EMAIL_1.email.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserSetPrimaryEmailError::NotFound));
+}
+
+#[tokio::test]
+async fn email_not_found() {
+    // Arrange
+    let db = MockDatabase::build(false);
+    let auth = MockAuthService::new().with_authenticate(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let user_repo = MockUserRepository::new().with_get_composite(BAR.user.id, Some(BAR.clone()));
+
+    let user_update = MockUserUpdateService::new().with_update_primary_email(
+        BAR.user.clone(),
+        FOO_EMAIL_1.email.clone(),
+        Err(UserUpdatePrimaryEmailError::NotFound),
+    );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        user_update,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .set_primary_user_email(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            FOO_EMAIL_1.email.clone(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserSetPrimaryEmailError::EmailNotFound));
+}
@@ -1,4 +1,5 @@
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_user_contracts::{
     update::MockUserUpdateService, UserFeatureService, UserUpdateError, UserUpdateRequest,
     UserUpdateUserRequest,
@@ -8,7 +9,10 @@ use academy_demo::{
     user::{ADMIN, FOO},
     UUID1,
 };
-use academy_models::user::{User, UserComposite, UserIdOrSelf};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    user::{User, UserComposite, UserIdOrSelf},
+};
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
 use academy_utils::assert_matches;
 
@@ -45,7 +49,15 @@ async fn update_admin() {
         let user_update =
             MockUserUpdateService::new().with_update_admin(user_composite.user.id, admin, true);
 
+        let audit = MockAuditService::new().with_record(
+            AuditContext::default(),
+            Some(ADMIN.user.id),
+            user_composite.user.id,
+            AuditAction::UserUpdate,
+        );
+
         let sut = UserFeatureServiceImpl {
+            audit,
             auth,
             db,
             user_update,
@@ -65,6 +77,7 @@ async fn update_admin() {
                     },
                     ..Default::default()
                 },
+                &AuditContext::default(),
             )
             .await;
 
@@ -103,6 +116,7 @@ async fn demote_self() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
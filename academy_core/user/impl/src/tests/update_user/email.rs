@@ -1,5 +1,7 @@
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_user_contracts::{
+    email_confirmation::MockUserEmailConfirmationService,
     update::{MockUserUpdateService, UserUpdateEmailError},
     UserFeatureService, UserUpdateError, UserUpdateRequest, UserUpdateUserRequest,
 };
@@ -7,7 +9,10 @@ use academy_demo::{
     session::{ADMIN_1, FOO_1},
     user::{ADMIN, FOO},
 };
-use academy_models::user::{User, UserComposite, UserIdOrSelf};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    user::{User, UserComposite, UserIdOrSelf},
+};
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
 use academy_utils::assert_matches;
 
@@ -16,32 +21,43 @@ use crate::{tests::Sut, UserFeatureServiceImpl};
 #[tokio::test]
 async fn update_email_self() {
     // Arrange
-    let expected = UserComposite {
-        user: User {
-            email: Some(ADMIN.user.email.clone().unwrap()),
-            email_verified: false,
-            ..FOO.user.clone()
-        },
-        ..FOO.clone()
-    };
+    let new_email = ADMIN.user.email.clone().unwrap();
 
     let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
 
     let db = MockDatabase::build(true);
 
-    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
-
-    let user_update = MockUserUpdateService::new().with_update_email(
+    let user_repo = MockUserRepository::new()
+        .with_get_composite(FOO.user.id, Some(FOO.clone()))
+        .with_get_composite_by_email(new_email.clone(), None);
+
+    let user_email_confirmation = MockUserEmailConfirmationService::new()
+        .with_request_email_change(
+            FOO.user.id,
+            Some(
+                FOO.user
+                    .email
+                    .clone()
+                    .unwrap()
+                    .with_name(FOO.profile.display_name.clone().into_inner()),
+            ),
+            new_email
+                .clone()
+                .with_name(FOO.profile.display_name.clone().into_inner()),
+        );
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
         FOO.user.id,
-        expected.user.email.clone().unwrap(),
-        expected.user.email_verified,
-        Ok(true),
+        AuditAction::UserUpdate,
     );
 
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
-        user_update,
+        user_email_confirmation,
         user_repo,
         ..Sut::default()
     };
@@ -53,16 +69,17 @@ async fn update_email_self() {
             UserIdOrSelf::Slf,
             UserUpdateRequest {
                 user: UserUpdateUserRequest {
-                    email: expected.user.email.clone().unwrap().into(),
+                    email: new_email.into(),
                     ..Default::default()
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
     // Assert
-    assert_eq!(result.unwrap(), expected);
+    assert_eq!(result.unwrap(), *FOO);
 }
 
 #[tokio::test]
@@ -91,7 +108,15 @@ async fn update_email_admin() {
         Ok(true),
     );
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_update,
@@ -111,6 +136,7 @@ async fn update_email_admin() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -144,7 +170,15 @@ async fn update_email_admin_verified() {
         Ok(true),
     );
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_update,
@@ -165,6 +199,7 @@ async fn update_email_admin_verified() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -198,7 +233,15 @@ async fn update_email_admin_unverified() {
         Ok(true),
     );
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_update,
@@ -219,6 +262,7 @@ async fn update_email_admin_unverified() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -254,7 +298,15 @@ async fn update_set_email_verified() {
         Ok(true),
     );
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_update,
@@ -274,6 +326,7 @@ async fn update_set_email_verified() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -306,7 +359,15 @@ async fn update_set_email_unverified() {
         Ok(true),
     );
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_update,
@@ -326,6 +387,7 @@ async fn update_set_email_unverified() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -340,6 +402,45 @@ async fn update_email_conflict() {
 
     let db = MockDatabase::build(false);
 
+    let user_repo = MockUserRepository::new()
+        .with_get_composite(FOO.user.id, Some(FOO.clone()))
+        .with_get_composite_by_email(ADMIN.user.email.clone().unwrap(), Some(ADMIN.clone()));
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_user(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            UserUpdateRequest {
+                user: UserUpdateUserRequest {
+                    email: ADMIN.user.email.clone().unwrap().into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserUpdateError::EmailConflict));
+}
+
+#[tokio::test]
+async fn update_email_admin_conflict() {
+    // Arrange
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let db = MockDatabase::build(false);
+
     let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
 
     let user_update = MockUserUpdateService::new().with_update_email(
@@ -361,7 +462,7 @@ async fn update_email_conflict() {
     let result = sut
         .update_user(
             &"token".into(),
-            UserIdOrSelf::Slf,
+            FOO.user.id.into(),
             UserUpdateRequest {
                 user: UserUpdateUserRequest {
                     email: ADMIN.user.email.clone().unwrap().into(),
@@ -369,6 +470,7 @@ async fn update_email_conflict() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
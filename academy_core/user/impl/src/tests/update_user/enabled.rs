@@ -1,13 +1,20 @@
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_user_contracts::{
     update::MockUserUpdateService, UserFeatureService, UserUpdateError, UserUpdateRequest,
     UserUpdateUserRequest,
 };
 use academy_demo::{
-    session::ADMIN_1,
+    personal_access_token::ADMIN_PAT,
+    session::{ADMIN_1, FOO_1},
     user::{ADMIN, BAR, FOO},
 };
-use academy_models::user::{User, UserComposite, UserIdOrSelf};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthorizeError},
+    role::Permission,
+    user::{User, UserComposite, UserIdOrSelf, UserName},
+};
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
 use academy_utils::assert_matches;
 
@@ -36,7 +43,15 @@ async fn update_enabled() {
         let user_update =
             MockUserUpdateService::new().with_update_enabled(user_composite.user.id, enabled, true);
 
+        let audit = MockAuditService::new().with_record(
+            AuditContext::default(),
+            Some(ADMIN.user.id),
+            user_composite.user.id,
+            AuditAction::UserUpdate,
+        );
+
         let sut = UserFeatureServiceImpl {
+            audit,
             auth,
             db,
             user_update,
@@ -56,6 +71,7 @@ async fn update_enabled() {
                     },
                     ..Default::default()
                 },
+                &AuditContext::default(),
             )
             .await;
 
@@ -64,6 +80,213 @@ async fn update_enabled() {
     }
 }
 
+#[tokio::test]
+async fn update_enabled_personal_access_token() {
+    // Arrange
+    let expected = UserComposite {
+        user: User {
+            enabled: false,
+            ..FOO.user.clone()
+        },
+        ..FOO.clone()
+    };
+
+    let auth = MockAuthService::new()
+        .with_authenticate_personal_access_token(Some((ADMIN.user.clone(), ADMIN_PAT.clone())));
+
+    let db = MockDatabase::build(true);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let user_update = MockUserUpdateService::new().with_update_enabled(FOO.user.id, false, true);
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        audit,
+        auth,
+        db,
+        user_update,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_user(
+            &"token".into(),
+            UserIdOrSelf::UserId(FOO.user.id),
+            UserUpdateRequest {
+                user: UserUpdateUserRequest {
+                    enabled: false.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn update_enabled_permission() {
+    // Arrange
+    let expected = UserComposite {
+        user: User {
+            enabled: true,
+            ..BAR.user.clone()
+        },
+        ..BAR.clone()
+    };
+
+    let auth = MockAuthService::new().with_authenticate_permissions(
+        FOO.user.clone(),
+        FOO_1.clone(),
+        [Permission::DisableUsers].into(),
+    );
+
+    let db = MockDatabase::build(true);
+
+    let user_repo = MockUserRepository::new().with_get_composite(BAR.user.id, Some(BAR.clone()));
+
+    let user_update = MockUserUpdateService::new().with_update_enabled(BAR.user.id, true, true);
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        BAR.user.id,
+        AuditAction::UserUpdate,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        audit,
+        auth,
+        db,
+        user_update,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_user(
+            &"token".into(),
+            BAR.user.id.into(),
+            UserUpdateRequest {
+                user: UserUpdateUserRequest {
+                    enabled: true.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn update_enabled_permission_admin_user() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate_permissions(
+        FOO.user.clone(),
+        FOO_1.clone(),
+        [Permission::DisableUsers].into(),
+    );
+
+    let db = MockDatabase::build(false);
+
+    let user_repo =
+        MockUserRepository::new().with_get_composite(ADMIN.user.id, Some(ADMIN.clone()));
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_user(
+            &"token".into(),
+            ADMIN.user.id.into(),
+            UserUpdateRequest {
+                user: UserUpdateUserRequest {
+                    enabled: false.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserUpdateError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn update_other_fields_permission() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate_permissions(
+        FOO.user.clone(),
+        FOO_1.clone(),
+        [Permission::DisableUsers].into(),
+    );
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite(BAR.user.id, Some(BAR.clone()));
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_user(
+            &"token".into(),
+            BAR.user.id.into(),
+            UserUpdateRequest {
+                user: UserUpdateUserRequest {
+                    enabled: true.into(),
+                    name: UserName::try_new("othername").unwrap().into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserUpdateError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
 #[tokio::test]
 async fn disable_self() {
     // Arrange
@@ -94,6 +317,7 @@ async fn disable_self() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
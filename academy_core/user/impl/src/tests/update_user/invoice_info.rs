@@ -1,13 +1,18 @@
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_user_contracts::{
     update::MockUserUpdateService, UserFeatureService, UserUpdateError, UserUpdateRequest,
 };
 use academy_demo::{
-    session::BAR_1,
+    session::{BAR_1, FOO_1},
     user::{BAR, FOO},
 };
 use academy_extern_contracts::{internal::MockInternalApiService, vat::MockVatApiService};
-use academy_models::user::{UserComposite, UserIdOrSelf, UserInvoiceInfo};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    role::Permission,
+    user::{UserComposite, UserIdOrSelf, UserInvoiceInfo},
+};
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
 use academy_utils::{assert_matches, patch::Patch, Apply};
 
@@ -43,7 +48,15 @@ async fn ok() {
         expected.invoice_info.clone(),
     );
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(BAR.user.id),
+        BAR.user.id,
+        AuditAction::UserUpdate,
+    );
+
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_repo,
@@ -60,6 +73,74 @@ async fn ok() {
                 invoice_info: expected.invoice_info.clone(),
                 ..Default::default()
             },
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn ok_permission() {
+    // Arrange
+    let expected = UserComposite {
+        invoice_info: UserInvoiceInfo {
+            business: Some(false),
+            country: Some("Germany".try_into().unwrap()),
+            ..Default::default()
+        },
+        ..BAR.clone().with(|u| u.user.email_verified = true)
+    };
+
+    let auth = MockAuthService::new().with_authenticate_permissions(
+        FOO.user.clone(),
+        FOO_1.clone(),
+        [Permission::EditInvoiceInfo].into(),
+    );
+
+    let db = MockDatabase::build(true);
+
+    let user_repo = MockUserRepository::new().with_get_composite(
+        BAR.user.id,
+        Some(BAR.clone().with(|u| u.user.email_verified = true)),
+    );
+
+    let user_update = MockUserUpdateService::new().with_update_invoice_info(
+        BAR.user.id,
+        BAR.invoice_info.clone(),
+        academy_models::user::UserInvoiceInfoPatch::new()
+            .update_business(expected.invoice_info.business)
+            .update_country(expected.invoice_info.country.clone()),
+        expected.invoice_info.clone(),
+    );
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        BAR.user.id,
+        AuditAction::UserUpdate,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        audit,
+        auth,
+        db,
+        user_repo,
+        user_update,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_user(
+            &"token".into(),
+            BAR.user.id.into(),
+            UserUpdateRequest {
+                invoice_info: expected.invoice_info.clone(),
+                ..Default::default()
+            },
+            &AuditContext::default(),
         )
         .await;
 
@@ -96,7 +177,15 @@ async fn ok_release_coins() {
 
     let internal_api = MockInternalApiService::new().with_release_coins(BAR.user.id);
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(BAR.user.id),
+        BAR.user.id,
+        AuditAction::UserUpdate,
+    );
+
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_repo,
@@ -115,6 +204,7 @@ async fn ok_release_coins() {
                 invoice_info: expected.invoice_info.clone(),
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -159,6 +249,7 @@ async fn invalid_vat_id() {
                 invoice_info: expected.invoice_info.clone(),
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -1,14 +1,20 @@
 use academy_auth_contracts::MockAuthService;
 use academy_core_user_contracts::{
-    UserFeatureService, UserUpdateError, UserUpdateRequest, UserUpdateUserRequest,
+    PasswordUpdate, UserFeatureService, UserUpdateError, UserUpdateRequest, UserUpdateUserRequest,
 };
 use academy_demo::{
+    personal_access_token::FOO_PAT,
     session::{ADMIN_1, BAR_1, FOO_1},
     user::{ADMIN, BAR, FOO},
 };
-use academy_models::auth::{AuthError, AuthenticateError, AuthorizeError};
+use academy_models::{
+    audit::AuditContext,
+    auth::{AuthError, AuthenticateError, AuthorizeError},
+    role::Permission,
+    user::UserPassword,
+};
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
-use academy_utils::assert_matches;
+use academy_utils::{assert_matches, patch::PatchValue};
 
 use crate::{tests::Sut, UserFeatureServiceImpl};
 
@@ -34,7 +40,12 @@ async fn unauthenticated() {
 
     // Act
     let result = sut
-        .update_user(&"token".into(), FOO.user.id.into(), Default::default())
+        .update_user(
+            &"token".into(),
+            FOO.user.id.into(),
+            Default::default(),
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
@@ -58,7 +69,12 @@ async fn unauthorized() {
 
     // Act
     let result = sut
-        .update_user(&"token".into(), FOO.user.id.into(), Default::default())
+        .update_user(
+            &"token".into(),
+            FOO.user.id.into(),
+            Default::default(),
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
@@ -70,6 +86,50 @@ async fn unauthorized() {
     );
 }
 
+#[tokio::test]
+async fn personal_access_token_not_allowed() {
+    // Arrange
+    let auth = MockAuthService::new()
+        .with_authenticate_personal_access_token(Some((FOO.user.clone(), FOO_PAT.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_user(
+            &"token".into(),
+            FOO.user.id.into(),
+            UserUpdateRequest {
+                user: UserUpdateUserRequest {
+                    password: PatchValue::Update(PasswordUpdate::Change(
+                        UserPassword::try_new("the new password").unwrap(),
+                    )),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserUpdateError::Auth(AuthError::Authorize(
+            AuthorizeError::Session
+        )))
+    );
+}
+
 #[tokio::test]
 async fn unauthorized_admin() {
     let requests = [
@@ -80,13 +140,6 @@ async fn unauthorized_admin() {
             },
             ..Default::default()
         },
-        UserUpdateRequest {
-            user: UserUpdateUserRequest {
-                enabled: false.into(),
-                ..Default::default()
-            },
-            ..Default::default()
-        },
         UserUpdateRequest {
             user: UserUpdateUserRequest {
                 admin: true.into(),
@@ -117,7 +170,12 @@ async fn unauthorized_admin() {
 
         // Act
         let result = sut
-            .update_user(&"token".into(), FOO.user.id.into(), request)
+            .update_user(
+                &"token".into(),
+                FOO.user.id.into(),
+                request,
+                &AuditContext::default(),
+            )
             .await;
 
         // Assert
@@ -130,6 +188,47 @@ async fn unauthorized_admin() {
     }
 }
 
+#[tokio::test]
+async fn unauthorized_enabled() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_user(
+            &"token".into(),
+            FOO.user.id.into(),
+            UserUpdateRequest {
+                user: UserUpdateUserRequest {
+                    enabled: false.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserUpdateError::Auth(AuthError::Authorize(
+            AuthorizeError::Permission(Permission::DisableUsers)
+        )))
+    );
+}
+
 #[tokio::test]
 async fn not_found() {
     // Arrange
@@ -149,7 +248,12 @@ async fn not_found() {
 
     // Act
     let result = sut
-        .update_user(&"token".into(), FOO.user.id.into(), Default::default())
+        .update_user(
+            &"token".into(),
+            FOO.user.id.into(),
+            Default::default(),
+            &AuditContext::default(),
+        )
         .await;
 
     // Assert
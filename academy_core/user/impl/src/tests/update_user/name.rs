@@ -1,7 +1,9 @@
 use std::time::Duration;
 
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_user_contracts::{
+    name_policy::{MockUserNamePolicyService, UserNamePolicyViolation},
     update::{MockUserUpdateService, UserUpdateNameError, UserUpdateNameRateLimitPolicy},
     UserFeatureService, UserUpdateError, UserUpdateRequest, UserUpdateUserRequest,
 };
@@ -9,7 +11,10 @@ use academy_demo::{
     session::{ADMIN_1, FOO_1},
     user::{ADMIN, BAR, FOO},
 };
-use academy_models::user::{User, UserComposite, UserIdOrSelf};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    user::{User, UserComposite, UserIdOrSelf},
+};
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
 use academy_utils::assert_matches;
 
@@ -33,6 +38,12 @@ async fn update_name_self() {
 
     let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
 
+    let user_name_policy = MockUserNamePolicyService::new().with_check(
+        BAR.user.name.clone(),
+        Some(FOO.user.id),
+        Ok(()),
+    );
+
     let user_update = MockUserUpdateService::new().with_update_name(
         FOO.user.clone(),
         BAR.user.name.clone(),
@@ -40,11 +51,20 @@ async fn update_name_self() {
         Ok(expected.user.clone()),
     );
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_update,
         user_repo,
+        user_name_policy,
         ..Sut::default()
     };
 
@@ -60,6 +80,7 @@ async fn update_name_self() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -93,7 +114,15 @@ async fn update_name_admin_no_rate_limit() {
         Ok(expected.user.clone()),
     );
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_update,
@@ -113,6 +142,7 @@ async fn update_name_admin_no_rate_limit() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -129,6 +159,12 @@ async fn update_name_self_rate_limit() {
 
     let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
 
+    let user_name_policy = MockUserNamePolicyService::new().with_check(
+        BAR.user.name.clone(),
+        Some(FOO.user.id),
+        Ok(()),
+    );
+
     let expected = FOO.user.last_name_change.unwrap() + Duration::from_secs(17);
 
     let user_update = MockUserUpdateService::new().with_update_name(
@@ -143,6 +179,7 @@ async fn update_name_self_rate_limit() {
         db,
         user_update,
         user_repo,
+        user_name_policy,
         ..Sut::default()
     };
 
@@ -158,6 +195,7 @@ async fn update_name_self_rate_limit() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -174,6 +212,12 @@ async fn update_name_conflict() {
 
     let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
 
+    let user_name_policy = MockUserNamePolicyService::new().with_check(
+        BAR.user.name.clone(),
+        Some(FOO.user.id),
+        Ok(()),
+    );
+
     let user_update = MockUserUpdateService::new().with_update_name(
         FOO.user.clone(),
         BAR.user.name.clone(),
@@ -186,6 +230,7 @@ async fn update_name_conflict() {
         db,
         user_update,
         user_repo,
+        user_name_policy,
         ..Sut::default()
     };
 
@@ -201,9 +246,58 @@ async fn update_name_conflict() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
     // Assert
     assert_matches!(result, Err(UserUpdateError::NameConflict));
 }
+
+#[tokio::test]
+async fn update_name_policy_violation() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let user_name_policy = MockUserNamePolicyService::new().with_check(
+        BAR.user.name.clone(),
+        Some(FOO.user.id),
+        Err(UserNamePolicyViolation::Blocked),
+    );
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        db,
+        user_repo,
+        user_name_policy,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_user(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            UserUpdateRequest {
+                user: UserUpdateUserRequest {
+                    name: BAR.user.name.clone().into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserUpdateError::NamePolicy(
+            UserNamePolicyViolation::Blocked
+        ))
+    );
+}
@@ -1,4 +1,5 @@
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_user_contracts::{
     email_confirmation::MockUserEmailConfirmationService, UserFeatureService, UserUpdateError,
     UserUpdateRequest, UserUpdateUserRequest,
@@ -7,7 +8,10 @@ use academy_demo::{
     session::{ADMIN_1, BAR_1, FOO_1},
     user::{ADMIN, BAR, FOO},
 };
-use academy_models::user::{User, UserComposite, UserIdOrSelf, UserPatch};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    user::{User, UserComposite, UserIdOrSelf, UserPatch},
+};
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
 use academy_utils::assert_matches;
 
@@ -60,6 +64,7 @@ async fn enable_self() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -92,7 +97,15 @@ async fn enable_admin() {
             Ok(true),
         );
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(ADMIN.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_repo,
@@ -111,6 +124,7 @@ async fn enable_admin() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -141,7 +155,15 @@ async fn disable_self() {
             Ok(true),
         );
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_repo,
@@ -160,6 +182,7 @@ async fn disable_self() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -195,6 +218,7 @@ async fn enable_self_no_email() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
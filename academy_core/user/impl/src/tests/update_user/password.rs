@@ -1,10 +1,16 @@
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_user_contracts::{
-    update::MockUserUpdateService, PasswordUpdate, UserFeatureService, UserUpdateError,
-    UserUpdateRequest, UserUpdateUserRequest,
+    name_policy::MockUserNamePolicyService,
+    password_policy::{MockUserPasswordPolicyService, UserPasswordPolicyViolation},
+    update::MockUserUpdateService,
+    PasswordUpdate, UserFeatureService, UserUpdateError, UserUpdateRequest, UserUpdateUserRequest,
 };
 use academy_demo::{session::FOO_1, user::FOO};
-use academy_models::user::{UserIdOrSelf, UserPassword};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    user::{UserIdOrSelf, UserName, UserPassword},
+};
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
 use academy_utils::{assert_matches, patch::PatchValue, Apply};
 
@@ -21,13 +27,29 @@ async fn update_password() {
 
     let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
 
+    let user_password_policy = MockUserPasswordPolicyService::new().with_check(
+        new_password.clone(),
+        FOO.user.name.clone(),
+        FOO.user.email.clone(),
+        Ok(()),
+    );
+
     let user_update =
         MockUserUpdateService::new().with_update_password(FOO.user.id, new_password.clone());
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_update,
+        user_password_policy,
         user_repo,
         ..Sut::default()
     };
@@ -44,6 +66,7 @@ async fn update_password() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -51,6 +74,63 @@ async fn update_password() {
     assert_eq!(result.unwrap(), *FOO);
 }
 
+#[tokio::test]
+async fn update_password_and_name_policy_violation() {
+    // Arrange
+    let new_name = UserName::try_new("the-new-name").unwrap();
+    let new_password = UserPassword::try_new("The-New-Name").unwrap();
+
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let db = MockDatabase::build(false);
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let user_name_policy =
+        MockUserNamePolicyService::new().with_check(new_name.clone(), Some(FOO.user.id), Ok(()));
+
+    let user_password_policy = MockUserPasswordPolicyService::new().with_check(
+        new_password.clone(),
+        new_name.clone(),
+        FOO.user.email.clone(),
+        Err(UserPasswordPolicyViolation::MatchesUserData),
+    );
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        db,
+        user_name_policy,
+        user_password_policy,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .update_user(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            UserUpdateRequest {
+                user: UserUpdateUserRequest {
+                    name: PatchValue::Update(new_name),
+                    password: PatchValue::Update(PasswordUpdate::Change(new_password)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserUpdateError::PasswordPolicy(
+            UserPasswordPolicyViolation::MatchesUserData
+        ))
+    );
+}
+
 #[tokio::test]
 async fn remove_password_oauth() {
     // Arrange
@@ -62,7 +142,15 @@ async fn remove_password_oauth() {
         .with_get_composite(FOO.user.id, Some(FOO.clone()))
         .with_remove_password_hash(FOO.user.id, true);
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_repo,
@@ -81,6 +169,7 @@ async fn remove_password_oauth() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -122,6 +211,7 @@ async fn remove_password_no_oauth() {
                 },
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -1,10 +1,14 @@
 use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
 use academy_core_user_contracts::{UserFeatureService, UserUpdateRequest};
 use academy_demo::{
     session::FOO_1,
     user::{BAR, FOO},
 };
-use academy_models::user::{UserComposite, UserIdOrSelf};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    user::{UserComposite, UserIdOrSelf},
+};
 use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
 use academy_utils::patch::Patch;
 
@@ -26,7 +30,15 @@ async fn update_profile() {
         .with_get_composite(FOO.user.id, Some(FOO.clone()))
         .with_update_profile(FOO.user.id, expected.profile.clone().into_patch(), true);
 
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
     let sut = UserFeatureServiceImpl {
+        audit,
         auth,
         db,
         user_repo,
@@ -42,6 +54,7 @@ async fn update_profile() {
                 profile: expected.profile.clone().into_patch(),
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -74,6 +87,7 @@ async fn update_profile_no_changes() {
                 profile: FOO.profile.clone().into_patch(),
                 ..Default::default()
             },
+            &AuditContext::default(),
         )
         .await;
 
@@ -0,0 +1,188 @@
+use academy_auth_contracts::MockAuthService;
+use academy_core_audit_contracts::audit::MockAuditService;
+use academy_core_user_contracts::{
+    avatar::{MockUserAvatarService, UserAvatarUploadError},
+    UserFeatureService, UserUploadAvatarError,
+};
+use academy_demo::{
+    session::{ADMIN_1, BAR_1, FOO_1},
+    user::{ADMIN, BAR, FOO},
+};
+use academy_models::{
+    audit::{AuditAction, AuditContext},
+    auth::{AuthError, AuthorizeError},
+    url::Url,
+    user::{UserComposite, UserIdOrSelf, UserProfile, UserProfilePatch},
+};
+use academy_persistence_contracts::{user::MockUserRepository, MockDatabase};
+use academy_utils::assert_matches;
+
+use crate::{tests::Sut, UserFeatureServiceImpl};
+
+#[tokio::test]
+async fn ok() {
+    // Arrange
+    let avatar_url: Url = "https://bootstrap.academy/storage/avatars/foo/256.png?v=42"
+        .parse()
+        .unwrap();
+    let expected = UserComposite {
+        profile: UserProfile {
+            avatar_url: Some(avatar_url.clone()),
+            ..FOO.profile.clone()
+        },
+        ..FOO.clone()
+    };
+
+    let db = MockDatabase::build(true);
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let user_avatar = MockUserAvatarService::new().with_upload(
+        FOO.user.id,
+        b"image".into(),
+        Ok(avatar_url.clone()),
+    );
+
+    let user_repo = MockUserRepository::new()
+        .with_get_composite(FOO.user.id, Some(FOO.clone()))
+        .with_update_profile(
+            FOO.user.id,
+            UserProfilePatch::new().update_avatar_url(Some(avatar_url)),
+            true,
+        );
+
+    let audit = MockAuditService::new().with_record(
+        AuditContext::default(),
+        Some(FOO.user.id),
+        FOO.user.id,
+        AuditAction::UserUpdate,
+    );
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_avatar,
+        user_repo,
+        audit,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .upload_user_avatar(
+            &"token".into(),
+            UserIdOrSelf::Slf,
+            b"image".into(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    // Arrange
+    let auth = MockAuthService::new().with_authenticate(Some((BAR.user.clone(), BAR_1.clone())));
+
+    let sut = UserFeatureServiceImpl {
+        auth,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .upload_user_avatar(
+            &"token".into(),
+            FOO.user.id.into(),
+            b"image".into(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(
+        result,
+        Err(UserUploadAvatarError::Auth(AuthError::Authorize(
+            AuthorizeError::Admin
+        )))
+    );
+}
+
+#[tokio::test]
+async fn not_found() {
+    // Arrange
+    let db = MockDatabase::build(false);
+    let auth =
+        MockAuthService::new().with_authenticate(Some((ADMIN.user.clone(), ADMIN_1.clone())));
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, None);
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    let result = sut
+        .upload_user_avatar(
+            &"token".into(),
+            FOO.user.id.into(),
+            b"image".into(),
+            &AuditContext::default(),
+        )
+        .await;
+
+    // Assert
+    assert_matches!(result, Err(UserUploadAvatarError::NotFound));
+}
+
+#[tokio::test]
+async fn too_large() {
+    let result = upload_with_avatar_error(UserAvatarUploadError::TooLarge).await;
+    assert_matches!(result, Err(UserUploadAvatarError::TooLarge));
+}
+
+#[tokio::test]
+async fn unsupported_format() {
+    let result = upload_with_avatar_error(UserAvatarUploadError::UnsupportedFormat).await;
+    assert_matches!(result, Err(UserUploadAvatarError::UnsupportedFormat));
+}
+
+#[tokio::test]
+async fn invalid_image() {
+    let result = upload_with_avatar_error(UserAvatarUploadError::InvalidImage).await;
+    assert_matches!(result, Err(UserUploadAvatarError::InvalidImage));
+}
+
+async fn upload_with_avatar_error(
+    err: UserAvatarUploadError,
+) -> Result<UserComposite, UserUploadAvatarError> {
+    // Arrange
+    let db = MockDatabase::build(false);
+    let auth = MockAuthService::new().with_authenticate(Some((FOO.user.clone(), FOO_1.clone())));
+
+    let user_avatar =
+        MockUserAvatarService::new().with_upload(FOO.user.id, b"image".into(), Err(err));
+
+    let user_repo = MockUserRepository::new().with_get_composite(FOO.user.id, Some(FOO.clone()));
+
+    let sut = UserFeatureServiceImpl {
+        db,
+        auth,
+        user_avatar,
+        user_repo,
+        ..Sut::default()
+    };
+
+    // Act
+    sut.upload_user_avatar(
+        &"token".into(),
+        UserIdOrSelf::Slf,
+        b"image".into(),
+        &AuditContext::default(),
+    )
+    .await
+}
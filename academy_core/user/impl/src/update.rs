@@ -1,14 +1,15 @@
 use academy_auth_contracts::AuthService;
 use academy_core_session_contracts::session::SessionService;
 use academy_core_user_contracts::update::{
-    UserUpdateEmailError, UserUpdateNameError, UserUpdateNameRateLimitPolicy, UserUpdateService,
+    UserUpdateEmailError, UserUpdateNameError, UserUpdateNameRateLimitPolicy,
+    UserUpdatePrimaryEmailError, UserUpdateService,
 };
 use academy_di::Build;
 use academy_models::{
     email_address::EmailAddress,
     user::{
-        User, UserId, UserInvoiceInfo, UserInvoiceInfoPatch, UserName, UserPassword, UserPatch,
-        UserPatchRef,
+        User, UserEmail, UserId, UserInvoiceInfo, UserInvoiceInfoPatch, UserName,
+        UserNameHistoryEntry, UserPassword, UserPatch, UserPatchRef,
     },
 };
 use academy_persistence_contracts::user::{UserRepoError, UserRepository};
@@ -50,9 +51,9 @@ where
         name: UserName,
         rate_limit_policy: UserUpdateNameRateLimitPolicy,
     ) -> Result<User, UserUpdateNameError> {
+        let now = self.time.now();
         let last_name_change = match rate_limit_policy {
             UserUpdateNameRateLimitPolicy::Enforce => {
-                let now = self.time.now();
                 if let Some(last_name_change) = user.last_name_change {
                     let rate_limit_until = last_name_change + self.config.name_change_rate_limit;
                     if now < rate_limit_until {
@@ -75,13 +76,26 @@ where
         self.user_repo
             .update(txn, user.id, patch.as_ref())
             .await
-            .map(|_| user.update(patch))
             .map_err(|err| match err {
                 UserRepoError::NameConflict => UserUpdateNameError::Conflict,
                 err => anyhow!(err)
                     .context("Failed to update user in database")
                     .into(),
-            })
+            })?;
+
+        self.user_repo
+            .add_name_history_entry(
+                txn,
+                &UserNameHistoryEntry {
+                    user_id: user.id,
+                    name: user.name.clone(),
+                    changed_at: now,
+                },
+            )
+            .await
+            .context("Failed to add name history entry to database")?;
+
+        Ok(user.update(patch))
     }
 
     #[trace_instrument(skip(self, txn))]
@@ -121,6 +135,63 @@ where
         Ok(result)
     }
 
+    #[trace_instrument(skip(self, txn))]
+    async fn update_primary_email(
+        &self,
+        txn: &mut Txn,
+        user: User,
+        email: EmailAddress,
+    ) -> Result<User, UserUpdatePrimaryEmailError> {
+        let email = self
+            .user_repo
+            .list_emails(txn, user.id)
+            .await
+            .context("Failed to get email addresses from database")?
+            .into_iter()
+            .find(|x| x.email.as_str().to_lowercase() == email.as_str().to_lowercase())
+            .ok_or(UserUpdatePrimaryEmailError::NotFound)?
+            .email;
+
+        // the additional email address has to be removed first, because email
+        // addresses must be unique across primary and additional addresses
+        self.user_repo
+            .remove_email(txn, user.id, &email)
+            .await
+            .context("Failed to remove email address from database")?;
+
+        let patch = UserPatch::new()
+            .update_email(Some(email))
+            .update_email_verified(true);
+
+        self.user_repo
+            .update(txn, user.id, patch.as_ref())
+            .await
+            .context("Failed to update user in database")?;
+
+        if let Some(old_email) = user.email.clone().filter(|_| user.email_verified) {
+            self.user_repo
+                .add_email(
+                    txn,
+                    &UserEmail {
+                        user_id: user.id,
+                        email: old_email,
+                        created_at: self.time.now(),
+                    },
+                )
+                .await
+                .context("Failed to save previous primary email address in database")?;
+        }
+
+        // access tokens contain the `email_verified` field, so we need to invalidate
+        // them when changing this value
+        self.auth
+            .invalidate_access_tokens(txn, user.id)
+            .await
+            .context("Failed to invalidate access tokens")?;
+
+        Ok(user.update(patch))
+    }
+
     #[trace_instrument(skip(self, txn))]
     async fn update_password(
         &self,
@@ -209,7 +280,7 @@ mod tests {
 
     use academy_auth_contracts::MockAuthService;
     use academy_core_session_contracts::session::MockSessionService;
-    use academy_demo::user::{ADMIN, BAR, FOO};
+    use academy_demo::user::{ADMIN, BAR, FOO, FOO_EMAIL_1};
     use academy_models::user::UserPatch;
     use academy_persistence_contracts::user::MockUserRepository;
     use academy_shared_contracts::{password::MockPasswordService, time::MockTimeService};
@@ -242,13 +313,19 @@ mod tests {
 
         let time = MockTimeService::new().with_now(now);
 
-        let user_repo = MockUserRepository::new().with_update(
-            FOO.user.id,
-            UserPatch::new()
-                .update_name(BAR.user.name.clone())
-                .update_last_name_change(Some(now)),
-            Ok(true),
-        );
+        let user_repo = MockUserRepository::new()
+            .with_update(
+                FOO.user.id,
+                UserPatch::new()
+                    .update_name(BAR.user.name.clone())
+                    .update_last_name_change(Some(now)),
+                Ok(true),
+            )
+            .with_add_name_history_entry(UserNameHistoryEntry {
+                user_id: FOO.user.id,
+                name: FOO.user.name.clone(),
+                changed_at: now,
+            });
 
         let sut = UserUpdateServiceImpl {
             time,
@@ -273,18 +350,26 @@ mod tests {
     #[tokio::test]
     async fn update_name_ok_bypass_rate_limit() {
         // Arrange
+        let now = FOO.user.last_name_change.unwrap() + Duration::from_secs(2);
+
         let expected = User {
             name: BAR.user.name.clone(),
             ..FOO.user.clone()
         };
 
-        let time = MockTimeService::new();
+        let time = MockTimeService::new().with_now(now);
 
-        let user_repo = MockUserRepository::new().with_update(
-            FOO.user.id,
-            UserPatch::new().update_name(BAR.user.name.clone()),
-            Ok(true),
-        );
+        let user_repo = MockUserRepository::new()
+            .with_update(
+                FOO.user.id,
+                UserPatch::new().update_name(BAR.user.name.clone()),
+                Ok(true),
+            )
+            .with_add_name_history_entry(UserNameHistoryEntry {
+                user_id: FOO.user.id,
+                name: FOO.user.name.clone(),
+                changed_at: now,
+            });
 
         let sut = UserUpdateServiceImpl {
             time,
@@ -343,7 +428,7 @@ mod tests {
     #[tokio::test]
     async fn update_name_conflict() {
         // Arrange
-        let time = MockTimeService::new();
+        let time = MockTimeService::new().with_now(FOO.user.last_name_change.unwrap());
 
         let user_repo = MockUserRepository::new().with_update(
             FOO.user.id,
@@ -439,6 +524,122 @@ mod tests {
         assert_matches!(result, Err(UserUpdateEmailError::Conflict));
     }
 
+    #[tokio::test]
+    async fn update_primary_email_ok() {
+        // Arrange
+        let now = FOO_EMAIL_1.created_at + Duration::from_secs(60);
+
+        let expected = User {
+            email: Some(FOO_EMAIL_1.email.clone()),
+            email_verified: true,
+            ..FOO.user.clone()
+        };
+
+        let auth = MockAuthService::new().with_invalidate_access_tokens(FOO.user.id);
+
+        let time = MockTimeService::new().with_now(now);
+
+        let user_repo = MockUserRepository::new()
+            .with_list_emails(FOO.user.id, vec![FOO_EMAIL_1.clone()])
+            .with_remove_email(FOO.user.id, FOO_EMAIL_1.email.clone(), true)
+            .with_update(
+                FOO.user.id,
+                UserPatch::new()
+                    .update_email(Some(FOO_EMAIL_1.email.clone()))
+                    .update_email_verified(true),
+                Ok(true),
+            )
+            .with_add_email(
+                UserEmail {
+                    user_id: FOO.user.id,
+                    email: FOO.user.email.clone().unwrap(),
+                    created_at: now,
+                },
+                Ok(()),
+            );
+
+        let sut = UserUpdateServiceImpl {
+            auth,
+            time,
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .update_primary_email(
+                &mut (),
+                FOO.user.clone(),
+                FOO_EMAIL_1.email.as_str().to_uppercase().parse().unwrap(),
+            )
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn update_primary_email_unverified() {
+        // Arrange
+        let user = User {
+            email_verified: false,
+            ..FOO.user.clone()
+        };
+
+        let expected = User {
+            email: Some(FOO_EMAIL_1.email.clone()),
+            email_verified: true,
+            ..FOO.user.clone()
+        };
+
+        let auth = MockAuthService::new().with_invalidate_access_tokens(FOO.user.id);
+
+        let user_repo = MockUserRepository::new()
+            .with_list_emails(FOO.user.id, vec![FOO_EMAIL_1.clone()])
+            .with_remove_email(FOO.user.id, FOO_EMAIL_1.email.clone(), true)
+            .with_update(
+                FOO.user.id,
+                UserPatch::new()
+                    .update_email(Some(FOO_EMAIL_1.email.clone()))
+                    .update_email_verified(true),
+                Ok(true),
+            );
+
+        let sut = UserUpdateServiceImpl {
+            auth,
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .update_primary_email(&mut (), user, FOO_EMAIL_1.email.clone())
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn update_primary_email_not_found() {
+        // Arrange
+        let user_repo =
+            MockUserRepository::new().with_list_emails(FOO.user.id, vec![FOO_EMAIL_1.clone()]);
+
+        let sut = UserUpdateServiceImpl {
+            user_repo,
+            ..Sut::default()
+        };
+
+        // Act
+        let result = sut
+            .update_primary_email(&mut (), FOO.user.clone(), ADMIN.user.email.clone().unwrap())
+            .await;
+
+        // Assert
+        assert_matches!(result, Err(UserUpdatePrimaryEmailError::NotFound));
+    }
+
     #[tokio::test]
     async fn update_password() {
         // Arrange
@@ -84,12 +84,14 @@ where
             enabled,
             admin,
             newsletter: false,
+            deleted_at: None,
         };
 
         let profile = UserProfile {
             display_name,
             bio: Default::default(),
             tags: Default::default(),
+            avatar_url: None,
         };
 
         let details = UserDetails {
@@ -469,11 +471,13 @@ mod tests {
                 enabled: true,
                 admin: false,
                 newsletter: false,
+                deleted_at: None,
             },
             profile: UserProfile {
                 display_name: FOO.profile.display_name.clone(),
                 bio: Default::default(),
                 tags: Default::default(),
+                avatar_url: None,
             },
             details: UserDetails {
                 mfa_enabled: false,
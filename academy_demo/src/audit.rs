@@ -0,0 +1,74 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    sync::LazyLock,
+    time::Duration,
+};
+
+use academy_models::audit::{AuditAction, AuditEvent};
+use academy_persistence_contracts::audit::AuditEventRepository;
+use uuid::uuid;
+
+use crate::user::{ADMIN, ADMIN2, BAR, FOO};
+
+pub static ALL_AUDIT_EVENTS: LazyLock<Vec<&AuditEvent>> = LazyLock::new(|| {
+    vec![
+        &ADMIN2_MFA_ENABLE,
+        &FOO_PASSWORD_RESET,
+        &ADMIN_UPDATE_FOO,
+        &ADMIN_IMPERSONATE_BAR,
+    ]
+});
+
+pub static ADMIN2_MFA_ENABLE: LazyLock<AuditEvent> = LazyLock::new(|| AuditEvent {
+    id: uuid!("9d3f6a1b-2c8e-4f7a-b5d1-6e4c2a8f9b3d").into(),
+    actor_id: Some(ADMIN2.user.id),
+    user_id: ADMIN2.user.id,
+    action: AuditAction::MfaEnable,
+    ip: Some(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1))),
+    user_agent: Some("Mozilla/5.0 (X11; Linux x86_64; rv:131.0) Firefox/131.0".into()),
+    request_id: Some(uuid!("4c8a2e6f-1b3d-4a9e-8f5c-7d2b1e9a6c4f")),
+    created_at: ADMIN2.user.created_at + Duration::from_secs(3600),
+});
+
+pub static FOO_PASSWORD_RESET: LazyLock<AuditEvent> = LazyLock::new(|| AuditEvent {
+    id: uuid!("1e7b4d9a-6f2c-4b8e-a3d5-9c1f7e2b4a6d").into(),
+    actor_id: Some(FOO.user.id),
+    user_id: FOO.user.id,
+    action: AuditAction::PasswordReset,
+    ip: Some(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1))),
+    user_agent: None,
+    request_id: Some(uuid!("b7e1d4a9-3c6f-4e2b-9a8d-5f1c3e7b2d9a")),
+    created_at: FOO.user.created_at + Duration::from_secs(7200),
+});
+
+pub static ADMIN_UPDATE_FOO: LazyLock<AuditEvent> = LazyLock::new(|| AuditEvent {
+    id: uuid!("6a2f8c4e-9b1d-4e7a-8c3f-2d5b9e1a7f4c").into(),
+    actor_id: Some(ADMIN.user.id),
+    user_id: FOO.user.id,
+    action: AuditAction::UserUpdate,
+    ip: Some(IpAddr::V4(Ipv4Addr::new(198, 51, 100, 7))),
+    user_agent: Some("curl/8.10.1".into()),
+    request_id: Some(uuid!("e3a9c7f1-5d2b-4f8e-b6a4-1c9d7e3f5b2a")),
+    created_at: FOO.user.created_at + Duration::from_secs(86400),
+});
+
+pub static ADMIN_IMPERSONATE_BAR: LazyLock<AuditEvent> = LazyLock::new(|| AuditEvent {
+    id: uuid!("c5d1a7e3-4f9b-4c2e-9d6a-8b3e5f1c7a2d").into(),
+    actor_id: Some(ADMIN.user.id),
+    user_id: BAR.user.id,
+    action: AuditAction::UserImpersonate,
+    ip: Some(IpAddr::V4(Ipv4Addr::new(198, 51, 100, 7))),
+    user_agent: Some("curl/8.10.1".into()),
+    request_id: Some(uuid!("7f4b2d8e-1a6c-4e9b-a5f3-3d8c1b7e4a9f")),
+    created_at: BAR.user.created_at + Duration::from_secs(3600),
+});
+
+pub async fn create<Txn: Send + Sync + 'static>(
+    txn: &mut Txn,
+    repo: impl AuditEventRepository<Txn>,
+) -> anyhow::Result<()> {
+    for &event in &*ALL_AUDIT_EVENTS {
+        repo.create(txn, event).await?;
+    }
+    Ok(())
+}
@@ -2,13 +2,19 @@ use std::sync::LazyLock;
 
 use academy_models::{Sha256Hash, VerificationCode};
 use academy_persistence_contracts::{
-    mfa::MfaRepository, oauth2::OAuth2Repository, session::SessionRepository, user::UserRepository,
+    audit::AuditEventRepository, mfa::MfaRepository, oauth2::OAuth2Repository,
+    oauth2_server::OAuth2ServerRepository, personal_access_token::PersonalAccessTokenRepository,
+    role::RoleRepository, session::SessionRepository, user::UserRepository,
 };
 use anyhow::Context;
 use uuid::{uuid, Uuid};
 
+pub mod audit;
 pub mod mfa;
 pub mod oauth2;
+pub mod oauth2_server;
+pub mod personal_access_token;
+pub mod role;
 pub mod session;
 pub mod user;
 
@@ -32,12 +38,20 @@ pub static VERIFICATION_CODE_1: LazyLock<VerificationCode> =
 pub static VERIFICATION_CODE_2: LazyLock<VerificationCode> =
     LazyLock::new(|| "HFWG-6TTY-0UY4-73YZ".try_into().unwrap());
 
+#[expect(
+    clippy::too_many_arguments,
+    reason = "demo data is created through one repository per feature"
+)]
 pub async fn create<Txn: Send + Sync + 'static>(
     txn: &mut Txn,
     user: impl UserRepository<Txn>,
     session: impl SessionRepository<Txn>,
     mfa: impl MfaRepository<Txn>,
     oauth2: impl OAuth2Repository<Txn>,
+    oauth2_server: impl OAuth2ServerRepository<Txn>,
+    personal_access_token: impl PersonalAccessTokenRepository<Txn>,
+    role: impl RoleRepository<Txn>,
+    audit: impl AuditEventRepository<Txn>,
 ) -> anyhow::Result<()> {
     macro_rules! create {
         ($($ident:ident),* $(,)?) => { $(
@@ -47,7 +61,16 @@ pub async fn create<Txn: Send + Sync + 'static>(
         )*};
     }
 
-    create!(user, session, mfa, oauth2);
+    create!(
+        user,
+        session,
+        mfa,
+        oauth2,
+        oauth2_server,
+        personal_access_token,
+        role,
+        audit,
+    );
 
     Ok(())
 }
@@ -1,6 +1,9 @@
 use std::{collections::HashMap, sync::LazyLock, time::Duration};
 
-use academy_models::mfa::{TotpDevice, TotpDeviceId, TotpSecret};
+use academy_models::{
+    encryption::EncryptedData,
+    mfa::{TotpDevice, TotpDeviceId, TotpSecret, WebauthnDevice},
+};
 use academy_persistence_contracts::mfa::MfaRepository;
 use uuid::uuid;
 
@@ -12,6 +15,7 @@ pub static ALL_TOTP_DEVICES: LazyLock<Vec<&TotpDevice>> =
 pub static ADMIN2_TOTP_1: LazyLock<TotpDevice> = LazyLock::new(|| TotpDevice {
     id: uuid!("75a9def2-688f-4211-9fc7-750eb89600cd").into(),
     user_id: ADMIN2.user.id,
+    name: "Phone".try_into().unwrap(),
     enabled: true,
     created_at: ADMIN2.user.created_at + Duration::from_secs(600),
 });
@@ -19,6 +23,7 @@ pub static ADMIN2_TOTP_1: LazyLock<TotpDevice> = LazyLock::new(|| TotpDevice {
 pub static FOO_TOTP_1: LazyLock<TotpDevice> = LazyLock::new(|| TotpDevice {
     id: uuid!("24532ed6-9126-4b8a-b0b3-c6979ff0549e").into(),
     user_id: FOO.user.id,
+    name: "Authenticator".try_into().unwrap(),
     enabled: false,
     created_at: FOO.user.created_at + Duration::from_secs(2 * 24 * 3600),
 });
@@ -32,6 +37,25 @@ pub static TOTP_SECRETS: LazyLock<HashMap<TotpDeviceId, TotpSecret>> = LazyLock:
     .into()
 });
 
+pub static ALL_WEBAUTHN_DEVICES: LazyLock<Vec<&WebauthnDevice>> =
+    LazyLock::new(|| vec![&ADMIN2_WEBAUTHN_1]);
+
+pub static ADMIN2_WEBAUTHN_1: LazyLock<WebauthnDevice> = LazyLock::new(|| WebauthnDevice {
+    id: uuid!("0a7e3d7b-57e4-4b9e-8a3f-5d2c0c6b9f41").into(),
+    user_id: ADMIN2.user.id,
+    name: "YubiKey".try_into().unwrap(),
+    credential_id: hex::decode("8d2b4d7e1f9c4a0b").unwrap().try_into().unwrap(),
+    // Ed25519 public key in COSE format
+    public_key: hex::decode(
+        "a4010103272006215820d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+    )
+    .unwrap()
+    .into(),
+    sign_count: 3,
+    created_at: ADMIN2.user.created_at + Duration::from_secs(1200),
+    last_used_at: Some(ADMIN2.user.created_at + Duration::from_secs(3600)),
+});
+
 fn decode_secret(secret: &str) -> TotpSecret {
     base32::decode(base32::Alphabet::Rfc4648 { padding: false }, secret)
         .unwrap()
@@ -44,8 +68,12 @@ pub async fn create<Txn: Send + Sync + 'static>(
     repo: impl MfaRepository<Txn>,
 ) -> anyhow::Result<()> {
     for &totp_device in &*ALL_TOTP_DEVICES {
-        repo.create_totp_device(txn, totp_device, &TOTP_SECRETS[&totp_device.id])
+        let secret = TOTP_SECRETS[&totp_device.id].clone().into_inner();
+        repo.create_totp_device(txn, totp_device, &EncryptedData::unencrypted(secret))
             .await?;
     }
+    for &webauthn_device in &*ALL_WEBAUTHN_DEVICES {
+        repo.create_webauthn_device(txn, webauthn_device).await?;
+    }
     Ok(())
 }
@@ -1,6 +1,8 @@
 use std::{sync::LazyLock, time::Duration};
 
-use academy_models::oauth2::{OAuth2Link, OAuth2Provider, OAuth2ProviderId, OAuth2UserInfo};
+use academy_models::oauth2::{
+    OAuth2Link, OAuth2Provider, OAuth2ProviderId, OAuth2ProviderKind, OAuth2UserInfo,
+};
 use academy_persistence_contracts::oauth2::OAuth2Repository;
 use uuid::uuid;
 
@@ -13,11 +15,13 @@ pub static TEST_OAUTH2_PROVIDER: LazyLock<OAuth2Provider> = LazyLock::new(|| OAu
     name: "Test Provider".into(),
     client_id: "test-id".into(),
     client_secret: Some("test-secret".into()),
-    auth_url: "http://test/auth".parse().unwrap(),
-    token_url: "http://test/token".parse().unwrap(),
-    userinfo_url: "http://test/user".parse().unwrap(),
-    userinfo_id_key: "id".into(),
-    userinfo_name_key: "name".into(),
+    kind: OAuth2ProviderKind::OAuth2 {
+        auth_url: "http://test/auth".parse().unwrap(),
+        token_url: "http://test/token".parse().unwrap(),
+        userinfo_url: "http://test/user".parse().unwrap(),
+        userinfo_id_key: "id".into(),
+        userinfo_name_key: "name".into(),
+    },
     scopes: ["foo", "bar", "baz"].map(Into::into).into(),
 });
 
@@ -32,6 +36,8 @@ pub static FOO_OAUTH2_LINK_1: LazyLock<OAuth2Link> = LazyLock::new(|| OAuth2Link
     remote_user: OAuth2UserInfo {
         id: "28374".try_into().unwrap(),
         name: "Foo42".try_into().unwrap(),
+        email: None,
+        email_verified: false,
     },
 });
 
@@ -0,0 +1,45 @@
+use std::{sync::LazyLock, time::Duration};
+
+use academy_models::oauth2_server::{OAuth2Client, OAuth2ClientSecretHash};
+use academy_persistence_contracts::oauth2_server::OAuth2ServerRepository;
+use uuid::uuid;
+
+use crate::{user::ADMIN, SHA256HASH1};
+
+pub static ALL_OAUTH2_CLIENTS: LazyLock<Vec<&OAuth2Client>> =
+    LazyLock::new(|| vec![&TEST_OAUTH2_CLIENT, &PUBLIC_OAUTH2_CLIENT]);
+
+pub static TEST_OAUTH2_CLIENT: LazyLock<OAuth2Client> = LazyLock::new(|| OAuth2Client {
+    id: uuid!("a4c0a5e2-3b5f-4b43-9a4e-6f2f0f8b6f3a").into(),
+    name: "Test Client".try_into().unwrap(),
+    redirect_uris: vec!["https://client.example.com/callback".parse().unwrap()],
+    confidential: true,
+    created_at: ADMIN.user.created_at + Duration::from_secs(3600),
+});
+
+pub static TEST_OAUTH2_CLIENT_SECRET_HASH: LazyLock<OAuth2ClientSecretHash> =
+    LazyLock::new(|| (*SHA256HASH1).into());
+
+pub static PUBLIC_OAUTH2_CLIENT: LazyLock<OAuth2Client> = LazyLock::new(|| OAuth2Client {
+    id: uuid!("5d7b4a3e-4c1f-4f0e-b2c6-0e1d9c6a8f21").into(),
+    name: "Public Client".try_into().unwrap(),
+    redirect_uris: vec![
+        "http://127.0.0.1:8080/callback".parse().unwrap(),
+        "com.example.app:/callback".parse().unwrap(),
+    ],
+    confidential: false,
+    created_at: ADMIN.user.created_at + Duration::from_secs(7200),
+});
+
+pub async fn create<Txn: Send + Sync + 'static>(
+    txn: &mut Txn,
+    repo: impl OAuth2ServerRepository<Txn>,
+) -> anyhow::Result<()> {
+    for &client in &*ALL_OAUTH2_CLIENTS {
+        let secret_hash = client
+            .confidential
+            .then_some(*TEST_OAUTH2_CLIENT_SECRET_HASH);
+        repo.create_client(txn, client, secret_hash).await?;
+    }
+    Ok(())
+}
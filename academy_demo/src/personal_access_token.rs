@@ -0,0 +1,51 @@
+use std::{sync::LazyLock, time::Duration};
+
+use academy_models::personal_access_token::{
+    PersonalAccessToken, PersonalAccessTokenHash, PersonalAccessTokenScope,
+};
+use academy_persistence_contracts::personal_access_token::PersonalAccessTokenRepository;
+use uuid::uuid;
+
+use crate::{
+    user::{ADMIN, FOO},
+    SHA256HASH1, SHA256HASH2,
+};
+
+pub static ALL_PERSONAL_ACCESS_TOKENS: LazyLock<
+    Vec<(&PersonalAccessToken, PersonalAccessTokenHash)>,
+> = LazyLock::new(|| {
+    vec![
+        (&ADMIN_PAT, (*SHA256HASH1).into()),
+        (&FOO_PAT, (*SHA256HASH2).into()),
+    ]
+});
+
+pub static ADMIN_PAT: LazyLock<PersonalAccessToken> = LazyLock::new(|| PersonalAccessToken {
+    id: uuid!("7c1e5b8a-0f3d-4e2a-9b6c-3d8f1a2e4c5b").into(),
+    user_id: ADMIN.user.id,
+    name: "admin script".try_into().unwrap(),
+    scopes: [PersonalAccessTokenScope::Admin].into(),
+    created_at: ADMIN.user.created_at + Duration::from_secs(3600),
+    expires_at: None,
+    last_used_at: Some(ADMIN.user.created_at + Duration::from_secs(7200)),
+});
+
+pub static FOO_PAT: LazyLock<PersonalAccessToken> = LazyLock::new(|| PersonalAccessToken {
+    id: uuid!("d3a9f2c1-6b4e-4f8a-8c2d-5e7b9a1f3c6d").into(),
+    user_id: FOO.user.id,
+    name: "cli".try_into().unwrap(),
+    scopes: Default::default(),
+    created_at: FOO.user.created_at + Duration::from_secs(60),
+    expires_at: Some(FOO.user.created_at + Duration::from_secs(90 * 24 * 3600)),
+    last_used_at: None,
+});
+
+pub async fn create<Txn: Send + Sync + 'static>(
+    txn: &mut Txn,
+    repo: impl PersonalAccessTokenRepository<Txn>,
+) -> anyhow::Result<()> {
+    for &(token, hash) in &*ALL_PERSONAL_ACCESS_TOKENS {
+        repo.create(txn, token, hash).await?;
+    }
+    Ok(())
+}
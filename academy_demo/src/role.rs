@@ -0,0 +1,49 @@
+use std::{sync::LazyLock, time::Duration};
+
+use academy_models::{
+    role::{Permission, Role},
+    user::User,
+};
+use academy_persistence_contracts::role::RoleRepository;
+use uuid::uuid;
+
+use crate::user::{ADMIN, FOO};
+
+pub static ALL_ROLES: LazyLock<Vec<&Role>> = LazyLock::new(|| vec![&SUPPORT, &MODERATOR, &FINANCE]);
+
+pub static ALL_USER_ROLES: LazyLock<Vec<(&Role, &User)>> =
+    LazyLock::new(|| vec![(&SUPPORT, &FOO.user)]);
+
+pub static SUPPORT: LazyLock<Role> = LazyLock::new(|| Role {
+    id: uuid!("5b2d8c1e-7a3f-4e9b-a6d4-1c8e3f2b9a7d").into(),
+    name: "support".try_into().unwrap(),
+    permissions: [Permission::ReadUsers, Permission::ResendVerificationEmails].into(),
+    created_at: ADMIN.user.created_at + Duration::from_secs(600),
+});
+
+pub static MODERATOR: LazyLock<Role> = LazyLock::new(|| Role {
+    id: uuid!("a8e4f1c2-3d9b-4b6a-8f2e-7c5d1a9b3e6f").into(),
+    name: "moderator".try_into().unwrap(),
+    permissions: [Permission::ReadUsers, Permission::DisableUsers].into(),
+    created_at: ADMIN.user.created_at + Duration::from_secs(1200),
+});
+
+pub static FINANCE: LazyLock<Role> = LazyLock::new(|| Role {
+    id: uuid!("2f7c9e3a-8b1d-4a5e-9c6f-4e2b8d1a7c3e").into(),
+    name: "finance".try_into().unwrap(),
+    permissions: [Permission::ReadUsers, Permission::EditInvoiceInfo].into(),
+    created_at: ADMIN.user.created_at + Duration::from_secs(1800),
+});
+
+pub async fn create<Txn: Send + Sync + 'static>(
+    txn: &mut Txn,
+    repo: impl RoleRepository<Txn>,
+) -> anyhow::Result<()> {
+    for &role in &*ALL_ROLES {
+        repo.create(txn, role).await?;
+    }
+    for &(role, user) in &*ALL_USER_ROLES {
+        repo.add_to_user(txn, user.id, role.id).await?;
+    }
+    Ok(())
+}
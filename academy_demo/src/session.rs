@@ -1,10 +1,18 @@
-use std::{sync::LazyLock, time::Duration};
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    sync::LazyLock,
+    time::Duration,
+};
 
-use academy_models::session::Session;
+use academy_models::session::{Session, SessionLoginMethod};
 use academy_persistence_contracts::session::SessionRepository;
 use uuid::uuid;
 
-use crate::user::{ADMIN, BAR, FOO};
+use crate::{
+    oauth2::TEST_OAUTH2_PROVIDER_ID,
+    user::{ADMIN, BAR, FOO},
+    SHA256HASH1, SHA256HASH2,
+};
 
 pub static ALL_SESSIONS: LazyLock<Vec<&Session>> = LazyLock::new(|| vec![&ADMIN_1, &FOO_1, &FOO_2]);
 
@@ -12,6 +20,12 @@ pub static ADMIN_1: LazyLock<Session> = LazyLock::new(|| Session {
     id: uuid!("1943a975-8895-428d-9fb1-f8d450f29dae").into(),
     user_id: ADMIN.user.id,
     device_name: Some("laptop".try_into().unwrap()),
+    ip: Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))),
+    device_fingerprint: Some((*SHA256HASH1).into()),
+    last_ip: Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))),
+    browser: Some("Firefox 131.0".into()),
+    os: Some("Linux".into()),
+    login_method: SessionLoginMethod::Password,
     created_at: ADMIN.user.created_at,
     updated_at: ADMIN.user.created_at + Duration::from_secs(1337),
 });
@@ -20,6 +34,12 @@ pub static FOO_1: LazyLock<Session> = LazyLock::new(|| Session {
     id: uuid!("b2b772de-4fc6-4651-9684-c71e70b9197b").into(),
     user_id: FOO.user.id,
     device_name: Some("desktop".try_into().unwrap()),
+    ip: Some(IpAddr::V4(Ipv4Addr::new(192, 168, 0, 42))),
+    device_fingerprint: Some((*SHA256HASH2).into()),
+    last_ip: Some(IpAddr::V4(Ipv4Addr::new(192, 168, 0, 43))),
+    browser: Some("Chrome 130.0.0.0".into()),
+    os: Some("Windows 10".into()),
+    login_method: SessionLoginMethod::Password,
     created_at: FOO.user.created_at + Duration::from_secs(42),
     updated_at: FOO.user.created_at + Duration::from_secs(1337),
 });
@@ -28,6 +48,12 @@ pub static FOO_2: LazyLock<Session> = LazyLock::new(|| Session {
     id: uuid!("eb0fe09a-552e-40c1-a912-e77ec9ca8b36").into(),
     user_id: FOO.user.id,
     device_name: None,
+    ip: Some(IpAddr::V6(Ipv6Addr::LOCALHOST)),
+    device_fingerprint: None,
+    last_ip: Some(IpAddr::V6(Ipv6Addr::LOCALHOST)),
+    browser: None,
+    os: None,
+    login_method: SessionLoginMethod::OAuth2(TEST_OAUTH2_PROVIDER_ID.clone()),
     created_at: FOO.user.created_at,
     updated_at: FOO.user.created_at + Duration::from_secs(17),
 });
@@ -36,6 +62,12 @@ pub static BAR_1: LazyLock<Session> = LazyLock::new(|| Session {
     id: uuid!("2dbe3650-aad6-412a-9207-68a444697909").into(),
     user_id: BAR.user.id,
     device_name: None,
+    ip: None,
+    device_fingerprint: None,
+    last_ip: None,
+    browser: None,
+    os: None,
+    login_method: SessionLoginMethod::Impersonation,
     created_at: BAR.user.created_at,
     updated_at: BAR.user.created_at + Duration::from_secs(23),
 });
@@ -46,6 +78,8 @@ pub async fn create<Txn: Send + Sync + 'static>(
 ) -> anyhow::Result<()> {
     for &session in &*ALL_SESSIONS {
         repo.create(txn, session).await?;
+        repo.save_known_device(txn, session.user_id, session.device_fingerprint, session.ip)
+            .await?;
     }
     Ok(())
 }
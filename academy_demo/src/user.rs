@@ -1,7 +1,8 @@
 use std::sync::LazyLock;
 
 use academy_models::user::{
-    User, UserComposite, UserDetails, UserInvoiceInfo, UserPassword, UserProfile,
+    User, UserComposite, UserDetails, UserEmail, UserInvoiceInfo, UserNameHistoryEntry,
+    UserPassword, UserProfile,
 };
 use academy_persistence_contracts::user::UserRepository;
 use argon2::{
@@ -26,11 +27,13 @@ pub static ADMIN: LazyLock<UserComposite> = LazyLock::new(|| UserComposite {
         enabled: true,
         admin: true,
         newsletter: false,
+        deleted_at: None,
     },
     profile: UserProfile {
         display_name: "Administrator".try_into().unwrap(),
         bio: Default::default(),
         tags: Default::default(),
+        avatar_url: None,
     },
     details: UserDetails {
         mfa_enabled: false,
@@ -55,11 +58,13 @@ pub static ADMIN2: LazyLock<UserComposite> = LazyLock::new(|| UserComposite {
         enabled: true,
         admin: true,
         newsletter: true,
+        deleted_at: None,
     },
     profile: UserProfile {
         display_name: "Administrator2".try_into().unwrap(),
         bio: Default::default(),
         tags: Default::default(),
+        avatar_url: None,
     },
     details: UserDetails {
         mfa_enabled: true,
@@ -84,6 +89,7 @@ pub static FOO: LazyLock<UserComposite> = LazyLock::new(|| UserComposite {
         enabled: true,
         admin: false,
         newsletter: true,
+        deleted_at: None,
     },
     profile: UserProfile {
         display_name: "Foo 42".try_into().unwrap(),
@@ -95,6 +101,7 @@ pub static FOO: LazyLock<UserComposite> = LazyLock::new(|| UserComposite {
             .unwrap()
             .try_into()
             .unwrap(),
+        avatar_url: None,
     },
     details: UserDetails {
         mfa_enabled: false,
@@ -116,7 +123,21 @@ pub static FOO: LazyLock<UserComposite> = LazyLock::new(|| UserComposite {
 pub static FOO_PASSWORD: LazyLock<UserPassword> =
     LazyLock::new(|| "foo password".try_into().unwrap());
 
-pub static BAR: LazyLock<UserComposite> = LazyLock::new(|| UserComposite {
+pub static FOO_EMAIL_1: LazyLock<UserEmail> = LazyLock::new(|| UserEmail {
+    user_id: FOO.user.id,
+    email: "foo@university.example".parse().unwrap(),
+    created_at: Utc.with_ymd_and_hms(2024, 4, 2, 8, 15, 0).unwrap(),
+});
+
+pub static FOO_NAME_HISTORY_1: LazyLock<UserNameHistoryEntry> =
+    LazyLock::new(|| UserNameHistoryEntry {
+        user_id: FOO.user.id,
+        name: "foo_old".try_into().unwrap(),
+        changed_at: FOO.user.last_name_change.unwrap(),
+    });
+
+pub static BAR: LazyLock<UserComposite> = LazyLock::new(|| {
+    UserComposite {
     user: User {
         id: uuid!("94d0e3ca-bf16-486b-a172-b87f4bcbd039").into(),
         name: "bar".try_into().unwrap(),
@@ -128,6 +149,7 @@ pub static BAR: LazyLock<UserComposite> = LazyLock::new(|| UserComposite {
         enabled: false,
         admin: false,
         newsletter: false,
+        deleted_at: None,
     },
     profile: UserProfile {
         display_name: "Bar".try_into().unwrap(),
@@ -139,6 +161,11 @@ pub static BAR: LazyLock<UserComposite> = LazyLock::new(|| UserComposite {
             .unwrap()
             .try_into()
             .unwrap(),
+        avatar_url: Some(
+            "https://bootstrap.academy/storage/avatars/94d0e3ca-bf16-486b-a172-b87f4bcbd039/256.png?v=1719544455"
+                .parse()
+                .unwrap(),
+        ),
     },
     details: UserDetails {
         mfa_enabled: false,
@@ -146,6 +173,7 @@ pub static BAR: LazyLock<UserComposite> = LazyLock::new(|| UserComposite {
         oauth2_login: false,
     },
     invoice_info: UserInvoiceInfo::default(),
+}
 });
 
 pub static BAR_PASSWORD: LazyLock<UserPassword> =
@@ -169,6 +197,10 @@ pub async fn create<Txn: Send + Sync + 'static>(
             .await?;
     }
 
+    repo.add_email(txn, &FOO_EMAIL_1).await?;
+    repo.add_name_history_entry(txn, &FOO_NAME_HISTORY_1)
+        .await?;
+
     repo.save_password_hash(txn, ADMIN.user.id, hash_password(&ADMIN_PASSWORD)?)
         .await?;
     repo.save_password_hash(txn, ADMIN2.user.id, hash_password(&ADMIN2_PASSWORD)?)
@@ -2,7 +2,9 @@ use std::future::Future;
 
 use academy_models::email_address::EmailAddressWithName;
 use academy_templates_contracts::{
-    ResetPasswordTemplate, SubscribeNewsletterTemplate, VerifyEmailTemplate,
+    AccountLockedTemplate, AddEmailTemplate, ChangeEmailTemplate, EmailChangeNoticeTemplate,
+    LoginCodeTemplate, NewSignInTemplate, ResetPasswordTemplate, SubscribeNewsletterTemplate,
+    UserDeletedTemplate, UserExportTemplate, VerifyEmailTemplate,
 };
 
 #[cfg_attr(feature = "mock", mockall::automock)]
@@ -24,6 +26,54 @@ pub trait TemplateEmailService: Send + Sync + 'static {
         recipient: EmailAddressWithName,
         data: &VerifyEmailTemplate,
     ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    fn send_account_locked_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &AccountLockedTemplate,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    fn send_new_sign_in_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &NewSignInTemplate,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    fn send_login_code_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &LoginCodeTemplate,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    fn send_user_export_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &UserExportTemplate,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    fn send_user_deleted_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &UserDeletedTemplate,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    fn send_change_email_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &ChangeEmailTemplate,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    fn send_email_change_notice_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &EmailChangeNoticeTemplate,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    fn send_add_email_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &AddEmailTemplate,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
 }
 
 #[cfg(feature = "mock")]
@@ -75,4 +125,132 @@ impl MockTemplateEmailService {
             .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
         self
     }
+
+    pub fn with_send_account_locked_email(
+        mut self,
+        recipient: EmailAddressWithName,
+        data: AccountLockedTemplate,
+        result: bool,
+    ) -> Self {
+        self.expect_send_account_locked_email()
+            .once()
+            .with(
+                mockall::predicate::eq(recipient),
+                mockall::predicate::eq(data),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_send_new_sign_in_email(
+        mut self,
+        recipient: EmailAddressWithName,
+        data: NewSignInTemplate,
+        result: bool,
+    ) -> Self {
+        self.expect_send_new_sign_in_email()
+            .once()
+            .with(
+                mockall::predicate::eq(recipient),
+                mockall::predicate::eq(data),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_send_login_code_email(
+        mut self,
+        recipient: EmailAddressWithName,
+        data: LoginCodeTemplate,
+        result: bool,
+    ) -> Self {
+        self.expect_send_login_code_email()
+            .once()
+            .with(
+                mockall::predicate::eq(recipient),
+                mockall::predicate::eq(data),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_send_user_export_email(
+        mut self,
+        recipient: EmailAddressWithName,
+        data: UserExportTemplate,
+        result: bool,
+    ) -> Self {
+        self.expect_send_user_export_email()
+            .once()
+            .with(
+                mockall::predicate::eq(recipient),
+                mockall::predicate::eq(data),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_send_user_deleted_email(
+        mut self,
+        recipient: EmailAddressWithName,
+        data: UserDeletedTemplate,
+        result: bool,
+    ) -> Self {
+        self.expect_send_user_deleted_email()
+            .once()
+            .with(
+                mockall::predicate::eq(recipient),
+                mockall::predicate::eq(data),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_send_change_email_email(
+        mut self,
+        recipient: EmailAddressWithName,
+        data: ChangeEmailTemplate,
+        result: bool,
+    ) -> Self {
+        self.expect_send_change_email_email()
+            .once()
+            .with(
+                mockall::predicate::eq(recipient),
+                mockall::predicate::eq(data),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_send_email_change_notice_email(
+        mut self,
+        recipient: EmailAddressWithName,
+        data: EmailChangeNoticeTemplate,
+        result: bool,
+    ) -> Self {
+        self.expect_send_email_change_notice_email()
+            .once()
+            .with(
+                mockall::predicate::eq(recipient),
+                mockall::predicate::eq(data),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_send_add_email_email(
+        mut self,
+        recipient: EmailAddressWithName,
+        data: AddEmailTemplate,
+        result: bool,
+    ) -> Self {
+        self.expect_send_add_email_email()
+            .once()
+            .with(
+                mockall::predicate::eq(recipient),
+                mockall::predicate::eq(data),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
 }
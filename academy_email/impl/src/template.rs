@@ -2,8 +2,9 @@ use academy_di::Build;
 use academy_email_contracts::{template::TemplateEmailService, ContentType, Email, EmailService};
 use academy_models::email_address::EmailAddressWithName;
 use academy_templates_contracts::{
-    ResetPasswordTemplate, SubscribeNewsletterTemplate, Template, TemplateService,
-    VerifyEmailTemplate,
+    AccountLockedTemplate, AddEmailTemplate, ChangeEmailTemplate, EmailChangeNoticeTemplate,
+    LoginCodeTemplate, NewSignInTemplate, ResetPasswordTemplate, SubscribeNewsletterTemplate,
+    Template, TemplateService, UserDeletedTemplate, UserExportTemplate, VerifyEmailTemplate,
 };
 use academy_utils::trace_instrument;
 
@@ -47,6 +48,102 @@ where
         self.send_email(recipient, data, "Willkommen bei der Bootstrap Academy!")
             .await
     }
+
+    #[trace_instrument(skip(self))]
+    async fn send_account_locked_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &AccountLockedTemplate,
+    ) -> anyhow::Result<bool> {
+        self.send_email(
+            recipient,
+            data,
+            "Account vorübergehend gesperrt - Bootstrap Academy",
+        )
+        .await
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn send_new_sign_in_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &NewSignInTemplate,
+    ) -> anyhow::Result<bool> {
+        self.send_email(recipient, data, "Neue Anmeldung - Bootstrap Academy")
+            .await
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn send_login_code_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &LoginCodeTemplate,
+    ) -> anyhow::Result<bool> {
+        self.send_email(recipient, data, "Anmeldung - Bootstrap Academy")
+            .await
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn send_user_export_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &UserExportTemplate,
+    ) -> anyhow::Result<bool> {
+        self.send_email(recipient, data, "Datenexport - Bootstrap Academy")
+            .await
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn send_user_deleted_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &UserDeletedTemplate,
+    ) -> anyhow::Result<bool> {
+        self.send_email(recipient, data, "Konto gelöscht - Bootstrap Academy")
+            .await
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn send_change_email_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &ChangeEmailTemplate,
+    ) -> anyhow::Result<bool> {
+        self.send_email(
+            recipient,
+            data,
+            "E-Mail-Adresse bestätigen - Bootstrap Academy",
+        )
+        .await
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn send_email_change_notice_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &EmailChangeNoticeTemplate,
+    ) -> anyhow::Result<bool> {
+        self.send_email(
+            recipient,
+            data,
+            "Änderung deiner E-Mail-Adresse - Bootstrap Academy",
+        )
+        .await
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn send_add_email_email(
+        &self,
+        recipient: EmailAddressWithName,
+        data: &AddEmailTemplate,
+    ) -> anyhow::Result<bool> {
+        self.send_email(
+            recipient,
+            data,
+            "E-Mail-Adresse hinzufügen - Bootstrap Academy",
+        )
+        .await
+    }
 }
 
 impl<EmailS, TemplateS> TemplateEmailServiceImpl<EmailS, TemplateS>
@@ -1,7 +1,10 @@
 use std::future::Future;
 
 use academy_models::{
-    oauth2::{OAuth2AuthorizationCode, OAuth2Provider, OAuth2UserInfo},
+    oauth2::{
+        OAuth2AuthorizationCode, OAuth2Nonce, OAuth2PkceCodeVerifier, OAuth2Provider, OAuth2State,
+        OAuth2UserInfo,
+    },
     url::Url,
 };
 use thiserror::Error;
@@ -9,15 +12,32 @@ use thiserror::Error;
 #[cfg_attr(feature = "mock", mockall::automock)]
 pub trait OAuth2ApiService: Send + Sync + 'static {
     /// Build the authorize URL for the given OAuth2 provider.
-    fn generate_auth_url(&self, provider: &OAuth2Provider) -> Url;
+    ///
+    /// The `state` and the PKCE code challenge derived from `pkce_verifier`
+    /// are included in the URL. For OpenID Connect providers, the `nonce` is
+    /// included as well and the authorize endpoint is discovered
+    /// automatically.
+    fn generate_auth_url(
+        &self,
+        provider: &OAuth2Provider,
+        redirect_url: &Url,
+        state: &OAuth2State,
+        pkce_verifier: &OAuth2PkceCodeVerifier,
+        nonce: &OAuth2Nonce,
+    ) -> impl Future<Output = anyhow::Result<Url>> + Send;
 
     /// Try to resolve an authorization code and return the remote user
     /// information in case of success.
+    ///
+    /// For OpenID Connect providers, the remote user information is taken
+    /// from the ID token, which must contain the given `nonce`.
     fn resolve_code(
         &self,
         provider: OAuth2Provider,
         code: OAuth2AuthorizationCode,
         redirect_url: Url,
+        pkce_verifier: OAuth2PkceCodeVerifier,
+        nonce: OAuth2Nonce,
     ) -> impl Future<Output = Result<OAuth2UserInfo, OAuth2ResolveCodeError>> + Send;
 }
 
@@ -31,11 +51,25 @@ pub enum OAuth2ResolveCodeError {
 
 #[cfg(feature = "mock")]
 impl MockOAuth2ApiService {
-    pub fn with_generate_auth_url(mut self, provider: OAuth2Provider, result: Url) -> Self {
+    pub fn with_generate_auth_url(
+        mut self,
+        provider: OAuth2Provider,
+        redirect_url: Url,
+        state: OAuth2State,
+        pkce_verifier: OAuth2PkceCodeVerifier,
+        nonce: OAuth2Nonce,
+        result: Url,
+    ) -> Self {
         self.expect_generate_auth_url()
             .once()
-            .with(mockall::predicate::eq(provider))
-            .return_once(|_| result);
+            .with(
+                mockall::predicate::eq(provider),
+                mockall::predicate::eq(redirect_url),
+                mockall::predicate::eq(state),
+                mockall::predicate::eq(pkce_verifier),
+                mockall::predicate::eq(nonce),
+            )
+            .return_once(|_, _, _, _, _| Box::pin(std::future::ready(Ok(result))));
         self
     }
 
@@ -44,6 +78,8 @@ impl MockOAuth2ApiService {
         provider: OAuth2Provider,
         code: OAuth2AuthorizationCode,
         redirect_url: Url,
+        pkce_verifier: OAuth2PkceCodeVerifier,
+        nonce: OAuth2Nonce,
         result: Result<OAuth2UserInfo, OAuth2ResolveCodeError>,
     ) -> Self {
         self.expect_resolve_code()
@@ -52,8 +88,10 @@ impl MockOAuth2ApiService {
                 mockall::predicate::eq(provider),
                 mockall::predicate::eq(code),
                 mockall::predicate::eq(redirect_url),
+                mockall::predicate::eq(pkce_verifier),
+                mockall::predicate::eq(nonce),
             )
-            .return_once(|_, _, _| Box::pin(std::future::ready(result)));
+            .return_once(|_, _, _, _, _| Box::pin(std::future::ready(result)));
         self
     }
 }
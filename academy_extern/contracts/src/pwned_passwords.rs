@@ -0,0 +1,22 @@
+use std::future::Future;
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait PwnedPasswordsApiService: Send + Sync + 'static {
+    /// Return how often the given password has appeared in known data
+    /// breaches.
+    ///
+    /// Only the first five characters of the password's SHA-1 hash are sent to
+    /// the api (k-anonymity).
+    fn get_breach_count(&self, password: &str) -> impl Future<Output = anyhow::Result<u64>> + Send;
+}
+
+#[cfg(feature = "mock")]
+impl MockPwnedPasswordsApiService {
+    pub fn with_get_breach_count(mut self, password: String, count: u64) -> Self {
+        self.expect_get_breach_count()
+            .once()
+            .with(mockall::predicate::eq(password))
+            .return_once(move |_| Box::pin(std::future::ready(Ok(count))));
+        self
+    }
+}
@@ -1,5 +1,7 @@
 mod http;
 pub mod internal;
 pub mod oauth2;
+mod oidc;
+pub mod pwned_passwords;
 pub mod recaptcha;
 pub mod vat;
@@ -3,43 +3,83 @@ use std::collections::HashMap;
 use academy_di::Build;
 use academy_extern_contracts::oauth2::{OAuth2ApiService, OAuth2ResolveCodeError};
 use academy_models::{
-    oauth2::{OAuth2AuthorizationCode, OAuth2Provider, OAuth2UserInfo},
+    oauth2::{
+        OAuth2AuthorizationCode, OAuth2Nonce, OAuth2PkceCodeVerifier, OAuth2Provider,
+        OAuth2ProviderKind, OAuth2State, OAuth2UserInfo,
+    },
     url::Url,
 };
 use academy_utils::{trace_instrument, Apply};
 use anyhow::{anyhow, Context};
 use oauth2::{
-    basic::BasicClient, AuthUrl, AuthorizationCode, ClientId, ClientSecret, RedirectUrl,
-    RequestTokenError, TokenResponse, TokenUrl,
+    basic::{
+        BasicErrorResponse, BasicRevocationErrorResponse, BasicTokenIntrospectionResponse,
+        BasicTokenType,
+    },
+    AuthUrl, AuthorizationCode, ClientId, ClientSecret, ExtraTokenFields, PkceCodeChallenge,
+    PkceCodeVerifier, RedirectUrl, RequestTokenError, StandardRevocableToken,
+    StandardTokenResponse, TokenResponse, TokenUrl,
 };
+use serde::{Deserialize, Serialize};
 use tracing::trace;
 
-use crate::http::{HttpClient, USER_AGENT};
+use crate::{
+    http::{HttpClient, USER_AGENT},
+    oidc::{validate_id_token, OidcCache, ValidateIdTokenError},
+};
 
 #[derive(Debug, Clone, Build, Default)]
 pub struct OAuth2ApiServiceImpl {
     #[di(default)]
     http: HttpClient,
+    #[di(default)]
+    oidc_cache: OidcCache,
 }
 
 impl OAuth2ApiService for OAuth2ApiServiceImpl {
     #[trace_instrument(skip(self))]
-    fn generate_auth_url(&self, provider: &OAuth2Provider) -> Url {
-        let mut url = provider.auth_url.clone();
+    async fn generate_auth_url(
+        &self,
+        provider: &OAuth2Provider,
+        redirect_url: &Url,
+        state: &OAuth2State,
+        pkce_verifier: &OAuth2PkceCodeVerifier,
+        nonce: &OAuth2Nonce,
+    ) -> anyhow::Result<Url> {
+        let (mut url, oidc) = match &provider.kind {
+            OAuth2ProviderKind::OAuth2 { auth_url, .. } => (auth_url.clone(), false),
+            OAuth2ProviderKind::Oidc { issuer_url } => {
+                let oidc_provider = self.oidc_cache.get(&self.http, issuer_url, false).await?;
+                (oidc_provider.metadata.authorization_endpoint.clone(), true)
+            }
+        };
+
+        let pkce_challenge = PkceCodeChallenge::from_code_verifier_sha256(&PkceCodeVerifier::new(
+            (**pkce_verifier).clone(),
+        ));
+
         url.query_pairs_mut()
             .append_pair("response_type", "code")
             .append_pair("client_id", &provider.client_id)
-            .apply_if(!provider.scopes.is_empty(), |q| {
-                let mut it = provider.scopes.iter();
-                let mut scopes = it.next().unwrap().clone();
-                for scope in it {
-                    scopes.push(' ');
-                    scopes.push_str(scope);
-                }
-                q.append_pair("scope", &scopes)
+            .append_pair("redirect_uri", redirect_url.as_str())
+            .append_pair("state", state)
+            .append_pair("code_challenge", pkce_challenge.as_str())
+            .append_pair("code_challenge_method", pkce_challenge.method())
+            .apply_if(oidc, |q| q.append_pair("nonce", nonce))
+            .apply_if(oidc || !provider.scopes.is_empty(), |q| {
+                let mut scopes = if oidc { vec!["openid"] } else { Vec::new() };
+                scopes.extend(
+                    provider
+                        .scopes
+                        .iter()
+                        .map(String::as_str)
+                        .filter(|&scope| !oidc || scope != "openid"),
+                );
+                q.append_pair("scope", &scopes.join(" "))
             })
             .finish();
-        url
+
+        Ok(url)
     }
 
     #[trace_instrument(skip(self))]
@@ -48,20 +88,38 @@ impl OAuth2ApiService for OAuth2ApiServiceImpl {
         provider: OAuth2Provider,
         code: OAuth2AuthorizationCode,
         redirect_url: Url,
+        pkce_verifier: OAuth2PkceCodeVerifier,
+        nonce: OAuth2Nonce,
     ) -> Result<OAuth2UserInfo, OAuth2ResolveCodeError> {
-        let client = BasicClient::new(
-            ClientId::new(provider.client_id),
+        let (auth_url, token_url) = match &provider.kind {
+            OAuth2ProviderKind::OAuth2 {
+                auth_url,
+                token_url,
+                ..
+            } => (auth_url.clone(), token_url.clone()),
+            OAuth2ProviderKind::Oidc { issuer_url } => {
+                let oidc_provider = self.oidc_cache.get(&self.http, issuer_url, false).await?;
+                (
+                    oidc_provider.metadata.authorization_endpoint.clone(),
+                    oidc_provider.metadata.token_endpoint.clone(),
+                )
+            }
+        };
+
+        let client = Client::new(
+            ClientId::new(provider.client_id.clone()),
             provider
                 .client_secret
                 .map(|x| ClientSecret::new(x.into_inner())),
-            AuthUrl::from_url(provider.auth_url.0),
-            Some(TokenUrl::from_url(provider.token_url.0)),
+            AuthUrl::from_url(auth_url.0),
+            Some(TokenUrl::from_url(token_url.0)),
         )
         .set_redirect_uri(RedirectUrl::from_url(redirect_url.0));
 
         // exchange the authorization code for an access token
         let response = client
             .exchange_code(AuthorizationCode::new(code.into_inner()))
+            .set_pkce_verifier(PkceCodeVerifier::new(pkce_verifier.into_inner()))
             .request_async(http_client)
             .await
             .map_err(|err| match err {
@@ -79,10 +137,89 @@ impl OAuth2ApiService for OAuth2ApiServiceImpl {
             "exchanged authorization code for access token"
         );
 
+        match &provider.kind {
+            OAuth2ProviderKind::OAuth2 {
+                userinfo_url,
+                userinfo_id_key,
+                userinfo_name_key,
+                ..
+            } => {
+                self.fetch_userinfo(
+                    access_token,
+                    userinfo_url,
+                    userinfo_id_key,
+                    userinfo_name_key,
+                )
+                .await
+            }
+            OAuth2ProviderKind::Oidc { issuer_url } => {
+                let id_token = response
+                    .extra_fields()
+                    .id_token
+                    .as_deref()
+                    .ok_or_else(|| anyhow!("Token response does not contain an ID token"))?;
+
+                self.resolve_id_token(issuer_url, &provider.client_id, id_token, &nonce)
+                    .await
+            }
+        }
+    }
+}
+
+impl OAuth2ApiServiceImpl {
+    async fn resolve_id_token(
+        &self,
+        issuer_url: &Url,
+        client_id: &str,
+        id_token: &str,
+        nonce: &str,
+    ) -> Result<OAuth2UserInfo, OAuth2ResolveCodeError> {
+        let mut oidc_provider = self.oidc_cache.get(&self.http, issuer_url, false).await?;
+        let claims = match validate_id_token(id_token, &oidc_provider, client_id, nonce) {
+            // the provider may have rotated its signing keys
+            Err(ValidateIdTokenError::UnknownKey) => {
+                trace!("unknown key, refreshing jwks");
+                oidc_provider = self.oidc_cache.get(&self.http, issuer_url, true).await?;
+                validate_id_token(id_token, &oidc_provider, client_id, nonce)
+            }
+            result => result,
+        }
+        .map_err(|err| match err {
+            ValidateIdTokenError::InvalidNonce => OAuth2ResolveCodeError::InvalidCode,
+            err => anyhow!(err).context("Failed to validate ID token").into(),
+        })?;
+        trace!(?claims, "validated id token");
+
+        let name = claims
+            .preferred_username
+            .or(claims.name)
+            .or_else(|| claims.email.clone())
+            .unwrap_or_else(|| claims.sub.clone());
+
+        Ok(OAuth2UserInfo {
+            id: claims
+                .sub
+                .try_into()
+                .map_err(|id| anyhow!("Failed to deserialize remote user id {id:?}"))?,
+            name: name
+                .try_into()
+                .map_err(|name| anyhow!("Failed to deserialize remote user name {name:?}"))?,
+            email: claims.email.and_then(|email| email.parse().ok()),
+            email_verified: claims.email_verified,
+        })
+    }
+
+    async fn fetch_userinfo(
+        &self,
+        access_token: &str,
+        userinfo_url: &Url,
+        userinfo_id_key: &str,
+        userinfo_name_key: &str,
+    ) -> Result<OAuth2UserInfo, OAuth2ResolveCodeError> {
         // use the access token to fetch the remote user's id and name
         let userinfo = self
             .http
-            .get(provider.userinfo_url.0)
+            .get(userinfo_url.0.clone())
             .bearer_auth(access_token)
             .send()
             .await
@@ -94,7 +231,7 @@ impl OAuth2ApiService for OAuth2ApiServiceImpl {
             .context("Failed to deserialize userinfo")?;
         trace!(?userinfo, "fetched userinfo");
 
-        let id = match userinfo.get(&provider.userinfo_id_key) {
+        let id = match userinfo.get(userinfo_id_key) {
             Some(serde_json::Value::Number(id)) => Ok(id.to_string()),
             Some(serde_json::Value::String(id)) => Ok(id.to_owned()),
             Some(x) => Err(anyhow!("Invalid user id: {x}")),
@@ -104,7 +241,7 @@ impl OAuth2ApiService for OAuth2ApiServiceImpl {
         .try_into()
         .map_err(|id| anyhow!("Failed to deserialize remote user id {id:?}"))?;
 
-        let name = match userinfo.get(&provider.userinfo_name_key) {
+        let name = match userinfo.get(userinfo_name_key) {
             Some(serde_json::Value::String(name)) => Ok(name.clone()),
             Some(x) => Err(anyhow!("Invalid username: {x}")),
             None => Err(anyhow!("Username missing")),
@@ -113,10 +250,33 @@ impl OAuth2ApiService for OAuth2ApiServiceImpl {
         .try_into()
         .map_err(|name| anyhow!("Failed to deserialize remote user name {name:?}"))?;
 
-        Ok(OAuth2UserInfo { id, name })
+        Ok(OAuth2UserInfo {
+            id,
+            name,
+            email: None,
+            email_verified: false,
+        })
     }
 }
 
+/// OAuth2 client which also accepts the `id_token` returned by OpenID Connect
+/// providers.
+type Client = oauth2::Client<
+    BasicErrorResponse,
+    StandardTokenResponse<IdTokenFields, BasicTokenType>,
+    BasicTokenType,
+    BasicTokenIntrospectionResponse,
+    StandardRevocableToken,
+    BasicRevocationErrorResponse,
+>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IdTokenFields {
+    id_token: Option<String>,
+}
+
+impl ExtraTokenFields for IdTokenFields {}
+
 async fn http_client(
     mut request: oauth2::HttpRequest,
 ) -> Result<oauth2::HttpResponse, oauth2::reqwest::AsyncHttpClientError> {
@@ -131,37 +291,56 @@ async fn http_client(
 mod tests {
     use super::*;
 
-    #[test]
-    fn generate_auth_url_with_scopes() {
+    #[tokio::test]
+    async fn generate_auth_url_with_scopes() {
         // Arrange
         let provider = make_provider();
+        let (redirect_url, state, pkce_verifier, nonce) = make_params();
 
         let sut = OAuth2ApiServiceImpl::default();
 
         // Act
-        let result = sut.generate_auth_url(&provider);
+        let result = sut
+            .generate_auth_url(&provider, &redirect_url, &state, &pkce_verifier, &nonce)
+            .await
+            .unwrap();
 
         // Assert
-        assert_eq!(result.as_str(), "https://oauth2.provider/auth?response_type=code&client_id=the-client-id&scope=foo+bar+baz");
+        assert_eq!(
+            result.as_str(),
+            "https://oauth2.provider/auth?response_type=code&client_id=the-client-id\
+             &redirect_uri=https%3A%2F%2Facademy%2Foauth2%2Fcallback\
+             &state=hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu\
+             &code_challenge=0JwGuChQdkJ0HevUCyjJTCyt5RFtKKTPYD1MW34nn00\
+             &code_challenge_method=S256&scope=foo+bar+baz"
+        );
     }
 
-    #[test]
-    fn generate_auth_url_without_scopes() {
+    #[tokio::test]
+    async fn generate_auth_url_without_scopes() {
         // Arrange
         let provider = OAuth2Provider {
             scopes: Vec::new(),
             ..make_provider()
         };
+        let (redirect_url, state, pkce_verifier, nonce) = make_params();
 
         let sut = OAuth2ApiServiceImpl::default();
 
         // Act
-        let result = sut.generate_auth_url(&provider);
+        let result = sut
+            .generate_auth_url(&provider, &redirect_url, &state, &pkce_verifier, &nonce)
+            .await
+            .unwrap();
 
         // Assert
         assert_eq!(
             result.as_str(),
-            "https://oauth2.provider/auth?response_type=code&client_id=the-client-id"
+            "https://oauth2.provider/auth?response_type=code&client_id=the-client-id\
+             &redirect_uri=https%3A%2F%2Facademy%2Foauth2%2Fcallback\
+             &state=hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu\
+             &code_challenge=0JwGuChQdkJ0HevUCyjJTCyt5RFtKKTPYD1MW34nn00\
+             &code_challenge_method=S256"
         );
     }
 
@@ -170,12 +349,26 @@ mod tests {
             name: "test".into(),
             client_id: "the-client-id".into(),
             client_secret: None,
-            auth_url: "https://oauth2.provider/auth".parse().unwrap(),
-            token_url: "http://test".parse().unwrap(),
-            userinfo_url: "http://test".parse().unwrap(),
-            userinfo_id_key: String::new(),
-            userinfo_name_key: String::new(),
+            kind: OAuth2ProviderKind::OAuth2 {
+                auth_url: "https://oauth2.provider/auth".parse().unwrap(),
+                token_url: "http://test".parse().unwrap(),
+                userinfo_url: "http://test".parse().unwrap(),
+                userinfo_id_key: String::new(),
+                userinfo_name_key: String::new(),
+            },
             scopes: ["foo", "bar", "baz"].map(Into::into).into(),
         }
     }
+
+    fn make_params() -> (Url, OAuth2State, OAuth2PkceCodeVerifier, OAuth2Nonce) {
+        (
+            "https://academy/oauth2/callback".parse().unwrap(),
+            OAuth2State::try_new("hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu").unwrap(),
+            OAuth2PkceCodeVerifier::try_new(
+                "W1NhzrHUhfLDxK8WPoV0ufCTbU9hH2Y3Tgp2sR1QCbsOM8V6RMoP12ieQQNqmbu9",
+            )
+            .unwrap(),
+            OAuth2Nonce::try_new("tsF4ivBH8EmhQV8dOKEb2hlsvU6aKBSH").unwrap(),
+        )
+    }
 }
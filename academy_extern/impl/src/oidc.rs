@@ -0,0 +1,423 @@
+//! OpenID Connect discovery and ID token validation
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use academy_models::url::Url;
+use anyhow::{anyhow, Context};
+use jsonwebtoken::{jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use serde::{Deserialize, Deserializer};
+use thiserror::Error;
+use tracing::trace;
+
+use crate::http::HttpClient;
+
+/// How long discovery documents and JWKS are cached before they are fetched
+/// again.
+const CACHE_TTL: Duration = Duration::from_secs(3600);
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct OidcProviderMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: Url,
+    pub token_endpoint: Url,
+    pub jwks_uri: Url,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct OidcProvider {
+    pub metadata: Arc<OidcProviderMetadata>,
+    pub jwks: Arc<JwkSet>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OidcCache(Arc<RwLock<HashMap<String, (Instant, OidcProvider)>>>);
+
+impl OidcCache {
+    /// Return the discovered provider metadata and JWKS of the given issuer.
+    ///
+    /// Cached values are reused unless they have expired or `refresh` is set.
+    pub(crate) async fn get(
+        &self,
+        http: &HttpClient,
+        issuer_url: &Url,
+        refresh: bool,
+    ) -> anyhow::Result<OidcProvider> {
+        if !refresh {
+            if let Some((fetched_at, provider)) = self.0.read().unwrap().get(issuer_url.as_str()) {
+                if fetched_at.elapsed() < CACHE_TTL {
+                    return Ok(provider.clone());
+                }
+            }
+        }
+
+        let provider = discover(http, issuer_url).await?;
+        self.0
+            .write()
+            .unwrap()
+            .insert(issuer_url.to_string(), (Instant::now(), provider.clone()));
+
+        Ok(provider)
+    }
+}
+
+async fn discover(http: &HttpClient, issuer_url: &Url) -> anyhow::Result<OidcProvider> {
+    let issuer = issuer_url.as_str().trim_end_matches('/');
+
+    let metadata = http
+        .get(format!("{issuer}/.well-known/openid-configuration"))
+        .send()
+        .await
+        .context("Failed to send OpenID Connect discovery request")?
+        .error_for_status()
+        .context("OpenID Connect discovery request returned an error")?
+        .json::<OidcProviderMetadata>()
+        .await
+        .context("Failed to deserialize OpenID Connect provider metadata")?;
+    trace!(?metadata, "fetched openid connect provider metadata");
+
+    if metadata.issuer.trim_end_matches('/') != issuer {
+        return Err(anyhow!(
+            "Issuer mismatch in OpenID Connect provider metadata: expected {issuer:?}, got {:?}",
+            metadata.issuer
+        ));
+    }
+
+    let jwks = http
+        .get(metadata.jwks_uri.0.clone())
+        .send()
+        .await
+        .context("Failed to send request to fetch JWKS")?
+        .error_for_status()
+        .context("Fetch JWKS request returned an error")?
+        .json::<JwkSet>()
+        .await
+        .context("Failed to deserialize JWKS")?;
+    trace!(keys = jwks.keys.len(), "fetched jwks");
+
+    Ok(OidcProvider {
+        metadata: metadata.into(),
+        jwks: jwks.into(),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct IdTokenClaims {
+    pub sub: String,
+    pub nonce: Option<String>,
+    pub name: Option<String>,
+    pub preferred_username: Option<String>,
+    pub email: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_bool_or_string")]
+    pub email_verified: bool,
+}
+
+#[derive(Debug, Error)]
+pub(crate) enum ValidateIdTokenError {
+    #[error("The ID token has been signed with an unknown key.")]
+    UnknownKey,
+    #[error("The ID token contains an invalid nonce.")]
+    InvalidNonce,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+/// Verify the signature of the given ID token using the provider's JWKS and
+/// validate its issuer, audience, expiry and nonce.
+pub(crate) fn validate_id_token(
+    id_token: &str,
+    provider: &OidcProvider,
+    client_id: &str,
+    nonce: &str,
+) -> Result<IdTokenClaims, ValidateIdTokenError> {
+    let header = jsonwebtoken::decode_header(id_token).context("Failed to decode ID token")?;
+
+    if matches!(
+        header.alg,
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+    ) {
+        return Err(anyhow!("Unsupported ID token algorithm {:?}", header.alg).into());
+    }
+
+    let jwk = match &header.kid {
+        Some(kid) => provider.jwks.find(kid),
+        None if provider.jwks.keys.len() == 1 => provider.jwks.keys.first(),
+        None => None,
+    }
+    .ok_or(ValidateIdTokenError::UnknownKey)?;
+    let key = DecodingKey::from_jwk(jwk).context("Failed to load JWK")?;
+
+    let mut validation = Validation::new(header.alg);
+    validation.set_issuer(&[&provider.metadata.issuer]);
+    validation.set_audience(&[client_id]);
+    validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+    let claims = jsonwebtoken::decode::<IdTokenClaims>(id_token, &key, &validation)
+        .context("Failed to validate ID token")?
+        .claims;
+
+    if claims.nonce.as_deref() != Some(nonce) {
+        return Err(ValidateIdTokenError::InvalidNonce);
+    }
+
+    Ok(claims)
+}
+
+/// Some providers encode `email_verified` as a string instead of a boolean.
+fn deserialize_bool_or_string<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<bool, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BoolOrString {
+        Bool(bool),
+        String(String),
+    }
+
+    Ok(match BoolOrString::deserialize(deserializer)? {
+        BoolOrString::Bool(x) => x,
+        BoolOrString::String(x) => x == "true",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_utils::assert_matches;
+    use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+    use jsonwebtoken::{EncodingKey, Header};
+    use ring::{
+        rand::SystemRandom,
+        signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING},
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    const ISSUER: &str = "https://idp.example.com";
+    const CLIENT_ID: &str = "the-client-id";
+    const NONCE: &str = "tsF4ivBH8EmhQV8dOKEb2hlsvU6aKBSH";
+
+    #[test]
+    fn ok() {
+        // Arrange
+        let key = TestKey::generate();
+        let provider = make_provider(&[&key]);
+        let id_token = key.sign(claims());
+
+        // Act
+        let result = validate_id_token(&id_token, &provider, CLIENT_ID, NONCE);
+
+        // Assert
+        assert_eq!(
+            result.unwrap(),
+            IdTokenClaims {
+                sub: "the-remote-user-id".into(),
+                nonce: Some(NONCE.into()),
+                name: Some("Foo Bar".into()),
+                preferred_username: Some("foo".into()),
+                email: Some("foo@example.com".into()),
+                email_verified: true,
+            }
+        );
+    }
+
+    #[test]
+    fn email_verified_string() {
+        // Arrange
+        let key = TestKey::generate();
+        let provider = make_provider(&[&key]);
+        let mut claims = claims();
+        claims["email_verified"] = json!("true");
+        let id_token = key.sign(claims);
+
+        // Act
+        let result = validate_id_token(&id_token, &provider, CLIENT_ID, NONCE);
+
+        // Assert
+        assert!(result.unwrap().email_verified);
+    }
+
+    #[test]
+    fn invalid_nonce() {
+        // Arrange
+        let key = TestKey::generate();
+        let provider = make_provider(&[&key]);
+        let mut claims = claims();
+        claims["nonce"] = json!("some other nonce");
+        let id_token = key.sign(claims);
+
+        // Act
+        let result = validate_id_token(&id_token, &provider, CLIENT_ID, NONCE);
+
+        // Assert
+        assert_matches!(result, Err(ValidateIdTokenError::InvalidNonce));
+    }
+
+    #[test]
+    fn unknown_key() {
+        // Arrange
+        let key = TestKey::generate();
+        let provider = make_provider(&[&TestKey::generate_with_kid("other-key")]);
+        let id_token = key.sign(claims());
+
+        // Act
+        let result = validate_id_token(&id_token, &provider, CLIENT_ID, NONCE);
+
+        // Assert
+        assert_matches!(result, Err(ValidateIdTokenError::UnknownKey));
+    }
+
+    #[test]
+    fn invalid_signature() {
+        // Arrange
+        let key = TestKey::generate();
+        let provider = make_provider(&[&TestKey::generate()]);
+        let id_token = key.sign(claims());
+
+        // Act
+        let result = validate_id_token(&id_token, &provider, CLIENT_ID, NONCE);
+
+        // Assert
+        assert_matches!(result, Err(ValidateIdTokenError::Other(_)));
+    }
+
+    #[test]
+    fn invalid_issuer() {
+        // Arrange
+        let key = TestKey::generate();
+        let provider = make_provider(&[&key]);
+        let mut claims = claims();
+        claims["iss"] = json!("https://evil.example.com");
+        let id_token = key.sign(claims);
+
+        // Act
+        let result = validate_id_token(&id_token, &provider, CLIENT_ID, NONCE);
+
+        // Assert
+        assert_matches!(result, Err(ValidateIdTokenError::Other(_)));
+    }
+
+    #[test]
+    fn invalid_audience() {
+        // Arrange
+        let key = TestKey::generate();
+        let provider = make_provider(&[&key]);
+        let mut claims = claims();
+        claims["aud"] = json!("some-other-client");
+        let id_token = key.sign(claims);
+
+        // Act
+        let result = validate_id_token(&id_token, &provider, CLIENT_ID, NONCE);
+
+        // Assert
+        assert_matches!(result, Err(ValidateIdTokenError::Other(_)));
+    }
+
+    #[test]
+    fn expired() {
+        // Arrange
+        let key = TestKey::generate();
+        let provider = make_provider(&[&key]);
+        let mut claims = claims();
+        claims["exp"] = json!(now() - 3600);
+        let id_token = key.sign(claims);
+
+        // Act
+        let result = validate_id_token(&id_token, &provider, CLIENT_ID, NONCE);
+
+        // Assert
+        assert_matches!(result, Err(ValidateIdTokenError::Other(_)));
+    }
+
+    struct TestKey {
+        kid: String,
+        pkcs8: Vec<u8>,
+        public_key: Vec<u8>,
+    }
+
+    impl TestKey {
+        fn generate() -> Self {
+            Self::generate_with_kid("test-key")
+        }
+
+        fn generate_with_kid(kid: &str) -> Self {
+            let rng = SystemRandom::new();
+            let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng)
+                .unwrap()
+                .as_ref()
+                .to_vec();
+            let public_key =
+                EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &pkcs8, &rng)
+                    .unwrap()
+                    .public_key()
+                    .as_ref()
+                    .to_vec();
+            Self {
+                kid: kid.into(),
+                pkcs8,
+                public_key,
+            }
+        }
+
+        fn jwk(&self) -> serde_json::Value {
+            // uncompressed point: 0x04 || x || y
+            let (x, y) = self.public_key[1..].split_at(32);
+            json!({
+                "kty": "EC",
+                "crv": "P-256",
+                "alg": "ES256",
+                "use": "sig",
+                "kid": self.kid,
+                "x": BASE64_URL_SAFE_NO_PAD.encode(x),
+                "y": BASE64_URL_SAFE_NO_PAD.encode(y),
+            })
+        }
+
+        fn sign(&self, claims: serde_json::Value) -> String {
+            let header = Header {
+                kid: Some(self.kid.clone()),
+                ..Header::new(Algorithm::ES256)
+            };
+            jsonwebtoken::encode(&header, &claims, &EncodingKey::from_ec_der(&self.pkcs8)).unwrap()
+        }
+    }
+
+    fn make_provider(keys: &[&TestKey]) -> OidcProvider {
+        let jwks = json!({ "keys": keys.iter().map(|k| k.jwk()).collect::<Vec<_>>() });
+        OidcProvider {
+            metadata: OidcProviderMetadata {
+                issuer: ISSUER.into(),
+                authorization_endpoint: "https://idp.example.com/auth".parse().unwrap(),
+                token_endpoint: "https://idp.example.com/token".parse().unwrap(),
+                jwks_uri: "https://idp.example.com/jwks".parse().unwrap(),
+            }
+            .into(),
+            jwks: serde_json::from_value::<JwkSet>(jwks).unwrap().into(),
+        }
+    }
+
+    fn claims() -> serde_json::Value {
+        json!({
+            "iss": ISSUER,
+            "aud": CLIENT_ID,
+            "sub": "the-remote-user-id",
+            "exp": now() + 3600,
+            "iat": now(),
+            "nonce": NONCE,
+            "name": "Foo Bar",
+            "preferred_username": "foo",
+            "email": "foo@example.com",
+            "email_verified": true,
+        })
+    }
+
+    fn now() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+}
@@ -0,0 +1,190 @@
+use std::{path::PathBuf, sync::Arc};
+
+use academy_di::Build;
+use academy_extern_contracts::pwned_passwords::PwnedPasswordsApiService;
+use academy_models::url::Url;
+use academy_utils::trace_instrument;
+use anyhow::{anyhow, Context};
+use sha1::{Digest, Sha1};
+use tracing::warn;
+
+use crate::http::HttpClient;
+
+#[derive(Debug, Clone, Build)]
+pub struct PwnedPasswordsApiServiceImpl {
+    config: PwnedPasswordsApiServiceConfig,
+    #[di(default)]
+    http: HttpClient,
+}
+
+#[derive(Debug, Clone)]
+pub struct PwnedPasswordsApiServiceConfig {
+    source: Option<PwnedPasswordsSource>,
+}
+
+/// Local source of the hash-prefix ranges. Password hashes are never sent to
+/// a third party.
+#[derive(Debug, Clone)]
+pub enum PwnedPasswordsSource {
+    /// Directory containing one `<PREFIX>.txt` file per hash prefix. A missing
+    /// file is treated like an empty range.
+    Dataset(Arc<PathBuf>),
+    /// Self-hosted implementation of the range API
+    /// (https://haveibeenpwned.com/API/v3#PwnedPasswords).
+    RangeEndpoint(Arc<Url>),
+}
+
+impl PwnedPasswordsApiServiceConfig {
+    /// Without a source, every breach check fails.
+    pub fn new(source: Option<PwnedPasswordsSource>) -> Self {
+        Self { source }
+    }
+}
+
+impl PwnedPasswordsApiService for PwnedPasswordsApiServiceImpl {
+    #[trace_instrument(skip(self, password))]
+    async fn get_breach_count(&self, password: &str) -> anyhow::Result<u64> {
+        let hash = hex::encode_upper(Sha1::digest(password.as_bytes()));
+        let (prefix, suffix) = hash.split_at(5);
+
+        let range = match &self.config.source {
+            Some(PwnedPasswordsSource::Dataset(path)) => {
+                let path = path.join(format!("{prefix}.txt"));
+                match tokio::fs::read_to_string(&path).await {
+                    Ok(range) => range,
+                    // an incomplete dataset must not prevent users from setting
+                    // a password
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                        warn!(
+                            "Pwned passwords range file {} does not exist",
+                            path.display()
+                        );
+                        return Ok(0);
+                    }
+                    Err(err) => {
+                        return Err(err).with_context(|| {
+                            format!(
+                                "Failed to read pwned passwords range file {}",
+                                path.display()
+                            )
+                        })
+                    }
+                }
+            }
+            Some(PwnedPasswordsSource::RangeEndpoint(range_endpoint)) => {
+                let url = range_endpoint
+                    .join(prefix)
+                    .context("Failed to build pwned passwords range URL")?;
+
+                self.http
+                    .get(url)
+                    .send()
+                    .await
+                    .context("Failed to send pwned passwords range request")?
+                    .error_for_status()
+                    .context("Pwned passwords range request returned an error")?
+                    .text()
+                    .await
+                    .context("Failed to read pwned passwords range response")?
+            }
+            None => return Err(anyhow!("No pwned passwords source configured")),
+        };
+
+        parse_range_response(&range, suffix)
+    }
+}
+
+/// Find the given hash suffix in a range response consisting of lines in the
+/// format `SUFFIX:COUNT`.
+fn parse_range_response(response: &str, suffix: &str) -> anyhow::Result<u64> {
+    for line in response.lines() {
+        let Some((line_suffix, count)) = line.trim().split_once(':') else {
+            continue;
+        };
+        if line_suffix.eq_ignore_ascii_case(suffix) {
+            return count
+                .parse()
+                .context("Failed to parse pwned passwords breach count");
+        }
+    }
+
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn get_breach_count_dataset() {
+        // Arrange
+        let dir = std::env::temp_dir().join(format!("academy-pwned-passwords-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        // sha1("password") = 5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8
+        std::fs::write(
+            dir.join("5BAA6.txt"),
+            "1E4C9B93F3F0682250B6CF8331B7EE68FD8:10434004\r\n",
+        )
+        .unwrap();
+
+        let sut = PwnedPasswordsApiServiceImpl {
+            config: PwnedPasswordsApiServiceConfig::new(Some(PwnedPasswordsSource::Dataset(
+                dir.clone().into(),
+            ))),
+            http: Default::default(),
+        };
+
+        // Act
+        let breached = sut.get_breach_count("password").await;
+        let missing_range = sut.get_breach_count("correct horse battery staple").await;
+
+        // Assert
+        assert_eq!(breached.unwrap(), 10434004);
+        assert_eq!(missing_range.unwrap(), 0);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn get_breach_count_no_source() {
+        // Arrange
+        let sut = PwnedPasswordsApiServiceImpl {
+            config: PwnedPasswordsApiServiceConfig::new(None),
+            http: Default::default(),
+        };
+
+        // Act
+        let result = sut.get_breach_count("password").await;
+
+        // Assert
+        result.unwrap_err();
+    }
+
+    #[test]
+    fn parse_range_response_found() {
+        // Arrange
+        let response = "0018A45C4D1DEF81644B54AB7F969B88D65:1\r\n\
+                        1E4C9B93F3F0682250B6CF8331B7EE68FD8:10434004\r\n\
+                        1E59C4D5C8E1B9B2F1A1B1E6A63D2B7F9A3:0\r\n";
+
+        // Act
+        let result = parse_range_response(response, "1E4C9B93F3F0682250B6CF8331B7EE68FD8").unwrap();
+
+        // Assert
+        assert_eq!(result, 10434004);
+    }
+
+    #[test]
+    fn parse_range_response_not_found() {
+        // Arrange
+        let response = "0018A45C4D1DEF81644B54AB7F969B88D65:1\r\n";
+
+        // Act
+        let result = parse_range_response(response, "1E4C9B93F3F0682250B6CF8331B7EE68FD8").unwrap();
+
+        // Assert
+        assert_eq!(result, 0);
+    }
+}
@@ -3,7 +3,10 @@ use std::{collections::HashMap, str::FromStr};
 use academy_extern_contracts::oauth2::{OAuth2ApiService, OAuth2ResolveCodeError};
 use academy_extern_impl::oauth2::OAuth2ApiServiceImpl;
 use academy_models::{
-    oauth2::{OAuth2Provider, OAuth2UserInfo},
+    oauth2::{
+        OAuth2Nonce, OAuth2PkceCodeVerifier, OAuth2Provider, OAuth2ProviderKind, OAuth2State,
+        OAuth2UserInfo,
+    },
     url::Url,
 };
 use academy_utils::assert_matches;
@@ -11,19 +14,24 @@ use academy_utils::assert_matches;
 #[tokio::test]
 async fn oauth2() {
     let provider = get_provider();
+    let state = OAuth2State::try_new("hyhk0a5cIEgOtSTtvb7MM4FBBHBPhxSu").unwrap();
+    let pkce_verifier = OAuth2PkceCodeVerifier::try_new(
+        "W1NhzrHUhfLDxK8WPoV0ufCTbU9hH2Y3Tgp2sR1QCbsOM8V6RMoP12ieQQNqmbu9",
+    )
+    .unwrap();
+    let nonce = OAuth2Nonce::try_new("tsF4ivBH8EmhQV8dOKEb2hlsvU6aKBSH").unwrap();
+
+    let sut = OAuth2ApiServiceImpl::default();
 
     let client = reqwest::Client::builder()
         .redirect(reqwest::redirect::Policy::none())
         .build()
         .unwrap();
 
-    let mut url = provider.auth_url.clone();
-    url.query_pairs_mut()
-        .append_pair("response_type", "code")
-        .append_pair("client_id", &provider.client_id)
-        .append_pair("state", "thestate")
-        .append_pair("redirect_uri", redirect_url().as_str())
-        .finish();
+    let url = sut
+        .generate_auth_url(&provider, &redirect_url(), &state, &pkce_verifier, &nonce)
+        .await
+        .unwrap();
     let form = HashMap::from([("id", "userid123"), ("name", "theremoteusername")]);
     let response = client
         .post(url.0)
@@ -42,16 +50,16 @@ async fn oauth2() {
         .parse::<Url>()
         .unwrap();
     let code = url.query_pairs().find(|(k, _)| *k == "code").unwrap().1;
-    let state = url.query_pairs().find(|(k, _)| *k == "state").unwrap().1;
-    assert_eq!(state, "thestate");
-
-    let sut = OAuth2ApiServiceImpl::default();
+    let returned_state = url.query_pairs().find(|(k, _)| *k == "state").unwrap().1;
+    assert_eq!(returned_state, state.as_str());
 
     let result = sut
         .resolve_code(
             provider.clone(),
             code.as_ref().try_into().unwrap(),
             redirect_url(),
+            pkce_verifier.clone(),
+            nonce.clone(),
         )
         .await
         .unwrap();
@@ -59,12 +67,20 @@ async fn oauth2() {
         result,
         OAuth2UserInfo {
             id: "userid123".try_into().unwrap(),
-            name: "theremoteusername".try_into().unwrap()
+            name: "theremoteusername".try_into().unwrap(),
+            email: None,
+            email_verified: false,
         }
     );
 
     let result = sut
-        .resolve_code(provider, "invalidcode".try_into().unwrap(), redirect_url())
+        .resolve_code(
+            provider,
+            "invalidcode".try_into().unwrap(),
+            redirect_url(),
+            pkce_verifier,
+            nonce,
+        )
         .await;
     assert_matches!(result, Err(OAuth2ResolveCodeError::InvalidCode));
 }
@@ -76,11 +92,13 @@ fn get_provider() -> OAuth2Provider {
         name: "test".into(),
         client_id: "client-id".into(),
         client_secret: Some("client-secret".into()),
-        auth_url: base_url.join("oauth2/authorize").unwrap().into(),
-        token_url: base_url.join("oauth2/token").unwrap().into(),
-        userinfo_url: base_url.join("user").unwrap().into(),
-        userinfo_id_key: "id".into(),
-        userinfo_name_key: "name".into(),
+        kind: OAuth2ProviderKind::OAuth2 {
+            auth_url: base_url.join("oauth2/authorize").unwrap().into(),
+            token_url: base_url.join("oauth2/token").unwrap().into(),
+            userinfo_url: base_url.join("user").unwrap().into(),
+            userinfo_id_key: "id".into(),
+            userinfo_name_key: "name".into(),
+        },
         scopes: vec![],
     }
 }
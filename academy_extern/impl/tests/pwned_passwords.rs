@@ -0,0 +1,41 @@
+use academy_di::{provider, Provide};
+use academy_extern_contracts::pwned_passwords::PwnedPasswordsApiService;
+use academy_extern_impl::pwned_passwords::{
+    PwnedPasswordsApiServiceConfig, PwnedPasswordsApiServiceImpl, PwnedPasswordsSource,
+};
+
+#[tokio::test]
+async fn breached() {
+    let sut = make_sut();
+    let result = sut.get_breach_count("password").await.unwrap();
+    assert!(result > 0);
+}
+
+#[tokio::test]
+async fn not_breached() {
+    let sut = make_sut();
+    let result = sut
+        .get_breach_count("correct horse battery staple 8a3f")
+        .await
+        .unwrap();
+    assert_eq!(result, 0);
+}
+
+fn make_sut() -> PwnedPasswordsApiServiceImpl {
+    let config = academy_config::load().unwrap();
+
+    provider! {
+        Provider { pwned_passwords_api_service_config: PwnedPasswordsApiServiceConfig, }
+    }
+
+    let mut provider = Provider {
+        _cache: Default::default(),
+        pwned_passwords_api_service_config: PwnedPasswordsApiServiceConfig::new(Some(
+            PwnedPasswordsSource::RangeEndpoint(
+                config.pwned_passwords.range_endpoint.unwrap().into(),
+            ),
+        )),
+    };
+
+    provider.provide()
+}
@@ -0,0 +1,179 @@
+use std::{net::IpAddr, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{macros::id, user::UserId};
+
+id!(AuditEventId);
+
+/// A record of a security-relevant action which has been performed on a user
+/// account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub id: AuditEventId,
+    /// The user who performed the action, if any.
+    pub actor_id: Option<UserId>,
+    /// The user affected by the action.
+    pub user_id: UserId,
+    pub action: AuditAction,
+    pub ip: Option<IpAddr>,
+    pub user_agent: Option<String>,
+    pub request_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Information about the request in which an auditable action is performed.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AuditContext {
+    pub ip: Option<IpAddr>,
+    pub user_agent: Option<String>,
+    pub request_id: Option<Uuid>,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// The user account has been updated.
+    UserUpdate,
+    /// The user account has been marked as deleted.
+    UserDelete,
+    /// The user account has been restored after being marked as deleted.
+    UserRestore,
+    /// A data export of the user account has been requested.
+    UserExport,
+    /// An administrator has logged in as the user.
+    UserImpersonate,
+    /// An administrator has lifted a lockout caused by failed login attempts.
+    UserUnlock,
+    /// A session created from an unknown device has been revoked using the
+    /// link in the new sign-in notification.
+    SessionRevoke,
+    /// A superseded refresh token has been presented again, which caused the
+    /// affected session to be revoked.
+    RefreshTokenReuse,
+    /// The password of the user has been reset using a verification code.
+    PasswordReset,
+    /// Multi-factor authentication has been enabled.
+    MfaEnable,
+    /// Multi-factor authentication has been disabled.
+    MfaDisable,
+    /// The MFA recovery codes have been replaced by a new set.
+    RecoveryCodesRegenerate,
+    /// An OAuth2 provider has been linked to the user account.
+    #[serde(rename = "oauth2_link")]
+    OAuth2Link,
+    /// An OAuth2 provider has been unlinked from the user account.
+    #[serde(rename = "oauth2_unlink")]
+    OAuth2Unlink,
+    /// A personal access token has been created.
+    PersonalAccessTokenCreate,
+    /// A personal access token has been revoked.
+    PersonalAccessTokenDelete,
+    /// A role has been assigned to the user.
+    RoleAssign,
+    /// A role has been removed from the user.
+    RoleRemove,
+    /// The user has authorized an OAuth2 client application to access their
+    /// account.
+    #[serde(rename = "oauth2_client_authorize")]
+    OAuth2ClientAuthorize,
+    /// A token issued to an OAuth2 client application has been revoked by
+    /// the client.
+    #[serde(rename = "oauth2_client_revoke")]
+    OAuth2ClientRevoke,
+}
+
+impl AuditAction {
+    pub const ALL: [Self; 20] = [
+        Self::UserUpdate,
+        Self::UserDelete,
+        Self::UserRestore,
+        Self::UserExport,
+        Self::UserImpersonate,
+        Self::UserUnlock,
+        Self::SessionRevoke,
+        Self::RefreshTokenReuse,
+        Self::PasswordReset,
+        Self::MfaEnable,
+        Self::MfaDisable,
+        Self::RecoveryCodesRegenerate,
+        Self::OAuth2Link,
+        Self::OAuth2Unlink,
+        Self::PersonalAccessTokenCreate,
+        Self::PersonalAccessTokenDelete,
+        Self::RoleAssign,
+        Self::RoleRemove,
+        Self::OAuth2ClientAuthorize,
+        Self::OAuth2ClientRevoke,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::UserUpdate => "user_update",
+            Self::UserDelete => "user_delete",
+            Self::UserRestore => "user_restore",
+            Self::UserExport => "user_export",
+            Self::UserImpersonate => "user_impersonate",
+            Self::UserUnlock => "user_unlock",
+            Self::SessionRevoke => "session_revoke",
+            Self::RefreshTokenReuse => "refresh_token_reuse",
+            Self::PasswordReset => "password_reset",
+            Self::MfaEnable => "mfa_enable",
+            Self::MfaDisable => "mfa_disable",
+            Self::RecoveryCodesRegenerate => "recovery_codes_regenerate",
+            Self::OAuth2Link => "oauth2_link",
+            Self::OAuth2Unlink => "oauth2_unlink",
+            Self::PersonalAccessTokenCreate => "personal_access_token_create",
+            Self::PersonalAccessTokenDelete => "personal_access_token_delete",
+            Self::RoleAssign => "role_assign",
+            Self::RoleRemove => "role_remove",
+            Self::OAuth2ClientAuthorize => "oauth2_client_authorize",
+            Self::OAuth2ClientRevoke => "oauth2_client_revoke",
+        }
+    }
+}
+
+impl FromStr for AuditAction {
+    type Err = InvalidAuditActionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|x| x.as_str() == s)
+            .ok_or_else(|| InvalidAuditActionError(s.into()))
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid audit action: {0:?}")]
+pub struct InvalidAuditActionError(pub String);
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AuditEventFilter {
+    pub user_id: Option<UserId>,
+    pub actor_id: Option<UserId>,
+    pub action: Option<AuditAction>,
+    /// Only include events which happened at or after this time.
+    pub after: Option<DateTime<Utc>>,
+    /// Only include events which happened before this time.
+    pub before: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audit_action_str_roundtrip() {
+        for action in AuditAction::ALL {
+            assert_eq!(action.as_str().parse::<AuditAction>().unwrap(), action);
+            assert_eq!(serde_json::to_value(action).unwrap(), action.as_str());
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-use crate::{macros::nutype_string, session::Session, user::UserComposite};
+use crate::{macros::nutype_string, role::Permission, session::Session, user::UserComposite};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Login {
@@ -22,6 +22,8 @@ pub enum AuthError {
 pub enum AuthenticateError {
     #[error("The access token is invalid or has expired.")]
     InvalidToken,
+    #[error("The personal access token can only be used for read-only requests.")]
+    ReadOnlyToken,
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -32,6 +34,10 @@ pub enum AuthorizeError {
     Admin,
     #[error("The user's email address is not verified.")]
     EmailVerified,
+    #[error("The user has not been authenticated using a session.")]
+    Session,
+    #[error("The user does not have the {0:?} permission.")]
+    Permission(Permission),
 }
 
 nutype_string!(AccessToken(sensitive));
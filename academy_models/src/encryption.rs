@@ -0,0 +1,22 @@
+/// Data that has been encrypted using one of the configured encryption keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedData {
+    /// Version of the key that has been used to encrypt the data or `None` if
+    /// the data has been stored without encryption.
+    pub key_version: Option<EncryptionKeyVersion>,
+    /// The encrypted data, prefixed with the nonce
+    pub data: Vec<u8>,
+}
+
+pub type EncryptionKeyVersion = u32;
+
+impl EncryptedData {
+    /// Wrap data that has not been encrypted (e.g. data that has been stored
+    /// before encryption was introduced).
+    pub fn unencrypted(data: Vec<u8>) -> Self {
+        Self {
+            key_version: None,
+            data,
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Algorithm used to sign JWTs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub enum JwtAlgorithm {
+    /// HMAC using SHA-256 (symmetric)
+    HS256,
+    /// RSASSA-PKCS1-v1_5 using SHA-256
+    RS256,
+    /// Ed25519
+    EdDSA,
+}
+
+impl JwtAlgorithm {
+    /// Whether tokens signed with this algorithm can be verified using only a
+    /// public key.
+    pub fn is_asymmetric(self) -> bool {
+        match self {
+            Self::HS256 => false,
+            Self::RS256 | Self::EdDSA => true,
+        }
+    }
+}
+
+/// A JSON Web Key Set as defined in RFC 7517
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct Jwks {
+    pub keys: Vec<Jwk>,
+}
+
+/// The public part of a key that is used to sign JWTs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct Jwk {
+    /// Key ID, matches the `kid` header of the JWTs signed with this key
+    pub kid: String,
+    pub alg: JwtAlgorithm,
+    /// Always `sig`
+    #[serde(rename = "use")]
+    pub use_: JwkUse,
+    #[serde(flatten)]
+    pub params: JwkParams,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JwkUse {
+    Sig,
+}
+
+/// The key type and the (base64url encoded) public key parameters
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(tag = "kty")]
+pub enum JwkParams {
+    #[serde(rename = "RSA")]
+    Rsa {
+        /// Modulus
+        n: String,
+        /// Public exponent
+        e: String,
+    },
+    #[serde(rename = "OKP")]
+    Okp {
+        /// Always `Ed25519`
+        crv: JwkCurve,
+        /// Public key
+        x: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+pub enum JwkCurve {
+    Ed25519,
+}
@@ -5,16 +5,24 @@ use regex::Regex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+pub mod audit;
 pub mod auth;
 pub mod contact;
 pub mod email_address;
+pub mod encryption;
+pub mod jwt;
 mod macros;
 pub mod mfa;
 pub mod oauth2;
+pub mod oauth2_server;
 pub mod pagination;
+pub mod personal_access_token;
+pub mod rate_limit;
+pub mod role;
 pub mod session;
 pub mod url;
 pub mod user;
+pub mod user_export;
 
 #[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Sha256Hash(#[serde(with = "academy_utils::serde::hex")] pub [u8; 32]);
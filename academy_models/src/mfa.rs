@@ -4,26 +4,34 @@ use academy_utils::patch::Patch;
 use chrono::{DateTime, Utc};
 use nutype::nutype;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     hyphenated_code_regex,
     macros::{id, nutype_string, sensitive_debug, sha256hash},
-    user::UserId,
+    user::{UserDisplayName, UserId, UserName},
 };
 
 id!(TotpDeviceId);
 
-#[derive(Debug, Clone, PartialEq, Eq, Patch)]
+#[derive(Debug, Clone, PartialEq, Eq, Patch, Serialize, Deserialize)]
 pub struct TotpDevice {
     #[no_patch]
     pub id: TotpDeviceId,
     #[no_patch]
     pub user_id: UserId,
+    pub name: TotpDeviceName,
     pub enabled: bool,
     #[no_patch]
     pub created_at: DateTime<Utc>,
 }
 
+nutype_string!(TotpDeviceName(
+    validate(len_char_min = 1, len_char_max = 64),
+    derive(Default),
+    default = "Authenticator"
+));
+
 nutype_string!(TotpCode(validate(regex = TOTP_CODE_REGEX)));
 pub static TOTP_CODE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new("^[0-9]{6}$").unwrap());
 
@@ -72,8 +80,100 @@ impl MfaRecoveryCode {
 
 sha256hash!(MfaRecoveryCodeHash);
 
+id!(WebauthnDeviceId);
+
+#[derive(Debug, Clone, PartialEq, Eq, Patch)]
+pub struct WebauthnDevice {
+    #[no_patch]
+    pub id: WebauthnDeviceId,
+    #[no_patch]
+    pub user_id: UserId,
+    pub name: WebauthnDeviceName,
+    #[no_patch]
+    pub credential_id: WebauthnCredentialId,
+    #[no_patch]
+    pub public_key: WebauthnPublicKey,
+    /// The signature counter of the authenticator (used to detect cloned
+    /// authenticators).
+    pub sign_count: u32,
+    #[no_patch]
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+nutype_string!(WebauthnDeviceName(validate(
+    len_char_min = 1,
+    len_char_max = 64
+)));
+
+#[nutype(
+    validate(predicate = |x| !x.is_empty() && x.len() <= 1023),
+    derive(Debug, Clone, PartialEq, Eq, Hash, Deref, TryFrom, AsRef)
+)]
+pub struct WebauthnCredentialId(Vec<u8>);
+
+/// COSE encoded public key of a WebAuthn credential.
+#[nutype(derive(Debug, Clone, PartialEq, Eq, Deref, From))]
+pub struct WebauthnPublicKey(Vec<u8>);
+
+#[nutype(derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Deref,
+    From,
+    AsRef,
+    Serialize,
+    Deserialize
+))]
+pub struct WebauthnChallenge(Vec<u8>);
+
+/// The relying party (i.e. this application) as seen by WebAuthn
+/// authenticators.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebauthnRelyingParty {
+    pub id: String,
+    pub name: String,
+}
+
+/// Options for `navigator.credentials.create()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebauthnRegistrationOptions {
+    pub challenge: WebauthnChallenge,
+    pub rp: WebauthnRelyingParty,
+    pub user_id: UserId,
+    pub user_name: UserName,
+    pub user_display_name: UserDisplayName,
+    pub exclude_credentials: Vec<WebauthnCredentialId>,
+}
+
+/// Options for `navigator.credentials.get()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebauthnAssertionOptions {
+    pub challenge: WebauthnChallenge,
+    pub rp_id: String,
+}
+
+/// Response of an authenticator to `navigator.credentials.create()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebauthnRegistration {
+    pub client_data_json: Vec<u8>,
+    pub attestation_object: Vec<u8>,
+}
+
+/// Response of an authenticator to `navigator.credentials.get()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebauthnAssertion {
+    pub credential_id: WebauthnCredentialId,
+    pub client_data_json: Vec<u8>,
+    pub authenticator_data: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct MfaAuthentication {
     pub totp_code: Option<TotpCode>,
     pub recovery_code: Option<MfaRecoveryCode>,
+    pub webauthn_assertion: Option<WebauthnAssertion>,
 }
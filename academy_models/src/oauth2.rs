@@ -2,9 +2,11 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    email_address::EmailAddress,
     macros::{id, nutype_string},
     url::Url,
     user::UserId,
+    Sha256Hash,
 };
 
 id!(OAuth2LinkId);
@@ -14,22 +16,38 @@ pub struct OAuth2Provider {
     pub name: OAuth2ProviderName,
     pub client_id: String,
     pub client_secret: Option<OAuth2ProviderClientSecret>,
-    pub auth_url: Url,
-    pub token_url: Url,
-    pub userinfo_url: Url,
-    pub userinfo_id_key: String,
-    pub userinfo_name_key: String,
+    pub kind: OAuth2ProviderKind,
     pub scopes: Vec<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(
+    clippy::large_enum_variant,
+    reason = "providers are loaded once from the config and rarely moved"
+)]
+pub enum OAuth2ProviderKind {
+    /// Plain OAuth2 provider. The remote user is fetched from the userinfo
+    /// endpoint using the configured JSON keys.
+    OAuth2 {
+        auth_url: Url,
+        token_url: Url,
+        userinfo_url: Url,
+        userinfo_id_key: String,
+        userinfo_name_key: String,
+    },
+    /// OpenID Connect provider. The endpoints are discovered using the
+    /// issuer's `.well-known/openid-configuration` document and the remote
+    /// user is taken from the validated ID token.
+    Oidc { issuer_url: Url },
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OAuth2ProviderSummary {
     pub id: OAuth2ProviderId,
     pub name: OAuth2ProviderName,
-    pub auth_url: Url,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct OAuth2Link {
     pub id: OAuth2LinkId,
     pub user_id: UserId,
@@ -42,6 +60,13 @@ pub struct OAuth2Link {
 pub struct OAuth2UserInfo {
     pub id: OAuth2RemoteUserId,
     pub name: OAuth2RemoteUserName,
+    /// Email address of the remote user (only available for OpenID Connect
+    /// providers)
+    #[serde(default)]
+    pub email: Option<EmailAddress>,
+    /// Whether the provider has verified the email address
+    #[serde(default)]
+    pub email_verified: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -49,6 +74,27 @@ pub struct OAuth2Login {
     pub provider_id: OAuth2ProviderId,
     pub code: OAuth2AuthorizationCode,
     pub redirect_uri: Url,
+    pub state: OAuth2State,
+    pub binding: OAuth2LoginBinding,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuth2AuthorizationRequest {
+    pub auth_url: Url,
+    pub state: OAuth2State,
+    /// Secret which is only known to the client that has started the login
+    /// and must be presented again to complete it.
+    pub binding: OAuth2LoginBinding,
+}
+
+/// A pending OAuth2 login that has been started, but not yet completed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OAuth2LoginAttempt {
+    pub provider_id: OAuth2ProviderId,
+    pub redirect_uri: Url,
+    pub pkce_verifier: OAuth2PkceCodeVerifier,
+    pub nonce: OAuth2Nonce,
+    pub binding_hash: Sha256Hash,
 }
 
 nutype_string!(OAuth2ProviderId);
@@ -60,6 +106,50 @@ nutype_string!(OAuth2AuthorizationCode(
     validate(len_char_max = 256)
 ));
 
+nutype_string!(OAuth2State(
+    sensitive,
+    validate(
+        len_char_min = OAuth2State::LEN,
+        len_char_max = OAuth2State::LEN
+    )
+));
+impl OAuth2State {
+    pub const LEN: usize = 32;
+}
+
+nutype_string!(OAuth2PkceCodeVerifier(
+    sensitive,
+    validate(
+        len_char_min = OAuth2PkceCodeVerifier::LEN,
+        len_char_max = OAuth2PkceCodeVerifier::LEN
+    )
+));
+impl OAuth2PkceCodeVerifier {
+    pub const LEN: usize = 64;
+}
+
+nutype_string!(OAuth2Nonce(
+    sensitive,
+    validate(
+        len_char_min = OAuth2Nonce::LEN,
+        len_char_max = OAuth2Nonce::LEN
+    )
+));
+impl OAuth2Nonce {
+    pub const LEN: usize = 32;
+}
+
+nutype_string!(OAuth2LoginBinding(
+    sensitive,
+    validate(
+        len_char_min = OAuth2LoginBinding::LEN,
+        len_char_max = OAuth2LoginBinding::LEN
+    )
+));
+impl OAuth2LoginBinding {
+    pub const LEN: usize = 32;
+}
+
 nutype_string!(OAuth2RemoteUserId(validate(len_char_max = 256)));
 nutype_string!(OAuth2RemoteUserName(validate(len_char_max = 256)));
 
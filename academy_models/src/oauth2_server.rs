@@ -0,0 +1,316 @@
+use std::{collections::BTreeSet, str::FromStr, time::Duration};
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    email_address::EmailAddress,
+    macros::{id, nutype_string, sha256hash},
+    session::SessionId,
+    url::Url,
+    user::{UserComposite, UserDisplayName, UserId, UserName},
+};
+
+id!(OAuth2ClientId);
+
+/// A third-party application which can request access to user accounts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuth2Client {
+    pub id: OAuth2ClientId,
+    pub name: OAuth2ClientName,
+    /// Exact redirect URIs the client is allowed to use.
+    pub redirect_uris: Vec<Url>,
+    /// Whether the client has a secret it must use to authenticate itself.
+    /// Public clients (e.g. native or single-page apps) rely on PKCE only.
+    pub confidential: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+nutype_string!(OAuth2ClientName(validate(
+    len_char_min = 1,
+    len_char_max = 64
+)));
+
+nutype_string!(OAuth2ClientSecret(
+    sensitive,
+    validate(
+        len_char_min = OAuth2ClientSecret::LEN,
+        len_char_max = OAuth2ClientSecret::LEN
+    )
+));
+impl OAuth2ClientSecret {
+    pub const LEN: usize = 64;
+}
+
+sha256hash!(OAuth2ClientSecretHash);
+
+/// The credentials a client uses to authenticate itself on the token,
+/// revocation and introspection endpoints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuth2ClientCredentials {
+    pub client_id: OAuth2ClientId,
+    pub client_secret: Option<OAuth2ClientSecret>,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum OAuth2Scope {
+    /// Request an OpenID Connect ID token.
+    Openid,
+    /// Access the user's name and display name.
+    Profile,
+    /// Access the user's email address.
+    Email,
+}
+
+impl OAuth2Scope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Openid => "openid",
+            Self::Profile => "profile",
+            Self::Email => "email",
+        }
+    }
+}
+
+impl FromStr for OAuth2Scope {
+    type Err = OAuth2InvalidScopeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "openid" => Ok(Self::Openid),
+            "profile" => Ok(Self::Profile),
+            "email" => Ok(Self::Email),
+            _ => Err(OAuth2InvalidScopeError(s.into())),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid scope: {0:?}")]
+pub struct OAuth2InvalidScopeError(pub String);
+
+/// A set of scopes, formatted as a space-delimited list.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OAuth2Scopes(pub BTreeSet<OAuth2Scope>);
+
+impl OAuth2Scopes {
+    pub fn contains(&self, scope: OAuth2Scope) -> bool {
+        self.0.contains(&scope)
+    }
+}
+
+impl FromIterator<OAuth2Scope> for OAuth2Scopes {
+    fn from_iter<T: IntoIterator<Item = OAuth2Scope>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl FromStr for OAuth2Scopes {
+    type Err = OAuth2InvalidScopeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split_ascii_whitespace().map(str::parse).collect()
+    }
+}
+
+impl std::fmt::Display for OAuth2Scopes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, scope) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(scope.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// An authorization request sent by a client via the user's browser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuth2AuthorizeRequest {
+    pub client_id: OAuth2ClientId,
+    pub redirect_uri: Url,
+    pub scopes: OAuth2Scopes,
+    pub state: Option<OAuth2ClientState>,
+    /// The S256 PKCE code challenge
+    pub code_challenge: OAuth2PkceCodeChallenge,
+    pub nonce: Option<OAuth2ClientNonce>,
+}
+
+/// The data the consent screen displays before the user authorizes a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuth2Consent {
+    pub client: OAuth2Client,
+    pub scopes: OAuth2Scopes,
+}
+
+/// An authorization which has been granted by a user, but not yet exchanged
+/// for tokens by the client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OAuth2Authorization {
+    pub client_id: OAuth2ClientId,
+    pub user_id: UserId,
+    pub redirect_uri: Url,
+    pub scopes: OAuth2Scopes,
+    pub code_challenge: OAuth2PkceCodeChallenge,
+    pub nonce: Option<OAuth2ClientNonce>,
+}
+
+/// Access to a user account granted to a client. Each grant is backed by a
+/// session of the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuth2Grant {
+    pub session_id: SessionId,
+    pub client_id: OAuth2ClientId,
+    pub scopes: OAuth2Scopes,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OAuth2TokenRequest {
+    AuthorizationCode {
+        code: OAuth2ServerAuthorizationCode,
+        redirect_uri: Url,
+        code_verifier: OAuth2ClientPkceCodeVerifier,
+    },
+    RefreshToken {
+        refresh_token: OAuth2ServerRefreshToken,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuth2TokenResponse {
+    pub access_token: OAuth2ServerAccessToken,
+    pub refresh_token: OAuth2ServerRefreshToken,
+    /// Only included if the `openid` scope has been granted
+    pub id_token: Option<OAuth2ServerIdToken>,
+    pub expires_in: Duration,
+    pub scopes: OAuth2Scopes,
+}
+
+/// Information about an active access or refresh token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuth2TokenInfo {
+    pub user_id: UserId,
+    pub session_id: SessionId,
+    pub client_id: OAuth2ClientId,
+    pub scopes: OAuth2Scopes,
+}
+
+/// The OpenID Connect claims about a user that a client may access.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct OAuth2ServerUserInfo {
+    pub sub: UserId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_username: Option<UserName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<UserDisplayName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<EmailAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_verified: Option<bool>,
+}
+
+impl OAuth2ServerUserInfo {
+    /// Return the claims of the given user that are covered by `scopes`.
+    pub fn new(user_composite: &UserComposite, scopes: &OAuth2Scopes) -> Self {
+        let profile = scopes.contains(OAuth2Scope::Profile);
+        let email = scopes.contains(OAuth2Scope::Email);
+        Self {
+            sub: user_composite.user.id,
+            preferred_username: profile.then(|| user_composite.user.name.clone()),
+            name: profile.then(|| user_composite.profile.display_name.clone()),
+            email: email.then(|| user_composite.user.email.clone()).flatten(),
+            email_verified: email.then_some(user_composite.user.email_verified),
+        }
+    }
+}
+
+nutype_string!(OAuth2ClientState(validate(len_char_max = 1024)));
+nutype_string!(OAuth2ClientNonce(validate(len_char_max = 256)));
+
+nutype_string!(OAuth2PkceCodeChallenge(validate(
+    len_char_min = 43,
+    len_char_max = 128
+)));
+nutype_string!(OAuth2ClientPkceCodeVerifier(
+    sensitive,
+    validate(len_char_min = 43, len_char_max = 128)
+));
+
+nutype_string!(OAuth2ServerAuthorizationCode(
+    sensitive,
+    validate(
+        len_char_min = OAuth2ServerAuthorizationCode::LEN,
+        len_char_max = OAuth2ServerAuthorizationCode::LEN
+    )
+));
+impl OAuth2ServerAuthorizationCode {
+    pub const LEN: usize = 64;
+}
+
+nutype_string!(OAuth2ServerAccessToken(sensitive));
+nutype_string!(OAuth2ServerIdToken(sensitive));
+
+nutype_string!(OAuth2ServerRefreshToken(
+    sensitive,
+    validate(
+        len_char_min = OAuth2ServerRefreshToken::LEN,
+        len_char_max = OAuth2ServerRefreshToken::LEN
+    )
+));
+impl OAuth2ServerRefreshToken {
+    pub const LEN: usize = 64;
+}
+
+sha256hash!(OAuth2ServerRefreshTokenHash);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scopes_from_str() {
+        // Act
+        let result = "  profile openid profile  email".parse::<OAuth2Scopes>();
+
+        // Assert
+        assert_eq!(
+            result.unwrap(),
+            [
+                OAuth2Scope::Openid,
+                OAuth2Scope::Profile,
+                OAuth2Scope::Email
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
+
+    #[test]
+    fn scopes_from_str_invalid() {
+        // Act
+        let result = "openid admin".parse::<OAuth2Scopes>();
+
+        // Assert
+        assert_eq!(result.unwrap_err().0, "admin");
+    }
+
+    #[test]
+    fn scopes_to_string() {
+        // Arrange
+        let scopes = [OAuth2Scope::Email, OAuth2Scope::Openid]
+            .into_iter()
+            .collect::<OAuth2Scopes>();
+
+        // Act
+        let result = scopes.to_string();
+
+        // Assert
+        assert_eq!(result, "openid email");
+    }
+}
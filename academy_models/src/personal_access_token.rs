@@ -0,0 +1,85 @@
+use std::{collections::BTreeSet, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    macros::{id, nutype_string, sha256hash},
+    user::UserId,
+};
+
+id!(PersonalAccessTokenId);
+
+/// A long-lived token which can be used instead of an access token, e.g. by
+/// scripts or CLI tools.
+///
+/// Personal access tokens cannot be used to change the credentials, email
+/// addresses, MFA devices or login methods of an account or to delete it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonalAccessToken {
+    pub id: PersonalAccessTokenId,
+    pub user_id: UserId,
+    pub name: PersonalAccessTokenName,
+    pub scopes: BTreeSet<PersonalAccessTokenScope>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl PersonalAccessToken {
+    /// Prefix of all personal access tokens, used to distinguish them from
+    /// access tokens.
+    pub const PREFIX: &str = "bapat_";
+    /// Length of the random part of a personal access token
+    pub const SECRET_LEN: usize = 48;
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+nutype_string!(PersonalAccessTokenName(validate(
+    len_char_min = 1,
+    len_char_max = 64
+)));
+
+sha256hash!(PersonalAccessTokenHash);
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PersonalAccessTokenScope {
+    /// Use the administrator privileges and role permissions of the user.
+    /// Tokens without this scope are treated as tokens of a regular user.
+    Admin,
+    /// Only allow requests which do not modify any data.
+    ReadOnly,
+}
+
+impl PersonalAccessTokenScope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Admin => "admin",
+            Self::ReadOnly => "read_only",
+        }
+    }
+}
+
+impl FromStr for PersonalAccessTokenScope {
+    type Err = PersonalAccessTokenInvalidScopeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "admin" => Ok(Self::Admin),
+            "read_only" => Ok(Self::ReadOnly),
+            _ => Err(PersonalAccessTokenInvalidScopeError(s.into())),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid scope: {0:?}")]
+pub struct PersonalAccessTokenInvalidScopeError(pub String);
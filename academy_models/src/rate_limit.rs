@@ -0,0 +1,45 @@
+use serde::Deserialize;
+
+/// A group of endpoints which share a rate limit policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitGroup {
+    /// Login (session creation)
+    Login,
+    /// Account creation
+    Signup,
+    /// Password reset requests
+    PasswordReset,
+    /// Verification email requests
+    VerificationEmail,
+    /// Contact form
+    Contact,
+    /// User data export requests
+    UserExport,
+}
+
+impl RateLimitGroup {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Login => "login",
+            Self::Signup => "signup",
+            Self::PasswordReset => "password_reset",
+            Self::VerificationEmail => "verification_email",
+            Self::Contact => "contact",
+            Self::UserExport => "user_export",
+        }
+    }
+}
+
+/// The subject a rate limit counter is tracked for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitKey {
+    /// Track requests per client ip address
+    Ip,
+    /// Track requests per authenticated user (unauthenticated requests are
+    /// tracked per client ip address)
+    User,
+    /// Track all requests to the route group together
+    Route,
+}
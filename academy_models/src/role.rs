@@ -0,0 +1,95 @@
+use std::{collections::BTreeSet, str::FromStr};
+
+use academy_utils::patch::Patch;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::macros::{id, nutype_string};
+
+id!(RoleId);
+
+/// A named set of permissions which can be granted to users who should not
+/// receive full administrator privileges.
+#[derive(Debug, Clone, PartialEq, Eq, Patch)]
+pub struct Role {
+    #[no_patch]
+    pub id: RoleId,
+    pub name: RoleName,
+    pub permissions: BTreeSet<Permission>,
+    #[no_patch]
+    pub created_at: DateTime<Utc>,
+}
+
+nutype_string!(RoleName(validate(len_char_min = 1, len_char_max = 32)));
+
+/// A permission which can be granted to users via roles.
+///
+/// Administrators implicitly have all permissions.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    /// List and read all user accounts.
+    ReadUsers,
+    /// Request verification emails for other users.
+    ResendVerificationEmails,
+    /// Enable or disable user accounts of non-administrators.
+    DisableUsers,
+    /// Edit the invoice information of other users.
+    EditInvoiceInfo,
+}
+
+impl Permission {
+    pub const ALL: [Self; 4] = [
+        Self::ReadUsers,
+        Self::ResendVerificationEmails,
+        Self::DisableUsers,
+        Self::EditInvoiceInfo,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ReadUsers => "read_users",
+            Self::ResendVerificationEmails => "resend_verification_emails",
+            Self::DisableUsers => "disable_users",
+            Self::EditInvoiceInfo => "edit_invoice_info",
+        }
+    }
+}
+
+impl FromStr for Permission {
+    type Err = InvalidPermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|x| x.as_str() == s)
+            .ok_or_else(|| InvalidPermissionError(s.into()))
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid permission: {0:?}")]
+pub struct InvalidPermissionError(pub String);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permission_str_roundtrip() {
+        for permission in Permission::ALL {
+            assert_eq!(
+                permission.as_str().parse::<Permission>().unwrap(),
+                permission
+            );
+            assert_eq!(
+                serde_json::to_value(permission).unwrap(),
+                permission.as_str()
+            );
+        }
+    }
+}
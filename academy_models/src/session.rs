@@ -1,25 +1,67 @@
+use std::{net::IpAddr, sync::LazyLock};
+
 use academy_utils::patch::Patch;
 use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     macros::{id, nutype_string, sha256hash},
+    oauth2::OAuth2ProviderId,
     user::UserId,
 };
 
 id!(SessionId);
 
-#[derive(Debug, Clone, PartialEq, Eq, Patch)]
+#[derive(Debug, Clone, PartialEq, Eq, Patch, Serialize, Deserialize)]
 pub struct Session {
     #[no_patch]
     pub id: SessionId,
     #[no_patch]
     pub user_id: UserId,
     pub device_name: Option<DeviceName>,
+    /// The ip address of the client which created the session.
+    #[no_patch]
+    pub ip: Option<IpAddr>,
+    /// Hash of the user agent of the client which created the session.
+    #[no_patch]
+    pub device_fingerprint: Option<SessionDeviceFingerprint>,
+    /// The ip address of the client which has most recently refreshed the
+    /// session.
+    pub last_ip: Option<IpAddr>,
+    /// The browser of the client which created the session, as parsed from
+    /// its user agent.
+    #[no_patch]
+    pub browser: Option<String>,
+    /// The operating system of the client which created the session, as
+    /// parsed from its user agent.
+    #[no_patch]
+    pub os: Option<String>,
+    #[no_patch]
+    pub login_method: SessionLoginMethod,
     #[no_patch]
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// The method which has been used to create a session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionLoginMethod {
+    /// Username/email and password
+    Password,
+    /// Remote user of an external OAuth2 provider
+    #[serde(rename = "oauth2")]
+    OAuth2(OAuth2ProviderId),
+    /// Impersonation by an administrator
+    Impersonation,
+    /// Authorization of an OAuth2 client application
+    #[serde(rename = "oauth2_client")]
+    OAuth2Client,
+    /// One-time login code sent via email
+    LoginCode,
+}
+
 nutype_string!(DeviceName(validate(len_char_max = DeviceName::MAX_LEN)));
 
 impl DeviceName {
@@ -31,7 +73,15 @@ impl DeviceName {
     }
 }
 
+nutype_string!(SessionLoginCode(
+    sensitive,
+    validate(regex = SESSION_LOGIN_CODE_REGEX)
+));
+pub static SESSION_LOGIN_CODE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new("^[0-9]{6}$").unwrap());
+
 sha256hash!(SessionRefreshTokenHash);
+sha256hash!(SessionDeviceFingerprint);
 
 #[cfg(test)]
 mod tests {
@@ -9,7 +9,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     email_address::EmailAddress,
-    macros::{id, nutype_string},
+    macros::{id, nutype_string, sha256hash},
+    url::Url,
     SearchTerm,
 };
 
@@ -39,7 +40,7 @@ impl UserIdOrSelf {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UserComposite {
     pub user: User,
     pub profile: UserProfile,
@@ -47,7 +48,7 @@ pub struct UserComposite {
     pub invoice_info: UserInvoiceInfo,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Patch)]
+#[derive(Debug, Clone, PartialEq, Eq, Patch, Serialize, Deserialize)]
 pub struct User {
     #[no_patch]
     pub id: UserId,
@@ -61,23 +62,28 @@ pub struct User {
     pub enabled: bool,
     pub admin: bool,
     pub newsletter: bool,
+    /// Timestamp at which the user requested the deletion of their account.
+    /// The account is purged once the deletion grace period has expired.
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Patch)]
+#[derive(Debug, Clone, PartialEq, Eq, Patch, Serialize, Deserialize)]
 pub struct UserProfile {
     pub display_name: UserDisplayName,
     pub bio: UserBio,
     pub tags: UserTags,
+    /// URL of the uploaded avatar image (in the default size)
+    pub avatar_url: Option<Url>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UserDetails {
     pub mfa_enabled: bool,
     pub password_login: bool,
     pub oauth2_login: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Patch, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Patch, Default, Serialize, Deserialize)]
 pub struct UserInvoiceInfo {
     pub business: Option<bool>,
     pub first_name: Option<UserFirstName>,
@@ -89,6 +95,33 @@ pub struct UserInvoiceInfo {
     pub vat_id: Option<UserVatId>,
 }
 
+/// An additional verified email address of a user.
+///
+/// The primary email address is stored in [`User::email`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserEmail {
+    pub user_id: UserId,
+    pub email: EmailAddress,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A previous name of a user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserNameHistoryEntry {
+    pub user_id: UserId,
+    pub name: UserName,
+    /// Timestamp at which the user stopped using this name.
+    pub changed_at: DateTime<Utc>,
+}
+
+impl User {
+    /// Whether the user is allowed to log in, i.e. the account is enabled and
+    /// has not been marked as deleted.
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.deleted_at.is_none()
+    }
+}
+
 impl UserComposite {
     pub fn can_receive_coins(&self) -> bool {
         self.user.email_verified
@@ -110,6 +143,8 @@ impl UserComposite {
     }
 }
 
+sha256hash!(UserRestoreCodeHash);
+
 nutype_string!(UserName(validate(regex = USER_NAME_REGEX)));
 nutype_string!(UserDisplayName(validate(
     len_char_min = 1,
@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    audit::AuditEvent,
+    mfa::{TotpDevice, WebauthnDevice, WebauthnDeviceId, WebauthnDeviceName},
+    oauth2::OAuth2Link,
+    session::Session,
+    user::{UserComposite, UserEmail, UserNameHistoryEntry},
+};
+
+/// An archive of all data stored about a user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserExport {
+    pub created_at: DateTime<Utc>,
+    pub user_composite: UserComposite,
+    pub emails: Vec<UserEmail>,
+    pub name_history: Vec<UserNameHistoryEntry>,
+    pub sessions: Vec<Session>,
+    pub oauth2_links: Vec<OAuth2Link>,
+    pub totp_devices: Vec<TotpDevice>,
+    pub webauthn_devices: Vec<UserExportWebauthnDevice>,
+    pub audit_events: Vec<AuditEvent>,
+}
+
+/// The metadata of a [`WebauthnDevice`], without any credential material.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserExportWebauthnDevice {
+    pub id: WebauthnDeviceId,
+    pub name: WebauthnDeviceName,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<WebauthnDevice> for UserExportWebauthnDevice {
+    fn from(value: WebauthnDevice) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            created_at: value.created_at,
+            last_used_at: value.last_used_at,
+        }
+    }
+}
@@ -1,7 +1,11 @@
 use std::future::Future;
 
+pub mod audit;
 pub mod mfa;
 pub mod oauth2;
+pub mod oauth2_server;
+pub mod personal_access_token;
+pub mod role;
 pub mod session;
 pub mod user;
 
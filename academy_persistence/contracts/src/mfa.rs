@@ -1,7 +1,11 @@
 use std::future::Future;
 
 use academy_models::{
-    mfa::{MfaRecoveryCodeHash, TotpDevice, TotpDeviceId, TotpDevicePatchRef, TotpSecret},
+    encryption::EncryptedData,
+    mfa::{
+        MfaRecoveryCodeHash, TotpDevice, TotpDeviceId, TotpDevicePatchRef, WebauthnCredentialId,
+        WebauthnDevice, WebauthnDeviceId, WebauthnDevicePatchRef,
+    },
     user::UserId,
 };
 
@@ -14,12 +18,19 @@ pub trait MfaRepository<Txn: Send + Sync + 'static>: Send + Sync + 'static {
         user_id: UserId,
     ) -> impl Future<Output = anyhow::Result<Vec<TotpDevice>>> + Send;
 
-    /// Create a new TOTP device and set the associated secret.
+    /// Return the TOTP device with the given id.
+    fn get_totp_device(
+        &self,
+        txn: &mut Txn,
+        totp_device_id: TotpDeviceId,
+    ) -> impl Future<Output = anyhow::Result<Option<TotpDevice>>> + Send;
+
+    /// Create a new TOTP device and set the associated encrypted secret.
     fn create_totp_device(
         &self,
         txn: &mut Txn,
         totp_device: &TotpDevice,
-        secret: &TotpSecret,
+        secret: &EncryptedData,
     ) -> impl Future<Output = anyhow::Result<()>> + Send;
 
     /// Update an existing TOTP device.
@@ -30,6 +41,13 @@ pub trait MfaRepository<Txn: Send + Sync + 'static>: Send + Sync + 'static {
         patch: TotpDevicePatchRef<'a>,
     ) -> impl Future<Output = anyhow::Result<bool>> + Send;
 
+    /// Delete the given TOTP device.
+    fn delete_totp_device(
+        &self,
+        txn: &mut Txn,
+        totp_device_id: TotpDeviceId,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
     /// Delete all TOTP devices of the given user.
     fn delete_totp_devices_by_user(
         &self,
@@ -37,48 +55,115 @@ pub trait MfaRepository<Txn: Send + Sync + 'static>: Send + Sync + 'static {
         user_id: UserId,
     ) -> impl Future<Output = anyhow::Result<()>> + Send;
 
-    /// Return the secrets of all enabled TOTP devices of the given user.
+    /// Return the encrypted secrets of all enabled TOTP devices of the given
+    /// user.
     fn list_enabled_totp_device_secrets_by_user(
         &self,
         txn: &mut Txn,
         user_id: UserId,
-    ) -> impl Future<Output = anyhow::Result<Vec<TotpSecret>>> + Send;
+    ) -> impl Future<Output = anyhow::Result<Vec<EncryptedData>>> + Send;
+
+    /// Return the encrypted secrets of all TOTP devices.
+    fn list_totp_device_secrets(
+        &self,
+        txn: &mut Txn,
+    ) -> impl Future<Output = anyhow::Result<Vec<(TotpDeviceId, EncryptedData)>>> + Send;
 
-    /// Return the secret of the given TOTP device.
+    /// Return the encrypted secret of the given TOTP device.
     fn get_totp_device_secret(
         &self,
         txn: &mut Txn,
         totp_device_id: TotpDeviceId,
-    ) -> impl Future<Output = anyhow::Result<TotpSecret>> + Send;
+    ) -> impl Future<Output = anyhow::Result<EncryptedData>> + Send;
 
-    /// Update the secret of the given TOTP device.
+    /// Update the encrypted secret of the given TOTP device.
     fn save_totp_device_secret(
         &self,
         txn: &mut Txn,
         totp_device_id: TotpDeviceId,
-        secret: &TotpSecret,
+        secret: &EncryptedData,
     ) -> impl Future<Output = anyhow::Result<()>> + Send;
 
-    /// Return the MFA recovery code hash of the given user.
-    fn get_mfa_recovery_code_hash(
+    /// Return the number of unused MFA recovery codes of the given user.
+    fn count_mfa_recovery_code_hashes(
         &self,
         txn: &mut Txn,
         user_id: UserId,
-    ) -> impl Future<Output = anyhow::Result<Option<MfaRecoveryCodeHash>>> + Send;
+    ) -> impl Future<Output = anyhow::Result<u64>> + Send;
 
-    /// Set the MFA recovery code hash of the given user.
-    fn save_mfa_recovery_code_hash(
+    /// Replace all MFA recovery code hashes of the given user.
+    fn save_mfa_recovery_code_hashes(
         &self,
         txn: &mut Txn,
         user_id: UserId,
-        recovery_code_hash: MfaRecoveryCodeHash,
+        recovery_code_hashes: &[MfaRecoveryCodeHash],
     ) -> impl Future<Output = anyhow::Result<()>> + Send;
 
-    /// Delete the MFA recovery code hash of the given user.
+    /// Delete the given MFA recovery code hash of the given user.
+    ///
+    /// Returns `false` if the user does not have this recovery code.
     fn delete_mfa_recovery_code_hash(
         &self,
         txn: &mut Txn,
         user_id: UserId,
+        recovery_code_hash: MfaRecoveryCodeHash,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    /// Delete all MFA recovery code hashes of the given user.
+    fn delete_mfa_recovery_code_hashes_by_user(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Return all WebAuthn devices of the given user.
+    fn list_webauthn_devices_by_user(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+    ) -> impl Future<Output = anyhow::Result<Vec<WebauthnDevice>>> + Send;
+
+    /// Return the WebAuthn device with the given id.
+    fn get_webauthn_device(
+        &self,
+        txn: &mut Txn,
+        webauthn_device_id: WebauthnDeviceId,
+    ) -> impl Future<Output = anyhow::Result<Option<WebauthnDevice>>> + Send;
+
+    /// Return the WebAuthn device with the given credential id.
+    fn get_webauthn_device_by_credential_id(
+        &self,
+        txn: &mut Txn,
+        credential_id: &WebauthnCredentialId,
+    ) -> impl Future<Output = anyhow::Result<Option<WebauthnDevice>>> + Send;
+
+    /// Create a new WebAuthn device.
+    fn create_webauthn_device(
+        &self,
+        txn: &mut Txn,
+        webauthn_device: &WebauthnDevice,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Update an existing WebAuthn device.
+    fn update_webauthn_device<'a>(
+        &self,
+        txn: &mut Txn,
+        webauthn_device_id: WebauthnDeviceId,
+        patch: WebauthnDevicePatchRef<'a>,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    /// Delete the given WebAuthn device.
+    fn delete_webauthn_device(
+        &self,
+        txn: &mut Txn,
+        webauthn_device_id: WebauthnDeviceId,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    /// Delete all WebAuthn devices of the given user.
+    fn delete_webauthn_devices_by_user(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
     ) -> impl Future<Output = anyhow::Result<()>> + Send;
 }
 
@@ -99,7 +184,26 @@ impl<Txn: Send + Sync + 'static> MockMfaRepository<Txn> {
         self
     }
 
-    pub fn with_create_totp_device(mut self, totp_device: TotpDevice, secret: TotpSecret) -> Self {
+    pub fn with_get_totp_device(
+        mut self,
+        totp_device_id: TotpDeviceId,
+        result: Option<TotpDevice>,
+    ) -> Self {
+        self.expect_get_totp_device()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(totp_device_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_create_totp_device(
+        mut self,
+        totp_device: TotpDevice,
+        secret: EncryptedData,
+    ) -> Self {
         self.expect_create_totp_device()
             .once()
             .with(
@@ -124,6 +228,17 @@ impl<Txn: Send + Sync + 'static> MockMfaRepository<Txn> {
         self
     }
 
+    pub fn with_delete_totp_device(mut self, totp_device_id: TotpDeviceId, result: bool) -> Self {
+        self.expect_delete_totp_device()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(totp_device_id),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
     pub fn with_delete_totp_devices_by_user(mut self, user_id: UserId) -> Self {
         self.expect_delete_totp_devices_by_user()
             .once()
@@ -138,7 +253,7 @@ impl<Txn: Send + Sync + 'static> MockMfaRepository<Txn> {
     pub fn with_list_enabled_totp_device_secrets_by_user(
         mut self,
         user_id: UserId,
-        secrets: Vec<TotpSecret>,
+        secrets: Vec<EncryptedData>,
     ) -> Self {
         self.expect_list_enabled_totp_device_secrets_by_user()
             .once()
@@ -150,10 +265,21 @@ impl<Txn: Send + Sync + 'static> MockMfaRepository<Txn> {
         self
     }
 
+    pub fn with_list_totp_device_secrets(
+        mut self,
+        secrets: Vec<(TotpDeviceId, EncryptedData)>,
+    ) -> Self {
+        self.expect_list_totp_device_secrets()
+            .once()
+            .with(mockall::predicate::always())
+            .return_once(|_| Box::pin(std::future::ready(Ok(secrets))));
+        self
+    }
+
     pub fn with_get_totp_device_secret(
         mut self,
         totp_device_id: TotpDeviceId,
-        secret: TotpSecret,
+        secret: EncryptedData,
     ) -> Self {
         self.expect_get_totp_device_secret()
             .once()
@@ -168,7 +294,7 @@ impl<Txn: Send + Sync + 'static> MockMfaRepository<Txn> {
     pub fn with_save_totp_device_secret(
         mut self,
         totp_device_id: TotpDeviceId,
-        secret: TotpSecret,
+        secret: EncryptedData,
     ) -> Self {
         self.expect_save_totp_device_secret()
             .once()
@@ -181,39 +307,143 @@ impl<Txn: Send + Sync + 'static> MockMfaRepository<Txn> {
         self
     }
 
-    pub fn with_get_mfa_recovery_code_hash(
+    pub fn with_count_mfa_recovery_code_hashes(mut self, user_id: UserId, result: u64) -> Self {
+        self.expect_count_mfa_recovery_code_hashes()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_save_mfa_recovery_code_hashes(
+        mut self,
+        user_id: UserId,
+        recovery_code_hashes: Vec<MfaRecoveryCodeHash>,
+    ) -> Self {
+        self.expect_save_mfa_recovery_code_hashes()
+            .once()
+            .withf(move |_, id, hashes| *id == user_id && *hashes == recovery_code_hashes)
+            .return_once(|_, _, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_delete_mfa_recovery_code_hash(
         mut self,
         user_id: UserId,
-        recovery_code: Option<MfaRecoveryCodeHash>,
+        recovery_code_hash: MfaRecoveryCodeHash,
+        result: bool,
     ) -> Self {
-        self.expect_get_mfa_recovery_code_hash()
+        self.expect_delete_mfa_recovery_code_hash()
             .once()
             .with(
                 mockall::predicate::always(),
                 mockall::predicate::eq(user_id),
+                mockall::predicate::eq(recovery_code_hash),
             )
-            .return_once(move |_, _| Box::pin(std::future::ready(Ok(recovery_code))));
+            .return_once(move |_, _, _| Box::pin(std::future::ready(Ok(result))));
         self
     }
 
-    pub fn with_save_mfa_recovery_code_hash(
+    pub fn with_delete_mfa_recovery_code_hashes_by_user(mut self, user_id: UserId) -> Self {
+        self.expect_delete_mfa_recovery_code_hashes_by_user()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_list_webauthn_devices_by_user(
         mut self,
         user_id: UserId,
-        recovery_code_hash: MfaRecoveryCodeHash,
+        result: Vec<WebauthnDevice>,
     ) -> Self {
-        self.expect_save_mfa_recovery_code_hash()
+        self.expect_list_webauthn_devices_by_user()
             .once()
             .with(
                 mockall::predicate::always(),
                 mockall::predicate::eq(user_id),
-                mockall::predicate::eq(recovery_code_hash),
             )
-            .return_once(|_, _, _| Box::pin(std::future::ready(Ok(()))));
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
         self
     }
 
-    pub fn with_delete_mfa_recovery_code_hash(mut self, user_id: UserId) -> Self {
-        self.expect_delete_mfa_recovery_code_hash()
+    pub fn with_get_webauthn_device(
+        mut self,
+        webauthn_device_id: WebauthnDeviceId,
+        result: Option<WebauthnDevice>,
+    ) -> Self {
+        self.expect_get_webauthn_device()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(webauthn_device_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_get_webauthn_device_by_credential_id(
+        mut self,
+        credential_id: WebauthnCredentialId,
+        result: Option<WebauthnDevice>,
+    ) -> Self {
+        self.expect_get_webauthn_device_by_credential_id()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(credential_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_create_webauthn_device(mut self, webauthn_device: WebauthnDevice) -> Self {
+        self.expect_create_webauthn_device()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(webauthn_device),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_update_webauthn_device(
+        mut self,
+        webauthn_device_id: WebauthnDeviceId,
+        patch: academy_models::mfa::WebauthnDevicePatch,
+        result: bool,
+    ) -> Self {
+        self.expect_update_webauthn_device()
+            .once()
+            .withf(move |_, id, p| *id == webauthn_device_id && *p == patch.as_ref())
+            .return_once(move |_, _, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_delete_webauthn_device(
+        mut self,
+        webauthn_device_id: WebauthnDeviceId,
+        result: bool,
+    ) -> Self {
+        self.expect_delete_webauthn_device()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(webauthn_device_id),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_delete_webauthn_devices_by_user(mut self, user_id: UserId) -> Self {
+        self.expect_delete_webauthn_devices_by_user()
             .once()
             .with(
                 mockall::predicate::always(),
@@ -0,0 +1,246 @@
+use std::future::Future;
+
+use academy_models::{
+    oauth2_server::{
+        OAuth2Client, OAuth2ClientId, OAuth2ClientSecretHash, OAuth2Grant,
+        OAuth2ServerRefreshTokenHash,
+    },
+    session::SessionId,
+    user::UserId,
+};
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait OAuth2ServerRepository<Txn: Send + Sync + 'static>: Send + Sync + 'static {
+    /// Return all OAuth2 clients.
+    fn list_clients(
+        &self,
+        txn: &mut Txn,
+    ) -> impl Future<Output = anyhow::Result<Vec<OAuth2Client>>> + Send;
+
+    /// Return the OAuth2 client with the given id.
+    fn get_client(
+        &self,
+        txn: &mut Txn,
+        client_id: OAuth2ClientId,
+    ) -> impl Future<Output = anyhow::Result<Option<OAuth2Client>>> + Send;
+
+    /// Create a new OAuth2 client.
+    ///
+    /// `secret_hash` must be set if and only if the client is confidential.
+    fn create_client(
+        &self,
+        txn: &mut Txn,
+        client: &OAuth2Client,
+        secret_hash: Option<OAuth2ClientSecretHash>,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Delete the given OAuth2 client together with all of its grants.
+    fn delete_client(
+        &self,
+        txn: &mut Txn,
+        client_id: OAuth2ClientId,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    /// Return the secret hash of the given OAuth2 client.
+    fn get_client_secret_hash(
+        &self,
+        txn: &mut Txn,
+        client_id: OAuth2ClientId,
+    ) -> impl Future<Output = anyhow::Result<Option<OAuth2ClientSecretHash>>> + Send;
+
+    /// Return the grant backed by the given session.
+    fn get_grant(
+        &self,
+        txn: &mut Txn,
+        session_id: SessionId,
+    ) -> impl Future<Output = anyhow::Result<Option<OAuth2Grant>>> + Send;
+
+    /// Return all grants that have been issued to the given OAuth2 client.
+    fn list_grants_by_client(
+        &self,
+        txn: &mut Txn,
+        client_id: OAuth2ClientId,
+    ) -> impl Future<Output = anyhow::Result<Vec<OAuth2Grant>>> + Send;
+
+    /// Return the grant with the given refresh token hash.
+    fn get_grant_by_refresh_token_hash(
+        &self,
+        txn: &mut Txn,
+        refresh_token_hash: OAuth2ServerRefreshTokenHash,
+    ) -> impl Future<Output = anyhow::Result<Option<OAuth2Grant>>> + Send;
+
+    /// Create a new grant. The backing session must already exist.
+    fn create_grant(
+        &self,
+        txn: &mut Txn,
+        grant: &OAuth2Grant,
+        refresh_token_hash: OAuth2ServerRefreshTokenHash,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Update the refresh token hash of the given grant.
+    fn update_grant_refresh_token_hash(
+        &self,
+        txn: &mut Txn,
+        session_id: SessionId,
+        refresh_token_hash: OAuth2ServerRefreshTokenHash,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    /// Delete all grants that have been issued to the given user.
+    fn delete_grants_by_user(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
+#[cfg(feature = "mock")]
+impl<Txn: Send + Sync + 'static> MockOAuth2ServerRepository<Txn> {
+    pub fn with_list_clients(mut self, result: Vec<OAuth2Client>) -> Self {
+        self.expect_list_clients()
+            .once()
+            .with(mockall::predicate::always())
+            .return_once(|_| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_get_client(
+        mut self,
+        client_id: OAuth2ClientId,
+        result: Option<OAuth2Client>,
+    ) -> Self {
+        self.expect_get_client()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(client_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_create_client(
+        mut self,
+        client: OAuth2Client,
+        secret_hash: Option<OAuth2ClientSecretHash>,
+    ) -> Self {
+        self.expect_create_client()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(client),
+                mockall::predicate::eq(secret_hash),
+            )
+            .return_once(|_, _, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_delete_client(mut self, client_id: OAuth2ClientId, result: bool) -> Self {
+        self.expect_delete_client()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(client_id),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_get_client_secret_hash(
+        mut self,
+        client_id: OAuth2ClientId,
+        result: Option<OAuth2ClientSecretHash>,
+    ) -> Self {
+        self.expect_get_client_secret_hash()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(client_id),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_get_grant(mut self, session_id: SessionId, result: Option<OAuth2Grant>) -> Self {
+        self.expect_get_grant()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(session_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_list_grants_by_client(
+        mut self,
+        client_id: OAuth2ClientId,
+        result: Vec<OAuth2Grant>,
+    ) -> Self {
+        self.expect_list_grants_by_client()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(client_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_get_grant_by_refresh_token_hash(
+        mut self,
+        refresh_token_hash: OAuth2ServerRefreshTokenHash,
+        result: Option<OAuth2Grant>,
+    ) -> Self {
+        self.expect_get_grant_by_refresh_token_hash()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(refresh_token_hash),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_create_grant(
+        mut self,
+        grant: OAuth2Grant,
+        refresh_token_hash: OAuth2ServerRefreshTokenHash,
+    ) -> Self {
+        self.expect_create_grant()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(grant),
+                mockall::predicate::eq(refresh_token_hash),
+            )
+            .return_once(|_, _, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_update_grant_refresh_token_hash(
+        mut self,
+        session_id: SessionId,
+        refresh_token_hash: OAuth2ServerRefreshTokenHash,
+    ) -> Self {
+        self.expect_update_grant_refresh_token_hash()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(session_id),
+                mockall::predicate::eq(refresh_token_hash),
+            )
+            .return_once(|_, _, _| Box::pin(std::future::ready(Ok(true))));
+        self
+    }
+
+    pub fn with_delete_grants_by_user(mut self, user_id: UserId) -> Self {
+        self.expect_delete_grants_by_user()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+}
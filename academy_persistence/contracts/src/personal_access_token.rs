@@ -0,0 +1,166 @@
+use std::future::Future;
+
+use academy_models::{
+    personal_access_token::{PersonalAccessToken, PersonalAccessTokenHash, PersonalAccessTokenId},
+    user::UserId,
+};
+use chrono::{DateTime, Utc};
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait PersonalAccessTokenRepository<Txn: Send + Sync + 'static>: Send + Sync + 'static {
+    /// Return all personal access tokens of the given user.
+    fn list_by_user(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+    ) -> impl Future<Output = anyhow::Result<Vec<PersonalAccessToken>>> + Send;
+
+    /// Return the personal access token with the given id.
+    fn get(
+        &self,
+        txn: &mut Txn,
+        token_id: PersonalAccessTokenId,
+    ) -> impl Future<Output = anyhow::Result<Option<PersonalAccessToken>>> + Send;
+
+    /// Return the personal access token with the given hash.
+    fn get_by_hash(
+        &self,
+        txn: &mut Txn,
+        hash: PersonalAccessTokenHash,
+    ) -> impl Future<Output = anyhow::Result<Option<PersonalAccessToken>>> + Send;
+
+    /// Create a new personal access token.
+    fn create(
+        &self,
+        txn: &mut Txn,
+        token: &PersonalAccessToken,
+        hash: PersonalAccessTokenHash,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Update the timestamp of the last usage of the given personal access
+    /// token.
+    fn update_last_used_at(
+        &self,
+        txn: &mut Txn,
+        token_id: PersonalAccessTokenId,
+        last_used_at: DateTime<Utc>,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    /// Delete the given personal access token.
+    fn delete(
+        &self,
+        txn: &mut Txn,
+        token_id: PersonalAccessTokenId,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    /// Delete all personal access tokens of the given user.
+    fn delete_by_user(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Delete all personal access tokens that have expired before `now`.
+    ///
+    /// Returns the number of deleted tokens.
+    fn delete_expired(
+        &self,
+        txn: &mut Txn,
+        now: DateTime<Utc>,
+    ) -> impl Future<Output = anyhow::Result<u64>> + Send;
+}
+
+#[cfg(feature = "mock")]
+impl<Txn: Send + Sync + 'static> MockPersonalAccessTokenRepository<Txn> {
+    pub fn with_list_by_user(mut self, user_id: UserId, result: Vec<PersonalAccessToken>) -> Self {
+        self.expect_list_by_user()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_get(
+        mut self,
+        token_id: PersonalAccessTokenId,
+        result: Option<PersonalAccessToken>,
+    ) -> Self {
+        self.expect_get()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(token_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_get_by_hash(
+        mut self,
+        hash: PersonalAccessTokenHash,
+        result: Option<PersonalAccessToken>,
+    ) -> Self {
+        self.expect_get_by_hash()
+            .once()
+            .with(mockall::predicate::always(), mockall::predicate::eq(hash))
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_create(
+        mut self,
+        token: PersonalAccessToken,
+        hash: PersonalAccessTokenHash,
+    ) -> Self {
+        self.expect_create()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(token),
+                mockall::predicate::eq(hash),
+            )
+            .return_once(|_, _, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_update_last_used_at(
+        mut self,
+        token_id: PersonalAccessTokenId,
+        last_used_at: DateTime<Utc>,
+    ) -> Self {
+        self.expect_update_last_used_at()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(token_id),
+                mockall::predicate::eq(last_used_at),
+            )
+            .return_once(|_, _, _| Box::pin(std::future::ready(Ok(true))));
+        self
+    }
+
+    pub fn with_delete(mut self, token_id: PersonalAccessTokenId, result: bool) -> Self {
+        self.expect_delete()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(token_id),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_delete_by_user(mut self, user_id: UserId) -> Self {
+        self.expect_delete_by_user()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+}
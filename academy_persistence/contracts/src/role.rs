@@ -0,0 +1,199 @@
+use std::future::Future;
+
+use academy_models::{
+    role::{Role, RoleId, RoleName, RolePatchRef},
+    user::UserId,
+};
+use thiserror::Error;
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait RoleRepository<Txn: Send + Sync + 'static>: Send + Sync + 'static {
+    /// Return all roles.
+    fn list(&self, txn: &mut Txn) -> impl Future<Output = anyhow::Result<Vec<Role>>> + Send;
+
+    /// Return the role with the given id.
+    fn get(
+        &self,
+        txn: &mut Txn,
+        role_id: RoleId,
+    ) -> impl Future<Output = anyhow::Result<Option<Role>>> + Send;
+
+    /// Return the role with the given name.
+    fn get_by_name(
+        &self,
+        txn: &mut Txn,
+        name: &RoleName,
+    ) -> impl Future<Output = anyhow::Result<Option<Role>>> + Send;
+
+    /// Create a new role.
+    fn create(
+        &self,
+        txn: &mut Txn,
+        role: &Role,
+    ) -> impl Future<Output = Result<(), RoleRepoError>> + Send;
+
+    /// Update an existing role.
+    fn update<'a>(
+        &self,
+        txn: &mut Txn,
+        role_id: RoleId,
+        patch: RolePatchRef<'a>,
+    ) -> impl Future<Output = Result<bool, RoleRepoError>> + Send;
+
+    /// Delete a role.
+    fn delete(
+        &self,
+        txn: &mut Txn,
+        role_id: RoleId,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    /// Return all roles granted to the given user.
+    fn list_by_user(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+    ) -> impl Future<Output = anyhow::Result<Vec<Role>>> + Send;
+
+    /// Return the ids of all users who have been granted the given role.
+    fn list_users(
+        &self,
+        txn: &mut Txn,
+        role_id: RoleId,
+    ) -> impl Future<Output = anyhow::Result<Vec<UserId>>> + Send;
+
+    /// Grant a role to a user.
+    ///
+    /// Returns `false` if the user already had the role.
+    fn add_to_user(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        role_id: RoleId,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    /// Revoke a role from a user.
+    ///
+    /// Returns `false` if the user did not have the role.
+    fn remove_from_user(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        role_id: RoleId,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+}
+
+#[derive(Debug, Error)]
+pub enum RoleRepoError {
+    #[error("A role with the same name already exists.")]
+    NameConflict,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[cfg(feature = "mock")]
+impl<Txn: Send + Sync + 'static> MockRoleRepository<Txn> {
+    pub fn with_list(mut self, result: Vec<Role>) -> Self {
+        self.expect_list()
+            .once()
+            .with(mockall::predicate::always())
+            .return_once(|_| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_get(mut self, role_id: RoleId, result: Option<Role>) -> Self {
+        self.expect_get()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(role_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_get_by_name(mut self, name: RoleName, result: Option<Role>) -> Self {
+        self.expect_get_by_name()
+            .once()
+            .with(mockall::predicate::always(), mockall::predicate::eq(name))
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_create(mut self, role: Role, result: Result<(), RoleRepoError>) -> Self {
+        self.expect_create()
+            .once()
+            .with(mockall::predicate::always(), mockall::predicate::eq(role))
+            .return_once(|_, _| Box::pin(std::future::ready(result)));
+        self
+    }
+
+    pub fn with_update(
+        mut self,
+        role_id: RoleId,
+        patch: academy_models::role::RolePatch,
+        result: Result<bool, RoleRepoError>,
+    ) -> Self {
+        self.expect_update()
+            .once()
+            .withf(move |_, id, p| *id == role_id && *p == patch.as_ref())
+            .return_once(|_, _, _| Box::pin(std::future::ready(result)));
+        self
+    }
+
+    pub fn with_delete(mut self, role_id: RoleId, result: bool) -> Self {
+        self.expect_delete()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(role_id),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_list_by_user(mut self, user_id: UserId, result: Vec<Role>) -> Self {
+        self.expect_list_by_user()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_list_users(mut self, role_id: RoleId, result: Vec<UserId>) -> Self {
+        self.expect_list_users()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(role_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_add_to_user(mut self, user_id: UserId, role_id: RoleId, result: bool) -> Self {
+        self.expect_add_to_user()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(role_id),
+            )
+            .return_once(move |_, _, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_remove_from_user(mut self, user_id: UserId, role_id: RoleId, result: bool) -> Self {
+        self.expect_remove_from_user()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(role_id),
+            )
+            .return_once(move |_, _, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+}
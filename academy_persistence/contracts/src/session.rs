@@ -1,7 +1,9 @@
-use std::future::Future;
+use std::{future::Future, net::IpAddr};
 
 use academy_models::{
-    session::{Session, SessionId, SessionPatchRef, SessionRefreshTokenHash},
+    session::{
+        Session, SessionDeviceFingerprint, SessionId, SessionPatchRef, SessionRefreshTokenHash,
+    },
     user::UserId,
 };
 use chrono::{DateTime, Utc};
@@ -88,6 +90,61 @@ pub trait SessionRepository<Txn: Send + Sync + 'static>: Send + Sync + 'static {
         session_id: SessionId,
         refresh_token_hash: SessionRefreshTokenHash,
     ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Return the session which previously used the given refresh token hash
+    /// before it was replaced by a new one.
+    fn get_by_superseded_refresh_token_hash(
+        &self,
+        txn: &mut Txn,
+        refresh_token_hash: SessionRefreshTokenHash,
+    ) -> impl Future<Output = anyhow::Result<Option<Session>>> + Send;
+
+    /// Remember that the given refresh token hash of a session has been
+    /// replaced by a new one.
+    fn save_superseded_refresh_token_hash(
+        &self,
+        txn: &mut Txn,
+        session_id: SessionId,
+        refresh_token_hash: SessionRefreshTokenHash,
+        superseded_at: DateTime<Utc>,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Delete all superseded refresh token hashes that have been replaced
+    /// before `superseded_at`.
+    ///
+    /// Returns the number of deleted refresh token hashes.
+    fn delete_superseded_refresh_token_hashes(
+        &self,
+        txn: &mut Txn,
+        superseded_at: DateTime<Utc>,
+    ) -> impl Future<Output = anyhow::Result<u64>> + Send;
+
+    /// Return whether the given user has signed in from any device before.
+    fn has_known_devices(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    /// Return whether the given user has signed in from a device with the given
+    /// fingerprint or from the given ip address before.
+    fn is_known_device(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        device_fingerprint: Option<SessionDeviceFingerprint>,
+        ip: Option<IpAddr>,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    /// Remember that the given user has signed in from a device with the given
+    /// fingerprint and ip address.
+    fn save_known_device(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        device_fingerprint: Option<SessionDeviceFingerprint>,
+        ip: Option<IpAddr>,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
 }
 
 #[cfg(feature = "mock")]
@@ -220,4 +277,85 @@ impl<Txn: Send + Sync + 'static> MockSessionRepository<Txn> {
             .return_once(|_, _, _| Box::pin(std::future::ready(Ok(()))));
         self
     }
+
+    pub fn with_get_by_superseded_refresh_token_hash(
+        mut self,
+        refresh_token_hash: SessionRefreshTokenHash,
+        result: Option<Session>,
+    ) -> Self {
+        self.expect_get_by_superseded_refresh_token_hash()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(refresh_token_hash),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_save_superseded_refresh_token_hash(
+        mut self,
+        session_id: SessionId,
+        refresh_token_hash: SessionRefreshTokenHash,
+        superseded_at: DateTime<Utc>,
+    ) -> Self {
+        self.expect_save_superseded_refresh_token_hash()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(session_id),
+                mockall::predicate::eq(refresh_token_hash),
+                mockall::predicate::eq(superseded_at),
+            )
+            .return_once(|_, _, _, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_has_known_devices(mut self, user_id: UserId, result: bool) -> Self {
+        self.expect_has_known_devices()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_is_known_device(
+        mut self,
+        user_id: UserId,
+        device_fingerprint: Option<SessionDeviceFingerprint>,
+        ip: Option<IpAddr>,
+        result: bool,
+    ) -> Self {
+        self.expect_is_known_device()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(device_fingerprint),
+                mockall::predicate::eq(ip),
+            )
+            .return_once(move |_, _, _, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_save_known_device(
+        mut self,
+        user_id: UserId,
+        device_fingerprint: Option<SessionDeviceFingerprint>,
+        ip: Option<IpAddr>,
+    ) -> Self {
+        self.expect_save_known_device()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(device_fingerprint),
+                mockall::predicate::eq(ip),
+            )
+            .return_once(|_, _, _, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
 }
@@ -5,10 +5,12 @@ use academy_models::{
     oauth2::{OAuth2ProviderId, OAuth2RemoteUserId},
     pagination::PaginationSlice,
     user::{
-        User, UserComposite, UserFilter, UserId, UserInvoiceInfo, UserInvoiceInfoPatchRef,
-        UserName, UserNameOrEmailAddress, UserPatchRef, UserProfile, UserProfilePatchRef,
+        User, UserComposite, UserEmail, UserFilter, UserId, UserInvoiceInfo,
+        UserInvoiceInfoPatchRef, UserName, UserNameHistoryEntry, UserNameOrEmailAddress,
+        UserPatchRef, UserProfile, UserProfilePatchRef, UserRestoreCodeHash,
     },
 };
+use chrono::{DateTime, Utc};
 use thiserror::Error;
 
 #[cfg_attr(feature = "mock", mockall::automock)]
@@ -50,7 +52,8 @@ pub trait UserRepository<Txn: Send + Sync + 'static>: Send + Sync + 'static {
         name: &UserName,
     ) -> impl Future<Output = anyhow::Result<Option<UserComposite>>> + Send;
 
-    /// Return the user composite with the given email.
+    /// Return the user composite with the given primary or additional email
+    /// address (case insensitive).
     fn get_composite_by_email(
         &self,
         txn: &mut Txn,
@@ -124,6 +127,98 @@ pub trait UserRepository<Txn: Send + Sync + 'static>: Send + Sync + 'static {
         user_id: UserId,
     ) -> impl Future<Output = anyhow::Result<bool>> + Send;
 
+    /// Delete all users that have been marked as deleted before `deleted_at`.
+    ///
+    /// The current names of the deleted users are added to the name history
+    /// with `now` as the time of the change, so they stay quarantined.
+    ///
+    /// Returns the ids of the deleted users.
+    fn delete_by_deleted_at(
+        &self,
+        txn: &mut Txn,
+        deleted_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> impl Future<Output = anyhow::Result<Vec<UserId>>> + Send;
+
+    /// Save the hash of the code which can be used to restore the given user
+    /// after they have been marked as deleted, or remove it if `None` is
+    /// given.
+    fn save_restore_code_hash(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        restore_code_hash: Option<UserRestoreCodeHash>,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Return the hash of the restore code of the given user.
+    fn get_restore_code_hash(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+    ) -> impl Future<Output = anyhow::Result<Option<UserRestoreCodeHash>>> + Send;
+
+    /// Mark or unmark the given user as having requested an export of their
+    /// data.
+    fn save_export_requested(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        export_requested: bool,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Return the ids of all users with a pending data export request.
+    fn list_export_requests(
+        &self,
+        txn: &mut Txn,
+    ) -> impl Future<Output = anyhow::Result<Vec<UserId>>> + Send;
+
+    /// Return the additional email addresses of the given user.
+    fn list_emails(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+    ) -> impl Future<Output = anyhow::Result<Vec<UserEmail>>> + Send;
+
+    /// Add an additional email address to a user.
+    ///
+    /// Returns an error if the email address is already used as a primary or
+    /// additional email address by any user (case insensitive).
+    fn add_email(
+        &self,
+        txn: &mut Txn,
+        email: &UserEmail,
+    ) -> impl Future<Output = Result<(), UserRepoError>> + Send;
+
+    /// Remove an additional email address from a user (case insensitive).
+    fn remove_email(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+        email: &EmailAddress,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    /// Return the previous names of the given user, most recent first.
+    fn list_name_history(
+        &self,
+        txn: &mut Txn,
+        user_id: UserId,
+    ) -> impl Future<Output = anyhow::Result<Vec<UserNameHistoryEntry>>> + Send;
+
+    /// Return the most recent name history entry of any user with the given
+    /// name (case insensitive).
+    fn get_latest_name_history_entry(
+        &self,
+        txn: &mut Txn,
+        name: &UserName,
+    ) -> impl Future<Output = anyhow::Result<Option<UserNameHistoryEntry>>> + Send;
+
+    /// Add an entry to the name history of a user.
+    fn add_name_history_entry(
+        &self,
+        txn: &mut Txn,
+        entry: &UserNameHistoryEntry,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
     /// Save or update the password hash for a given user.
     fn save_password_hash(
         &self,
@@ -145,6 +240,12 @@ pub trait UserRepository<Txn: Send + Sync + 'static>: Send + Sync + 'static {
         txn: &mut Txn,
         user_id: UserId,
     ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    /// Return the password hashes of all users.
+    fn list_password_hashes(
+        &self,
+        txn: &mut Txn,
+    ) -> impl Future<Output = anyhow::Result<Vec<String>>> + Send;
 }
 
 #[derive(Debug, Error)]
@@ -331,6 +432,140 @@ impl<Txn: Send + Sync + 'static> MockUserRepository<Txn> {
         self
     }
 
+    pub fn with_delete_by_deleted_at(
+        mut self,
+        deleted_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+        result: Vec<UserId>,
+    ) -> Self {
+        self.expect_delete_by_deleted_at()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(deleted_at),
+                mockall::predicate::eq(now),
+            )
+            .return_once(move |_, _, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_save_restore_code_hash(
+        mut self,
+        user_id: UserId,
+        restore_code_hash: Option<UserRestoreCodeHash>,
+    ) -> Self {
+        self.expect_save_restore_code_hash()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(restore_code_hash),
+            )
+            .return_once(|_, _, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_get_restore_code_hash(
+        mut self,
+        user_id: UserId,
+        result: Option<UserRestoreCodeHash>,
+    ) -> Self {
+        self.expect_get_restore_code_hash()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+            )
+            .return_once(move |_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_save_export_requested(mut self, user_id: UserId, export_requested: bool) -> Self {
+        self.expect_save_export_requested()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(export_requested),
+            )
+            .return_once(|_, _, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_list_export_requests(mut self, result: Vec<UserId>) -> Self {
+        self.expect_list_export_requests()
+            .once()
+            .with(mockall::predicate::always())
+            .return_once(move |_| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_list_emails(mut self, user_id: UserId, result: Vec<UserEmail>) -> Self {
+        self.expect_list_emails()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_add_email(mut self, email: UserEmail, result: Result<(), UserRepoError>) -> Self {
+        self.expect_add_email()
+            .once()
+            .with(mockall::predicate::always(), mockall::predicate::eq(email))
+            .return_once(|_, _| Box::pin(std::future::ready(result)));
+        self
+    }
+
+    pub fn with_remove_email(mut self, user_id: UserId, email: EmailAddress, result: bool) -> Self {
+        self.expect_remove_email()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(email),
+            )
+            .return_once(move |_, _, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_list_name_history(
+        mut self,
+        user_id: UserId,
+        result: Vec<UserNameHistoryEntry>,
+    ) -> Self {
+        self.expect_list_name_history()
+            .once()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(user_id),
+            )
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_get_latest_name_history_entry(
+        mut self,
+        name: UserName,
+        result: Option<UserNameHistoryEntry>,
+    ) -> Self {
+        self.expect_get_latest_name_history_entry()
+            .once()
+            .with(mockall::predicate::always(), mockall::predicate::eq(name))
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(result))));
+        self
+    }
+
+    pub fn with_add_name_history_entry(mut self, entry: UserNameHistoryEntry) -> Self {
+        self.expect_add_name_history_entry()
+            .once()
+            .with(mockall::predicate::always(), mockall::predicate::eq(entry))
+            .return_once(|_, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
     pub fn with_save_password_hash(mut self, user_id: UserId, password_hash: String) -> Self {
         self.expect_save_password_hash()
             .once()
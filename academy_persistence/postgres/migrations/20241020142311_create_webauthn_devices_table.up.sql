@@ -0,0 +1,13 @@
+create table webauthn_devices (
+    id uuid primary key,
+    user_id uuid not null references users(id) on delete cascade,
+    name text not null,
+    credential_id bytea not null,
+    public_key bytea not null,
+    sign_count bigint not null,
+    created_at timestamp with time zone not null,
+    last_used_at timestamp with time zone
+);
+
+create index webauthn_devices_user_id_idx on webauthn_devices (user_id);
+create unique index webauthn_devices_credential_id_idx on webauthn_devices (credential_id);
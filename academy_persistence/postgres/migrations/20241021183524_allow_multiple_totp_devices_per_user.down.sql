@@ -0,0 +1,8 @@
+drop index totp_devices_user_id_idx;
+
+delete from totp_devices where id not in (
+    select distinct on (user_id) id from totp_devices order by user_id, enabled desc, created_at
+);
+
+alter table totp_devices drop column name;
+alter table totp_devices add constraint totp_devices_user_id_key unique (user_id);
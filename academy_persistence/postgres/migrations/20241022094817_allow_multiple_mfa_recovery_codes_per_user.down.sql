@@ -0,0 +1,6 @@
+delete from mfa_recovery_codes where ctid not in (
+    select distinct on (user_id) ctid from mfa_recovery_codes order by user_id
+);
+
+alter table mfa_recovery_codes drop constraint mfa_recovery_codes_pkey;
+alter table mfa_recovery_codes add primary key (user_id);
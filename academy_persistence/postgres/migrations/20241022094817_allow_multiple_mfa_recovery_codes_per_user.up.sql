@@ -0,0 +1,2 @@
+alter table mfa_recovery_codes drop constraint mfa_recovery_codes_pkey;
+alter table mfa_recovery_codes add primary key (user_id, code);
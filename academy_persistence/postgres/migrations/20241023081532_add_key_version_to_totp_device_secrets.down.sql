@@ -0,0 +1,9 @@
+do $$
+begin
+    if exists (select 1 from totp_device_secrets where key_version is not null) then
+        raise exception 'totp device secrets are still encrypted';
+    end if;
+end
+$$;
+
+alter table totp_device_secrets drop column key_version;
@@ -0,0 +1,2 @@
+-- secrets without a key version have been stored before encryption was introduced
+alter table totp_device_secrets add column key_version integer;
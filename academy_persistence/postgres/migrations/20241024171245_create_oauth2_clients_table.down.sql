@@ -0,0 +1,3 @@
+delete from sessions where id in (select session_id from oauth2_grants);
+drop table oauth2_grants;
+drop table oauth2_clients;
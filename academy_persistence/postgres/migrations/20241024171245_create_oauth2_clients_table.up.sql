@@ -0,0 +1,15 @@
+create table oauth2_clients (
+    id uuid primary key,
+    name text not null,
+    redirect_uris text[] not null,
+    -- only confidential clients have a secret
+    secret_hash bytea,
+    created_at timestamp with time zone not null
+);
+
+create table oauth2_grants (
+    session_id uuid primary key references sessions(id) on delete cascade,
+    client_id uuid not null references oauth2_clients(id) on delete cascade,
+    scopes text[] not null,
+    refresh_token_hash bytea not null unique
+);
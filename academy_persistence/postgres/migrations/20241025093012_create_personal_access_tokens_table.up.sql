@@ -0,0 +1,11 @@
+create table personal_access_tokens (
+    id uuid primary key,
+    user_id uuid not null references users(id) on delete cascade,
+    name text not null,
+    scopes text[] not null,
+    token_hash bytea not null unique,
+    created_at timestamp with time zone not null,
+    expires_at timestamp with time zone,
+    last_used_at timestamp with time zone
+);
+create index personal_access_tokens_user_id_idx on personal_access_tokens (user_id);
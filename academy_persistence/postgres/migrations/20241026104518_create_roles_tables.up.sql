@@ -0,0 +1,14 @@
+create table roles (
+    id uuid primary key,
+    name text not null,
+    permissions text[] not null,
+    created_at timestamp with time zone not null
+);
+create unique index roles_name_idx on roles (lower(name));
+
+create table user_roles (
+    user_id uuid not null references users(id) on delete cascade,
+    role_id uuid not null references roles(id) on delete cascade,
+    primary key (user_id, role_id)
+);
+create index user_roles_role_id_idx on user_roles (role_id);
@@ -0,0 +1,12 @@
+create table audit_events (
+    id uuid primary key,
+    actor_id uuid,
+    user_id uuid not null,
+    action text not null,
+    ip inet,
+    user_agent text,
+    request_id uuid,
+    created_at timestamp with time zone not null
+);
+create index audit_events_user_id_idx on audit_events (user_id, created_at);
+create index audit_events_created_at_idx on audit_events (created_at);
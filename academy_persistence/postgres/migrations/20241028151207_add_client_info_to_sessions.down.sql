@@ -0,0 +1,2 @@
+drop table session_known_devices;
+alter table sessions drop column ip, drop column device_fingerprint;
@@ -0,0 +1,8 @@
+alter table sessions add column ip inet, add column device_fingerprint bytea;
+
+create table session_known_devices (
+    user_id uuid not null references users(id) on delete cascade,
+    device_fingerprint bytea,
+    ip inet
+);
+create index session_known_devices_user_id_idx on session_known_devices (user_id);
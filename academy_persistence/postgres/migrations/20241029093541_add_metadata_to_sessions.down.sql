@@ -0,0 +1,6 @@
+alter table sessions
+    drop column last_ip,
+    drop column browser,
+    drop column os,
+    drop column login_method,
+    drop column oauth2_provider_id;
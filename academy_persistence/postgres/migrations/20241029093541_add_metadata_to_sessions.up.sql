@@ -0,0 +1,8 @@
+alter table sessions
+    add column last_ip inet,
+    add column browser text,
+    add column os text,
+    add column login_method text not null default 'password',
+    add column oauth2_provider_id text;
+alter table sessions alter column login_method drop default;
+update sessions set last_ip=ip;
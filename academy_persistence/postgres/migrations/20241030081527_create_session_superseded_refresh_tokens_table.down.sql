@@ -0,0 +1 @@
+drop table session_superseded_refresh_tokens;
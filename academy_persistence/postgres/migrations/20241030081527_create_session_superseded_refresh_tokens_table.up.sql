@@ -0,0 +1,7 @@
+create table session_superseded_refresh_tokens (
+    refresh_token_hash bytea primary key,
+    session_id uuid not null references sessions(id) on delete cascade,
+    superseded_at timestamp with time zone not null
+);
+create index session_superseded_refresh_tokens_session_id_idx on session_superseded_refresh_tokens (session_id);
+create index session_superseded_refresh_tokens_superseded_at_idx on session_superseded_refresh_tokens (superseded_at);
@@ -0,0 +1,4 @@
+drop trigger users_check_email_unique on users;
+drop function users_check_email_unique;
+drop table user_emails;
+drop function user_emails_check_email_unique;
@@ -0,0 +1,33 @@
+create table user_emails (
+    user_id uuid not null references users(id) on delete cascade,
+    email text not null,
+    created_at timestamp with time zone not null
+);
+create unique index user_emails_email_idx on user_emails (lower(email));
+create index user_emails_user_id_idx on user_emails (user_id);
+
+-- email addresses must be unique across primary (`users`) and additional
+-- (`user_emails`) addresses of all users
+create function users_check_email_unique() returns trigger as $$
+begin
+    if exists (select 1 from user_emails where lower(email)=lower(new.email)) then
+        raise unique_violation using constraint = 'users_email_idx';
+    end if;
+    return new;
+end;
+$$ language plpgsql;
+
+create trigger users_check_email_unique before insert or update of email on users
+    for each row when (new.email is not null) execute function users_check_email_unique();
+
+create function user_emails_check_email_unique() returns trigger as $$
+begin
+    if exists (select 1 from users where lower(email)=lower(new.email)) then
+        raise unique_violation using constraint = 'user_emails_email_idx';
+    end if;
+    return new;
+end;
+$$ language plpgsql;
+
+create trigger user_emails_check_email_unique before insert or update of email on user_emails
+    for each row execute function user_emails_check_email_unique();
@@ -0,0 +1,7 @@
+create table user_name_history (
+    user_id uuid not null references users(id) on delete cascade,
+    name text not null,
+    changed_at timestamp with time zone not null
+);
+create index user_name_history_user_id_idx on user_name_history (user_id);
+create index user_name_history_name_idx on user_name_history (lower(name));
@@ -0,0 +1 @@
+alter table user_profiles drop column avatar_url;
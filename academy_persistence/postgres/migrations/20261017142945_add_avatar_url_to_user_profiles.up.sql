@@ -0,0 +1 @@
+alter table user_profiles add column avatar_url text;
@@ -0,0 +1,2 @@
+delete from user_name_history where user_id not in (select id from users);
+alter table user_name_history add foreign key (user_id) references users(id) on delete cascade;
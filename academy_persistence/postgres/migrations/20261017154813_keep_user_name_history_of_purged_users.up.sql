@@ -0,0 +1 @@
+alter table user_name_history drop constraint user_name_history_user_id_fkey;
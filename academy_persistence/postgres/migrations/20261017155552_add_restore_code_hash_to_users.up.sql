@@ -0,0 +1 @@
+alter table users add column restore_code_hash bytea;
@@ -0,0 +1 @@
+alter table users drop column export_requested;
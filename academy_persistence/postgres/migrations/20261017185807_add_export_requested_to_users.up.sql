@@ -0,0 +1 @@
+alter table users add column export_requested boolean not null default false;
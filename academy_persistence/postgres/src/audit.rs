@@ -0,0 +1,134 @@
+use std::net::IpAddr;
+
+use academy_di::Build;
+use academy_models::{
+    audit::{AuditEvent, AuditEventFilter},
+    pagination::PaginationSlice,
+};
+use academy_persistence_contracts::audit::AuditEventRepository;
+use academy_utils::trace_instrument;
+use bb8_postgres::tokio_postgres::{types::ToSql, Row};
+use uuid::Uuid;
+
+use crate::{arg_indices, columns, ColumnCounter, PostgresTransaction};
+
+#[derive(Debug, Clone, Build)]
+pub struct PostgresAuditEventRepository;
+
+columns!(audit_events as "a": "id", "actor_id", "user_id", "action", "ip", "user_agent", "request_id", "created_at");
+
+impl AuditEventRepository<PostgresTransaction> for PostgresAuditEventRepository {
+    #[trace_instrument(skip(self, txn))]
+    async fn count(
+        &self,
+        txn: &mut PostgresTransaction,
+        filter: &AuditEventFilter,
+    ) -> anyhow::Result<u64> {
+        let mut query = "select count(*) from audit_events a where true".to_owned();
+        let mut params: Vec<&(dyn ToSql + Sync)> = Vec::new();
+        let action = filter.action.map(|x| x.as_str());
+        make_filter(filter, &action, &mut query, &mut params);
+
+        txn.txn()
+            .query_one(&query, &params)
+            .await
+            .map(|row| row.get::<_, i64>(0) as _)
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn list(
+        &self,
+        txn: &mut PostgresTransaction,
+        filter: &AuditEventFilter,
+        pagination: PaginationSlice,
+    ) -> anyhow::Result<Vec<AuditEvent>> {
+        let mut query = format!("select {AUDIT_EVENTS_COLS} from audit_events a where true");
+        let mut params: Vec<&(dyn ToSql + Sync)> = Vec::new();
+        let action = filter.action.map(|x| x.as_str());
+        make_filter(filter, &action, &mut query, &mut params);
+        query.push_str(&format!(
+            " order by a.created_at desc limit {} offset {}",
+            *pagination.limit, pagination.offset
+        ));
+
+        txn.txn()
+            .query(&query, &params)
+            .await
+            .map_err(Into::into)
+            .and_then(|rows| {
+                rows.into_iter()
+                    .map(|row| decode_audit_event(&row, &mut Default::default()))
+                    .collect()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn create(
+        &self,
+        txn: &mut PostgresTransaction,
+        event: &AuditEvent,
+    ) -> anyhow::Result<()> {
+        txn.txn()
+            .execute(
+                &format!(
+                    "insert into audit_events ({AUDIT_EVENTS_COL_NAMES}) values ({})",
+                    arg_indices(1..=AUDIT_EVENTS_CNT)
+                ),
+                &[
+                    &*event.id,
+                    &event.actor_id.map(|x| *x),
+                    &*event.user_id,
+                    &event.action.as_str(),
+                    &event.ip,
+                    &event.user_agent,
+                    &event.request_id,
+                    &event.created_at,
+                ],
+            )
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+}
+
+fn make_filter<'a>(
+    filter: &'a AuditEventFilter,
+    action: &'a Option<&'static str>,
+    query: &mut String,
+    params: &mut Vec<&'a (dyn ToSql + Sync)>,
+) {
+    if let Some(user_id) = &filter.user_id {
+        params.push(&**user_id);
+        query.push_str(&format!(" and a.user_id=${}", params.len()));
+    }
+    if let Some(actor_id) = &filter.actor_id {
+        params.push(&**actor_id);
+        query.push_str(&format!(" and a.actor_id=${}", params.len()));
+    }
+    if let Some(action) = action {
+        params.push(action);
+        query.push_str(&format!(" and a.action=${}", params.len()));
+    }
+    if let Some(after) = &filter.after {
+        params.push(after);
+        query.push_str(&format!(" and a.created_at>=${}", params.len()));
+    }
+    if let Some(before) = &filter.before {
+        params.push(before);
+        query.push_str(&format!(" and a.created_at<${}", params.len()));
+    }
+}
+
+fn decode_audit_event(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<AuditEvent> {
+    Ok(AuditEvent {
+        id: row.get::<_, Uuid>(cnt.idx()).into(),
+        actor_id: row.get::<_, Option<Uuid>>(cnt.idx()).map(Into::into),
+        user_id: row.get::<_, Uuid>(cnt.idx()).into(),
+        action: row.get::<_, String>(cnt.idx()).parse()?,
+        ip: row.get::<_, Option<IpAddr>>(cnt.idx()),
+        user_agent: row.get(cnt.idx()),
+        request_id: row.get(cnt.idx()),
+        created_at: row.get(cnt.idx()),
+    })
+}
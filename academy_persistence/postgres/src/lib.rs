@@ -12,8 +12,12 @@ use bb8_postgres::{
 use ouroboros::self_referencing;
 use tracing::trace;
 
+pub mod audit;
 pub mod mfa;
 pub mod oauth2;
+pub mod oauth2_server;
+pub mod personal_access_token;
+pub mod role;
 pub mod session;
 pub mod user;
 
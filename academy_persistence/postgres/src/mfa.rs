@@ -2,7 +2,11 @@ use std::fmt::Write;
 
 use academy_di::Build;
 use academy_models::{
-    mfa::{MfaRecoveryCodeHash, TotpDevice, TotpDeviceId, TotpDevicePatchRef, TotpSecret},
+    encryption::EncryptedData,
+    mfa::{
+        MfaRecoveryCodeHash, TotpDevice, TotpDeviceId, TotpDevicePatchRef, WebauthnCredentialId,
+        WebauthnDevice, WebauthnDeviceId, WebauthnDevicePatchRef,
+    },
     user::UserId,
 };
 use academy_persistence_contracts::mfa::MfaRepository;
@@ -10,12 +14,13 @@ use academy_utils::{patch::PatchValue, trace_instrument};
 use bb8_postgres::tokio_postgres::{types::ToSql, Row};
 use uuid::Uuid;
 
-use crate::{arg_indices, columns, decode_sha256hash, ColumnCounter, PostgresTransaction};
+use crate::{arg_indices, columns, ColumnCounter, PostgresTransaction};
 
 #[derive(Debug, Clone, Build)]
 pub struct PostgresMfaRepository;
 
-columns!(totp_device as "td": "id", "user_id", "enabled", "created_at");
+columns!(totp_device as "td": "id", "user_id", "name", "enabled", "created_at");
+columns!(webauthn_device as "wd": "id", "user_id", "name", "credential_id", "public_key", "sign_count", "created_at", "last_used_at");
 
 impl MfaRepository<PostgresTransaction> for PostgresMfaRepository {
     #[trace_instrument(skip(self, txn))]
@@ -38,12 +43,31 @@ impl MfaRepository<PostgresTransaction> for PostgresMfaRepository {
             })
     }
 
+    #[trace_instrument(skip(self, txn))]
+    async fn get_totp_device(
+        &self,
+        txn: &mut PostgresTransaction,
+        totp_device_id: TotpDeviceId,
+    ) -> anyhow::Result<Option<TotpDevice>> {
+        txn.txn()
+            .query_opt(
+                &format!("select {TOTP_DEVICE_COLS} from totp_devices td where id=$1"),
+                &[&*totp_device_id],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|row| {
+                row.map(|row| decode_totp_device(&row, &mut Default::default()))
+                    .transpose()
+            })
+    }
+
     #[trace_instrument(skip(self, txn))]
     async fn create_totp_device(
         &self,
         txn: &mut PostgresTransaction,
         totp_device: &TotpDevice,
-        secret: &TotpSecret,
+        secret: &EncryptedData,
     ) -> anyhow::Result<()> {
         txn.txn()
             .execute(
@@ -54,6 +78,7 @@ impl MfaRepository<PostgresTransaction> for PostgresMfaRepository {
                 &[
                     &*totp_device.id,
                     &*totp_device.user_id,
+                    &totp_device.name.as_str(),
                     &totp_device.enabled,
                     &totp_device.created_at,
                 ],
@@ -71,11 +96,16 @@ impl MfaRepository<PostgresTransaction> for PostgresMfaRepository {
         &self,
         txn: &mut PostgresTransaction,
         totp_device_id: TotpDeviceId,
-        TotpDevicePatchRef { enabled }: TotpDevicePatchRef<'a>,
+        TotpDevicePatchRef { name, enabled }: TotpDevicePatchRef<'a>,
     ) -> anyhow::Result<bool> {
         let mut query = "update totp_devices set id=id".to_owned();
         let mut params: Vec<&(dyn ToSql + Sync)> = vec![&*totp_device_id];
 
+        if let PatchValue::Update(name) = name {
+            params.push(&**name);
+            write!(&mut query, ", name=${}", params.len()).unwrap();
+        }
+
         if let PatchValue::Update(enabled) = enabled {
             params.push(enabled);
             write!(&mut query, ", enabled=${}", params.len()).unwrap();
@@ -90,6 +120,19 @@ impl MfaRepository<PostgresTransaction> for PostgresMfaRepository {
             .map_err(Into::into)
     }
 
+    #[trace_instrument(skip(self, txn))]
+    async fn delete_totp_device(
+        &self,
+        txn: &mut PostgresTransaction,
+        totp_device_id: TotpDeviceId,
+    ) -> anyhow::Result<bool> {
+        txn.txn()
+            .execute("delete from totp_devices where id=$1", &[&*totp_device_id])
+            .await
+            .map(|n| n != 0)
+            .map_err(Into::into)
+    }
+
     #[trace_instrument(skip(self, txn))]
     async fn delete_totp_devices_by_user(
         &self,
@@ -108,18 +151,41 @@ impl MfaRepository<PostgresTransaction> for PostgresMfaRepository {
         &self,
         txn: &mut PostgresTransaction,
         user_id: UserId,
-    ) -> anyhow::Result<Vec<TotpSecret>> {
+    ) -> anyhow::Result<Vec<EncryptedData>> {
         txn.txn()
             .query(
-                "select secret from totp_device_secrets inner join totp_devices using(id) where \
-                 user_id=$1 and enabled",
+                "select key_version, secret from totp_device_secrets inner join totp_devices \
+                 using(id) where user_id=$1 and enabled",
                 &[&*user_id],
             )
             .await
             .map_err(Into::into)
             .and_then(|rows| {
                 rows.into_iter()
-                    .map(|row| decode_totp_device_secret(row.get(0)))
+                    .map(|row| decode_totp_device_secret(&row, &mut Default::default()))
+                    .collect()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn list_totp_device_secrets(
+        &self,
+        txn: &mut PostgresTransaction,
+    ) -> anyhow::Result<Vec<(TotpDeviceId, EncryptedData)>> {
+        txn.txn()
+            .query(
+                "select id, key_version, secret from totp_device_secrets",
+                &[],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|rows| {
+                rows.into_iter()
+                    .map(|row| {
+                        let mut cnt = ColumnCounter::default();
+                        let id = row.get::<_, Uuid>(cnt.idx()).into();
+                        decode_totp_device_secret(&row, &mut cnt).map(|secret| (id, secret))
+                    })
                     .collect()
             })
     }
@@ -129,15 +195,15 @@ impl MfaRepository<PostgresTransaction> for PostgresMfaRepository {
         &self,
         txn: &mut PostgresTransaction,
         totp_device_id: TotpDeviceId,
-    ) -> anyhow::Result<TotpSecret> {
+    ) -> anyhow::Result<EncryptedData> {
         txn.txn()
             .query_one(
-                "select secret from totp_device_secrets where id=$1",
+                "select key_version, secret from totp_device_secrets where id=$1",
                 &[&*totp_device_id],
             )
             .await
             .map_err(Into::into)
-            .and_then(|row| decode_totp_device_secret(row.get(0)))
+            .and_then(|row| decode_totp_device_secret(&row, &mut Default::default()))
     }
 
     #[trace_instrument(skip(self, txn))]
@@ -145,13 +211,14 @@ impl MfaRepository<PostgresTransaction> for PostgresMfaRepository {
         &self,
         txn: &mut PostgresTransaction,
         totp_device_id: TotpDeviceId,
-        secret: &TotpSecret,
+        secret: &EncryptedData,
     ) -> anyhow::Result<()> {
+        let key_version = secret.key_version.map(i32::try_from).transpose()?;
         txn.txn()
             .execute(
-                "insert into totp_device_secrets (id, secret) values ($1, $2) on conflict (id) do \
-                 update set secret=$2",
-                &[&*totp_device_id, &**secret],
+                "insert into totp_device_secrets (id, key_version, secret) values ($1, $2, $3) on \
+                 conflict (id) do update set key_version=$2, secret=$3",
+                &[&*totp_device_id, &key_version, &secret.data],
             )
             .await
             .map(|_| ())
@@ -159,36 +226,161 @@ impl MfaRepository<PostgresTransaction> for PostgresMfaRepository {
     }
 
     #[trace_instrument(skip(self, txn))]
-    async fn get_mfa_recovery_code_hash(
+    async fn count_mfa_recovery_code_hashes(
         &self,
         txn: &mut PostgresTransaction,
         user_id: UserId,
-    ) -> anyhow::Result<Option<MfaRecoveryCodeHash>> {
+    ) -> anyhow::Result<u64> {
         txn.txn()
-            .query_opt(
-                "select code from mfa_recovery_codes where user_id=$1",
+            .query_one(
+                "select count(*) from mfa_recovery_codes where user_id=$1",
+                &[&*user_id],
+            )
+            .await
+            .map(|row| row.get::<_, i64>(0) as _)
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn save_mfa_recovery_code_hashes(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+        recovery_code_hashes: &[MfaRecoveryCodeHash],
+    ) -> anyhow::Result<()> {
+        self.delete_mfa_recovery_code_hashes_by_user(txn, user_id)
+            .await?;
+
+        for recovery_code_hash in recovery_code_hashes {
+            txn.txn()
+                .execute(
+                    "insert into mfa_recovery_codes (user_id, code) values ($1, $2)",
+                    &[&*user_id, &recovery_code_hash.0.as_slice()],
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn delete_mfa_recovery_code_hash(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+        recovery_code_hash: MfaRecoveryCodeHash,
+    ) -> anyhow::Result<bool> {
+        txn.txn()
+            .execute(
+                "delete from mfa_recovery_codes where user_id=$1 and code=$2",
+                &[&*user_id, &recovery_code_hash.0.as_slice()],
+            )
+            .await
+            .map(|n| n != 0)
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn delete_mfa_recovery_code_hashes_by_user(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+    ) -> anyhow::Result<()> {
+        txn.txn()
+            .execute(
+                "delete from mfa_recovery_codes where user_id=$1",
+                &[&*user_id],
+            )
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn list_webauthn_devices_by_user(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+    ) -> anyhow::Result<Vec<WebauthnDevice>> {
+        txn.txn()
+            .query(
+                &format!(
+                    "select {WEBAUTHN_DEVICE_COLS} from webauthn_devices wd where user_id=$1 \
+                     order by created_at"
+                ),
                 &[&*user_id],
             )
             .await
             .map_err(Into::into)
+            .and_then(|rows| {
+                rows.into_iter()
+                    .map(|row| decode_webauthn_device(&row, &mut Default::default()))
+                    .collect()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn get_webauthn_device(
+        &self,
+        txn: &mut PostgresTransaction,
+        webauthn_device_id: WebauthnDeviceId,
+    ) -> anyhow::Result<Option<WebauthnDevice>> {
+        txn.txn()
+            .query_opt(
+                &format!("select {WEBAUTHN_DEVICE_COLS} from webauthn_devices wd where id=$1"),
+                &[&*webauthn_device_id],
+            )
+            .await
+            .map_err(Into::into)
             .and_then(|row| {
-                row.map(|row| decode_sha256hash(row.get(0)).map(Into::into))
+                row.map(|row| decode_webauthn_device(&row, &mut Default::default()))
                     .transpose()
             })
     }
 
     #[trace_instrument(skip(self, txn))]
-    async fn save_mfa_recovery_code_hash(
+    async fn get_webauthn_device_by_credential_id(
         &self,
         txn: &mut PostgresTransaction,
-        user_id: UserId,
-        recovery_code_hash: MfaRecoveryCodeHash,
+        credential_id: &WebauthnCredentialId,
+    ) -> anyhow::Result<Option<WebauthnDevice>> {
+        txn.txn()
+            .query_opt(
+                &format!(
+                    "select {WEBAUTHN_DEVICE_COLS} from webauthn_devices wd where credential_id=$1"
+                ),
+                &[&credential_id.as_slice()],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|row| {
+                row.map(|row| decode_webauthn_device(&row, &mut Default::default()))
+                    .transpose()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn create_webauthn_device(
+        &self,
+        txn: &mut PostgresTransaction,
+        webauthn_device: &WebauthnDevice,
     ) -> anyhow::Result<()> {
         txn.txn()
             .execute(
-                "insert into mfa_recovery_codes (user_id, code) values ($1, $2) on conflict \
-                 (user_id) do update set code=$2",
-                &[&*user_id, &recovery_code_hash.0.as_slice()],
+                &format!(
+                    "insert into webauthn_devices ({WEBAUTHN_DEVICE_COL_NAMES}) values ({})",
+                    arg_indices(1..=WEBAUTHN_DEVICE_CNT)
+                ),
+                &[
+                    &*webauthn_device.id,
+                    &*webauthn_device.user_id,
+                    &webauthn_device.name.as_str(),
+                    &webauthn_device.credential_id.as_slice(),
+                    &webauthn_device.public_key.as_slice(),
+                    &i64::from(webauthn_device.sign_count),
+                    &webauthn_device.created_at,
+                    &webauthn_device.last_used_at,
+                ],
             )
             .await
             .map(|_| ())
@@ -196,14 +388,69 @@ impl MfaRepository<PostgresTransaction> for PostgresMfaRepository {
     }
 
     #[trace_instrument(skip(self, txn))]
-    async fn delete_mfa_recovery_code_hash(
+    async fn update_webauthn_device<'a>(
+        &self,
+        txn: &mut PostgresTransaction,
+        webauthn_device_id: WebauthnDeviceId,
+        WebauthnDevicePatchRef {
+            name,
+            sign_count,
+            last_used_at,
+        }: WebauthnDevicePatchRef<'a>,
+    ) -> anyhow::Result<bool> {
+        let mut query = "update webauthn_devices set id=id".to_owned();
+        let mut params: Vec<&(dyn ToSql + Sync)> = vec![&*webauthn_device_id];
+
+        if let PatchValue::Update(name) = name {
+            params.push(&**name);
+            write!(&mut query, ", name=${}", params.len()).unwrap();
+        }
+
+        let sign_count = sign_count.map(|&x| i64::from(x));
+        if let PatchValue::Update(sign_count) = &sign_count {
+            params.push(sign_count);
+            write!(&mut query, ", sign_count=${}", params.len()).unwrap();
+        }
+
+        if let PatchValue::Update(last_used_at) = last_used_at {
+            params.push(last_used_at);
+            write!(&mut query, ", last_used_at=${}", params.len()).unwrap();
+        }
+
+        query.push_str(" where id=$1");
+
+        txn.txn()
+            .execute(&query, &params)
+            .await
+            .map(|n| n != 0)
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn delete_webauthn_device(
+        &self,
+        txn: &mut PostgresTransaction,
+        webauthn_device_id: WebauthnDeviceId,
+    ) -> anyhow::Result<bool> {
+        txn.txn()
+            .execute(
+                "delete from webauthn_devices where id=$1",
+                &[&*webauthn_device_id],
+            )
+            .await
+            .map(|n| n != 0)
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn delete_webauthn_devices_by_user(
         &self,
         txn: &mut PostgresTransaction,
         user_id: UserId,
     ) -> anyhow::Result<()> {
         txn.txn()
             .execute(
-                "delete from mfa_recovery_codes where user_id=$1",
+                "delete from webauthn_devices where user_id=$1",
                 &[&*user_id],
             )
             .await
@@ -216,11 +463,31 @@ fn decode_totp_device(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<Totp
     Ok(TotpDevice {
         id: row.get::<_, Uuid>(cnt.idx()).into(),
         user_id: row.get::<_, Uuid>(cnt.idx()).into(),
+        name: row.get::<_, String>(cnt.idx()).try_into()?,
         enabled: row.get(cnt.idx()),
         created_at: row.get(cnt.idx()),
     })
 }
 
-fn decode_totp_device_secret(data: Vec<u8>) -> anyhow::Result<TotpSecret> {
-    data.try_into().map_err(Into::into)
+fn decode_totp_device_secret(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<EncryptedData> {
+    Ok(EncryptedData {
+        key_version: row
+            .get::<_, Option<i32>>(cnt.idx())
+            .map(u32::try_from)
+            .transpose()?,
+        data: row.get(cnt.idx()),
+    })
+}
+
+fn decode_webauthn_device(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<WebauthnDevice> {
+    Ok(WebauthnDevice {
+        id: row.get::<_, Uuid>(cnt.idx()).into(),
+        user_id: row.get::<_, Uuid>(cnt.idx()).into(),
+        name: row.get::<_, String>(cnt.idx()).try_into()?,
+        credential_id: row.get::<_, Vec<u8>>(cnt.idx()).try_into()?,
+        public_key: row.get::<_, Vec<u8>>(cnt.idx()).into(),
+        sign_count: row.get::<_, i64>(cnt.idx()).try_into()?,
+        created_at: row.get(cnt.idx()),
+        last_used_at: row.get(cnt.idx()),
+    })
 }
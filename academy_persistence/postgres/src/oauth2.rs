@@ -104,6 +104,8 @@ fn decode_oauth2_link(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<OAut
         remote_user: OAuth2UserInfo {
             id: row.get::<_, String>(cnt.idx()).try_into()?,
             name: row.get::<_, String>(cnt.idx()).try_into()?,
+            email: None,
+            email_verified: false,
         },
     })
 }
@@ -0,0 +1,275 @@
+use academy_di::Build;
+use academy_models::{
+    oauth2_server::{
+        OAuth2Client, OAuth2ClientId, OAuth2ClientSecretHash, OAuth2Grant, OAuth2Scopes,
+        OAuth2ServerRefreshTokenHash,
+    },
+    session::SessionId,
+    user::UserId,
+};
+use academy_persistence_contracts::oauth2_server::OAuth2ServerRepository;
+use academy_utils::trace_instrument;
+use bb8_postgres::tokio_postgres::Row;
+use uuid::Uuid;
+
+use crate::{arg_indices, columns, decode_sha256hash, ColumnCounter, PostgresTransaction};
+
+#[derive(Debug, Clone, Build)]
+pub struct PostgresOAuth2ServerRepository;
+
+columns!(oauth2_clients as "oc": "id", "name", "redirect_uris", "secret_hash", "created_at");
+columns!(oauth2_grants as "og": "session_id", "client_id", "scopes");
+
+impl OAuth2ServerRepository<PostgresTransaction> for PostgresOAuth2ServerRepository {
+    #[trace_instrument(skip(self, txn))]
+    async fn list_clients(
+        &self,
+        txn: &mut PostgresTransaction,
+    ) -> anyhow::Result<Vec<OAuth2Client>> {
+        txn.txn()
+            .query(
+                &format!("select {OAUTH2_CLIENTS_COLS} from oauth2_clients oc order by created_at"),
+                &[],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|rows| {
+                rows.into_iter()
+                    .map(|row| decode_oauth2_client(&row, &mut Default::default()))
+                    .collect()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn get_client(
+        &self,
+        txn: &mut PostgresTransaction,
+        client_id: OAuth2ClientId,
+    ) -> anyhow::Result<Option<OAuth2Client>> {
+        txn.txn()
+            .query_opt(
+                &format!("select {OAUTH2_CLIENTS_COLS} from oauth2_clients oc where id=$1"),
+                &[&*client_id],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|row| {
+                row.map(|row| decode_oauth2_client(&row, &mut Default::default()))
+                    .transpose()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn create_client(
+        &self,
+        txn: &mut PostgresTransaction,
+        client: &OAuth2Client,
+        secret_hash: Option<OAuth2ClientSecretHash>,
+    ) -> anyhow::Result<()> {
+        txn.txn()
+            .execute(
+                &format!(
+                    "insert into oauth2_clients ({OAUTH2_CLIENTS_COL_NAMES}) values ({})",
+                    arg_indices(1..=OAUTH2_CLIENTS_CNT)
+                ),
+                &[
+                    &*client.id,
+                    &*client.name,
+                    &client
+                        .redirect_uris
+                        .iter()
+                        .map(|x| x.as_str())
+                        .collect::<Vec<_>>(),
+                    &secret_hash.as_ref().map(|x| x.0.as_slice()),
+                    &client.created_at,
+                ],
+            )
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn delete_client(
+        &self,
+        txn: &mut PostgresTransaction,
+        client_id: OAuth2ClientId,
+    ) -> anyhow::Result<bool> {
+        txn.txn()
+            .execute("delete from oauth2_clients where id=$1", &[&*client_id])
+            .await
+            .map(|n| n != 0)
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn get_client_secret_hash(
+        &self,
+        txn: &mut PostgresTransaction,
+        client_id: OAuth2ClientId,
+    ) -> anyhow::Result<Option<OAuth2ClientSecretHash>> {
+        txn.txn()
+            .query_opt(
+                "select secret_hash from oauth2_clients where id=$1",
+                &[&*client_id],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|row| {
+                row.and_then(|row| row.get::<_, Option<Vec<u8>>>(0))
+                    .map(|hash| decode_sha256hash(hash).map(Into::into))
+                    .transpose()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn get_grant(
+        &self,
+        txn: &mut PostgresTransaction,
+        session_id: SessionId,
+    ) -> anyhow::Result<Option<OAuth2Grant>> {
+        txn.txn()
+            .query_opt(
+                &format!("select {OAUTH2_GRANTS_COLS} from oauth2_grants og where session_id=$1"),
+                &[&*session_id],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|row| {
+                row.map(|row| decode_oauth2_grant(&row, &mut Default::default()))
+                    .transpose()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn list_grants_by_client(
+        &self,
+        txn: &mut PostgresTransaction,
+        client_id: OAuth2ClientId,
+    ) -> anyhow::Result<Vec<OAuth2Grant>> {
+        txn.txn()
+            .query(
+                &format!("select {OAUTH2_GRANTS_COLS} from oauth2_grants og where client_id=$1"),
+                &[&*client_id],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|rows| {
+                rows.into_iter()
+                    .map(|row| decode_oauth2_grant(&row, &mut Default::default()))
+                    .collect()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn get_grant_by_refresh_token_hash(
+        &self,
+        txn: &mut PostgresTransaction,
+        refresh_token_hash: OAuth2ServerRefreshTokenHash,
+    ) -> anyhow::Result<Option<OAuth2Grant>> {
+        txn.txn()
+            .query_opt(
+                &format!(
+                    "select {OAUTH2_GRANTS_COLS} from oauth2_grants og where refresh_token_hash=$1"
+                ),
+                &[&refresh_token_hash.0.as_slice()],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|row| {
+                row.map(|row| decode_oauth2_grant(&row, &mut Default::default()))
+                    .transpose()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn create_grant(
+        &self,
+        txn: &mut PostgresTransaction,
+        grant: &OAuth2Grant,
+        refresh_token_hash: OAuth2ServerRefreshTokenHash,
+    ) -> anyhow::Result<()> {
+        txn.txn()
+            .execute(
+                &format!(
+                    "insert into oauth2_grants ({OAUTH2_GRANTS_COL_NAMES}, refresh_token_hash) \
+                     values ({})",
+                    arg_indices(1..=OAUTH2_GRANTS_CNT + 1)
+                ),
+                &[
+                    &*grant.session_id,
+                    &*grant.client_id,
+                    &grant
+                        .scopes
+                        .0
+                        .iter()
+                        .map(|x| x.as_str())
+                        .collect::<Vec<_>>(),
+                    &refresh_token_hash.0.as_slice(),
+                ],
+            )
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn update_grant_refresh_token_hash(
+        &self,
+        txn: &mut PostgresTransaction,
+        session_id: SessionId,
+        refresh_token_hash: OAuth2ServerRefreshTokenHash,
+    ) -> anyhow::Result<bool> {
+        txn.txn()
+            .execute(
+                "update oauth2_grants set refresh_token_hash=$2 where session_id=$1",
+                &[&*session_id, &refresh_token_hash.0.as_slice()],
+            )
+            .await
+            .map(|n| n != 0)
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn delete_grants_by_user(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+    ) -> anyhow::Result<()> {
+        txn.txn()
+            .execute(
+                "delete from oauth2_grants where session_id in (select id from sessions where \
+                 user_id=$1)",
+                &[&*user_id],
+            )
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+}
+
+fn decode_oauth2_client(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<OAuth2Client> {
+    Ok(OAuth2Client {
+        id: row.get::<_, Uuid>(cnt.idx()).into(),
+        name: row.get::<_, String>(cnt.idx()).try_into()?,
+        redirect_uris: row
+            .get::<_, Vec<String>>(cnt.idx())
+            .into_iter()
+            .map(|x| x.parse())
+            .collect::<Result<_, _>>()?,
+        confidential: row.get::<_, Option<Vec<u8>>>(cnt.idx()).is_some(),
+        created_at: row.get(cnt.idx()),
+    })
+}
+
+fn decode_oauth2_grant(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<OAuth2Grant> {
+    Ok(OAuth2Grant {
+        session_id: row.get::<_, Uuid>(cnt.idx()).into(),
+        client_id: row.get::<_, Uuid>(cnt.idx()).into(),
+        scopes: row
+            .get::<_, Vec<String>>(cnt.idx())
+            .into_iter()
+            .map(|x| x.parse())
+            .collect::<Result<OAuth2Scopes, _>>()?,
+    })
+}
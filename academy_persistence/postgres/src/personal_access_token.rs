@@ -0,0 +1,199 @@
+use academy_di::Build;
+use academy_models::{
+    personal_access_token::{PersonalAccessToken, PersonalAccessTokenHash, PersonalAccessTokenId},
+    user::UserId,
+};
+use academy_persistence_contracts::personal_access_token::PersonalAccessTokenRepository;
+use academy_utils::trace_instrument;
+use bb8_postgres::tokio_postgres::Row;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{arg_indices, columns, ColumnCounter, PostgresTransaction};
+
+#[derive(Debug, Clone, Build)]
+pub struct PostgresPersonalAccessTokenRepository;
+
+columns!(personal_access_tokens as "pat": "id", "user_id", "name", "scopes", "created_at", "expires_at", "last_used_at");
+
+impl PersonalAccessTokenRepository<PostgresTransaction> for PostgresPersonalAccessTokenRepository {
+    #[trace_instrument(skip(self, txn))]
+    async fn list_by_user(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+    ) -> anyhow::Result<Vec<PersonalAccessToken>> {
+        txn.txn()
+            .query(
+                &format!(
+                    "select {PERSONAL_ACCESS_TOKENS_COLS} from personal_access_tokens pat where \
+                     user_id=$1 order by created_at"
+                ),
+                &[&*user_id],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|rows| {
+                rows.into_iter()
+                    .map(|row| decode_personal_access_token(&row, &mut Default::default()))
+                    .collect()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn get(
+        &self,
+        txn: &mut PostgresTransaction,
+        token_id: PersonalAccessTokenId,
+    ) -> anyhow::Result<Option<PersonalAccessToken>> {
+        txn.txn()
+            .query_opt(
+                &format!(
+                    "select {PERSONAL_ACCESS_TOKENS_COLS} from personal_access_tokens pat where \
+                     id=$1"
+                ),
+                &[&*token_id],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|row| {
+                row.map(|row| decode_personal_access_token(&row, &mut Default::default()))
+                    .transpose()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn get_by_hash(
+        &self,
+        txn: &mut PostgresTransaction,
+        hash: PersonalAccessTokenHash,
+    ) -> anyhow::Result<Option<PersonalAccessToken>> {
+        txn.txn()
+            .query_opt(
+                &format!(
+                    "select {PERSONAL_ACCESS_TOKENS_COLS} from personal_access_tokens pat where \
+                     token_hash=$1"
+                ),
+                &[&hash.0.as_slice()],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|row| {
+                row.map(|row| decode_personal_access_token(&row, &mut Default::default()))
+                    .transpose()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn create(
+        &self,
+        txn: &mut PostgresTransaction,
+        token: &PersonalAccessToken,
+        hash: PersonalAccessTokenHash,
+    ) -> anyhow::Result<()> {
+        txn.txn()
+            .execute(
+                &format!(
+                    "insert into personal_access_tokens ({PERSONAL_ACCESS_TOKENS_COL_NAMES}, \
+                     token_hash) values ({})",
+                    arg_indices(1..=PERSONAL_ACCESS_TOKENS_CNT + 1)
+                ),
+                &[
+                    &*token.id,
+                    &*token.user_id,
+                    &*token.name,
+                    &token.scopes.iter().map(|x| x.as_str()).collect::<Vec<_>>(),
+                    &token.created_at,
+                    &token.expires_at,
+                    &token.last_used_at,
+                    &hash.0.as_slice(),
+                ],
+            )
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn update_last_used_at(
+        &self,
+        txn: &mut PostgresTransaction,
+        token_id: PersonalAccessTokenId,
+        last_used_at: DateTime<Utc>,
+    ) -> anyhow::Result<bool> {
+        txn.txn()
+            .execute(
+                "update personal_access_tokens set last_used_at=$2 where id=$1",
+                &[&*token_id, &last_used_at],
+            )
+            .await
+            .map(|n| n != 0)
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn delete(
+        &self,
+        txn: &mut PostgresTransaction,
+        token_id: PersonalAccessTokenId,
+    ) -> anyhow::Result<bool> {
+        txn.txn()
+            .execute(
+                "delete from personal_access_tokens where id=$1",
+                &[&*token_id],
+            )
+            .await
+            .map(|n| n != 0)
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn delete_by_user(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+    ) -> anyhow::Result<()> {
+        txn.txn()
+            .execute(
+                "delete from personal_access_tokens where user_id=$1",
+                &[&*user_id],
+            )
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn delete_expired(
+        &self,
+        txn: &mut PostgresTransaction,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<u64> {
+        txn.txn()
+            .execute(
+                "delete from personal_access_tokens where expires_at<=$1",
+                &[&now],
+            )
+            .await
+            .map_err(Into::into)
+    }
+}
+
+fn decode_personal_access_token(
+    row: &Row,
+    cnt: &mut ColumnCounter,
+) -> anyhow::Result<PersonalAccessToken> {
+    Ok(PersonalAccessToken {
+        id: row.get::<_, Uuid>(cnt.idx()).into(),
+        user_id: row.get::<_, Uuid>(cnt.idx()).into(),
+        name: row.get::<_, String>(cnt.idx()).try_into()?,
+        scopes: row
+            .get::<_, Vec<String>>(cnt.idx())
+            .into_iter()
+            .map(|x| x.parse())
+            .collect::<Result<_, _>>()?,
+        created_at: row.get(cnt.idx()),
+        expires_at: row.get(cnt.idx()),
+        last_used_at: row.get(cnt.idx()),
+    })
+}
@@ -0,0 +1,238 @@
+use std::fmt::Write;
+
+use academy_di::Build;
+use academy_models::{
+    role::{Role, RoleId, RoleName, RolePatchRef},
+    user::UserId,
+};
+use academy_persistence_contracts::role::{RoleRepoError, RoleRepository};
+use academy_utils::{patch::PatchValue, trace_instrument};
+use bb8_postgres::tokio_postgres::{self, types::ToSql, Row};
+use uuid::Uuid;
+
+use crate::{arg_indices, columns, ColumnCounter, PostgresTransaction};
+
+#[derive(Debug, Clone, Build)]
+pub struct PostgresRoleRepository;
+
+columns!(roles as "r": "id", "name", "permissions", "created_at");
+
+impl RoleRepository<PostgresTransaction> for PostgresRoleRepository {
+    #[trace_instrument(skip(self, txn))]
+    async fn list(&self, txn: &mut PostgresTransaction) -> anyhow::Result<Vec<Role>> {
+        txn.txn()
+            .query(
+                &format!("select {ROLES_COLS} from roles r order by created_at"),
+                &[],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|rows| {
+                rows.into_iter()
+                    .map(|row| decode_role(&row, &mut Default::default()))
+                    .collect()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn get(
+        &self,
+        txn: &mut PostgresTransaction,
+        role_id: RoleId,
+    ) -> anyhow::Result<Option<Role>> {
+        txn.txn()
+            .query_opt(
+                &format!("select {ROLES_COLS} from roles r where id=$1"),
+                &[&*role_id],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|row| {
+                row.map(|row| decode_role(&row, &mut Default::default()))
+                    .transpose()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn get_by_name(
+        &self,
+        txn: &mut PostgresTransaction,
+        name: &RoleName,
+    ) -> anyhow::Result<Option<Role>> {
+        txn.txn()
+            .query_opt(
+                &format!("select {ROLES_COLS} from roles r where lower(name)=lower($1)"),
+                &[&**name],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|row| {
+                row.map(|row| decode_role(&row, &mut Default::default()))
+                    .transpose()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn create(
+        &self,
+        txn: &mut PostgresTransaction,
+        role: &Role,
+    ) -> Result<(), RoleRepoError> {
+        txn.txn()
+            .execute(
+                &format!(
+                    "insert into roles ({ROLES_COL_NAMES}) values ({})",
+                    arg_indices(1..=ROLES_CNT)
+                ),
+                &[
+                    &*role.id,
+                    &*role.name,
+                    &role
+                        .permissions
+                        .iter()
+                        .map(|x| x.as_str())
+                        .collect::<Vec<_>>(),
+                    &role.created_at,
+                ],
+            )
+            .await
+            .map(|_| ())
+            .map_err(map_role_repo_error)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn update<'a>(
+        &self,
+        txn: &mut PostgresTransaction,
+        role_id: RoleId,
+        RolePatchRef { name, permissions }: RolePatchRef<'a>,
+    ) -> Result<bool, RoleRepoError> {
+        let mut query = "update roles set id=id".to_owned();
+        let mut params: Vec<&(dyn ToSql + Sync)> = vec![&*role_id];
+
+        let permissions = permissions.map(|x| x.iter().map(|x| x.as_str()).collect::<Vec<_>>());
+
+        if let PatchValue::Update(name) = name {
+            params.push(&**name);
+            write!(&mut query, ", name=${}", params.len()).unwrap();
+        }
+        if let PatchValue::Update(permissions) = &permissions {
+            params.push(permissions);
+            write!(&mut query, ", permissions=${}", params.len()).unwrap();
+        }
+
+        query.push_str(" where id=$1");
+
+        txn.txn()
+            .execute(&query, &params)
+            .await
+            .map(|n| n != 0)
+            .map_err(map_role_repo_error)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn delete(&self, txn: &mut PostgresTransaction, role_id: RoleId) -> anyhow::Result<bool> {
+        txn.txn()
+            .execute("delete from roles where id=$1", &[&*role_id])
+            .await
+            .map(|n| n != 0)
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn list_by_user(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+    ) -> anyhow::Result<Vec<Role>> {
+        txn.txn()
+            .query(
+                &format!(
+                    "select {ROLES_COLS} from roles r join user_roles ur on ur.role_id=r.id where \
+                     ur.user_id=$1 order by r.created_at"
+                ),
+                &[&*user_id],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|rows| {
+                rows.into_iter()
+                    .map(|row| decode_role(&row, &mut Default::default()))
+                    .collect()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn list_users(
+        &self,
+        txn: &mut PostgresTransaction,
+        role_id: RoleId,
+    ) -> anyhow::Result<Vec<UserId>> {
+        txn.txn()
+            .query(
+                "select user_id from user_roles where role_id=$1",
+                &[&*role_id],
+            )
+            .await
+            .map(|rows| {
+                rows.into_iter()
+                    .map(|row| row.get::<_, Uuid>(0).into())
+                    .collect()
+            })
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn add_to_user(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+        role_id: RoleId,
+    ) -> anyhow::Result<bool> {
+        txn.txn()
+            .execute(
+                "insert into user_roles (user_id, role_id) values ($1, $2) on conflict do nothing",
+                &[&*user_id, &*role_id],
+            )
+            .await
+            .map(|n| n != 0)
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn remove_from_user(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+        role_id: RoleId,
+    ) -> anyhow::Result<bool> {
+        txn.txn()
+            .execute(
+                "delete from user_roles where user_id=$1 and role_id=$2",
+                &[&*user_id, &*role_id],
+            )
+            .await
+            .map(|n| n != 0)
+            .map_err(Into::into)
+    }
+}
+
+fn decode_role(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<Role> {
+    Ok(Role {
+        id: row.get::<_, Uuid>(cnt.idx()).into(),
+        name: row.get::<_, String>(cnt.idx()).try_into()?,
+        permissions: row
+            .get::<_, Vec<String>>(cnt.idx())
+            .into_iter()
+            .map(|x| x.parse())
+            .collect::<Result<_, _>>()?,
+        created_at: row.get(cnt.idx()),
+    })
+}
+
+fn map_role_repo_error(err: tokio_postgres::Error) -> RoleRepoError {
+    match err.as_db_error() {
+        Some(err) if err.constraint() == Some("roles_name_idx") => RoleRepoError::NameConflict,
+        _ => RoleRepoError::Other(err.into()),
+    }
+}
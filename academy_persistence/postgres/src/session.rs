@@ -1,12 +1,16 @@
-use std::fmt::Write;
+use std::{fmt::Write, net::IpAddr};
 
 use academy_di::Build;
 use academy_models::{
-    session::{Session, SessionId, SessionPatchRef, SessionRefreshTokenHash},
+    session::{
+        Session, SessionDeviceFingerprint, SessionId, SessionLoginMethod, SessionPatchRef,
+        SessionRefreshTokenHash,
+    },
     user::UserId,
 };
 use academy_persistence_contracts::session::SessionRepository;
 use academy_utils::{patch::PatchValue, trace_instrument};
+use anyhow::anyhow;
 use bb8_postgres::tokio_postgres::{types::ToSql, Row};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
@@ -16,7 +20,7 @@ use crate::{arg_indices, columns, decode_sha256hash, ColumnCounter, PostgresTran
 #[derive(Debug, Clone, Build)]
 pub struct PostgresSessionRepository;
 
-columns!(session as "s": "id", "user_id", "device_name", "created_at", "updated_at");
+columns!(session as "s": "id", "user_id", "device_name", "ip", "device_fingerprint", "last_ip", "browser", "os", "login_method", "oauth2_provider_id", "created_at", "updated_at");
 
 impl SessionRepository<PostgresTransaction> for PostgresSessionRepository {
     #[trace_instrument(skip(self, txn))]
@@ -82,6 +86,7 @@ impl SessionRepository<PostgresTransaction> for PostgresSessionRepository {
 
     #[trace_instrument(skip(self, txn))]
     async fn create(&self, txn: &mut PostgresTransaction, session: &Session) -> anyhow::Result<()> {
+        let (login_method, oauth2_provider_id) = encode_login_method(&session.login_method);
         txn.txn()
             .execute(
                 &format!(
@@ -92,6 +97,13 @@ impl SessionRepository<PostgresTransaction> for PostgresSessionRepository {
                     &*session.id,
                     &*session.user_id,
                     &session.device_name.as_deref(),
+                    &session.ip,
+                    &session.device_fingerprint.as_ref().map(|x| x.0.as_slice()),
+                    &session.last_ip,
+                    &session.browser,
+                    &session.os,
+                    &login_method,
+                    &oauth2_provider_id,
                     &session.created_at,
                     &session.updated_at,
                 ],
@@ -108,6 +120,7 @@ impl SessionRepository<PostgresTransaction> for PostgresSessionRepository {
         session_id: SessionId,
         SessionPatchRef {
             device_name,
+            last_ip,
             updated_at,
         }: SessionPatchRef<'_>,
     ) -> anyhow::Result<bool> {
@@ -120,6 +133,10 @@ impl SessionRepository<PostgresTransaction> for PostgresSessionRepository {
             params.push(device_name);
             write!(&mut query, ", device_name=${}", params.len()).unwrap();
         }
+        if let PatchValue::Update(last_ip) = last_ip {
+            params.push(last_ip);
+            write!(&mut query, ", last_ip=${}", params.len()).unwrap();
+        }
         if let PatchValue::Update(updated_at) = updated_at {
             params.push(updated_at);
             write!(&mut query, ", updated_at=${}", params.len()).unwrap();
@@ -229,6 +246,130 @@ impl SessionRepository<PostgresTransaction> for PostgresSessionRepository {
             .map(|_| ())
             .map_err(Into::into)
     }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn get_by_superseded_refresh_token_hash(
+        &self,
+        txn: &mut PostgresTransaction,
+        refresh_token_hash: SessionRefreshTokenHash,
+    ) -> anyhow::Result<Option<Session>> {
+        txn.txn()
+            .query_opt(
+                &format!(
+                    "select {SESSION_COLS} from sessions s inner join \
+                     session_superseded_refresh_tokens srt on s.id=srt.session_id where \
+                     srt.refresh_token_hash=$1"
+                ),
+                &[&refresh_token_hash.0.as_slice()],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|row| {
+                row.map(|row| decode_session(&row, &mut Default::default()))
+                    .transpose()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn save_superseded_refresh_token_hash(
+        &self,
+        txn: &mut PostgresTransaction,
+        session_id: SessionId,
+        refresh_token_hash: SessionRefreshTokenHash,
+        superseded_at: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        txn.txn()
+            .execute(
+                "insert into session_superseded_refresh_tokens (refresh_token_hash, session_id, \
+                 superseded_at) values ($1, $2, $3) on conflict (refresh_token_hash) do nothing",
+                &[
+                    &refresh_token_hash.0.as_slice(),
+                    &*session_id,
+                    &superseded_at,
+                ],
+            )
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn delete_superseded_refresh_token_hashes(
+        &self,
+        txn: &mut PostgresTransaction,
+        superseded_at: DateTime<Utc>,
+    ) -> anyhow::Result<u64> {
+        txn.txn()
+            .execute(
+                "delete from session_superseded_refresh_tokens where superseded_at<$1",
+                &[&superseded_at],
+            )
+            .await
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn has_known_devices(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+    ) -> anyhow::Result<bool> {
+        txn.txn()
+            .query_one(
+                "select exists(select 1 from session_known_devices where user_id=$1)",
+                &[&*user_id],
+            )
+            .await
+            .map(|row| row.get(0))
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn is_known_device(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+        device_fingerprint: Option<SessionDeviceFingerprint>,
+        ip: Option<IpAddr>,
+    ) -> anyhow::Result<bool> {
+        txn.txn()
+            .query_one(
+                "select exists(select 1 from session_known_devices where user_id=$1 and \
+                 (device_fingerprint=$2 or ip=$3))",
+                &[
+                    &*user_id,
+                    &device_fingerprint.as_ref().map(|x| x.0.as_slice()),
+                    &ip,
+                ],
+            )
+            .await
+            .map(|row| row.get(0))
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn save_known_device(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+        device_fingerprint: Option<SessionDeviceFingerprint>,
+        ip: Option<IpAddr>,
+    ) -> anyhow::Result<()> {
+        txn.txn()
+            .execute(
+                "insert into session_known_devices (user_id, device_fingerprint, ip) select $1, \
+                 $2, $3 where not exists (select 1 from session_known_devices where user_id=$1 \
+                 and device_fingerprint is not distinct from $2 and ip is not distinct from $3)",
+                &[
+                    &*user_id,
+                    &device_fingerprint.as_ref().map(|x| x.0.as_slice()),
+                    &ip,
+                ],
+            )
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+    }
 }
 
 fn decode_session(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<Session> {
@@ -239,7 +380,41 @@ fn decode_session(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<Session>
             .get::<_, Option<String>>(cnt.idx())
             .map(TryInto::try_into)
             .transpose()?,
+        ip: row.get(cnt.idx()),
+        device_fingerprint: row
+            .get::<_, Option<Vec<u8>>>(cnt.idx())
+            .map(decode_sha256hash)
+            .transpose()?
+            .map(Into::into),
+        last_ip: row.get(cnt.idx()),
+        browser: row.get(cnt.idx()),
+        os: row.get(cnt.idx()),
+        login_method: decode_login_method(row.get(cnt.idx()), row.get(cnt.idx()))?,
         created_at: row.get(cnt.idx()),
         updated_at: row.get(cnt.idx()),
     })
 }
+
+fn encode_login_method(login_method: &SessionLoginMethod) -> (&'static str, Option<&str>) {
+    match login_method {
+        SessionLoginMethod::Password => ("password", None),
+        SessionLoginMethod::OAuth2(provider_id) => ("oauth2", Some(provider_id.as_str())),
+        SessionLoginMethod::Impersonation => ("impersonation", None),
+        SessionLoginMethod::OAuth2Client => ("oauth2_client", None),
+        SessionLoginMethod::LoginCode => ("login_code", None),
+    }
+}
+
+fn decode_login_method(
+    login_method: &str,
+    oauth2_provider_id: Option<String>,
+) -> anyhow::Result<SessionLoginMethod> {
+    Ok(match (login_method, oauth2_provider_id) {
+        ("password", _) => SessionLoginMethod::Password,
+        ("oauth2", Some(provider_id)) => SessionLoginMethod::OAuth2(provider_id.into()),
+        ("impersonation", _) => SessionLoginMethod::Impersonation,
+        ("oauth2_client", _) => SessionLoginMethod::OAuth2Client,
+        ("login_code", _) => SessionLoginMethod::LoginCode,
+        _ => return Err(anyhow!("Invalid session login method: {login_method}")),
+    })
+}
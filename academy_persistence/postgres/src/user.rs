@@ -6,23 +6,27 @@ use academy_models::{
     oauth2::{OAuth2ProviderId, OAuth2RemoteUserId},
     pagination::PaginationSlice,
     user::{
-        User, UserComposite, UserDetails, UserFilter, UserId, UserInvoiceInfo,
-        UserInvoiceInfoPatchRef, UserName, UserPatchRef, UserProfile, UserProfilePatchRef,
+        User, UserComposite, UserDetails, UserEmail, UserFilter, UserId, UserInvoiceInfo,
+        UserInvoiceInfoPatchRef, UserName, UserNameHistoryEntry, UserPatchRef, UserProfile,
+        UserProfilePatchRef, UserRestoreCodeHash,
     },
 };
 use academy_persistence_contracts::user::{UserRepoError, UserRepository};
 use academy_utils::{patch::PatchValue, trace_instrument};
 use bb8_postgres::tokio_postgres::{self, types::ToSql, Row};
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::{arg_indices, columns, ColumnCounter, PostgresTransaction};
+use crate::{arg_indices, columns, decode_sha256hash, ColumnCounter, PostgresTransaction};
 
 #[derive(Debug, Clone, Copy, Default, Build)]
 pub struct PostgresUserRepository;
 
-columns!(user as "u": "id", "name", "email", "email_verified", "created_at", "last_login", "last_name_change", "enabled", "admin", "newsletter");
-columns!(profile as "p": "user_id", "display_name", "bio", "tags");
+columns!(user as "u": "id", "name", "email", "email_verified", "created_at", "last_login", "last_name_change", "enabled", "admin", "newsletter", "deleted_at");
+columns!(profile as "p": "user_id", "display_name", "bio", "tags", "avatar_url");
 columns!(details as "d": "user_id", "mfa_enabled", "password_login", "oauth2_login");
+columns!(email as "e": "user_id", "email", "created_at");
+columns!(name_history as "h": "user_id", "name", "changed_at");
 columns!(invoice_info as "i": "user_id", "business", "first_name", "last_name", "street", "zip_code", "city", "country", "vat_id");
 
 const JOIN_PROFILE: &str = "inner join user_profiles p on u.id=p.user_id";
@@ -149,7 +153,8 @@ impl UserRepository<PostgresTransaction> for PostgresUserRepository {
                 &format!(
                     "select {USER_COLS}, {PROFILE_COLS}, {DETAILS_COLS}, {INVOICE_INFO_COLS} from \
                      users u {JOIN_PROFILE} {JOIN_DETAILS} {JOIN_INVOICE_INFO} where \
-                     lower(email)=lower($1)"
+                     lower(u.email)=lower($1) or u.id=(select user_id from user_emails where \
+                     lower(email)=lower($1))"
                 ),
                 &[&email.as_str()],
             )
@@ -211,6 +216,7 @@ impl UserRepository<PostgresTransaction> for PostgresUserRepository {
                     &user.enabled,
                     &user.admin,
                     &user.newsletter,
+                    &user.deleted_at,
                 ],
             )
             .await
@@ -227,6 +233,7 @@ impl UserRepository<PostgresTransaction> for PostgresUserRepository {
                     &profile.display_name.as_str(),
                     &profile.bio.as_str(),
                     &profile.tags.iter().map(|x| x.as_str()).collect::<Vec<_>>(),
+                    &profile.avatar_url.as_ref().map(|x| x.as_str()),
                 ],
             )
             .await
@@ -270,6 +277,7 @@ impl UserRepository<PostgresTransaction> for PostgresUserRepository {
             enabled,
             admin,
             newsletter,
+            deleted_at,
         }: UserPatchRef<'a>,
     ) -> Result<bool, UserRepoError> {
         let mut query = "update users set id=id".to_owned();
@@ -309,6 +317,10 @@ impl UserRepository<PostgresTransaction> for PostgresUserRepository {
             params.push(newsletter);
             write!(&mut query, ", newsletter=${}", params.len()).unwrap();
         }
+        if let PatchValue::Update(deleted_at) = deleted_at {
+            params.push(deleted_at);
+            write!(&mut query, ", deleted_at=${}", params.len()).unwrap();
+        }
 
         query.push_str(" where id=$1");
 
@@ -328,12 +340,14 @@ impl UserRepository<PostgresTransaction> for PostgresUserRepository {
             display_name,
             bio,
             tags,
+            avatar_url,
         }: UserProfilePatchRef<'a>,
     ) -> anyhow::Result<bool> {
         let mut query = "update user_profiles set user_id=user_id".to_owned();
         let mut params: Vec<&(dyn ToSql + Sync)> = vec![&*user_id];
 
         let tags = tags.map(|x| x.iter().map(|x| x.as_str()).collect::<Vec<_>>());
+        let avatar_url = avatar_url.map(|x| x.as_ref().map(|x| x.as_str()));
 
         if let PatchValue::Update(display_name) = display_name {
             params.push(&**display_name);
@@ -347,6 +361,10 @@ impl UserRepository<PostgresTransaction> for PostgresUserRepository {
             params.push(tags);
             write!(&mut query, ", tags=${}", params.len()).unwrap();
         }
+        if let PatchValue::Update(avatar_url) = &avatar_url {
+            params.push(avatar_url);
+            write!(&mut query, ", avatar_url=${}", params.len()).unwrap();
+        }
 
         query.push_str(" where user_id=$1");
 
@@ -435,6 +453,223 @@ impl UserRepository<PostgresTransaction> for PostgresUserRepository {
             .map_err(Into::into)
     }
 
+    #[trace_instrument(skip(self, txn))]
+    async fn delete_by_deleted_at(
+        &self,
+        txn: &mut PostgresTransaction,
+        deleted_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<UserId>> {
+        txn.txn()
+            .query(
+                "with deleted as (delete from users where deleted_at<$1 returning id, name), \
+                 history as (insert into user_name_history (user_id, name, changed_at) select \
+                 id, name, $2 from deleted) select id from deleted",
+                &[&deleted_at, &now],
+            )
+            .await
+            .map(|rows| {
+                rows.into_iter()
+                    .map(|row| row.get::<_, Uuid>(0).into())
+                    .collect()
+            })
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn save_restore_code_hash(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+        restore_code_hash: Option<UserRestoreCodeHash>,
+    ) -> anyhow::Result<()> {
+        txn.txn()
+            .execute(
+                "update users set restore_code_hash=$2 where id=$1",
+                &[
+                    &*user_id,
+                    &restore_code_hash.as_ref().map(|hash| hash.0.as_slice()),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn get_restore_code_hash(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+    ) -> anyhow::Result<Option<UserRestoreCodeHash>> {
+        txn.txn()
+            .query_opt(
+                "select restore_code_hash from users where id=$1",
+                &[&*user_id],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|row| {
+                row.and_then(|row| row.get::<_, Option<Vec<u8>>>(0))
+                    .map(|hash| decode_sha256hash(hash).map(Into::into))
+                    .transpose()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn save_export_requested(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+        export_requested: bool,
+    ) -> anyhow::Result<()> {
+        txn.txn()
+            .execute(
+                "update users set export_requested=$2 where id=$1",
+                &[&*user_id, &export_requested],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn list_export_requests(
+        &self,
+        txn: &mut PostgresTransaction,
+    ) -> anyhow::Result<Vec<UserId>> {
+        txn.txn()
+            .query("select id from users where export_requested", &[])
+            .await
+            .map(|rows| {
+                rows.into_iter()
+                    .map(|row| row.get::<_, Uuid>(0).into())
+                    .collect()
+            })
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn list_emails(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+    ) -> anyhow::Result<Vec<UserEmail>> {
+        txn.txn()
+            .query(
+                &format!(
+                    "select {EMAIL_COLS} from user_emails e where user_id=$1 order by created_at \
+                     asc"
+                ),
+                &[&*user_id],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|rows| {
+                rows.into_iter()
+                    .map(|row| decode_email(&row, &mut Default::default()))
+                    .collect()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn add_email(
+        &self,
+        txn: &mut PostgresTransaction,
+        email: &UserEmail,
+    ) -> Result<(), UserRepoError> {
+        txn.txn()
+            .execute(
+                &format!(
+                    "insert into user_emails ({EMAIL_COL_NAMES}) values ({})",
+                    arg_indices(1..=EMAIL_CNT)
+                ),
+                &[&*email.user_id, &email.email.as_str(), &email.created_at],
+            )
+            .await
+            .map(|_| ())
+            .map_err(map_user_repo_error)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn remove_email(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+        email: &EmailAddress,
+    ) -> anyhow::Result<bool> {
+        txn.txn()
+            .execute(
+                "delete from user_emails where user_id=$1 and lower(email)=lower($2)",
+                &[&*user_id, &email.as_str()],
+            )
+            .await
+            .map(|n| n != 0)
+            .map_err(Into::into)
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn list_name_history(
+        &self,
+        txn: &mut PostgresTransaction,
+        user_id: UserId,
+    ) -> anyhow::Result<Vec<UserNameHistoryEntry>> {
+        txn.txn()
+            .query(
+                &format!(
+                    "select {NAME_HISTORY_COLS} from user_name_history h where user_id=$1 order \
+                     by changed_at desc"
+                ),
+                &[&*user_id],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|rows| {
+                rows.into_iter()
+                    .map(|row| decode_name_history_entry(&row, &mut Default::default()))
+                    .collect()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn get_latest_name_history_entry(
+        &self,
+        txn: &mut PostgresTransaction,
+        name: &UserName,
+    ) -> anyhow::Result<Option<UserNameHistoryEntry>> {
+        txn.txn()
+            .query_opt(
+                &format!(
+                    "select {NAME_HISTORY_COLS} from user_name_history h where \
+                     lower(name)=lower($1) order by changed_at desc limit 1"
+                ),
+                &[&name.as_str()],
+            )
+            .await
+            .map_err(Into::into)
+            .and_then(|row| {
+                row.map(|row| decode_name_history_entry(&row, &mut Default::default()))
+                    .transpose()
+            })
+    }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn add_name_history_entry(
+        &self,
+        txn: &mut PostgresTransaction,
+        entry: &UserNameHistoryEntry,
+    ) -> anyhow::Result<()> {
+        txn.txn()
+            .execute(
+                &format!(
+                    "insert into user_name_history ({NAME_HISTORY_COL_NAMES}) values ({})",
+                    arg_indices(1..=NAME_HISTORY_CNT)
+                ),
+                &[&*entry.user_id, &entry.name.as_str(), &entry.changed_at],
+            )
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
     #[trace_instrument(skip(self, txn))]
     async fn save_password_hash(
         &self,
@@ -480,6 +715,18 @@ impl UserRepository<PostgresTransaction> for PostgresUserRepository {
             .map(|n| n != 0)
             .map_err(Into::into)
     }
+
+    #[trace_instrument(skip(self, txn))]
+    async fn list_password_hashes(
+        &self,
+        txn: &mut PostgresTransaction,
+    ) -> anyhow::Result<Vec<String>> {
+        txn.txn()
+            .query("select password_hash from user_passwords", &[])
+            .await
+            .map(|rows| rows.into_iter().map(|row| row.get(0)).collect())
+            .map_err(Into::into)
+    }
 }
 
 fn make_filter<'a>(
@@ -496,7 +743,11 @@ fn make_filter<'a>(
     }
     if let Some(email) = &filter.email {
         params.push(&**email);
-        query.push_str(&format!(" and lower(email)~lower(${})", params.len()));
+        query.push_str(&format!(
+            " and (lower(u.email)~lower(${0}) or exists (select 1 from user_emails e where \
+             e.user_id=u.id and lower(e.email)~lower(${0})))",
+            params.len()
+        ));
     }
     if let Some(enabled) = &filter.enabled {
         params.push(enabled);
@@ -536,6 +787,7 @@ fn decode_user(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<User> {
         enabled: row.get(cnt.idx()),
         admin: row.get(cnt.idx()),
         newsletter: row.get(cnt.idx()),
+        deleted_at: row.get(cnt.idx()),
     })
 }
 
@@ -550,6 +802,10 @@ fn decode_profile(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<UserProf
             .map(TryInto::try_into)
             .collect::<Result<Vec<_>, _>>()?
             .try_into()?,
+        avatar_url: row
+            .get::<_, Option<String>>(cnt.idx())
+            .map(|x| x.parse())
+            .transpose()?,
     })
 }
 
@@ -562,6 +818,25 @@ fn decode_details(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<UserDeta
     })
 }
 
+fn decode_email(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<UserEmail> {
+    Ok(UserEmail {
+        user_id: row.get::<_, Uuid>(cnt.idx()).into(),
+        email: row.get::<_, String>(cnt.idx()).parse()?,
+        created_at: row.get(cnt.idx()),
+    })
+}
+
+fn decode_name_history_entry(
+    row: &Row,
+    cnt: &mut ColumnCounter,
+) -> anyhow::Result<UserNameHistoryEntry> {
+    Ok(UserNameHistoryEntry {
+        user_id: row.get::<_, Uuid>(cnt.idx()).into(),
+        name: row.get::<_, String>(cnt.idx()).try_into()?,
+        changed_at: row.get(cnt.idx()),
+    })
+}
+
 fn decode_invoice_info(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<UserInvoiceInfo> {
     cnt.idx(); // user_id
     Ok(UserInvoiceInfo {
@@ -609,7 +884,14 @@ fn decode_composite(row: &Row, cnt: &mut ColumnCounter) -> anyhow::Result<UserCo
 fn map_user_repo_error(err: tokio_postgres::Error) -> UserRepoError {
     match err.as_db_error() {
         Some(err) if err.constraint() == Some("users_name_idx") => UserRepoError::NameConflict,
-        Some(err) if err.constraint() == Some("users_email_idx") => UserRepoError::EmailConflict,
+        Some(err)
+            if matches!(
+                err.constraint(),
+                Some("users_email_idx" | "user_emails_email_idx")
+            ) =>
+        {
+            UserRepoError::EmailConflict
+        }
         _ => UserRepoError::Other(err.into()),
     }
 }
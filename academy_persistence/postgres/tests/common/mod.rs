@@ -1,6 +1,8 @@
 use academy_persistence_contracts::{Database, Transaction};
 use academy_persistence_postgres::{
-    mfa::PostgresMfaRepository, oauth2::PostgresOAuth2Repository,
+    audit::PostgresAuditEventRepository, mfa::PostgresMfaRepository,
+    oauth2::PostgresOAuth2Repository, oauth2_server::PostgresOAuth2ServerRepository,
+    personal_access_token::PostgresPersonalAccessTokenRepository, role::PostgresRoleRepository,
     session::PostgresSessionRepository, user::PostgresUserRepository, PostgresDatabase,
     PostgresDatabaseConfig,
 };
@@ -20,6 +22,10 @@ pub async fn setup() -> Db {
         PostgresSessionRepository,
         PostgresMfaRepository,
         PostgresOAuth2Repository,
+        PostgresOAuth2ServerRepository,
+        PostgresPersonalAccessTokenRepository,
+        PostgresRoleRepository,
+        PostgresAuditEventRepository,
     )
     .await
     .unwrap();
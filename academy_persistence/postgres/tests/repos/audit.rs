@@ -0,0 +1,128 @@
+use std::sync::LazyLock;
+
+use academy_demo::{
+    audit::{
+        ADMIN2_MFA_ENABLE, ADMIN_IMPERSONATE_BAR, ADMIN_UPDATE_FOO, ALL_AUDIT_EVENTS,
+        FOO_PASSWORD_RESET,
+    },
+    user::{ADMIN, BAR, FOO},
+    UUID1,
+};
+use academy_models::audit::{AuditAction, AuditEvent, AuditEventFilter};
+use academy_persistence_contracts::{audit::AuditEventRepository, Database, Transaction};
+use academy_persistence_postgres::audit::PostgresAuditEventRepository;
+use pretty_assertions::assert_eq;
+
+use crate::{
+    common::setup,
+    repos::{make_slice, sliced},
+};
+
+const REPO: PostgresAuditEventRepository = PostgresAuditEventRepository;
+
+static FILTER_TESTS: LazyLock<Vec<(AuditEventFilter, Vec<&AuditEvent>)>> = LazyLock::new(|| {
+    let all = ALL_AUDIT_EVENTS.iter().rev().copied().collect::<Vec<_>>();
+    vec![
+        (AuditEventFilter::default(), all),
+        (
+            AuditEventFilter {
+                user_id: Some(FOO.user.id),
+                ..Default::default()
+            },
+            vec![&ADMIN_UPDATE_FOO, &FOO_PASSWORD_RESET],
+        ),
+        (
+            AuditEventFilter {
+                actor_id: Some(ADMIN.user.id),
+                ..Default::default()
+            },
+            vec![&ADMIN_IMPERSONATE_BAR, &ADMIN_UPDATE_FOO],
+        ),
+        (
+            AuditEventFilter {
+                action: Some(AuditAction::MfaEnable),
+                ..Default::default()
+            },
+            vec![&ADMIN2_MFA_ENABLE],
+        ),
+        (
+            AuditEventFilter {
+                action: Some(AuditAction::UserDelete),
+                ..Default::default()
+            },
+            vec![],
+        ),
+        (
+            AuditEventFilter {
+                after: Some(FOO_PASSWORD_RESET.created_at),
+                before: Some(ADMIN_IMPERSONATE_BAR.created_at),
+                ..Default::default()
+            },
+            vec![&ADMIN_UPDATE_FOO, &FOO_PASSWORD_RESET],
+        ),
+        (
+            AuditEventFilter {
+                user_id: Some(BAR.user.id),
+                actor_id: Some(ADMIN.user.id),
+                action: Some(AuditAction::UserImpersonate),
+                ..Default::default()
+            },
+            vec![&ADMIN_IMPERSONATE_BAR],
+        ),
+    ]
+});
+
+#[tokio::test]
+async fn count() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    for (filter, expected) in &*FILTER_TESTS {
+        let count = REPO.count(&mut txn, filter).await.unwrap();
+        assert_eq!(count, expected.len() as u64);
+    }
+}
+
+#[tokio::test]
+async fn list() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    for (filter, expected) in &*FILTER_TESTS {
+        for slice in [make_slice(100, 0), make_slice(1, 0), make_slice(1, 1)] {
+            let result = REPO.list(&mut txn, filter, slice).await.unwrap();
+            assert_eq!(&result.iter().collect::<Vec<_>>(), sliced(expected, slice));
+        }
+    }
+}
+
+#[tokio::test]
+async fn create() {
+    let event = AuditEvent {
+        id: UUID1.into(),
+        actor_id: None,
+        user_id: UUID1.into(),
+        action: AuditAction::UserDelete,
+        ip: None,
+        user_agent: None,
+        request_id: None,
+        created_at: ADMIN_IMPERSONATE_BAR.created_at,
+    };
+
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    REPO.create(&mut txn, &event).await.unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let filter = AuditEventFilter {
+        user_id: Some(event.user_id),
+        ..Default::default()
+    };
+    let result = REPO
+        .list(&mut txn, &filter, make_slice(100, 0))
+        .await
+        .unwrap();
+    assert_eq!(result, vec![event]);
+}
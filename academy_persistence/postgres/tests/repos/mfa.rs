@@ -1,9 +1,14 @@
 use academy_demo::{
-    mfa::{ADMIN2_TOTP_1, FOO_TOTP_1},
+    mfa::{ADMIN2_TOTP_1, ADMIN2_WEBAUTHN_1, FOO_TOTP_1},
     user::{ADMIN2, BAR, FOO},
-    SHA256HASH1, UUID1,
+    SHA256HASH1, SHA256HASH2, UUID1, UUID2,
+};
+use academy_models::{
+    encryption::EncryptedData,
+    mfa::{
+        MfaRecoveryCodeHash, TotpDevice, TotpDevicePatchRef, WebauthnDevice, WebauthnDevicePatchRef,
+    },
 };
-use academy_models::mfa::{MfaRecoveryCodeHash, TotpDevice, TotpDevicePatchRef, TotpSecret};
 use academy_persistence_contracts::{mfa::MfaRepository, Database, Transaction};
 use academy_persistence_postgres::mfa::PostgresMfaRepository;
 use academy_utils::Apply;
@@ -36,15 +41,31 @@ async fn list_totp_devices_by_user() {
     assert_eq!(result, []);
 }
 
+#[tokio::test]
+async fn get_totp_device() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    let result = REPO.get_totp_device(&mut txn, FOO_TOTP_1.id).await.unwrap();
+    assert_eq!(result.unwrap(), *FOO_TOTP_1);
+
+    let result = REPO.get_totp_device(&mut txn, UUID1.into()).await.unwrap();
+    assert_eq!(result, None);
+}
+
 #[tokio::test]
 async fn create_totp_device() {
     let expected = TotpDevice {
         id: UUID1.into(),
         user_id: BAR.user.id,
+        name: "Phone".try_into().unwrap(),
         enabled: true,
         created_at: BAR.user.created_at,
     };
-    let secret = TotpSecret::try_new("IZ6GJPVVwQWfRhQTuxwrdBfn".to_owned().into_bytes()).unwrap();
+    let secret = EncryptedData {
+        key_version: Some(1),
+        data: b"encrypted totp secret".to_vec(),
+    };
 
     let db = setup().await;
 
@@ -68,6 +89,45 @@ async fn create_totp_device() {
     assert_eq!(result, secret);
 }
 
+#[tokio::test]
+async fn create_multiple_totp_devices() {
+    let devices = [UUID1, UUID2].map(|id| TotpDevice {
+        id: id.into(),
+        user_id: ADMIN2.user.id,
+        name: "Backup Phone".try_into().unwrap(),
+        enabled: false,
+        created_at: ADMIN2.user.created_at,
+    });
+    let secret = EncryptedData {
+        key_version: Some(1),
+        data: b"encrypted totp secret".to_vec(),
+    };
+
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    for device in &devices {
+        REPO.create_totp_device(&mut txn, device, &secret)
+            .await
+            .unwrap();
+    }
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let mut result = REPO
+        .list_totp_devices_by_user(&mut txn, ADMIN2.user.id)
+        .await
+        .unwrap();
+    result.sort_by_key(|x| x.id);
+    let mut expected = vec![
+        ADMIN2_TOTP_1.clone(),
+        devices[0].clone(),
+        devices[1].clone(),
+    ];
+    expected.sort_by_key(|x| x.id);
+    assert_eq!(result, expected);
+}
+
 #[tokio::test]
 async fn update_totp_device() {
     let expected = FOO_TOTP_1.clone().with(|x| x.enabled = true);
@@ -94,6 +154,62 @@ async fn update_totp_device() {
     assert_eq!(result, [expected]);
 }
 
+#[tokio::test]
+async fn rename_totp_device() {
+    let expected = FOO_TOTP_1
+        .clone()
+        .with(|x| x.name = "Password Manager".try_into().unwrap());
+
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .update_totp_device(
+            &mut txn,
+            expected.id,
+            TotpDevicePatchRef::new().update_name(&expected.name),
+        )
+        .await
+        .unwrap();
+    assert!(result);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.get_totp_device(&mut txn, expected.id).await.unwrap();
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn delete_totp_device() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .delete_totp_device(&mut txn, FOO_TOTP_1.id)
+        .await
+        .unwrap();
+    assert!(result);
+    let result = REPO
+        .delete_totp_device(&mut txn, FOO_TOTP_1.id)
+        .await
+        .unwrap();
+    assert!(!result);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .list_totp_devices_by_user(&mut txn, FOO.user.id)
+        .await
+        .unwrap();
+    assert_eq!(result, []);
+
+    let result = REPO
+        .list_totp_devices_by_user(&mut txn, ADMIN2.user.id)
+        .await
+        .unwrap();
+    assert_eq!(result, vec![ADMIN2_TOTP_1.clone()]);
+}
+
 #[tokio::test]
 async fn delete_totp_devices() {
     let db = setup().await;
@@ -115,7 +231,10 @@ async fn delete_totp_devices() {
 
 #[tokio::test]
 async fn save_and_get_totp_device_secret() {
-    let secret = TotpSecret::try_new("IZ6GJPVVwQWfRhQTuxwrdBfn".to_owned().into_bytes()).unwrap();
+    let secret = EncryptedData {
+        key_version: Some(1),
+        data: b"encrypted totp secret".to_vec(),
+    };
 
     let db = setup().await;
 
@@ -163,33 +282,265 @@ async fn list_enabled_totp_device_secrets() {
 }
 
 #[tokio::test]
-async fn save_get_and_delete_mfa_recovery_code_hash() {
-    let hash = MfaRecoveryCodeHash::from(*SHA256HASH1);
+async fn list_totp_device_secrets() {
+    let secret = EncryptedData {
+        key_version: Some(1),
+        data: b"encrypted totp secret".to_vec(),
+    };
 
     let db = setup().await;
 
     let mut txn = db.begin_transaction().await.unwrap();
-    REPO.save_mfa_recovery_code_hash(&mut txn, FOO.user.id, hash)
+    REPO.save_totp_device_secret(&mut txn, FOO_TOTP_1.id, &secret)
         .await
         .unwrap();
     txn.commit().await.unwrap();
 
+    let mut txn = db.begin_transaction().await.unwrap();
+    let admin_secret = REPO
+        .get_totp_device_secret(&mut txn, ADMIN2_TOTP_1.id)
+        .await
+        .unwrap();
+
+    let mut result = REPO.list_totp_device_secrets(&mut txn).await.unwrap();
+    result.sort_by_key(|(id, _)| *id != ADMIN2_TOTP_1.id);
+    assert_eq!(
+        result,
+        [(ADMIN2_TOTP_1.id, admin_secret), (FOO_TOTP_1.id, secret)]
+    );
+}
+
+#[tokio::test]
+async fn save_count_and_delete_mfa_recovery_code_hashes() {
+    let hashes = [*SHA256HASH1, *SHA256HASH2].map(MfaRecoveryCodeHash::from);
+
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    REPO.save_mfa_recovery_code_hashes(&mut txn, FOO.user.id, &hashes)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .count_mfa_recovery_code_hashes(&mut txn, FOO.user.id)
+        .await
+        .unwrap();
+    assert_eq!(result, 2);
+
+    let result = REPO
+        .delete_mfa_recovery_code_hash(&mut txn, FOO.user.id, hashes[0])
+        .await
+        .unwrap();
+    assert!(result);
+    let result = REPO
+        .delete_mfa_recovery_code_hash(&mut txn, FOO.user.id, hashes[0])
+        .await
+        .unwrap();
+    assert!(!result);
+    let result = REPO
+        .delete_mfa_recovery_code_hash(&mut txn, BAR.user.id, hashes[1])
+        .await
+        .unwrap();
+    assert!(!result);
+    txn.commit().await.unwrap();
+
     let mut txn = db.begin_transaction().await.unwrap();
     let result = REPO
-        .get_mfa_recovery_code_hash(&mut txn, FOO.user.id)
+        .count_mfa_recovery_code_hashes(&mut txn, FOO.user.id)
         .await
         .unwrap();
-    assert_eq!(result.unwrap(), hash);
+    assert_eq!(result, 1);
 
-    REPO.delete_mfa_recovery_code_hash(&mut txn, FOO.user.id)
+    REPO.delete_mfa_recovery_code_hashes_by_user(&mut txn, FOO.user.id)
         .await
         .unwrap();
     txn.commit().await.unwrap();
 
     let mut txn = db.begin_transaction().await.unwrap();
     let result = REPO
-        .get_mfa_recovery_code_hash(&mut txn, FOO.user.id)
+        .count_mfa_recovery_code_hashes(&mut txn, FOO.user.id)
+        .await
+        .unwrap();
+    assert_eq!(result, 0);
+}
+
+#[tokio::test]
+async fn save_mfa_recovery_code_hashes_replaces_existing() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    REPO.save_mfa_recovery_code_hashes(
+        &mut txn,
+        FOO.user.id,
+        &[(*SHA256HASH1).into(), (*SHA256HASH2).into()],
+    )
+    .await
+    .unwrap();
+    REPO.save_mfa_recovery_code_hashes(&mut txn, FOO.user.id, &[(*SHA256HASH1).into()])
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .count_mfa_recovery_code_hashes(&mut txn, FOO.user.id)
+        .await
+        .unwrap();
+    assert_eq!(result, 1);
+}
+
+#[tokio::test]
+async fn list_webauthn_devices_by_user() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    let result = REPO
+        .list_webauthn_devices_by_user(&mut txn, ADMIN2.user.id)
+        .await
+        .unwrap();
+    assert_eq!(result, vec![ADMIN2_WEBAUTHN_1.clone()]);
+
+    let result = REPO
+        .list_webauthn_devices_by_user(&mut txn, FOO.user.id)
+        .await
+        .unwrap();
+    assert_eq!(result, []);
+}
+
+#[tokio::test]
+async fn get_webauthn_device() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    let result = REPO
+        .get_webauthn_device(&mut txn, ADMIN2_WEBAUTHN_1.id)
+        .await
+        .unwrap();
+    assert_eq!(result.unwrap(), *ADMIN2_WEBAUTHN_1);
+
+    let result = REPO
+        .get_webauthn_device(&mut txn, UUID1.into())
+        .await
+        .unwrap();
+    assert_eq!(result, None);
+
+    let result = REPO
+        .get_webauthn_device_by_credential_id(&mut txn, &ADMIN2_WEBAUTHN_1.credential_id)
+        .await
+        .unwrap();
+    assert_eq!(result.unwrap(), *ADMIN2_WEBAUTHN_1);
+
+    let result = REPO
+        .get_webauthn_device_by_credential_id(&mut txn, &vec![1, 2, 3].try_into().unwrap())
         .await
         .unwrap();
     assert_eq!(result, None);
 }
+
+#[tokio::test]
+async fn create_webauthn_device() {
+    let expected = WebauthnDevice {
+        id: UUID1.into(),
+        user_id: FOO.user.id,
+        name: "Phone".try_into().unwrap(),
+        credential_id: vec![1, 2, 3].try_into().unwrap(),
+        public_key: vec![4, 5, 6].into(),
+        sign_count: 0,
+        created_at: FOO.user.created_at,
+        last_used_at: None,
+    };
+
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    REPO.create_webauthn_device(&mut txn, &expected)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .list_webauthn_devices_by_user(&mut txn, FOO.user.id)
+        .await
+        .unwrap();
+    assert_eq!(result, [expected]);
+}
+
+#[tokio::test]
+async fn update_webauthn_device() {
+    let expected = ADMIN2_WEBAUTHN_1.clone().with(|x| {
+        x.name = "Backup Key".try_into().unwrap();
+        x.sign_count = 42;
+        x.last_used_at = Some(ADMIN2.user.created_at);
+    });
+
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .update_webauthn_device(
+            &mut txn,
+            expected.id,
+            WebauthnDevicePatchRef::new()
+                .update_name(&expected.name)
+                .update_sign_count(&expected.sign_count)
+                .update_last_used_at(&expected.last_used_at),
+        )
+        .await
+        .unwrap();
+    assert!(result);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .get_webauthn_device(&mut txn, expected.id)
+        .await
+        .unwrap();
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn delete_webauthn_device() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .delete_webauthn_device(&mut txn, ADMIN2_WEBAUTHN_1.id)
+        .await
+        .unwrap();
+    assert!(result);
+
+    let result = REPO
+        .delete_webauthn_device(&mut txn, ADMIN2_WEBAUTHN_1.id)
+        .await
+        .unwrap();
+    assert!(!result);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .list_webauthn_devices_by_user(&mut txn, ADMIN2.user.id)
+        .await
+        .unwrap();
+    assert_eq!(result, []);
+}
+
+#[tokio::test]
+async fn delete_webauthn_devices_by_user() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    REPO.delete_webauthn_devices_by_user(&mut txn, ADMIN2.user.id)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .list_webauthn_devices_by_user(&mut txn, ADMIN2.user.id)
+        .await
+        .unwrap();
+    assert_eq!(result, []);
+}
@@ -1,7 +1,11 @@
 use academy_models::pagination::PaginationSlice;
 
+mod audit;
 mod mfa;
 mod oauth2;
+mod oauth2_server;
+mod personal_access_token;
+mod role;
 mod session;
 mod user;
 
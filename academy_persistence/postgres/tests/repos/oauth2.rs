@@ -49,6 +49,8 @@ async fn create_link() {
         remote_user: OAuth2UserInfo {
             id: "test-id".try_into().unwrap(),
             name: "test-name".try_into().unwrap(),
+            email: None,
+            email_verified: false,
         },
     };
 
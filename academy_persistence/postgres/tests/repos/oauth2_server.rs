@@ -0,0 +1,276 @@
+use academy_demo::{
+    oauth2_server::{PUBLIC_OAUTH2_CLIENT, TEST_OAUTH2_CLIENT, TEST_OAUTH2_CLIENT_SECRET_HASH},
+    session::BAR_1,
+    user::ADMIN,
+    SHA256HASH1, SHA256HASH2, UUID1,
+};
+use academy_models::oauth2_server::{OAuth2Client, OAuth2Grant, OAuth2Scope};
+use academy_persistence_contracts::{
+    oauth2_server::OAuth2ServerRepository, session::SessionRepository, Database, Transaction,
+};
+use academy_persistence_postgres::{
+    oauth2_server::PostgresOAuth2ServerRepository, session::PostgresSessionRepository,
+    PostgresTransaction,
+};
+use pretty_assertions::assert_eq;
+
+use crate::common::setup;
+
+const REPO: PostgresOAuth2ServerRepository = PostgresOAuth2ServerRepository;
+
+#[tokio::test]
+async fn list_clients() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    let result = REPO.list_clients(&mut txn).await.unwrap();
+    assert_eq!(
+        result,
+        [TEST_OAUTH2_CLIENT.clone(), PUBLIC_OAUTH2_CLIENT.clone()]
+    );
+}
+
+#[tokio::test]
+async fn get_client() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    let result = REPO
+        .get_client(&mut txn, TEST_OAUTH2_CLIENT.id)
+        .await
+        .unwrap();
+    assert_eq!(result.unwrap(), *TEST_OAUTH2_CLIENT);
+
+    let result = REPO.get_client(&mut txn, UUID1.into()).await.unwrap();
+    assert_eq!(result, None);
+}
+
+#[tokio::test]
+async fn create_client() {
+    let client = OAuth2Client {
+        id: UUID1.into(),
+        name: "New Client".try_into().unwrap(),
+        redirect_uris: vec!["https://new.example.com/callback".parse().unwrap()],
+        confidential: true,
+        created_at: ADMIN.user.created_at,
+    };
+
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    REPO.create_client(&mut txn, &client, Some((*SHA256HASH2).into()))
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.get_client(&mut txn, client.id).await.unwrap();
+    assert_eq!(result.unwrap(), client);
+
+    let result = REPO
+        .get_client_secret_hash(&mut txn, client.id)
+        .await
+        .unwrap();
+    assert_eq!(result, Some((*SHA256HASH2).into()));
+}
+
+#[tokio::test]
+async fn get_client_secret_hash() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    let result = REPO
+        .get_client_secret_hash(&mut txn, TEST_OAUTH2_CLIENT.id)
+        .await
+        .unwrap();
+    assert_eq!(result, Some(*TEST_OAUTH2_CLIENT_SECRET_HASH));
+
+    let result = REPO
+        .get_client_secret_hash(&mut txn, PUBLIC_OAUTH2_CLIENT.id)
+        .await
+        .unwrap();
+    assert_eq!(result, None);
+
+    let result = REPO
+        .get_client_secret_hash(&mut txn, UUID1.into())
+        .await
+        .unwrap();
+    assert_eq!(result, None);
+}
+
+#[tokio::test]
+async fn delete_client() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    create_grant(&mut txn).await;
+
+    let result = REPO
+        .delete_client(&mut txn, TEST_OAUTH2_CLIENT.id)
+        .await
+        .unwrap();
+    assert!(result);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .get_client(&mut txn, TEST_OAUTH2_CLIENT.id)
+        .await
+        .unwrap();
+    assert_eq!(result, None);
+
+    let result = REPO.get_grant(&mut txn, BAR_1.id).await.unwrap();
+    assert_eq!(result, None);
+
+    let result = REPO
+        .delete_client(&mut txn, TEST_OAUTH2_CLIENT.id)
+        .await
+        .unwrap();
+    assert!(!result);
+}
+
+#[tokio::test]
+async fn create_get_grant() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let grant = create_grant(&mut txn).await;
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.get_grant(&mut txn, BAR_1.id).await.unwrap();
+    assert_eq!(result.unwrap(), grant);
+
+    let result = REPO
+        .get_grant_by_refresh_token_hash(&mut txn, (*SHA256HASH1).into())
+        .await
+        .unwrap();
+    assert_eq!(result.unwrap(), grant);
+
+    let result = REPO.get_grant(&mut txn, UUID1.into()).await.unwrap();
+    assert_eq!(result, None);
+
+    let result = REPO
+        .get_grant_by_refresh_token_hash(&mut txn, (*SHA256HASH2).into())
+        .await
+        .unwrap();
+    assert_eq!(result, None);
+}
+
+#[tokio::test]
+async fn update_grant_refresh_token_hash() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let grant = create_grant(&mut txn).await;
+
+    let result = REPO
+        .update_grant_refresh_token_hash(&mut txn, BAR_1.id, (*SHA256HASH2).into())
+        .await
+        .unwrap();
+    assert!(result);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .get_grant_by_refresh_token_hash(&mut txn, (*SHA256HASH1).into())
+        .await
+        .unwrap();
+    assert_eq!(result, None);
+
+    let result = REPO
+        .get_grant_by_refresh_token_hash(&mut txn, (*SHA256HASH2).into())
+        .await
+        .unwrap();
+    assert_eq!(result.unwrap(), grant);
+
+    let result = REPO
+        .update_grant_refresh_token_hash(&mut txn, UUID1.into(), (*SHA256HASH1).into())
+        .await
+        .unwrap();
+    assert!(!result);
+}
+
+#[tokio::test]
+async fn delete_session_deletes_grant() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    create_grant(&mut txn).await;
+
+    PostgresSessionRepository
+        .delete(&mut txn, BAR_1.id)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.get_grant(&mut txn, BAR_1.id).await.unwrap();
+    assert_eq!(result, None);
+}
+
+#[tokio::test]
+async fn list_grants_by_client() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let grant = create_grant(&mut txn).await;
+
+    let result = REPO
+        .list_grants_by_client(&mut txn, TEST_OAUTH2_CLIENT.id)
+        .await
+        .unwrap();
+    assert_eq!(result, [grant]);
+
+    let result = REPO
+        .list_grants_by_client(&mut txn, PUBLIC_OAUTH2_CLIENT.id)
+        .await
+        .unwrap();
+    assert_eq!(result, []);
+}
+
+#[tokio::test]
+async fn delete_grants_by_user() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    create_grant(&mut txn).await;
+
+    REPO.delete_grants_by_user(&mut txn, ADMIN.user.id)
+        .await
+        .unwrap();
+    let result = REPO.get_grant(&mut txn, BAR_1.id).await.unwrap();
+    assert!(result.is_some());
+
+    REPO.delete_grants_by_user(&mut txn, BAR_1.user_id)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.get_grant(&mut txn, BAR_1.id).await.unwrap();
+    assert_eq!(result, None);
+
+    let result = PostgresSessionRepository
+        .get(&mut txn, BAR_1.id)
+        .await
+        .unwrap();
+    assert!(result.is_some());
+}
+
+async fn create_grant(txn: &mut PostgresTransaction) -> OAuth2Grant {
+    let grant = OAuth2Grant {
+        session_id: BAR_1.id,
+        client_id: TEST_OAUTH2_CLIENT.id,
+        scopes: [OAuth2Scope::Openid, OAuth2Scope::Email]
+            .into_iter()
+            .collect(),
+    };
+
+    PostgresSessionRepository.create(txn, &BAR_1).await.unwrap();
+    REPO.create_grant(txn, &grant, (*SHA256HASH1).into())
+        .await
+        .unwrap();
+
+    grant
+}
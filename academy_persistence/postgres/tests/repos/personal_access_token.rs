@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use academy_demo::{
+    personal_access_token::{ADMIN_PAT, FOO_PAT},
+    user::{ADMIN, BAR, FOO},
+    SHA256HASH1, SHA256HASH2, UUID1,
+};
+use academy_models::{personal_access_token::PersonalAccessToken, Sha256Hash};
+use academy_persistence_contracts::{
+    personal_access_token::PersonalAccessTokenRepository, user::UserRepository, Database,
+    Transaction,
+};
+use academy_persistence_postgres::{
+    personal_access_token::PostgresPersonalAccessTokenRepository, user::PostgresUserRepository,
+};
+use pretty_assertions::assert_eq;
+
+use crate::common::setup;
+
+const REPO: PostgresPersonalAccessTokenRepository = PostgresPersonalAccessTokenRepository;
+
+#[tokio::test]
+async fn list_by_user() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    let result = REPO.list_by_user(&mut txn, ADMIN.user.id).await.unwrap();
+    assert_eq!(result, vec![ADMIN_PAT.clone()]);
+
+    let result = REPO.list_by_user(&mut txn, FOO.user.id).await.unwrap();
+    assert_eq!(result, vec![FOO_PAT.clone()]);
+
+    let result = REPO.list_by_user(&mut txn, BAR.user.id).await.unwrap();
+    assert_eq!(result, []);
+}
+
+#[tokio::test]
+async fn get() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    let result = REPO.get(&mut txn, FOO_PAT.id).await.unwrap();
+    assert_eq!(result.unwrap(), *FOO_PAT);
+
+    let result = REPO.get(&mut txn, UUID1.into()).await.unwrap();
+    assert_eq!(result, None);
+}
+
+#[tokio::test]
+async fn get_by_hash() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    let result = REPO
+        .get_by_hash(&mut txn, (*SHA256HASH1).into())
+        .await
+        .unwrap();
+    assert_eq!(result.unwrap(), *ADMIN_PAT);
+
+    let result = REPO
+        .get_by_hash(&mut txn, (*SHA256HASH2).into())
+        .await
+        .unwrap();
+    assert_eq!(result.unwrap(), *FOO_PAT);
+
+    let result = REPO
+        .get_by_hash(&mut txn, Sha256Hash([42; 32]).into())
+        .await
+        .unwrap();
+    assert_eq!(result, None);
+}
+
+#[tokio::test]
+async fn create() {
+    let token = PersonalAccessToken {
+        id: UUID1.into(),
+        user_id: BAR.user.id,
+        name: "test".try_into().unwrap(),
+        scopes: Default::default(),
+        created_at: BAR.user.created_at,
+        expires_at: Some(BAR.user.created_at + Duration::from_secs(3600)),
+        last_used_at: None,
+    };
+
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    REPO.create(&mut txn, &token, Sha256Hash([42; 32]).into())
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .get_by_hash(&mut txn, Sha256Hash([42; 32]).into())
+        .await
+        .unwrap();
+    assert_eq!(result.unwrap(), token);
+
+    let result = REPO.list_by_user(&mut txn, BAR.user.id).await.unwrap();
+    assert_eq!(result, [token]);
+}
+
+#[tokio::test]
+async fn update_last_used_at() {
+    let db = setup().await;
+    let now = FOO_PAT.created_at + Duration::from_secs(1234);
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .update_last_used_at(&mut txn, FOO_PAT.id, now)
+        .await
+        .unwrap();
+    assert!(result);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.get(&mut txn, FOO_PAT.id).await.unwrap();
+    assert_eq!(
+        result.unwrap(),
+        PersonalAccessToken {
+            last_used_at: Some(now),
+            ..FOO_PAT.clone()
+        }
+    );
+
+    let result = REPO
+        .update_last_used_at(&mut txn, UUID1.into(), now)
+        .await
+        .unwrap();
+    assert!(!result);
+}
+
+#[tokio::test]
+async fn delete() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.delete(&mut txn, FOO_PAT.id).await.unwrap();
+    assert!(result);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.get(&mut txn, FOO_PAT.id).await.unwrap();
+    assert_eq!(result, None);
+
+    let result = REPO.delete(&mut txn, FOO_PAT.id).await.unwrap();
+    assert!(!result);
+}
+
+#[tokio::test]
+async fn delete_expired() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .delete_expired(
+            &mut txn,
+            FOO_PAT.expires_at.unwrap() - Duration::from_secs(1),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result, 0);
+
+    let result = REPO
+        .delete_expired(&mut txn, FOO_PAT.expires_at.unwrap())
+        .await
+        .unwrap();
+    assert_eq!(result, 1);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.get(&mut txn, FOO_PAT.id).await.unwrap();
+    assert_eq!(result, None);
+
+    let result = REPO.get(&mut txn, ADMIN_PAT.id).await.unwrap();
+    assert_eq!(result.unwrap(), *ADMIN_PAT);
+}
+
+#[tokio::test]
+async fn delete_user_cascades() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    PostgresUserRepository
+        .delete(&mut txn, FOO.user.id)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.get(&mut txn, FOO_PAT.id).await.unwrap();
+    assert_eq!(result, None);
+}
+
+#[tokio::test]
+async fn delete_by_user() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    REPO.delete_by_user(&mut txn, FOO.user.id).await.unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.list_by_user(&mut txn, FOO.user.id).await.unwrap();
+    assert_eq!(result, []);
+
+    let result = REPO.get(&mut txn, ADMIN_PAT.id).await.unwrap();
+    assert_eq!(result.unwrap(), *ADMIN_PAT);
+}
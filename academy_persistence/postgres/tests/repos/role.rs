@@ -0,0 +1,244 @@
+use academy_demo::{
+    role::{ALL_ROLES, FINANCE, MODERATOR, SUPPORT},
+    user::{ADMIN, BAR, FOO},
+    UUID1,
+};
+use academy_models::role::{Permission, Role};
+use academy_persistence_contracts::{
+    role::{RoleRepoError, RoleRepository},
+    user::UserRepository,
+    Database, Transaction,
+};
+use academy_persistence_postgres::{role::PostgresRoleRepository, user::PostgresUserRepository};
+use academy_utils::{assert_matches, patch::Patch};
+use pretty_assertions::assert_eq;
+
+use crate::common::setup;
+
+const REPO: PostgresRoleRepository = PostgresRoleRepository;
+
+#[tokio::test]
+async fn list() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    let result = REPO.list(&mut txn).await.unwrap();
+    assert_eq!(
+        result,
+        ALL_ROLES.iter().copied().cloned().collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn get() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    let result = REPO.get(&mut txn, MODERATOR.id).await.unwrap();
+    assert_eq!(result.unwrap(), *MODERATOR);
+
+    let result = REPO.get(&mut txn, UUID1.into()).await.unwrap();
+    assert_eq!(result, None);
+}
+
+#[tokio::test]
+async fn get_by_name() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    let result = REPO
+        .get_by_name(&mut txn, &"FiNaNcE".try_into().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(result.unwrap(), *FINANCE);
+
+    let result = REPO
+        .get_by_name(&mut txn, &"nobody".try_into().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(result, None);
+}
+
+#[tokio::test]
+async fn create() {
+    let role = Role {
+        id: UUID1.into(),
+        name: "test".try_into().unwrap(),
+        permissions: [Permission::DisableUsers, Permission::EditInvoiceInfo].into(),
+        created_at: SUPPORT.created_at,
+    };
+
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    REPO.create(&mut txn, &role).await.unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.get(&mut txn, role.id).await.unwrap();
+    assert_eq!(result.unwrap(), role);
+}
+
+#[tokio::test]
+async fn create_name_conflict() {
+    let role = Role {
+        id: UUID1.into(),
+        name: "SUPPORT".try_into().unwrap(),
+        ..SUPPORT.clone()
+    };
+
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.create(&mut txn, &role).await;
+    assert_matches!(result, Err(RoleRepoError::NameConflict));
+}
+
+#[tokio::test]
+async fn update() {
+    let expected = Role {
+        name: "helpdesk".try_into().unwrap(),
+        permissions: [Permission::ReadUsers].into(),
+        ..SUPPORT.clone()
+    };
+
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .update(&mut txn, SUPPORT.id, expected.as_patch_ref())
+        .await
+        .unwrap();
+    assert!(result);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.get(&mut txn, SUPPORT.id).await.unwrap();
+    assert_eq!(result.unwrap(), expected);
+
+    let result = REPO
+        .update(&mut txn, UUID1.into(), expected.as_patch_ref())
+        .await
+        .unwrap();
+    assert!(!result);
+}
+
+#[tokio::test]
+async fn update_name_conflict() {
+    let role = Role {
+        name: "moderator".try_into().unwrap(),
+        ..SUPPORT.clone()
+    };
+
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.update(&mut txn, SUPPORT.id, role.as_patch_ref()).await;
+    assert_matches!(result, Err(RoleRepoError::NameConflict));
+}
+
+#[tokio::test]
+async fn delete() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.delete(&mut txn, SUPPORT.id).await.unwrap();
+    assert!(result);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.get(&mut txn, SUPPORT.id).await.unwrap();
+    assert_eq!(result, None);
+
+    let result = REPO.list_by_user(&mut txn, FOO.user.id).await.unwrap();
+    assert_eq!(result, []);
+
+    let result = REPO.delete(&mut txn, SUPPORT.id).await.unwrap();
+    assert!(!result);
+}
+
+#[tokio::test]
+async fn list_by_user() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    let result = REPO.list_by_user(&mut txn, FOO.user.id).await.unwrap();
+    assert_eq!(result, vec![SUPPORT.clone()]);
+
+    let result = REPO.list_by_user(&mut txn, BAR.user.id).await.unwrap();
+    assert_eq!(result, []);
+}
+
+#[tokio::test]
+async fn list_users() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    let result = REPO.list_users(&mut txn, SUPPORT.id).await.unwrap();
+    assert_eq!(result, vec![FOO.user.id]);
+
+    let result = REPO.list_users(&mut txn, FINANCE.id).await.unwrap();
+    assert_eq!(result, []);
+}
+
+#[tokio::test]
+async fn add_to_user() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .add_to_user(&mut txn, FOO.user.id, FINANCE.id)
+        .await
+        .unwrap();
+    assert!(result);
+
+    let result = REPO
+        .add_to_user(&mut txn, FOO.user.id, FINANCE.id)
+        .await
+        .unwrap();
+    assert!(!result);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.list_by_user(&mut txn, FOO.user.id).await.unwrap();
+    assert_eq!(result, vec![SUPPORT.clone(), FINANCE.clone()]);
+}
+
+#[tokio::test]
+async fn remove_from_user() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .remove_from_user(&mut txn, FOO.user.id, SUPPORT.id)
+        .await
+        .unwrap();
+    assert!(result);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.list_by_user(&mut txn, FOO.user.id).await.unwrap();
+    assert_eq!(result, []);
+
+    let result = REPO
+        .remove_from_user(&mut txn, ADMIN.user.id, SUPPORT.id)
+        .await
+        .unwrap();
+    assert!(!result);
+}
+
+#[tokio::test]
+async fn delete_user_cascades() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    PostgresUserRepository
+        .delete(&mut txn, FOO.user.id)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO.list_users(&mut txn, SUPPORT.id).await.unwrap();
+    assert_eq!(result, []);
+}
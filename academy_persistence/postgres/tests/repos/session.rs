@@ -1,11 +1,12 @@
 use std::time::Duration;
 
 use academy_demo::{
+    oauth2::TEST_OAUTH2_PROVIDER_ID,
     session::{ADMIN_1, ALL_SESSIONS, FOO_1, FOO_2},
-    user::{ADMIN, ALL_USERS, FOO},
+    user::{ADMIN, ALL_USERS, BAR, FOO},
     SHA256HASH1, SHA256HASH2, UUID1,
 };
-use academy_models::session::{Session, SessionRefreshTokenHash};
+use academy_models::session::{Session, SessionLoginMethod, SessionRefreshTokenHash};
 use academy_persistence_contracts::{session::SessionRepository, Database, Transaction};
 use academy_persistence_postgres::session::PostgresSessionRepository;
 use academy_utils::patch::Patch;
@@ -82,6 +83,12 @@ async fn create() {
         id: UUID1.into(),
         user_id: ADMIN.user.id,
         device_name: Some("some device name".try_into().unwrap()),
+        ip: Some([1, 2, 3, 4].into()),
+        device_fingerprint: Some((*SHA256HASH1).into()),
+        last_ip: Some([5, 6, 7, 8].into()),
+        browser: Some("Safari 18.0".into()),
+        os: Some("Mac OSX".into()),
+        login_method: SessionLoginMethod::OAuth2(TEST_OAUTH2_PROVIDER_ID.clone()),
         created_at: ADMIN.user.created_at + Duration::from_secs(10 * 3600),
         updated_at: ADMIN.user.created_at + Duration::from_secs(7 * 24 * 3600),
     };
@@ -103,6 +110,11 @@ async fn update() {
 
     let expected = Session {
         id: FOO_1.id,
+        ip: FOO_1.ip,
+        device_fingerprint: FOO_1.device_fingerprint,
+        browser: FOO_1.browser.clone(),
+        os: FOO_1.os.clone(),
+        login_method: FOO_1.login_method.clone(),
         created_at: FOO_1.created_at,
         ..FOO_2.clone()
     };
@@ -234,3 +246,107 @@ async fn refresh_token_hash() {
         .unwrap();
     assert_eq!(result, None);
 }
+
+#[tokio::test]
+async fn superseded_refresh_token_hashes() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    REPO.save_superseded_refresh_token_hash(
+        &mut txn,
+        FOO_1.id,
+        (*SHA256HASH1).into(),
+        FOO_1.updated_at,
+    )
+    .await
+    .unwrap();
+    REPO.save_superseded_refresh_token_hash(
+        &mut txn,
+        FOO_2.id,
+        (*SHA256HASH2).into(),
+        FOO_2.updated_at,
+    )
+    .await
+    .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .get_by_superseded_refresh_token_hash(&mut txn, (*SHA256HASH1).into())
+        .await
+        .unwrap();
+    assert_eq!(result.unwrap(), *FOO_1);
+
+    let result = REPO
+        .get_by_refresh_token_hash(&mut txn, (*SHA256HASH1).into())
+        .await
+        .unwrap();
+    assert_eq!(result, None);
+
+    let result = REPO
+        .delete_superseded_refresh_token_hashes(&mut txn, FOO_1.updated_at)
+        .await
+        .unwrap();
+    assert_eq!(result, 1);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .get_by_superseded_refresh_token_hash(&mut txn, (*SHA256HASH1).into())
+        .await
+        .unwrap();
+    assert_eq!(result.unwrap(), *FOO_1);
+
+    let result = REPO
+        .get_by_superseded_refresh_token_hash(&mut txn, (*SHA256HASH2).into())
+        .await
+        .unwrap();
+    assert_eq!(result, None);
+}
+
+#[tokio::test]
+async fn known_devices() {
+    let db = setup().await;
+    let mut txn = db.begin_transaction().await.unwrap();
+
+    assert!(REPO.has_known_devices(&mut txn, FOO.user.id).await.unwrap());
+    assert!(!REPO.has_known_devices(&mut txn, BAR.user.id).await.unwrap());
+
+    let unknown_ip = Some([1, 2, 3, 4].into());
+    let unknown_fingerprint = Some((*SHA256HASH1).into());
+
+    assert!(REPO
+        .is_known_device(&mut txn, FOO.user.id, FOO_1.device_fingerprint, unknown_ip)
+        .await
+        .unwrap());
+    assert!(REPO
+        .is_known_device(&mut txn, FOO.user.id, unknown_fingerprint, FOO_2.ip)
+        .await
+        .unwrap());
+    assert!(!REPO
+        .is_known_device(&mut txn, FOO.user.id, unknown_fingerprint, unknown_ip)
+        .await
+        .unwrap());
+    assert!(!REPO
+        .is_known_device(&mut txn, FOO.user.id, None, None)
+        .await
+        .unwrap());
+
+    REPO.save_known_device(&mut txn, FOO.user.id, unknown_fingerprint, unknown_ip)
+        .await
+        .unwrap();
+    REPO.save_known_device(&mut txn, FOO.user.id, unknown_fingerprint, unknown_ip)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    assert!(REPO
+        .is_known_device(&mut txn, FOO.user.id, unknown_fingerprint, None)
+        .await
+        .unwrap());
+    assert!(!REPO
+        .is_known_device(&mut txn, BAR.user.id, unknown_fingerprint, unknown_ip)
+        .await
+        .unwrap());
+}
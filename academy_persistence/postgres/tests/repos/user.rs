@@ -1,11 +1,13 @@
-use std::sync::LazyLock;
+use std::{sync::LazyLock, time::Duration};
 
 use academy_demo::{
     oauth2::FOO_OAUTH2_LINK_1,
-    user::{ADMIN, ADMIN2, ALL_USERS, BAR, FOO},
-    UUID1,
+    user::{ADMIN, ADMIN2, ALL_USERS, BAR, FOO, FOO_EMAIL_1, FOO_NAME_HISTORY_1},
+    SHA256HASH1, UUID1,
+};
+use academy_models::user::{
+    User, UserComposite, UserDetails, UserEmail, UserFilter, UserNameHistoryEntry, UserPatch,
 };
-use academy_models::user::{User, UserComposite, UserDetails, UserFilter};
 use academy_persistence_contracts::{
     user::{UserRepoError, UserRepository},
     Database, Transaction,
@@ -40,6 +42,7 @@ static FILTER_TESTS: LazyLock<Vec<(UserFilter, Vec<&UserComposite>)>> = LazyLock
         (filter!(email: "EXAMPLE.com"), vec![&ADMIN, &ADMIN2, &FOO]),
         (filter!(email: ""), vec![&ADMIN, &ADMIN2, &FOO]),
         (filter!(email: "admin"), vec![&ADMIN, &ADMIN2]),
+        (filter!(email: "UNIVERSITY"), vec![&FOO]),
         (filter!(email_verified: true), vec![&ADMIN, &ADMIN2, &FOO]),
         (filter!(email_verified: false), vec![&BAR]),
         (filter!(admin: true), vec![&ADMIN, &ADMIN2]),
@@ -156,6 +159,16 @@ async fn get_composite_by_email() {
         assert_eq!(&result, user);
     }
 
+    let result = REPO
+        .get_composite_by_email(
+            &mut txn,
+            &FOO_EMAIL_1.email.as_str().to_uppercase().parse().unwrap(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(result, *FOO);
+
     let result = REPO
         .get_composite_by_email(&mut txn, &"doesnotexist@example.com".parse().unwrap())
         .await
@@ -354,6 +367,23 @@ async fn update_user_email_conflict() {
     assert_matches!(result, Err(UserRepoError::EmailConflict));
 }
 
+#[tokio::test]
+async fn update_user_email_conflict_additional() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .update(
+            &mut txn,
+            BAR.user.id,
+            UserPatch::new()
+                .update_email(Some(FOO_EMAIL_1.email.clone()))
+                .as_ref(),
+        )
+        .await;
+    assert_matches!(result, Err(UserRepoError::EmailConflict));
+}
+
 #[tokio::test]
 async fn update_profile() {
     let db = setup().await;
@@ -423,6 +453,270 @@ async fn delete() {
     assert!(!result);
 }
 
+#[tokio::test]
+async fn delete_by_deleted_at() {
+    let db = setup().await;
+
+    let deleted_at = FOO.user.created_at + Duration::from_secs(3600);
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    for (user_id, deleted_at) in [
+        (FOO.user.id, deleted_at),
+        (BAR.user.id, deleted_at + Duration::from_secs(2)),
+    ] {
+        REPO.update(
+            &mut txn,
+            user_id,
+            UserPatch::new()
+                .update_deleted_at(Some(deleted_at))
+                .as_ref(),
+        )
+        .await
+        .unwrap();
+    }
+    txn.commit().await.unwrap();
+
+    let now = deleted_at + Duration::from_secs(3600);
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let result = REPO
+        .delete_by_deleted_at(&mut txn, deleted_at + Duration::from_secs(1), now)
+        .await
+        .unwrap();
+    assert_eq!(result, vec![FOO.user.id]);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    assert_eq!(
+        REPO.get_composite(&mut txn, FOO.user.id).await.unwrap(),
+        None
+    );
+    assert!(REPO.exists(&mut txn, BAR.user.id).await.unwrap());
+    assert_eq!(
+        REPO.get_composite(&mut txn, ADMIN.user.id)
+            .await
+            .unwrap()
+            .unwrap(),
+        *ADMIN
+    );
+
+    assert_eq!(
+        REPO.get_latest_name_history_entry(&mut txn, &FOO.user.name)
+            .await
+            .unwrap(),
+        Some(UserNameHistoryEntry {
+            user_id: FOO.user.id,
+            name: FOO.user.name.clone(),
+            changed_at: now,
+        })
+    );
+    assert_eq!(
+        REPO.get_latest_name_history_entry(&mut txn, &FOO_NAME_HISTORY_1.name)
+            .await
+            .unwrap(),
+        Some(FOO_NAME_HISTORY_1.clone())
+    );
+}
+
+#[tokio::test]
+async fn restore_code_hash() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    assert_eq!(
+        REPO.get_restore_code_hash(&mut txn, FOO.user.id)
+            .await
+            .unwrap(),
+        None
+    );
+
+    REPO.save_restore_code_hash(&mut txn, FOO.user.id, Some((*SHA256HASH1).into()))
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    assert_eq!(
+        REPO.get_restore_code_hash(&mut txn, FOO.user.id)
+            .await
+            .unwrap(),
+        Some((*SHA256HASH1).into())
+    );
+    assert_eq!(
+        REPO.get_restore_code_hash(&mut txn, BAR.user.id)
+            .await
+            .unwrap(),
+        None
+    );
+
+    REPO.save_restore_code_hash(&mut txn, FOO.user.id, None)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    assert_eq!(
+        REPO.get_restore_code_hash(&mut txn, FOO.user.id)
+            .await
+            .unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn export_requests() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    assert_eq!(REPO.list_export_requests(&mut txn).await.unwrap(), []);
+
+    REPO.save_export_requested(&mut txn, FOO.user.id, true)
+        .await
+        .unwrap();
+    REPO.save_export_requested(&mut txn, BAR.user.id, true)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    let mut user_ids = REPO.list_export_requests(&mut txn).await.unwrap();
+    user_ids.sort();
+    let mut expected = [FOO.user.id, BAR.user.id];
+    expected.sort();
+    assert_eq!(user_ids, expected);
+
+    REPO.save_export_requested(&mut txn, BAR.user.id, false)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    assert_eq!(
+        REPO.list_export_requests(&mut txn).await.unwrap(),
+        [FOO.user.id]
+    );
+}
+
+#[tokio::test]
+async fn emails() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    assert_eq!(
+        REPO.list_emails(&mut txn, FOO.user.id).await.unwrap(),
+        vec![FOO_EMAIL_1.clone()]
+    );
+    assert_eq!(REPO.list_emails(&mut txn, BAR.user.id).await.unwrap(), []);
+
+    let email = UserEmail {
+        user_id: BAR.user.id,
+        email: "bar@example.com".parse().unwrap(),
+        created_at: BAR.user.created_at,
+    };
+    REPO.add_email(&mut txn, &email).await.unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    assert_eq!(
+        REPO.list_emails(&mut txn, BAR.user.id).await.unwrap(),
+        vec![email.clone()]
+    );
+    assert_eq!(
+        REPO.get_composite_by_email(&mut txn, &email.email)
+            .await
+            .unwrap()
+            .unwrap(),
+        *BAR
+    );
+
+    let result = REPO
+        .remove_email(&mut txn, BAR.user.id, &"BAR@example.com".parse().unwrap())
+        .await
+        .unwrap();
+    assert!(result);
+    let result = REPO
+        .remove_email(&mut txn, BAR.user.id, &email.email)
+        .await
+        .unwrap();
+    assert!(!result);
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    assert_eq!(REPO.list_emails(&mut txn, BAR.user.id).await.unwrap(), []);
+}
+
+#[tokio::test]
+async fn add_email_conflict() {
+    let db = setup().await;
+
+    for email in [
+        FOO.user.email.clone().unwrap(),
+        FOO_EMAIL_1.email.as_str().to_uppercase().parse().unwrap(),
+    ] {
+        let mut txn = db.begin_transaction().await.unwrap();
+        let result = REPO
+            .add_email(
+                &mut txn,
+                &UserEmail {
+                    user_id: BAR.user.id,
+                    email,
+                    created_at: BAR.user.created_at,
+                },
+            )
+            .await;
+        assert_matches!(result, Err(UserRepoError::EmailConflict));
+    }
+}
+
+#[tokio::test]
+async fn name_history() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    assert_eq!(
+        REPO.list_name_history(&mut txn, FOO.user.id).await.unwrap(),
+        vec![FOO_NAME_HISTORY_1.clone()]
+    );
+    assert_eq!(
+        REPO.list_name_history(&mut txn, BAR.user.id).await.unwrap(),
+        []
+    );
+    assert_eq!(
+        REPO.get_latest_name_history_entry(&mut txn, &"FOO_OLD".try_into().unwrap())
+            .await
+            .unwrap()
+            .unwrap(),
+        *FOO_NAME_HISTORY_1
+    );
+
+    let entry = UserNameHistoryEntry {
+        user_id: BAR.user.id,
+        name: "Foo_Old".try_into().unwrap(),
+        changed_at: FOO_NAME_HISTORY_1.changed_at + Duration::from_secs(60),
+    };
+    REPO.add_name_history_entry(&mut txn, &entry).await.unwrap();
+    txn.commit().await.unwrap();
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    assert_eq!(
+        REPO.list_name_history(&mut txn, BAR.user.id).await.unwrap(),
+        vec![entry.clone()]
+    );
+    assert_eq!(
+        REPO.get_latest_name_history_entry(&mut txn, &FOO_NAME_HISTORY_1.name)
+            .await
+            .unwrap()
+            .unwrap(),
+        entry
+    );
+    assert_eq!(
+        REPO.get_latest_name_history_entry(&mut txn, &"bar_old".try_into().unwrap())
+            .await
+            .unwrap(),
+        None
+    );
+}
+
 #[tokio::test]
 async fn password() {
     let db = setup().await;
@@ -465,3 +759,27 @@ async fn password() {
     let result = REPO.get_password_hash(&mut txn, FOO.user.id).await.unwrap();
     assert_eq!(result, None);
 }
+
+#[tokio::test]
+async fn list_password_hashes() {
+    let db = setup().await;
+
+    let mut txn = db.begin_transaction().await.unwrap();
+    REPO.save_password_hash(&mut txn, FOO.user.id, "the password hash".into())
+        .await
+        .unwrap();
+    REPO.save_password_hash(&mut txn, BAR.user.id, "another password hash".into())
+        .await
+        .unwrap();
+
+    let result = REPO.list_password_hashes(&mut txn).await.unwrap();
+    assert_eq!(
+        result.len(),
+        ALL_USERS
+            .iter()
+            .filter(|u| u.details.password_login)
+            .count()
+    );
+    assert!(result.contains(&"the password hash".into()));
+    assert!(result.contains(&"another password hash".into()));
+}
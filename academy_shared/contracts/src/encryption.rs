@@ -0,0 +1,42 @@
+use academy_models::{encryption::EncryptedData, Sensitive};
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait EncryptionService: Send + Sync + 'static {
+    /// Encrypt the given data using the current encryption key.
+    fn encrypt(&self, data: &[u8]) -> anyhow::Result<EncryptedData>;
+
+    /// Decrypt data that has been encrypted using any of the configured
+    /// encryption keys.
+    fn decrypt(&self, data: &EncryptedData) -> anyhow::Result<Sensitive<Vec<u8>>>;
+
+    /// Return whether the given data has not been encrypted using the current
+    /// encryption key and should therefore be encrypted again.
+    fn is_outdated(&self, data: &EncryptedData) -> bool;
+}
+
+#[cfg(feature = "mock")]
+impl MockEncryptionService {
+    pub fn with_encrypt(mut self, data: Vec<u8>, result: EncryptedData) -> Self {
+        self.expect_encrypt()
+            .once()
+            .withf(move |x| x == data)
+            .return_once(|_| Ok(result));
+        self
+    }
+
+    pub fn with_decrypt(mut self, data: EncryptedData, result: Vec<u8>) -> Self {
+        self.expect_decrypt()
+            .once()
+            .with(mockall::predicate::eq(data))
+            .return_once(|_| Ok(result.into()));
+        self
+    }
+
+    pub fn with_is_outdated(mut self, data: EncryptedData, result: bool) -> Self {
+        self.expect_is_outdated()
+            .once()
+            .with(mockall::predicate::eq(data))
+            .return_once(move |_| result);
+        self
+    }
+}
@@ -1,5 +1,6 @@
 use std::{fmt::Debug, time::Duration};
 
+use academy_models::jwt::Jwks;
 use serde::{de::DeserializeOwned, Serialize};
 use thiserror::Error;
 
@@ -21,6 +22,10 @@ pub trait JwtService: Send + Sync + 'static {
         &self,
         jwt: &S,
     ) -> Result<T, VerifyJwtError<T>>;
+
+    /// Return the public keys that can be used to verify the signatures of
+    /// JWTs signed with an asymmetric algorithm.
+    fn jwks(&self) -> &Jwks;
 }
 
 #[derive(Debug, Error)]
@@ -63,4 +68,9 @@ impl MockJwtService {
             .return_once(|_| result);
         self
     }
+
+    pub fn with_jwks(mut self, jwks: Jwks) -> Self {
+        self.expect_jwks().once().with().return_const(jwks);
+        self
+    }
 }
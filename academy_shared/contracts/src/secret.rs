@@ -1,4 +1,6 @@
-use academy_models::{mfa::MfaRecoveryCode, Sensitive, VerificationCode};
+use academy_models::{
+    mfa::MfaRecoveryCode, session::SessionLoginCode, Sensitive, VerificationCode,
+};
 
 #[cfg_attr(feature = "mock", mockall::automock)]
 pub trait SecretService: Send + Sync + 'static {
@@ -13,6 +15,9 @@ pub trait SecretService: Send + Sync + 'static {
 
     /// Generate a new random mfa recovery code.
     fn generate_mfa_recovery_code(&self) -> MfaRecoveryCode;
+
+    /// Generate a new random numeric session login code.
+    fn generate_session_login_code(&self) -> SessionLoginCode;
 }
 
 #[cfg(feature = "mock")]
@@ -48,4 +53,12 @@ impl MockSecretService {
             .return_once(|| result);
         self
     }
+
+    pub fn with_generate_session_login_code(mut self, result: SessionLoginCode) -> Self {
+        self.expect_generate_session_login_code()
+            .once()
+            .with()
+            .return_once(|| result);
+        self
+    }
 }
@@ -0,0 +1,25 @@
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait UserAgentService: Send + Sync + 'static {
+    /// Extract the browser and operating system from the given user agent
+    /// string.
+    fn parse(&self, user_agent: &str) -> UserAgentInfo;
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserAgentInfo {
+    /// Name and version of the browser (e.g. `Firefox 131.0`)
+    pub browser: Option<String>,
+    /// Name of the operating system (e.g. `Linux`)
+    pub os: Option<String>,
+}
+
+#[cfg(feature = "mock")]
+impl MockUserAgentService {
+    pub fn with_parse(mut self, user_agent: String, result: UserAgentInfo) -> Self {
+        self.expect_parse()
+            .once()
+            .with(mockall::predicate::eq(user_agent))
+            .return_once(|_| result);
+        self
+    }
+}
@@ -0,0 +1,131 @@
+use academy_models::{
+    mfa::{
+        WebauthnAssertion, WebauthnAssertionOptions, WebauthnChallenge, WebauthnCredentialId,
+        WebauthnPublicKey, WebauthnRegistration, WebauthnRegistrationOptions,
+    },
+    user::{UserDisplayName, UserId, UserName},
+};
+use thiserror::Error;
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait WebauthnService: Send + Sync + 'static {
+    /// Generate the options for registering a new WebAuthn credential
+    /// including a new random challenge.
+    fn generate_registration_options(
+        &self,
+        user_id: UserId,
+        user_name: UserName,
+        user_display_name: UserDisplayName,
+        exclude_credentials: Vec<WebauthnCredentialId>,
+    ) -> WebauthnRegistrationOptions;
+
+    /// Generate the options for requesting a WebAuthn assertion including a
+    /// new random challenge.
+    fn generate_assertion_options(&self) -> WebauthnAssertionOptions;
+
+    /// Verify the response of an authenticator to a registration request.
+    ///
+    /// The caller is responsible for checking the returned challenge.
+    fn verify_registration(
+        &self,
+        registration: &WebauthnRegistration,
+    ) -> Result<WebauthnVerifiedRegistration, WebauthnVerifyError>;
+
+    /// Verify the response of an authenticator to an assertion request using
+    /// the stored public key and signature counter of the credential.
+    ///
+    /// The caller is responsible for checking the returned challenge.
+    fn verify_assertion(
+        &self,
+        assertion: &WebauthnAssertion,
+        public_key: &WebauthnPublicKey,
+        sign_count: u32,
+    ) -> Result<WebauthnVerifiedAssertion, WebauthnVerifyError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebauthnVerifiedRegistration {
+    pub challenge: WebauthnChallenge,
+    pub credential_id: WebauthnCredentialId,
+    pub public_key: WebauthnPublicKey,
+    pub sign_count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebauthnVerifiedAssertion {
+    pub challenge: WebauthnChallenge,
+    pub sign_count: u32,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WebauthnVerifyError {
+    #[error("The client data is invalid.")]
+    InvalidClientData,
+    #[error("The authenticator data is invalid.")]
+    InvalidAuthenticatorData,
+    #[error("The public key is invalid or uses an unsupported algorithm.")]
+    InvalidPublicKey,
+    #[error("The signature is invalid.")]
+    InvalidSignature,
+    #[error("The signature counter did not increase.")]
+    SignCountNotIncreased,
+}
+
+#[cfg(feature = "mock")]
+impl MockWebauthnService {
+    pub fn with_generate_registration_options(
+        mut self,
+        user_id: UserId,
+        exclude_credentials: Vec<WebauthnCredentialId>,
+        result: WebauthnRegistrationOptions,
+    ) -> Self {
+        self.expect_generate_registration_options()
+            .once()
+            .with(
+                mockall::predicate::eq(user_id),
+                mockall::predicate::always(),
+                mockall::predicate::always(),
+                mockall::predicate::eq(exclude_credentials),
+            )
+            .return_once(|_, _, _, _| result);
+        self
+    }
+
+    pub fn with_generate_assertion_options(mut self, result: WebauthnAssertionOptions) -> Self {
+        self.expect_generate_assertion_options()
+            .once()
+            .with()
+            .return_once(|| result);
+        self
+    }
+
+    pub fn with_verify_registration(
+        mut self,
+        registration: WebauthnRegistration,
+        result: Result<WebauthnVerifiedRegistration, WebauthnVerifyError>,
+    ) -> Self {
+        self.expect_verify_registration()
+            .once()
+            .with(mockall::predicate::eq(registration))
+            .return_once(|_| result);
+        self
+    }
+
+    pub fn with_verify_assertion(
+        mut self,
+        assertion: WebauthnAssertion,
+        public_key: WebauthnPublicKey,
+        sign_count: u32,
+        result: Result<WebauthnVerifiedAssertion, WebauthnVerifyError>,
+    ) -> Self {
+        self.expect_verify_assertion()
+            .once()
+            .with(
+                mockall::predicate::eq(assertion),
+                mockall::predicate::eq(public_key),
+                mockall::predicate::eq(sign_count),
+            )
+            .return_once(|_, _, _| result);
+        self
+    }
+}
@@ -0,0 +1,229 @@
+use std::{collections::HashMap, sync::Arc};
+
+use academy_di::Build;
+use academy_models::{
+    encryption::{EncryptedData, EncryptionKeyVersion},
+    Sensitive,
+};
+use academy_shared_contracts::{encryption::EncryptionService, secret::SecretService};
+use academy_utils::trace_instrument;
+use anyhow::{anyhow, bail, Context};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+
+#[derive(Debug, Clone, Build)]
+pub struct EncryptionServiceImpl<Secret> {
+    secret: Secret,
+    config: EncryptionServiceConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct EncryptionServiceConfig {
+    current_key_version: EncryptionKeyVersion,
+    keys: Arc<HashMap<EncryptionKeyVersion, LessSafeKey>>,
+}
+
+impl EncryptionServiceConfig {
+    /// Load the given base64 encoded 256-bit encryption keys.
+    ///
+    /// New data is always encrypted using the key with the version
+    /// `current_key_version`, while all keys can be used for decryption.
+    pub fn new<'a>(
+        current_key_version: EncryptionKeyVersion,
+        keys: impl IntoIterator<Item = (EncryptionKeyVersion, &'a str)>,
+    ) -> anyhow::Result<Self> {
+        let keys = keys
+            .into_iter()
+            .map(|(version, key)| {
+                let key = BASE64_STANDARD
+                    .decode(key)
+                    .with_context(|| format!("Failed to decode encryption key {version}"))?;
+                let key = UnboundKey::new(&AES_256_GCM, &key)
+                    .map_err(|_| anyhow!("Encryption key {version} must be 32 bytes long"))?;
+                Ok((version, LessSafeKey::new(key)))
+            })
+            .collect::<anyhow::Result<HashMap<_, _>>>()?;
+
+        if !keys.contains_key(&current_key_version) {
+            bail!("The current encryption key {current_key_version} has not been configured");
+        }
+
+        Ok(Self {
+            current_key_version,
+            keys: keys.into(),
+        })
+    }
+}
+
+impl<Secret> EncryptionService for EncryptionServiceImpl<Secret>
+where
+    Secret: SecretService,
+{
+    #[trace_instrument(skip(self, data))]
+    fn encrypt(&self, data: &[u8]) -> anyhow::Result<EncryptedData> {
+        let version = self.config.current_key_version;
+        let key = &self.config.keys[&version];
+
+        let mut out = self.secret.generate_bytes(NONCE_LEN).0;
+        let nonce = Nonce::try_assume_unique_for_key(&out)
+            .map_err(|_| anyhow!("Failed to generate nonce"))?;
+
+        let mut buf = data.to_vec();
+        key.seal_in_place_append_tag(nonce, aad(version), &mut buf)
+            .map_err(|_| anyhow!("Failed to encrypt data"))?;
+        out.extend(buf);
+
+        Ok(EncryptedData {
+            key_version: Some(version),
+            data: out,
+        })
+    }
+
+    #[trace_instrument(skip(self, data))]
+    fn decrypt(&self, data: &EncryptedData) -> anyhow::Result<Sensitive<Vec<u8>>> {
+        let Some(version) = data.key_version else {
+            return Ok(data.data.clone().into());
+        };
+
+        let key = self
+            .config
+            .keys
+            .get(&version)
+            .with_context(|| format!("Encryption key {version} has not been configured"))?;
+
+        if data.data.len() < NONCE_LEN {
+            bail!("Encrypted data is too short");
+        }
+        let (nonce, ciphertext) = data.data.split_at(NONCE_LEN);
+        let nonce =
+            Nonce::try_assume_unique_for_key(nonce).map_err(|_| anyhow!("Failed to load nonce"))?;
+
+        let mut buf = ciphertext.to_vec();
+        let len = key
+            .open_in_place(nonce, aad(version), &mut buf)
+            .map_err(|_| anyhow!("Failed to decrypt data"))?
+            .len();
+        buf.truncate(len);
+
+        Ok(buf.into())
+    }
+
+    #[trace_instrument(skip(self, data))]
+    fn is_outdated(&self, data: &EncryptedData) -> bool {
+        data.key_version != Some(self.config.current_key_version)
+    }
+}
+
+/// Bind the ciphertext to the version of the key that has been used to create
+/// it.
+fn aad(version: EncryptionKeyVersion) -> Aad<[u8; 4]> {
+    Aad::from(version.to_be_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_shared_contracts::secret::MockSecretService;
+    use academy_utils::assert_matches;
+
+    use super::*;
+
+    const KEY1: &str = "MTIzNDU2Nzg5MDEyMzQ1Njc4OTAxMjM0NTY3ODkwMTI=";
+    const KEY2: &str = "YWJjZGVmZ2hpamtsbW5vcHFyc3R1dnd4eXphYmNkZWY=";
+    const NONCE: &[u8; NONCE_LEN] = b"the nonce!!!";
+
+    #[test]
+    fn encrypt_decrypt() {
+        // Arrange
+        let secret = MockSecretService::new().with_generate_bytes(NONCE_LEN, NONCE.to_vec());
+        let config = EncryptionServiceConfig::new(2, [(1, KEY1), (2, KEY2)]).unwrap();
+        let sut = EncryptionServiceImpl { secret, config };
+
+        // Act
+        let encrypted = sut.encrypt(b"hello world").unwrap();
+        let decrypted = sut.decrypt(&encrypted).unwrap();
+
+        // Assert
+        assert_eq!(encrypted.key_version, Some(2));
+        assert_eq!(&encrypted.data[..NONCE_LEN], NONCE);
+        assert_ne!(&encrypted.data[NONCE_LEN..], b"hello world");
+        assert_eq!(decrypted.0, b"hello world");
+        assert!(!sut.is_outdated(&encrypted));
+    }
+
+    #[test]
+    fn decrypt_old_key() {
+        // Arrange
+        let secret = MockSecretService::new().with_generate_bytes(NONCE_LEN, NONCE.to_vec());
+        let config = EncryptionServiceConfig::new(1, [(1, KEY1)]).unwrap();
+        let encrypted = EncryptionServiceImpl { secret, config }
+            .encrypt(b"hello world")
+            .unwrap();
+
+        let config = EncryptionServiceConfig::new(2, [(1, KEY1), (2, KEY2)]).unwrap();
+        let sut = EncryptionServiceImpl {
+            secret: MockSecretService::new(),
+            config,
+        };
+
+        // Act
+        let result = sut.decrypt(&encrypted);
+
+        // Assert
+        assert_eq!(result.unwrap().0, b"hello world");
+        assert!(sut.is_outdated(&encrypted));
+    }
+
+    #[test]
+    fn decrypt_unencrypted() {
+        // Arrange
+        let data = EncryptedData::unencrypted(b"hello world".to_vec());
+
+        let config = EncryptionServiceConfig::new(1, [(1, KEY1)]).unwrap();
+        let sut = EncryptionServiceImpl {
+            secret: MockSecretService::new(),
+            config,
+        };
+
+        // Act
+        let result = sut.decrypt(&data);
+
+        // Assert
+        assert_eq!(result.unwrap().0, b"hello world");
+        assert!(sut.is_outdated(&data));
+    }
+
+    #[test]
+    fn decrypt_tampered() {
+        // Arrange
+        let secret = MockSecretService::new().with_generate_bytes(NONCE_LEN, NONCE.to_vec());
+        let config = EncryptionServiceConfig::new(1, [(1, KEY1), (2, KEY2)]).unwrap();
+        let sut = EncryptionServiceImpl { secret, config };
+
+        let mut encrypted = sut.encrypt(b"hello world").unwrap();
+        encrypted.key_version = Some(2);
+
+        // Act
+        let result = sut.decrypt(&encrypted);
+
+        // Assert
+        assert_matches!(result, Err(_));
+    }
+
+    #[test]
+    fn config_missing_current_key() {
+        // Act
+        let result = EncryptionServiceConfig::new(2, [(1, KEY1)]);
+
+        // Assert
+        assert_matches!(result, Err(_));
+    }
+
+    #[test]
+    fn config_invalid_key_length() {
+        // Act
+        let result = EncryptionServiceConfig::new(1, [(1, "dG9vIHNob3J0")]);
+
+        // Assert
+        assert_matches!(result, Err(_));
+    }
+}
@@ -1,16 +1,20 @@
-use std::{fmt::Debug, sync::Arc, time::Duration};
+use std::{collections::HashMap, fmt::Debug, sync::Arc, time::Duration};
 
 use academy_di::Build;
+use academy_models::jwt::{Jwk, JwkCurve, JwkParams, JwkUse, Jwks, JwtAlgorithm};
 use academy_shared_contracts::{
     jwt::{JwtService, VerifyJwtError},
     time::TimeService,
 };
 use academy_utils::trace_instrument;
-use anyhow::Context;
-use hmac::{digest::KeyInit, Hmac};
-use jwt::{SignWithKey, VerifyWithKey};
+use anyhow::{anyhow, bail, Context};
+use base64::{
+    prelude::{BASE64_STANDARD, BASE64_URL_SAFE_NO_PAD},
+    Engine,
+};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use ring::signature::{Ed25519KeyPair, KeyPair, RsaKeyPair, RsaPublicKeyComponents};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use sha2::Sha256;
 
 #[derive(Debug, Clone, Build)]
 pub struct JwtServiceImpl<Time> {
@@ -20,19 +24,138 @@ pub struct JwtServiceImpl<Time> {
 
 #[derive(Debug, Clone)]
 pub struct JwtServiceConfig {
-    jwt_secret: Arc<Hmac<Sha256>>,
+    signing_key: String,
+    keys: Arc<HashMap<String, JwtKey>>,
+    jwks: Arc<Jwks>,
+}
+
+struct JwtKey {
+    algorithm: JwtAlgorithm,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    validation: Validation,
+}
+
+impl Debug for JwtKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JwtKey")
+            .field("algorithm", &self.algorithm)
+            .finish_non_exhaustive()
+    }
 }
 
 impl JwtServiceConfig {
-    pub fn new(jwt_secret: &str) -> anyhow::Result<Self> {
+    /// Load the given JWT signing keys.
+    ///
+    /// New JWTs are always signed using the key with the id `signing_key`,
+    /// while all keys can be used for verification. Depending on the
+    /// algorithm, `key` must be
+    /// - `HS256`: the shared secret
+    /// - `RS256`: a base64 encoded RSA private key in PKCS#1 DER format
+    /// - `EdDSA`: a base64 encoded Ed25519 private key in PKCS#8 DER format
+    pub fn new<'a>(
+        signing_key: &str,
+        keys: impl IntoIterator<Item = (&'a str, JwtAlgorithm, &'a str)>,
+    ) -> anyhow::Result<Self> {
+        let mut jwks = Jwks::default();
+        let keys = keys
+            .into_iter()
+            .map(|(kid, algorithm, key)| {
+                let (key, params) = load_key(algorithm, key)
+                    .with_context(|| format!("Failed to load JWT key {kid}"))?;
+                if let Some(params) = params {
+                    jwks.keys.push(Jwk {
+                        kid: kid.into(),
+                        alg: algorithm,
+                        use_: JwkUse::Sig,
+                        params,
+                    });
+                }
+                Ok((kid.into(), key))
+            })
+            .collect::<anyhow::Result<HashMap<_, _>>>()?;
+
+        if !keys.contains_key(signing_key) {
+            bail!("The JWT signing key {signing_key} has not been configured");
+        }
+
+        jwks.keys.sort_by(|a, b| a.kid.cmp(&b.kid));
+
         Ok(Self {
-            jwt_secret: Hmac::new_from_slice(jwt_secret.as_bytes())
-                .context("Failed to load JWT secret")?
-                .into(),
+            signing_key: signing_key.into(),
+            keys: keys.into(),
+            jwks: jwks.into(),
         })
     }
 }
 
+fn load_key(algorithm: JwtAlgorithm, key: &str) -> anyhow::Result<(JwtKey, Option<JwkParams>)> {
+    let (encoding_key, decoding_key, params) = match algorithm {
+        JwtAlgorithm::HS256 => (
+            EncodingKey::from_secret(key.as_bytes()),
+            DecodingKey::from_secret(key.as_bytes()),
+            None,
+        ),
+        JwtAlgorithm::RS256 => {
+            let der = BASE64_STANDARD
+                .decode(key)
+                .context("Failed to decode key")?;
+            let key_pair = RsaKeyPair::from_der(&der)
+                .map_err(|err| anyhow!("Invalid RSA private key: {err}"))?;
+            let RsaPublicKeyComponents::<Vec<u8>> { n, e } = key_pair.public().into();
+            (
+                EncodingKey::from_rsa_der(&der),
+                DecodingKey::from_rsa_raw_components(&n, &e),
+                Some(JwkParams::Rsa {
+                    n: BASE64_URL_SAFE_NO_PAD.encode(n),
+                    e: BASE64_URL_SAFE_NO_PAD.encode(e),
+                }),
+            )
+        }
+        JwtAlgorithm::EdDSA => {
+            let der = BASE64_STANDARD
+                .decode(key)
+                .context("Failed to decode key")?;
+            let key_pair = Ed25519KeyPair::from_pkcs8_maybe_unchecked(&der)
+                .map_err(|err| anyhow!("Invalid Ed25519 private key: {err}"))?;
+            let public_key = key_pair.public_key().as_ref();
+            (
+                EncodingKey::from_ed_der(&der),
+                DecodingKey::from_ed_der(public_key),
+                Some(JwkParams::Okp {
+                    crv: JwkCurve::Ed25519,
+                    x: BASE64_URL_SAFE_NO_PAD.encode(public_key),
+                }),
+            )
+        }
+    };
+
+    // Expiration is checked using the `TimeService`, all other registered
+    // claims are not used.
+    let mut validation = Validation::new(jsonwebtoken_algorithm(algorithm));
+    validation.required_spec_claims.clear();
+    validation.validate_exp = false;
+    validation.validate_aud = false;
+
+    Ok((
+        JwtKey {
+            algorithm,
+            encoding_key,
+            decoding_key,
+            validation,
+        },
+        params,
+    ))
+}
+
+fn jsonwebtoken_algorithm(algorithm: JwtAlgorithm) -> Algorithm {
+    match algorithm {
+        JwtAlgorithm::HS256 => Algorithm::HS256,
+        JwtAlgorithm::RS256 => Algorithm::RS256,
+        JwtAlgorithm::EdDSA => Algorithm::EdDSA,
+    }
+}
+
 impl<Time> JwtService for JwtServiceImpl<Time>
 where
     Time: TimeService,
@@ -46,8 +169,13 @@ where
         let now = self.time.now().timestamp() as u64;
         let exp = now + ttl.as_secs();
 
-        JwtData { exp, data }
-            .sign_with_key(&*self.config.jwt_secret)
+        let key = &self.config.keys[&self.config.signing_key];
+        let header = Header {
+            kid: Some(self.config.signing_key.clone()),
+            ..Header::new(jsonwebtoken_algorithm(key.algorithm))
+        };
+
+        jsonwebtoken::encode(&header, &JwtData { exp, data }, &key.encoding_key)
             .context("Failed to sign JWT")
             .map(Into::into)
     }
@@ -57,10 +185,18 @@ where
         &self,
         jwt: &S,
     ) -> Result<T, VerifyJwtError<T>> {
-        let JwtData { exp, data } = jwt
-            .as_ref()
-            .verify_with_key(&*self.config.jwt_secret)
-            .map_err(|_| VerifyJwtError::Invalid)?;
+        let header =
+            jsonwebtoken::decode_header(jwt.as_ref()).map_err(|_| VerifyJwtError::Invalid)?;
+
+        // JWTs without a key id have been issued before key rotation was
+        // supported and are verified using the current signing key.
+        let kid = header.kid.as_deref().unwrap_or(&self.config.signing_key);
+        let key = self.config.keys.get(kid).ok_or(VerifyJwtError::Invalid)?;
+
+        let JwtData { exp, data } =
+            jsonwebtoken::decode::<JwtData<T>>(jwt.as_ref(), &key.decoding_key, &key.validation)
+                .map_err(|_| VerifyJwtError::Invalid)?
+                .claims;
 
         let now = self.time.now().timestamp() as u64;
         if now < exp {
@@ -69,6 +205,10 @@ where
             Err(VerifyJwtError::Expired(data))
         }
     }
+
+    fn jwks(&self) -> &Jwks {
+        &self.config.jwks
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -94,7 +234,7 @@ mod tests {
             bar: "hello world".into(),
         };
 
-        let config = JwtServiceConfig::new("the jwt secret").unwrap();
+        let config = hs256_config("the jwt secret");
 
         let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
         let then = now + Duration::from_secs(10);
@@ -118,7 +258,7 @@ mod tests {
             bar: "hello world".into(),
         };
 
-        let config = JwtServiceConfig::new("the jwt secret").unwrap();
+        let config = hs256_config("the jwt secret");
 
         let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
         let then = now + Duration::from_secs(20);
@@ -142,8 +282,8 @@ mod tests {
             bar: "hello world".into(),
         };
 
-        let config = JwtServiceConfig::new("the jwt secret").unwrap();
-        let config2 = JwtServiceConfig::new("other jwt secret").unwrap();
+        let config = hs256_config("the jwt secret");
+        let config2 = hs256_config("other jwt secret");
 
         let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
         let time = MockTimeService::new().with_now(now);
@@ -162,7 +302,284 @@ mod tests {
         assert_matches!(verified, Err(VerifyJwtError::Invalid));
     }
 
-    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    #[test]
+    fn sign_verify_asymmetric() {
+        for (algorithm, key) in [
+            (JwtAlgorithm::EdDSA, ED25519_KEY),
+            (JwtAlgorithm::RS256, RSA_KEY),
+        ] {
+            // Arrange
+            let data = Data {
+                foo: 42,
+                bar: "hello world".into(),
+            };
+
+            let config = JwtServiceConfig::new("key", [("key", algorithm, key)]).unwrap();
+
+            let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+            let then = now + Duration::from_secs(10);
+            let time = MockTimeService::new().with_now(now).with_now(then);
+
+            let sut = JwtServiceImpl { time, config };
+
+            // Act
+            let jwt = sut
+                .sign::<_, String>(data.clone(), Duration::from_secs(20))
+                .unwrap();
+            let header = jsonwebtoken::decode_header(&jwt).unwrap();
+            let verified = sut.verify::<String, Data>(&jwt);
+
+            // Assert
+            assert_eq!(header.alg, jsonwebtoken_algorithm(algorithm));
+            assert_eq!(header.kid.unwrap(), "key");
+            assert_eq!(verified.unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn verify_rotated_key() {
+        // Arrange
+        let data = Data {
+            foo: 42,
+            bar: "hello world".into(),
+        };
+
+        let old_config =
+            JwtServiceConfig::new("old", [("old", JwtAlgorithm::HS256, "the jwt secret")]).unwrap();
+        let config = JwtServiceConfig::new(
+            "new",
+            [
+                ("old", JwtAlgorithm::HS256, "the jwt secret"),
+                ("new", JwtAlgorithm::EdDSA, ED25519_KEY),
+            ],
+        )
+        .unwrap();
+
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let then = now + Duration::from_secs(10);
+
+        let old_sut = JwtServiceImpl {
+            time: MockTimeService::new().with_now(now),
+            config: old_config,
+        };
+        let sut = JwtServiceImpl {
+            time: MockTimeService::new().with_now(then),
+            config,
+        };
+
+        // Act
+        let jwt = old_sut
+            .sign::<_, String>(data.clone(), Duration::from_secs(20))
+            .unwrap();
+        let verified = sut.verify::<String, Data>(&jwt);
+
+        // Assert
+        assert_eq!(verified.unwrap(), data);
+    }
+
+    #[test]
+    fn verify_unknown_key() {
+        // Arrange
+        let data = Data {
+            foo: 42,
+            bar: "hello world".into(),
+        };
+
+        let config = JwtServiceConfig::new("a", [("a", JwtAlgorithm::HS256, "secret")]).unwrap();
+        let config2 = JwtServiceConfig::new("b", [("b", JwtAlgorithm::HS256, "secret")]).unwrap();
+
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let time = MockTimeService::new().with_now(now);
+
+        let sut = JwtServiceImpl { time, config };
+        let sut2 = JwtServiceImpl {
+            time: MockTimeService::new(),
+            config: config2,
+        };
+
+        // Act
+        let jwt = sut
+            .sign::<_, String>(data, Duration::from_secs(10))
+            .unwrap();
+        let verified = sut2.verify::<String, Data>(&jwt);
+
+        // Assert
+        assert_matches!(verified, Err(VerifyJwtError::Invalid));
+    }
+
+    #[test]
+    fn verify_algorithm_mismatch() {
+        // Arrange
+        let jwks = JwtServiceConfig::new("key", [("key", JwtAlgorithm::EdDSA, ED25519_KEY)])
+            .unwrap()
+            .jwks;
+        let JwkParams::Okp { x, .. } = &jwks.keys[0].params else {
+            panic!()
+        };
+
+        // an attacker signs a JWT using the public key as HMAC secret
+        let config =
+            JwtServiceConfig::new("key", [("key", JwtAlgorithm::HS256, x.as_str())]).unwrap();
+        let config2 =
+            JwtServiceConfig::new("key", [("key", JwtAlgorithm::EdDSA, ED25519_KEY)]).unwrap();
+
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let time = MockTimeService::new().with_now(now);
+
+        let sut = JwtServiceImpl { time, config };
+        let sut2 = JwtServiceImpl {
+            time: MockTimeService::new(),
+            config: config2,
+        };
+
+        // Act
+        let jwt = sut
+            .sign::<_, String>(Data::default(), Duration::from_secs(10))
+            .unwrap();
+        let verified = sut2.verify::<String, Data>(&jwt);
+
+        // Assert
+        assert_matches!(verified, Err(VerifyJwtError::Invalid));
+    }
+
+    #[test]
+    fn verify_without_kid() {
+        // Arrange
+        let data = Data {
+            foo: 42,
+            bar: "hello world".into(),
+        };
+
+        let jwt = jsonwebtoken::encode(
+            &Header::default(),
+            &JwtData {
+                exp: 1704067220,
+                data: data.clone(),
+            },
+            &EncodingKey::from_secret(b"the jwt secret"),
+        )
+        .unwrap();
+
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let time = MockTimeService::new().with_now(now);
+
+        let sut = JwtServiceImpl {
+            time,
+            config: hs256_config("the jwt secret"),
+        };
+
+        // Act
+        let verified = sut.verify::<String, Data>(&jwt);
+
+        // Assert
+        assert_eq!(verified.unwrap(), data);
+    }
+
+    #[test]
+    fn jwks() {
+        // Arrange
+        let config = JwtServiceConfig::new(
+            "eddsa",
+            [
+                ("hs256", JwtAlgorithm::HS256, "the jwt secret"),
+                ("rs256", JwtAlgorithm::RS256, RSA_KEY),
+                ("eddsa", JwtAlgorithm::EdDSA, ED25519_KEY),
+            ],
+        )
+        .unwrap();
+
+        let sut = JwtServiceImpl {
+            time: MockTimeService::new(),
+            config,
+        };
+
+        // Act
+        let result = sut.jwks();
+
+        // Assert
+        let kids = result
+            .keys
+            .iter()
+            .map(|k| k.kid.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(kids, ["eddsa", "rs256"]);
+        assert_eq!(
+            result.keys[0],
+            Jwk {
+                kid: "eddsa".into(),
+                alg: JwtAlgorithm::EdDSA,
+                use_: JwkUse::Sig,
+                params: JwkParams::Okp {
+                    crv: JwkCurve::Ed25519,
+                    x: ED25519_PUBLIC_KEY.into()
+                },
+            }
+        );
+        assert_matches!(&result.keys[1].params, JwkParams::Rsa { e, .. } if e == "AQAB");
+    }
+
+    #[test]
+    fn jwks_verify_with_public_key() {
+        for (algorithm, key) in [
+            (JwtAlgorithm::EdDSA, ED25519_KEY),
+            (JwtAlgorithm::RS256, RSA_KEY),
+        ] {
+            // Arrange
+            let data = Data {
+                foo: 42,
+                bar: "hello world".into(),
+            };
+
+            let config = JwtServiceConfig::new("key", [("key", algorithm, key)]).unwrap();
+
+            let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+            let time = MockTimeService::new().with_now(now);
+
+            let sut = JwtServiceImpl { time, config };
+
+            // Act
+            let jwt = sut
+                .sign::<_, String>(data.clone(), Duration::from_secs(20))
+                .unwrap();
+            let jwks = serde_json::to_value(sut.jwks()).unwrap();
+
+            // Assert
+            let jwks = serde_json::from_value::<jsonwebtoken::jwk::JwkSet>(jwks).unwrap();
+            let key = DecodingKey::from_jwk(jwks.find("key").unwrap()).unwrap();
+            let mut validation = Validation::new(jsonwebtoken_algorithm(algorithm));
+            validation.validate_exp = false;
+            let verified = jsonwebtoken::decode::<JwtData<Data>>(&jwt, &key, &validation).unwrap();
+            assert_eq!(verified.claims.data, data);
+        }
+    }
+
+    #[test]
+    fn config_missing_signing_key() {
+        // Act
+        let result = JwtServiceConfig::new("b", [("a", JwtAlgorithm::HS256, "secret")]);
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn config_invalid_key() {
+        // Act
+        let result = JwtServiceConfig::new("a", [("a", JwtAlgorithm::EdDSA, RSA_KEY)]);
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    fn hs256_config(secret: &str) -> JwtServiceConfig {
+        JwtServiceConfig::new("default", [("default", JwtAlgorithm::HS256, secret)]).unwrap()
+    }
+
+    const ED25519_KEY: &str = "MC4CAQAwBQYDK2VwBCIEIPoDoMvF0fElXJU2mVVD9IKsVCYDNyP0UcWcFQvWAc95";
+    const ED25519_PUBLIC_KEY: &str = "TjhJ4BFvRpD7SA1rgXkhEVCYldpBb0jSvehGcTrWwbo";
+    const RSA_KEY: &str = "MIIEowIBAAKCAQEA2xAYrd3Ov88sfYhHw6Ey3o1lm34X0Kf3Wfdb6+SGgB8ctAVE0sZnok2DD7UNqwLWrtx7deKyvcHuRroPAUZeMFb6f29M/3Zu2Gbyw6kE+4hABXOz4KE3v+4NTU2a82xiriLMnMDSK9U6bEZp4YgsPPx8KDTScp14CLjYxpmlAEQIZ/NTKiokFSCWheHSCwoFyb201McrbAjzGJSxRN+XasHLjdyDZQQUGC8n1DW8uPMb8LCm4Y8nuzqjckx2DwTjnZoLYL1zsJTLkeKhvEQs9CUexNrciBMWWPg7Skbn/jJIPkHeB+bX6Jph/Z9rY86aLvFy+5vcyPzaXEZqYRar7QIDAQABAoIBAB5yXoe90ihJHm0lJZpMW5Jgkyx5KkyzJ7ceoO1IoiQJDvd7Wv0zm8BMZyil+KwZ1SZRGSzBkSe8HkbAweiis+EimabgHJLODmpv5pJoubtPDNzlH61bIvE/YCRsIpruJ8n9prQrvZpfhML7R2iUAVgJnKUG8HADMGV/8Ysq3CrMyAPVQ6h1A/JchS353X2VnadsRHozCz01oVUyuNr4eDONOYSD2SvOjHB2Yctv5epF21gtKugPvojAtLmwoBCZTYgFRPANb9E7K5jfUFGNDUzZMIAjCfu6ZygFeW0sGa5LUJ4V3P2eCw8VbEfO/g586BGMR0P8t1B5SizUocfX7bECgYEA9NyMv1Q3XXDzJhAw/WtXzYJ0+U1qxqJSGRKBx/uMhMOlz+zjygEAMBpnQWaueeDPcC7Kj80odG8uFW0HfUKFlVRqTO6NpquVRFFIx0VxupTfI/Rsy+uCS69mnbOA29RNXOk/PMqlmzyw0N05cSG6RNXZu5LQn7daMwme00r6FD0CgYEA5QceDZ1lgnguJ6IWjpaEVskBFQBfbHl6hf68qbxnmwuEFNFry1MBbnh6QqGAuxttwk+EzYtAmDQ+mJdHPb9s27xJ2PQ0CMeeFi2nX5uNPFm1edT18syEAddiEEt1e2mhDG2m2CSDvDGWJ9F3Gq9EjX0dAWGAT0EhLzhwMUGVgXECgYAcEBbVarp5rBNB3oMu2BmVBQmQ9O1y3HGaTAOxvsOpB58+SEEHCrxRxwTkevcuIrTDf6lZd8LWLVPTws4j7fbPWYBhVsnHO0z9Km+z3uKc5qj4PvAlItWpBO42zrbK+vbu2hCUUAlQ1FDTbzdBHwNxFi1BER913pao2MZqIuKsZQKBgG+udtNAy31ByY/+FG3XrBT9049ALUgiPMMWoz1J6GPMy82zCZOOmIYcLdJMGxAiofU8/Y+VthwCbfvO2SidLiJAwOoQu0cEwGwkOxf+LHMfc8VDOeP6Iu0a8HDtuw5BRg1fcxq3Q54DuCsWk7Ppf85XEkvYFc+P/PSf2uXKC3OhAoGBAKl21zFDiq7fKvMkn3qsgRQjO0UeQWQojkdWQzuxFtah1K49CBaM6Wdx4Ayg7t19TW7vnA+8k94upiuPG8QhWkfxldqZ6+ouoFbX93m0Soqw76NgLIzwG1AH0mK9n1HW8MK1B4VYmnY+kUc+EAq0JYYbUc6Ss+BhhfZqWlRMgq2x";
+
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
     struct Data {
         foo: i32,
         bar: String,
@@ -1,4 +1,5 @@
 pub mod captcha;
+pub mod encryption;
 pub mod hash;
 pub mod id;
 pub mod jwt;
@@ -6,3 +7,5 @@ pub mod password;
 pub mod secret;
 pub mod time;
 pub mod totp;
+pub mod user_agent;
+pub mod webauthn;
@@ -7,19 +7,49 @@ use academy_utils::trace_instrument;
 use anyhow::{anyhow, Context};
 use argon2::{
     password_hash::{self, rand_core::OsRng, SaltString},
-    Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
+    Algorithm, Argon2, Params, PasswordHash, PasswordHasher, PasswordVerifier, Version,
 };
 
 #[derive(Debug, Clone, Default, Build)]
 pub struct PasswordServiceImpl {
-    #[di(default)]
+    config: PasswordServiceConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct PasswordServiceConfig {
     argon2: Arc<Argon2<'static>>,
+    params: Params,
+}
+
+impl PasswordServiceConfig {
+    /// Configure the argon2id parameters used for new password hashes.
+    ///
+    /// `memory_cost` is the memory size in KiB, `iterations` the number of
+    /// passes and `parallelism` the degree of parallelism.
+    pub fn new(memory_cost: u32, iterations: u32, parallelism: u32) -> anyhow::Result<Self> {
+        let params = Params::new(memory_cost, iterations, parallelism, None)
+            .map_err(|err| anyhow!(err).context("Invalid argon2 parameters"))?;
+        Ok(Self::from_params(params))
+    }
+
+    fn from_params(params: Params) -> Self {
+        Self {
+            argon2: Argon2::new(Algorithm::Argon2id, Version::V0x13, params.clone()).into(),
+            params,
+        }
+    }
+}
+
+impl Default for PasswordServiceConfig {
+    fn default() -> Self {
+        Self::from_params(Params::DEFAULT)
+    }
 }
 
 impl PasswordService for PasswordServiceImpl {
     #[trace_instrument(skip(self))]
     async fn hash(&self, password: Sensitive<String>) -> anyhow::Result<String> {
-        let argon2 = Arc::clone(&self.argon2);
+        let argon2 = Arc::clone(&self.config.argon2);
         let salt = SaltString::generate(&mut OsRng);
         tokio::task::spawn_blocking(move || {
             argon2
@@ -36,7 +66,7 @@ impl PasswordService for PasswordServiceImpl {
         password: Sensitive<String>,
         hash: String,
     ) -> Result<(), PasswordVerifyError> {
-        let argon2 = Arc::clone(&self.argon2);
+        let argon2 = Arc::clone(&self.config.argon2);
         tokio::task::spawn_blocking(move || {
             let hash =
                 PasswordHash::new(&hash).map_err(|err| PasswordVerifyError::Other(err.into()))?;
@@ -50,6 +80,26 @@ impl PasswordService for PasswordServiceImpl {
         .await
         .map_err(|err| anyhow!(err).context("Failed to verify password"))?
     }
+
+    fn needs_rehash(&self, hash: &str) -> bool {
+        let Ok(hash) = PasswordHash::new(hash) else {
+            return true;
+        };
+
+        if Algorithm::try_from(hash.algorithm) != Ok(Algorithm::Argon2id)
+            || hash.version != Some(Version::V0x13.into())
+        {
+            return true;
+        }
+
+        let Ok(params) = Params::try_from(&hash) else {
+            return true;
+        };
+
+        params.m_cost() != self.config.params.m_cost()
+            || params.t_cost() != self.config.params.t_cost()
+            || params.p_cost() != self.config.params.p_cost()
+    }
 }
 
 #[cfg(test)]
@@ -88,4 +138,72 @@ mod tests {
         // Assert
         assert_matches!(result, Err(PasswordVerifyError::InvalidPassword));
     }
+
+    #[tokio::test]
+    async fn needs_rehash_current_params() {
+        // Arrange
+        let sut = PasswordServiceImpl {
+            config: PasswordServiceConfig::new(1024, 1, 1).unwrap(),
+        };
+        let hash = sut
+            .hash("some user password".to_owned().into())
+            .await
+            .unwrap();
+
+        // Act
+        let result = sut.needs_rehash(&hash);
+
+        // Assert
+        assert!(!result);
+    }
+
+    #[tokio::test]
+    async fn needs_rehash_outdated_params() {
+        // Arrange
+        let old = PasswordServiceImpl {
+            config: PasswordServiceConfig::new(1024, 1, 1).unwrap(),
+        };
+        let hash = old
+            .hash("some user password".to_owned().into())
+            .await
+            .unwrap();
+
+        let sut = PasswordServiceImpl {
+            config: PasswordServiceConfig::new(2048, 2, 1).unwrap(),
+        };
+
+        // Act
+        let result = sut.needs_rehash(&hash);
+
+        // Assert
+        assert!(result);
+    }
+
+    #[test]
+    fn needs_rehash_legacy_algorithm() {
+        // Arrange
+        let hash = "$argon2i$v=19$m=16,t=2,p=1$c29tZXNhbHQ$nt5hyvw9w/LrQqcHhEBZ7g";
+
+        let sut = PasswordServiceImpl::default();
+
+        // Act
+        let result = sut.needs_rehash(hash);
+
+        // Assert
+        assert!(result);
+    }
+
+    #[test]
+    fn needs_rehash_unknown_format() {
+        // Arrange
+        let hash = "$2b$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW";
+
+        let sut = PasswordServiceImpl::default();
+
+        // Act
+        let result = sut.needs_rehash(hash);
+
+        // Assert
+        assert!(result);
+    }
 }
@@ -1,5 +1,7 @@
 use academy_di::Build;
-use academy_models::{mfa::MfaRecoveryCode, Sensitive, VerificationCode};
+use academy_models::{
+    mfa::MfaRecoveryCode, session::SessionLoginCode, Sensitive, VerificationCode,
+};
 use academy_shared_contracts::secret::SecretService;
 use academy_utils::trace_instrument;
 use rand::{
@@ -47,6 +49,13 @@ impl SecretService for SecretServiceImpl {
         .try_into()
         .unwrap()
     }
+
+    #[trace_instrument(skip(self))]
+    fn generate_session_login_code(&self) -> SessionLoginCode {
+        format!("{:06}", csprng().gen_range(0..1_000_000))
+            .try_into()
+            .unwrap()
+    }
 }
 
 fn generate_hyphenated_code(
@@ -138,6 +147,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn generate_session_login_code() {
+        // Arrange
+        let sut = SecretServiceImpl;
+
+        // Act + Assert
+        for _ in 0..4096 {
+            sut.generate_session_login_code();
+        }
+    }
+
     #[test]
     fn uppercase_digits() {
         // Arrange
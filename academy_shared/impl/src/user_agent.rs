@@ -0,0 +1,84 @@
+use academy_di::Build;
+use academy_shared_contracts::user_agent::{UserAgentInfo, UserAgentService};
+use academy_utils::trace_instrument;
+use woothee::{parser::Parser, woothee::VALUE_UNKNOWN};
+
+#[derive(Debug, Clone, Copy, Build)]
+pub struct UserAgentServiceImpl;
+
+impl UserAgentService for UserAgentServiceImpl {
+    #[trace_instrument(skip(self))]
+    fn parse(&self, user_agent: &str) -> UserAgentInfo {
+        let Some(result) = Parser::new().parse(user_agent) else {
+            return UserAgentInfo::default();
+        };
+
+        let known = |s: &str| (!s.is_empty() && s != VALUE_UNKNOWN).then(|| s.to_owned());
+
+        UserAgentInfo {
+            browser: known(result.name).map(|name| match known(result.version) {
+                Some(version) => format!("{name} {version}"),
+                None => name,
+            }),
+            os: known(result.os),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn firefox_linux() {
+        // Arrange
+        let user_agent = "Mozilla/5.0 (X11; Linux x86_64; rv:131.0) Gecko/20100101 Firefox/131.0";
+
+        let sut = UserAgentServiceImpl;
+
+        // Act
+        let result = sut.parse(user_agent);
+
+        // Assert
+        assert_eq!(
+            result,
+            UserAgentInfo {
+                browser: Some("Firefox 131.0".into()),
+                os: Some("Linux".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn chrome_windows() {
+        // Arrange
+        let user_agent = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, \
+                          like Gecko) Chrome/130.0.0.0 Safari/537.36";
+
+        let sut = UserAgentServiceImpl;
+
+        // Act
+        let result = sut.parse(user_agent);
+
+        // Assert
+        assert_eq!(
+            result,
+            UserAgentInfo {
+                browser: Some("Chrome 130.0.0.0".into()),
+                os: Some("Windows 10".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn unknown() {
+        // Arrange
+        let sut = UserAgentServiceImpl;
+
+        // Act
+        let result = sut.parse("foo bar");
+
+        // Assert
+        assert_eq!(result, UserAgentInfo::default());
+    }
+}
@@ -0,0 +1,598 @@
+use std::sync::Arc;
+
+use academy_di::Build;
+use academy_models::{
+    mfa::{
+        WebauthnAssertion, WebauthnAssertionOptions, WebauthnChallenge, WebauthnCredentialId,
+        WebauthnPublicKey, WebauthnRegistration, WebauthnRegistrationOptions, WebauthnRelyingParty,
+    },
+    user::{UserDisplayName, UserId, UserName},
+};
+use academy_shared_contracts::{
+    secret::SecretService,
+    webauthn::{
+        WebauthnService, WebauthnVerifiedAssertion, WebauthnVerifiedRegistration,
+        WebauthnVerifyError,
+    },
+};
+use academy_utils::trace_instrument;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ciborium::Value;
+use ring::signature::{
+    RsaPublicKeyComponents, UnparsedPublicKey, ECDSA_P256_SHA256_ASN1, ED25519,
+    RSA_PKCS1_2048_8192_SHA256,
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/// Length of generated challenges in bytes
+const CHALLENGE_LENGTH: usize = 32;
+
+/// User present
+const FLAG_UP: u8 = 0x01;
+/// Attested credential data included
+const FLAG_AT: u8 = 0x40;
+
+#[derive(Debug, Clone, Build)]
+pub struct WebauthnServiceImpl<Secret> {
+    secret: Secret,
+    config: WebauthnServiceConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebauthnServiceConfig {
+    /// The relying party id (effective domain of the frontend)
+    pub rp_id: Arc<String>,
+    /// The human readable name of the relying party
+    pub rp_name: Arc<String>,
+    /// Origins from which WebAuthn requests are accepted
+    pub origins: Arc<Vec<String>>,
+}
+
+impl<Secret> WebauthnService for WebauthnServiceImpl<Secret>
+where
+    Secret: SecretService,
+{
+    #[trace_instrument(skip(self))]
+    fn generate_registration_options(
+        &self,
+        user_id: UserId,
+        user_name: UserName,
+        user_display_name: UserDisplayName,
+        exclude_credentials: Vec<WebauthnCredentialId>,
+    ) -> WebauthnRegistrationOptions {
+        WebauthnRegistrationOptions {
+            challenge: self.generate_challenge(),
+            rp: WebauthnRelyingParty {
+                id: (*self.config.rp_id).clone(),
+                name: (*self.config.rp_name).clone(),
+            },
+            user_id,
+            user_name,
+            user_display_name,
+            exclude_credentials,
+        }
+    }
+
+    #[trace_instrument(skip(self))]
+    fn generate_assertion_options(&self) -> WebauthnAssertionOptions {
+        WebauthnAssertionOptions {
+            challenge: self.generate_challenge(),
+            rp_id: (*self.config.rp_id).clone(),
+        }
+    }
+
+    #[trace_instrument(skip(self))]
+    fn verify_registration(
+        &self,
+        registration: &WebauthnRegistration,
+    ) -> Result<WebauthnVerifiedRegistration, WebauthnVerifyError> {
+        let challenge =
+            self.verify_client_data(&registration.client_data_json, "webauthn.create")?;
+
+        // Attestation statements are not verified, as we only request `none`
+        // attestation from the authenticators.
+        let auth_data = parse_attestation_object(&registration.attestation_object)?;
+        let auth_data = self.parse_authenticator_data(&auth_data)?;
+
+        let (credential_id, public_key) = auth_data
+            .attested_credential
+            .ok_or(WebauthnVerifyError::InvalidAuthenticatorData)?;
+
+        // make sure we are able to verify signatures of this credential
+        CosePublicKey::parse(&public_key)?;
+
+        Ok(WebauthnVerifiedRegistration {
+            challenge,
+            credential_id: credential_id
+                .try_into()
+                .map_err(|_| WebauthnVerifyError::InvalidAuthenticatorData)?,
+            public_key: public_key.into(),
+            sign_count: auth_data.sign_count,
+        })
+    }
+
+    #[trace_instrument(skip(self))]
+    fn verify_assertion(
+        &self,
+        assertion: &WebauthnAssertion,
+        public_key: &WebauthnPublicKey,
+        sign_count: u32,
+    ) -> Result<WebauthnVerifiedAssertion, WebauthnVerifyError> {
+        let challenge = self.verify_client_data(&assertion.client_data_json, "webauthn.get")?;
+
+        let auth_data = self.parse_authenticator_data(&assertion.authenticator_data)?;
+
+        let mut message = assertion.authenticator_data.clone();
+        message.extend_from_slice(&Sha256::digest(&assertion.client_data_json));
+        CosePublicKey::parse(public_key)?.verify(&message, &assertion.signature)?;
+
+        // Authenticators that do not support signature counters always return zero.
+        // Otherwise, the counter must increase with every assertion, or the
+        // authenticator may have been cloned.
+        if (auth_data.sign_count != 0 || sign_count != 0) && auth_data.sign_count <= sign_count {
+            return Err(WebauthnVerifyError::SignCountNotIncreased);
+        }
+
+        Ok(WebauthnVerifiedAssertion {
+            challenge,
+            sign_count: auth_data.sign_count,
+        })
+    }
+}
+
+impl<Secret: SecretService> WebauthnServiceImpl<Secret> {
+    fn generate_challenge(&self) -> WebauthnChallenge {
+        self.secret.generate_bytes(CHALLENGE_LENGTH).0.into()
+    }
+
+    fn verify_client_data(
+        &self,
+        client_data_json: &[u8],
+        expected_type: &str,
+    ) -> Result<WebauthnChallenge, WebauthnVerifyError> {
+        #[derive(Deserialize)]
+        struct ClientData {
+            #[serde(rename = "type")]
+            ty: String,
+            challenge: String,
+            origin: String,
+        }
+
+        let client_data = serde_json::from_slice::<ClientData>(client_data_json)
+            .map_err(|_| WebauthnVerifyError::InvalidClientData)?;
+
+        if client_data.ty != expected_type || !self.config.origins.contains(&client_data.origin) {
+            return Err(WebauthnVerifyError::InvalidClientData);
+        }
+
+        URL_SAFE_NO_PAD
+            .decode(client_data.challenge.trim_end_matches('='))
+            .map(Into::into)
+            .map_err(|_| WebauthnVerifyError::InvalidClientData)
+    }
+
+    fn parse_authenticator_data(
+        &self,
+        data: &[u8],
+    ) -> Result<AuthenticatorData, WebauthnVerifyError> {
+        let err = || WebauthnVerifyError::InvalidAuthenticatorData;
+
+        let (rp_id_hash, data) = split_at_checked(data, 32).ok_or_else(err)?;
+        let (&flags, data) = data.split_first().ok_or_else(err)?;
+        let (sign_count, data) = split_at_checked(data, 4).ok_or_else(err)?;
+
+        if rp_id_hash != Sha256::digest(self.config.rp_id.as_bytes()).as_slice()
+            || flags & FLAG_UP == 0
+        {
+            return Err(err());
+        }
+
+        let attested_credential = if flags & FLAG_AT != 0 {
+            let (_aaguid, data) = split_at_checked(data, 16).ok_or_else(err)?;
+            let (credential_id_len, data) = split_at_checked(data, 2).ok_or_else(err)?;
+            let credential_id_len = u16::from_be_bytes(credential_id_len.try_into().unwrap());
+            let (credential_id, data) =
+                split_at_checked(data, credential_id_len.into()).ok_or_else(err)?;
+
+            // the public key is followed by optional extension data, so we have to parse it
+            // to figure out where it ends
+            let mut rest = data;
+            ciborium::from_reader::<Value, _>(&mut rest).map_err(|_| err())?;
+            let public_key = &data[..data.len() - rest.len()];
+
+            Some((credential_id.to_vec(), public_key.to_vec()))
+        } else {
+            None
+        };
+
+        Ok(AuthenticatorData {
+            sign_count: u32::from_be_bytes(sign_count.try_into().unwrap()),
+            attested_credential,
+        })
+    }
+}
+
+struct AuthenticatorData {
+    sign_count: u32,
+    /// Credential id and COSE encoded public key
+    attested_credential: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+fn split_at_checked(data: &[u8], mid: usize) -> Option<(&[u8], &[u8])> {
+    (mid <= data.len()).then(|| data.split_at(mid))
+}
+
+fn parse_attestation_object(data: &[u8]) -> Result<Vec<u8>, WebauthnVerifyError> {
+    let err = || WebauthnVerifyError::InvalidAuthenticatorData;
+
+    let Value::Map(entries) = ciborium::from_reader::<Value, _>(data).map_err(|_| err())? else {
+        return Err(err());
+    };
+
+    entries
+        .into_iter()
+        .find_map(|(key, value)| match (key, value) {
+            (Value::Text(key), Value::Bytes(auth_data)) if key == "authData" => Some(auth_data),
+            _ => None,
+        })
+        .ok_or_else(err)
+}
+
+/// Public key in COSE format (see [RFC 9053](https://www.rfc-editor.org/rfc/rfc9053))
+enum CosePublicKey {
+    Es256 { x: Vec<u8>, y: Vec<u8> },
+    EdDsa { x: Vec<u8> },
+    Rs256 { n: Vec<u8>, e: Vec<u8> },
+}
+
+impl CosePublicKey {
+    const KTY: i128 = 1;
+    const ALG: i128 = 3;
+
+    const KTY_OKP: i128 = 1;
+    const KTY_EC2: i128 = 2;
+    const KTY_RSA: i128 = 3;
+
+    const ALG_ES256: i128 = -7;
+    const ALG_EDDSA: i128 = -8;
+    const ALG_RS256: i128 = -257;
+
+    const CRV_P256: i128 = 1;
+    const CRV_ED25519: i128 = 6;
+
+    fn parse(data: &[u8]) -> Result<Self, WebauthnVerifyError> {
+        let err = || WebauthnVerifyError::InvalidPublicKey;
+
+        let Value::Map(entries) = ciborium::from_reader::<Value, _>(data).map_err(|_| err())?
+        else {
+            return Err(err());
+        };
+
+        let get = |label: i128| {
+            entries.iter().find_map(|(key, value)| {
+                key.as_integer()
+                    .is_some_and(|key| i128::from(key) == label)
+                    .then_some(value)
+            })
+        };
+        let get_int = |label| get(label).and_then(Value::as_integer).map(i128::from);
+        let get_bytes = |label| get(label).and_then(Value::as_bytes).cloned();
+
+        match (get_int(Self::KTY), get_int(Self::ALG)) {
+            (Some(Self::KTY_EC2), Some(Self::ALG_ES256)) if get_int(-1) == Some(Self::CRV_P256) => {
+                Ok(Self::Es256 {
+                    x: get_bytes(-2).filter(|x| x.len() == 32).ok_or_else(err)?,
+                    y: get_bytes(-3).filter(|y| y.len() == 32).ok_or_else(err)?,
+                })
+            }
+            (Some(Self::KTY_OKP), Some(Self::ALG_EDDSA))
+                if get_int(-1) == Some(Self::CRV_ED25519) =>
+            {
+                Ok(Self::EdDsa {
+                    x: get_bytes(-2).filter(|x| x.len() == 32).ok_or_else(err)?,
+                })
+            }
+            (Some(Self::KTY_RSA), Some(Self::ALG_RS256)) => Ok(Self::Rs256 {
+                n: get_bytes(-1).ok_or_else(err)?,
+                e: get_bytes(-2).ok_or_else(err)?,
+            }),
+            _ => Err(err()),
+        }
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), WebauthnVerifyError> {
+        match self {
+            Self::Es256 { x, y } => {
+                let point = [&[0x04], x.as_slice(), y.as_slice()].concat();
+                UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, point).verify(message, signature)
+            }
+            Self::EdDsa { x } => UnparsedPublicKey::new(&ED25519, x).verify(message, signature),
+            Self::Rs256 { n, e } => RsaPublicKeyComponents { n, e }.verify(
+                &RSA_PKCS1_2048_8192_SHA256,
+                message,
+                signature,
+            ),
+        }
+        .map_err(|_| WebauthnVerifyError::InvalidSignature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_demo::user::FOO;
+    use academy_shared_contracts::secret::MockSecretService;
+    use ring::{
+        rand::SystemRandom,
+        signature::{EcdsaKeyPair, Ed25519KeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING},
+    };
+
+    use super::*;
+
+    type Sut = WebauthnServiceImpl<MockSecretService>;
+
+    const RP_ID: &str = "bootstrap.academy";
+    const ORIGIN: &str = "https://bootstrap.academy";
+    const CHALLENGE: &[u8] = &[42; CHALLENGE_LENGTH];
+    const CREDENTIAL_ID: &[u8] = &[1, 2, 3, 4];
+
+    #[test]
+    fn generate_registration_options() {
+        // Arrange
+        let secret =
+            MockSecretService::new().with_generate_bytes(CHALLENGE_LENGTH, CHALLENGE.into());
+        let sut = make_sut(secret);
+
+        // Act
+        let result = sut.generate_registration_options(
+            FOO.user.id,
+            FOO.user.name.clone(),
+            FOO.profile.display_name.clone(),
+            vec![CREDENTIAL_ID.to_vec().try_into().unwrap()],
+        );
+
+        // Assert
+        assert_eq!(
+            result,
+            WebauthnRegistrationOptions {
+                challenge: CHALLENGE.to_vec().into(),
+                rp: WebauthnRelyingParty {
+                    id: RP_ID.into(),
+                    name: "Bootstrap Academy".into()
+                },
+                user_id: FOO.user.id,
+                user_name: FOO.user.name.clone(),
+                user_display_name: FOO.profile.display_name.clone(),
+                exclude_credentials: vec![CREDENTIAL_ID.to_vec().try_into().unwrap()],
+            }
+        );
+    }
+
+    #[test]
+    fn register_and_assert_es256() {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+        let key_pair =
+            EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng)
+                .unwrap();
+        let point = key_pair.public_key().as_ref();
+        let public_key = cose_key(vec![
+            (1, Value::from(2)),
+            (3, Value::from(-7)),
+            (-1, Value::from(1)),
+            (-2, Value::Bytes(point[1..33].to_vec())),
+            (-3, Value::Bytes(point[33..].to_vec())),
+        ]);
+
+        register_and_assert(public_key, |message| {
+            key_pair.sign(&rng, message).unwrap().as_ref().to_vec()
+        });
+    }
+
+    #[test]
+    fn register_and_assert_eddsa() {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let public_key = cose_key(vec![
+            (1, Value::from(1)),
+            (3, Value::from(-8)),
+            (-1, Value::from(6)),
+            (-2, Value::Bytes(key_pair.public_key().as_ref().to_vec())),
+        ]);
+
+        register_and_assert(public_key, |message| {
+            key_pair.sign(message).as_ref().to_vec()
+        });
+    }
+
+    #[test]
+    fn registration_invalid_origin() {
+        // Arrange
+        let sut = make_sut(MockSecretService::new());
+        let registration = WebauthnRegistration {
+            client_data_json: client_data("webauthn.create", "https://evil.example"),
+            attestation_object: attestation_object(authenticator_data(
+                RP_ID,
+                FLAG_UP | FLAG_AT,
+                0,
+                Some(&cose_key(vec![])),
+            )),
+        };
+
+        // Act
+        let result = sut.verify_registration(&registration);
+
+        // Assert
+        assert_eq!(result, Err(WebauthnVerifyError::InvalidClientData));
+    }
+
+    #[test]
+    fn registration_invalid_rp_id() {
+        // Arrange
+        let sut = make_sut(MockSecretService::new());
+        let registration = WebauthnRegistration {
+            client_data_json: client_data("webauthn.create", ORIGIN),
+            attestation_object: attestation_object(authenticator_data(
+                "evil.example",
+                FLAG_UP | FLAG_AT,
+                0,
+                Some(&cose_key(vec![])),
+            )),
+        };
+
+        // Act
+        let result = sut.verify_registration(&registration);
+
+        // Assert
+        assert_eq!(result, Err(WebauthnVerifyError::InvalidAuthenticatorData));
+    }
+
+    #[test]
+    fn registration_unsupported_algorithm() {
+        // Arrange
+        let sut = make_sut(MockSecretService::new());
+        let public_key = cose_key(vec![(1, Value::from(2)), (3, Value::from(-35))]);
+        let registration = WebauthnRegistration {
+            client_data_json: client_data("webauthn.create", ORIGIN),
+            attestation_object: attestation_object(authenticator_data(
+                RP_ID,
+                FLAG_UP | FLAG_AT,
+                0,
+                Some(&public_key),
+            )),
+        };
+
+        // Act
+        let result = sut.verify_registration(&registration);
+
+        // Assert
+        assert_eq!(result, Err(WebauthnVerifyError::InvalidPublicKey));
+    }
+
+    fn register_and_assert(public_key: Vec<u8>, sign: impl Fn(&[u8]) -> Vec<u8>) {
+        let sut = make_sut(MockSecretService::new());
+
+        // registration
+        let registration = WebauthnRegistration {
+            client_data_json: client_data("webauthn.create", ORIGIN),
+            attestation_object: attestation_object(authenticator_data(
+                RP_ID,
+                FLAG_UP | FLAG_AT,
+                0,
+                Some(&public_key),
+            )),
+        };
+        let registered = sut.verify_registration(&registration).unwrap();
+        assert_eq!(
+            registered,
+            WebauthnVerifiedRegistration {
+                challenge: CHALLENGE.to_vec().into(),
+                credential_id: CREDENTIAL_ID.to_vec().try_into().unwrap(),
+                public_key: public_key.into(),
+                sign_count: 0,
+            }
+        );
+
+        // valid assertion
+        let assertion = make_assertion(7, &sign);
+        let result = sut.verify_assertion(&assertion, &registered.public_key, 3);
+        assert_eq!(
+            result,
+            Ok(WebauthnVerifiedAssertion {
+                challenge: CHALLENGE.to_vec().into(),
+                sign_count: 7
+            })
+        );
+
+        // sign count did not increase
+        let result = sut.verify_assertion(&assertion, &registered.public_key, 7);
+        assert_eq!(result, Err(WebauthnVerifyError::SignCountNotIncreased));
+
+        // invalid signature
+        let mut assertion = make_assertion(8, &sign);
+        assertion.authenticator_data[33] ^= 1;
+        let result = sut.verify_assertion(&assertion, &registered.public_key, 7);
+        assert_eq!(result, Err(WebauthnVerifyError::InvalidSignature));
+
+        // wrong client data type
+        let mut assertion = make_assertion(8, &sign);
+        assertion.client_data_json = client_data("webauthn.create", ORIGIN);
+        let result = sut.verify_assertion(&assertion, &registered.public_key, 7);
+        assert_eq!(result, Err(WebauthnVerifyError::InvalidClientData));
+    }
+
+    fn make_assertion(sign_count: u32, sign: impl Fn(&[u8]) -> Vec<u8>) -> WebauthnAssertion {
+        let client_data_json = client_data("webauthn.get", ORIGIN);
+        let authenticator_data = authenticator_data(RP_ID, FLAG_UP, sign_count, None);
+        let mut message = authenticator_data.clone();
+        message.extend_from_slice(&Sha256::digest(&client_data_json));
+
+        WebauthnAssertion {
+            credential_id: CREDENTIAL_ID.to_vec().try_into().unwrap(),
+            client_data_json,
+            authenticator_data,
+            signature: sign(&message),
+        }
+    }
+
+    fn make_sut(secret: MockSecretService) -> Sut {
+        WebauthnServiceImpl {
+            secret,
+            config: WebauthnServiceConfig {
+                rp_id: RP_ID.to_owned().into(),
+                rp_name: "Bootstrap Academy".to_owned().into(),
+                origins: vec![ORIGIN.to_owned()].into(),
+            },
+        }
+    }
+
+    fn client_data(ty: &str, origin: &str) -> Vec<u8> {
+        serde_json::to_vec(&serde_json::json!({
+            "type": ty,
+            "challenge": URL_SAFE_NO_PAD.encode(CHALLENGE),
+            "origin": origin,
+        }))
+        .unwrap()
+    }
+
+    fn authenticator_data(
+        rp_id: &str,
+        flags: u8,
+        sign_count: u32,
+        public_key: Option<&[u8]>,
+    ) -> Vec<u8> {
+        let mut out = Sha256::digest(rp_id.as_bytes()).to_vec();
+        out.push(flags);
+        out.extend_from_slice(&sign_count.to_be_bytes());
+        if let Some(public_key) = public_key {
+            out.extend_from_slice(&[0; 16]);
+            out.extend_from_slice(&(CREDENTIAL_ID.len() as u16).to_be_bytes());
+            out.extend_from_slice(CREDENTIAL_ID);
+            out.extend_from_slice(public_key);
+        }
+        out
+    }
+
+    fn attestation_object(auth_data: Vec<u8>) -> Vec<u8> {
+        let value = Value::Map(vec![
+            (Value::from("fmt"), Value::from("none")),
+            (Value::from("attStmt"), Value::Map(vec![])),
+            (Value::from("authData"), Value::Bytes(auth_data)),
+        ]);
+        let mut out = Vec::new();
+        ciborium::into_writer(&value, &mut out).unwrap();
+        out
+    }
+
+    fn cose_key(entries: Vec<(i64, Value)>) -> Vec<u8> {
+        let value = Value::Map(
+            entries
+                .into_iter()
+                .map(|(k, v)| (Value::from(k), v))
+                .collect(),
+        );
+        let mut out = Vec::new();
+        ciborium::into_writer(&value, &mut out).unwrap();
+        out
+    }
+}
@@ -0,0 +1,56 @@
+use std::future::Future;
+
+use academy_models::url::Url;
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait StorageService: Send + Sync + 'static {
+    /// Create a new or replace an existing file.
+    ///
+    /// Keys are relative paths consisting of `/` separated segments, e.g.
+    /// `avatars/<user_id>/256.png`.
+    fn put(
+        &self,
+        key: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Remove an existing file.
+    ///
+    /// Does nothing if the file does not exist.
+    fn remove(&self, key: &str) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Return the public URL under which the file is served.
+    fn url(&self, key: &str) -> Url;
+}
+
+#[cfg(feature = "mock")]
+impl MockStorageService {
+    pub fn with_put(mut self, key: String, content_type: String) -> Self {
+        self.expect_put()
+            .once()
+            .with(
+                mockall::predicate::eq(key),
+                mockall::predicate::eq(content_type),
+                mockall::predicate::always(),
+            )
+            .return_once(|_, _, _| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_remove(mut self, key: String) -> Self {
+        self.expect_remove()
+            .once()
+            .with(mockall::predicate::eq(key))
+            .return_once(|_| Box::pin(std::future::ready(Ok(()))));
+        self
+    }
+
+    pub fn with_url(mut self, key: String, result: Url) -> Self {
+        self.expect_url()
+            .once()
+            .with(mockall::predicate::eq(key))
+            .return_once(|_| result);
+        self
+    }
+}
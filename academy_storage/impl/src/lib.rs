@@ -0,0 +1,190 @@
+use std::{path::PathBuf, sync::Arc};
+
+use academy_di::Build;
+use academy_models::url::Url;
+use academy_shared_contracts::time::TimeService;
+use academy_storage_contracts::StorageService;
+use academy_utils::trace_instrument;
+use anyhow::{anyhow, ensure};
+
+mod local;
+mod s3;
+
+#[derive(Debug, Clone, Build)]
+pub struct StorageServiceImpl<Time> {
+    time: Time,
+    config: StorageServiceConfig,
+    #[di(default)]
+    http: reqwest::Client,
+}
+
+#[derive(Debug, Clone)]
+pub enum StorageServiceConfig {
+    Local(LocalStorageServiceConfig),
+    S3(S3StorageServiceConfig),
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalStorageServiceConfig {
+    path: Arc<PathBuf>,
+    public_url: Arc<Url>,
+}
+
+impl LocalStorageServiceConfig {
+    pub fn new(path: PathBuf, public_url: Url) -> Self {
+        Self {
+            path: path.into(),
+            public_url: with_trailing_slash(public_url).into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct S3StorageServiceConfig {
+    endpoint: Arc<Url>,
+    bucket: Arc<str>,
+    region: Arc<str>,
+    access_key_id: Arc<str>,
+    secret_access_key: Arc<str>,
+    public_url: Arc<Url>,
+}
+
+impl S3StorageServiceConfig {
+    pub fn new(
+        endpoint: Url,
+        bucket: String,
+        region: String,
+        access_key_id: String,
+        secret_access_key: String,
+        public_url: Url,
+    ) -> Self {
+        Self {
+            endpoint: with_trailing_slash(endpoint).into(),
+            bucket: bucket.into(),
+            region: region.into(),
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            public_url: with_trailing_slash(public_url).into(),
+        }
+    }
+}
+
+impl<Time> StorageService for StorageServiceImpl<Time>
+where
+    Time: TimeService,
+{
+    #[trace_instrument(skip(self, data))]
+    async fn put(&self, key: &str, content_type: &str, data: Vec<u8>) -> anyhow::Result<()> {
+        validate_key(key)?;
+
+        match &self.config {
+            StorageServiceConfig::Local(config) => local::put(config, key, data).await,
+            StorageServiceConfig::S3(config) => {
+                s3::put(config, &self.http, self.time.now(), key, content_type, data).await
+            }
+        }
+    }
+
+    #[trace_instrument(skip(self))]
+    async fn remove(&self, key: &str) -> anyhow::Result<()> {
+        validate_key(key)?;
+
+        match &self.config {
+            StorageServiceConfig::Local(config) => local::remove(config, key).await,
+            StorageServiceConfig::S3(config) => {
+                s3::remove(config, &self.http, self.time.now(), key).await
+            }
+        }
+    }
+
+    #[trace_instrument(skip(self))]
+    fn url(&self, key: &str) -> Url {
+        let public_url = match &self.config {
+            StorageServiceConfig::Local(config) => &config.public_url,
+            StorageServiceConfig::S3(config) => &config.public_url,
+        };
+        public_url.join(key).unwrap().into()
+    }
+}
+
+/// Ensure that a key consists only of non-empty segments of safe characters,
+/// so it can neither escape the storage directory nor needs to be escaped in
+/// URLs.
+fn validate_key(key: &str) -> anyhow::Result<()> {
+    for segment in key.split('/') {
+        ensure!(
+            !segment.is_empty()
+                && !segment.chars().all(|c| c == '.')
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')),
+            anyhow!("Invalid storage key: {key:?}")
+        );
+    }
+    Ok(())
+}
+
+/// Append a trailing slash to the path of a URL so that it can be used as a
+/// base for [`Url::join`].
+fn with_trailing_slash(mut url: Url) -> Url {
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use academy_shared_contracts::time::MockTimeService;
+
+    use super::*;
+
+    #[test]
+    fn validate_key_ok() {
+        validate_key("avatars/0192e3c4-7f39-7c53-b2ba-6d8e6a9ff1c1/256.png").unwrap();
+        validate_key("foo").unwrap();
+        validate_key("foo/.bar_baz-42").unwrap();
+    }
+
+    #[test]
+    fn validate_key_invalid() {
+        for key in [
+            "",
+            "/foo",
+            "foo/",
+            "foo//bar",
+            "..",
+            "foo/../bar",
+            "./foo",
+            "foo bar",
+            "foo?bar",
+            "foo%2e",
+            "föö",
+        ] {
+            validate_key(key).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn url() {
+        // Arrange
+        let sut = StorageServiceImpl {
+            time: MockTimeService::new(),
+            config: StorageServiceConfig::Local(LocalStorageServiceConfig::new(
+                "/var/lib/academy/storage".into(),
+                "https://cdn.bootstrap.academy/storage".parse().unwrap(),
+            )),
+            http: Default::default(),
+        };
+
+        // Act
+        let result = sut.url("avatars/foo/256.png");
+
+        // Assert
+        assert_eq!(
+            result.as_str(),
+            "https://cdn.bootstrap.academy/storage/avatars/foo/256.png"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use std::io::ErrorKind;
+
+use anyhow::Context;
+use uuid::Uuid;
+
+use crate::LocalStorageServiceConfig;
+
+pub(crate) async fn put(
+    config: &LocalStorageServiceConfig,
+    key: &str,
+    data: Vec<u8>,
+) -> anyhow::Result<()> {
+    let path = config.path.join(key);
+    let parent = path.parent().context("Storage path has no parent")?;
+    tokio::fs::create_dir_all(parent)
+        .await
+        .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+
+    // write to a temporary file first so that readers never see partially
+    // written files
+    let tmp_path = parent.join(format!(".{}.tmp", Uuid::new_v4()));
+    tokio::fs::write(&tmp_path, data)
+        .await
+        .with_context(|| format!("Failed to write file {}", tmp_path.display()))?;
+
+    if let Err(err) = tokio::fs::rename(&tmp_path, &path).await {
+        let _ = tokio::fs::remove_file(&tmp_path).await;
+        return Err(err).with_context(|| format!("Failed to write file {}", path.display()));
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn remove(config: &LocalStorageServiceConfig, key: &str) -> anyhow::Result<()> {
+    let path = config.path.join(key);
+    match tokio::fs::remove_file(&path).await {
+        Err(err) if err.kind() != ErrorKind::NotFound => {
+            Err(err).with_context(|| format!("Failed to remove file {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn put_and_remove() {
+        // Arrange
+        let dir = std::env::temp_dir().join(format!("academy-storage-{}", Uuid::new_v4()));
+        let config = LocalStorageServiceConfig::new(
+            dir.clone(),
+            "http://localhost/storage/".parse().unwrap(),
+        );
+
+        // Act
+        put(&config, "foo/bar.txt", b"Hello".into()).await.unwrap();
+        put(&config, "foo/bar.txt", b"World".into()).await.unwrap();
+
+        // Assert
+        assert_eq!(
+            std::fs::read_to_string(dir.join("foo/bar.txt")).unwrap(),
+            "World"
+        );
+        assert_eq!(std::fs::read_dir(dir.join("foo")).unwrap().count(), 1);
+
+        // Act
+        remove(&config, "foo/bar.txt").await.unwrap();
+        remove(&config, "foo/bar.txt").await.unwrap();
+
+        // Assert
+        assert!(!dir.join("foo/bar.txt").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
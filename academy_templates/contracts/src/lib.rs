@@ -48,6 +48,14 @@ templates! {
     ResetPasswordTemplate(templates::RESET_PASSWORD_HTML),
     VerifyEmailTemplate(templates::VERIFY_EMAIL_HTML),
     SubscribeNewsletterTemplate(templates::SUBSCRIBE_NEWSLETTER_HTML),
+    AccountLockedTemplate(templates::ACCOUNT_LOCKED_HTML),
+    NewSignInTemplate(templates::NEW_SIGN_IN_HTML),
+    LoginCodeTemplate(templates::LOGIN_CODE_HTML),
+    UserExportTemplate(templates::USER_EXPORT_HTML),
+    UserDeletedTemplate(templates::USER_DELETED_HTML),
+    ChangeEmailTemplate(templates::CHANGE_EMAIL_HTML),
+    EmailChangeNoticeTemplate(templates::EMAIL_CHANGE_NOTICE_HTML),
+    AddEmailTemplate(templates::ADD_EMAIL_HTML),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -67,3 +75,52 @@ pub struct SubscribeNewsletterTemplate {
     pub code: String,
     pub url: String,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AccountLockedTemplate {
+    pub minutes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NewSignInTemplate {
+    pub device_name: Option<String>,
+    pub ip: Option<String>,
+    pub time: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LoginCodeTemplate {
+    pub code: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UserExportTemplate {
+    pub url: String,
+    pub expires_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UserDeletedTemplate {
+    pub url: String,
+    pub deadline: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangeEmailTemplate {
+    pub code: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EmailChangeNoticeTemplate {
+    pub email: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AddEmailTemplate {
+    pub code: String,
+    pub url: String,
+}
@@ -46,7 +46,9 @@ impl TemplateService for TemplateServiceImpl {
 #[cfg(test)]
 mod tests {
     use academy_templates_contracts::{
-        ResetPasswordTemplate, SubscribeNewsletterTemplate, VerifyEmailTemplate,
+        AccountLockedTemplate, AddEmailTemplate, ChangeEmailTemplate, EmailChangeNoticeTemplate,
+        LoginCodeTemplate, NewSignInTemplate, ResetPasswordTemplate, SubscribeNewsletterTemplate,
+        UserDeletedTemplate, UserExportTemplate, VerifyEmailTemplate,
     };
 
     use super::*;
@@ -75,6 +77,75 @@ mod tests {
         });
     }
 
+    #[test]
+    fn account_locked() {
+        test_template(AccountLockedTemplate { minutes: 15 });
+    }
+
+    #[test]
+    fn new_sign_in() {
+        test_template(NewSignInTemplate {
+            device_name: Some("Mozilla/5.0".into()),
+            ip: Some("127.0.0.1".into()),
+            time: "01.01.2024 13:37 UTC".into(),
+            url: "https://bootstrap.academy/?code=code".into(),
+        });
+        test_template(NewSignInTemplate {
+            device_name: None,
+            ip: None,
+            time: "01.01.2024 13:37 UTC".into(),
+            url: "https://bootstrap.academy/?code=code".into(),
+        });
+    }
+
+    #[test]
+    fn login_code() {
+        test_template(LoginCodeTemplate {
+            code: "123456".into(),
+            url: "https://bootstrap.academy/?code=123456".into(),
+        });
+    }
+
+    #[test]
+    fn user_export() {
+        test_template(UserExportTemplate {
+            url: "https://bootstrap.academy/?code=code".into(),
+            expires_at: "01.01.2024 13:37 UTC".into(),
+        });
+    }
+
+    #[test]
+    fn user_deleted() {
+        test_template(UserDeletedTemplate {
+            url: "https://bootstrap.academy/?code=code".into(),
+            deadline: "01.01.2024 13:37 UTC".into(),
+        });
+    }
+
+    #[test]
+    fn change_email() {
+        test_template(ChangeEmailTemplate {
+            code: "code".into(),
+            url: "https://bootstrap.academy/?code=code".into(),
+        });
+    }
+
+    #[test]
+    fn email_change_notice() {
+        test_template(EmailChangeNoticeTemplate {
+            email: "foo@example.com".into(),
+            url: "https://bootstrap.academy/?code=code".into(),
+        });
+    }
+
+    #[test]
+    fn add_email() {
+        test_template(AddEmailTemplate {
+            code: "code".into(),
+            url: "https://bootstrap.academy/?code=code".into(),
+        });
+    }
+
     fn test_template<T: Template + 'static>(template: T) {
         // Arrange
         let sut = TemplateServiceImpl {
@@ -1,4 +1,5 @@
 pub mod internal;
 pub mod oauth2;
+pub mod pwned_passwords;
 pub mod recaptcha;
 pub mod vat;
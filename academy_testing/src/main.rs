@@ -1,6 +1,6 @@
 use std::net::IpAddr;
 
-use academy_testing::{internal, oauth2, recaptcha, vat};
+use academy_testing::{internal, oauth2, pwned_passwords, recaptcha, vat};
 use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::Shell;
 use url::Url;
@@ -29,6 +29,7 @@ async fn main() -> anyhow::Result<()> {
         } => oauth2::start_server(host, port, client_id, client_secret, redirect_url).await?,
         Command::Vat { host, port } => vat::start_server(host, port).await?,
         Command::Internal { host, port } => internal::start_server(host, port).await?,
+        Command::PwnedPasswords { host, port } => pwned_passwords::start_server(host, port).await?,
         Command::Completion { shell } => {
             clap_complete::generate(
                 shell,
@@ -88,6 +89,13 @@ enum Command {
         #[arg(long, default_value = "8004")]
         port: u16,
     },
+    /// Start the pwned passwords api testing server
+    PwnedPasswords {
+        #[arg(long, default_value = "127.0.0.1")]
+        host: IpAddr,
+        #[arg(long, default_value = "8005")]
+        port: u16,
+    },
     /// Generate shell completions
     Completion {
         /// The shell to generate completions for
@@ -0,0 +1,52 @@
+use std::net::IpAddr;
+
+use anyhow::Context;
+use axum::{extract::Path, routing, Router};
+use sha1::{Digest, Sha1};
+use tokio::net::TcpListener;
+use tracing::info;
+
+const RANGE_ROUTE: &str = "/range/:prefix";
+
+/// Passwords that are reported as breached, together with their breach count.
+const BREACHED_PASSWORDS: &[(&str, u64)] = &[
+    ("123456", 42),
+    ("12345678", 23),
+    ("password", 1337),
+    ("Password1!", 7),
+    ("qwerty", 17),
+    ("Hunter2!", 3),
+];
+
+pub async fn start_server(host: IpAddr, port: u16) -> anyhow::Result<()> {
+    info!("Starting pwned passwords testing server on {host}:{port}");
+    info!("Range endpoint: http://{host}:{port}{RANGE_ROUTE}");
+    info!(
+        "The following passwords are reported as breached: {:?}",
+        BREACHED_PASSWORDS
+            .iter()
+            .map(|(password, _)| password)
+            .collect::<Vec<_>>()
+    );
+
+    let router = Router::new().route(RANGE_ROUTE, routing::get(range));
+
+    let listener = TcpListener::bind((host, port))
+        .await
+        .with_context(|| format!("Failed to bind to {host}:{port}"))?;
+    axum::serve(listener, router)
+        .await
+        .context("Failed to start HTTP server")
+}
+
+async fn range(Path(prefix): Path<String>) -> String {
+    let prefix = prefix.to_uppercase();
+    BREACHED_PASSWORDS
+        .iter()
+        .map(|&(password, count)| (hex::encode_upper(Sha1::digest(password)), count))
+        .filter_map(|(hash, count)| {
+            hash.strip_prefix(&prefix)
+                .map(|suffix| format!("{suffix}:{count}\r\n"))
+        })
+        .collect()
+}
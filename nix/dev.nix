@@ -65,6 +65,10 @@
     ${testing}/bin/academy-testing internal
   '';
 
+  processes.testing-pwned-passwords.exec = ''
+    ${testing}/bin/academy-testing pwned-passwords
+  '';
+
   env = {
     ACADEMY_DEVENV = "1";
 
@@ -39,7 +39,7 @@ in {
       default = {};
     };
 
-    tasks = lib.genAttrs ["prune-database"] (task: {
+    tasks = lib.genAttrs ["prune-database" "purge-deleted-users" "process-user-exports"] (task: {
       schedule = lib.mkOption {
         type = lib.types.either lib.types.str (lib.types.listOf lib.types.str);
         default = [];
@@ -73,6 +73,7 @@ in {
           serviceConfig = {
             User = "academy";
             Group = "academy";
+            StateDirectory = "academy";
           };
 
           environment = {
@@ -134,6 +135,7 @@ in {
 
       services.academy.backend.settings.database.url = lib.mkIf cfg.localDatabase "host=/run/postgresql user=academy";
       services.academy.backend.settings.cache.url = lib.mkIf cfg.localCache "redis+unix://${config.services.redis.servers.academy.unixSocket}";
+      services.academy.backend.settings.storage.path = lib.mkDefault "/var/lib/academy/storage";
 
       environment.systemPackages = [wrapper];
     };
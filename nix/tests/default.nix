@@ -53,6 +53,7 @@
           smtp_url = "smtp://127.0.0.1:25";
           from = "test@bootstrap.academy";
         };
+        storage.public_url = "http://127.0.0.1:8000/storage/";
         internal.shop_url = "http://127.0.0.1:8004/shop/";
         health = {
           database_cache_ttl = "2s";
@@ -68,6 +69,8 @@
           min_score = 0.5;
         };
         vat.validate_endpoint_override = "http://127.0.0.1:8003/validate/";
+        password_policy.enable = false;
+        oauth2_server.issuer = "http://127.0.0.1:8000";
         oauth2 = {
           enable = true;
           providers = let
@@ -139,7 +142,11 @@
       group = "academy";
       mode = "0400";
       argument = ''
-        jwt.secret = "changeme"
+        jwt.signing_key = "test"
+        jwt.keys.test.algorithm = "EdDSA"
+        jwt.keys.test.key = "MC4CAQAwBQYDK2VwBCIEILSzt4bhpYr7vdPS781n4/logjpPbJn0N5zvLuyaq1Pq"
+        encryption.key_version = 1
+        encryption.keys.1 = "Y2hhbmdlbWVjaGFuZ2VtZWNoYW5nZW1lY2hhbmdlbWU="
       '';
     };
   };
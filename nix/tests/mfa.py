@@ -75,7 +75,9 @@ assert get_self()["mfa_enabled"] is False
 # login
 resp = c.put("/auth/users/me/mfa", json={"code": totp.now()})
 assert resp.status_code == 200
-recovery_code = resp.json()
+recovery_codes = resp.json()
+assert len(recovery_codes) == 10
+recovery_code = recovery_codes[0]
 assert get_self()["mfa_enabled"] is True
 
 discard_auth()
@@ -113,6 +115,41 @@ resp = c.post(
 )
 assert resp.status_code == 200
 login = resp.json()
-assert login["user"]["mfa_enabled"] is False
+assert login["user"]["mfa_enabled"] is True
 save_auth(login)
+assert get_self()["mfa_enabled"] is True
+
+resp = c.get("/auth/users/me/mfa/recovery_codes")
+assert resp.status_code == 200
+assert resp.json() == {"remaining": 9}
+
+# recovery code already used
+discard_auth()
+resp = c.post(
+    "/auth/sessions",
+    json={"name_or_email": "a", "password": "a", "recovery_code": recovery_code, "recaptcha_response": "success-1.0"},
+)
+assert resp.status_code == 412
+assert resp.json() == {"detail": "Invalid code"}
+
+resp = c.post(
+    "/auth/sessions",
+    json={"name_or_email": "a", "password": "a", "recovery_code": recovery_codes[1], "recaptcha_response": "success-1.0"},
+)
+assert resp.status_code == 200
+save_auth(resp.json())
+
+# regenerate recovery codes
+resp = c.post("/auth/users/me/mfa/recovery_codes")
+assert resp.status_code == 200
+new_recovery_codes = resp.json()
+assert len(new_recovery_codes) == 10
+assert recovery_codes[2] not in new_recovery_codes
+
+resp = c.get("/auth/users/me/mfa/recovery_codes")
+assert resp.status_code == 200
+assert resp.json() == {"remaining": 10}
+
+resp = c.delete("/auth/users/me/mfa")
+assert resp.status_code == 200
 assert get_self()["mfa_enabled"] is False
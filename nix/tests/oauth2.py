@@ -4,29 +4,37 @@ from utils import c, create_account, discard_auth, get_self, save_auth
 
 
 def authenticate(id, name):
-    resp = c.post(
-        "http://127.0.0.1:8002/oauth2/authorize?response_type=code&client_id=client-id&state=test123&redirect_uri=http://localhost/oauth2/callback",
-        data={"id": str(id), "name": name},
-        follow_redirects=False,
-    )
+    redirect_uri = "http://localhost/oauth2/callback"
+    resp = c.post("/auth/oauth/authorize", json={"provider_id": "test", "redirect_uri": redirect_uri})
+    assert resp.status_code == 200
+    authorize_url = resp.json()["authorize_url"]
+    expected_state = resp.json()["state"]
+
+    resp = c.post(authorize_url, data={"id": str(id), "name": name}, follow_redirects=False)
     assert resp.is_redirect
     url = urlparse(resp.headers["location"])
     query = parse_qs(url.query)
     code = query["code"][0]
     state = query["state"][0]
-    assert state == "test123"
-    return {"provider_id": "test", "code": code, "redirect_uri": "http://localhost/oauth2/callback"}
+    assert state == expected_state
+    return {"provider_id": "test", "code": code, "redirect_uri": redirect_uri, "state": state}
 
 
 resp = c.get("/auth/oauth/providers")
 assert resp.status_code == 200
-assert resp.json() == [
-    {
-        "id": "test",
-        "name": "Test OAuth2 Provider",
-        "authorize_url": "http://127.0.0.1:8002/oauth2/authorize?response_type=code&client_id=client-id",
-    }
-]
+assert resp.json() == [{"id": "test", "name": "Test OAuth2 Provider"}]
+
+resp = c.post("/auth/oauth/authorize", json={"provider_id": "foo", "redirect_uri": "http://localhost/oauth2/callback"})
+assert resp.status_code == 404
+assert resp.json() == {"detail": "Provider not found"}
+
+# state can only be used once
+login = authenticate(42, "foo")
+resp = c.post("/auth/sessions/oauth", json=login)
+assert resp.status_code == 200
+resp = c.post("/auth/sessions/oauth", json=login)
+assert resp.status_code == 401
+assert resp.json() == {"detail": "Invalid state"}
 
 # create link
 login = create_account("a", "a@a", "a")